@@ -6,10 +6,14 @@
 use crate::conversion::py_dict_to_config;
 use crate::stats::PyStats;
 use crate::system::PySystem;
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::PyBytes;
 use rvsim_core::Simulator;
+use rvsim_core::common::Trap;
 use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::core::cpu::checkpoint::Checkpoint;
+use rvsim_core::isa::privileged::cause::exception;
 use rvsim_core::sim::loader;
 use std::io::Write;
 
@@ -17,6 +21,48 @@ use std::io::Write;
 #[pyclass]
 pub struct PyCpu {
     pub inner: Simulator,
+    /// Device tree blob placed in RAM by the last `load_kernel`.
+    pub dtb: Option<Vec<u8>>,
+}
+
+/// Outcome of `PyCpu::step`: how far the simulation got and why it stopped.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PyStepResult {
+    /// Cycles executed by this step.
+    #[pyo3(get)]
+    pub cycles: u64,
+    /// Instructions retired by this step.
+    #[pyo3(get)]
+    pub instructions: u64,
+    /// At least one trap (exception or interrupt) was taken.
+    #[pyo3(get)]
+    pub trapped: bool,
+    /// An `ebreak` trapped; the step stops on it.
+    #[pyo3(get)]
+    pub breakpoint: bool,
+    /// Exit code if the program exited; the step stops on it.
+    #[pyo3(get)]
+    pub exit_code: Option<u64>,
+    /// PC the step stopped at.
+    #[pyo3(get)]
+    pub pc: u64,
+}
+
+#[pymethods]
+impl PyStepResult {
+    /// The program exited during this step.
+    #[getter]
+    fn exited(&self) -> bool {
+        self.exit_code.is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "StepResult(cycles={}, instructions={}, trapped={}, breakpoint={}, exit_code={:?}, pc={:#x})",
+            self.cycles, self.instructions, self.trapped, self.breakpoint, self.exit_code, self.pc
+        )
+    }
 }
 
 #[pymethods]
@@ -42,46 +88,78 @@ impl PyCpu {
 
         let config = py_dict_to_config(py, config_dict)?;
 
-        let sim = Simulator::new(sys, &config);
+        let mut sim = Simulator::new(sys, &config);
+        sim.cpu.symbols = std::mem::take(&mut system.symbols);
 
-        Ok(PyCpu { inner: sim })
+        Ok(PyCpu {
+            inner: sim,
+            dtb: None,
+        })
     }
 
     /// Loads a kernel into memory and prepares the CPU for execution.
     ///
     /// This method sets up the kernel image, applies the provided configuration,
     /// and optionally loads a Device Tree Blob (DTB). It also disables direct mode
-    /// on the internal CPU state.
+    /// on the internal CPU state. The same setup is replayed when the guest
+    /// reboots (SysCon reset).
     ///
     /// # Arguments
     /// * `py` - Python interpreter token.
     /// * `kernel_path` - The file path to the kernel image to be loaded.
     /// * `config_dict` - A Python dictionary containing the system configuration.
-    /// * `dtb_path` - An optional file path to the Device Tree Blob.
+    /// * `dtb_path` - An optional file path to the Device Tree Blob; one is generated from the config if omitted.
+    /// * `initrd_path` - An optional initrd (initramfs) image, loaded at
+    ///   `system.initrd_offset` and recorded in the DTB's `/chosen` node.
     ///
     /// # Errors
     /// Returns a `PyResult` error if the configuration dictionary cannot be parsed.
-    #[pyo3(signature = (kernel_path, config_dict, dtb_path=None))]
+    #[pyo3(signature = (kernel_path, config_dict, dtb_path=None, initrd_path=None))]
     pub fn load_kernel(
         &mut self,
         py: Python,
         kernel_path: String,
         config_dict: &Bound<'_, PyAny>,
         dtb_path: Option<String>,
+        initrd_path: Option<String>,
     ) -> PyResult<()> {
         let config = py_dict_to_config(py, config_dict)?;
 
-        loader::setup_kernel_load(
+        self.dtb = Some(loader::setup_kernel_load(
             &mut self.inner.cpu,
             &config,
             "",
-            dtb_path,
-            Some(kernel_path),
-        );
+            dtb_path.clone(),
+            Some(kernel_path.clone()),
+            initrd_path.clone(),
+        ));
         self.inner.cpu.direct_mode = false;
+        // A guest reboot replays the same setup.
+        self.inner.set_boot(move |cpu| {
+            loader::setup_kernel_load(
+                cpu,
+                &config,
+                "",
+                dtb_path.clone(),
+                Some(kernel_path.clone()),
+                initrd_path.clone(),
+            );
+        });
         Ok(())
     }
 
+    /// Returns the device tree blob the last `load_kernel` placed in RAM,
+    /// or `None` if no kernel was loaded.
+    pub fn dtb<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        self.dtb.as_deref().map(|blob| PyBytes::new(py, blob))
+    }
+
+    /// Returns every device on the system bus as `(name, start, end)` in
+    /// address order, where `end` is one past the last mapped address.
+    pub fn memory_map(&self) -> Vec<(String, u64, u64)> {
+        self.inner.cpu.bus.bus.memory_map()
+    }
+
     /// Executes a single CPU cycle.
     ///
     /// This method advances the internal state of the CPU by one tick.
@@ -93,6 +171,81 @@ impl PyCpu {
         self.inner.tick().map_err(PyRuntimeError::new_err)
     }
 
+    /// Advances the simulation by up to `n` cycles.
+    ///
+    /// Stops early when the program exits or an `ebreak` traps (or halts,
+    /// with `general.ebreak_halt`; `pc` is then the `ebreak` and the next
+    /// call resumes after it). Checks Python signals every 10 000 cycles to
+    /// remain interruptible.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PyRuntimeError` if the underlying CPU operation fails.
+    pub fn step(&mut self, py: Python, n: u64) -> PyResult<PyStepResult> {
+        let start_cycles = self.inner.cpu.stats.cycles;
+        let start_insts = self.inner.cpu.stats.instructions_retired;
+        let mut result = PyStepResult {
+            cycles: 0,
+            instructions: 0,
+            trapped: false,
+            breakpoint: false,
+            exit_code: None,
+            pc: 0,
+        };
+        // Resume a hart an `ebreak` halted in an earlier call.
+        self.inner.take_halt();
+
+        for cycle in 0..n {
+            if cycle > 0 && cycle.is_multiple_of(10000) {
+                py.check_signals()?;
+            }
+
+            let traps = self.inner.cpu.stats.traps_taken;
+            self.inner.tick().map_err(PyRuntimeError::new_err)?;
+            if let Some(code) = self.inner.take_exit() {
+                result.exit_code = Some(code);
+                break;
+            }
+            if self.inner.cpu.halted_at.is_some() {
+                result.breakpoint = true;
+                break;
+            }
+            if self.inner.cpu.stats.traps_taken != traps {
+                result.trapped = true;
+                let csrs = &self.inner.cpu.csrs;
+                let cause = if self.inner.cpu.privilege == PrivilegeMode::Machine {
+                    csrs.mcause
+                } else {
+                    csrs.scause
+                };
+                if cause == exception::BREAKPOINT {
+                    result.breakpoint = true;
+                    break;
+                }
+            }
+        }
+
+        let _ = std::io::stdout().flush();
+        self.inner.cpu.flush_logs();
+        result.cycles = self.inner.cpu.stats.cycles - start_cycles;
+        result.instructions = self.inner.cpu.stats.instructions_retired - start_insts;
+        result.pc = self.inner.cpu.halted_at.unwrap_or(self.inner.cpu.pc);
+        Ok(result)
+    }
+
+    /// Returns the PC of the `ebreak` the hart is halted at (with
+    /// `general.ebreak_halt`), or `None` if it is running. The next `run`,
+    /// `step`, or `step_instruction` resumes after the `ebreak`.
+    pub fn halted_at(&self) -> Option<u64> {
+        self.inner.cpu.halted_at
+    }
+
+    /// Clears the statistics, keeping cache, predictor, and architectural
+    /// state, so a measurement can start after a warmup phase.
+    pub fn reset_stats(&mut self) {
+        self.inner.cpu.reset_stats();
+    }
+
     /// Returns a snapshot of the current CPU statistics.
     ///
     /// This method clones the internal statistics and converts them into a [`PyStats`]
@@ -106,19 +259,77 @@ impl PyCpu {
         self.inner.cpu.pc
     }
 
-    /// Runs the simulation until the program exits (e.g., via SysCon power-off) or until the optional cycle limit is reached.
+    /// Runs the simulation until the program exits (e.g., via SysCon power-off), an `ebreak` halts
+    /// the hart (with `general.ebreak_halt`), or until the optional cycle limit is reached.
     ///
     /// Periodically checks for Python signals (e.g., Ctrl-C) and flushes stdout so UART
-    /// output is visible when invoked from Python.
+    /// output is visible when invoked from Python. The assembly dump and commit log are flushed on return.
     ///
     /// # Arguments
     /// * `limit` - Optional maximum number of cycles to run. If None, runs until program exits.
+    /// * `warmup` - Run this many instructions first, then clear the statistics (see
+    ///   `Cpu::reset_stats`) so only the rest of the run is measured. Warmup cycles do not
+    ///   count toward `limit`.
+    /// * `sample_interval` - Invoke `callback` whenever the cycle count reaches a multiple of
+    ///   this many cycles.
+    /// * `callback` - Called with a `PyStats` snapshot at each sample point. Returning `False`
+    ///   stops the run early; any other return value continues.
     ///
     /// # Returns
     ///
-    /// The exit code returned by the simulated program if it exited, or None if the cycle limit was reached.
-    #[pyo3(signature = (limit=None))]
-    pub fn run(&mut self, py: Python, limit: Option<u64>) -> PyResult<Option<u64>> {
+    /// The exit code returned by the simulated program if it exited, or None if the cycle limit was
+    /// reached, an `ebreak` halted the hart, or the callback stopped the run. A later call resumes
+    /// after the `ebreak`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PyValueError` if only one of `sample_interval` and `callback` is given or the
+    /// interval is zero, and propagates any exception raised by the callback.
+    #[pyo3(signature = (limit=None, sample_interval=None, callback=None, warmup=None))]
+    pub fn run(
+        &mut self,
+        py: Python,
+        limit: Option<u64>,
+        sample_interval: Option<u64>,
+        callback: Option<&Bound<'_, PyAny>>,
+        warmup: Option<u64>,
+    ) -> PyResult<Option<u64>> {
+        let sampler = match (sample_interval, callback) {
+            (None, None) => None,
+            (Some(interval), Some(callback)) if interval > 0 => Some((interval, callback)),
+            (Some(0), Some(_)) => {
+                return Err(PyValueError::new_err("sample_interval must be positive"));
+            }
+            _ => {
+                return Err(PyValueError::new_err(
+                    "sample_interval and callback must be given together",
+                ));
+            }
+        };
+
+        // Resume a hart an `ebreak` halted in an earlier call.
+        self.inner.take_halt();
+
+        if let Some(insts) = warmup {
+            let target = self.inner.cpu.stats.instructions_retired + insts;
+            while self.inner.cpu.stats.instructions_retired < target {
+                if self.inner.cpu.stats.cycles.is_multiple_of(10000) {
+                    py.check_signals()?;
+                    let _ = std::io::stdout().flush();
+                }
+                self.inner.tick().map_err(PyRuntimeError::new_err)?;
+                if let Some(code) = self.inner.take_exit() {
+                    let _ = std::io::stdout().flush();
+                    return Ok(Some(code));
+                }
+                if self.inner.cpu.halted_at.is_some() {
+                    let _ = std::io::stdout().flush();
+                    return Ok(None);
+                }
+            }
+            self.inner.cpu.reset_stats();
+        }
+
         let start_cycles = self.inner.cpu.stats.cycles;
         loop {
             // Check if we've hit the cycle limit (if specified)
@@ -126,6 +337,7 @@ impl PyCpu {
                 && self.inner.cpu.stats.cycles - start_cycles >= max_cycles
             {
                 let _ = std::io::stdout().flush();
+                self.inner.cpu.flush_logs();
                 return Ok(None);
             }
 
@@ -140,9 +352,25 @@ impl PyCpu {
                         let _ = std::io::stdout().flush();
                         return Ok(Some(code));
                     }
+                    if self.inner.cpu.halted_at.is_some() {
+                        let _ = std::io::stdout().flush();
+                        return Ok(None);
+                    }
                 }
                 Err(e) => return Err(PyRuntimeError::new_err(e)),
             }
+
+            if let Some((interval, callback)) = sampler
+                && self.inner.cpu.stats.cycles.is_multiple_of(interval)
+            {
+                let snapshot = PyStats::from(self.inner.cpu.stats.clone());
+                let keep_going = callback.call1((snapshot,))?;
+                if keep_going.extract::<bool>().ok() == Some(false) {
+                    let _ = std::io::stdout().flush();
+                    self.inner.cpu.flush_logs();
+                    return Ok(None);
+                }
+            }
         }
     }
 
@@ -182,13 +410,65 @@ impl PyCpu {
     }
 
     /// Read a 32-bit value from a physical memory address.
-    pub fn read_memory_u32(&mut self, paddr: u64) -> u32 {
-        self.inner.cpu.bus.bus.read_u32(paddr)
+    ///
+    /// Raises `ValueError` if no device is mapped at `paddr`.
+    pub fn read_memory_u32(&mut self, paddr: u64) -> PyResult<u32> {
+        self.inner
+            .cpu
+            .bus
+            .bus
+            .read_u32(paddr)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
     /// Read a 64-bit value from a physical memory address.
-    pub fn read_memory_u64(&mut self, paddr: u64) -> u64 {
-        self.inner.cpu.bus.bus.read_u64(paddr)
+    ///
+    /// Raises `ValueError` if no device is mapped at `paddr`.
+    pub fn read_memory_u64(&mut self, paddr: u64) -> PyResult<u64> {
+        self.inner
+            .cpu
+            .bus
+            .bus
+            .read_u64(paddr)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write a 32-bit value to a physical memory address.
+    ///
+    /// Raises `ValueError` if no device is mapped at `paddr`.
+    pub fn write_memory_u32(&mut self, paddr: u64, val: u32) -> PyResult<()> {
+        self.inner
+            .cpu
+            .bus
+            .bus
+            .write_u32(paddr, val)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write a 64-bit value to a physical memory address.
+    ///
+    /// Raises `ValueError` if no device is mapped at `paddr`.
+    pub fn write_memory_u64(&mut self, paddr: u64, val: u64) -> PyResult<()> {
+        self.inner
+            .cpu
+            .bus
+            .bus
+            .write_u64(paddr, val)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Copy `data` into physical memory starting at `paddr`.
+    ///
+    /// Raises `ValueError` if the range is not entirely inside one device, in
+    /// which case nothing is written.
+    pub fn load_bytes(&mut self, paddr: u64, data: &[u8]) -> PyResult<()> {
+        let bus = &mut self.inner.cpu.bus.bus;
+        if !data.is_empty() {
+            bus.check_access(paddr, data.len() as u64)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        }
+        bus.load_binary_at(data, paddr);
+        Ok(())
     }
 
     /// Read a CSR by name. Returns None if unknown.
@@ -226,6 +506,25 @@ impl PyCpu {
         }
     }
 
+    /// Return `(accesses, misses)` per set of a cache level ("l1_i", "l1_d",
+    /// "l2", or "l3"; "l1i" and "l1d" are accepted too). Empty unless the
+    /// level was configured with `set_stats`.
+    pub fn cache_set_stats(&self, level: &str) -> PyResult<Vec<(u64, u64)>> {
+        let cpu = &self.inner.cpu;
+        let cache = match level {
+            "l1_i" | "l1i" => &cpu.l1_i_cache,
+            "l1_d" | "l1d" => &cpu.l1_d_cache,
+            "l2" => &cpu.l2_cache,
+            "l3" => &cpu.l3_cache,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown cache level {level:?}; expected l1_i, l1_d, l2, or l3"
+                )));
+            }
+        };
+        Ok(cache.set_stats())
+    }
+
     /// Enable or disable instruction tracing.
     pub fn set_trace(&mut self, enabled: bool) {
         self.inner.cpu.trace = enabled;
@@ -236,10 +535,175 @@ impl PyCpu {
         self.inner.cpu.pc_trace.clone()
     }
 
+    /// Save the architectural state (registers, CSRs, RAM, devices) to a file.
+    ///
+    /// In-flight instructions are discarded and refetched, so the simulation
+    /// can continue normally afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PyIOError` if the file cannot be written.
+    pub fn save_checkpoint(&mut self, path: &str) -> PyResult<()> {
+        self.inner
+            .save_checkpoint()
+            .save(path)
+            .map_err(|e| PyIOError::new_err(format!("{path}: {e}")))
+    }
+
+    /// Replace the architectural state with a checkpoint saved by `save_checkpoint`.
+    ///
+    /// The checkpoint must come from a system with the same memory map, RAM
+    /// size, and VLEN.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PyIOError` if the file cannot be read, or a `PyRuntimeError`
+    /// if the checkpoint does not fit this system.
+    pub fn load_checkpoint(&mut self, path: &str) -> PyResult<()> {
+        let ckpt =
+            Checkpoint::load(path).map_err(|e| PyIOError::new_err(format!("{path}: {e}")))?;
+        self.inner
+            .restore_checkpoint(&ckpt)
+            .map_err(PyRuntimeError::new_err)
+    }
+
+    /// Resolve `pc` to the nearest preceding ELF function symbol.
+    ///
+    /// Returns `(name, offset)`, or `None` if no symbol covers `pc` (or no
+    /// ELF with a symbol table was loaded).
+    pub fn resolve_symbol(&self, pc: u64) -> Option<(String, u64)> {
+        self.inner.cpu.resolve_symbol(pc)
+    }
+
+    /// Reconstruct the call stack as return addresses, innermost first.
+    ///
+    /// Walks saved `ra`/`fp` frames when the guest keeps frame pointers,
+    /// otherwise reports the return address stack.
+    pub fn backtrace(&self) -> Vec<u64> {
+        self.inner.cpu.backtrace()
+    }
+
+    /// Start recording bus accesses in a ring buffer of `capacity` entries.
+    ///
+    /// Replaces any existing log. With `start` and `end`, only accesses to
+    /// physical addresses in `[start, end)` are recorded. RAM accesses served
+    /// directly (without going through the bus) are never recorded.
+    #[pyo3(signature = (capacity, start=None, end=None))]
+    pub fn enable_mem_trace(&mut self, capacity: usize, start: Option<u64>, end: Option<u64>) {
+        let bus = &mut self.inner.cpu.bus.bus;
+        bus.enable_access_log(capacity);
+        bus.set_access_log_filter(start.zip(end));
+    }
+
+    /// Schedule an interrupt to become pending once `inst_count` instructions
+    /// have retired.
+    ///
+    /// `interrupt` names the interrupt, e.g. `"MachineTimerInterrupt"` or
+    /// `"SupervisorExternalInterrupt"`. Delivery still follows `mie`,
+    /// `mstatus`, and delegation; the interrupt stays pending until taken.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PyValueError` if `interrupt` is not an interrupt name.
+    pub fn inject_interrupt_at(&mut self, inst_count: u64, interrupt: &str) -> PyResult<()> {
+        let trap = match interrupt {
+            "UserSoftwareInterrupt" => Trap::UserSoftwareInterrupt,
+            "SupervisorSoftwareInterrupt" => Trap::SupervisorSoftwareInterrupt,
+            "MachineSoftwareInterrupt" => Trap::MachineSoftwareInterrupt,
+            "SupervisorTimerInterrupt" => Trap::SupervisorTimerInterrupt,
+            "MachineTimerInterrupt" => Trap::MachineTimerInterrupt,
+            "UserExternalInterrupt" => Trap::UserExternalInterrupt,
+            "SupervisorExternalInterrupt" => Trap::SupervisorExternalInterrupt,
+            "MachineExternalInterrupt" => Trap::MachineExternalInterrupt,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown interrupt '{other}'"
+                )));
+            }
+        };
+        self.inner
+            .cpu
+            .inject_interrupt_at(inst_count, trap)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Stop recording bus accesses and discard the log.
+    pub fn disable_mem_trace(&mut self) {
+        self.inner.cpu.bus.bus.disable_access_log();
+    }
+
+    /// Drain the bus access log, oldest first.
+    ///
+    /// Returns `(pc, addr, size, is_write, value)` tuples; empty if the log
+    /// is disabled (see `enable_mem_trace` or `general.mem_trace_size`).
+    pub fn get_mem_trace(&mut self) -> Vec<(u64, u64, u8, bool, u64)> {
+        self.inner
+            .cpu
+            .bus
+            .bus
+            .drain_access_log()
+            .into_iter()
+            .map(|a| (a.pc, a.addr, a.size, a.is_write, a.value))
+            .collect()
+    }
+
+    /// Start recording taken traps in a ring buffer of `capacity` entries.
+    ///
+    /// Replaces any existing log.
+    pub fn enable_trap_log(&mut self, capacity: usize) {
+        self.inner.cpu.enable_trap_log(capacity);
+    }
+
+    /// Stop recording traps and discard the log.
+    pub fn disable_trap_log(&mut self) {
+        self.inner.cpu.disable_trap_log();
+    }
+
+    /// Drain the trap log, oldest first.
+    ///
+    /// Returns `(pc, cause, tval, privilege)` tuples, where `cause` has bit
+    /// 63 set for interrupts and `privilege` is the mode the handler runs in
+    /// (`"Machine"` or `"Supervisor"`); empty if the log is disabled (see
+    /// `enable_trap_log` or `general.trap_log_size`).
+    pub fn get_trap_log(&mut self) -> Vec<(u64, u64, u64, &'static str)> {
+        self.inner
+            .cpu
+            .drain_trap_log()
+            .into_iter()
+            .map(|t| (t.pc, t.cause, t.tval, t.privilege.name()))
+            .collect()
+    }
+
+    /// Write the framebuffer's current contents to `path` as a PPM image.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PyRuntimeError` if the system has no framebuffer, or a
+    /// `PyIOError` if the file cannot be written.
+    pub fn dump_framebuffer(&mut self, path: &str) -> PyResult<()> {
+        let fb =
+            self.inner.cpu.bus.bus.framebuffer().ok_or_else(|| {
+                PyRuntimeError::new_err("no framebuffer (enable system.framebuffer)")
+            })?;
+        fb.dump_ppm(path)
+            .map_err(|e| PyIOError::new_err(format!("{path}: {e}")))
+    }
+
+    /// Enable or disable functional (fast-forward) execution.
+    ///
+    /// In functional mode each tick executes one instruction with no pipeline,
+    /// cache, or branch predictor modeling; only `instructions_retired` and
+    /// `cycles` advance. Switch back with `set_functional(False)` to simulate
+    /// the region of interest in detail.
+    pub fn set_functional(&mut self, enabled: bool) {
+        self.inner.cpu.set_functional(enabled);
+    }
+
     /// Advance the simulation until one new instruction commits, then return it.
     ///
     /// Returns `(pc, raw_inst, disasm_str)` for the committed instruction, or
-    /// `None` if the program exited before an instruction could commit.
+    /// `None` if the program exited (or an `ebreak` halted the hart) before
+    /// an instruction could commit.
     ///
     /// Useful for instruction-level single-stepping in debug scripts without
     /// having to guess how many cycles a single instruction takes.
@@ -251,6 +715,7 @@ impl PyCpu {
         py: Python,
         max_cycles: u64,
     ) -> PyResult<Option<(u64, u32, String)>> {
+        self.inner.take_halt();
         let before_len = self.inner.cpu.pc_trace.len();
         let before_last = self.inner.cpu.pc_trace.last().copied();
         let mut cycles_run: u64 = 0;
@@ -272,6 +737,9 @@ impl PyCpu {
                         let _ = code;
                         return Ok(None);
                     }
+                    if self.inner.cpu.halted_at.is_some() {
+                        return Ok(None);
+                    }
                 }
                 Err(e) => return Err(pyo3::exceptions::PyRuntimeError::new_err(e)),
             }
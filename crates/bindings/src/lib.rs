@@ -4,7 +4,7 @@
 //! 1. **System and CPU:** `PySystem` and `PyCpu` for configuration and cycle stepping.
 //! 2. **Statistics:** `PyStats` for performance metrics and selective section printing.
 //! 3. **Memory and devices:** `PyMemory`, `PyUart`, `PyPlic`, `PyVirtioBlock` for introspection.
-//! 4. **Utilities:** Version string and conversion helpers for Python↔Rust types.
+//! 4. **Utilities:** Version string, disassembly, DTB generation, and conversion helpers for Python↔Rust types.
 
 use pyo3::prelude::*;
 
@@ -25,8 +25,8 @@ pub mod utils;
 
 /// Registers all emulator classes and functions onto the given Python module.
 ///
-/// Called from the `#[pymodule]` entry point to expose `PyCpu`, `PySystem`, `PyStats`,
-/// `PyMemory`, device types, and `version`.
+/// Called from the `#[pymodule]` entry point to expose `PyCpu`, `PyStepResult`, `PySystem`,
+/// `PyStats`, `PyMemory`, device types, `version`, `disassemble`, and `generate_dtb`.
 ///
 /// # Arguments
 ///
@@ -37,6 +37,7 @@ pub mod utils;
 /// `Ok(())` on success, or a `PyErr` if registration fails.
 pub fn register_emulator_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<cpu::PyCpu>()?;
+    m.add_class::<cpu::PyStepResult>()?;
     m.add_class::<system::PySystem>()?;
     m.add_class::<stats::PyStats>()?;
     m.add_class::<memory::PyMemory>()?;
@@ -47,6 +48,7 @@ pub fn register_emulator_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     m.add_function(wrap_pyfunction!(utils::version, m)?)?;
     m.add_function(wrap_pyfunction!(utils::disassemble, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::generate_dtb, m)?)?;
 
     Ok(())
 }
@@ -2,7 +2,8 @@
 //!
 //! Exposes simulation statistics to Python: getters for cycles, cache hits/misses,
 //! branch accuracy, and instruction mix; `print` / `print_sections` for human-readable
-//! output; `to_dict` for JSON-serializable export (multisim, scripting).
+//! output; `to_dict` for JSON-serializable export (multisim, scripting); `to_json` and
+//! `to_csv` for writing results files.
 
 use pyo3::prelude::*;
 use rvsim_core::stats::SimStats;
@@ -31,7 +32,8 @@ impl PyStats {
         self.inner.print();
     }
 
-    /// Print only the given sections. Options: "summary", "core", "instruction_mix", "branch", "memory".
+    /// Print only the given sections. Options: "summary", "core", "cpi_stack", "instruction_mix",
+    /// "branch", "memory".
     /// Pass an empty list for full dump. Example: print_sections(["summary", "memory"]) for cycles + caches.
     fn print_sections(&self, sections: Vec<String>) {
         self.inner.print_sections(&sections);
@@ -54,6 +56,10 @@ impl PyStats {
         self.inner.icache_misses
     }
     #[getter]
+    fn icache_invalidations(&self) -> u64 {
+        self.inner.icache_invalidations
+    }
+    #[getter]
     fn dcache_hits(&self) -> u64 {
         self.inner.dcache_hits
     }
@@ -62,6 +68,10 @@ impl PyStats {
         self.inner.dcache_misses
     }
     #[getter]
+    fn dcache_bank_conflicts(&self) -> u64 {
+        self.inner.dcache_bank_conflicts
+    }
+    #[getter]
     fn l2_hits(&self) -> u64 {
         self.inner.l2_hits
     }
@@ -90,6 +100,30 @@ impl PyStats {
         self.inner.stalls_data
     }
     #[getter]
+    fn stalls_struct(&self) -> u64 {
+        self.inner.stalls_struct
+    }
+    #[getter]
+    fn subreg_dependency_stalls(&self) -> u64 {
+        self.inner.subreg_dependency_stalls
+    }
+    #[getter]
+    fn stalls_icache(&self) -> u64 {
+        self.inner.stalls_icache
+    }
+    #[getter]
+    fn stalls_dcache(&self) -> u64 {
+        self.inner.stalls_dcache
+    }
+    #[getter]
+    fn stalls_itlb(&self) -> u64 {
+        self.inner.stalls_itlb
+    }
+    #[getter]
+    fn stalls_dtlb(&self) -> u64 {
+        self.inner.stalls_dtlb
+    }
+    #[getter]
     fn branch_predictions(&self) -> u64 {
         self.inner.branch_predictions
     }
@@ -97,6 +131,80 @@ impl PyStats {
     fn branch_mispredictions(&self) -> u64 {
         self.inner.branch_mispredictions
     }
+    #[getter]
+    fn cond_branch_predictions(&self) -> u64 {
+        self.inner.cond_branch_predictions
+    }
+    #[getter]
+    fn cond_branch_mispredictions(&self) -> u64 {
+        self.inner.cond_branch_mispredictions
+    }
+    #[getter]
+    fn direct_jump_predictions(&self) -> u64 {
+        self.inner.direct_jump_predictions
+    }
+    #[getter]
+    fn direct_jump_mispredictions(&self) -> u64 {
+        self.inner.direct_jump_mispredictions
+    }
+    #[getter]
+    fn indirect_jump_predictions(&self) -> u64 {
+        self.inner.indirect_jump_predictions
+    }
+    #[getter]
+    fn indirect_jump_mispredictions(&self) -> u64 {
+        self.inner.indirect_jump_mispredictions
+    }
+    #[getter]
+    fn return_predictions(&self) -> u64 {
+        self.inner.return_predictions
+    }
+    #[getter]
+    fn return_mispredictions(&self) -> u64 {
+        self.inner.return_mispredictions
+    }
+    #[getter]
+    fn bp_trained(&self) -> u64 {
+        self.inner.bp_trained
+    }
+    #[getter]
+    fn bp_train_skipped(&self) -> u64 {
+        self.inner.bp_train_skipped
+    }
+    #[getter]
+    fn bp_threshold(&self) -> u64 {
+        self.inner.bp_threshold
+    }
+    #[getter]
+    fn max_mem_latency(&self) -> u64 {
+        self.inner.max_mem_latency
+    }
+    #[getter]
+    fn max_mem_latency_pc(&self) -> u64 {
+        self.inner.max_mem_latency_pc
+    }
+    #[getter]
+    fn max_mem_latency_addr(&self) -> u64 {
+        self.inner.max_mem_latency_addr
+    }
+    #[getter]
+    fn warmup_cycles(&self) -> u64 {
+        self.inner.warmup_cycles
+    }
+    #[getter]
+    fn warmup_instructions(&self) -> u64 {
+        self.inner.warmup_instructions
+    }
+
+    /// CPI stack: CPI split into base, branch, icache, dcache, tlb, and memory
+    /// components that sum to the measured CPI.
+    fn cpi_stack(&self, py: Python<'_>) -> pyo3::PyResult<pyo3::Py<pyo3::types::PyDict>> {
+        let d = pyo3::types::PyDict::new(py);
+        for (name, cpi) in self.inner.cpi_stack().components() {
+            d.set_item(name, cpi)?;
+        }
+        Ok(d.into())
+    }
 
     /// Export all stats as a Python dict (JSON-serializable) for reproducible experiments.
     fn to_dict(&self, py: Python<'_>) -> pyo3::PyResult<pyo3::Py<pyo3::types::PyDict>> {
@@ -106,8 +214,10 @@ impl PyStats {
         d.set_item("instructions_retired", s.instructions_retired)?;
         d.set_item("icache_hits", s.icache_hits)?;
         d.set_item("icache_misses", s.icache_misses)?;
+        d.set_item("icache_invalidations", s.icache_invalidations)?;
         d.set_item("dcache_hits", s.dcache_hits)?;
         d.set_item("dcache_misses", s.dcache_misses)?;
+        d.set_item("dcache_bank_conflicts", s.dcache_bank_conflicts)?;
         d.set_item("l2_hits", s.l2_hits)?;
         d.set_item("l2_misses", s.l2_misses)?;
         d.set_item("l3_hits", s.l3_hits)?;
@@ -115,10 +225,40 @@ impl PyStats {
         d.set_item("stalls_mem", s.stalls_mem)?;
         d.set_item("stalls_control", s.stalls_control)?;
         d.set_item("stalls_data", s.stalls_data)?;
+        d.set_item("stalls_struct", s.stalls_struct)?;
+        d.set_item("subreg_dependency_stalls", s.subreg_dependency_stalls)?;
+        d.set_item("stalls_icache", s.stalls_icache)?;
+        d.set_item("stalls_dcache", s.stalls_dcache)?;
+        d.set_item("stalls_itlb", s.stalls_itlb)?;
+        d.set_item("stalls_dtlb", s.stalls_dtlb)?;
+        for (name, cpi) in s.cpi_stack().components() {
+            d.set_item(format!("cpi_{name}"), cpi)?;
+        }
+        d.set_item("max_mem_latency", s.max_mem_latency)?;
+        d.set_item("max_mem_latency_pc", s.max_mem_latency_pc)?;
+        d.set_item("max_mem_latency_addr", s.max_mem_latency_addr)?;
+        d.set_item("warmup_cycles", s.warmup_cycles)?;
+        d.set_item("warmup_instructions", s.warmup_instructions)?;
 
         d.set_item("cycles_user", s.cycles_user)?;
         d.set_item("cycles_kernel", s.cycles_kernel)?;
         d.set_item("cycles_machine", s.cycles_machine)?;
+        d.set_item("cycles_wfi", s.cycles_wfi)?;
+        d.set_item("insts_user", s.insts_user)?;
+        d.set_item("insts_kernel", s.insts_kernel)?;
+        d.set_item("insts_machine", s.insts_machine)?;
+        for (key, insts, cycles) in [
+            ("ipc_user", s.insts_user, s.cycles_user),
+            ("ipc_kernel", s.insts_kernel, s.cycles_kernel),
+            ("ipc_machine", s.insts_machine, s.cycles_machine),
+        ] {
+            let ipc = if cycles > 0 {
+                insts as f64 / cycles as f64
+            } else {
+                0.0
+            };
+            d.set_item(key, ipc)?;
+        }
         d.set_item("traps_taken", s.traps_taken)?;
 
         d.set_item("branch_predictions", s.branch_predictions)?;
@@ -130,6 +270,20 @@ impl PyStats {
             0.0
         };
         d.set_item("branch_accuracy_pct", bp_acc)?;
+        d.set_item("cond_branch_predictions", s.cond_branch_predictions)?;
+        d.set_item("cond_branch_mispredictions", s.cond_branch_mispredictions)?;
+        d.set_item("direct_jump_predictions", s.direct_jump_predictions)?;
+        d.set_item("direct_jump_mispredictions", s.direct_jump_mispredictions)?;
+        d.set_item("indirect_jump_predictions", s.indirect_jump_predictions)?;
+        d.set_item(
+            "indirect_jump_mispredictions",
+            s.indirect_jump_mispredictions,
+        )?;
+        d.set_item("return_predictions", s.return_predictions)?;
+        d.set_item("return_mispredictions", s.return_mispredictions)?;
+        d.set_item("bp_trained", s.bp_trained)?;
+        d.set_item("bp_train_skipped", s.bp_train_skipped)?;
+        d.set_item("bp_threshold", s.bp_threshold)?;
         let ipc = if s.cycles > 0 {
             s.instructions_retired as f64 / s.cycles as f64
         } else {
@@ -140,6 +294,8 @@ impl PyStats {
         d.set_item("inst_load", s.inst_load)?;
         d.set_item("inst_store", s.inst_store)?;
         d.set_item("inst_branch", s.inst_branch)?;
+        d.set_item("inst_jump", s.inst_jump)?;
+        d.set_item("inst_atomic", s.inst_atomic)?;
         d.set_item("inst_alu", s.inst_alu)?;
         d.set_item("inst_system", s.inst_system)?;
         d.set_item("inst_fp_load", s.inst_fp_load)?;
@@ -150,6 +306,16 @@ impl PyStats {
 
         Ok(d.into())
     }
+
+    /// Export all stats as a flat JSON object string.
+    fn to_json(&self) -> String {
+        self.inner.to_json()
+    }
+
+    /// Export all stats as CSV: a header row of names and one row of values.
+    fn to_csv(&self) -> String {
+        self.inner.to_csv()
+    }
 }
 
 impl From<SimStats> for PyStats {
@@ -8,12 +8,15 @@ use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use rvsim_core::config::Config;
 use rvsim_core::sim::loader;
+use rvsim_core::sim::symbols::SymbolTable;
 use rvsim_core::soc::System;
 
 /// Python-exposed system: wraps the core `System` (bus, memory controller, devices). Consumed by `PyCpu::new`.
 #[pyclass]
 pub struct PySystem {
     pub inner: Option<System>,
+    /// Function symbols from the last loaded ELF, handed to the CPU.
+    pub symbols: SymbolTable,
 }
 
 #[pymethods]
@@ -36,13 +39,15 @@ impl PySystem {
 
         Ok(PySystem {
             inner: Some(system),
+            symbols: SymbolTable::default(),
         })
     }
 
     /// Loads an ELF into system memory.
     ///
     /// Parses the ELF, loads all segments, and registers an HTIF device if a
-    /// `tohost` symbol is found. Returns `(entry_point, tohost_addr)` where
+    /// `tohost` symbol is found. The function symbols are kept for the CPU
+    /// to symbolize PCs. Returns `(entry_point, tohost_addr)` where
     /// `tohost_addr` is `None` if not present in the ELF.
     ///
     /// Returns an error if the data is not a valid ELF.
@@ -56,6 +61,7 @@ impl PySystem {
             if let Some(tohost) = result.tohost_addr {
                 sys.add_htif(tohost);
             }
+            self.symbols = result.symbols;
             Ok((result.entry, result.tohost_addr))
         } else {
             Err(PyRuntimeError::new_err(
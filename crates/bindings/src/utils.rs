@@ -2,7 +2,9 @@
 //!
 //! Provides version and other helpers for the `rvsim` module.
 
+use crate::conversion::py_dict_to_config;
 use pyo3::prelude::*;
+use pyo3::types::PyBytes;
 
 /// Returns the emulator version string (e.g., for scripting or diagnostics).
 ///
@@ -32,3 +34,19 @@ pub fn version() -> String {
 pub fn disassemble(inst: u32) -> String {
     rvsim_core::isa::disasm::disassemble(inst)
 }
+
+/// Generates the device tree blob describing the machine in `config_dict`.
+///
+/// This is the DTB a kernel boot uses when no `.dtb` file is given.
+///
+/// # Errors
+///
+/// Returns an error if the configuration dictionary cannot be parsed.
+#[pyfunction]
+pub fn generate_dtb<'py>(
+    py: Python<'py>,
+    config_dict: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let config = py_dict_to_config(py, config_dict)?;
+    Ok(PyBytes::new(py, &rvsim_core::sim::dtb::generate(&config)))
+}
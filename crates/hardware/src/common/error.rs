@@ -3,11 +3,13 @@
 //! This module defines the error handling and trap mechanisms for the simulator. It provides:
 //! 1. **Trap Representation:** Encompassing all synchronous exceptions and asynchronous interrupts.
 //! 2. **Translation Results:** Reporting the outcome of virtual-to-physical address translation.
+//!    Physical accesses that no device claims are reported as a `BusError`.
 //! 3. **Error Handling:** Integrating with standard Rust error traits for system-level reporting.
 
 use std::fmt;
 
 use super::addr::PhysAddr;
+use super::data::AccessType;
 
 /// Pipeline stage where an exception was first detected.
 ///
@@ -212,6 +214,15 @@ impl fmt::Display for Trap {
 }
 
 impl Trap {
+    /// Returns the access-fault trap for an `access` to `addr`.
+    pub fn access_fault(access: AccessType, addr: u64) -> Self {
+        match access {
+            AccessType::Fetch => Trap::InstructionAccessFault(addr),
+            AccessType::Read => Trap::LoadAccessFault(addr),
+            AccessType::Write => Trap::StoreAccessFault(addr),
+        }
+    }
+
     /// Returns the exception priority per RISC-V Privileged Spec Table 3.7.
     ///
     /// Lower values indicate higher priority. Synchronous exceptions have
@@ -263,6 +274,31 @@ impl Trap {
 
 impl std::error::Error for Trap {}
 
+/// Failure of a physical access on the system bus.
+///
+/// The memory stage turns this into the access-fault trap matching the
+/// access type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BusError {
+    /// No device claims the physical address.
+    Unmapped(u64),
+    /// The access starts inside a device but runs past the end of it.
+    OutOfBounds(u64),
+}
+
+impl fmt::Display for BusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusError::Unmapped(addr) => write!(f, "no device mapped at {:#x}", addr),
+            BusError::OutOfBounds(addr) => {
+                write!(f, "access at {:#x} runs past the end of its device", addr)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BusError {}
+
 /// Result of a virtual-to-physical address translation operation.
 ///
 /// This structure encapsulates the outcome of an MMU walk, including performance
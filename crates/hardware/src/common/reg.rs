@@ -1,7 +1,8 @@
 //! Unified Register File.
 //!
 //! This module provides the `RegisterFile` struct, which acts as a unified interface for
-//! accessing both General Purpose Registers (GPRs) and Floating-Point Registers (FPRs).
+//! accessing General Purpose Registers (GPRs), Floating-Point Registers (FPRs) and
+//! Vector Registers (VPRs).
 //! It provides:
 //! 1. **Unified Storage:** Combined storage for all RISC-V architectural registers.
 //! 2. **Abstraction:** A single set of methods for reading and writing register values.
@@ -9,14 +10,16 @@
 
 use crate::core::arch::fpr::Fpr;
 use crate::core::arch::gpr::Gpr;
+use crate::core::arch::vpr::{DEFAULT_VLEN, Vpr};
 
-/// Unified register file containing both general-purpose and floating-point registers.
+/// Unified register file containing general-purpose, floating-point and vector registers.
 ///
 /// This structure provides a single interface for accessing all processor registers,
-/// abstracting the underlying GPR and FPR implementations.
+/// abstracting the underlying GPR, FPR and VPR implementations.
 pub struct RegisterFile {
     gpr: Gpr,
     fpr: Fpr,
+    vpr: Vpr,
 }
 
 impl Default for RegisterFile {
@@ -30,11 +33,22 @@ impl RegisterFile {
     ///
     /// # Returns
     ///
-    /// A new `RegisterFile` instance with initialized GPR and FPR components.
+    /// A new `RegisterFile` instance with initialized GPR and FPR components and
+    /// vector registers of `DEFAULT_VLEN` bits.
     pub fn new() -> Self {
+        Self::with_vlen(DEFAULT_VLEN)
+    }
+
+    /// Creates a new register file whose vector registers are `vlen` bits wide.
+    ///
+    /// # Arguments
+    ///
+    /// * `vlen` - Vector register width in bits (see `Vpr::new`).
+    pub fn with_vlen(vlen: usize) -> Self {
         Self {
             gpr: Gpr::new(),
             fpr: Fpr::new(),
+            vpr: Vpr::new(vlen),
         }
     }
 
@@ -84,6 +98,36 @@ impl RegisterFile {
         self.fpr.write(idx, val);
     }
 
+    /// Reads a 32-bit element of a vector register.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - Vector register index (0-31).
+    /// * `idx` - Element index within the register.
+    ///
+    /// # Returns
+    ///
+    /// The element value.
+    pub fn read_v32(&self, reg: usize, idx: usize) -> u32 {
+        self.vpr.read_u32(reg, idx)
+    }
+
+    /// Writes a 32-bit element of a vector register.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - Vector register index (0-31).
+    /// * `idx` - Element index within the register.
+    /// * `val` - The element value to write.
+    pub fn write_v32(&mut self, reg: usize, idx: usize, val: u32) {
+        self.vpr.write_u32(reg, idx, val);
+    }
+
+    /// Returns the vector register width in bytes (`VLEN / 8`).
+    pub fn vlenb(&self) -> usize {
+        self.vpr.vlenb()
+    }
+
     /// Dumps the contents of all general-purpose registers to stderr.
     ///
     /// Useful for debugging and tracing register state during simulation.
@@ -8,6 +8,8 @@
 //!
 //! Configuration is supplied via JSON from the Python API (`SimConfig`) or use `Config::default()` for the CLI.
 
+use crate::core::arch::mode::PrivilegeMode;
+use crate::core::arch::trap::STANDARD_INTERRUPT_PRIORITY;
 use crate::core::pipeline::engine::BackendType;
 use serde::Deserialize;
 
@@ -15,7 +17,7 @@ use serde::Deserialize;
 ///
 /// These values define the baseline hardware configuration when not
 /// explicitly overridden in TOML configuration files.
-mod defaults {
+pub(crate) mod defaults {
     /// Base address of main system RAM (2 GiB).
     ///
     /// This is the physical address where the main memory region begins.
@@ -34,6 +36,11 @@ mod defaults {
     /// while leaving space for bootloaders and initial stack.
     pub const KERNEL_OFFSET: u64 = 0x0020_0000;
 
+    /// Offset from RAM base where an initrd is loaded (64 MiB).
+    ///
+    /// Sits above the kernel image and the DTB so neither overlaps it.
+    pub const INITRD_OFFSET: u64 = 0x0400_0000;
+
     /// Base address of UART 16550-compatible serial port MMIO region.
     pub const UART_BASE: u64 = 0x1000_0000;
 
@@ -43,9 +50,24 @@ mod defaults {
     /// Base address of CLINT (Core Local Interruptor) timer MMIO region.
     pub const CLINT_BASE: u64 = 0x0200_0000;
 
+    /// Base address of the PLIC (Platform-Level Interrupt Controller) MMIO region.
+    pub const PLIC_BASE: u64 = 0x0c00_0000;
+
     /// Base address of system controller (power/reset) MMIO region.
     pub const SYSCON_BASE: u64 = 0x0010_0000;
 
+    /// Base address of the Goldfish RTC (real-time clock) MMIO region.
+    pub const RTC_BASE: u64 = 0x0010_1000;
+
+    /// Base address of the linear framebuffer MMIO region.
+    pub const FRAMEBUFFER_BASE: u64 = 0x4000_0000;
+
+    /// Framebuffer width in pixels.
+    pub const FRAMEBUFFER_WIDTH: u32 = 640;
+
+    /// Framebuffer height in pixels.
+    pub const FRAMEBUFFER_HEIGHT: u32 = 480;
+
     /// System bus width in bytes (8 bytes = 64-bit bus).
     ///
     /// Determines the maximum transfer size per bus transaction.
@@ -61,6 +83,14 @@ mod defaults {
     /// Divides the simulation cycle counter to produce the machine timer value.
     pub const CLINT_DIVIDER: u64 = 10;
 
+    /// `mtime` ticks per second advertised in the generated device tree.
+    ///
+    /// Matches a 100 MHz core clock with the default CLINT divider.
+    pub const TIMEBASE_FREQUENCY: u64 = 10_000_000;
+
+    /// Number of harts the CLINT provides MSIP and MTIMECMP registers for.
+    pub const NUM_HARTS: usize = 1;
+
     /// CAS (Column Access Strobe) latency in DRAM cycles.
     ///
     /// Time from column address assertion to data availability for reads.
@@ -82,11 +112,26 @@ mod defaults {
     /// currently open in the row buffer.
     pub const ROW_MISS_LATENCY: u64 = 120;
 
+    /// DRAM banks per channel.
+    pub const DRAM_BANKS: usize = 1;
+
+    /// DRAM channels.
+    pub const DRAM_CHANNELS: usize = 1;
+
+    /// Bytes mapped to one DRAM bank before interleaving to the next.
+    ///
+    /// Equal to the DRAM row size, so a sequential stream fills a row before
+    /// moving to another bank.
+    pub const DRAM_INTERLEAVE_GRANULARITY: u64 = 2048;
+
     /// Translation Lookaside Buffer entry count.
     ///
     /// Number of virtual-to-physical address translations cached in the TLB.
     pub const TLB_SIZE: usize = 32;
 
+    /// Default misaligned access support: handled in hardware.
+    pub const MISALIGNED_ACCESS: bool = true;
+
     /// Default cache size in bytes (4 KiB).
     pub const CACHE_SIZE: usize = 4096;
 
@@ -101,6 +146,9 @@ mod defaults {
     /// Default cache access latency in cycles.
     pub const CACHE_LATENCY: u64 = 1;
 
+    /// Default seed for the Random replacement policy's PRNG.
+    pub const CACHE_RANDOM_SEED: u64 = 123_456_789;
+
     /// Default prefetcher pattern table size (64 entries).
     pub const PREFETCH_TABLE_SIZE: usize = 64;
 
@@ -116,6 +164,12 @@ mod defaults {
     /// Default Return Address Stack size (8 entries).
     pub const RAS_SIZE: usize = 8;
 
+    /// Default loop predictor table size (64 entries).
+    pub const LOOP_TABLE_SIZE: usize = 64;
+
+    /// Default indirect predictor table size (256 entries per bank).
+    pub const INDIRECT_TABLE_SIZE: usize = 256;
+
     /// Default number of TAGE predictor banks (4 tagged tables).
     pub const TAGE_BANKS: usize = 4;
 
@@ -128,6 +182,31 @@ mod defaults {
     /// Default Store Buffer size (16 entries).
     pub const STORE_BUFFER_SIZE: usize = 16;
 
+    /// Default store-to-load forwarding latency in cycles.
+    pub const STORE_FORWARD_LATENCY: u64 = 1;
+
+    /// Default FENCE / FENCE.I latency in cycles, after the drain.
+    pub const FENCE_LATENCY: u64 = 1;
+
+    /// Default integer multiply latency in cycles.
+    pub const MUL_LATENCY: u64 = 3;
+
+    /// Default integer divide/remainder latency in cycles.
+    pub const DIV_LATENCY: u64 = 20;
+
+    /// Default floating-point divide latency in cycles.
+    pub const FDIV_LATENCY: u64 = 20;
+
+    /// Default floating-point square root latency in cycles.
+    pub const FSQRT_LATENCY: u64 = 20;
+
+    /// Default frontend depth: cycles between fetch and branch resolution,
+    /// paid on every misprediction.
+    pub const FRONTEND_DEPTH: u64 = 2;
+
+    /// Default vector register width in bits.
+    pub const VLEN: usize = crate::core::arch::vpr::DEFAULT_VLEN;
+
     /// Default TAGE loop predictor table size (256 entries).
     pub const TAGE_LOOP_SIZE: usize = 256;
 
@@ -140,6 +219,12 @@ mod defaults {
     /// Default Perceptron predictor table size (log2, 1024 entries).
     pub const PERCEPTRON_TABLE_BITS: usize = 10;
 
+    /// Default GShare pattern history table size (log2, 4096 entries).
+    pub const GSHARE_TABLE_BITS: usize = 12;
+
+    /// Default GShare global history length in bits.
+    pub const GSHARE_HISTORY: usize = 12;
+
     /// Default Tournament predictor global history table size (log2, 4096 entries).
     pub const TOURNAMENT_GLOBAL_BITS: usize = 12;
 
@@ -148,6 +233,12 @@ mod defaults {
 
     /// Default Tournament predictor local prediction table size (log2, 1024 entries).
     pub const TOURNAMENT_LOCAL_PRED_BITS: usize = 10;
+
+    /// Fixed latency charged per data memory access under simple timing.
+    pub const SIMPLE_MEM_LATENCY: u64 = 10;
+
+    /// Fixed frontend penalty charged per misprediction under simple timing.
+    pub const SIMPLE_MISPREDICT_PENALTY: u64 = 3;
 }
 
 /// Memory controller implementation types.
@@ -171,6 +262,28 @@ pub enum MemoryController {
     Dram,
 }
 
+/// DRAM row buffer management policy.
+///
+/// Specifies whether a bank leaves its row open after an access or
+/// precharges it straight away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum PagePolicy {
+    /// Open-page policy.
+    ///
+    /// The row stays open, so later accesses to it are row hits and
+    /// accesses to another row pay a precharge first. Favors locality.
+    #[default]
+    #[serde(alias = "open")]
+    Open,
+    /// Closed-page policy.
+    ///
+    /// The bank precharges right after each access, so every access pays
+    /// activate + CAS but no conflict precharge. Favors random access.
+    #[serde(alias = "closed")]
+    Closed,
+}
+
 /// Cache replacement policy algorithms.
 ///
 /// Specifies the algorithm used to select which cache line to evict
@@ -206,6 +319,13 @@ pub enum ReplacementPolicy {
     /// Effective for cyclic access patterns larger than the cache.
     #[serde(alias = "Mru")]
     Mru,
+    /// Dynamic Insertion Policy.
+    ///
+    /// LRU replacement whose insertion position is chosen by set dueling
+    /// between LRU and bimodal insertion. Resists thrashing by scans larger
+    /// than the cache while matching LRU on recency-friendly workloads.
+    #[serde(alias = "Dip")]
+    Dip,
 }
 
 /// Hardware prefetcher types for cache prefetching.
@@ -236,6 +356,11 @@ pub enum Prefetcher {
     ///
     /// Prefetches on demand misses and on hits to previously prefetched lines.
     Tagged,
+    /// Markov (correlation) prefetcher.
+    ///
+    /// Records the misses that followed each missed line and prefetches
+    /// the most frequent successors when that line misses again.
+    Markov,
 }
 
 /// Branch prediction algorithm types.
@@ -267,6 +392,27 @@ pub enum BranchPredictor {
     ///
     /// Selects between local and global predictors based on performance.
     Tournament,
+    /// Oracle predictor for upper-bound studies.
+    ///
+    /// Never mispredicts: the committed direction and target replace any
+    /// wrong guess at no cost.
+    Perfect,
+}
+
+/// How the GShare predictor forms its pattern history table index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum GShareIndex {
+    /// PC bits only (bimodal); ignores global history.
+    #[serde(alias = "pc", alias = "PC")]
+    Pc,
+    /// Global history only (GAg); ignores the PC.
+    #[serde(alias = "history")]
+    History,
+    /// PC XOR global history (gshare).
+    #[default]
+    #[serde(alias = "xor", alias = "XOR")]
+    Xor,
 }
 
 /// Root configuration structure containing all simulator settings.
@@ -395,6 +541,87 @@ pub struct GeneralConfig {
     /// Initial stack pointer (only used when direct_mode is true). Defaults to ram_base + 16MiB if not set.
     #[serde(default)]
     pub initial_sp: Option<u64>,
+
+    /// Simple deterministic timing: bypass caches, prefetchers, and the memory
+    /// controller and charge fixed latencies instead. Intended as a reproducible
+    /// baseline for debugging timing-dependent guest behavior.
+    #[serde(default)]
+    pub simple_timing: bool,
+
+    /// Latency in cycles charged per data memory access when `simple_timing` is set.
+    #[serde(default = "GeneralConfig::default_simple_mem_latency")]
+    pub simple_mem_latency: u64,
+
+    /// Extra fetch stall in cycles charged per misprediction when `simple_timing` is set.
+    #[serde(default = "GeneralConfig::default_simple_mispredict_penalty")]
+    pub simple_mispredict_penalty: u64,
+    /// Same-PC watchdog: stop with an error once this many consecutive
+    /// instructions retire at the same PC with no interrupt pending
+    /// (e.g. a `j .` spin). 0 disables the watchdog.
+    #[serde(default)]
+    pub max_same_pc: u64,
+    /// Write every retired instruction, in commit order, as assembly text to this file.
+    #[serde(default)]
+    pub asm_dump: Option<String>,
+    /// Write a Spike-compatible commit log (`--log-commits` format) of every
+    /// retired instruction to this file.
+    #[serde(default)]
+    pub commit_log: Option<String>,
+    /// Record bus accesses (MMIO and other non-RAM-fast-path traffic) in a
+    /// ring buffer of this many entries. 0 disables the log.
+    #[serde(default)]
+    pub mem_trace_size: usize,
+    /// Record each taken trap (`epc`, cause, `tval`, and target mode) in a
+    /// ring buffer of this many entries. 0 disables the log.
+    #[serde(default)]
+    pub trap_log_size: usize,
+    /// `mstatus` at reset. Defaults to `MSTATUS_DEFAULT_RV64`, with FS set to
+    /// Initial in direct mode.
+    #[serde(default)]
+    pub reset_mstatus: Option<u64>,
+    /// `medeleg` at reset.
+    #[serde(default)]
+    pub reset_medeleg: u64,
+    /// `mideleg` at reset.
+    #[serde(default)]
+    pub reset_mideleg: u64,
+    /// Privilege mode the hart starts in (`"Machine"`, `"Supervisor"`, or
+    /// `"User"`; `"M"`/`"S"`/`"U"` also accepted).
+    #[serde(default = "GeneralConfig::default_reset_privilege")]
+    pub reset_privilege: PrivilegeMode,
+    /// Value `mhartid` reads as. The hart also takes its CLINT interrupts from
+    /// this index, is passed it in `a0` at kernel boot, and is described as
+    /// `cpu@<hartid>` in the generated device tree.
+    #[serde(default)]
+    pub hartid: u64,
+    /// Raise an illegal-instruction exception on access to an unimplemented
+    /// CSR. Off by default: such CSRs read as zero and ignore writes, which
+    /// is friendlier to OS bring-up.
+    #[serde(default)]
+    pub strict_csrs: bool,
+    /// Order in which simultaneously pending interrupts are taken, as
+    /// interrupt codes, highest priority first. Defaults to the privileged
+    /// spec's MEI (11) > MSI (3) > MTI (7) > SEI (9) > SSI (1) > STI (5).
+    /// Unknown codes and repeats are ignored; interrupts left out keep
+    /// their standard relative order after the listed ones.
+    #[serde(default = "GeneralConfig::default_interrupt_priority")]
+    pub interrupt_priority: Vec<u64>,
+    /// Service supervisor-mode `ecall`s with a built-in SBI implementation
+    /// (legacy, Base, TIME, IPI, and SRST extensions) instead of trapping to
+    /// M-mode, so an S-mode kernel can boot without OpenSBI.
+    #[serde(default)]
+    pub sbi: bool,
+    /// Service `EBREAK`s wrapped in the semihosting sequence as host calls
+    /// (console, host files, exit) when the hart is in M- or S-mode. Off by
+    /// default, since a guest with semihosting can create or truncate any
+    /// host file the simulator can write.
+    #[serde(default)]
+    pub semihosting: bool,
+    /// Make `EBREAK` halt the hart and hand control back to the host (as
+    /// `dcsr.ebreakm/s/u` enter Debug Mode) instead of raising a breakpoint
+    /// exception. Semihosting calls are still serviced when enabled.
+    #[serde(default)]
+    pub ebreak_halt: bool,
 }
 
 impl GeneralConfig {
@@ -407,6 +634,26 @@ impl GeneralConfig {
     fn default_direct_mode() -> bool {
         true
     }
+
+    /// Returns the default per-access latency for simple timing.
+    fn default_simple_mem_latency() -> u64 {
+        defaults::SIMPLE_MEM_LATENCY
+    }
+
+    /// Returns the default mispredict penalty for simple timing.
+    fn default_simple_mispredict_penalty() -> u64 {
+        defaults::SIMPLE_MISPREDICT_PENALTY
+    }
+
+    /// Harts start in M-mode by default.
+    fn default_reset_privilege() -> PrivilegeMode {
+        PrivilegeMode::Machine
+    }
+
+    /// Returns the privileged spec's default interrupt priority.
+    fn default_interrupt_priority() -> Vec<u64> {
+        STANDARD_INTERRUPT_PRIORITY.to_vec()
+    }
 }
 
 impl Default for GeneralConfig {
@@ -416,6 +663,24 @@ impl Default for GeneralConfig {
             start_pc: defaults::RAM_BASE,
             direct_mode: true,
             initial_sp: None,
+            simple_timing: false,
+            simple_mem_latency: defaults::SIMPLE_MEM_LATENCY,
+            simple_mispredict_penalty: defaults::SIMPLE_MISPREDICT_PENALTY,
+            max_same_pc: 0,
+            asm_dump: None,
+            commit_log: None,
+            mem_trace_size: 0,
+            trap_log_size: 0,
+            reset_mstatus: None,
+            reset_medeleg: 0,
+            reset_mideleg: 0,
+            hartid: 0,
+            reset_privilege: PrivilegeMode::Machine,
+            strict_csrs: false,
+            interrupt_priority: STANDARD_INTERRUPT_PRIORITY.to_vec(),
+            sbi: false,
+            semihosting: false,
+            ebreak_halt: false,
         }
     }
 }
@@ -446,10 +711,18 @@ pub struct SystemConfig {
     #[serde(default = "SystemConfig::default_syscon_base")]
     pub syscon_base: u64,
 
+    /// Goldfish RTC (real-time clock) MMIO base address
+    #[serde(default = "SystemConfig::default_rtc_base")]
+    pub rtc_base: u64,
+
     /// Kernel load offset from RAM base
     #[serde(default = "SystemConfig::default_kernel_offset")]
     pub kernel_offset: u64,
 
+    /// Initrd (initramfs) load offset from RAM base
+    #[serde(default = "SystemConfig::default_initrd_offset")]
+    pub initrd_offset: u64,
+
     /// System bus width in bytes
     #[serde(default = "SystemConfig::default_bus_width")]
     pub bus_width: u64,
@@ -462,6 +735,18 @@ pub struct SystemConfig {
     #[serde(default = "SystemConfig::default_clint_divider")]
     pub clint_divider: u64,
 
+    /// Frequency in Hz at which `mtime` is taken to advance. Advertised as
+    /// the device tree's `timebase-frequency` and used by the RTC to convert
+    /// ticks to nanoseconds; it does not change how many cycles a tick takes.
+    #[serde(default = "SystemConfig::default_timebase_frequency")]
+    pub timebase_frequency: u64,
+
+    /// Number of harts the CLINT serves: one MSIP register at `0x0000 + 4*h`
+    /// and one MTIMECMP register at `0x4000 + 8*h` per hart. Only hart 0
+    /// executes instructions; the others exist for IPI and timer testing.
+    #[serde(default = "SystemConfig::default_num_harts")]
+    pub num_harts: usize,
+
     /// When true, UART output goes to stderr (for visibility when run from Python).
     #[serde(default)]
     pub uart_to_stderr: bool,
@@ -474,6 +759,14 @@ pub struct SystemConfig {
     /// registered at this address to intercept riscv-tests pass/fail writes.
     #[serde(default)]
     pub tohost_addr: u64,
+
+    /// Per-device clock-domain-crossing latency added to MMIO accesses
+    #[serde(default)]
+    pub cdc_latency: CdcLatencyConfig,
+
+    /// Linear framebuffer (disabled by default)
+    #[serde(default)]
+    pub framebuffer: FramebufferConfig,
 }
 
 impl SystemConfig {
@@ -502,11 +795,21 @@ impl SystemConfig {
         defaults::SYSCON_BASE
     }
 
+    /// Returns the default Goldfish RTC MMIO base address.
+    fn default_rtc_base() -> u64 {
+        defaults::RTC_BASE
+    }
+
     /// Returns the default kernel load offset from RAM base.
     fn default_kernel_offset() -> u64 {
         defaults::KERNEL_OFFSET
     }
 
+    /// Returns the default initrd load offset from RAM base.
+    fn default_initrd_offset() -> u64 {
+        defaults::INITRD_OFFSET
+    }
+
     /// Returns the default system bus width in bytes.
     fn default_bus_width() -> u64 {
         defaults::BUS_WIDTH
@@ -521,6 +824,16 @@ impl SystemConfig {
     fn default_clint_divider() -> u64 {
         defaults::CLINT_DIVIDER
     }
+
+    /// Returns the default timebase frequency in Hz.
+    fn default_timebase_frequency() -> u64 {
+        defaults::TIMEBASE_FREQUENCY
+    }
+
+    /// Returns the default hart count.
+    fn default_num_harts() -> usize {
+        defaults::NUM_HARTS
+    }
 }
 
 impl Default for SystemConfig {
@@ -535,17 +848,106 @@ impl Default for SystemConfig {
             ram_base: defaults::RAM_BASE,
             clint_base: defaults::CLINT_BASE,
             syscon_base: defaults::SYSCON_BASE,
+            rtc_base: defaults::RTC_BASE,
             kernel_offset: defaults::KERNEL_OFFSET,
+            initrd_offset: defaults::INITRD_OFFSET,
             bus_width: defaults::BUS_WIDTH,
             bus_latency: defaults::BUS_LATENCY,
             clint_divider: defaults::CLINT_DIVIDER,
+            timebase_frequency: defaults::TIMEBASE_FREQUENCY,
+            num_harts: defaults::NUM_HARTS,
             uart_to_stderr: false,
             uart_quiet: false,
             tohost_addr: 0,
+            cdc_latency: CdcLatencyConfig::default(),
+            framebuffer: FramebufferConfig::default(),
+        }
+    }
+}
+
+/// Linear framebuffer configuration.
+///
+/// When enabled, a `width` x `height` buffer of 32-bit `0x00RRGGBB` pixels is
+/// mapped at `base`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct FramebufferConfig {
+    /// Map the framebuffer on the bus
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// MMIO base address
+    #[serde(default = "FramebufferConfig::default_base")]
+    pub base: u64,
+
+    /// Width in pixels
+    #[serde(default = "FramebufferConfig::default_width")]
+    pub width: u32,
+
+    /// Height in pixels
+    #[serde(default = "FramebufferConfig::default_height")]
+    pub height: u32,
+}
+
+impl FramebufferConfig {
+    /// Returns the default framebuffer base address.
+    fn default_base() -> u64 {
+        defaults::FRAMEBUFFER_BASE
+    }
+
+    /// Returns the default framebuffer width in pixels.
+    fn default_width() -> u32 {
+        defaults::FRAMEBUFFER_WIDTH
+    }
+
+    /// Returns the default framebuffer height in pixels.
+    fn default_height() -> u32 {
+        defaults::FRAMEBUFFER_HEIGHT
+    }
+}
+
+impl Default for FramebufferConfig {
+    /// Creates a disabled framebuffer configuration with the default geometry.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base: defaults::FRAMEBUFFER_BASE,
+            width: defaults::FRAMEBUFFER_WIDTH,
+            height: defaults::FRAMEBUFFER_HEIGHT,
         }
     }
 }
 
+/// Clock-domain-crossing latency per MMIO device.
+///
+/// Devices in a slower clock domain pay a synchronizer cost on every
+/// access, in cycles, on top of the bus transit time. All default to 0.
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+pub struct CdcLatencyConfig {
+    /// UART
+    #[serde(default)]
+    pub uart: u64,
+
+    /// VirtIO block device
+    #[serde(default)]
+    pub disk: u64,
+
+    /// CLINT
+    #[serde(default)]
+    pub clint: u64,
+
+    /// PLIC
+    #[serde(default)]
+    pub plic: u64,
+
+    /// System controller
+    #[serde(default)]
+    pub syscon: u64,
+
+    /// Goldfish RTC
+    #[serde(default)]
+    pub rtc: u64,
+}
+
 /// Main memory system configuration.
 ///
 /// Specifies RAM size, memory controller type, DRAM timing parameters,
@@ -572,13 +974,74 @@ pub struct MemoryConfig {
     #[serde(default = "MemoryConfig::default_t_pre")]
     pub t_pre: u64,
 
+    /// Row buffer policy (open-page or closed-page)
+    #[serde(default)]
+    pub page_policy: PagePolicy,
+
+    /// CAS write latency, used for writes in place of `t_cas`. `None`
+    /// times writes like reads.
+    #[serde(default)]
+    pub t_cwl: Option<u64>,
+
+    /// Write recovery time: cycles a bank stays busy after a write
+    #[serde(default)]
+    pub t_wr: u64,
+
+    /// Write-to-read turnaround: cycles after a write completes before a
+    /// read in the same channel may start
+    #[serde(default)]
+    pub t_wtr: u64,
+
+    /// Refresh interval (tREFI) in cycles; 0 disables refresh
+    #[serde(default)]
+    pub t_refi: u64,
+
+    /// Refresh cycle time (tRFC): cycles each refresh blocks every bank
+    #[serde(default)]
+    pub t_rfc: u64,
+
     /// Row buffer miss penalty
     #[serde(default = "MemoryConfig::default_row_miss")]
     pub row_miss_latency: u64,
 
+    /// DRAM banks per channel, each with its own row buffer
+    #[serde(default = "MemoryConfig::default_num_banks")]
+    pub num_banks: usize,
+
+    /// Independent DRAM channels
+    #[serde(default = "MemoryConfig::default_num_channels")]
+    pub num_channels: usize,
+
+    /// Bytes per channel/bank interleaving block
+    #[serde(default = "MemoryConfig::default_interleave_granularity")]
+    pub interleave_granularity: u64,
+
     /// TLB entry count
     #[serde(default = "MemoryConfig::default_tlb_size")]
     pub tlb_size: usize,
+
+    /// Perform misaligned loads and stores in hardware (split into byte
+    /// accesses, with a latency penalty). When false they raise
+    /// `LoadAddressMisaligned`/`StoreAddressMisaligned` instead.
+    #[serde(default = "MemoryConfig::default_misaligned_access")]
+    pub misaligned_access: bool,
+
+    /// Big-endian data accesses in every privilege mode (`mstatus.MBE`,
+    /// `SBE` and `UBE` read as 1). Instruction fetch stays little-endian.
+    #[serde(default)]
+    pub big_endian: bool,
+
+    /// 32-bit word repeated (little-endian) across RAM before anything is
+    /// loaded, e.g. `0xFFFF_FFFF` or a `0xDEAD_BEEF` poison value that
+    /// exposes reads of uninitialized memory. 0 leaves RAM zeroed.
+    #[serde(default)]
+    pub fill_pattern: u32,
+
+    /// Svade: raise a page fault when a leaf PTE has A clear, or D clear on
+    /// a store, so software manages the bits. When false the page table
+    /// walker sets them in memory.
+    #[serde(default)]
+    pub svade: bool,
 }
 
 impl MemoryConfig {
@@ -607,10 +1070,30 @@ impl MemoryConfig {
         defaults::ROW_MISS_LATENCY
     }
 
+    /// Returns the default number of DRAM banks per channel.
+    fn default_num_banks() -> usize {
+        defaults::DRAM_BANKS
+    }
+
+    /// Returns the default number of DRAM channels.
+    fn default_num_channels() -> usize {
+        defaults::DRAM_CHANNELS
+    }
+
+    /// Returns the default DRAM interleaving granularity in bytes.
+    fn default_interleave_granularity() -> u64 {
+        defaults::DRAM_INTERLEAVE_GRANULARITY
+    }
+
     /// Returns the default TLB entry count.
     fn default_tlb_size() -> usize {
         defaults::TLB_SIZE
     }
+
+    /// Returns the default misaligned access support.
+    fn default_misaligned_access() -> bool {
+        defaults::MISALIGNED_ACCESS
+    }
 }
 
 impl Default for MemoryConfig {
@@ -625,14 +1108,27 @@ impl Default for MemoryConfig {
             t_cas: defaults::T_CAS,
             t_ras: defaults::T_RAS,
             t_pre: defaults::T_PRE,
+            page_policy: PagePolicy::default(),
+            t_cwl: None,
+            t_wr: 0,
+            t_wtr: 0,
+            t_refi: 0,
+            t_rfc: 0,
             row_miss_latency: defaults::ROW_MISS_LATENCY,
+            num_banks: defaults::DRAM_BANKS,
+            num_channels: defaults::DRAM_CHANNELS,
+            interleave_granularity: defaults::DRAM_INTERLEAVE_GRANULARITY,
             tlb_size: defaults::TLB_SIZE,
+            misaligned_access: defaults::MISALIGNED_ACCESS,
+            big_endian: false,
+            fill_pattern: 0,
+            svade: false,
         }
     }
 }
 
 /// Cache hierarchy configuration.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct CacheHierarchyConfig {
     /// L1 instruction cache
     pub l1_i: CacheConfig,
@@ -642,6 +1138,30 @@ pub struct CacheHierarchyConfig {
     pub l2: CacheConfig,
     /// Unified L3 cache (optional)
     pub l3: CacheConfig,
+    /// Seed for the Random replacement policy's PRNG. Every level using the
+    /// Random policy starts from this seed, so identical access streams
+    /// produce identical evictions.
+    #[serde(default = "CacheHierarchyConfig::default_random_seed")]
+    pub random_seed: u64,
+}
+
+impl CacheHierarchyConfig {
+    /// Returns the default Random replacement seed.
+    fn default_random_seed() -> u64 {
+        defaults::CACHE_RANDOM_SEED
+    }
+}
+
+impl Default for CacheHierarchyConfig {
+    fn default() -> Self {
+        Self {
+            l1_i: CacheConfig::default(),
+            l1_d: CacheConfig::default(),
+            l2: CacheConfig::default(),
+            l3: CacheConfig::default(),
+            random_seed: defaults::CACHE_RANDOM_SEED,
+        }
+    }
 }
 
 /// Individual cache level configuration.
@@ -667,7 +1187,9 @@ pub struct CacheConfig {
     #[serde(default)]
     pub policy: ReplacementPolicy,
 
-    /// Access latency in cycles
+    /// Access latency in cycles, charged on every access that reaches this
+    /// level. For L1 the first cycle is the pipeline's fetch or memory stage,
+    /// so only cycles beyond one stall.
     #[serde(default = "CacheConfig::default_latency")]
     pub latency: u64,
 
@@ -675,13 +1197,24 @@ pub struct CacheConfig {
     #[serde(default)]
     pub prefetcher: Prefetcher,
 
-    /// Prefetcher table size (for stride prefetcher)
+    /// Prefetcher table size (stride table or Markov correlation table)
     #[serde(default = "CacheConfig::default_prefetch_table")]
     pub prefetch_table_size: usize,
 
     /// Prefetch degree (lines to prefetch per trigger)
     #[serde(default = "CacheConfig::default_prefetch_degree")]
     pub prefetch_degree: usize,
+
+    /// Number of line-interleaved banks, each serving one line per cycle.
+    /// Same-cycle accesses to different lines in one bank serialize. 0 models
+    /// an unbanked cache with unlimited ports.
+    #[serde(default)]
+    pub banks: usize,
+
+    /// Record demand accesses and misses per set, readable through
+    /// `CacheSim::set_stats`.
+    #[serde(default)]
+    pub set_stats: bool,
 }
 
 impl CacheConfig {
@@ -732,6 +1265,8 @@ impl Default for CacheConfig {
             prefetcher: Prefetcher::default(),
             prefetch_table_size: defaults::PREFETCH_TABLE_SIZE,
             prefetch_degree: defaults::PREFETCH_DEGREE,
+            banks: 0,
+            set_stats: false,
         }
     }
 }
@@ -743,6 +1278,15 @@ pub struct PipelineConfig {
     #[serde(default = "PipelineConfig::default_width")]
     pub width: usize,
 
+    /// Instructions fetched per cycle (0 = same as `width`)
+    #[serde(default)]
+    pub fetch_width: usize,
+
+    /// Fetch queue entries between fetch and decode; fetch keeps filling it
+    /// while decode stalls (0 = a single fetch group, no decoupling)
+    #[serde(default)]
+    pub fetch_queue_size: usize,
+
     /// Branch predictor type
     #[serde(default)]
     pub branch_predictor: BranchPredictor,
@@ -755,6 +1299,22 @@ pub struct PipelineConfig {
     #[serde(default = "PipelineConfig::default_ras_size")]
     pub ras_size: usize,
 
+    /// Enable the loop-exit predictor on top of the base predictor
+    #[serde(default)]
+    pub loop_predictor: bool,
+
+    /// Loop predictor table size (must be power of 2)
+    #[serde(default = "PipelineConfig::default_loop_table_size")]
+    pub loop_table_size: usize,
+
+    /// Enable the ITTAGE-style indirect jump target predictor
+    #[serde(default)]
+    pub indirect_predictor: bool,
+
+    /// Indirect predictor entries per table (must be power of 2)
+    #[serde(default = "PipelineConfig::default_indirect_table_size")]
+    pub indirect_table_size: usize,
+
     /// MISA register override (e.g., "RV64IMAFDC")
     #[serde(default)]
     pub misa_override: Option<String>,
@@ -771,6 +1331,10 @@ pub struct PipelineConfig {
     #[serde(default)]
     pub tournament: TournamentConfig,
 
+    /// GShare predictor configuration
+    #[serde(default)]
+    pub gshare: GShareConfig,
+
     /// Backend type (InOrder or OutOfOrder)
     #[serde(default)]
     pub backend: BackendType,
@@ -782,6 +1346,45 @@ pub struct PipelineConfig {
     /// Store Buffer size
     #[serde(default = "PipelineConfig::default_store_buffer_size")]
     pub store_buffer_size: usize,
+
+    /// Cycles a load pays when its data is forwarded from the store buffer,
+    /// in place of the D-cache access
+    #[serde(default = "PipelineConfig::default_store_forward_latency")]
+    pub store_forward_latency: u64,
+
+    /// Cycles a FENCE or FENCE.I occupies execute once older instructions
+    /// have retired and the store buffer has drained
+    #[serde(default = "PipelineConfig::default_fence_latency")]
+    pub fence_latency: u64,
+
+    /// Integer multiply latency in cycles (MUL, MULH, MULHSU, MULHU, MULW)
+    #[serde(default = "PipelineConfig::default_mul_latency")]
+    pub mul_latency: u64,
+
+    /// Integer divide latency in cycles (DIV, DIVU, REM, REMU and W forms)
+    #[serde(default = "PipelineConfig::default_div_latency")]
+    pub div_latency: u64,
+
+    /// Floating-point divide latency in cycles (FDIV.S, FDIV.D)
+    #[serde(default = "PipelineConfig::default_fdiv_latency")]
+    pub fdiv_latency: u64,
+
+    /// Floating-point square root latency in cycles (FSQRT.S, FSQRT.D)
+    #[serde(default = "PipelineConfig::default_fsqrt_latency")]
+    pub fsqrt_latency: u64,
+
+    /// Cycles between fetch and branch resolution; the misprediction
+    /// flush penalty. Depths above 2 add refetch bubbles.
+    #[serde(default = "PipelineConfig::default_frontend_depth")]
+    pub frontend_depth: u64,
+
+    /// Track integer register dependencies per 32-bit half
+    #[serde(default)]
+    pub subreg_tracking: bool,
+
+    /// Vector register width in bits (VLEN), a power of two of at least 32
+    #[serde(default = "PipelineConfig::default_vlen")]
+    pub vlen: usize,
 }
 
 impl PipelineConfig {
@@ -800,6 +1403,16 @@ impl PipelineConfig {
         defaults::RAS_SIZE
     }
 
+    /// Returns the default loop predictor table size.
+    fn default_loop_table_size() -> usize {
+        defaults::LOOP_TABLE_SIZE
+    }
+
+    /// Returns the default indirect predictor table size.
+    fn default_indirect_table_size() -> usize {
+        defaults::INDIRECT_TABLE_SIZE
+    }
+
     /// Returns the default ROB size.
     fn default_rob_size() -> usize {
         defaults::ROB_SIZE
@@ -809,6 +1422,46 @@ impl PipelineConfig {
     fn default_store_buffer_size() -> usize {
         defaults::STORE_BUFFER_SIZE
     }
+
+    /// Returns the default store-to-load forwarding latency.
+    fn default_store_forward_latency() -> u64 {
+        defaults::STORE_FORWARD_LATENCY
+    }
+
+    /// Returns the default fence latency.
+    fn default_fence_latency() -> u64 {
+        defaults::FENCE_LATENCY
+    }
+
+    /// Returns the default integer multiply latency.
+    fn default_mul_latency() -> u64 {
+        defaults::MUL_LATENCY
+    }
+
+    /// Returns the default integer divide latency.
+    fn default_div_latency() -> u64 {
+        defaults::DIV_LATENCY
+    }
+
+    /// Returns the default floating-point divide latency.
+    fn default_fdiv_latency() -> u64 {
+        defaults::FDIV_LATENCY
+    }
+
+    /// Returns the default floating-point square root latency.
+    fn default_fsqrt_latency() -> u64 {
+        defaults::FSQRT_LATENCY
+    }
+
+    /// Returns the default frontend depth.
+    fn default_frontend_depth() -> u64 {
+        defaults::FRONTEND_DEPTH
+    }
+
+    /// Returns the default vector register width.
+    fn default_vlen() -> usize {
+        defaults::VLEN
+    }
 }
 
 impl Default for PipelineConfig {
@@ -819,16 +1472,32 @@ impl Default for PipelineConfig {
     fn default() -> Self {
         Self {
             width: defaults::PIPELINE_WIDTH,
+            fetch_width: 0,
+            fetch_queue_size: 0,
             branch_predictor: BranchPredictor::default(),
             btb_size: defaults::BTB_SIZE,
             ras_size: defaults::RAS_SIZE,
+            loop_predictor: false,
+            loop_table_size: defaults::LOOP_TABLE_SIZE,
+            indirect_predictor: false,
+            indirect_table_size: defaults::INDIRECT_TABLE_SIZE,
             misa_override: None,
             tage: TageConfig::default(),
             perceptron: PerceptronConfig::default(),
             tournament: TournamentConfig::default(),
+            gshare: GShareConfig::default(),
             backend: BackendType::default(),
             rob_size: defaults::ROB_SIZE,
             store_buffer_size: defaults::STORE_BUFFER_SIZE,
+            store_forward_latency: defaults::STORE_FORWARD_LATENCY,
+            fence_latency: defaults::FENCE_LATENCY,
+            mul_latency: defaults::MUL_LATENCY,
+            div_latency: defaults::DIV_LATENCY,
+            fdiv_latency: defaults::FDIV_LATENCY,
+            fsqrt_latency: defaults::FSQRT_LATENCY,
+            frontend_depth: defaults::FRONTEND_DEPTH,
+            subreg_tracking: false,
+            vlen: defaults::VLEN,
         }
     }
 }
@@ -907,6 +1576,10 @@ pub struct PerceptronConfig {
     /// Log2 of perceptron table size
     #[serde(default = "PerceptronConfig::default_table_bits")]
     pub table_bits: usize,
+
+    /// Adapt the training threshold at run time (O-GEHL dynamic theta)
+    #[serde(default)]
+    pub adaptive_threshold: bool,
 }
 
 impl PerceptronConfig {
@@ -921,6 +1594,45 @@ impl PerceptronConfig {
     }
 }
 
+/// GShare branch predictor configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GShareConfig {
+    /// Pattern history table size (log2)
+    #[serde(default = "GShareConfig::default_table_bits")]
+    pub table_bits: usize,
+
+    /// Global history length in bits (at most 64). Histories longer than
+    /// `table_bits` are XOR-folded down to the index width.
+    #[serde(default = "GShareConfig::default_history")]
+    pub history_length: usize,
+
+    /// Index function: PC only, history only, or PC XOR history
+    #[serde(default)]
+    pub index: GShareIndex,
+}
+
+impl GShareConfig {
+    /// Returns the default GShare pattern history table size (log2).
+    fn default_table_bits() -> usize {
+        defaults::GSHARE_TABLE_BITS
+    }
+
+    /// Returns the default GShare global history length.
+    fn default_history() -> usize {
+        defaults::GSHARE_HISTORY
+    }
+}
+
+impl Default for GShareConfig {
+    fn default() -> Self {
+        Self {
+            table_bits: defaults::GSHARE_TABLE_BITS,
+            history_length: defaults::GSHARE_HISTORY,
+            index: GShareIndex::default(),
+        }
+    }
+}
+
 /// Tournament branch predictor configuration.
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct TournamentConfig {
@@ -6,6 +6,8 @@
 //! 3. **Register Storage:** The `Csrs` struct for maintaining architectural state.
 //! 4. **Access Logic:** Standardized read and write operations for register interaction.
 
+use serde::{Deserialize, Serialize};
+
 /// Floating-point accrued exceptions CSR address.
 pub const FFLAGS: u32 = 0x001;
 
@@ -15,6 +17,18 @@ pub const FRM: u32 = 0x002;
 /// Floating-point control and status register CSR address.
 pub const FCSR: u32 = 0x003;
 
+/// Vector start element index CSR address.
+pub const VSTART: u32 = 0x008;
+
+/// Vector length CSR address (read-only; set by `vsetvl{i}`).
+pub const VL: u32 = 0xC20;
+
+/// Vector data type CSR address (read-only; set by `vsetvl{i}`).
+pub const VTYPE: u32 = 0xC21;
+
+/// Vector register length in bytes CSR address (read-only).
+pub const VLENB: u32 = 0xC22;
+
 /// Machine vendor ID CSR address.
 pub const MVENDORID: u32 = 0xF11;
 
@@ -48,6 +62,12 @@ pub const MTVEC: u32 = 0x305;
 /// Machine counter enable register CSR address.
 pub const MCOUNTEREN: u32 = 0x306;
 
+/// Machine counter-inhibit register CSR address.
+pub const MCOUNTINHIBIT: u32 = 0x320;
+
+/// Machine environment configuration register CSR address.
+pub const MENVCFG: u32 = 0x30A;
+
 /// Machine scratch register CSR address.
 pub const MSCRATCH: u32 = 0x340;
 
@@ -63,6 +83,13 @@ pub const MTVAL: u32 = 0x343;
 /// Machine interrupt pending register CSR address.
 pub const MIP: u32 = 0x344;
 
+/// Machine second trap value register CSR address (hypervisor extension;
+/// stubbed as hardwired zero).
+pub const MTVAL2: u32 = 0x34B;
+
+/// Hypervisor trap value register CSR address (stubbed as hardwired zero).
+pub const HTVAL: u32 = 0x643;
+
 /// Supervisor status register CSR address.
 pub const SSTATUS: u32 = 0x100;
 
@@ -111,6 +138,71 @@ pub const MCYCLE: u32 = 0xB00;
 /// Machine instructions retired counter CSR address.
 pub const MINSTRET: u32 = 0xB02;
 
+/// First machine hardware performance counter CSR address (`mhpmcounter3`).
+pub const MHPMCOUNTER3: u32 = 0xB03;
+
+/// Last machine hardware performance counter CSR address (`mhpmcounter31`).
+pub const MHPMCOUNTER31: u32 = 0xB1F;
+
+/// First user hardware performance counter CSR address (`hpmcounter3`).
+pub const HPMCOUNTER3: u32 = 0xC03;
+
+/// Last user hardware performance counter CSR address (`hpmcounter31`).
+pub const HPMCOUNTER31: u32 = 0xC1F;
+
+/// First hardware performance event selector CSR address (`mhpmevent3`).
+pub const MHPMEVENT3: u32 = 0x323;
+
+/// Last hardware performance event selector CSR address (`mhpmevent31`).
+pub const MHPMEVENT31: u32 = 0x33F;
+
+/// Number of programmable hardware performance counters (3 through 31).
+pub const HPM_COUNTERS: usize = 29;
+
+/// `mcountinhibit` bit freezing `mcycle`.
+pub const MCOUNTINHIBIT_CY: u64 = 1 << 0;
+
+/// `mcountinhibit` bit freezing `minstret`.
+pub const MCOUNTINHIBIT_IR: u64 = 1 << 2;
+
+/// Writable bits of `mcountinhibit`: CY, IR, and HPM3..HPM31. Bit 1 (`time`)
+/// is read-only zero.
+pub const MCOUNTINHIBIT_MASK: u64 = 0xFFFF_FFFD;
+
+/// `time` (TM) bit in `mcounteren`/`scounteren`.
+pub const COUNTEREN_TM: u64 = 1 << 1;
+
+/// Sstc enable bit (STCE) in `menvcfg`: `stimecmp` drives `mip.STIP` and
+/// is accessible from S-mode.
+pub const MENVCFG_STCE: u64 = 1 << 63;
+
+/// Writable bits of `menvcfg`; the other fields are read-only zero.
+pub const MENVCFG_WRITABLE: u64 = MENVCFG_STCE;
+
+/// HPM event: no event selected (counter does not increment).
+pub const HPM_EVENT_NONE: u64 = 0;
+
+/// HPM event: L1 instruction cache miss.
+pub const HPM_EVENT_ICACHE_MISS: u64 = 1;
+
+/// HPM event: L1 data cache miss.
+pub const HPM_EVENT_DCACHE_MISS: u64 = 2;
+
+/// HPM event: L2 cache miss.
+pub const HPM_EVENT_L2_MISS: u64 = 3;
+
+/// HPM event: branch or jump misprediction.
+pub const HPM_EVENT_BRANCH_MISPREDICT: u64 = 4;
+
+/// HPM event: instruction TLB miss.
+pub const HPM_EVENT_ITLB_MISS: u64 = 5;
+
+/// HPM event: data TLB miss.
+pub const HPM_EVENT_DTLB_MISS: u64 = 6;
+
+/// Number of HPM event codes, including `HPM_EVENT_NONE`.
+pub const HPM_EVENT_COUNT: usize = 7;
+
 /// User interrupt enable bit in `mstatus` register.
 pub const MSTATUS_UIE: u64 = 1 << 0;
 
@@ -223,6 +315,18 @@ pub const MSTATUS_MXR: u64 = 1 << 19;
 /// User XLEN field in `mstatus` register (bits 33:32).
 pub const MSTATUS_UXL: u64 = 3 << 32;
 
+/// User-mode big-endian data bit in `mstatus`/`sstatus`.
+pub const MSTATUS_UBE: u64 = 1 << 6;
+
+/// Supervisor-mode big-endian data bit in `mstatus`.
+pub const MSTATUS_SBE: u64 = 1 << 36;
+
+/// Machine-mode big-endian data bit in `mstatus`.
+pub const MSTATUS_MBE: u64 = 1 << 37;
+
+/// All `mstatus` data endianness bits; hardwired to the configured byte order.
+pub const MSTATUS_BE: u64 = MSTATUS_UBE | MSTATUS_SBE | MSTATUS_MBE;
+
 /// Bit shift for address translation mode field in `satp` register.
 pub const SATP_MODE_SHIFT: u64 = 60;
 
@@ -238,9 +342,18 @@ pub const SATP_MODE_MASK: u64 = 0xF;
 /// Physical page number mask in `satp` register.
 pub const SATP_PPN_MASK: u64 = 0xFFF_FFFF_FFFF;
 
+/// Bit shift for the address space identifier field in `satp` register.
+pub const SATP_ASID_SHIFT: u64 = 44;
+
+/// Bit mask for the address space identifier field in `satp` register.
+pub const SATP_ASID_MASK: u64 = 0xFFFF;
+
 /// MISA extension bit for atomic operations (A extension).
 pub const MISA_EXT_A: u64 = 1 << 0;
 
+/// MISA extension bit for bit manipulation (B extension: Zba, Zbb).
+pub const MISA_EXT_B: u64 = 1 << 1;
+
 /// MISA extension bit for compressed instructions (C extension).
 pub const MISA_EXT_C: u64 = 1 << 2;
 
@@ -271,12 +384,85 @@ pub const MISA_XLEN_64: u64 = 2 << 62;
 /// MISA XLEN field value for 128-bit architecture.
 pub const MISA_XLEN_128: u64 = 3 << 62;
 
+/// `vtype` illegal-configuration flag (bit XLEN-1).
+pub const VTYPE_VILL: u64 = 1 << 63;
+
+/// `vtype` field bits defined by the specification (vlmul, vsew, vta, vma).
+pub const VTYPE_FIELDS_MASK: u64 = 0xFF;
+
+/// Shift of the `vsew` (selected element width) field in `vtype`.
+pub const VTYPE_VSEW_SHIFT: u64 = 3;
+
+/// Mask of the `vsew` field in `vtype` (after shifting).
+pub const VTYPE_VSEW_MASK: u64 = 0x7;
+
+/// Mask of the `vlmul` (register group multiplier) field in `vtype`.
+pub const VTYPE_VLMUL_MASK: u64 = 0x7;
+
+/// `vsew` encoding for 32-bit elements.
+pub const VTYPE_VSEW_32: u64 = 0b010;
+
+/// `vlmul` encoding for LMUL=1.
+pub const VTYPE_VLMUL_1: u64 = 0b000;
+
 /// Default `mstatus` value for RV64 architecture.
 pub const MSTATUS_DEFAULT_RV64: u64 = 0xa000_00000;
 
 /// Default `misa` value for RV64IMAFDC architecture.
 pub const MISA_DEFAULT_RV64IMAFDC: u64 = 0x8000_0000_0014_1101;
 
+/// Mask of the Extensions field (bits 25:0) in `misa`.
+pub const MISA_EXTENSIONS_MASK: u64 = (1 << 26) - 1;
+
+/// `misa` extensions software may clear and set again. Only C can be
+/// switched off (fetch then requires 4-byte alignment); the others are
+/// hardwired to the configured set.
+pub const MISA_WRITABLE: u64 = MISA_EXT_C;
+
+/// Interrupt-enable bits implemented in `mie` (M- and S-level software,
+/// timer, and external interrupts).
+pub const MIE_IMPLEMENTED: u64 = MIE_SSIP | MIE_MSIP | MIE_STIE | MIE_MTIE | MIE_SEIP | MIE_MEIP;
+
+/// Interrupts `mideleg` can delegate to S-mode.
+pub const MIDELEG_WRITABLE: u64 = MIP_SSIP | MIP_STIP | MIP_SEIP;
+
+/// Exceptions `medeleg` can delegate to S-mode: every implemented exception
+/// except an `ecall` from M-mode, which can never be taken in S-mode.
+pub const MEDELEG_WRITABLE: u64 = 0xB3FF;
+
+/// `mtvec`/`stvec` mode bit that, when set, selects a reserved mode (2 or 3).
+pub const TVEC_MODE_RESERVED_BIT: u64 = 2;
+
+/// Legalizes a `misa` write: MXL stays 64-bit, the I base cannot be
+/// disabled, and the reserved bits and E (which cannot coexist with I)
+/// read as zero.
+pub fn legalize_misa(val: u64) -> u64 {
+    const MISA_EXT_E: u64 = 1 << 4;
+    MISA_XLEN_64 | MISA_EXT_I | (val & MISA_EXTENSIONS_MASK & !MISA_EXT_E)
+}
+
+/// Returns `misa` after a write of `val` over `old`: only the extensions in
+/// `writable` follow the write, and the result is legalized.
+pub fn write_misa(old: u64, val: u64, writable: u64) -> u64 {
+    legalize_misa((old & !writable) | (val & writable))
+}
+
+/// Legalizes a `mtvec`/`stvec` write. The reserved modes 2 and 3 fall back
+/// to Direct and Vectored by clearing the high mode bit.
+pub fn legalize_tvec(val: u64) -> u64 {
+    val & !TVEC_MODE_RESERVED_BIT
+}
+
+/// Legalizes a `mcause`/`scause` write: codes the hart can report are
+/// stored, anything else leaves the register at `old`.
+pub fn legalize_cause(old: u64, val: u64) -> u64 {
+    if crate::isa::privileged::cause::is_implemented(val) {
+        val
+    } else {
+        old
+    }
+}
+
 /// CSR serialization requirement classification.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CsrSerializationType {
@@ -306,7 +492,7 @@ pub fn csr_serialization_type(addr: u32) -> CsrSerializationType {
 ///
 /// Contains all machine-level and supervisor-level CSRs that control processor state,
 /// interrupt handling, memory management, and performance counters.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Csrs {
     /// Machine status register.
     pub mstatus: u64,
@@ -360,6 +546,8 @@ pub struct Csrs {
     pub minstret: u64,
     /// Supervisor timer compare (for timer interrupt).
     pub stimecmp: u64,
+    /// Machine environment configuration (only STCE is implemented).
+    pub menvcfg: u64,
     /// Floating-point accrued exception flags (5 bits: NV, DZ, OF, UF, NX).
     pub fflags: u64,
     /// Floating-point dynamic rounding mode (3 bits).
@@ -368,6 +556,24 @@ pub struct Csrs {
     pub mcounteren: u64,
     /// Supervisor counter-enable register.
     pub scounteren: u64,
+    /// Machine counter-inhibit register; a set bit freezes the matching counter.
+    pub mcountinhibit: u64,
+    /// Cycles that elapsed while `mcountinhibit.CY` was set, hidden from `mcycle`.
+    pub inhibited_cycles: u64,
+    /// Instructions retired while `mcountinhibit.IR` was set, hidden from `minstret`.
+    pub inhibited_instret: u64,
+    /// Hardware performance counters `mhpmcounter3..31`.
+    pub mhpmcounter: [u64; HPM_COUNTERS],
+    /// Event selectors `mhpmevent3..31` for the matching counters.
+    pub mhpmevent: [u64; HPM_COUNTERS],
+    /// First vector element to process (set on a trap mid-instruction).
+    pub vstart: u64,
+    /// Number of elements a vector instruction processes.
+    pub vl: u64,
+    /// Vector data type (element width, grouping, tail/mask policy).
+    pub vtype: u64,
+    /// Vector register length in bytes.
+    pub vlenb: u64,
 }
 
 impl Csrs {
@@ -412,6 +618,15 @@ impl Csrs {
             MINSTRET => self.minstret,
             MCOUNTEREN => self.mcounteren,
             SCOUNTEREN => self.scounteren,
+            MCOUNTINHIBIT => self.mcountinhibit,
+            MENVCFG => self.menvcfg,
+            MHPMCOUNTER3..=MHPMCOUNTER31 => self.mhpmcounter[(addr - MHPMCOUNTER3) as usize],
+            HPMCOUNTER3..=HPMCOUNTER31 => self.mhpmcounter[(addr - HPMCOUNTER3) as usize],
+            MHPMEVENT3..=MHPMEVENT31 => self.mhpmevent[(addr - MHPMEVENT3) as usize],
+            VSTART => self.vstart,
+            VL => self.vl,
+            VTYPE => self.vtype,
+            VLENB => self.vlenb,
             _ => 0,
         }
     }
@@ -431,22 +646,22 @@ impl Csrs {
                 self.frm = (val >> 5) & 0x7;
             }
             MSTATUS => self.mstatus = val,
-            MISA => self.misa = val,
-            MEDELEG => self.medeleg = val,
-            MIDELEG => self.mideleg = val,
-            MIE => self.mie = val,
-            MTVEC => self.mtvec = val,
+            MISA => self.misa = write_misa(self.misa, val, MISA_WRITABLE),
+            MEDELEG => self.medeleg = val & MEDELEG_WRITABLE,
+            MIDELEG => self.mideleg = val & MIDELEG_WRITABLE,
+            MIE => self.mie = val & MIE_IMPLEMENTED,
+            MTVEC => self.mtvec = legalize_tvec(val),
             MSCRATCH => self.mscratch = val,
             MEPC => self.mepc = val,
-            MCAUSE => self.mcause = val,
+            MCAUSE => self.mcause = legalize_cause(self.mcause, val),
             MTVAL => self.mtval = val,
             MIP => self.mip = val,
             SSTATUS => self.sstatus = val,
             SIE => self.sie = val,
-            STVEC => self.stvec = val,
+            STVEC => self.stvec = legalize_tvec(val),
             SSCRATCH => self.sscratch = val,
             SEPC => self.sepc = val,
-            SCAUSE => self.scause = val,
+            SCAUSE => self.scause = legalize_cause(self.scause, val),
             STVAL => self.stval = val,
             SIP => self.sip = val,
             SATP => {
@@ -466,6 +681,15 @@ impl Csrs {
             MINSTRET => self.minstret = val,
             MCOUNTEREN => self.mcounteren = val,
             SCOUNTEREN => self.scounteren = val,
+            MCOUNTINHIBIT => self.mcountinhibit = val & MCOUNTINHIBIT_MASK,
+            MENVCFG => self.menvcfg = val & MENVCFG_WRITABLE,
+            MHPMCOUNTER3..=MHPMCOUNTER31 => {
+                self.mhpmcounter[(addr - MHPMCOUNTER3) as usize] = val;
+            }
+            MHPMEVENT3..=MHPMEVENT31 => self.mhpmevent[(addr - MHPMEVENT3) as usize] = val,
+            VSTART => self.vstart = val,
+            VL => self.vl = val,
+            VTYPE => self.vtype = val,
             _ => {}
         }
     }
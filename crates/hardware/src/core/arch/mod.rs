@@ -7,6 +7,7 @@
 //! 3. **GPRs:** General-Purpose Register file implementation.
 //! 4. **Modes:** Privilege mode definitions and transitions.
 //! 5. **Traps:** Trap handling and exception processing utilities.
+//! 6. **VPRs:** Vector register file implementation.
 
 /// Control and Status Register (CSR) definitions and access logic.
 pub mod csr;
@@ -22,3 +23,6 @@ pub mod mode;
 
 /// Trap handling and exception processing.
 pub mod trap;
+
+/// Vector register file implementation.
+pub mod vpr;
@@ -2,11 +2,26 @@
 //!
 //! This module provides helper functions for managing processor traps. It performs
 //! the following:
-//! 1. **Interrupt Mapping:** Converts hardware interrupt pending bits into high-level trap types.
+//! 1. **Interrupt Mapping:** Converts between hardware interrupt pending bits and high-level trap types.
 //! 2. **Standardization:** Ensures consistent trap representation across the simulator.
+//! 3. **Priority:** Orders simultaneously pending interrupts.
 
+use crate::common::constants::{
+    DELEG_MEIP_BIT, DELEG_MSIP_BIT, DELEG_MTIP_BIT, DELEG_SEIP_BIT, DELEG_SSIP_BIT, DELEG_STIP_BIT,
+};
 use crate::common::error::Trap;
 
+/// Interrupt codes in the privileged spec's default priority order, highest
+/// first: MEI > MSI > MTI > SEI > SSI > STI.
+pub const STANDARD_INTERRUPT_PRIORITY: [u64; 6] = [
+    DELEG_MEIP_BIT,
+    DELEG_MSIP_BIT,
+    DELEG_MTIP_BIT,
+    DELEG_SEIP_BIT,
+    DELEG_SSIP_BIT,
+    DELEG_STIP_BIT,
+];
+
 /// Trap handler utility functions.
 ///
 /// Provides a unified interface for converting low-level interrupt signals into
@@ -38,4 +53,42 @@ impl TrapHandler {
             _ => Trap::MachineTimerInterrupt,
         }
     }
+
+    /// Converts an interrupt trap to its `MIP` pending bit.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `trap` is a synchronous exception rather than an interrupt.
+    pub fn trap_to_irq(trap: &Trap) -> Option<u64> {
+        use crate::core::arch::csr;
+        Some(match trap {
+            Trap::UserSoftwareInterrupt => csr::MIP_USIP,
+            Trap::SupervisorSoftwareInterrupt => csr::MIP_SSIP,
+            Trap::MachineSoftwareInterrupt => csr::MIP_MSIP,
+            Trap::SupervisorTimerInterrupt => csr::MIP_STIP,
+            Trap::MachineTimerInterrupt => csr::MIP_MTIP,
+            Trap::UserExternalInterrupt => csr::MIP_UEIP,
+            Trap::SupervisorExternalInterrupt => csr::MIP_SEIP,
+            Trap::MachineExternalInterrupt => csr::MIP_MEIP,
+            _ => return None,
+        })
+    }
+
+    /// Builds the order in which simultaneously pending interrupts are
+    /// taken from a requested list of interrupt codes, highest first.
+    ///
+    /// Codes outside the six standard M/S interrupts and repeats are
+    /// dropped, and any standard interrupt the list leaves out is appended
+    /// in its standard relative order, so every interrupt stays reachable.
+    pub fn interrupt_priority(requested: &[u64]) -> [u64; 6] {
+        let mut order = [0; 6];
+        let mut len = 0;
+        for &code in requested.iter().chain(STANDARD_INTERRUPT_PRIORITY.iter()) {
+            if STANDARD_INTERRUPT_PRIORITY.contains(&code) && !order[..len].contains(&code) {
+                order[len] = code;
+                len += 1;
+            }
+        }
+        order
+    }
 }
@@ -0,0 +1,81 @@
+//! RISC-V Vector Register File.
+//!
+//! This module implements the vector register file for the vector extension subset.
+//! It performs the following:
+//! 1. **Storage:** Maintains 32 vector registers (`v0`-`v31`) of `VLEN` bits each.
+//! 2. **Element Access:** Reads and writes 32-bit elements by register and element index.
+//! 3. **Geometry:** Reports `VLEN` in bytes for the `vlenb` CSR.
+
+/// Vector register width in bits when none is configured.
+pub const DEFAULT_VLEN: usize = 128;
+
+/// Smallest supported vector register width in bits (one 32-bit element).
+pub const MIN_VLEN: usize = 32;
+
+/// Bytes per 32-bit vector element.
+const ELEM32_BYTES: usize = 4;
+
+/// Vector register file.
+///
+/// Contains 32 vector registers stored back to back as little-endian bytes,
+/// so that element `i` of `vN` at SEW=32 occupies bytes `4*i..4*i+4` of the
+/// register.
+#[derive(Clone)]
+pub struct Vpr {
+    vlenb: usize,
+    bytes: Vec<u8>,
+}
+
+impl Vpr {
+    /// Creates a new vector register file with all registers initialized to zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `vlen` - Register width in bits. Rounded up to a power of two of at
+    ///   least `MIN_VLEN`, as the specification requires.
+    ///
+    /// # Returns
+    ///
+    /// A new `Vpr` instance with every register set to 0.
+    pub fn new(vlen: usize) -> Self {
+        let vlenb = vlen.max(MIN_VLEN).next_power_of_two() / 8;
+        Self {
+            vlenb,
+            bytes: vec![0; 32 * vlenb],
+        }
+    }
+
+    /// Returns the register width in bytes (`VLEN / 8`).
+    pub fn vlenb(&self) -> usize {
+        self.vlenb
+    }
+
+    /// Reads a 32-bit element of a vector register.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - Vector register index (0-31).
+    /// * `idx` - Element index; must be below `vlenb / 4`.
+    ///
+    /// # Returns
+    ///
+    /// The element value.
+    pub fn read_u32(&self, reg: usize, idx: usize) -> u32 {
+        let off = reg * self.vlenb + idx * ELEM32_BYTES;
+        let mut buf = [0; ELEM32_BYTES];
+        buf.copy_from_slice(&self.bytes[off..off + ELEM32_BYTES]);
+        u32::from_le_bytes(buf)
+    }
+
+    /// Writes a 32-bit element of a vector register.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - Vector register index (0-31).
+    /// * `idx` - Element index; must be below `vlenb / 4`.
+    /// * `val` - The element value to write.
+    pub fn write_u32(&mut self, reg: usize, idx: usize, val: u32) {
+        let off = reg * self.vlenb + idx * ELEM32_BYTES;
+        self.bytes[off..off + ELEM32_BYTES].copy_from_slice(&val.to_le_bytes());
+    }
+}
@@ -0,0 +1,62 @@
+//! Retired-Instruction Assembly Dump.
+//!
+//! Writes the instruction stream in commit order as RISC-V assembly text,
+//! one line per retired instruction, using the disassembler. Each line
+//! carries the PC and raw encoding in a trailing comment so that the dump
+//! can be read alongside a binary or trimmed into a minimized reproducer.
+//!
+//! Compressed instructions are emitted in their expanded 32-bit form (the
+//! pipeline only ever sees the expansion) and tagged `rvc` in the comment.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::isa::disasm::disassemble;
+
+/// Column at which the trailing `# pc` comment starts.
+const COMMENT_COLUMN: usize = 40;
+
+/// Buffered writer for the commit-order assembly dump.
+pub struct AsmDump {
+    /// Destination file.
+    out: BufWriter<File>,
+}
+
+impl AsmDump {
+    /// Creates the dump file at `path` and writes the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Destination file; truncated if it already exists.
+    pub fn create(path: &str) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(
+            out,
+            "# rvsim assembly dump: retired instructions in commit order"
+        )?;
+        writeln!(out, "    .text")?;
+        Ok(Self { out })
+    }
+
+    /// Appends one retired instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - Program counter of the instruction.
+    /// * `inst` - The (expanded) 32-bit encoding that was executed.
+    /// * `inst_size` - Size of the original encoding in bytes (2 or 4).
+    pub fn record(&mut self, pc: u64, inst: u32, inst_size: u64) -> io::Result<()> {
+        let text = disassemble(inst);
+        let rvc = if inst_size == 2 { " rvc" } else { "" };
+        writeln!(
+            self.out,
+            "    {text:<width$}# {pc:#x}: {inst:08x}{rvc}",
+            width = COMMENT_COLUMN
+        )
+    }
+
+    /// Flushes buffered lines to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
@@ -0,0 +1,124 @@
+//! Call-Stack Reconstruction.
+//!
+//! Recovers a best-effort list of return addresses for crash diagnostics. It
+//! performs the following:
+//! 1. **Frame Walk:** Follows the frame-pointer chain from `s0`/`fp`, reading
+//!    the saved `ra` at `fp - 8` and the caller's `fp` at `fp - 16`, as laid
+//!    out by code built with `-fno-omit-frame-pointer`.
+//! 2. **RAS Fallback:** When no frame can be walked (no frame pointers, or
+//!    the stack is not in RAM), reports the return address stack instead.
+//!
+//! Frames are read straight from RAM without touching caches or devices.
+//! Addresses are treated as physical, so the walk is skipped while address
+//! translation is active.
+
+use super::Cpu;
+use crate::core::arch::csr::{SATP_MODE_BARE, SATP_MODE_MASK, SATP_MODE_SHIFT};
+use crate::core::arch::mode::PrivilegeMode;
+use crate::core::units::bru::BranchPredictor;
+use crate::isa::abi;
+
+/// Maximum number of frames reported.
+const BACKTRACE_MAX_DEPTH: usize = 64;
+
+impl Cpu {
+    /// Returns the call stack as return addresses, innermost first.
+    ///
+    /// Walks saved `ra`/`fp` frames when the guest keeps frame pointers,
+    /// otherwise falls back to the return address stack. The walk stops at
+    /// a null, misaligned, non-RAM, or non-ascending frame pointer.
+    pub fn backtrace(&self) -> Vec<u64> {
+        let frames = self.walk_frames();
+        if !frames.is_empty() {
+            return frames;
+        }
+        self.branch_predictor
+            .ras_entries()
+            .iter()
+            .rev()
+            .copied()
+            .collect()
+    }
+
+    /// Formats the backtrace as `#n 0x.. <sym>` lines under a heading.
+    ///
+    /// Returns no lines if the backtrace is empty.
+    pub(crate) fn backtrace_lines(&self) -> Vec<String> {
+        let frames = self.backtrace();
+        if frames.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec!["Backtrace:".to_string()];
+        for (i, addr) in frames.into_iter().enumerate() {
+            lines.push(format!("  #{i:<2} {}", self.describe_pc(addr)));
+        }
+        lines
+    }
+
+    /// Follows the frame-pointer chain through RAM.
+    fn walk_frames(&self) -> Vec<u64> {
+        let mut frames = Vec::new();
+        if self.translation_active() {
+            return frames;
+        }
+        let mut fp = self.regs.read(abi::REG_FP);
+        while frames.len() < BACKTRACE_MAX_DEPTH && fp.is_multiple_of(8) {
+            let (Some(ra), Some(prev_fp)) = (
+                self.peek_ram_u64(fp.wrapping_sub(8)),
+                self.peek_ram_u64(fp.wrapping_sub(16)),
+            ) else {
+                break;
+            };
+            if ra == 0 {
+                break;
+            }
+            frames.push(ra);
+            // The stack grows down, so each caller's frame sits higher.
+            if prev_fp <= fp {
+                break;
+            }
+            fp = prev_fp;
+        }
+        frames
+    }
+
+    /// Returns `true` if data addresses are currently translated by the MMU.
+    fn translation_active(&self) -> bool {
+        let mode = (self.csrs.satp >> SATP_MODE_SHIFT) & SATP_MODE_MASK;
+        !self.direct_mode && self.privilege != PrivilegeMode::Machine && mode != SATP_MODE_BARE
+    }
+
+    /// Reads the instruction at `pc` straight from RAM, or `None` if `pc` is
+    /// translated or not in RAM. Compressed instructions are returned in the
+    /// low halfword.
+    pub(super) fn peek_inst(&self, pc: u64) -> Option<u32> {
+        if self.translation_active() {
+            return None;
+        }
+        let lo = self.peek_ram_u16(pc)? as u32;
+        if lo & 0x3 != 0x3 {
+            return Some(lo);
+        }
+        Some(lo | (self.peek_ram_u16(pc.checked_add(2)?)? as u32) << 16)
+    }
+
+    /// Reads a halfword from RAM, or `None` if it is not entirely in RAM.
+    fn peek_ram_u16(&self, addr: u64) -> Option<u16> {
+        if addr < self.ram_start || addr.checked_add(2)? > self.ram_end {
+            return None;
+        }
+        let offset = (addr - self.ram_start) as usize;
+        // SAFETY: the range was checked against `ram_start..ram_end` above.
+        unsafe { Some((self.ram_ptr.add(offset) as *const u16).read_unaligned()) }
+    }
+
+    /// Reads a doubleword from RAM, or `None` if it is not entirely in RAM.
+    fn peek_ram_u64(&self, addr: u64) -> Option<u64> {
+        if addr < self.ram_start || addr.checked_add(8)? > self.ram_end {
+            return None;
+        }
+        let offset = (addr - self.ram_start) as usize;
+        // SAFETY: the range was checked against `ram_start..ram_end` above.
+        unsafe { Some((self.ram_ptr.add(offset) as *const u64).read_unaligned()) }
+    }
+}
@@ -0,0 +1,215 @@
+//! Architectural Checkpoints.
+//!
+//! Captures everything a program can observe so that a simulation can be
+//! forked at an interesting point (e.g. right after a Linux boot) and resumed
+//! any number of times without re-running from reset. A checkpoint holds:
+//! 1. **Core state:** PC, privilege mode, integer, FP, and vector registers,
+//!    every CSR, and the cycle/instret counters behind `mcycle`/`minstret`.
+//! 2. **Device state:** RAM contents and the registers of each stateful bus
+//!    device (CLINT, PLIC, UART, VirtIO disk image), as opaque blobs.
+//!
+//! Microarchitectural state (caches, predictors, TLBs) and statistics are not
+//! included; see `microarch` for warming those. Checkpoints must be taken and
+//! applied with the pipeline drained, which `Simulator::save_checkpoint` and
+//! `Simulator::restore_checkpoint` take care of.
+//!
+//! # File Format
+//!
+//! `RVSIMCKP` magic and a little-endian `u32` version, followed by the
+//! checkpoint itself encoded with `bincode`. The header is written by hand so
+//! that files from other versions are rejected before decoding.
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::Cpu;
+use crate::core::arch::csr::Csrs;
+use crate::core::arch::mode::PrivilegeMode;
+
+/// File magic identifying a checkpoint.
+const MAGIC: &[u8; 8] = b"RVSIMCKP";
+
+/// Format version; bumped whenever the layout or core state changes.
+const VERSION: u32 = 1;
+
+/// Core architectural state.
+#[derive(Clone, Serialize, Deserialize)]
+struct ArchState {
+    pc: u64,
+    privilege: u8,
+    gpr: Vec<u64>,
+    /// FP registers as raw (NaN-boxed) bit patterns.
+    fpr: Vec<u64>,
+    /// Vector register width in bytes.
+    vlenb: usize,
+    /// Vector registers as 32-bit elements, `v0` first.
+    vregs: Vec<u32>,
+    csrs: Csrs,
+    cycles: u64,
+    instructions_retired: u64,
+    wfi_waiting: bool,
+    wfi_pc: u64,
+    /// Deadline armed through the built-in SBI timer.
+    sbi_timer: u64,
+}
+
+/// A saved architectural state of a CPU and its devices.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    arch: ArchState,
+    /// `(name, base_address, state)` for each stateful bus device.
+    devices: Vec<(String, u64, Vec<u8>)>,
+}
+
+impl Checkpoint {
+    /// Returns the PC execution resumes from.
+    pub fn pc(&self) -> u64 {
+        self.arch.pc
+    }
+
+    /// Writes the checkpoint to a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Destination file; truncated if it already exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        bincode::serialize_into(&mut out, self).map_err(io::Error::other)?;
+        out.flush()
+    }
+
+    /// Reads a checkpoint written by [`Checkpoint::save`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Checkpoint file.
+    ///
+    /// # Returns
+    ///
+    /// The checkpoint, or an `InvalidData` error if the file is not a
+    /// checkpoint of this version.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut input = BufReader::new(File::open(path)?);
+        let mut magic = [0; 8];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not an rvsim checkpoint".to_string()));
+        }
+        let mut version = [0; 4];
+        input.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported checkpoint version {version} (expected {VERSION})"
+            )));
+        }
+        bincode::deserialize_from(input).map_err(|e| invalid_data(e.to_string()))
+    }
+}
+
+impl Cpu {
+    /// Captures the architectural state of the CPU and its devices.
+    ///
+    /// The pipeline must be drained (no in-flight instructions or
+    /// committed-but-undrained stores); `Simulator::save_checkpoint` does
+    /// this before calling here.
+    pub fn save_checkpoint(&self) -> Checkpoint {
+        let vlenb = self.regs.vlenb();
+        let elems = vlenb / 4;
+        let arch = ArchState {
+            pc: self.pc,
+            privilege: self.privilege.to_u8(),
+            gpr: (0..32).map(|i| self.regs.read(i)).collect(),
+            fpr: (0..32).map(|i| self.regs.read_f(i)).collect(),
+            vlenb,
+            vregs: (0..32)
+                .flat_map(|r| (0..elems).map(move |i| (r, i)))
+                .map(|(r, i)| self.regs.read_v32(r, i))
+                .collect(),
+            csrs: self.csrs.clone(),
+            cycles: self.stats.total_cycles(),
+            instructions_retired: self.stats.total_instructions(),
+            wfi_waiting: self.wfi_waiting,
+            wfi_pc: self.wfi_pc,
+            sbi_timer: self.sbi_timer,
+        };
+        Checkpoint {
+            arch,
+            devices: self.bus.bus.save_device_states(),
+        }
+    }
+
+    /// Replaces the architectural state with a checkpoint.
+    ///
+    /// The checkpoint is validated in full before anything is written, so
+    /// on error the CPU and its devices are left untouched. The LR reservation is dropped and the TLBs are flushed, since they may
+    /// hold translations from the abandoned execution. Caches, predictors,
+    /// and statistics other than `cycles`/`instructions_retired` are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `ckpt` - A checkpoint taken on a system with the same memory map,
+    ///   RAM size, and vector length.
+    ///
+    /// # Returns
+    ///
+    /// An error if the checkpoint does not fit this system.
+    pub fn restore_checkpoint(&mut self, ckpt: &Checkpoint) -> Result<(), String> {
+        let arch = &ckpt.arch;
+        if arch.vlenb != self.regs.vlenb() {
+            return Err(format!(
+                "VLEN mismatch: checkpoint has {} bytes, CPU has {}",
+                arch.vlenb,
+                self.regs.vlenb()
+            ));
+        }
+        let elems = arch.vlenb / 4;
+        for (name, regs, expected) in [
+            ("integer", arch.gpr.len(), 32),
+            ("FP", arch.fpr.len(), 32),
+            ("vector", arch.vregs.len(), 32 * elems),
+        ] {
+            if regs != expected {
+                return Err(format!(
+                    "malformed checkpoint: {regs} {name} register words, expected {expected}"
+                ));
+            }
+        }
+        self.bus.bus.restore_device_states(&ckpt.devices)?;
+
+        self.pc = arch.pc;
+        self.privilege = PrivilegeMode::from_u8(arch.privilege);
+        for (i, (&x, &f)) in arch.gpr.iter().zip(&arch.fpr).enumerate() {
+            self.regs.write(i, x);
+            self.regs.write_f(i, f);
+        }
+        for (n, &v) in arch.vregs.iter().enumerate() {
+            self.regs.write_v32(n / elems, n % elems, v);
+        }
+        self.csrs = arch.csrs.clone();
+        self.stats.set_total_cycles(arch.cycles);
+        self.stats.set_total_instructions(arch.instructions_retired);
+        self.wfi_waiting = arch.wfi_waiting;
+        self.wfi_pc = arch.wfi_pc;
+        self.sbi_timer = arch.sbi_timer;
+
+        self.clear_reservation();
+        self.mmu.itlb.flush();
+        self.mmu.dtlb.flush();
+        self.exit_code = None;
+        self.bus
+            .exit_request
+            .store(u64::MAX, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Builds an `InvalidData` error.
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,165 @@
+//! Spike-Compatible Commit Log.
+//!
+//! Writes one line per retired instruction in the format of Spike's
+//! `--log-commits` output, so that a run can be diffed line by line against
+//! the reference simulator. Each line carries:
+//! 1. **Hart and privilege:** `core   0: 3` (3 = M, 1 = S, 0 = U).
+//! 2. **PC and encoding:** the instruction as fetched, so compressed
+//!    instructions appear as their 16-bit parcel.
+//! 3. **Register writes:** integer (`x10 0x...`) and FP (`f1  0x...`)
+//!    destinations, and CSR writes (`c768_mstatus 0x...`). Writes to `x0`
+//!    are omitted, as in Spike.
+//! 4. **Memory accesses:** `mem <addr>` for loads, `mem <addr> <data>` for
+//!    stores; an AMO logs both.
+//!
+//! Trapping instructions do not retire and produce no line.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::core::arch::csr;
+use crate::core::arch::mode::PrivilegeMode;
+use crate::core::pipeline::rob::RobEntry;
+use crate::core::pipeline::signals::{AtomicOp, MemWidth};
+
+/// Buffered writer for the commit log.
+pub struct CommitLog {
+    /// Destination file.
+    out: BufWriter<File>,
+    /// Hart ID printed in the `core N:` prefix.
+    hart: u64,
+}
+
+impl CommitLog {
+    /// Creates the log file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Destination file; truncated if it already exists.
+    /// * `hart` - Hart ID printed at the start of every line.
+    pub fn create(path: &str, hart: u64) -> io::Result<Self> {
+        Ok(Self {
+            out: BufWriter::new(File::create(path)?),
+            hart,
+        })
+    }
+
+    /// Appends the line for one retired instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `privilege` - Privilege mode the instruction executed in.
+    /// * `entry` - The retiring ROB entry; `store_addr` and `store_data`
+    ///   must hold the access address and written data for memory ops.
+    pub fn record(&mut self, privilege: PrivilegeMode, entry: &RobEntry) -> io::Result<()> {
+        let mut line = format!(
+            "core {:>3}: {} 0x{:016x} ({})",
+            self.hart,
+            privilege.to_u8(),
+            entry.pc,
+            format_inst(entry.raw_inst, entry.inst_size)
+        );
+
+        if entry.ctrl.fp_reg_write {
+            line.push_str(&format!(" f{:<2} 0x{:016x}", entry.rd, entry.result));
+        } else if entry.ctrl.reg_write && entry.rd != 0 {
+            line.push_str(&format!(" x{:<2} 0x{:016x}", entry.rd, entry.result));
+        }
+        if let Some(update) = &entry.csr_update {
+            line.push_str(&format!(
+                " c{}_{} 0x{:016x}",
+                update.addr,
+                csr_name(update.addr),
+                update.new_val
+            ));
+        }
+
+        let is_sc = entry.ctrl.atomic_op == AtomicOp::Sc;
+        if entry.ctrl.mem_read && !is_sc {
+            line.push_str(&format!(" mem 0x{:016x}", entry.store_addr));
+        }
+        // A failed SC (rd = 1) writes nothing.
+        if entry.ctrl.mem_write && !(is_sc && entry.result != 0) {
+            let digits = width_bytes(entry.ctrl.width) * 2;
+            let mask = if digits >= 16 {
+                u64::MAX
+            } else {
+                (1u64 << (digits * 4)) - 1
+            };
+            line.push_str(&format!(
+                " mem 0x{:016x} 0x{:0digits$x}",
+                entry.store_addr,
+                entry.store_data & mask
+            ));
+        }
+
+        writeln!(self.out, "{line}")
+    }
+
+    /// Flushes buffered lines to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+/// Formats an encoding the way Spike does: 4 hex digits for a compressed
+/// parcel, 8 otherwise.
+fn format_inst(raw_inst: u32, inst_size: u64) -> String {
+    if inst_size == 2 {
+        format!("0x{:04x}", raw_inst & 0xFFFF)
+    } else {
+        format!("0x{raw_inst:08x}")
+    }
+}
+
+/// Returns the access size in bytes for a memory width.
+fn width_bytes(width: MemWidth) -> usize {
+    match width {
+        MemWidth::Byte => 1,
+        MemWidth::Half => 2,
+        MemWidth::Word => 4,
+        _ => 8,
+    }
+}
+
+/// Returns the Spike name of a CSR, or `unknown_<addr>` for CSRs it does
+/// not name here.
+fn csr_name(addr: u32) -> String {
+    let name = match addr {
+        csr::FFLAGS => "fflags",
+        csr::FRM => "frm",
+        csr::FCSR => "fcsr",
+        csr::VSTART => "vstart",
+        csr::VL => "vl",
+        csr::VTYPE => "vtype",
+        csr::MSTATUS => "mstatus",
+        csr::MISA => "misa",
+        csr::MEDELEG => "medeleg",
+        csr::MIDELEG => "mideleg",
+        csr::MIE => "mie",
+        csr::MTVEC => "mtvec",
+        csr::MCOUNTEREN => "mcounteren",
+        csr::MCOUNTINHIBIT => "mcountinhibit",
+        csr::MENVCFG => "menvcfg",
+        csr::MSCRATCH => "mscratch",
+        csr::MEPC => "mepc",
+        csr::MCAUSE => "mcause",
+        csr::MTVAL => "mtval",
+        csr::MIP => "mip",
+        csr::SSTATUS => "sstatus",
+        csr::SIE => "sie",
+        csr::STVEC => "stvec",
+        csr::SCOUNTEREN => "scounteren",
+        csr::SSCRATCH => "sscratch",
+        csr::SEPC => "sepc",
+        csr::SCAUSE => "scause",
+        csr::STVAL => "stval",
+        csr::SIP => "sip",
+        csr::SATP => "satp",
+        csr::STIMECMP => "stimecmp",
+        csr::MCYCLE => "mcycle",
+        csr::MINSTRET => "minstret",
+        _ => return format!("unknown_{addr:03x}"),
+    };
+    name.to_string()
+}
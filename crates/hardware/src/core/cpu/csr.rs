@@ -11,6 +11,17 @@ use crate::common::Trap;
 use crate::core::arch::csr;
 
 impl Cpu {
+    /// Returns the machine time read through the `time` CSR.
+    ///
+    /// This is the CLINT's `mtime`, so it follows software writes to the
+    /// CLINT; without a CLINT it falls back to cycles over `clint_divider`.
+    pub fn mtime(&self) -> u64 {
+        self.bus
+            .bus
+            .mtime()
+            .unwrap_or_else(|| self.stats.total_cycles() / self.clint_divider)
+    }
+
     /// Reads a value from a Control and Status Register (CSR).
     ///
     /// # Arguments
@@ -28,7 +39,7 @@ impl Cpu {
             csr::MVENDORID => 0,
             csr::MARCHID => 0,
             csr::MIMPID => 0,
-            csr::MHARTID => 0,
+            csr::MHARTID => self.hartid,
             csr::MSTATUS => self.csrs.mstatus,
             csr::MEDELEG => self.csrs.medeleg,
             csr::MIDELEG => self.csrs.mideleg,
@@ -40,6 +51,7 @@ impl Cpu {
             csr::MCAUSE => self.csrs.mcause,
             csr::MTVAL => self.csrs.mtval,
             csr::MIP => self.csrs.mip,
+            csr::MTVAL2 | csr::HTVAL => 0,
             csr::SSTATUS => self.csrs.sstatus,
             csr::SIE => self.csrs.mie & self.csrs.mideleg,
             csr::STVEC => self.csrs.stvec,
@@ -52,9 +64,30 @@ impl Cpu {
             csr::SATP => self.csrs.satp,
             csr::MCOUNTEREN => self.csrs.mcounteren,
             csr::SCOUNTEREN => self.csrs.scounteren,
-            csr::CYCLE | csr::MCYCLE => self.stats.cycles,
-            csr::TIME => self.stats.cycles / self.clint_divider,
-            csr::INSTRET | csr::MINSTRET => self.stats.instructions_retired,
+            csr::MCOUNTINHIBIT => self.csrs.mcountinhibit,
+            csr::MENVCFG => self.csrs.menvcfg,
+            csr::CYCLE | csr::MCYCLE => self
+                .stats
+                .total_cycles()
+                .wrapping_sub(self.csrs.inhibited_cycles),
+            csr::TIME => self.mtime(),
+            csr::INSTRET | csr::MINSTRET => self
+                .stats
+                .total_instructions()
+                .wrapping_sub(self.csrs.inhibited_instret),
+            csr::MHPMCOUNTER3..=csr::MHPMCOUNTER31 => {
+                self.csrs.mhpmcounter[(addr - csr::MHPMCOUNTER3) as usize]
+            }
+            csr::HPMCOUNTER3..=csr::HPMCOUNTER31 => {
+                self.csrs.mhpmcounter[(addr - csr::HPMCOUNTER3) as usize]
+            }
+            csr::MHPMEVENT3..=csr::MHPMEVENT31 => {
+                self.csrs.mhpmevent[(addr - csr::MHPMEVENT3) as usize]
+            }
+            csr::VSTART => self.csrs.vstart,
+            csr::VL => self.csrs.vl,
+            csr::VTYPE => self.csrs.vtype,
+            csr::VLENB => self.csrs.vlenb,
             0x3A0 => {
                 self.pmp.get_cfg(0) as u64
                     | ((self.pmp.get_cfg(1) as u64) << 8)
@@ -80,6 +113,89 @@ impl Cpu {
         }
     }
 
+    /// Returns `true` if `addr` names a CSR this hart implements.
+    ///
+    /// Unimplemented CSRs read as zero and ignore writes; with
+    /// `general.strict_csrs` set, accessing one raises an illegal-instruction
+    /// exception instead. Keep this list in step with `csr_read`.
+    pub fn csr_implemented(&self, addr: u32) -> bool {
+        matches!(
+            addr,
+            csr::FFLAGS
+                | csr::FRM
+                | csr::FCSR
+                | csr::MVENDORID
+                | csr::MARCHID
+                | csr::MIMPID
+                | csr::MHARTID
+                | csr::MSTATUS
+                | csr::MEDELEG
+                | csr::MIDELEG
+                | csr::MIE
+                | csr::MTVEC
+                | csr::MISA
+                | csr::MSCRATCH
+                | csr::MEPC
+                | csr::MCAUSE
+                | csr::MTVAL
+                | csr::MIP
+                | csr::MTVAL2
+                | csr::HTVAL
+                | csr::SSTATUS
+                | csr::SIE
+                | csr::STVEC
+                | csr::SSCRATCH
+                | csr::SEPC
+                | csr::SCAUSE
+                | csr::STVAL
+                | csr::SIP
+                | csr::STIMECMP
+                | csr::SATP
+                | csr::MCOUNTEREN
+                | csr::SCOUNTEREN
+                | csr::MCOUNTINHIBIT
+                | csr::MENVCFG
+                | csr::CYCLE
+                | csr::MCYCLE
+                | csr::TIME
+                | csr::INSTRET
+                | csr::MINSTRET
+                | csr::MHPMCOUNTER3..=csr::MHPMCOUNTER31
+                | csr::HPMCOUNTER3..=csr::HPMCOUNTER31
+                | csr::MHPMEVENT3..=csr::MHPMEVENT31
+                | csr::VSTART
+                | csr::VL
+                | csr::VTYPE
+                | csr::VLENB
+                | csr::CSR_SIM_PANIC
+                | 0x3A0
+                | 0x3A2
+                | 0x3B0..=0x3BF
+        )
+    }
+
+    /// Returns the value a CSR instruction writes to `addr`, given the CSR's
+    /// current value `old`, the computed value `new`, and the address of the
+    /// next instruction.
+    ///
+    /// A `misa` write that would clear C raises IALIGN to 32 bits; if the
+    /// next instruction is not 4-byte aligned the write is suppressed and
+    /// `old` is returned.
+    pub(crate) fn csr_write_value(&self, addr: u32, old: u64, new: u64, next_pc: u64) -> u64 {
+        let clears_c = old & csr::MISA_EXT_C != 0 && self.legal_misa(new) & csr::MISA_EXT_C == 0;
+        if addr == csr::MISA && clears_c && next_pc & 0b11 != 0 {
+            old
+        } else {
+            new
+        }
+    }
+
+    /// Returns `misa` after a write of `val`: only the switchable extensions
+    /// of the configured set follow the write; the base and MXL are fixed.
+    fn legal_misa(&self, val: u64) -> u64 {
+        csr::write_misa(self.csrs.misa, val, csr::MISA_WRITABLE & self.reset_misa)
+    }
+
     /// Writes a value to a Control and Status Register (CSR).
     ///
     /// # Arguments
@@ -88,22 +204,31 @@ impl Cpu {
     /// * `val` - The 64-bit value to write to the register.
     pub fn csr_write(&mut self, addr: u32, val: u64) {
         match addr {
-            csr::FFLAGS => self.csrs.fflags = val & 0x1F,
-            csr::FRM => self.csrs.frm = val & 0x7,
+            csr::FFLAGS => {
+                self.csrs.fflags = val & 0x1F;
+                self.set_fs_dirty();
+            }
+            csr::FRM => {
+                self.csrs.frm = val & 0x7;
+                self.set_fs_dirty();
+            }
             csr::FCSR => {
                 self.csrs.fflags = val & 0x1F;
                 self.csrs.frm = (val >> 5) & 0x7;
+                self.set_fs_dirty();
             }
             csr::CSR_SIM_PANIC => {
                 self.trap(Trap::RequestedTrap(val), self.pc);
             }
             csr::MSTATUS => {
-                // WARL: preserve UXL and SXL (bits 35:32) — always 2 (64-bit) on RV64.
-                let uxl_sxl_mask: u64 = 0xF << 32;
-                let preserved = self.csrs.mstatus & uxl_sxl_mask;
-                self.csrs.mstatus = (val & !uxl_sxl_mask) | preserved;
+                // WARL: preserve UXL and SXL (bits 35:32) — always 2 (64-bit) on RV64 —
+                // and the endianness bits, which are fixed by the configuration.
+                let fixed_mask: u64 = (0xF << 32) | csr::MSTATUS_BE;
+                let preserved = self.csrs.mstatus & fixed_mask;
+                self.csrs.mstatus = (val & !fixed_mask) | preserved;
 
                 let mask = csr::MSTATUS_SIE
+                    | csr::MSTATUS_UBE
                     | csr::MSTATUS_SPIE
                     | csr::MSTATUS_SPP
                     | csr::MSTATUS_FS
@@ -112,21 +237,23 @@ impl Cpu {
                     | csr::MSTATUS_UXL;
                 self.csrs.sstatus = self.csrs.mstatus & mask;
             }
-            csr::MEDELEG => self.csrs.medeleg = val,
-            csr::MIDELEG => self.csrs.mideleg = val,
+            csr::MEDELEG => self.csrs.medeleg = val & csr::MEDELEG_WRITABLE,
+            csr::MIDELEG => self.csrs.mideleg = val & csr::MIDELEG_WRITABLE,
             csr::MIE => {
-                self.csrs.mie = val;
-            }
-            csr::MTVEC => self.csrs.mtvec = val,
-            csr::MISA => {
-                // MISA is WARL: writes are silently ignored (extensions are hardwired).
+                self.csrs.mie = val & csr::MIE_IMPLEMENTED;
             }
+            csr::MTVEC => self.csrs.mtvec = csr::legalize_tvec(val),
+            csr::MISA => self.csrs.misa = self.legal_misa(val),
             csr::MSCRATCH => self.csrs.mscratch = val,
             csr::MEPC => self.csrs.mepc = val & !1,
-            csr::MCAUSE => self.csrs.mcause = val,
+            csr::MCAUSE => self.csrs.mcause = csr::legalize_cause(self.csrs.mcause, val),
             csr::MTVAL => self.csrs.mtval = val,
             csr::MIP => {
-                let mask = csr::MIP_SSIP | csr::MIP_STIP | csr::MIP_SEIP;
+                // With Sstc enabled, STIP follows `stimecmp` and is read-only.
+                let mut mask = csr::MIP_SSIP | csr::MIP_STIP | csr::MIP_SEIP;
+                if self.sstc_enabled() {
+                    mask &= !csr::MIP_STIP;
+                }
                 self.csrs.mip = (self.csrs.mip & !mask) | (val & mask);
             }
             csr::SSTATUS => {
@@ -137,7 +264,7 @@ impl Cpu {
                     | csr::MSTATUS_FS
                     | csr::MSTATUS_SUM
                     | csr::MSTATUS_MXR;
-                let read_mask = writable_mask | csr::MSTATUS_UXL;
+                let read_mask = writable_mask | csr::MSTATUS_UXL | csr::MSTATUS_UBE;
 
                 self.csrs.mstatus = (self.csrs.mstatus & !writable_mask) | (val & writable_mask);
                 self.csrs.sstatus = self.csrs.mstatus & read_mask;
@@ -147,20 +274,48 @@ impl Cpu {
                 self.csrs.mie = (self.csrs.mie & !mask) | (val & mask);
             }
             csr::STVEC => {
-                self.csrs.stvec = val;
+                self.csrs.stvec = csr::legalize_tvec(val);
             }
             csr::SSCRATCH => self.csrs.sscratch = val,
             csr::SEPC => self.csrs.sepc = val & !1,
-            csr::SCAUSE => self.csrs.scause = val,
+            csr::SCAUSE => self.csrs.scause = csr::legalize_cause(self.csrs.scause, val),
             csr::STVAL => self.csrs.stval = val,
             csr::SIP => {
                 let mask = self.csrs.mideleg & (csr::MIP_SSIP);
                 self.csrs.mip = (self.csrs.mip & !mask) | (val & mask);
             }
-            csr::MCOUNTEREN => self.csrs.mcounteren = val,
-            csr::SCOUNTEREN => self.csrs.scounteren = val,
-            csr::MCYCLE => self.stats.cycles = val,
-            csr::MINSTRET => self.stats.instructions_retired = val,
+            // The counter-enable registers are 32 bits wide.
+            csr::MCOUNTEREN => self.csrs.mcounteren = val & 0xFFFF_FFFF,
+            csr::SCOUNTEREN => self.csrs.scounteren = val & 0xFFFF_FFFF,
+            csr::MCOUNTINHIBIT => self.csrs.mcountinhibit = val & csr::MCOUNTINHIBIT_MASK,
+            csr::MENVCFG => {
+                self.csrs.menvcfg = val & csr::MENVCFG_WRITABLE;
+                self.update_stip();
+            }
+            csr::MCYCLE => self
+                .stats
+                .set_total_cycles(val.wrapping_add(self.csrs.inhibited_cycles)),
+            csr::MINSTRET => self
+                .stats
+                .set_total_instructions(val.wrapping_add(self.csrs.inhibited_instret)),
+            csr::MHPMCOUNTER3..=csr::MHPMCOUNTER31 => {
+                self.csrs.mhpmcounter[(addr - csr::MHPMCOUNTER3) as usize] = val;
+            }
+            csr::MHPMEVENT3..=csr::MHPMEVENT31 => {
+                // WARL: unsupported event codes read back as "no event".
+                let event = if (val as usize) < csr::HPM_EVENT_COUNT {
+                    val
+                } else {
+                    csr::HPM_EVENT_NONE
+                };
+                self.csrs.mhpmevent[(addr - csr::MHPMEVENT3) as usize] = event;
+            }
+            // WARL: vstart holds any element index of a VLEN-bit register.
+            csr::VSTART => self.csrs.vstart = val & (self.csrs.vlenb * 8 - 1),
+            // vl/vtype are read-only to CSR instructions; these writes come
+            // from vsetvl{i} at commit.
+            csr::VL => self.csrs.vl = val,
+            csr::VTYPE => self.csrs.vtype = val,
             0x3A0 => {
                 for i in 0..8 {
                     self.pmp.set_cfg(i, ((val >> (i * 8)) & 0xFF) as u8);
@@ -176,7 +331,7 @@ impl Cpu {
             }
             csr::STIMECMP => {
                 self.csrs.stimecmp = val;
-                self.csrs.mip &= !csr::MIP_STIP;
+                self.update_stip();
             }
             csr::SATP => {
                 let mode = (val >> csr::SATP_MODE_SHIFT) & csr::SATP_MODE_MASK;
@@ -194,10 +349,48 @@ impl Cpu {
                 self.l1_i_cache.flush();
                 self.l1_d_cache.flush();
 
-                self.mmu.dtlb.flush();
-                self.mmu.itlb.flush();
+                // TLB entries are tagged with their ASID, so switching
+                // address spaces needs no flush; software fences with
+                // SFENCE.VMA when it reuses an ASID or edits page tables.
             }
             _ => {}
         }
     }
+
+    /// Returns `true` if `menvcfg.STCE` enables the Sstc extension.
+    pub fn sstc_enabled(&self) -> bool {
+        self.csrs.menvcfg & csr::MENVCFG_STCE != 0
+    }
+
+    /// Returns the `mtime` value at which hardware raises `mip.STIP`:
+    /// `stimecmp` under Sstc, else the built-in SBI timer when enabled.
+    /// `None` leaves STIP to M-mode software.
+    pub(crate) fn stip_deadline(&self) -> Option<u64> {
+        if self.sstc_enabled() {
+            Some(self.csrs.stimecmp)
+        } else if self.sbi {
+            Some(self.sbi_timer)
+        } else {
+            None
+        }
+    }
+
+    /// Recomputes `mip.STIP` from [`Cpu::stip_deadline`]. Without a
+    /// deadline, STIP is left to M-mode software.
+    pub(crate) fn update_stip(&mut self) {
+        let Some(deadline) = self.stip_deadline() else {
+            return;
+        };
+        if self.mtime() >= deadline {
+            self.csrs.mip |= csr::MIP_STIP;
+        } else {
+            self.csrs.mip &= !csr::MIP_STIP;
+        }
+    }
+
+    /// Marks the FP state dirty in `mstatus` and `sstatus`.
+    pub(crate) fn set_fs_dirty(&mut self) {
+        self.csrs.mstatus = (self.csrs.mstatus & !csr::MSTATUS_FS) | csr::MSTATUS_FS_DIRTY;
+        self.csrs.sstatus = (self.csrs.sstatus & !csr::MSTATUS_FS) | csr::MSTATUS_FS_DIRTY;
+    }
 }
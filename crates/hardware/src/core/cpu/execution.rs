@@ -7,13 +7,16 @@
 //! 4. **Observability:** Provides tracing and pipeline visualization for debugging.
 
 use super::Cpu;
+use crate::common::Trap;
 use crate::common::constants::{
     DEBUG_PC_END, DEBUG_PC_START, HANG_DETECTION_THRESHOLD, PAGE_OFFSET_MASK, PAGE_SHIFT,
     STATUS_UPDATE_INTERVAL, VPN_MASK, WFI_INSTRUCTION,
 };
 use crate::core::arch::csr;
 use crate::core::arch::mode::PrivilegeMode;
-use crate::isa::abi;
+use crate::core::arch::trap::TrapHandler;
+use crate::core::units::mmu::satp_asid;
+use crate::isa::{abi, disasm};
 
 impl Cpu {
     /// Pre-tick: exit checks, interrupts, timers, cycle counting.
@@ -32,6 +35,14 @@ impl Cpu {
             return Ok(true);
         }
 
+        if self.max_same_pc > 0 && self.same_commit_pc_count >= self.max_same_pc {
+            return Err(format!(
+                "PC stuck at {:#x}: {} consecutive instructions retired without advancing \
+                 and no interrupt pending (general.max_same_pc = {})",
+                self.last_commit_pc, self.same_commit_pc_count, self.max_same_pc
+            ));
+        }
+
         #[allow(clippy::absurd_extreme_comparisons)]
         if self.pc >= DEBUG_PC_START && self.pc <= DEBUG_PC_END {
             self.trace = true;
@@ -40,11 +51,12 @@ impl Cpu {
         if self.pc == self.last_pc {
             self.same_pc_count += 1;
             if self.same_pc_count == HANG_DETECTION_THRESHOLD {
-                let inst = if let Some((ppn, _, _, _, _, _)) =
-                    self.mmu.dtlb.lookup((self.pc >> PAGE_SHIFT) & VPN_MASK)
-                {
+                let inst = if let Some((ppn, _, _, _, _, _)) = self.mmu.dtlb.lookup(
+                    (self.pc >> PAGE_SHIFT) & VPN_MASK,
+                    satp_asid(self.csrs.satp),
+                ) {
                     let paddr = (ppn << PAGE_SHIFT) | (self.pc & PAGE_OFFSET_MASK);
-                    self.bus.bus.read_u32(paddr)
+                    self.bus.bus.read_u32(paddr).unwrap_or(0)
                 } else {
                     0
                 };
@@ -57,8 +69,10 @@ impl Cpu {
                         );
                     } else {
                         println!(
-                            "\n[CPU] POTENTIAL HANG: Stuck at PC {:#x} (Inst: {:#010x})",
-                            self.pc, inst
+                            "\n[CPU] POTENTIAL HANG: Stuck at PC {:#x} (Inst: {:#010x} {})",
+                            self.pc,
+                            inst,
+                            disasm(inst)
                         );
                     }
                 }
@@ -68,15 +82,21 @@ impl Cpu {
             self.same_pc_count = 0;
         }
 
-        let (timer_irq, meip, seip) = self.bus.tick();
+        let (clint, meip, seip) = self.bus.tick();
+        let hart = self.hartid as usize;
 
         let mut mip = self.csrs.mip;
 
-        if timer_irq {
+        if clint.mtip(hart) {
             mip |= csr::MIP_MTIP;
         } else {
             mip &= !csr::MIP_MTIP;
         }
+        if clint.msip(hart) {
+            mip |= csr::MIP_MSIP;
+        } else {
+            mip &= !csr::MIP_MSIP;
+        }
 
         if meip {
             mip |= csr::MIP_MEIP;
@@ -89,15 +109,25 @@ impl Cpu {
             mip &= !csr::MIP_SEIP;
         }
 
-        let mtime = self.stats.cycles / self.clint_divider;
-        if self.csrs.stimecmp > 0 {
-            if mtime >= self.csrs.stimecmp {
+        // Sstc: with menvcfg.STCE set, STIP is the `mtime >= stimecmp`
+        // comparator (or the SBI timer's, with built-in SBI); otherwise it
+        // is left to M-mode software.
+        if let Some(deadline) = self.stip_deadline() {
+            if self.mtime() >= deadline {
                 mip |= csr::MIP_STIP;
             } else {
                 mip &= !csr::MIP_STIP;
             }
         }
 
+        while let Some(&(at, bit)) = self.injected_irqs.first()
+            && self.stats.total_instructions() >= at
+        {
+            self.injected_mip |= bit;
+            let _ = self.injected_irqs.remove(0);
+        }
+        mip |= self.injected_mip;
+
         self.csrs.mip = mip;
 
         if self.trace {
@@ -105,14 +135,66 @@ impl Cpu {
         }
 
         self.stats.cycles += 1;
+        if self.csrs.mcountinhibit & csr::MCOUNTINHIBIT_CY != 0 {
+            self.csrs.inhibited_cycles += 1;
+        }
+        if self.wfi_waiting {
+            self.stats.cycles_wfi += 1;
+        }
         self.track_mode_cycles();
 
         Ok(false)
     }
 
+    /// Schedules `interrupt` to become pending once `inst_count` instructions
+    /// have retired, independent of CLINT and PLIC timing.
+    ///
+    /// The interrupt is raised before the next instruction retires and stays
+    /// pending until it is taken, so whether and when it is delivered still
+    /// follows `mie`, `mstatus`, and delegation. A count already reached
+    /// raises it on the next cycle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `interrupt` is a synchronous exception.
+    pub fn inject_interrupt_at(&mut self, inst_count: u64, interrupt: Trap) -> Result<(), String> {
+        let bit = TrapHandler::trap_to_irq(&interrupt)
+            .ok_or_else(|| format!("{interrupt} is not an interrupt"))?;
+        let idx = self
+            .injected_irqs
+            .partition_point(|&(at, _)| at <= inst_count);
+        self.injected_irqs.insert(idx, (inst_count, bit));
+        Ok(())
+    }
+
+    /// Returns `true` if an injected interrupt is due at the current
+    /// instruction count, so commit stops before retiring past it.
+    pub(crate) fn injected_irq_due(&self) -> bool {
+        self.injected_irqs
+            .first()
+            .is_some_and(|&(at, _)| self.stats.total_instructions() >= at)
+    }
+
+    /// Records a retired PC for the same-PC watchdog.
+    ///
+    /// Consecutive retirements at one PC count toward `max_same_pc` only
+    /// while no enabled interrupt is pending; a spin that is about to be
+    /// interrupted is not a hang.
+    pub(crate) fn watch_commit_pc(&mut self, pc: u64) {
+        if pc != self.last_commit_pc {
+            self.last_commit_pc = pc;
+            self.same_commit_pc_count = 1;
+        } else if self.csrs.mip & self.csrs.mie != 0 {
+            self.same_commit_pc_count = 0;
+        } else {
+            self.same_commit_pc_count += 1;
+        }
+    }
+
     /// Post-tick: zero x0, privilege tracing, status printing.
     pub fn post_tick(&mut self, prev_priv: PrivilegeMode) {
         self.regs.write(abi::REG_ZERO, 0);
+        self.hpm_tick();
 
         if self.trace {
             if self.privilege != prev_priv {
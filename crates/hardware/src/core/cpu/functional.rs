@@ -0,0 +1,572 @@
+//! Functional (No-Timing) Execution.
+//!
+//! Fast-forward mode for reaching a region of interest quickly. Each tick
+//! executes exactly one instruction to completion against the architectural
+//! state, bypassing the pipeline latches, caches, and branch predictors:
+//! 1. **Fetch:** Translates the PC and reads the instruction straight from
+//!    RAM or the bus, expanding compressed encodings.
+//! 2. **Execute:** Reuses the pipeline's decoder, ALU, and CSR/branch helpers
+//!    so that results match detailed mode bit for bit.
+//! 3. **Memory:** Loads, stores, and atomics access memory immediately with
+//!    no store buffer and no `simulate_memory_access`.
+//! 4. **Retire:** Updates registers, CSRs, and `instructions_retired`, and
+//!    feeds the PC trace, assembly dump, and commit log.
+//!
+//! Cycles and devices still advance once per tick, so timers keep running.
+//! `Simulator::tick` drains the pipeline before the first functional step;
+//! switching back to detailed mode resumes fetch from `cpu.pc`.
+
+use super::{Cpu, PC_TRACE_MAX};
+use crate::common::constants::{
+    COMPRESSED_INSTRUCTION_MASK, COMPRESSED_INSTRUCTION_VALUE, INSTRUCTION_SIZE_16,
+    INSTRUCTION_SIZE_32, OPCODE_MASK,
+};
+use crate::common::{AccessType, BusError, Trap, VirtAddr};
+use crate::core::arch::csr;
+use crate::core::arch::mode::PrivilegeMode;
+use crate::core::pipeline::backend::inorder::execute::{
+    branch_taken, compute_alu, csr_access_allowed, csr_new_value,
+};
+use crate::core::pipeline::backend::shared::commit::{check_interrupts, write_store};
+use crate::core::pipeline::frontend::decode::decode_instruction;
+use crate::core::pipeline::rob::{CsrUpdate, RobEntry};
+use crate::core::pipeline::signals::{
+    AtomicOp, ControlSignals, CsrOp, MemWidth, OpASrc, OpBSrc, VectorOp,
+};
+use crate::core::units::lsu::Lsu;
+use crate::core::units::lsu::unaligned::{is_aligned, split_load, width_to_bytes};
+use crate::isa::decode::decode as instruction_decode;
+use crate::isa::instruction::InstructionBits;
+use crate::isa::privileged::opcodes as sys_ops;
+use crate::isa::rv64i::opcodes;
+use crate::isa::rvc::expand::expand;
+
+/// A fetched and decoded instruction with its source operands.
+struct FunctionalInst {
+    pc: u64,
+    inst: u32,
+    raw_inst: u32,
+    inst_size: u64,
+    rs1: usize,
+    rd: usize,
+    imm: i64,
+    ctrl: ControlSignals,
+    rv1: u64,
+    rv2: u64,
+    rv3: u64,
+}
+
+/// Architectural effects of an instruction, applied at retirement.
+#[derive(Default)]
+struct Outcome {
+    /// Value for rd (the old CSR value, `vl`, load data, or link address).
+    result: u64,
+    next_pc: u64,
+    csr_update: Option<CsrUpdate>,
+    /// Virtual address and written data of a memory access, for the commit log.
+    mem_addr: u64,
+    mem_data: u64,
+}
+
+impl Cpu {
+    /// Enables or disables functional (no-timing) execution.
+    ///
+    /// While enabled, `Simulator::tick` executes one instruction per tick
+    /// without modeling the pipeline, caches, or branch predictors, and only
+    /// `instructions_retired` (plus `cycles`) advances among the statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to fast-forward, `false` for detailed simulation.
+    pub fn set_functional(&mut self, enabled: bool) {
+        self.functional = enabled;
+    }
+
+    /// Returns `true` if functional execution is enabled.
+    pub fn is_functional(&self) -> bool {
+        self.functional
+    }
+
+    /// Executes a single instruction functionally.
+    ///
+    /// Interrupts are taken and WFI is honored exactly as at commit in
+    /// detailed mode. The pipeline must be empty.
+    pub(crate) fn step_functional(&mut self) {
+        if let Some(interrupt) = check_interrupts(self) {
+            let epc = if self.wfi_waiting {
+                self.wfi_pc
+            } else {
+                self.pc
+            };
+            self.wfi_waiting = false;
+            self.trap(interrupt, epc);
+            return;
+        }
+        if self.wfi_waiting {
+            if (self.csrs.mip & self.csrs.mie) == 0 {
+                return;
+            }
+            self.wfi_waiting = false;
+            self.pc = self.wfi_pc;
+        }
+
+        let pc = self.pc;
+        if let Err(trap) = self.execute_functional(pc) {
+            if self.trace {
+                eprintln!("FN  pc={:#x} * TRAP: {:?}", pc, trap);
+            }
+            self.trap(trap, pc);
+        }
+    }
+
+    /// Fetches, executes, and retires the instruction at `pc`.
+    fn execute_functional(&mut self, pc: u64) -> Result<(), Trap> {
+        let (inst, raw_inst, inst_size) = self.fetch_functional(pc)?;
+        let d = instruction_decode(inst);
+        let ctrl = decode_instruction(inst, pc, &d)?;
+
+        let rs3 = inst.rs3();
+        let op = FunctionalInst {
+            pc,
+            inst,
+            raw_inst,
+            inst_size,
+            rs1: d.rs1,
+            rd: d.rd,
+            imm: d.imm,
+            ctrl,
+            rv1: if ctrl.rs1_fp {
+                self.regs.read_f(d.rs1)
+            } else {
+                self.regs.read(d.rs1)
+            },
+            rv2: if ctrl.rs2_fp {
+                self.regs.read_f(d.rs2)
+            } else {
+                self.regs.read(d.rs2)
+            },
+            rv3: if ctrl.rs3_fp {
+                self.regs.read_f(rs3)
+            } else {
+                0
+            },
+        };
+
+        let outcome = match self.execute_system_functional(&op)? {
+            Some(outcome) => outcome,
+            None => self.execute_op_functional(&op)?,
+        };
+        self.retire_functional(&op, outcome);
+        Ok(())
+    }
+
+    /// Reads the instruction at `pc`, returning `(inst, raw_inst, size)`.
+    fn fetch_functional(&mut self, pc: u64) -> Result<(u32, u32, u64), Trap> {
+        let align_mask: u64 = if (self.csrs.misa & csr::MISA_EXT_C) != 0 {
+            1
+        } else {
+            3
+        };
+        if (pc & align_mask) != 0 {
+            return Err(Trap::InstructionAddressMisaligned(pc));
+        }
+
+        let paddr = self.translate_functional(pc, AccessType::Fetch)?;
+        let half_word = self
+            .read_phys(paddr, MemWidth::Half, false)
+            .map_err(|_| Trap::InstructionAccessFault(pc))? as u16;
+
+        if (half_word & COMPRESSED_INSTRUCTION_MASK) != COMPRESSED_INSTRUCTION_VALUE {
+            let expanded = expand(half_word);
+            if expanded == 0 {
+                return Err(Trap::IllegalInstruction(half_word as u32));
+            }
+            return Ok((expanded, half_word as u32, INSTRUCTION_SIZE_16));
+        }
+
+        let upper_va = pc.wrapping_add(2);
+        let upper_paddr = if (pc >> 12) != (upper_va >> 12) {
+            self.translate_functional(upper_va, AccessType::Fetch)?
+        } else {
+            paddr + 2
+        };
+        let upper_half = self
+            .read_phys(upper_paddr, MemWidth::Half, false)
+            .map_err(|_| Trap::InstructionAccessFault(upper_va))? as u16;
+        let inst = (upper_half as u32) << 16 | (half_word as u32);
+        Ok((inst, inst, INSTRUCTION_SIZE_32))
+    }
+
+    /// Handles system instructions (xRET, WFI, SFENCE.VMA, ECALL, CSR).
+    ///
+    /// Returns `None` for anything else, including non-system instructions.
+    fn execute_system_functional(&mut self, op: &FunctionalInst) -> Result<Option<Outcome>, Trap> {
+        if !op.ctrl.is_system {
+            return Ok(None);
+        }
+        let next_pc = op.pc.wrapping_add(op.inst_size);
+        let done = Outcome {
+            next_pc,
+            ..Default::default()
+        };
+        let illegal = Trap::IllegalInstruction(op.inst);
+
+        if op.ctrl.is_mret {
+            return Ok(Some(done));
+        }
+        if op.ctrl.is_sret {
+            let tsr = (self.csrs.mstatus >> 22) & 1;
+            if self.privilege == PrivilegeMode::Supervisor && tsr != 0 {
+                return Err(illegal);
+            }
+            return Ok(Some(done));
+        }
+        if op.inst == sys_ops::WFI {
+            let tw = (self.csrs.mstatus >> 21) & 1;
+            if self.privilege == PrivilegeMode::User
+                || (self.privilege == PrivilegeMode::Supervisor && tw != 0)
+            {
+                return Err(illegal);
+            }
+            if self.csrs.mie != 0 || self.csrs.mip != 0 {
+                self.wfi_waiting = true;
+                self.wfi_pc = next_pc;
+            }
+            return Ok(Some(done));
+        }
+        if (op.inst & 0xFE007FFF) == sys_ops::SFENCE_VMA {
+            let tvm = (self.csrs.mstatus >> 20) & 1;
+            if self.privilege == PrivilegeMode::Supervisor && tvm != 0 {
+                return Err(illegal);
+            }
+            self.clear_reservation();
+            self.mmu.sfence_vma(
+                (op.rs1 != 0).then_some(op.rv1),
+                (op.inst.rs2() != 0).then_some(op.rv2 as u16),
+            );
+            return Ok(Some(done));
+        }
+        if op.inst == sys_ops::ECALL {
+            return Err(match self.privilege {
+                PrivilegeMode::User => Trap::EnvironmentCallFromUMode,
+                PrivilegeMode::Supervisor => Trap::EnvironmentCallFromSMode,
+                PrivilegeMode::Machine => Trap::EnvironmentCallFromMMode,
+            });
+        }
+        if op.ctrl.csr_op != CsrOp::None {
+            if !csr_access_allowed(self, &op.ctrl, op.rs1) {
+                return Err(illegal);
+            }
+            let old = self.csr_read(op.ctrl.csr_addr);
+            let new = self.csr_write_value(
+                op.ctrl.csr_addr,
+                old,
+                csr_new_value(op.ctrl.csr_op, old, op.rs1, op.rv1),
+                op.pc.wrapping_add(op.inst_size),
+            );
+            return Ok(Some(Outcome {
+                result: old,
+                csr_update: Some(CsrUpdate {
+                    addr: op.ctrl.csr_addr,
+                    old_val: old,
+                    new_val: new,
+                }),
+                ..done
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Executes FP, vector, ALU, branch, jump, and memory instructions.
+    fn execute_op_functional(&mut self, op: &FunctionalInst) -> Result<Outcome, Trap> {
+        let ctrl = &op.ctrl;
+        let mut outcome = Outcome {
+            next_pc: op.pc.wrapping_add(op.inst_size),
+            ..Default::default()
+        };
+
+        let fs = (self.csrs.mstatus & csr::MSTATUS_FS) >> 13;
+        if fs == 0 && (ctrl.fp_reg_write || ctrl.rs1_fp || ctrl.rs2_fp || ctrl.rs3_fp) {
+            return Err(Trap::IllegalInstruction(op.inst));
+        }
+
+        let op_a = match ctrl.a_src {
+            OpASrc::Reg1 => op.rv1,
+            OpASrc::Pc => op.pc,
+            OpASrc::Zero => 0,
+        };
+        let op_b = match ctrl.b_src {
+            OpBSrc::Reg2 => op.rv2,
+            OpBSrc::Imm => op.imm as u64,
+            OpBSrc::Zero => 0,
+        };
+
+        if ctrl.vector_op != VectorOp::None {
+            if ctrl.vector_op == VectorOp::SetVl {
+                let (vl, vtype) = self.vsetvl(op.rs1, op.rd, op_a, op_b);
+                outcome.result = vl;
+                outcome.csr_update = Some(CsrUpdate {
+                    addr: csr::VTYPE,
+                    old_val: self.csrs.vtype,
+                    new_val: vtype,
+                });
+            } else if self.vector_illegal() {
+                return Err(Trap::IllegalInstruction(op.inst));
+            }
+            return Ok(outcome);
+        }
+
+        let (alu, fp_flags) = compute_alu(ctrl.alu, op_a, op_b, op.rv3, ctrl.is_rv32);
+        if fp_flags != 0 {
+            self.csrs.fflags |= fp_flags as u64;
+            self.set_fs_dirty();
+        }
+        outcome.result = alu;
+
+        if ctrl.branch && branch_taken(op.inst, op_a, op_b) {
+            outcome.next_pc = op.pc.wrapping_add(op.imm as u64);
+        }
+        if ctrl.jump {
+            outcome.next_pc = if (op.inst & OPCODE_MASK) == opcodes::OP_JALR {
+                op.rv1.wrapping_add(op.imm as u64) & !1
+            } else {
+                op.pc.wrapping_add(op.imm as u64)
+            };
+            outcome.result = op.pc.wrapping_add(op.inst_size);
+        }
+
+        if ctrl.mem_read || ctrl.mem_write {
+            let access = if ctrl.mem_write {
+                AccessType::Write
+            } else {
+                AccessType::Read
+            };
+            let paddr = self.translate_functional(alu, access)?;
+            if let Some(trap) = self.check_misaligned(
+                alu,
+                paddr,
+                width_to_bytes(ctrl.width),
+                access,
+                ctrl.atomic_op != AtomicOp::None,
+            ) {
+                return Err(trap);
+            }
+            outcome.mem_addr = alu;
+            outcome.mem_data = op.rv2;
+            self.bus.bus.set_access_pc(self.pc);
+            outcome.result = self
+                .access_memory_functional(ctrl, paddr, op.rv2, &mut outcome)
+                .map_err(|_| Trap::access_fault(access, alu))?;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Performs a load, store, or atomic at `paddr` and returns the value
+    /// for rd (0 for plain stores).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `BusError` if `paddr` is not backed by a device.
+    fn access_memory_functional(
+        &mut self,
+        ctrl: &ControlSignals,
+        paddr: u64,
+        store_data: u64,
+        outcome: &mut Outcome,
+    ) -> Result<u64, BusError> {
+        let width = ctrl.width;
+        match ctrl.atomic_op {
+            AtomicOp::None => {}
+            AtomicOp::Lr => {
+                let val = self.read_data(paddr, width, true)?;
+                self.set_reservation(paddr);
+                return Ok(val);
+            }
+            AtomicOp::Sc => {
+                let success = self.check_reservation(paddr);
+                self.clear_reservation();
+                if success {
+                    write_store(self, paddr, width, self.store_value(store_data, width))?;
+                }
+                return Ok(if success { 0 } else { 1 });
+            }
+            amo => {
+                let old = self.read_data(paddr, width, true)?;
+                let new = Lsu::atomic_alu(amo, old, store_data, width);
+                write_store(self, paddr, width, self.store_value(new, width))?;
+                self.snoop_store(paddr, width_to_bytes(width));
+                outcome.mem_data = new;
+                return Ok(old);
+            }
+        }
+
+        if ctrl.mem_read {
+            let mut ld = self.read_data(paddr, width, ctrl.signed_load)?;
+            if ctrl.fp_reg_write && matches!(width, MemWidth::Word) {
+                ld |= 0xFFFF_FFFF_0000_0000;
+            }
+            Ok(ld)
+        } else {
+            write_store(self, paddr, width, self.store_value(store_data, width))?;
+            self.snoop_store(paddr, width_to_bytes(width));
+            Ok(0)
+        }
+    }
+
+    /// Applies an instruction's architectural effects and retires it.
+    fn retire_functional(&mut self, op: &FunctionalInst, outcome: Outcome) {
+        let ctrl = &op.ctrl;
+
+        self.pc_trace.push((op.pc, op.inst));
+        if self.pc_trace.len() > PC_TRACE_MAX {
+            self.pc_trace.remove(0);
+        }
+        if let Some(dump) = &mut self.asm_dump {
+            let _ = dump.record(op.pc, op.inst, op.inst_size);
+        }
+        if let Some(log) = &mut self.commit_log {
+            let entry = RobEntry {
+                pc: op.pc,
+                inst: op.inst,
+                raw_inst: op.raw_inst,
+                inst_size: op.inst_size,
+                rd: op.rd,
+                rd_fp: ctrl.fp_reg_write,
+                result: outcome.result,
+                store_data: outcome.mem_data,
+                store_addr: outcome.mem_addr,
+                ctrl: *ctrl,
+                csr_update: outcome.csr_update.clone(),
+                ..Default::default()
+            };
+            let _ = log.record(self.privilege, &entry);
+        }
+        self.record_retired(1);
+        self.watch_commit_pc(op.pc);
+
+        if ctrl.fp_reg_write {
+            self.regs.write_f(op.rd, outcome.result);
+            self.set_fs_dirty();
+        } else if ctrl.reg_write && op.rd != 0 {
+            self.regs.write(op.rd, outcome.result);
+        }
+
+        match ctrl.vector_op {
+            VectorOp::None => {}
+            VectorOp::SetVl => {
+                self.csrs.vl = outcome.result;
+                self.csrs.vstart = 0;
+            }
+            vop => self.execute_vector(vop, op.inst),
+        }
+
+        if let Some(update) = outcome.csr_update {
+            self.csr_write(update.addr, update.new_val);
+        }
+        if ctrl.mem_write && ctrl.rs2_fp {
+            self.set_fs_dirty();
+        }
+
+        self.pc = outcome.next_pc;
+        if ctrl.is_mret {
+            self.do_mret();
+        } else if ctrl.is_sret {
+            self.do_sret();
+        }
+        self.regs.write(0, 0);
+    }
+
+    /// Translates `vaddr`, returning the physical address or the fault.
+    pub(crate) fn translate_functional(
+        &mut self,
+        vaddr: u64,
+        access: AccessType,
+    ) -> Result<u64, Trap> {
+        let result = self.translate(VirtAddr::new(vaddr), access);
+        match result.trap {
+            Some(trap) => Err(trap),
+            None => Ok(result.paddr.val()),
+        }
+    }
+
+    /// Reads `width` bytes at `paddr` from RAM or the bus, sign-extending
+    /// if `signed`.
+    ///
+    /// Misaligned reads are split into byte reads so they never run past the
+    /// end of RAM or a device.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `BusError` if any byte read is not backed by a device.
+    pub(crate) fn read_phys(
+        &mut self,
+        paddr: u64,
+        width: MemWidth,
+        signed: bool,
+    ) -> Result<u64, BusError> {
+        let size = width_to_bytes(width);
+        let raw = if !is_aligned(paddr, size) {
+            let mut fault = None;
+            let raw = split_load(paddr, size, |addr| {
+                match self.read_phys(addr, MemWidth::Byte, false) {
+                    Ok(byte) => byte as u8,
+                    Err(e) => {
+                        fault.get_or_insert(e);
+                        0
+                    }
+                }
+            });
+            if let Some(e) = fault {
+                return Err(e);
+            }
+            raw
+        } else if self.in_ram(paddr, size) {
+            let offset = (paddr - self.ram_start) as usize;
+            // SAFETY: the whole access lies within RAM per the check above.
+            unsafe {
+                let ptr = self.ram_ptr.add(offset);
+                match width {
+                    MemWidth::Byte => *ptr as u64,
+                    MemWidth::Half => (ptr as *const u16).read_unaligned() as u64,
+                    MemWidth::Word => (ptr as *const u32).read_unaligned() as u64,
+                    MemWidth::Double => (ptr as *const u64).read_unaligned(),
+                    _ => 0,
+                }
+            }
+        } else {
+            match width {
+                MemWidth::Byte => self.bus.bus.read_u8(paddr)? as u64,
+                MemWidth::Half => self.bus.bus.read_u16(paddr)? as u64,
+                MemWidth::Word => self.bus.bus.read_u32(paddr)? as u64,
+                MemWidth::Double => self.bus.bus.read_u64(paddr)?,
+                _ => 0,
+            }
+        };
+        if !signed {
+            return Ok(raw);
+        }
+        Ok(match width {
+            MemWidth::Byte => raw as u8 as i8 as i64 as u64,
+            MemWidth::Half => raw as u16 as i16 as i64 as u64,
+            MemWidth::Word => raw as u32 as i32 as i64 as u64,
+            _ => raw,
+        })
+    }
+
+    /// Reads a data value of `width` at physical address `paddr` in the
+    /// hart's data byte order, sign-extending if `signed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `BusError` if any byte read is not backed by a device.
+    pub(crate) fn read_data(
+        &mut self,
+        paddr: u64,
+        width: MemWidth,
+        signed: bool,
+    ) -> Result<u64, BusError> {
+        let raw = self.read_phys(paddr, width, false)?;
+        Ok(self.load_value(raw, width, signed))
+    }
+}
@@ -0,0 +1,95 @@
+//! Hardware Performance Monitor (Zihpm).
+//!
+//! This module implements the programmable counters `mhpmcounter3..31`. It performs the following:
+//! 1. **Event Sampling:** Reads the running totals of each selectable event once per cycle.
+//! 2. **Counter Update:** Adds the per-cycle increment of the selected event to each counter.
+//! 3. **Access Control:** Applies `mcounteren`/`scounteren` to user-level counter reads.
+//! 4. **Inhibit:** Holds any counter whose `mcountinhibit` bit is set.
+
+use super::Cpu;
+use crate::core::arch::csr::{
+    self, HPM_EVENT_BRANCH_MISPREDICT, HPM_EVENT_COUNT, HPM_EVENT_DCACHE_MISS, HPM_EVENT_DTLB_MISS,
+    HPM_EVENT_ICACHE_MISS, HPM_EVENT_ITLB_MISS, HPM_EVENT_L2_MISS,
+};
+use crate::core::arch::mode::PrivilegeMode;
+
+impl Cpu {
+    /// Returns the running total of every HPM event, indexed by event code.
+    pub(crate) fn hpm_event_totals(&self) -> [u64; HPM_EVENT_COUNT] {
+        let mut totals = [0; HPM_EVENT_COUNT];
+        totals[HPM_EVENT_ICACHE_MISS as usize] = self.stats.icache_misses;
+        totals[HPM_EVENT_DCACHE_MISS as usize] = self.stats.dcache_misses;
+        totals[HPM_EVENT_L2_MISS as usize] = self.stats.l2_misses;
+        totals[HPM_EVENT_BRANCH_MISPREDICT as usize] = self.stats.branch_mispredictions;
+        totals[HPM_EVENT_ITLB_MISS as usize] = self.mmu.itlb_misses;
+        totals[HPM_EVENT_DTLB_MISS as usize] = self.mmu.dtlb_misses;
+        totals
+    }
+
+    /// Advances each programmed HPM counter by the events of the current cycle.
+    ///
+    /// Called once at the end of every tick. Counters whose `mhpmevent` is
+    /// `HPM_EVENT_NONE`, or whose `mcountinhibit` bit is set, hold their value.
+    pub fn hpm_tick(&mut self) {
+        let totals = self.hpm_event_totals();
+        let mut delta = [0; HPM_EVENT_COUNT];
+        for (d, (now, prev)) in delta.iter_mut().zip(totals.iter().zip(&self.hpm_prev)) {
+            *d = now.wrapping_sub(*prev);
+        }
+        self.hpm_prev = totals;
+
+        let inhibit = self.csrs.mcountinhibit;
+        for (i, (counter, &event)) in self
+            .csrs
+            .mhpmcounter
+            .iter_mut()
+            .zip(&self.csrs.mhpmevent)
+            .enumerate()
+        {
+            if inhibit & (1 << (i + 3)) != 0 {
+                continue;
+            }
+            if let Some(&d) = delta.get(event as usize) {
+                *counter = counter.wrapping_add(d);
+            }
+        }
+    }
+
+    /// Counts `count` instructions retired in the current privilege mode.
+    ///
+    /// While `mcountinhibit.IR` is set they still reach the statistics but
+    /// are hidden from `minstret`.
+    pub(crate) fn record_retired(&mut self, count: u64) {
+        self.stats.record_retired(self.privilege, count);
+        if self.csrs.mcountinhibit & csr::MCOUNTINHIBIT_IR != 0 {
+            self.csrs.inhibited_instret += count;
+        }
+    }
+
+    /// Checks whether the current privilege level may read a user counter CSR.
+    ///
+    /// Supervisor mode needs the counter's bit set in `mcounteren`; user mode
+    /// additionally needs it in `scounteren`. Machine mode and CSRs outside
+    /// `cycle..hpmcounter31` are always accessible.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The 12-bit CSR address.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the access must raise an illegal-instruction exception.
+    pub fn counter_accessible(&self, addr: u32) -> bool {
+        if !(csr::CYCLE..=csr::HPMCOUNTER31).contains(&addr) {
+            return true;
+        }
+        let bit = 1u64 << (addr - csr::CYCLE);
+        match self.privilege {
+            PrivilegeMode::Machine => true,
+            PrivilegeMode::Supervisor => self.csrs.mcounteren & bit != 0,
+            PrivilegeMode::User => {
+                self.csrs.mcounteren & bit != 0 && self.csrs.scounteren & bit != 0
+            }
+        }
+    }
+}
@@ -4,10 +4,17 @@
 //! It performs the following:
 //! 1. **Address Translation:** Interfaces with the MMU to convert virtual to physical addresses.
 //! 2. **Cache Simulation:** Models the behavior of L1, L2, and L3 caches during memory access.
-//! 3. **Latency Modeling:** Calculates timing penalties for cache hits, misses, and bus transit.
+//! 3. **Latency Modeling:** Calculates timing penalties for cache hits, misses, and bus transit,
+//!    scheduling DRAM-bound misses issued together as one batch.
+//! 4. **Misaligned Accesses:** Decides whether a misaligned load or store is performed or traps.
+//! 5. **Byte Order:** Converts data between register and memory byte order.
 
 use super::Cpu;
+use crate::common::constants::PAGE_SHIFT;
 use crate::common::{AccessType, PhysAddr, TranslationResult, Trap, VirtAddr};
+use crate::core::pipeline::signals::MemWidth;
+use crate::core::units::lsu::{endian, unaligned};
+use crate::soc::memory::controller::MemRequest;
 
 impl Cpu {
     /// Translates a virtual address to a physical address using the MMU.
@@ -24,12 +31,7 @@ impl Cpu {
         if self.direct_mode {
             let paddr = vaddr.val();
             if !self.bus.bus.is_valid_address(paddr) {
-                let trap = match access {
-                    AccessType::Fetch => Trap::InstructionAccessFault(paddr),
-                    AccessType::Read => Trap::LoadAccessFault(paddr),
-                    AccessType::Write => Trap::StoreAccessFault(paddr),
-                };
-                return TranslationResult::fault(trap, 0);
+                return TranslationResult::fault(Trap::access_fault(access, paddr), 0);
             }
             return TranslationResult::success(PhysAddr::new(paddr), 0);
         }
@@ -50,8 +52,57 @@ impl Cpu {
             .translate(vaddr, access, effective_priv, &self.csrs, &mut self.bus.bus)
     }
 
+    /// Checks a translated data access of `size` bytes at `vaddr` for
+    /// misalignment.
+    ///
+    /// Aligned accesses always pass. A misaligned access traps when
+    /// `misaligned_access` is off or it is atomic. Otherwise it is performed
+    /// as split byte accesses from `paddr`, so when it crosses into another
+    /// page that page is translated too: a fault there is returned, and a
+    /// physically discontiguous pair of pages raises the misaligned trap for
+    /// software to emulate, as the privileged spec allows.
+    ///
+    /// # Returns
+    ///
+    /// The trap to raise, or `None` if the access may proceed.
+    pub fn check_misaligned(
+        &mut self,
+        vaddr: u64,
+        paddr: u64,
+        size: u64,
+        access: AccessType,
+        atomic: bool,
+    ) -> Option<Trap> {
+        if unaligned::is_aligned(vaddr, size) {
+            return None;
+        }
+        let misaligned = match access {
+            AccessType::Write => unaligned::store_misaligned_trap(vaddr),
+            _ => unaligned::load_misaligned_trap(vaddr),
+        };
+        if atomic || !self.misaligned_access {
+            return Some(misaligned);
+        }
+
+        let last = vaddr.wrapping_add(size - 1);
+        if last >> PAGE_SHIFT == vaddr >> PAGE_SHIFT {
+            return None;
+        }
+        let result = self.translate(VirtAddr::new(last), access);
+        if let Some(trap) = result.trap {
+            return Some(trap);
+        }
+        if result.paddr.val() != paddr.wrapping_add(size - 1) {
+            return Some(misaligned);
+        }
+        None
+    }
+
     /// Simulates a memory access through the cache hierarchy.
     ///
+    /// In simple timing mode the hierarchy is bypassed entirely: data
+    /// accesses cost `simple_mem_latency` and instruction fetches are free.
+    ///
     /// # Arguments
     ///
     /// * `addr` - The physical address to access.
@@ -61,10 +112,156 @@ impl Cpu {
     ///
     /// The total latency penalty in cycles for the memory operation.
     pub fn simulate_memory_access(&mut self, addr: PhysAddr, access: AccessType) -> u64 {
+        self.simulate_memory_accesses(&[(addr, access)])[0]
+    }
+
+    /// Simulates a group of memory accesses issued in the same cycle.
+    ///
+    /// Every access that misses all enabled cache levels becomes a line fill
+    /// (a read) to the memory controller, and every dirty line the last
+    /// level evicts becomes a write. The requests are scheduled as one batch,
+    /// so independent misses are in flight together rather than queuing
+    /// behind each other. Data accesses that collide in a banked L1-D wait
+    /// for their bank first. Each latency is measured from the current cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `accesses` - Physical address and access type of each access.
+    ///
+    /// # Returns
+    ///
+    /// The latency penalty of each access, in the same order as `accesses`.
+    pub fn simulate_memory_accesses(&mut self, accesses: &[(PhysAddr, AccessType)]) -> Vec<u64> {
+        if self.simple_timing {
+            return accesses
+                .iter()
+                .map(|&(_, access)| match access {
+                    AccessType::Fetch => 0,
+                    _ => self.simple_mem_latency,
+                })
+                .collect();
+        }
+
+        // Enqueue one line fill per distinct line that has to come from DRAM.
+        // Caches are write-allocate, so a store miss is a read as well.
+        let now = self.stats.total_cycles();
+        let mut lines = Vec::new();
+        let mut requests = Vec::new();
+        let mut request_of = vec![None; accesses.len()];
+        for (slot, &(addr, access)) in request_of.iter_mut().zip(accesses) {
+            let Some(line) = self.dram_line(addr.val(), access) else {
+                continue;
+            };
+            *slot = Some(lines.iter().position(|&l| l == line).unwrap_or_else(|| {
+                lines.push(line);
+                requests.push(MemRequest {
+                    addr: addr.val(),
+                    is_write: false,
+                    arrival: now,
+                });
+                requests.len() - 1
+            }));
+        }
+
+        // Walking the hierarchy installs the lines and evicts their victims;
+        // the dirty ones the last level drops are written back to DRAM.
+        let walks: Vec<(u64, bool)> = accesses
+            .iter()
+            .map(|&(addr, access)| self.walk_hierarchy(addr.val(), access))
+            .collect();
+        requests.extend(
+            self.take_dram_writebacks()
+                .into_iter()
+                .map(|addr| MemRequest {
+                    addr,
+                    is_write: true,
+                    arrival: now,
+                }),
+        );
+        let done = if requests.is_empty() {
+            Vec::new()
+        } else {
+            self.bus.mem_controller.schedule(&requests)
+        };
+
+        // Same-cycle data accesses to one L1-D bank serialize.
+        let data_addrs: Vec<u64> = accesses
+            .iter()
+            .filter(|(_, access)| !matches!(access, AccessType::Fetch))
+            .map(|(addr, _)| addr.val())
+            .collect();
+        let bank_delays = self.l1_d_cache.bank_delays(&data_addrs);
+        self.stats.dcache_bank_conflicts += bank_delays.iter().filter(|&&d| d > 0).count() as u64;
+        let mut bank_delays = bank_delays.into_iter();
+
+        accesses
+            .iter()
+            .zip(request_of)
+            .zip(walks)
+            .map(|((&(_, access), slot), (penalty, reached_dram))| {
+                let bank_delay = match access {
+                    AccessType::Fetch => 0,
+                    _ => bank_delays.next().unwrap_or(0),
+                };
+                let ram_latency = match slot {
+                    Some(r) if reached_dram => done[r] - now,
+                    _ => 0,
+                };
+                bank_delay + penalty + ram_latency
+            })
+            .collect()
+    }
+
+    /// Returns the addresses of the dirty lines the last enabled data cache
+    /// level evicted, which go to DRAM. Lines evicted from a higher level
+    /// were written into the next level, which charged for them.
+    fn take_dram_writebacks(&mut self) -> Vec<u64> {
+        let l1 = self.l1_d_cache.take_writebacks();
+        let l2 = self.l2_cache.take_writebacks();
+        let l3 = self.l3_cache.take_writebacks();
+        if self.l3_cache.enabled {
+            l3
+        } else if self.l2_cache.enabled {
+            l2
+        } else {
+            l1
+        }
+    }
+
+    /// Returns the line an access has to fetch from DRAM, or `None` if it
+    /// hits in an enabled cache level or no level is enabled.
+    fn dram_line(&self, addr: u64, access: AccessType) -> Option<u64> {
+        let l1 = if matches!(access, AccessType::Fetch) {
+            &self.l1_i_cache
+        } else {
+            &self.l1_d_cache
+        };
+        let levels = [l1, &self.l2_cache, &self.l3_cache];
+        let last = levels.iter().rev().find(|c| c.enabled)?;
+        if levels.iter().any(|c| c.contains(addr)) {
+            return None;
+        }
+        Some(addr / last.line_bytes() as u64)
+    }
+
+    /// Walks the cache hierarchy for one access, updating cache state and
+    /// statistics.
+    ///
+    /// A dirty victim costs the latency of the next enabled level it is
+    /// written into; the last level's victims cost nothing here and are
+    /// written back to DRAM by the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw_addr` - The physical address to access.
+    /// * `access` - The type of memory access.
+    ///
+    /// # Returns
+    ///
+    /// The latency penalty in cycles, excluding the wait for DRAM, and
+    /// whether the access missed every enabled level and so reached DRAM.
+    fn walk_hierarchy(&mut self, raw_addr: u64, access: AccessType) -> (u64, bool) {
         let mut total_penalty = 0;
-        let raw_addr = addr.val();
-        let ram_latency = self.bus.mem_controller.access_latency(raw_addr);
-        let next_lat = ram_latency;
         let is_inst = matches!(access, AccessType::Fetch);
         let is_write = matches!(access, AccessType::Write);
 
@@ -78,61 +275,115 @@ impl Cpu {
         // If no cache level is enabled, there is no memory hierarchy to
         // simulate — the pipeline structural latency is the only cost.
         if !l1_enabled && !self.l2_cache.enabled && !self.l3_cache.enabled {
-            return 0;
+            return (0, false);
         }
 
+        let l2_writeback = if self.l3_cache.enabled {
+            self.l3_cache.latency
+        } else {
+            0
+        };
+        let l1_writeback = if self.l2_cache.enabled {
+            self.l2_cache.latency
+        } else {
+            l2_writeback
+        };
+
+        // Prefetches triggered by this access must not touch the line an
+        // LR/SC pair or an atomic holds.
+        let exclusion = self.prefetch_exclusion();
+        self.l1_d_cache.no_prefetch = exclusion;
+        self.l2_cache.no_prefetch = exclusion;
+        self.l3_cache.no_prefetch = exclusion;
+
         let (l1_hit, l1_pen) = if is_inst {
             if self.l1_i_cache.enabled {
-                self.l1_i_cache.access(raw_addr, false, next_lat)
+                self.l1_i_cache.access(raw_addr, false, l1_writeback)
             } else {
                 (false, 0)
             }
         } else if self.l1_d_cache.enabled {
-            self.l1_d_cache.access(raw_addr, is_write, next_lat)
+            self.l1_d_cache.access(raw_addr, is_write, l1_writeback)
         } else {
             (false, 0)
         };
 
+        // The first cycle of an L1 access is the fetch or memory stage itself;
+        // a slower L1 stalls for the rest, hit or miss.
+        let l1_latency = if is_inst {
+            self.l1_i_cache.latency
+        } else {
+            self.l1_d_cache.latency
+        };
+        if l1_enabled {
+            total_penalty += l1_latency.saturating_sub(1);
+        }
+
         total_penalty += l1_pen;
         if is_inst && self.l1_i_cache.enabled {
             if l1_hit {
                 self.stats.icache_hits += 1;
-                return total_penalty;
+                return (total_penalty, false);
             }
             self.stats.icache_misses += 1;
         } else if !is_inst && self.l1_d_cache.enabled {
             if l1_hit {
                 self.stats.dcache_hits += 1;
-                return total_penalty;
+                return (total_penalty, false);
             }
             self.stats.dcache_misses += 1;
         }
 
         if self.l2_cache.enabled {
             total_penalty += self.l2_cache.latency;
-            let (l2_hit, l2_pen) = self.l2_cache.access(raw_addr, is_write, next_lat);
+            let (l2_hit, l2_pen) = self.l2_cache.access(raw_addr, is_write, l2_writeback);
             total_penalty += l2_pen;
             if l2_hit {
                 self.stats.l2_hits += 1;
-                return total_penalty;
+                return (total_penalty, false);
             }
             self.stats.l2_misses += 1;
         }
 
         if self.l3_cache.enabled {
             total_penalty += self.l3_cache.latency;
-            let (l3_hit, l3_pen) = self.l3_cache.access(raw_addr, is_write, next_lat);
+            let (l3_hit, l3_pen) = self.l3_cache.access(raw_addr, is_write, 0);
             total_penalty += l3_pen;
             if l3_hit {
                 self.stats.l3_hits += 1;
-                return total_penalty;
+                return (total_penalty, false);
             }
             self.stats.l3_misses += 1;
         }
 
         total_penalty += self.bus.bus.calculate_transit_time(8);
-        total_penalty += ram_latency;
         total_penalty += self.bus.bus.calculate_transit_time(64);
-        total_penalty
+        (total_penalty, true)
+    }
+
+    /// Converts the bytes a data load read, assembled little-endian, to the
+    /// value written to the destination register.
+    ///
+    /// Under big-endian data the bytes are reversed first; the result is
+    /// then sign- or zero-extended from `width`.
+    #[inline]
+    pub(crate) fn load_value(&self, raw: u64, width: MemWidth, signed: bool) -> u64 {
+        let value = if self.big_endian {
+            endian::swap(raw, width)
+        } else {
+            raw
+        };
+        endian::extend(value, width, signed)
+    }
+
+    /// Converts a register value to the value a data store writes, whose
+    /// little-endian bytes are the bytes placed in memory.
+    #[inline]
+    pub(crate) fn store_value(&self, value: u64, width: MemWidth) -> u64 {
+        if self.big_endian {
+            endian::swap(value, width)
+        } else {
+            value
+        }
     }
 }
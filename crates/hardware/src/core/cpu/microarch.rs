@@ -0,0 +1,70 @@
+//! Microarchitectural State Snapshots.
+//!
+//! Captures the warm state of the structures that affect timing but not
+//! architectural results: the cache tag arrays (with replacement and
+//! prefetcher state), the branch predictor tables, and the TLBs. For sampled
+//! simulation a snapshot taken in one run can be imported into a CPU that was
+//! fast-forwarded to the same point, so the detailed region starts warm.
+//!
+//! Snapshots should be taken and applied with the pipeline drained; the
+//! predictor's speculative history is copied as-is.
+
+use super::Cpu;
+use crate::core::units::bru::BranchPredictorWrapper;
+use crate::core::units::bru::ras::RasCheckpoint;
+use crate::core::units::cache::CacheSim;
+use crate::core::units::mmu::tlb::Tlb;
+
+/// Snapshot of the caches, branch predictor, and TLBs of a CPU.
+#[derive(Clone)]
+pub struct MicroarchState {
+    l1_i_cache: CacheSim,
+    l1_d_cache: CacheSim,
+    l2_cache: CacheSim,
+    l3_cache: CacheSim,
+    branch_predictor: BranchPredictorWrapper,
+    retired_ras: RasCheckpoint,
+    itlb: Tlb,
+    dtlb: Tlb,
+}
+
+impl Cpu {
+    /// Exports the current microarchitectural state.
+    ///
+    /// # Returns
+    ///
+    /// A `MicroarchState` that can be applied to this or another CPU with
+    /// [`Cpu::import_microarch`].
+    pub fn export_microarch(&self) -> MicroarchState {
+        MicroarchState {
+            l1_i_cache: self.l1_i_cache.clone(),
+            l1_d_cache: self.l1_d_cache.clone(),
+            l2_cache: self.l2_cache.clone(),
+            l3_cache: self.l3_cache.clone(),
+            branch_predictor: self.branch_predictor.clone(),
+            retired_ras: self.retired_ras,
+            itlb: self.mmu.itlb.clone(),
+            dtlb: self.mmu.dtlb.clone(),
+        }
+    }
+
+    /// Replaces the caches, branch predictor, and TLBs with a snapshot.
+    ///
+    /// Architectural state (registers, CSRs, memory) and statistics are left
+    /// untouched. The cache geometry and predictor type come from the
+    /// snapshot, not from this CPU's configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - A snapshot produced by [`Cpu::export_microarch`].
+    pub fn import_microarch(&mut self, state: &MicroarchState) {
+        self.l1_i_cache = state.l1_i_cache.clone();
+        self.l1_d_cache = state.l1_d_cache.clone();
+        self.l2_cache = state.l2_cache.clone();
+        self.l3_cache = state.l3_cache.clone();
+        self.branch_predictor = state.branch_predictor.clone();
+        self.retired_ras = state.retired_ras;
+        self.mmu.itlb = state.itlb.clone();
+        self.mmu.dtlb = state.dtlb.clone();
+    }
+}
@@ -6,26 +6,68 @@
 //! 2. **Memory Hierarchy:** MMU, TLBs, and multi-level cache simulations.
 //! 3. **System Integration:** System bus, devices, and RAM.
 
+/// Commit-order assembly dump of retired instructions.
+pub mod asm_dump;
+
+/// Best-effort call-stack reconstruction for crash diagnostics.
+pub mod backtrace;
+
+/// Save and restore of architectural and device state.
+pub mod checkpoint;
+
+/// Spike-compatible commit log of retired instructions.
+pub mod commit_log;
+
 /// Control and Status Register access and management.
 pub mod csr;
 
+/// Functional (no-timing) fast-forward execution.
+pub mod functional;
+
+/// Hardware performance monitor (Zihpm) counters and counter-enable checks.
+pub mod hpm;
+
 /// Instruction execution orchestration and pipeline coordination.
 pub mod execution;
 
 /// Memory access handling and load/store operations.
 pub mod memory;
 
+/// Export and import of microarchitectural (cache, predictor, TLB) state.
+pub mod microarch;
+
+/// Built-in SBI firmware for supervisor-mode kernels.
+pub mod sbi;
+
+/// Guest-to-host semihosting calls.
+pub mod semihosting;
+
 /// Trap and exception handling logic.
 pub mod trap;
 
+/// Ring buffer of taken traps.
+pub mod trap_log;
+
+/// Vector extension (RVV-lite) configuration and arithmetic.
+pub mod vector;
+
 use crate::common::RegisterFile;
 use crate::config::Config;
-use crate::core::arch::csr::Csrs;
+use crate::core::arch::csr::{Csrs, HPM_EVENT_COUNT};
 use crate::core::arch::mode::PrivilegeMode;
+use crate::core::arch::trap::TrapHandler;
+use crate::core::cpu::asm_dump::AsmDump;
+use crate::core::cpu::commit_log::CommitLog;
+use crate::core::cpu::semihosting::SemihostFile;
+use crate::core::cpu::trap_log::TrapLog;
+use crate::core::pipeline::signals::FuncUnit;
 use crate::core::units::bru::BranchPredictorWrapper;
+use crate::core::units::bru::ras::RasCheckpoint;
 use crate::core::units::cache::CacheSim;
 use crate::core::units::mmu::Mmu;
 use crate::core::units::mmu::pmp::Pmp;
+use crate::isa::disasm;
+use crate::sim::symbols::SymbolTable;
 use crate::soc::System;
 use crate::stats::SimStats;
 
@@ -38,12 +80,46 @@ pub struct Cpu {
     pub regs: RegisterFile,
     /// Program Counter.
     pub pc: u64,
+    /// PC the hart restarts from on reset.
+    pub reset_pc: u64,
+    /// `mstatus` value loaded on reset.
+    pub reset_mstatus: u64,
+    /// Configured `misa`, loaded on reset. Bounds the extensions software
+    /// can re-enable.
+    pub reset_misa: u64,
+    /// `medeleg` value loaded on reset.
+    pub reset_medeleg: u64,
+    /// `mideleg` value loaded on reset.
+    pub reset_mideleg: u64,
+    /// Privilege mode the hart restarts in on reset.
+    pub reset_privilege: PrivilegeMode,
+    /// Hart ID reported by `mhartid`.
+    pub hartid: u64,
+    /// Trap on access to an unimplemented CSR instead of reading zero.
+    pub strict_csrs: bool,
+    /// Interrupt codes in the order simultaneous interrupts are taken,
+    /// highest priority first.
+    pub interrupt_priority: [u64; 6],
+    /// Service S-mode `ecall`s with the built-in SBI firmware.
+    pub sbi: bool,
+    /// `mtime` deadline set through the SBI timer; `u64::MAX` when disarmed.
+    pub sbi_timer: u64,
+    /// Service semihosting calls from M- and S-mode.
+    pub semihosting: bool,
+    /// `EBREAK` halts the hart for the host instead of trapping.
+    pub ebreak_halt: bool,
+    /// PC of the `EBREAK` the hart is halted at, until the host takes it
+    /// with `take_halt`.
+    pub halted_at: Option<u64>,
     /// Control and Status Registers.
     pub csrs: Csrs,
     /// Current Privilege Mode (M, S, U).
     pub privilege: PrivilegeMode,
     /// Load Reservation address (for LR/SC).
     pub load_reservation: Option<u64>,
+    /// Physical address of an LR or AMO whose access is being simulated;
+    /// its granule is kept out of reach of the prefetchers like a reservation.
+    pub atomic_line: Option<u64>,
 
     /// System Bus and Devices.
     pub bus: System,
@@ -61,11 +137,20 @@ pub struct Cpu {
     pub l3_cache: CacheSim,
     /// Base address for MMIO (used to bypass cache).
     pub mmio_base: u64,
+    /// Perform misaligned loads/stores in hardware instead of trapping.
+    pub misaligned_access: bool,
+    /// Data loads and stores are big-endian.
+    pub big_endian: bool,
 
     /// Branch Predictor Unit.
     pub branch_predictor: BranchPredictorWrapper,
+    /// RAS checkpoint of the most recently retired instruction, restored
+    /// when the whole pipeline is flushed.
+    pub retired_ras: RasCheckpoint,
     /// Pipeline width (superscalar degree).
     pub pipeline_width: usize,
+    /// Instructions fetched per cycle.
+    pub fetch_width: usize,
 
     /// Enable instruction tracing.
     pub trace: bool,
@@ -81,6 +166,12 @@ pub struct Cpu {
     pub last_pc: u64,
     /// Hang detection counter.
     pub same_pc_count: u64,
+    /// Last retired PC (for the same-PC watchdog).
+    pub last_commit_pc: u64,
+    /// Consecutive instructions retired at `last_commit_pc`.
+    pub same_commit_pc_count: u64,
+    /// Same-PC watchdog limit (0 = disabled).
+    pub max_same_pc: u64,
     /// WFI state.
     pub wfi_waiting: bool,
     /// PC when WFI was entered.
@@ -92,7 +183,8 @@ pub struct Cpu {
     /// This pointer must maintain the following invariants at all times:
     /// - Points to a valid, allocated memory region of size `(ram_end - ram_start)` bytes
     /// - The memory region remains valid for the entire lifetime of the `Cpu` instance
-    /// - All accesses must verify: `ram_start <= address < ram_end` before dereferencing
+    /// - All accesses must verify the whole access lies in `ram_start..ram_end`
+    ///   (see `in_ram`) before dereferencing
     /// - The pointer is valid for both reads and writes (memory is mutable)
     /// - Memory is properly aligned for the underlying allocation (even if individual
     ///   accesses use `read_unaligned`/`write_unaligned`)
@@ -110,14 +202,79 @@ pub struct Cpu {
 
     /// Ring buffer of (pc, inst) for last N retired instructions (for invalid-PC debug trace).
     pub pc_trace: Vec<(u64, u32)>,
+    /// ELF function symbols for annotating PCs in traces and dumps.
+    pub symbols: SymbolTable,
     /// Last invalid PC we printed debug for (avoid duplicate dumps).
     pub last_invalid_pc_debug: Option<u64>,
+    /// Assembly dump of retired instructions, written at commit.
+    pub asm_dump: Option<AsmDump>,
+    /// Spike-format commit log of retired instructions, written at commit.
+    pub commit_log: Option<CommitLog>,
 
     /// Set by the backend when a PC redirect occurs (branch misprediction,
     /// trap, FENCE.I, etc.). The pipeline uses this to flush the frontend,
     /// rather than relying solely on `cpu.pc != pc_before` which can miss
     /// redirects when the target happens to equal the current fetch PC.
     pub redirect_pending: bool,
+
+    /// PC of the oldest instruction held in the frontend or rename latch,
+    /// sampled by the pipeline before the backend runs each cycle. Commit
+    /// uses it as the interrupt EPC when the ROB is empty, since `pc` is
+    /// the fetch PC and may already be past instructions that were fetched
+    /// but not yet renamed.
+    pub frontend_head_pc: Option<u64>,
+
+    /// Functional mode: execute one instruction per tick with no pipeline,
+    /// cache, or predictor modeling.
+    pub(crate) functional: bool,
+    /// Simple timing mode: fixed memory latency, no cache/controller modeling.
+    pub simple_timing: bool,
+    /// Latency charged per data memory access in simple timing mode.
+    pub simple_mem_latency: u64,
+    /// Fetch stall charged per misprediction in simple timing mode.
+    pub simple_mispredict_penalty: u64,
+    /// Fetch stall to apply after the next frontend flush (set on mispredict).
+    pub redirect_penalty: u64,
+    /// Set when the perfect predictor corrects a wrong fetch path; the next
+    /// frontend flush refills the frontend within the same cycle.
+    pub oracle_redirect: bool,
+    /// Cycles between fetch and branch resolution, charged per misprediction.
+    pub frontend_depth: u64,
+
+    /// Cycles charged for a load forwarded from the store buffer.
+    pub store_forward_latency: u64,
+    /// Cycles a FENCE or FENCE.I holds execute after the store buffer drains.
+    pub fence_latency: u64,
+
+    /// Integer multiply latency in cycles.
+    pub mul_latency: u64,
+    /// Integer divide/remainder latency in cycles.
+    pub div_latency: u64,
+    /// Floating-point divide latency in cycles.
+    pub fdiv_latency: u64,
+    /// Floating-point square root latency in cycles.
+    pub fsqrt_latency: u64,
+    /// Remaining cycles the execute unit is busy with a multi-cycle
+    /// operation; issue and execute stall while non-zero.
+    pub alu_timer: u64,
+    /// Cycle (see `SimStats::total_cycles`) at which each non-pipelined
+    /// functional unit accepts its next operation, indexed by `FuncUnit`.
+    pub fu_busy_until: [u64; FuncUnit::COUNT],
+    /// Check integer register dependencies per 32-bit half.
+    pub subreg_tracking: bool,
+    /// Event totals at the end of the previous cycle, indexed by HPM event code.
+    pub hpm_prev: [u64; HPM_EVENT_COUNT],
+    /// Host streams opened through semihosting, indexed by handle.
+    pub semihost_files: Vec<Option<SemihostFile>>,
+    /// Interrupts scheduled by `inject_interrupt_at` that are not yet due,
+    /// as `(instruction count, MIP bit)` sorted by count.
+    pub injected_irqs: Vec<(u64, u64)>,
+    /// MIP bits raised by due injected interrupts; each stays pending until
+    /// its interrupt is taken.
+    pub injected_mip: u64,
+    /// Recent traps, when `general.trap_log_size` or `enable_trap_log`
+    /// turned the log on.
+    pub trap_log: Option<TrapLog>,
 }
 
 /// Maximum number of (pc, inst) entries kept for invalid-PC debug trace.
@@ -158,6 +315,35 @@ impl Cpu {
         self.load_reservation = None;
     }
 
+    /// Returns the granule prefetchers must leave alone, as `[start, end)`:
+    /// that of an in-flight atomic, otherwise that of the reservation.
+    pub(crate) fn prefetch_exclusion(&self) -> Option<(u64, u64)> {
+        self.atomic_line.or(self.load_reservation).map(|addr| {
+            let start = Self::align_reservation_address(addr);
+            (start, start + Self::RESERVATION_GRANULE)
+        })
+    }
+
+    /// Returns `true` if all `len` bytes at `paddr` lie in RAM, so the access
+    /// may use `ram_ptr` directly.
+    #[inline]
+    pub(crate) fn in_ram(&self, paddr: u64, len: u64) -> bool {
+        paddr >= self.ram_start && paddr < self.ram_end && self.ram_end - paddr >= len
+    }
+
+    /// Invalidates the load reservation if a store of `size` bytes at `addr`
+    /// touches the reserved granule.
+    ///
+    /// Called for every store this hart performs; other agents that write
+    /// memory (e.g. another hart) call it to break this hart's LR/SC pair.
+    #[inline]
+    pub fn snoop_store(&mut self, addr: u64, size: u64) {
+        let last = addr.wrapping_add(size.max(1) - 1);
+        if self.check_reservation(addr) || self.check_reservation(last) {
+            self.clear_reservation();
+        }
+    }
+
     /// Creates a new CPU instance with the specified system and configuration.
     ///
     /// # Arguments
@@ -170,9 +356,9 @@ impl Cpu {
     /// A new `Cpu` instance initialized according to the provided configuration.
     pub fn new(mut system: System, config: &Config) -> Self {
         use crate::core::arch::csr::{
-            MISA_DEFAULT_RV64IMAFDC, MISA_EXT_A, MISA_EXT_C, MISA_EXT_D, MISA_EXT_F, MISA_EXT_I,
-            MISA_EXT_M, MISA_EXT_S, MISA_EXT_U, MISA_XLEN_64, MSTATUS_DEFAULT_RV64,
-            MSTATUS_FS_INIT,
+            MISA_DEFAULT_RV64IMAFDC, MISA_EXT_A, MISA_EXT_B, MISA_EXT_C, MISA_EXT_D, MISA_EXT_F,
+            MISA_EXT_I, MISA_EXT_M, MISA_EXT_S, MISA_EXT_U, MISA_XLEN_64, MSTATUS_BE,
+            MSTATUS_DEFAULT_RV64, MSTATUS_FS_INIT, VTYPE_VILL,
         };
         use crate::isa::abi;
 
@@ -182,6 +368,7 @@ impl Cpu {
         } else {
             let mut val = MISA_XLEN_64;
             val |= MISA_EXT_A;
+            val |= MISA_EXT_B;
             val |= MISA_EXT_C;
             val |= MISA_EXT_D;
             val |= MISA_EXT_F;
@@ -197,20 +384,45 @@ impl Cpu {
         // In direct (SE) mode, enable FP state so user programs can use
         // floating-point instructions without an OS to set mstatus.FS.
         // In full-system mode, firmware/OS is responsible for enabling FP.
-        let mstatus = if direct_mode {
+        let mstatus = config.general.reset_mstatus.unwrap_or(if direct_mode {
             MSTATUS_DEFAULT_RV64 | MSTATUS_FS_INIT
         } else {
             MSTATUS_DEFAULT_RV64
+        });
+        // The data byte order is fixed by the configuration.
+        let mstatus = if config.memory.big_endian {
+            mstatus | MSTATUS_BE
+        } else {
+            mstatus & !MSTATUS_BE
         };
 
-        let csrs = Csrs {
+        let mut csrs = Csrs {
             mstatus,
+            medeleg: config.general.reset_medeleg,
+            mideleg: config.general.reset_mideleg,
             misa: configured_misa,
+            // No vector configuration until the first vsetvl{i}.
+            vtype: VTYPE_VILL,
             ..Default::default()
         };
 
         let bp = BranchPredictorWrapper::new(config);
 
+        let asm_dump = config.general.asm_dump.as_deref().and_then(|path| {
+            AsmDump::create(path)
+                .map_err(|e| eprintln!("[asm-dump] cannot create {path}: {e}"))
+                .ok()
+        });
+        let commit_log = config.general.commit_log.as_deref().and_then(|path| {
+            CommitLog::create(path, 0)
+                .map_err(|e| eprintln!("[commit-log] cannot create {path}: {e}"))
+                .ok()
+        });
+
+        if config.general.mem_trace_size > 0 {
+            system.bus.enable_access_log(config.general.mem_trace_size);
+        }
+
         let (ram_ptr, ram_start, ram_end) =
             system
                 .bus
@@ -221,19 +433,35 @@ impl Cpu {
                 .general
                 .initial_sp
                 .unwrap_or(config.system.ram_base + 0x100_0000);
-            let mut r = RegisterFile::new();
+            let mut r = RegisterFile::with_vlen(config.pipeline.vlen);
             r.write(abi::REG_SP, sp);
             r
         } else {
-            RegisterFile::new()
+            RegisterFile::with_vlen(config.pipeline.vlen)
         };
-        // Always start in Machine mode. The riscv-tests switch to lower modes
-        // via their own trap handlers; bare-metal binaries need M-mode too.
-        let privilege = PrivilegeMode::Machine;
+        csrs.vlenb = regs.vlenb() as u64;
+        // Start in Machine mode unless configured otherwise. The riscv-tests
+        // switch to lower modes via their own trap handlers; bare-metal
+        // binaries need M-mode too.
+        let privilege = config.general.reset_privilege;
 
         Self {
             regs,
             pc: config.general.start_pc,
+            reset_pc: config.general.start_pc,
+            reset_mstatus: mstatus,
+            reset_misa: configured_misa,
+            reset_medeleg: config.general.reset_medeleg,
+            reset_mideleg: config.general.reset_mideleg,
+            reset_privilege: privilege,
+            hartid: config.general.hartid,
+            strict_csrs: config.general.strict_csrs,
+            interrupt_priority: TrapHandler::interrupt_priority(&config.general.interrupt_priority),
+            sbi: config.general.sbi,
+            sbi_timer: u64::MAX,
+            semihosting: config.general.semihosting,
+            ebreak_halt: config.general.ebreak_halt,
+            halted_at: None,
             trace: config.general.trace_instructions,
             bus: system,
             exit_code: None,
@@ -241,19 +469,35 @@ impl Cpu {
             privilege,
             direct_mode,
             mmio_base: config.system.ram_base,
+            misaligned_access: config.memory.misaligned_access,
+            big_endian: config.memory.big_endian,
             stats: SimStats::default(),
             branch_predictor: bp,
-            l1_i_cache: CacheSim::new(&config.cache.l1_i),
-            l1_d_cache: CacheSim::new(&config.cache.l1_d),
-            l2_cache: CacheSim::new(&config.cache.l2),
-            l3_cache: CacheSim::new(&config.cache.l3),
-            mmu: Mmu::new(config.memory.tlb_size),
+            retired_ras: RasCheckpoint::default(),
+            l1_i_cache: CacheSim::with_seed(&config.cache.l1_i, config.cache.random_seed),
+            l1_d_cache: CacheSim::with_seed(&config.cache.l1_d, config.cache.random_seed),
+            l2_cache: CacheSim::with_seed(&config.cache.l2, config.cache.random_seed),
+            l3_cache: CacheSim::with_seed(&config.cache.l3, config.cache.random_seed),
+            mmu: Mmu {
+                svade: config.memory.svade,
+                big_endian: config.memory.big_endian,
+                ..Mmu::new(config.memory.tlb_size)
+            },
             pmp: Pmp::new(),
             load_reservation: None,
+            atomic_line: None,
             pipeline_width: config.pipeline.width,
+            fetch_width: if config.pipeline.fetch_width == 0 {
+                config.pipeline.width
+            } else {
+                config.pipeline.fetch_width
+            },
             clint_divider: config.system.clint_divider,
             last_pc: 0,
             same_pc_count: 0,
+            last_commit_pc: 0,
+            same_commit_pc_count: 0,
+            max_same_pc: config.general.max_same_pc,
             wfi_waiting: false,
             wfi_pc: 0,
             ram_ptr,
@@ -261,8 +505,54 @@ impl Cpu {
             ram_end,
             htif_range: None,
             pc_trace: Vec::with_capacity(PC_TRACE_MAX),
+            symbols: SymbolTable::default(),
             last_invalid_pc_debug: None,
+            asm_dump,
+            commit_log,
             redirect_pending: false,
+            frontend_head_pc: None,
+            functional: false,
+            simple_timing: config.general.simple_timing,
+            simple_mem_latency: config.general.simple_mem_latency,
+            simple_mispredict_penalty: config.general.simple_mispredict_penalty,
+            redirect_penalty: 0,
+            oracle_redirect: false,
+            frontend_depth: config.pipeline.frontend_depth,
+            store_forward_latency: config.pipeline.store_forward_latency,
+            fence_latency: config.pipeline.fence_latency,
+            mul_latency: config.pipeline.mul_latency,
+            div_latency: config.pipeline.div_latency,
+            fdiv_latency: config.pipeline.fdiv_latency,
+            fsqrt_latency: config.pipeline.fsqrt_latency,
+            alu_timer: 0,
+            fu_busy_until: [0; FuncUnit::COUNT],
+            subreg_tracking: config.pipeline.subreg_tracking,
+            hpm_prev: [0; HPM_EVENT_COUNT],
+            semihost_files: Vec::new(),
+            injected_irqs: Vec::new(),
+            injected_mip: 0,
+            trap_log: (config.general.trap_log_size > 0)
+                .then(|| TrapLog::new(config.general.trap_log_size)),
+        }
+    }
+
+    /// Clears the statistics so a measurement starts after a warmup phase.
+    ///
+    /// Caches, predictors, TLBs, and architectural state keep what they
+    /// learned. `mcycle`, `minstret`, and `time` keep counting from where
+    /// they were; the cycles and instructions so far are recorded in
+    /// `stats.warmup_cycles` and `stats.warmup_instructions`. Per-set cache
+    /// counters are zeroed as well.
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+        self.hpm_prev = self.hpm_event_totals();
+        for cache in [
+            &mut self.l1_i_cache,
+            &mut self.l1_d_cache,
+            &mut self.l2_cache,
+            &mut self.l3_cache,
+        ] {
+            cache.clear_set_stats();
         }
     }
 
@@ -275,9 +565,105 @@ impl Cpu {
         self.exit_code.take()
     }
 
-    /// Dumps the current CPU state (PC and registers) to stdout.
+    /// Flushes the assembly dump and commit log to their files, so their
+    /// tails are not lost if the host exits without dropping the `Cpu`.
+    pub fn flush_logs(&mut self) {
+        if let Some(dump) = &mut self.asm_dump
+            && let Err(e) = dump.flush()
+        {
+            eprintln!("[asm-dump] flush failed: {e}");
+        }
+        if let Some(log) = &mut self.commit_log
+            && let Err(e) = log.flush()
+        {
+            eprintln!("[commit-log] flush failed: {e}");
+        }
+    }
+
+    /// Retrieves the PC of the `EBREAK` the hart halted at, resuming it.
+    ///
+    /// # Returns
+    ///
+    /// `Some(pc)` if the hart was halted; execution continues after the
+    /// `EBREAK` on the next tick. `None` otherwise.
+    pub fn take_halt(&mut self) -> Option<u64> {
+        self.halted_at.take()
+    }
+
+    /// Returns the hart to its power-on architectural state, as on a reboot.
+    ///
+    /// Registers and CSRs are cleared (keeping `vlenb`) and the configured
+    /// `misa` and reset `mstatus`, `medeleg`, and `mideleg` are reloaded. The
+    /// hart restarts in its reset privilege mode at the configured start PC,
+    /// and TLBs, PMP, load reservations, functional-unit timing, open
+    /// semihosting files, WFI, and any pending exit are dropped. Memory,
+    /// devices, caches, predictors, and statistics are left untouched. The
+    /// pipeline must be drained first; `Simulator::reset` takes care of that.
+    pub fn reset(&mut self) {
+        use crate::core::arch::csr::VTYPE_VILL;
+
+        self.csrs = Csrs {
+            mstatus: self.reset_mstatus,
+            medeleg: self.reset_medeleg,
+            mideleg: self.reset_mideleg,
+            misa: self.reset_misa,
+            vtype: VTYPE_VILL,
+            vlenb: self.csrs.vlenb,
+            ..Default::default()
+        };
+        self.regs = RegisterFile::with_vlen(self.regs.vlenb() * 8);
+        self.pc = self.reset_pc;
+        self.privilege = self.reset_privilege;
+        self.mmu.dtlb.flush();
+        self.mmu.itlb.flush();
+        self.pmp = Pmp::new();
+        self.load_reservation = None;
+        self.atomic_line = None;
+        self.alu_timer = 0;
+        self.fu_busy_until = [0; FuncUnit::COUNT];
+        self.semihost_files.clear();
+        self.wfi_waiting = false;
+        self.redirect_pending = false;
+        self.frontend_head_pc = None;
+        self.sbi_timer = u64::MAX;
+        self.halted_at = None;
+        self.oracle_redirect = false;
+        self.exit_code = None;
+        self.last_pc = 0;
+        self.same_pc_count = 0;
+        self.last_commit_pc = 0;
+        self.same_commit_pc_count = 0;
+        self.pc_trace.clear();
+        self.injected_irqs.clear();
+        self.injected_mip = 0;
+    }
+
+    /// Resolves `pc` to the nearest preceding ELF function symbol and the
+    /// offset into it.
+    pub fn resolve_symbol(&self, pc: u64) -> Option<(String, u64)> {
+        self.symbols
+            .resolve(pc)
+            .map(|(name, offset)| (name.to_string(), offset))
+    }
+
+    /// Formats `pc` with its symbol, e.g. `0x80000010 <main+0x4>`.
+    pub fn describe_pc(&self, pc: u64) -> String {
+        match self.symbols.describe(pc) {
+            sym if sym.is_empty() => format!("{pc:#x}"),
+            sym => format!("{pc:#x} {sym}"),
+        }
+    }
+
+    /// Dumps the current CPU state (PC, the instruction there, registers, and
+    /// backtrace) to stdout.
     pub fn dump_state(&self) {
-        println!("PC = {:#018x}", self.pc);
+        println!("PC = {:#018x} {}", self.pc, self.symbols.describe(self.pc));
+        if let Some(inst) = self.peek_inst(self.pc) {
+            println!("     {:#010x}  {}", inst, disasm(inst));
+        }
         self.regs.dump();
+        for line in self.backtrace_lines() {
+            println!("{line}");
+        }
     }
 }
@@ -0,0 +1,220 @@
+//! Built-in SBI Firmware.
+//!
+//! Services the Supervisor Binary Interface calls an S-mode kernel would
+//! normally hand to M-mode firmware such as OpenSBI, so a kernel can be
+//! booted directly. When `general.sbi` is set, an `ECALL` from S-mode is
+//! intercepted in `Cpu::trap` and serviced here instead of trapping to
+//! M-mode. The extension ID is taken from `a7` and the function ID from
+//! `a6`; results go to `a0` (error) and `a1` (value), and execution resumes
+//! after the `ECALL` in S-mode. This module implements:
+//! 1. **Legacy (v0.1):** `set_timer`, `console_putchar`, `console_getchar`,
+//!    `clear_ipi`, `send_ipi`, the remote fences, and `shutdown`. These
+//!    return a single value in `a0`.
+//! 2. **Base:** spec/implementation versions and extension probing.
+//! 3. **TIME:** `set_timer`, which drives `mip.STIP` from `mtime`.
+//! 4. **IPI:** `send_ipi`, which raises `mip.SSIP` on this hart.
+//! 5. **SRST:** `system_reset`, as a shutdown or a reboot.
+//!
+//! Other extensions return `SBI_ERR_NOT_SUPPORTED`.
+
+use super::Cpu;
+use crate::core::arch::csr;
+use crate::isa::abi;
+use crate::soc::devices::syscon::RESET_REQUEST;
+use std::io::Write;
+use std::sync::atomic::Ordering;
+
+/// Legacy `sbi_set_timer`.
+pub const LEGACY_SET_TIMER: u64 = 0x00;
+/// Legacy `sbi_console_putchar`.
+pub const LEGACY_CONSOLE_PUTCHAR: u64 = 0x01;
+/// Legacy `sbi_console_getchar`.
+pub const LEGACY_CONSOLE_GETCHAR: u64 = 0x02;
+/// Legacy `sbi_clear_ipi`.
+pub const LEGACY_CLEAR_IPI: u64 = 0x03;
+/// Legacy `sbi_send_ipi`.
+pub const LEGACY_SEND_IPI: u64 = 0x04;
+/// Legacy `sbi_remote_fence_i`.
+pub const LEGACY_REMOTE_FENCE_I: u64 = 0x05;
+/// Legacy `sbi_remote_sfence_vma`.
+pub const LEGACY_REMOTE_SFENCE_VMA: u64 = 0x06;
+/// Legacy `sbi_remote_sfence_vma_asid`.
+pub const LEGACY_REMOTE_SFENCE_VMA_ASID: u64 = 0x07;
+/// Legacy `sbi_shutdown`.
+pub const LEGACY_SHUTDOWN: u64 = 0x08;
+
+/// Base extension.
+pub const EXT_BASE: u64 = 0x10;
+/// Timer extension ("TIME").
+pub const EXT_TIME: u64 = 0x5449_4D45;
+/// IPI extension ("sPI").
+pub const EXT_IPI: u64 = 0x0073_5049;
+/// System reset extension ("SRST").
+pub const EXT_SRST: u64 = 0x5352_5354;
+
+/// The call completed successfully.
+pub const SBI_SUCCESS: i64 = 0;
+/// The call failed.
+pub const SBI_ERR_FAILED: i64 = -1;
+/// The extension or function is not implemented.
+pub const SBI_ERR_NOT_SUPPORTED: i64 = -2;
+/// A parameter is invalid.
+pub const SBI_ERR_INVALID_PARAM: i64 = -3;
+
+/// SBI specification version reported by the Base extension (v2.0).
+pub const SBI_SPEC_VERSION: u64 = 2 << 24;
+/// Implementation ID reported by the Base extension; outside the range
+/// assigned to real firmware.
+pub const SBI_IMPL_ID: u64 = 0x7276;
+/// Implementation version reported by the Base extension.
+pub const SBI_IMPL_VERSION: u64 = 1;
+
+/// `sbi_system_reset` type: shut the system down.
+const RESET_TYPE_SHUTDOWN: u64 = 0;
+/// `sbi_system_reset` type: cold reboot.
+const RESET_TYPE_COLD_REBOOT: u64 = 1;
+/// `sbi_system_reset` type: warm reboot.
+const RESET_TYPE_WARM_REBOOT: u64 = 2;
+
+/// `hart_mask_base` value selecting every hart.
+const ALL_HARTS: u64 = u64::MAX;
+
+/// Result of an SBI v0.2+ call: `(error, value)`.
+type SbiRet = (i64, u64);
+
+impl Cpu {
+    /// Services an SBI call made by the `ECALL` at `epc`.
+    ///
+    /// Writes the result to `a0`/`a1` (legacy calls only write `a0`) and
+    /// moves the PC past the `ECALL`; the hart stays in S-mode. Only called
+    /// when built-in SBI is enabled.
+    pub fn sbi_call(&mut self, epc: u64) {
+        let eid = self.regs.read(abi::REG_A7);
+        let fid = self.regs.read(abi::REG_A6);
+        let args = [abi::REG_A0, abi::REG_A1, abi::REG_A2].map(|r| self.regs.read(r));
+
+        if eid <= LEGACY_SHUTDOWN {
+            let result = self.sbi_legacy(eid, args[0]);
+            self.regs.write(abi::REG_A0, result as u64);
+        } else {
+            let (error, value) = match eid {
+                EXT_BASE => self.sbi_base(fid, args[0]),
+                EXT_TIME if fid == 0 => self.sbi_set_timer(args[0]),
+                EXT_IPI if fid == 0 => self.sbi_send_ipi(args[0], args[1]),
+                EXT_SRST if fid == 0 => self.sbi_system_reset(args[0], args[1]),
+                _ => (SBI_ERR_NOT_SUPPORTED, 0),
+            };
+            self.regs.write(abi::REG_A0, error as u64);
+            self.regs.write(abi::REG_A1, value);
+        }
+        self.pc = epc + 4;
+    }
+
+    /// Legacy (v0.1) calls. Returns the value for `a0`.
+    fn sbi_legacy(&mut self, eid: u64, arg: u64) -> i64 {
+        match eid {
+            LEGACY_SET_TIMER => self.sbi_set_timer(arg).0,
+            LEGACY_CONSOLE_PUTCHAR => {
+                let mut stdout = std::io::stdout();
+                match stdout.write_all(&[arg as u8]).and_then(|()| stdout.flush()) {
+                    Ok(()) => SBI_SUCCESS,
+                    Err(_) => SBI_ERR_FAILED,
+                }
+            }
+            // No console input is wired up; report "no character".
+            LEGACY_CONSOLE_GETCHAR => -1,
+            LEGACY_CLEAR_IPI => {
+                self.csrs.mip &= !csr::MIP_SSIP;
+                SBI_SUCCESS
+            }
+            LEGACY_SEND_IPI => {
+                // `arg` points to the hart mask; null selects every hart.
+                let (mask, base) = if arg == 0 {
+                    (0, ALL_HARTS)
+                } else {
+                    match self.guest_u64(arg) {
+                        Some(mask) => (mask, 0),
+                        None => return SBI_ERR_INVALID_PARAM,
+                    }
+                };
+                self.sbi_send_ipi(mask, base).0
+            }
+            LEGACY_REMOTE_FENCE_I => {
+                self.l1_i_cache.flush();
+                SBI_SUCCESS
+            }
+            LEGACY_REMOTE_SFENCE_VMA | LEGACY_REMOTE_SFENCE_VMA_ASID => {
+                self.mmu.sfence_vma(None, None);
+                SBI_SUCCESS
+            }
+            _ => {
+                self.exit_code = Some(0);
+                SBI_SUCCESS
+            }
+        }
+    }
+
+    /// Base extension: version queries and extension probing.
+    fn sbi_base(&self, fid: u64, arg: u64) -> SbiRet {
+        match fid {
+            0 => (SBI_SUCCESS, SBI_SPEC_VERSION),
+            1 => (SBI_SUCCESS, SBI_IMPL_ID),
+            2 => (SBI_SUCCESS, SBI_IMPL_VERSION),
+            3 => {
+                let present = matches!(
+                    arg,
+                    0..=LEGACY_SHUTDOWN | EXT_BASE | EXT_TIME | EXT_IPI | EXT_SRST
+                );
+                (SBI_SUCCESS, present as u64)
+            }
+            // mvendorid, marchid, and mimpid all read as zero.
+            4..=6 => (SBI_SUCCESS, 0),
+            _ => (SBI_ERR_NOT_SUPPORTED, 0),
+        }
+    }
+
+    /// `sbi_set_timer`: raises `mip.STIP` once `mtime` reaches `deadline`,
+    /// clearing any pending timer interrupt until then.
+    fn sbi_set_timer(&mut self, deadline: u64) -> SbiRet {
+        self.sbi_timer = deadline;
+        self.update_stip();
+        (SBI_SUCCESS, 0)
+    }
+
+    /// `sbi_send_ipi`: raises `mip.SSIP` if this hart is in the mask.
+    fn sbi_send_ipi(&mut self, mask: u64, base: u64) -> SbiRet {
+        let targeted = if base == ALL_HARTS {
+            true
+        } else {
+            // This is the only hart; naming any other is invalid.
+            let ours = self
+                .hartid
+                .checked_sub(base)
+                .filter(|&bit| bit < 64)
+                .map_or(0, |bit| 1 << bit);
+            if mask & !ours != 0 {
+                return (SBI_ERR_INVALID_PARAM, 0);
+            }
+            mask & ours != 0
+        };
+        if targeted {
+            self.csrs.mip |= csr::MIP_SSIP;
+        }
+        (SBI_SUCCESS, 0)
+    }
+
+    /// `sbi_system_reset`: shuts down (exit code 0, or 1 for a
+    /// system-failure reason) or requests a reboot.
+    fn sbi_system_reset(&mut self, reset_type: u64, reason: u64) -> SbiRet {
+        match reset_type {
+            RESET_TYPE_SHUTDOWN => self.exit_code = Some((reason != 0) as u64),
+            RESET_TYPE_COLD_REBOOT | RESET_TYPE_WARM_REBOOT => {
+                self.bus
+                    .exit_request
+                    .store(RESET_REQUEST, Ordering::Relaxed);
+            }
+            _ => return (SBI_ERR_INVALID_PARAM, 0),
+        }
+        (SBI_SUCCESS, 0)
+    }
+}
@@ -0,0 +1,267 @@
+//! RISC-V Semihosting.
+//!
+//! Lets bare-metal programs use host I/O without a device driver. A
+//! semihosting call is an `EBREAK` wrapped in the magic sequence
+//!
+//! ```text
+//! slli x0, x0, 0x1f
+//! ebreak
+//! srai x0, x0, 7
+//! ```
+//!
+//! with the operation number in `a0` and the parameter (usually a pointer to
+//! a block of XLEN-sized words) in `a1`. The result is written back to `a0`
+//! and execution resumes after the `EBREAK`. Calls are serviced only with
+//! `general.semihosting` set and only from M- or S-mode; otherwise the
+//! sequence takes the ordinary breakpoint trap. This module implements:
+//! 1. **Console:** `SYS_WRITEC` and `SYS_WRITE0` print to the host's stdout.
+//! 2. **Files:** `SYS_OPEN`, `SYS_CLOSE`, `SYS_WRITE`, and `SYS_READ` on host
+//!    files, with `":tt"` naming the console.
+//! 3. **Exit:** `SYS_EXIT` and `SYS_EXIT_EXTENDED` end the simulation.
+//!
+//! Unsupported operations return -1.
+
+use super::Cpu;
+use crate::common::AccessType;
+use crate::core::arch::mode::PrivilegeMode;
+use crate::core::pipeline::backend::shared::commit::write_store;
+use crate::core::pipeline::signals::MemWidth;
+use crate::isa::abi;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+
+/// `slli x0, x0, 0x1f`: marks the instruction before a semihosting `EBREAK`.
+pub const SEMIHOST_ENTRY: u32 = 0x01f0_1013;
+/// `srai x0, x0, 7`: marks the instruction after a semihosting `EBREAK`.
+pub const SEMIHOST_EXIT: u32 = 0x4070_5013;
+
+/// Open a host file.
+pub const SYS_OPEN: u64 = 0x01;
+/// Close a handle returned by `SYS_OPEN`.
+pub const SYS_CLOSE: u64 = 0x02;
+/// Write the character at `a1` to the console.
+pub const SYS_WRITEC: u64 = 0x03;
+/// Write the NUL-terminated string at `a1` to the console.
+pub const SYS_WRITE0: u64 = 0x04;
+/// Write a buffer to a handle.
+pub const SYS_WRITE: u64 = 0x05;
+/// Read from a handle into a buffer.
+pub const SYS_READ: u64 = 0x06;
+/// Report an exception or application exit.
+pub const SYS_EXIT: u64 = 0x18;
+/// Report an exit with an explicit reason and subcode.
+pub const SYS_EXIT_EXTENDED: u64 = 0x20;
+
+/// `ADP_Stopped_ApplicationExit`: the reason code for a normal exit.
+pub const ADP_STOPPED_APPLICATION_EXIT: u64 = 0x2_0026;
+
+/// Longest string read for `SYS_WRITE0` or a file name.
+const MAX_STRING: u64 = 4096;
+
+/// A host stream behind a semihosting handle.
+#[derive(Debug)]
+pub enum SemihostFile {
+    /// The host's standard input (`":tt"` opened for reading).
+    Stdin,
+    /// The host's standard output (`":tt"` opened for writing).
+    Stdout,
+    /// The host's standard error (`":tt"` opened for appending).
+    Stderr,
+    /// A host file.
+    File(File),
+}
+
+impl Cpu {
+    /// Services a semihosting call if the `EBREAK` at `epc` is wrapped in the
+    /// semihosting sequence.
+    ///
+    /// Returns `false` (leaving all state untouched) for a plain `EBREAK`,
+    /// when semihosting is disabled, or when the hart is in U-mode.
+    /// Otherwise performs the call, writes the result to `a0`, moves the PC
+    /// past the `EBREAK`, and returns `true`.
+    pub fn semihosting_call(&mut self, epc: u64) -> bool {
+        if !self.semihosting || self.privilege == PrivilegeMode::User {
+            return false;
+        }
+        let Some(entry) = epc.checked_sub(4) else {
+            return false;
+        };
+        if self.fetch_word(entry) != Some(SEMIHOST_ENTRY)
+            || self.fetch_word(epc + 4) != Some(SEMIHOST_EXIT)
+        {
+            return false;
+        }
+
+        let op = self.regs.read(abi::REG_A0);
+        let param = self.regs.read(abi::REG_A1);
+        let result = match op {
+            SYS_OPEN => self.semihost_open(param),
+            SYS_CLOSE => self.semihost_close(param),
+            SYS_WRITEC => self.semihost_writec(param),
+            SYS_WRITE0 => self.semihost_write0(param),
+            SYS_WRITE => self.semihost_write(param),
+            SYS_READ => self.semihost_read(param),
+            SYS_EXIT => self.semihost_exit(param, false),
+            SYS_EXIT_EXTENDED => self.semihost_exit(param, true),
+            _ => None,
+        };
+        self.regs.write(abi::REG_A0, result.unwrap_or(u64::MAX));
+        self.pc = epc + 4;
+        true
+    }
+
+    /// `SYS_OPEN`: `[name, mode, name_len]`. Returns the new handle.
+    fn semihost_open(&mut self, param: u64) -> Option<u64> {
+        let name_ptr = self.guest_u64(param)?;
+        let mode = self.guest_u64(param + 8)?;
+        let len = self.guest_u64(param + 16)?;
+        let name = String::from_utf8(self.guest_bytes(name_ptr, len)?).ok()?;
+
+        let file = if name == ":tt" {
+            match mode {
+                0..=3 => SemihostFile::Stdin,
+                4..=7 => SemihostFile::Stdout,
+                _ => SemihostFile::Stderr,
+            }
+        } else {
+            // Modes follow fopen: r, r+, w, w+, a, a+ (each also as binary).
+            let mut options = OpenOptions::new();
+            let update = mode & 2 != 0;
+            match mode >> 2 {
+                0 => options.read(true).write(update),
+                1 => options.write(true).create(true).truncate(true).read(update),
+                2 => options.append(true).create(true).read(update),
+                _ => return None,
+            };
+            SemihostFile::File(options.open(&name).ok()?)
+        };
+
+        let handle = match self.semihost_files.iter().position(Option::is_none) {
+            Some(free) => {
+                self.semihost_files[free] = Some(file);
+                free
+            }
+            None => {
+                self.semihost_files.push(Some(file));
+                self.semihost_files.len() - 1
+            }
+        };
+        Some(handle as u64)
+    }
+
+    /// `SYS_CLOSE`: `[handle]`. Returns 0.
+    fn semihost_close(&mut self, param: u64) -> Option<u64> {
+        let handle = self.guest_u64(param)? as usize;
+        self.semihost_files.get_mut(handle)?.take()?;
+        Some(0)
+    }
+
+    /// `SYS_WRITEC`: prints the byte at `param`.
+    fn semihost_writec(&mut self, param: u64) -> Option<u64> {
+        let byte = self.guest_bytes(param, 1)?;
+        let mut stdout = std::io::stdout();
+        stdout.write_all(&byte).ok()?;
+        stdout.flush().ok()?;
+        Some(0)
+    }
+
+    /// `SYS_WRITE0`: prints the NUL-terminated string at `param`.
+    fn semihost_write0(&mut self, param: u64) -> Option<u64> {
+        let mut bytes = Vec::new();
+        for addr in param..param + MAX_STRING {
+            match self.guest_bytes(addr, 1)?[0] {
+                0 => break,
+                byte => bytes.push(byte),
+            }
+        }
+        let mut stdout = std::io::stdout();
+        stdout.write_all(&bytes).ok()?;
+        stdout.flush().ok()?;
+        Some(0)
+    }
+
+    /// `SYS_WRITE`: `[handle, buf, len]`. Returns the number of bytes *not*
+    /// written.
+    fn semihost_write(&mut self, param: u64) -> Option<u64> {
+        let handle = self.guest_u64(param)? as usize;
+        let buf = self.guest_u64(param + 8)?;
+        let len = self.guest_u64(param + 16)?;
+        let data = self.guest_bytes(buf, len)?;
+        let written = match self.semihost_files.get_mut(handle)?.as_mut()? {
+            SemihostFile::Stdin => return None,
+            SemihostFile::Stdout => {
+                let mut stdout = std::io::stdout();
+                stdout.write_all(&data).and_then(|()| stdout.flush()).ok()?;
+                data.len()
+            }
+            SemihostFile::Stderr => {
+                std::io::stderr().write_all(&data).ok()?;
+                data.len()
+            }
+            SemihostFile::File(file) => file.write(&data).ok()?,
+        };
+        Some(len - written as u64)
+    }
+
+    /// `SYS_READ`: `[handle, buf, len]`. Returns the number of bytes *not*
+    /// read (`len` at end of file).
+    fn semihost_read(&mut self, param: u64) -> Option<u64> {
+        let handle = self.guest_u64(param)? as usize;
+        let buf = self.guest_u64(param + 8)?;
+        let len = self.guest_u64(param + 16)?;
+        let mut data = vec![0u8; len.min(1 << 20) as usize];
+        let read = match self.semihost_files.get_mut(handle)?.as_mut()? {
+            SemihostFile::Stdin => std::io::stdin().read(&mut data).ok()?,
+            SemihostFile::File(file) => file.read(&mut data).ok()?,
+            SemihostFile::Stdout | SemihostFile::Stderr => return None,
+        };
+        for (i, &byte) in data[..read].iter().enumerate() {
+            let paddr = self
+                .translate_functional(buf + i as u64, AccessType::Write)
+                .ok()?;
+            write_store(self, paddr, MemWidth::Byte, byte as u64).ok()?;
+        }
+        Some(len - read as u64)
+    }
+
+    /// `SYS_EXIT` / `SYS_EXIT_EXTENDED`: `[reason, subcode]`.
+    ///
+    /// A normal application exit ends the simulation with the subcode as the
+    /// exit code; any other reason exits with 1. `SYS_EXIT` also accepts the
+    /// bare reason code in `a1`, as 32-bit programs pass it.
+    fn semihost_exit(&mut self, param: u64, extended: bool) -> Option<u64> {
+        let (reason, subcode) = if !extended && param == ADP_STOPPED_APPLICATION_EXIT {
+            (param, 0)
+        } else {
+            (self.guest_u64(param)?, self.guest_u64(param + 8)?)
+        };
+        self.exit_code = Some(if reason == ADP_STOPPED_APPLICATION_EXIT {
+            subcode
+        } else {
+            1
+        });
+        Some(0)
+    }
+
+    /// Fetches the 32-bit word at virtual address `vaddr`.
+    pub(super) fn fetch_word(&mut self, vaddr: u64) -> Option<u32> {
+        let paddr = self.translate_functional(vaddr, AccessType::Fetch).ok()?;
+        Some(self.read_phys(paddr, MemWidth::Word, false).ok()? as u32)
+    }
+
+    /// Reads the doubleword at virtual address `vaddr`.
+    pub(super) fn guest_u64(&mut self, vaddr: u64) -> Option<u64> {
+        let paddr = self.translate_functional(vaddr, AccessType::Read).ok()?;
+        self.read_data(paddr, MemWidth::Double, false).ok()
+    }
+
+    /// Reads `len` bytes starting at virtual address `vaddr`.
+    fn guest_bytes(&mut self, vaddr: u64, len: u64) -> Option<Vec<u8>> {
+        (vaddr..vaddr.checked_add(len)?)
+            .map(|addr| {
+                let paddr = self.translate_functional(addr, AccessType::Read).ok()?;
+                Some(self.read_phys(paddr, MemWidth::Byte, false).ok()? as u8)
+            })
+            .collect()
+    }
+}
@@ -8,10 +8,12 @@
 //! 4. **Return Handling:** Implements `MRET` and `SRET` instructions for returning from trap handlers.
 
 use super::Cpu;
+use super::trap_log::TrapRecord;
 use crate::common::Trap;
 use crate::common::constants::CAUSE_INTERRUPT_BIT;
 use crate::core::arch::csr;
 use crate::core::arch::mode::PrivilegeMode;
+use crate::core::arch::trap::TrapHandler;
 use crate::isa::abi;
 use crate::isa::privileged::cause::{exception, interrupt};
 use crate::isa::privileged::opcodes as sys_ops;
@@ -25,6 +27,31 @@ impl Cpu {
     /// * `epc` - The Exception Program Counter (PC where the trap occurred).
     pub fn trap(&mut self, cause: Trap, epc: u64) {
         self.load_reservation = None;
+        // An injected interrupt is one-shot: taking it lowers the pending bit.
+        if let Some(bit) = TrapHandler::trap_to_irq(&cause)
+            && self.injected_mip & bit != 0
+        {
+            self.injected_mip &= !bit;
+            self.csrs.mip &= !bit;
+        }
+
+        if matches!(cause, Trap::Breakpoint(_)) && self.semihosting_call(epc) {
+            return;
+        }
+        if self.sbi && matches!(cause, Trap::EnvironmentCallFromSMode) {
+            self.sbi_call(epc);
+            return;
+        }
+        if self.ebreak_halt && matches!(cause, Trap::Breakpoint(_)) {
+            // Halt for the host; resuming continues after the EBREAK.
+            let len = match self.fetch_word(epc) {
+                Some(inst) if inst & 0b11 != 0b11 => 2,
+                _ => 4,
+            };
+            self.halted_at = Some(epc);
+            self.pc = epc + len;
+            return;
+        }
 
         if self.direct_mode {
             // In direct mode, ecall is handled here at commit time so that
@@ -50,8 +77,10 @@ impl Cpu {
 
                 // Unknown syscall in direct mode — treat as fatal.
                 eprintln!(
-                    "\n[!] Unhandled ecall in direct mode: a7={} a0={} at PC {:#x}",
-                    val_a7, val_a0, epc
+                    "\n[!] Unhandled ecall in direct mode: a7={} a0={} at PC {}",
+                    val_a7,
+                    val_a0,
+                    self.describe_pc(epc)
                 );
                 self.exit_code = Some(1);
                 return;
@@ -63,9 +92,13 @@ impl Cpu {
                 return;
             }
             eprintln!(
-                "\n[!] Fatal trap in direct mode: {:?} at PC {:#x}",
-                cause, epc
+                "\n[!] Fatal trap in direct mode: {:?} at PC {}",
+                cause,
+                self.describe_pc(epc)
             );
+            for line in self.backtrace_lines() {
+                eprintln!("{line}");
+            }
             self.exit_code = Some(1);
             return;
         }
@@ -84,13 +117,18 @@ impl Cpu {
         if self.trace {
             if self.csrs.stvec == 0x80000530 || epc == 0x80000530 {
                 println!(
-                    "[Trap] Cause: {:?} | EPC: {:#x} | Priv: {} | STVEC: {:#x}",
-                    cause, epc, self.privilege, self.csrs.stvec
+                    "[Trap] Cause: {:?} | EPC: {} | Priv: {} | STVEC: {:#x}",
+                    cause,
+                    self.describe_pc(epc),
+                    self.privilege,
+                    self.csrs.stvec
                 );
             } else if !is_timer && !is_ecall {
                 println!(
-                    "[Trap] Cause: {:?} | EPC: {:#x} | Priv: {}",
-                    cause, epc, self.privilege
+                    "[Trap] Cause: {:?} | EPC: {} | Priv: {}",
+                    cause,
+                    self.describe_pc(epc),
+                    self.privilege
                 );
             }
         }
@@ -321,6 +359,18 @@ impl Cpu {
             self.pc = target_pc;
         }
 
+        if let Some(log) = &mut self.trap_log {
+            log.record(TrapRecord {
+                pc: epc,
+                cause: if is_interrupt {
+                    CAUSE_INTERRUPT_BIT | code
+                } else {
+                    code
+                },
+                tval,
+                privilege: self.privilege,
+            });
+        }
         self.stats.traps_taken += 1;
     }
 
@@ -0,0 +1,96 @@
+//! Trap Log.
+//!
+//! Records the traps a hart takes, for tracking down trap loops and the
+//! fault that started them without full instruction tracing. It provides:
+//! 1. **Recording:** Each trap delivered to a handler is stored as a
+//!    `TrapRecord` with its `epc`, cause, `tval`, and the mode it entered.
+//! 2. **Bounded history:** Entries are kept in a ring buffer; once full, the
+//!    oldest entry is dropped for each new one.
+//!
+//! Calls serviced by the simulator itself (semihosting, built-in SBI, and
+//! direct-mode syscalls) never reach a handler and are not recorded.
+
+use std::collections::VecDeque;
+
+use super::Cpu;
+use crate::core::arch::mode::PrivilegeMode;
+
+/// One taken trap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrapRecord {
+    /// PC of the trapping or interrupted instruction.
+    pub pc: u64,
+    /// Cause as written to `xcause`, with bit 63 set for interrupts.
+    pub cause: u64,
+    /// Value written to `xtval`.
+    pub tval: u64,
+    /// Privilege mode the handler runs in.
+    pub privilege: PrivilegeMode,
+}
+
+/// Ring buffer of recent traps.
+#[derive(Clone, Debug)]
+pub struct TrapLog {
+    /// Recorded traps, oldest first.
+    entries: VecDeque<TrapRecord>,
+    /// Maximum number of entries kept.
+    capacity: usize,
+}
+
+impl TrapLog {
+    /// Creates an empty log holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(4096)),
+            capacity,
+        }
+    }
+
+    /// Records `trap`, dropping the oldest entry if the log is full.
+    pub fn record(&mut self, trap: TrapRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            let _ = self.entries.pop_front();
+        }
+        self.entries.push_back(trap);
+    }
+
+    /// Removes and returns all recorded traps, oldest first.
+    pub fn drain(&mut self) -> Vec<TrapRecord> {
+        self.entries.drain(..).collect()
+    }
+
+    /// Returns the number of recorded traps.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Cpu {
+    /// Starts recording taken traps in a ring buffer of `capacity` entries,
+    /// replacing any existing log.
+    pub fn enable_trap_log(&mut self, capacity: usize) {
+        self.trap_log = Some(TrapLog::new(capacity));
+    }
+
+    /// Stops recording traps and discards the log.
+    pub fn disable_trap_log(&mut self) {
+        self.trap_log = None;
+    }
+
+    /// Removes and returns all recorded traps, oldest first. Empty if the
+    /// log is disabled.
+    pub fn drain_trap_log(&mut self) -> Vec<TrapRecord> {
+        self.trap_log
+            .as_mut()
+            .map(TrapLog::drain)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,89 @@
+//! Vector Extension Execution (RVV-lite).
+//!
+//! This module implements the architectural effects of the supported vector
+//! instructions. It performs the following:
+//! 1. **Configuration:** Computes the new `vl`/`vtype` for `vsetvli`/`vsetvl`.
+//! 2. **Legality:** Reports whether the current `vtype` allows vector arithmetic.
+//! 3. **Arithmetic:** Applies element-wise `vadd.vv`/`vsub.vv` to the vector registers.
+//!
+//! Only SEW=32 with LMUL=1 is supported; any other `vtype` sets `vill`.
+
+use super::Cpu;
+use crate::core::arch::csr::{
+    VTYPE_FIELDS_MASK, VTYPE_VILL, VTYPE_VLMUL_1, VTYPE_VLMUL_MASK, VTYPE_VSEW_32, VTYPE_VSEW_MASK,
+    VTYPE_VSEW_SHIFT,
+};
+use crate::core::pipeline::signals::VectorOp;
+use crate::isa::instruction::InstructionBits;
+
+/// Bytes per element at the only supported SEW (32 bits).
+const SEW32_BYTES: u64 = 4;
+
+impl Cpu {
+    /// Returns the maximum vector length for 32-bit elements at LMUL=1.
+    pub fn vlmax(&self) -> u64 {
+        self.csrs.vlenb / SEW32_BYTES
+    }
+
+    /// Computes the result of a `vsetvli`/`vsetvl` without applying it.
+    ///
+    /// # Arguments
+    ///
+    /// * `rs1` - AVL source register index.
+    /// * `rd` - Destination register index.
+    /// * `avl` - Application vector length (value of `rs1`).
+    /// * `vtype` - Requested vtype (zimm or value of `rs2`).
+    ///
+    /// # Returns
+    ///
+    /// `(vl, vtype)`: the new vector length (also the value written to `rd`)
+    /// and the new `vtype`, which is `VTYPE_VILL` with `vl = 0` when the
+    /// requested configuration is unsupported.
+    pub fn vsetvl(&self, rs1: usize, rd: usize, avl: u64, vtype: u64) -> (u64, u64) {
+        let vsew = (vtype >> VTYPE_VSEW_SHIFT) & VTYPE_VSEW_MASK;
+        let vlmul = vtype & VTYPE_VLMUL_MASK;
+        if vtype & !VTYPE_FIELDS_MASK != 0 || vsew != VTYPE_VSEW_32 || vlmul != VTYPE_VLMUL_1 {
+            return (0, VTYPE_VILL);
+        }
+
+        let vl = match (rs1, rd) {
+            // rs1 = x0, rd = x0: keep the current vl.
+            (0, 0) => self.csrs.vl,
+            // rs1 = x0, rd != x0: request the maximum.
+            (0, _) => self.vlmax(),
+            _ => avl.min(self.vlmax()),
+        };
+        (vl, vtype)
+    }
+
+    /// Returns true if the current `vtype` is illegal, in which case any
+    /// vector arithmetic instruction raises an illegal-instruction exception.
+    pub fn vector_illegal(&self) -> bool {
+        self.csrs.vtype & VTYPE_VILL != 0
+    }
+
+    /// Applies a vector arithmetic instruction to the vector registers.
+    ///
+    /// Processes elements `vstart..vl` of `vd = vs2 op vs1` and then resets
+    /// `vstart` to zero. Tail elements are left undisturbed.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - The vector operation (`Add` or `Sub`).
+    /// * `inst` - The instruction encoding, for the register fields.
+    pub fn execute_vector(&mut self, op: VectorOp, inst: u32) {
+        let (vd, vs1, vs2) = (inst.rd(), inst.rs1(), inst.rs2());
+        for i in self.csrs.vstart..self.csrs.vl {
+            let i = i as usize;
+            let a = self.regs.read_v32(vs2, i);
+            let b = self.regs.read_v32(vs1, i);
+            let val = match op {
+                VectorOp::Add => a.wrapping_add(b),
+                VectorOp::Sub => a.wrapping_sub(b),
+                VectorOp::None | VectorOp::SetVl => continue,
+            };
+            self.regs.write_v32(vd, i, val);
+        }
+        self.csrs.vstart = 0;
+    }
+}
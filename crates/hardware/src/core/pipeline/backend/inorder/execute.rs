@@ -7,11 +7,12 @@ use crate::common::error::{ExceptionStage, Trap};
 use crate::core::Cpu;
 use crate::core::pipeline::latches::{ExMem1Entry, RenameIssueEntry};
 use crate::core::pipeline::rob::{CsrUpdate, Rob};
-use crate::core::pipeline::signals::{AluOp, CsrOp, OpASrc, OpBSrc};
+use crate::core::pipeline::signals::{AluOp, ControlSignals, CsrOp, OpASrc, OpBSrc, VectorOp};
+use crate::core::pipeline::subreg;
 use crate::core::units::alu::Alu;
 use crate::core::units::bru::BranchPredictor;
+use crate::core::units::bru::ras::ras_hint;
 use crate::core::units::fpu::Fpu;
-use crate::isa::abi;
 use crate::isa::privileged::opcodes as sys_ops;
 use crate::isa::rv64i::{funct3, opcodes};
 
@@ -19,6 +20,10 @@ const FUNCT3_SHIFT: u32 = 12;
 const FUNCT3_MASK: u32 = 0x7;
 const JALR_ALIGNMENT_MASK: u64 = !1;
 
+/// Misprediction penalty the modeled frontend stages already impose; deeper
+/// frontends pay the difference as refetch bubbles.
+const MODELED_FRONTEND_DEPTH: u64 = 2;
+
 /// Executes instructions in the in-order backend.
 ///
 /// Takes issued instructions, performs ALU/FPU operations, resolves branches,
@@ -88,10 +93,12 @@ pub fn execute_inorder(
         };
         let op_c = fwd_c;
 
-        // FENCE.I: flush caches and frontend
+        // FENCE.I: flush caches and frontend; the refetch waits out the
+        // fence latency
         if id.ctrl.is_fence_i {
+            cpu.redirect_penalty += cpu.fence_latency.saturating_sub(1);
             cpu.l1_d_cache.flush();
-            cpu.l1_i_cache.flush();
+            cpu.l1_i_cache.invalidate_all();
             cpu.pc = id.pc.wrapping_add(id.inst_size);
             cpu.redirect_pending = true;
             flush_remaining = true;
@@ -253,8 +260,10 @@ pub fn execute_inorder(
                 }
 
                 cpu.clear_reservation();
-                cpu.mmu.dtlb.flush();
-                cpu.mmu.itlb.flush();
+                cpu.mmu.sfence_vma(
+                    (id.rs1 != 0).then_some(fwd_a),
+                    (id.rs2 != 0).then_some(fwd_b as u16),
+                );
                 cpu.l1_d_cache.flush();
                 cpu.l1_i_cache.flush();
 
@@ -304,35 +313,7 @@ pub fn execute_inorder(
 
             // CSR operations: compute old/new but defer write to commit
             if id.ctrl.csr_op != CsrOp::None {
-                // In S-mode, SATP access is illegal if mstatus.TVM=1
-                if id.ctrl.csr_addr == crate::core::arch::csr::SATP
-                    && cpu.privilege == crate::core::arch::mode::PrivilegeMode::Supervisor
-                    && ((cpu.csrs.mstatus >> 20) & 1) != 0
-                {
-                    rob.fault(
-                        id.rob_tag,
-                        Trap::IllegalInstruction(id.inst),
-                        ExceptionStage::Execute,
-                    );
-                    flush_remaining = true;
-                    results.push(ExMem1Entry {
-                        rob_tag: id.rob_tag,
-                        pc: id.pc,
-                        inst: id.inst,
-                        inst_size: id.inst_size,
-                        rd: id.rd,
-                        alu: 0,
-                        store_data: 0,
-                        ctrl: id.ctrl,
-                        trap: None,
-                        exception_stage: None,
-                    });
-                    continue;
-                }
-
-                // Privilege check: CSR bits [9:8] encode minimum privilege level.
-                let csr_priv = (id.ctrl.csr_addr >> 8) & 3;
-                if (cpu.privilege.to_u8() as u32) < csr_priv {
+                if !csr_access_allowed(cpu, &id.ctrl, id.rs1) {
                     rob.fault(
                         id.rob_tag,
                         Trap::IllegalInstruction(id.inst),
@@ -354,51 +335,13 @@ pub fn execute_inorder(
                     continue;
                 }
 
-                // Read-only check: CSR bits [11:10] == 0b11 means read-only.
-                // CSRRW/CSRRWI always write. CSRRS/CSRRC/CSRRSI/CSRRCI write
-                // only when rs1 (or uimm) != 0.
-                let read_only = (id.ctrl.csr_addr >> 10) & 3 == 3;
-                if read_only {
-                    let would_write = match id.ctrl.csr_op {
-                        CsrOp::Rw | CsrOp::Rwi => true,
-                        CsrOp::Rs | CsrOp::Rc => id.rs1 != 0,
-                        CsrOp::Rsi | CsrOp::Rci => (id.rs1 & 0x1f) != 0,
-                        CsrOp::None => false,
-                    };
-                    if would_write {
-                        rob.fault(
-                            id.rob_tag,
-                            Trap::IllegalInstruction(id.inst),
-                            ExceptionStage::Execute,
-                        );
-                        flush_remaining = true;
-                        results.push(ExMem1Entry {
-                            rob_tag: id.rob_tag,
-                            pc: id.pc,
-                            inst: id.inst,
-                            inst_size: id.inst_size,
-                            rd: id.rd,
-                            alu: 0,
-                            store_data: 0,
-                            ctrl: id.ctrl,
-                            trap: None,
-                            exception_stage: None,
-                        });
-                        continue;
-                    }
-                }
-
                 let old = cpu.csr_read(id.ctrl.csr_addr);
-                let src = match id.ctrl.csr_op {
-                    CsrOp::Rwi | CsrOp::Rsi | CsrOp::Rci => (id.rs1 as u64) & 0x1f,
-                    _ => fwd_a,
-                };
-                let new = match id.ctrl.csr_op {
-                    CsrOp::Rw | CsrOp::Rwi => src,
-                    CsrOp::Rs | CsrOp::Rsi => old | src,
-                    CsrOp::Rc | CsrOp::Rci => old & !src,
-                    CsrOp::None => old,
-                };
+                let new = cpu.csr_write_value(
+                    id.ctrl.csr_addr,
+                    old,
+                    csr_new_value(id.ctrl.csr_op, old, id.rs1, fwd_a),
+                    id.pc.wrapping_add(id.inst_size),
+                );
 
                 // Store the deferred CSR update in the ROB
                 rob.set_csr_update(
@@ -461,9 +404,73 @@ pub fn execute_inorder(
             }
         }
 
+        // Vector instructions. vsetvl{i} produces the new vl for rd and
+        // defers its vtype write to commit like a CSR write; arithmetic
+        // updates the vector registers at commit.
+        if id.ctrl.vector_op != VectorOp::None {
+            let mut alu = 0;
+            if id.ctrl.vector_op == VectorOp::SetVl {
+                let (vl, vtype) = cpu.vsetvl(id.rs1, id.rd, op_a, op_b);
+                rob.set_csr_update(
+                    id.rob_tag,
+                    CsrUpdate {
+                        addr: crate::core::arch::csr::VTYPE,
+                        old_val: cpu.csrs.vtype,
+                        new_val: vtype,
+                    },
+                );
+                alu = vl;
+
+                // Younger vector instructions must see the new vtype.
+                cpu.pc = id.pc.wrapping_add(id.inst_size);
+                cpu.redirect_pending = true;
+                flush_remaining = true;
+            } else if cpu.vector_illegal() {
+                rob.fault(
+                    id.rob_tag,
+                    Trap::IllegalInstruction(id.inst),
+                    ExceptionStage::Execute,
+                );
+                flush_remaining = true;
+            }
+
+            results.push(ExMem1Entry {
+                rob_tag: id.rob_tag,
+                pc: id.pc,
+                inst: id.inst,
+                inst_size: id.inst_size,
+                rd: id.rd,
+                alu,
+                store_data: 0,
+                ctrl: id.ctrl,
+                trap: None,
+                exception_stage: None,
+            });
+            continue;
+        }
+
         // ALU / FPU execution
         let (alu_out, fp_flags) = compute_alu(id.ctrl.alu, op_a, op_b, op_c, id.ctrl.is_rv32);
 
+        // Under sub-register tracking the high half of a `.w` result is
+        // available as soon as it is computed.
+        if cpu.subreg_tracking && subreg::write_halves(id.inst, &id.ctrl) == subreg::HALF_LOW {
+            rob.set_early_result(id.rob_tag, alu_out);
+        }
+
+        // Long operations hold the execute unit for their remaining cycles,
+        // and the non-pipelined ones their functional unit as well. A shorter
+        // op later in the same issue group keeps the longest hold.
+        let latency = if id.ctrl.is_fence {
+            cpu.fence_latency
+        } else {
+            op_latency(cpu, id.ctrl.alu, op_b, id.ctrl.is_rv32)
+        };
+        cpu.alu_timer = cpu.alu_timer.max(latency.saturating_sub(1));
+        if let Some(unit) = id.ctrl.alu.func_unit() {
+            cpu.fu_busy_until[unit as usize] = cpu.stats.total_cycles() + latency;
+        }
+
         // Accumulate FP exception flags into fcsr.fflags
         if fp_flags != 0 {
             cpu.csrs.fflags |= fp_flags as u64;
@@ -475,15 +482,7 @@ pub fn execute_inorder(
 
         // Branch resolution
         if id.ctrl.branch {
-            let taken = match (id.inst >> FUNCT3_SHIFT) & FUNCT3_MASK {
-                funct3::BEQ => op_a == op_b,
-                funct3::BNE => op_a != op_b,
-                funct3::BLT => (op_a as i64) < (op_b as i64),
-                funct3::BGE => (op_a as i64) >= (op_b as i64),
-                funct3::BLTU => op_a < op_b,
-                funct3::BGEU => op_a >= op_b,
-                _ => false,
-            };
+            let taken = branch_taken(id.inst, op_a, op_b);
             let actual_target = id.pc.wrapping_add(id.imm as u64);
             let fallthrough = id.pc.wrapping_add(id.inst_size);
 
@@ -496,21 +495,41 @@ pub fn execute_inorder(
 
             let mispredicted = predicted_target != actual_next_pc;
 
+            // Train against the history the branch was predicted with. On a
+            // correct prediction, put back the speculative history so that
+            // younger in-flight branches keep their outcomes.
+            let speculative_ghr = cpu.branch_predictor.snapshot_history();
             cpu.branch_predictor.repair_history(id.ghr_snapshot);
+            let before = cpu.branch_predictor.training_counts();
             cpu.branch_predictor.update_branch(
                 id.pc,
                 taken,
                 if taken { Some(actual_target) } else { None },
             );
+            if let (Some(before), Some(after)) = (before, cpu.branch_predictor.training_counts()) {
+                cpu.stats.bp_trained += after.trained - before.trained;
+                cpu.stats.bp_train_skipped += after.skipped - before.skipped;
+                cpu.stats.bp_threshold = after.threshold as u64;
+            }
+            if !mispredicted {
+                cpu.branch_predictor.repair_history(speculative_ghr);
+            }
 
-            if mispredicted {
+            if mispredicted && cpu.branch_predictor.is_oracle() {
+                oracle_redirect(cpu, actual_next_pc);
+                flush_remaining = true;
+                cpu.stats.branch_predictions += 1;
+                cpu.stats.cond_branch_predictions += 1;
+            } else if mispredicted {
                 cpu.stats.branch_mispredictions += 1;
-                cpu.stats.stalls_control += 2;
+                cpu.stats.cond_branch_mispredictions += 1;
+                charge_mispredict(cpu);
                 cpu.pc = actual_next_pc;
                 cpu.redirect_pending = true;
                 flush_remaining = true;
             } else {
                 cpu.stats.branch_predictions += 1;
+                cpu.stats.cond_branch_predictions += 1;
             }
         }
 
@@ -518,8 +537,9 @@ pub fn execute_inorder(
         if id.ctrl.jump {
             use crate::common::constants::OPCODE_MASK;
             let is_jalr = (id.inst & OPCODE_MASK) == opcodes::OP_JALR;
-            let is_call = (id.inst & OPCODE_MASK) == opcodes::OP_JAL && id.rd == abi::REG_RA;
-            let is_ret = is_jalr && id.rd == abi::REG_ZERO && id.rs1 == abi::REG_RA;
+            // Any jump that links into ra or t0 is a call (JAL, JALR, and
+            // the expanded C.JALR), so the RAS sees compressed calls as well.
+            let (is_call, is_ret) = ras_hint(is_jalr, id.rd, id.rs1);
 
             let actual_target = if is_jalr {
                 (fwd_a.wrapping_add(id.imm as u64)) & JALR_ALIGNMENT_MASK
@@ -533,24 +553,55 @@ pub fn execute_inorder(
                 id.pc.wrapping_add(id.inst_size)
             };
 
-            if actual_target != predicted_target {
+            let redirected = actual_target != predicted_target;
+            let mispredicted = redirected && !cpu.branch_predictor.is_oracle();
+            let (kind_correct, kind_miss) = if is_ret {
+                (
+                    &mut cpu.stats.return_predictions,
+                    &mut cpu.stats.return_mispredictions,
+                )
+            } else if is_jalr {
+                (
+                    &mut cpu.stats.indirect_jump_predictions,
+                    &mut cpu.stats.indirect_jump_mispredictions,
+                )
+            } else {
+                (
+                    &mut cpu.stats.direct_jump_predictions,
+                    &mut cpu.stats.direct_jump_mispredictions,
+                )
+            };
+            *(if mispredicted {
+                kind_miss
+            } else {
+                kind_correct
+            }) += 1;
+
+            if mispredicted {
                 cpu.stats.branch_mispredictions += 1;
-                cpu.stats.stalls_control += 2;
+                charge_mispredict(cpu);
+                // Drop branch history speculated down the wrong path.
+                cpu.branch_predictor.repair_history(id.ghr_snapshot);
                 cpu.pc = actual_target;
                 cpu.redirect_pending = true;
                 flush_remaining = true;
             } else {
+                if redirected {
+                    cpu.branch_predictor.repair_history(id.ghr_snapshot);
+                    oracle_redirect(cpu, actual_target);
+                    flush_remaining = true;
+                }
                 cpu.stats.branch_predictions += 1;
             }
 
+            // Fetch already pushed or popped the RAS for this jump; only the
+            // BTB is left to train, and only with the resolved target.
             if is_call {
-                cpu.branch_predictor.on_call(
-                    id.pc,
-                    id.pc.wrapping_add(id.inst_size),
-                    actual_target,
-                );
-            } else if is_ret {
-                cpu.branch_predictor.on_return();
+                cpu.branch_predictor.update_btb(id.pc, actual_target);
+            }
+            if is_jalr && !is_ret {
+                cpu.branch_predictor
+                    .update_indirect(id.pc, actual_target, id.ghr_snapshot);
             }
         }
 
@@ -571,9 +622,147 @@ pub fn execute_inorder(
     (results, flush_remaining)
 }
 
+/// Checks whether a CSR instruction may access its CSR.
+///
+/// The access is illegal if:
+/// - It touches `satp` from S-mode while `mstatus.TVM` is set.
+/// - It touches `fflags`, `frm`, or `fcsr` while `mstatus.FS` is Off.
+/// - The current privilege is below the level encoded in CSR bits [9:8], or
+///   a counter CSR is disabled by `mcounteren`/`scounteren`.
+/// - It would write a read-only CSR (bits [11:10] == 0b11). CSRRW/CSRRWI
+///   always write; the set/clear forms write only when rs1 (or uimm) != 0.
+/// - It touches `stimecmp` below M-mode without `menvcfg.STCE` and
+///   `mcounteren.TM` both set.
+/// - `general.strict_csrs` is set and the CSR is not implemented.
+pub(crate) fn csr_access_allowed(cpu: &Cpu, ctrl: &ControlSignals, rs1: usize) -> bool {
+    if cpu.strict_csrs && !cpu.csr_implemented(ctrl.csr_addr) {
+        return false;
+    }
+
+    if ctrl.csr_addr == crate::core::arch::csr::SATP
+        && cpu.privilege == crate::core::arch::mode::PrivilegeMode::Supervisor
+        && ((cpu.csrs.mstatus >> 20) & 1) != 0
+    {
+        return false;
+    }
+
+    {
+        use crate::core::arch::csr::{FCSR, FFLAGS, FRM, MSTATUS_FS, MSTATUS_FS_OFF};
+        if matches!(ctrl.csr_addr, FFLAGS | FRM | FCSR)
+            && cpu.csrs.mstatus & MSTATUS_FS == MSTATUS_FS_OFF
+        {
+            return false;
+        }
+    }
+
+    // Sstc: below M-mode, stimecmp needs both menvcfg.STCE and mcounteren.TM.
+    if ctrl.csr_addr == crate::core::arch::csr::STIMECMP
+        && cpu.privilege != crate::core::arch::mode::PrivilegeMode::Machine
+        && (!cpu.sstc_enabled() || cpu.csrs.mcounteren & crate::core::arch::csr::COUNTEREN_TM == 0)
+    {
+        return false;
+    }
+
+    let csr_priv = (ctrl.csr_addr >> 8) & 3;
+    if (cpu.privilege.to_u8() as u32) < csr_priv || !cpu.counter_accessible(ctrl.csr_addr) {
+        return false;
+    }
+
+    let read_only = (ctrl.csr_addr >> 10) & 3 == 3;
+    let would_write = match ctrl.csr_op {
+        CsrOp::Rw | CsrOp::Rwi => true,
+        CsrOp::Rs | CsrOp::Rc => rs1 != 0,
+        CsrOp::Rsi | CsrOp::Rci => (rs1 & 0x1f) != 0,
+        CsrOp::None => false,
+    };
+    !(read_only && would_write)
+}
+
+/// Computes the value a CSR instruction writes.
+///
+/// # Arguments
+///
+/// * `op` - The CSR operation.
+/// * `old` - The current CSR value.
+/// * `rs1` - The rs1 field, used as a 5-bit immediate by the `*I` forms.
+/// * `rv1` - The value of register rs1.
+pub(crate) fn csr_new_value(op: CsrOp, old: u64, rs1: usize, rv1: u64) -> u64 {
+    let src = match op {
+        CsrOp::Rwi | CsrOp::Rsi | CsrOp::Rci => (rs1 as u64) & 0x1f,
+        _ => rv1,
+    };
+    match op {
+        CsrOp::Rw | CsrOp::Rwi => src,
+        CsrOp::Rs | CsrOp::Rsi => old | src,
+        CsrOp::Rc | CsrOp::Rci => old & !src,
+        CsrOp::None => old,
+    }
+}
+
+/// Evaluates the condition of a conditional branch instruction.
+pub(crate) fn branch_taken(inst: u32, op_a: u64, op_b: u64) -> bool {
+    match (inst >> FUNCT3_SHIFT) & FUNCT3_MASK {
+        funct3::BEQ => op_a == op_b,
+        funct3::BNE => op_a != op_b,
+        funct3::BLT => (op_a as i64) < (op_b as i64),
+        funct3::BGE => (op_a as i64) >= (op_b as i64),
+        funct3::BLTU => op_a < op_b,
+        funct3::BGEU => op_a >= op_b,
+        _ => false,
+    }
+}
+
+/// Charges a resolved misprediction.
+///
+/// Counts `frontend_depth` control-stall cycles and, for frontends deeper
+/// than the modeled stages (or in simple timing mode), stalls fetch after
+/// the flush so the extra refill cycles are actually spent.
+fn charge_mispredict(cpu: &mut Cpu) {
+    cpu.stats.stalls_control += cpu.frontend_depth;
+    cpu.redirect_penalty = cpu.frontend_depth.saturating_sub(MODELED_FRONTEND_DEPTH);
+    if cpu.simple_timing {
+        cpu.redirect_penalty += cpu.simple_mispredict_penalty;
+        cpu.stats.stalls_control += cpu.simple_mispredict_penalty;
+    }
+}
+
+/// Steers fetch onto the committed path for the perfect predictor.
+///
+/// Nothing is counted or charged; the pipeline refills the frontend at once
+/// so the correct path arrives as if it had been fetched all along.
+fn oracle_redirect(cpu: &mut Cpu, target: u64) {
+    cpu.pc = target;
+    cpu.redirect_pending = true;
+    cpu.oracle_redirect = true;
+}
+
+/// Returns the execution latency in cycles of `alu_op`.
+///
+/// Multiply, divide, FP divide and FP square root use the configured
+/// latencies; everything else is single-cycle. A divide by zero is detected
+/// up front and also completes in a single cycle.
+fn op_latency(cpu: &Cpu, alu_op: AluOp, op_b: u64, is_rv32: bool) -> u64 {
+    match alu_op {
+        AluOp::Mul | AluOp::Mulh | AluOp::Mulhsu | AluOp::Mulhu => cpu.mul_latency,
+        AluOp::Div | AluOp::Divu | AluOp::Rem | AluOp::Remu => {
+            let divisor_zero = if is_rv32 { op_b as u32 == 0 } else { op_b == 0 };
+            if divisor_zero { 1 } else { cpu.div_latency }
+        }
+        AluOp::FDiv => cpu.fdiv_latency,
+        AluOp::FSqrt => cpu.fsqrt_latency,
+        _ => 1,
+    }
+}
+
 /// Computes the ALU/FPU result and returns (result, fp_flags).
 /// fp_flags is non-zero only for floating-point arithmetic operations.
-fn compute_alu(alu_op: AluOp, op_a: u64, op_b: u64, op_c: u64, is_rv32: bool) -> (u64, u8) {
+pub(crate) fn compute_alu(
+    alu_op: AluOp,
+    op_a: u64,
+    op_b: u64,
+    op_c: u64,
+    is_rv32: bool,
+) -> (u64, u8) {
     // FP conversions and moves that need special handling
     match alu_op {
         AluOp::FCvtSW
@@ -619,9 +808,10 @@ fn compute_alu(alu_op: AluOp, op_a: u64, op_b: u64, op_c: u64, is_rv32: bool) ->
                     box_f32_canon(val_s)
                 }
                 AluOp::FCvtDS => {
-                    let val_s = f32::from_bits(op_a as u32);
-                    let val_d = val_s as f64;
-                    val_d.to_bits()
+                    use crate::core::units::fpu::nan_handling::{canonicalize_f64_bits, unbox_f32};
+                    // An improperly boxed source reads as the canonical NaN.
+                    let val_s = unbox_f32(op_a);
+                    canonicalize_f64_bits(val_s as f64)
                 }
                 AluOp::FMvToF => {
                     if is_rv32 {
@@ -5,10 +5,25 @@
 //! - If tag is None → read from architectural register file.
 //! - If tag points to a completed ROB entry → bypass the result.
 //! - If the ROB entry is still in-flight → stall (operand not ready).
+//!
+//! Under sub-register tracking, a producer that writes only the low half of
+//! a register does not block a consumer that reads only the high half; the
+//! consumer takes the value the producer published at execute.
+//!
+//! A FENCE or FENCE.I issues only from the head of the ROB once every
+//! committed store has drained to memory, so later accesses and refetched
+//! instructions observe all earlier stores.
+//!
+//! An operation that needs a non-pipelined functional unit (divider, FP
+//! divide/sqrt) also waits until the unit is free and no earlier instruction
+//! in the same issue group has claimed it (a structural hazard).
 
 use crate::core::Cpu;
 use crate::core::pipeline::latches::RenameIssueEntry;
-use crate::core::pipeline::rob::{Rob, RobState, RobTag};
+use crate::core::pipeline::rob::{Rob, RobEntry, RobState, RobTag};
+use crate::core::pipeline::signals::FuncUnit;
+use crate::core::pipeline::store_buffer::StoreBuffer;
+use crate::core::pipeline::subreg::{self, HALF_FULL};
 use std::collections::VecDeque;
 
 /// FIFO issue unit for in-order execution.
@@ -47,7 +62,13 @@ impl InOrderIssueUnit {
     /// operands populated.
     ///
     /// In-order: if the head-of-queue is blocked, nothing behind it can issue.
-    pub fn select(&mut self, width: usize, rob: &Rob, cpu: &Cpu) -> Vec<RenameIssueEntry> {
+    pub fn select(
+        &mut self,
+        width: usize,
+        rob: &Rob,
+        store_buffer: &StoreBuffer,
+        cpu: &mut Cpu,
+    ) -> Vec<RenameIssueEntry> {
         let mut selected = Vec::with_capacity(width);
 
         for _ in 0..width {
@@ -62,16 +83,66 @@ impl InOrderIssueUnit {
                 continue;
             }
 
+            // Fences wait for older instructions to retire and their
+            // stores to drain
+            if (entry.ctrl.is_fence || entry.ctrl.is_fence_i)
+                && (rob.peek_head().is_none_or(|head| head.tag != entry.rob_tag)
+                    || store_buffer.has_committed())
+            {
+                if selected.is_empty() {
+                    cpu.stats.stalls_mem += 1;
+                }
+                if cpu.trace {
+                    eprintln!("IS  pc={:#x} STALL fence draining", entry.pc);
+                }
+                break;
+            }
+
+            let halves = if cpu.subreg_tracking {
+                subreg::source_halves(entry.inst, &entry.ctrl)
+            } else {
+                HALF_FULL
+            };
+
             // Try to read all source operands using tags captured at rename
-            let rv1 = read_operand_by_tag(entry.rs1, entry.ctrl.rs1_fp, entry.rs1_tag, rob, cpu);
-            let rv2 = read_operand_by_tag(entry.rs2, entry.ctrl.rs2_fp, entry.rs2_tag, rob, cpu);
+            let rv1 = read_operand_by_tag(
+                entry.rs1,
+                entry.ctrl.rs1_fp,
+                entry.rs1_tag,
+                halves,
+                rob,
+                cpu,
+            );
+            let rv2 = read_operand_by_tag(
+                entry.rs2,
+                entry.ctrl.rs2_fp,
+                entry.rs2_tag,
+                halves,
+                rob,
+                cpu,
+            );
             let rv3 = if entry.ctrl.rs3_fp {
-                read_operand_by_tag(entry.rs3, true, entry.rs3_tag, rob, cpu)
+                read_operand_by_tag(entry.rs3, true, entry.rs3_tag, HALF_FULL, rob, cpu)
             } else {
                 Some(0)
             };
 
             match (rv1, rv2, rv3) {
+                (Some(_), Some(_), Some(_))
+                    if entry
+                        .ctrl
+                        .alu
+                        .func_unit()
+                        .is_some_and(|unit| unit_busy(unit, &selected, cpu)) =>
+                {
+                    // Operands are ready but the unit is not; this counts
+                    // even when earlier instructions in the group issued.
+                    cpu.stats.stalls_struct += 1;
+                    if cpu.trace {
+                        eprintln!("IS  pc={:#x} STALL functional unit busy", entry.pc);
+                    }
+                    break;
+                }
                 (Some(v1), Some(v2), Some(v3)) => {
                     let mut issued = self.queue.pop_front().unwrap();
                     issued.rv1 = v1;
@@ -80,6 +151,12 @@ impl InOrderIssueUnit {
                     selected.push(issued);
                 }
                 _ => {
+                    if cpu.subreg_tracking && blocked_on_half(entry, halves, rob) {
+                        cpu.stats.subreg_dependency_stalls += 1;
+                    }
+                    if selected.is_empty() {
+                        cpu.stats.stalls_data += 1;
+                    }
                     // Head of queue blocked — in-order can't skip
                     if cpu.trace {
                         eprintln!(
@@ -106,13 +183,47 @@ impl InOrderIssueUnit {
     }
 }
 
+/// Returns true if `unit` is still working on an earlier operation or has
+/// been claimed by an instruction already selected this cycle.
+fn unit_busy(unit: FuncUnit, selected: &[RenameIssueEntry], cpu: &Cpu) -> bool {
+    cpu.fu_busy_until[unit as usize] > cpu.stats.total_cycles()
+        || selected
+            .iter()
+            .any(|e| e.trap.is_none() && e.ctrl.alu.func_unit() == Some(unit))
+}
+
+/// Returns the halves of an integer register written by a ROB entry.
+fn producer_halves(producer: &RobEntry) -> u8 {
+    subreg::write_halves(producer.inst, &producer.ctrl)
+}
+
+/// Returns true if the head is stalled on an integer producer and the
+/// dependency between them covers only one half of the register.
+fn blocked_on_half(entry: &RenameIssueEntry, halves: u8, rob: &Rob) -> bool {
+    [
+        (entry.rs1, entry.ctrl.rs1_fp, entry.rs1_tag),
+        (entry.rs2, entry.ctrl.rs2_fp, entry.rs2_tag),
+    ]
+    .into_iter()
+    .filter(|&(reg, is_fp, _)| !is_fp && reg != 0)
+    .filter_map(|(_, _, tag)| rob.find_entry(tag?))
+    .any(|p| {
+        let overlap = halves & producer_halves(p);
+        p.state != RobState::Completed && overlap != 0 && overlap != HALF_FULL
+    })
+}
+
 /// Read a single operand value using the tag captured at rename time.
 ///
+/// `halves` are the halves of an integer source the consumer reads; a
+/// producer writing none of them is bypassed through its early result.
+///
 /// Returns `Some(value)` if the operand is ready, `None` if stalled.
 fn read_operand_by_tag(
     reg: usize,
     is_fp: bool,
     tag: Option<RobTag>,
+    halves: u8,
     rob: &Rob,
     cpu: &Cpu,
 ) -> Option<u64> {
@@ -134,6 +245,7 @@ fn read_operand_by_tag(
             // In-flight producer — check if ROB entry has completed
             match rob.find_entry(t) {
                 Some(entry) if entry.state == RobState::Completed => Some(entry.result),
+                Some(entry) if !is_fp && producer_halves(entry) & halves == 0 => entry.early_result,
                 Some(_) => None, // Not ready — stall
                 None => {
                     // ROB entry gone (already committed) — value is in register file
@@ -15,6 +15,7 @@ use crate::core::pipeline::latches::{ExMem1Entry, Mem1Mem2Entry, Mem2WbEntry, Re
 use crate::core::pipeline::rob::Rob;
 use crate::core::pipeline::scoreboard::Scoreboard;
 use crate::core::pipeline::store_buffer::StoreBuffer;
+use crate::core::units::bru::BranchPredictor;
 
 use self::issue::InOrderIssueUnit;
 
@@ -113,11 +114,13 @@ impl ExecutionEngine for InOrderEngine {
         // Memory1: address translation (gated by mem1_stall)
         if self.mem1_stall > 0 {
             self.mem1_stall -= 1;
+            cpu.stats.stalls_mem += 1;
         } else {
             memory1::memory1_stage(
                 cpu,
                 &mut self.execute_mem1,
                 &mut self.mem1_mem2,
+                &self.store_buffer,
                 &mut self.mem1_stall,
             );
         }
@@ -136,11 +139,20 @@ impl ExecutionEngine for InOrderEngine {
             );
         }
 
+        // A multi-cycle operation (multiply, divide, FP divide/sqrt) still
+        // occupies the execute unit, so nothing new issues this cycle.
+        let exec_busy = cpu.alu_timer > 0;
+        if exec_busy {
+            cpu.alu_timer -= 1;
+        }
+
         // Issue + Execute: select and read operands via tags
-        let (results, needs_flush) = if backpressured {
+        let (results, needs_flush) = if backpressured || exec_busy {
             (Vec::new(), false)
         } else {
-            let issued = self.issuer.select(self.width, &self.rob, cpu);
+            let issued = self
+                .issuer
+                .select(self.width, &self.rob, &self.store_buffer, cpu);
             execute::execute_inorder(cpu, issued, &mut self.rob)
         };
         self.execute_mem1.extend(results);
@@ -170,6 +182,10 @@ impl ExecutionEngine for InOrderEngine {
             // All ROB entries after this one are speculative and must be flushed.
             if let Some(last) = self.execute_mem1.last() {
                 let keep_tag = last.rob_tag;
+                // Undo RAS pushes/pops made at fetch by the flushed wrong path.
+                if let Some(cp) = self.rob.find_entry(keep_tag).map(|e| e.ras_checkpoint) {
+                    cpu.branch_predictor.restore_ras(cp);
+                }
                 self.rob.flush_after(keep_tag);
                 // Only flush store buffer entries allocated after the branch.
                 // Pre-branch stores may still be in-flight (Ready but not yet
@@ -200,7 +216,10 @@ impl ExecutionEngine for InOrderEngine {
         rob_free.min(sb_free).min(issue_free).min(self.width)
     }
 
-    fn flush(&mut self, _cpu: &mut Cpu) {
+    fn flush(&mut self, cpu: &mut Cpu) {
+        // Nothing in flight survives, so the RAS returns to its state after
+        // the last retired instruction.
+        cpu.branch_predictor.restore_ras(cpu.retired_ras);
         self.rob.flush_all();
         self.store_buffer.flush_speculative();
         self.scoreboard.flush();
@@ -209,6 +228,7 @@ impl ExecutionEngine for InOrderEngine {
         self.mem1_mem2.clear();
         self.mem2_wb.clear();
         self.mem1_stall = 0;
+        cpu.alu_timer = 0;
     }
 
     fn read_csr_speculative(&self, cpu: &crate::core::Cpu, addr: u32) -> u64 {
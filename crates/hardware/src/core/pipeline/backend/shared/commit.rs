@@ -7,10 +7,7 @@
 //! 4. Handle traps/interrupts.
 //! 5. Drain one committed store to memory per cycle.
 
-use crate::common::Trap;
-use crate::common::constants::{
-    DELEG_MEIP_BIT, DELEG_MSIP_BIT, DELEG_MTIP_BIT, DELEG_SEIP_BIT, DELEG_SSIP_BIT, DELEG_STIP_BIT,
-};
+use crate::common::{BusError, Trap};
 use crate::core::Cpu;
 use crate::core::arch::csr;
 use crate::core::arch::mode::PrivilegeMode;
@@ -18,8 +15,10 @@ use crate::core::arch::trap::TrapHandler;
 use crate::core::cpu::PC_TRACE_MAX;
 use crate::core::pipeline::rob::{Rob, RobState};
 use crate::core::pipeline::scoreboard::Scoreboard;
-use crate::core::pipeline::signals::{AluOp, MemWidth};
+use crate::core::pipeline::signals::{MemWidth, VectorOp};
 use crate::core::pipeline::store_buffer::StoreBuffer;
+use crate::core::units::lsu::unaligned;
+use crate::isa::disasm;
 
 /// Executes the Commit stage.
 ///
@@ -37,14 +36,19 @@ pub fn commit_stage(
 
     // Check for interrupts before committing.
     // Always check — even with an empty ROB (e.g., timer fired during a stall
-    // with no instructions in-flight). Use cpu.pc as EPC when ROB is empty.
+    // with no instructions in-flight). The EPC is the oldest instruction not
+    // yet retired: the ROB head, else the oldest instruction still in the
+    // frontend, else the fetch PC. Everything younger is flushed by the trap
+    // and refetched after it returns; a multi-cycle operation still in
+    // execute is squashed with its `alu_timer` and re-executes from scratch.
     {
         let epc = if cpu.wfi_waiting {
             cpu.wfi_pc
         } else if let Some(head) = rob.peek_head() {
             head.pc
         } else {
-            cpu.pc // ROB empty: next instruction to fetch
+            // ROB empty: fetched-but-unrenamed instructions come first.
+            cpu.frontend_head_pc.unwrap_or(cpu.pc)
         };
 
         let interrupt = check_interrupts(cpu);
@@ -75,6 +79,12 @@ pub fn commit_stage(
 
     // Commit up to `width` entries from ROB head
     for _ in 0..width {
+        // An injected interrupt is raised at an exact instruction count;
+        // stop there so it is taken before the next instruction retires.
+        if cpu.injected_irq_due() {
+            break;
+        }
+
         let head = match rob.peek_head() {
             Some(h) => h,
             None => break,
@@ -102,7 +112,12 @@ pub fn commit_stage(
         let entry = rob.commit_head().unwrap();
 
         if cpu.trace {
-            eprintln!("CM  pc={:#x} rob_tag={} COMMIT", entry.pc, entry.tag.0);
+            eprintln!(
+                "CM  pc={} rob_tag={} COMMIT {}",
+                cpu.describe_pc(entry.pc),
+                entry.tag.0,
+                disasm(entry.inst)
+            );
         }
 
         // Update PC trace
@@ -110,12 +125,21 @@ pub fn commit_stage(
         if cpu.pc_trace.len() > PC_TRACE_MAX {
             cpu.pc_trace.remove(0);
         }
-
-        // Statistics
-        if entry.inst != 0 && entry.inst != 0x13 {
-            cpu.stats.instructions_retired += 1;
-            update_instruction_stats(cpu, &entry);
+        cpu.retired_ras = entry.ras_checkpoint;
+        cpu.watch_commit_pc(entry.pc);
+        if let Some(dump) = &mut cpu.asm_dump {
+            let _ = dump.record(entry.pc, entry.inst, entry.inst_size);
         }
+        if let Some(log) = &mut cpu.commit_log {
+            let _ = log.record(cpu.privilege, &entry);
+        }
+
+        // Statistics. Every entry that reaches commit retires exactly once:
+        // NOPs, hints, FENCE, WFI and xRET included, a compressed instruction
+        // as one, and trapping instructions never get here. A CSR write to
+        // `minstret` lands below, so the next instruction sees its value.
+        cpu.record_retired(1);
+        cpu.stats.record_instruction(&entry.ctrl);
 
         // Write to register file
         let val = entry.result;
@@ -136,6 +160,17 @@ pub fn commit_stage(
             }
         }
 
+        // Apply vector state changes in program order. vsetvl{i} sets vl
+        // here; its vtype write follows as a deferred CSR write.
+        match entry.ctrl.vector_op {
+            VectorOp::None => {}
+            VectorOp::SetVl => {
+                cpu.csrs.vl = val;
+                cpu.csrs.vstart = 0;
+            }
+            op => cpu.execute_vector(op, entry.inst),
+        }
+
         // Apply deferred CSR write
         if let Some(csr_update) = entry.csr_update {
             cpu.csr_write(csr_update.addr, csr_update.new_val);
@@ -183,139 +218,120 @@ pub fn commit_stage(
     if let Some(store) = store_buffer.drain_one()
         && let Some(paddr) = store.paddr
     {
-        let in_htif = cpu
-            .htif_range
-            .is_some_and(|(lo, hi)| paddr >= lo && paddr < hi);
-        let is_ram = !in_htif && paddr >= cpu.ram_start && paddr < cpu.ram_end;
-        if is_ram {
-            let offset = (paddr - cpu.ram_start) as usize;
-            unsafe {
-                match store.width {
-                    MemWidth::Byte => *cpu.ram_ptr.add(offset) = store.data as u8,
-                    MemWidth::Half => {
-                        (cpu.ram_ptr.add(offset) as *mut u16).write_unaligned(store.data as u16)
-                    }
-                    MemWidth::Word => {
-                        (cpu.ram_ptr.add(offset) as *mut u32).write_unaligned(store.data as u32)
-                    }
-                    MemWidth::Double => {
-                        (cpu.ram_ptr.add(offset) as *mut u64).write_unaligned(store.data)
-                    }
-                    _ => {}
-                }
+        cpu.bus.bus.set_access_pc(store.pc);
+        // The address was checked when the store resolved in memory2.
+        let _ = write_store(cpu, paddr, store.width, store.data);
+    }
+
+    trap_event
+}
+
+/// Writes a drained store to memory: RAM directly, anything else (MMIO,
+/// HTIF) through the bus. Misaligned stores are written a byte at a time.
+///
+/// # Arguments
+///
+/// * `cpu` - The CPU whose RAM or bus receives the write.
+/// * `paddr` - Physical address of the store.
+/// * `width` - Access width.
+/// * `data` - Store data (low bytes are written).
+///
+/// # Errors
+///
+/// Returns a `BusError` if the store is not backed by a device. Bytes of a
+/// split store before the unmapped one are still written.
+pub fn write_store(cpu: &mut Cpu, paddr: u64, width: MemWidth, data: u64) -> Result<(), BusError> {
+    let size = unaligned::width_to_bytes(width);
+    if !unaligned::is_aligned(paddr, size) {
+        // Split so the store never runs past the end of RAM or a device.
+        let mut result = Ok(());
+        unaligned::split_store(paddr, size, data, |addr, byte| {
+            if result.is_ok() {
+                result = write_store(cpu, addr, MemWidth::Byte, byte as u64);
             }
-        } else {
-            match store.width {
-                MemWidth::Byte => cpu.bus.bus.write_u8(paddr, store.data as u8),
-                MemWidth::Half => cpu.bus.bus.write_u16(paddr, store.data as u16),
-                MemWidth::Word => cpu.bus.bus.write_u32(paddr, store.data as u32),
-                MemWidth::Double => cpu.bus.bus.write_u64(paddr, store.data),
+        });
+        return result;
+    }
+    let in_htif = cpu
+        .htif_range
+        .is_some_and(|(lo, hi)| paddr >= lo && paddr < hi);
+    let is_ram = !in_htif && cpu.in_ram(paddr, size);
+    if is_ram {
+        let offset = (paddr - cpu.ram_start) as usize;
+        unsafe {
+            match width {
+                MemWidth::Byte => *cpu.ram_ptr.add(offset) = data as u8,
+                MemWidth::Half => {
+                    (cpu.ram_ptr.add(offset) as *mut u16).write_unaligned(data as u16)
+                }
+                MemWidth::Word => {
+                    (cpu.ram_ptr.add(offset) as *mut u32).write_unaligned(data as u32)
+                }
+                MemWidth::Double => (cpu.ram_ptr.add(offset) as *mut u64).write_unaligned(data),
                 _ => {}
             }
         }
-
-        if cpu.trace {
-            eprintln!("CM  STORE DRAIN paddr={:#x} data={:#x}", paddr, store.data);
+    } else {
+        match width {
+            MemWidth::Byte => cpu.bus.bus.write_u8(paddr, data as u8)?,
+            MemWidth::Half => cpu.bus.bus.write_u16(paddr, data as u16)?,
+            MemWidth::Word => cpu.bus.bus.write_u32(paddr, data as u32)?,
+            MemWidth::Double => cpu.bus.bus.write_u64(paddr, data)?,
+            _ => {}
         }
     }
 
-    trap_event
+    // The I-cache snoops stores, so patched code misses and is refetched
+    if cpu.l1_i_cache.invalidate_line(paddr) {
+        cpu.stats.icache_invalidations += 1;
+    }
+
+    if cpu.trace {
+        eprintln!("CM  STORE DRAIN paddr={:#x} data={:#x}", paddr, data);
+    }
+    Ok(())
 }
 
 /// Checks for pending interrupts. Returns the trap if one should be taken.
-fn check_interrupts(cpu: &Cpu) -> Option<Trap> {
-    let mip = cpu.csrs.mip;
-    let mie = cpu.csrs.mie;
+///
+/// Among interrupts that are pending, enabled, and takeable at the current
+/// privilege, those destined for M-mode come before those delegated to
+/// S-mode; within a destination, `cpu.interrupt_priority` decides (the
+/// privileged spec's MEI > MSI > MTI > SEI > SSI > STI by default).
+pub(crate) fn check_interrupts(cpu: &Cpu) -> Option<Trap> {
     let mstatus = cpu.csrs.mstatus;
-
     let m_global_ie = (mstatus & csr::MSTATUS_MIE) != 0;
     let s_global_ie = (mstatus & csr::MSTATUS_SIE) != 0;
 
-    let check = |bit: u64, enable_bit: u64, deleg_bit: u64| -> Option<Trap> {
-        let pending = (mip & bit) != 0;
-        let enabled = (mie & enable_bit) != 0;
-        if !pending || !enabled {
-            return None;
+    // An interrupt's pending, enable, and delegation bits share its code.
+    let takeable = |code: u64, target: PrivilegeMode| -> bool {
+        let bit = 1 << code;
+        if cpu.csrs.mip & cpu.csrs.mie & bit == 0 {
+            return false;
         }
-
-        let delegated = (cpu.csrs.mideleg & deleg_bit) != 0;
-        let target_priv = if delegated {
+        let delegated = (cpu.csrs.mideleg & bit) != 0;
+        let destination = if delegated {
             PrivilegeMode::Supervisor
         } else {
             PrivilegeMode::Machine
         };
-
-        if cpu.privilege.to_u8() < target_priv.to_u8() {
-            return Some(TrapHandler::irq_to_trap(bit));
-        }
-        if cpu.privilege == target_priv {
-            if target_priv == PrivilegeMode::Machine && m_global_ie {
-                return Some(TrapHandler::irq_to_trap(bit));
-            }
-            if target_priv == PrivilegeMode::Supervisor && s_global_ie {
-                return Some(TrapHandler::irq_to_trap(bit));
-            }
+        if destination != target {
+            return false;
         }
-        None
+        let global_ie = match target {
+            PrivilegeMode::Machine => m_global_ie,
+            _ => s_global_ie,
+        };
+        cpu.privilege.to_u8() < target.to_u8() || (cpu.privilege == target && global_ie)
     };
 
-    check(csr::MIP_MEIP, csr::MIE_MEIP, 1 << DELEG_MEIP_BIT)
-        .or_else(|| check(csr::MIP_MSIP, csr::MIE_MSIP, 1 << DELEG_MSIP_BIT))
-        .or_else(|| check(csr::MIP_MTIP, csr::MIE_MTIE, 1 << DELEG_MTIP_BIT))
-        .or_else(|| check(csr::MIP_SEIP, csr::MIE_SEIP, 1 << DELEG_SEIP_BIT))
-        .or_else(|| check(csr::MIP_SSIP, csr::MIE_SSIP, 1 << DELEG_SSIP_BIT))
-        .or_else(|| check(csr::MIP_STIP, csr::MIE_STIE, 1 << DELEG_STIP_BIT))
-}
-
-/// Updates instruction statistics based on the committed entry.
-fn update_instruction_stats(cpu: &mut Cpu, entry: &crate::core::pipeline::rob::RobEntry) {
-    if entry.ctrl.mem_read {
-        if entry.ctrl.fp_reg_write {
-            cpu.stats.inst_fp_load += 1;
-        } else {
-            cpu.stats.inst_load += 1;
-        }
-    } else if entry.ctrl.mem_write {
-        if entry.ctrl.rs2_fp {
-            cpu.stats.inst_fp_store += 1;
-        } else {
-            cpu.stats.inst_store += 1;
-        }
-    } else if entry.ctrl.branch || entry.ctrl.jump {
-        cpu.stats.inst_branch += 1;
-    } else if entry.ctrl.is_system {
-        cpu.stats.inst_system += 1;
-    } else {
-        match entry.ctrl.alu {
-            AluOp::FAdd
-            | AluOp::FSub
-            | AluOp::FMul
-            | AluOp::FMin
-            | AluOp::FMax
-            | AluOp::FSgnJ
-            | AluOp::FSgnJN
-            | AluOp::FSgnJX
-            | AluOp::FEq
-            | AluOp::FLt
-            | AluOp::FLe
-            | AluOp::FClass
-            | AluOp::FCvtWS
-            | AluOp::FCvtWUS
-            | AluOp::FCvtLS
-            | AluOp::FCvtLUS
-            | AluOp::FCvtSW
-            | AluOp::FCvtSWU
-            | AluOp::FCvtSL
-            | AluOp::FCvtSLU
-            | AluOp::FCvtSD
-            | AluOp::FCvtDS
-            | AluOp::FMvToX
-            | AluOp::FMvToF => cpu.stats.inst_fp_arith += 1,
-            AluOp::FDiv | AluOp::FSqrt => cpu.stats.inst_fp_div_sqrt += 1,
-            AluOp::FMAdd | AluOp::FMSub | AluOp::FNMAdd | AluOp::FNMSub => {
-                cpu.stats.inst_fp_fma += 1
-            }
-            _ => cpu.stats.inst_alu += 1,
-        }
-    }
+    [PrivilegeMode::Machine, PrivilegeMode::Supervisor]
+        .into_iter()
+        .find_map(|target| {
+            cpu.interrupt_priority
+                .iter()
+                .copied()
+                .find(|&code| takeable(code, target))
+        })
+        .map(|code| TrapHandler::irq_to_trap(1 << code))
 }
@@ -1,11 +1,15 @@
 //! Memory1 Stage: MMU/TLB address translation.
 //!
 //! Translates virtual addresses to physical addresses for loads and stores.
+//! Loads fully covered by a pending store are charged the store-forwarding
+//! latency instead of a D-cache access; Memory2 supplies the data.
 //! This stage is the same for both in-order and O3 backends.
 
 use crate::common::{AccessType, ExceptionStage, TranslationResult, VirtAddr};
 use crate::core::Cpu;
 use crate::core::pipeline::latches::{ExMem1Entry, Mem1Mem2Entry};
+use crate::core::pipeline::signals::AtomicOp;
+use crate::core::pipeline::store_buffer::{ForwardResult, StoreBuffer};
 use crate::core::units::lsu::unaligned;
 
 /// Executes the Memory1 stage: address translation.
@@ -13,6 +17,7 @@ pub fn memory1_stage(
     cpu: &mut Cpu,
     input: &mut Vec<ExMem1Entry>,
     output: &mut Vec<Mem1Mem2Entry>,
+    store_buffer: &StoreBuffer,
     stall_out: &mut u64,
 ) {
     let entries = std::mem::take(input);
@@ -20,6 +25,9 @@ pub fn memory1_stage(
     // into this latch. We append new entries after any stalled ones.
 
     let mut flush_remaining = false;
+    // RAM accesses of this bundle, simulated together after translation.
+    let mut ram_accesses = Vec::new();
+    let mut ram_pcs = Vec::new();
 
     for ex in entries {
         if flush_remaining {
@@ -52,13 +60,7 @@ pub fn memory1_stage(
         let needs_translation = ex.ctrl.mem_read || ex.ctrl.mem_write;
 
         if needs_translation {
-            // Check alignment
             let size = unaligned::width_to_bytes(ex.ctrl.width);
-            if !unaligned::is_aligned(ex.alu, size) {
-                let latency_penalty = unaligned::calculate_unaligned_latency(ex.alu, size, 64);
-                *stall_out += latency_penalty;
-            }
-
             let access_type = if ex.ctrl.mem_write {
                 AccessType::Write
             } else {
@@ -71,6 +73,16 @@ pub fn memory1_stage(
                 trap: fault,
             } = cpu.translate(VirtAddr::new(ex.alu), access_type);
             *stall_out += cycles;
+            cpu.stats.stalls_dtlb += cycles;
+            let fault = fault.or_else(|| {
+                cpu.check_misaligned(
+                    ex.alu,
+                    paddr.val(),
+                    size,
+                    access_type,
+                    ex.ctrl.atomic_op != AtomicOp::None,
+                )
+            });
 
             if let Some(t) = fault {
                 if cpu.trace {
@@ -94,6 +106,9 @@ pub fn memory1_stage(
                 continue;
             }
 
+            // Misaligned accesses are split, costing extra cycles
+            *stall_out += unaligned::calculate_unaligned_latency(ex.alu, size, 64);
+
             if cpu.trace {
                 if ex.ctrl.mem_read {
                     eprintln!(
@@ -112,16 +127,39 @@ pub fn memory1_stage(
                 }
             }
 
-            // D-cache/bus latency for RAM and MMIO
-            if paddr.val() >= cpu.mmio_base {
-                let lat = cpu.simulate_memory_access(paddr, access_type);
+            let forwarded = !cpu.simple_timing
+                && ex.ctrl.mem_read
+                && !ex.ctrl.mem_write
+                && ex.ctrl.atomic_op == AtomicOp::None
+                && matches!(
+                    store_buffer.forward_load(paddr.val(), ex.ctrl.width),
+                    ForwardResult::Hit(_)
+                );
+
+            // D-cache/bus latency for RAM and MMIO (every access under simple timing)
+            if forwarded {
+                // Store-to-load forwarding: the data never leaves the core
+                cpu.stats
+                    .record_mem_latency(ex.pc, paddr.val(), cpu.store_forward_latency);
+                *stall_out += cpu.store_forward_latency;
+            } else if cpu.simple_timing || paddr.val() >= cpu.mmio_base {
+                ram_accesses.push((paddr, access_type));
+                ram_pcs.push(ex.pc);
+                if ex.ctrl.atomic_op != AtomicOp::None {
+                    cpu.atomic_line = Some(paddr.val());
+                }
+            } else {
+                // Device access: bus transit plus any clock-domain crossing
+                let lat = cpu.bus.bus.device_access_time(paddr.val(), size as usize);
+                cpu.stats.record_mem_latency(ex.pc, paddr.val(), lat);
                 *stall_out += lat;
-            } else if ex.ctrl.mem_write {
-                let addr = paddr.val();
-                if (0x10001000..0x10002000).contains(&addr) {
-                    cpu.l1_d_cache.flush();
-                    cpu.l2_cache.flush();
-                    cpu.l3_cache.flush();
+                if ex.ctrl.mem_write {
+                    let addr = paddr.val();
+                    if (0x10001000..0x10002000).contains(&addr) {
+                        cpu.l1_d_cache.flush();
+                        cpu.l2_cache.flush();
+                        cpu.l3_cache.flush();
+                    }
                 }
             }
 
@@ -160,4 +198,22 @@ pub fn memory1_stage(
             });
         }
     }
+
+    if ram_accesses.is_empty() {
+        return;
+    }
+    let latencies = cpu.simulate_memory_accesses(&ram_accesses);
+    cpu.atomic_line = None;
+    for ((pc, (paddr, _)), &lat) in ram_pcs.iter().zip(&ram_accesses).zip(&latencies) {
+        cpu.stats.record_mem_latency(*pc, paddr.val(), lat);
+    }
+    // The bundle's misses are in flight together, so it waits for the
+    // slowest one. Simple timing has no memory-level parallelism.
+    let stall = if cpu.simple_timing {
+        latencies.iter().sum()
+    } else {
+        latencies.iter().copied().max().unwrap_or(0)
+    };
+    *stall_out += stall;
+    cpu.stats.stalls_dcache += stall;
 }
@@ -11,6 +11,32 @@ use crate::core::pipeline::rob::Rob;
 use crate::core::pipeline::signals::{AtomicOp, MemWidth};
 use crate::core::pipeline::store_buffer::{ForwardResult, StoreBuffer};
 use crate::core::units::lsu::Lsu;
+use crate::core::units::lsu::unaligned::{is_aligned, width_to_bytes};
+
+/// Reads the current value at `paddr` for an LR or AMO, forwarding from the
+/// store buffer when a pending store covers it.
+///
+/// `.w` results are sign-extended to 64 bits whether they were forwarded or
+/// read from memory. Returns `None` when a partially overlapping store forces
+/// a stall.
+fn atomic_read(
+    cpu: &mut Cpu,
+    store_buffer: &StoreBuffer,
+    paddr: u64,
+    width: MemWidth,
+) -> Option<u64> {
+    let raw = match store_buffer.forward_load(paddr, width) {
+        ForwardResult::Hit(fwd) => fwd,
+        ForwardResult::Stall => return None,
+        // The caller has checked that `paddr` is mapped.
+        ForwardResult::Miss => match width {
+            MemWidth::Word => cpu.bus.bus.read_u32(paddr).unwrap_or(0) as u64,
+            MemWidth::Double => cpu.bus.bus.read_u64(paddr).unwrap_or(0),
+            _ => 0,
+        },
+    };
+    Some(cpu.load_value(raw, width, true))
+}
 
 /// Executes the Memory2 stage: D-cache access + store buffer forwarding.
 pub fn memory2_stage(
@@ -18,7 +44,7 @@ pub fn memory2_stage(
     input: &mut Vec<Mem1Mem2Entry>,
     output: &mut Vec<Mem2WbEntry>,
     store_buffer: &mut StoreBuffer,
-    _rob: &mut Rob,
+    rob: &mut Rob,
 ) {
     let entries = std::mem::take(input);
     output.clear();
@@ -52,7 +78,9 @@ pub fn memory2_stage(
         }
 
         let raw_paddr = mem.paddr;
-        let is_ram = raw_paddr >= cpu.ram_start && raw_paddr < cpu.ram_end;
+        cpu.bus.bus.set_access_pc(mem.pc);
+        let access_size = width_to_bytes(mem.ctrl.width);
+        let is_ram = cpu.in_ram(raw_paddr, access_size);
         let ram_offset = if is_ram {
             (raw_paddr - cpu.ram_start) as usize
         } else {
@@ -60,57 +88,55 @@ pub fn memory2_stage(
         };
 
         let mut ld: u64 = 0;
-        let trap: Option<Trap> = None;
-        let exception_stage: Option<ExceptionStage> = None;
+        let mut written = mem.store_data;
+        let mut trap: Option<Trap> = None;
+        let mut exception_stage: Option<ExceptionStage> = None;
+
+        // An access no device claims, or one running past the end of its
+        // device, faults instead of reading zeros or having its store
+        // dropped when it drains after commit.
+        let unmapped = !is_ram && cpu.bus.bus.check_access(raw_paddr, access_size).is_err();
 
-        if mem.ctrl.atomic_op != AtomicOp::None {
+        if unmapped && (mem.ctrl.atomic_op != AtomicOp::None || mem.ctrl.mem_write) {
+            trap = Some(if mem.ctrl.atomic_op == AtomicOp::Lr {
+                Trap::LoadAccessFault(mem.vaddr)
+            } else {
+                Trap::StoreAccessFault(mem.vaddr)
+            });
+            exception_stage = Some(ExceptionStage::Memory);
+        } else if mem.ctrl.atomic_op != AtomicOp::None {
             // Atomic operations
             match mem.ctrl.atomic_op {
                 AtomicOp::Lr => {
-                    ld = match store_buffer.forward_load(raw_paddr, mem.ctrl.width) {
-                        ForwardResult::Hit(fwd) => fwd,
-                        ForwardResult::Stall => {
-                            input.push(mem);
-                            break;
-                        }
-                        ForwardResult::Miss => match mem.ctrl.width {
-                            MemWidth::Word => {
-                                (cpu.bus.bus.read_u32(raw_paddr) as i32) as i64 as u64
-                            }
-                            MemWidth::Double => cpu.bus.bus.read_u64(raw_paddr),
-                            _ => 0,
-                        },
+                    let Some(val) = atomic_read(cpu, store_buffer, raw_paddr, mem.ctrl.width)
+                    else {
+                        input.push(mem);
+                        break;
                     };
+                    ld = val;
                     cpu.set_reservation(raw_paddr);
                 }
                 AtomicOp::Sc => {
                     if cpu.check_reservation(raw_paddr) {
                         // SC success — store will be deferred to commit via store buffer
                         // Resolve the store buffer entry
-                        store_buffer.resolve(mem.rob_tag, mem.vaddr, raw_paddr, mem.store_data);
+                        let data = cpu.store_value(mem.store_data, mem.ctrl.width);
+                        store_buffer.resolve(mem.rob_tag, mem.vaddr, raw_paddr, data);
                         ld = 0; // success
-                        cpu.clear_reservation();
                     } else {
                         // SC failed — cancel the store buffer entry (no memory write)
                         store_buffer.cancel(mem.rob_tag);
-                        ld = 1; // fail — do NOT clear reservation
+                        ld = 1; // fail
                     }
+                    // SC invalidates the reservation whether or not it succeeds
+                    cpu.clear_reservation();
                 }
                 _ => {
                     // AMO: read old value (check store buffer first for forwarding)
-                    let old_val = match store_buffer.forward_load(raw_paddr, mem.ctrl.width) {
-                        ForwardResult::Hit(fwd) => fwd,
-                        ForwardResult::Stall => {
-                            input.push(mem);
-                            break;
-                        }
-                        ForwardResult::Miss => match mem.ctrl.width {
-                            MemWidth::Word => {
-                                (cpu.bus.bus.read_u32(raw_paddr) as i32) as i64 as u64
-                            }
-                            MemWidth::Double => cpu.bus.bus.read_u64(raw_paddr),
-                            _ => 0,
-                        },
+                    let Some(old_val) = atomic_read(cpu, store_buffer, raw_paddr, mem.ctrl.width)
+                    else {
+                        input.push(mem);
+                        break;
                     };
 
                     let new_val = Lsu::atomic_alu(
@@ -121,30 +147,21 @@ pub fn memory2_stage(
                     );
 
                     // Resolve store buffer with the computed new value
-                    store_buffer.resolve(mem.rob_tag, mem.vaddr, raw_paddr, new_val);
+                    let data = cpu.store_value(new_val, mem.ctrl.width);
+                    store_buffer.resolve(mem.rob_tag, mem.vaddr, raw_paddr, data);
+                    written = new_val;
 
                     ld = old_val;
-                    if cpu.check_reservation(raw_paddr) {
-                        cpu.clear_reservation();
-                    }
+                    cpu.snoop_store(raw_paddr, width_to_bytes(mem.ctrl.width));
                 }
             }
         } else if mem.ctrl.mem_read {
             // Check store buffer for forwarding first
             match store_buffer.forward_load(raw_paddr, mem.ctrl.width) {
                 ForwardResult::Hit(forwarded) => {
-                    // Apply sign extension for signed loads (LB, LH, LW on RV64).
-                    // The store buffer returns raw masked data without sign extension.
-                    ld = if mem.ctrl.signed_load {
-                        match mem.ctrl.width {
-                            MemWidth::Byte => (forwarded as u8 as i8) as i64 as u64,
-                            MemWidth::Half => (forwarded as u16 as i16) as i64 as u64,
-                            MemWidth::Word => (forwarded as u32 as i32) as i64 as u64,
-                            _ => forwarded,
-                        }
-                    } else {
-                        forwarded
-                    };
+                    // The store buffer returns raw masked data in memory byte
+                    // order, without sign extension (LB, LH, LW on RV64).
+                    ld = cpu.load_value(forwarded, mem.ctrl.width, mem.ctrl.signed_load);
                     // NaN-boxing for FP loads forwarded from store buffer
                     if mem.ctrl.fp_reg_write && matches!(mem.ctrl.width, MemWidth::Word) {
                         ld |= 0xFFFF_FFFF_0000_0000;
@@ -163,57 +180,44 @@ pub fn memory2_stage(
                 }
                 ForwardResult::Miss => {
                     // Read from memory/cache
-                    ld = if is_ram {
-                        unsafe {
-                            match (mem.ctrl.width, mem.ctrl.signed_load) {
-                                (MemWidth::Byte, true) => {
-                                    (*cpu.ram_ptr.add(ram_offset) as i8) as i64 as u64
-                                }
-                                (MemWidth::Half, true) => {
-                                    ((cpu.ram_ptr.add(ram_offset) as *const u16).read_unaligned()
-                                        as i16) as i64 as u64
-                                }
-                                (MemWidth::Word, true) => {
-                                    ((cpu.ram_ptr.add(ram_offset) as *const u32).read_unaligned()
-                                        as i32) as i64 as u64
-                                }
-                                (MemWidth::Byte, false) => *cpu.ram_ptr.add(ram_offset) as u64,
-                                (MemWidth::Half, false) => {
-                                    (cpu.ram_ptr.add(ram_offset) as *const u16).read_unaligned()
-                                        as u64
-                                }
-                                (MemWidth::Word, false) => {
-                                    (cpu.ram_ptr.add(ram_offset) as *const u32).read_unaligned()
-                                        as u64
-                                }
-                                (MemWidth::Double, _) => {
-                                    (cpu.ram_ptr.add(ram_offset) as *const u64).read_unaligned()
-                                }
+                    // Read the raw bytes; byte order and sign extension are
+                    // applied below.
+                    let loaded = if !is_aligned(raw_paddr, width_to_bytes(mem.ctrl.width)) {
+                        cpu.read_phys(raw_paddr, mem.ctrl.width, false)
+                    } else if is_ram {
+                        Ok(unsafe {
+                            let ptr = cpu.ram_ptr.add(ram_offset);
+                            match mem.ctrl.width {
+                                MemWidth::Byte => *ptr as u64,
+                                MemWidth::Half => (ptr as *const u16).read_unaligned() as u64,
+                                MemWidth::Word => (ptr as *const u32).read_unaligned() as u64,
+                                MemWidth::Double => (ptr as *const u64).read_unaligned(),
                                 _ => 0,
                             }
-                        }
+                        })
                     } else {
-                        match (mem.ctrl.width, mem.ctrl.signed_load) {
-                            (MemWidth::Byte, true) => {
-                                (cpu.bus.bus.read_u8(raw_paddr) as i8) as i64 as u64
-                            }
-                            (MemWidth::Half, true) => {
-                                (cpu.bus.bus.read_u16(raw_paddr) as i16) as i64 as u64
-                            }
-                            (MemWidth::Word, true) => {
-                                (cpu.bus.bus.read_u32(raw_paddr) as i32) as i64 as u64
-                            }
-                            (MemWidth::Byte, false) => cpu.bus.bus.read_u8(raw_paddr) as u64,
-                            (MemWidth::Half, false) => cpu.bus.bus.read_u16(raw_paddr) as u64,
-                            (MemWidth::Word, false) => cpu.bus.bus.read_u32(raw_paddr) as u64,
-                            (MemWidth::Double, _) => cpu.bus.bus.read_u64(raw_paddr),
-                            _ => 0,
+                        let bus = &mut cpu.bus.bus;
+                        match mem.ctrl.width {
+                            MemWidth::Byte => bus.read_u8(raw_paddr).map(u64::from),
+                            MemWidth::Half => bus.read_u16(raw_paddr).map(u64::from),
+                            MemWidth::Word => bus.read_u32(raw_paddr).map(u64::from),
+                            MemWidth::Double => bus.read_u64(raw_paddr),
+                            _ => Ok(0),
                         }
                     };
 
-                    // NaN-boxing for FP loads
-                    if mem.ctrl.fp_reg_write && matches!(mem.ctrl.width, MemWidth::Word) {
-                        ld |= 0xFFFF_FFFF_0000_0000;
+                    match loaded {
+                        Ok(val) => {
+                            ld = cpu.load_value(val, mem.ctrl.width, mem.ctrl.signed_load);
+                            // NaN-boxing for FP loads
+                            if mem.ctrl.fp_reg_write && matches!(mem.ctrl.width, MemWidth::Word) {
+                                ld |= 0xFFFF_FFFF_0000_0000;
+                            }
+                        }
+                        Err(_) => {
+                            trap = Some(Trap::LoadAccessFault(mem.vaddr));
+                            exception_stage = Some(ExceptionStage::Memory);
+                        }
                     }
                 }
             }
@@ -226,11 +230,11 @@ pub fn memory2_stage(
             }
         } else if mem.ctrl.mem_write {
             // Stores: resolve store buffer with paddr + data, NO memory write
-            store_buffer.resolve(mem.rob_tag, mem.vaddr, raw_paddr, mem.store_data);
+            let data = cpu.store_value(mem.store_data, mem.ctrl.width);
+            store_buffer.resolve(mem.rob_tag, mem.vaddr, raw_paddr, data);
 
-            if cpu.check_reservation(raw_paddr) {
-                cpu.clear_reservation();
-            }
+            // Any store to the reserved granule breaks an outstanding LR/SC pair
+            cpu.snoop_store(raw_paddr, width_to_bytes(mem.ctrl.width));
 
             if cpu.trace {
                 eprintln!(
@@ -242,6 +246,11 @@ pub fn memory2_stage(
             eprintln!("M2  pc={:#x} (pass-through)", mem.pc);
         }
 
+        // The commit log reports each access's address and written data
+        if cpu.commit_log.is_some() && (mem.ctrl.mem_read || mem.ctrl.mem_write) {
+            rob.set_store_info(mem.rob_tag, mem.vaddr, written);
+        }
+
         output.push(Mem2WbEntry {
             rob_tag: mem.rob_tag,
             pc: mem.pc,
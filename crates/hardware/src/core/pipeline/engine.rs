@@ -6,12 +6,16 @@
 //! 3. **`ExecutionEngine`** — high-level trait covering the entire backend.
 //! 4. **`PipelineDispatch`** — enum dispatch for type-erased pipeline storage.
 
+use crate::core::pipeline::backend::shared::commit::write_store;
 use crate::core::pipeline::latches::RenameIssueEntry;
 use crate::core::pipeline::rob::Rob;
 use crate::core::pipeline::scoreboard::Scoreboard;
 use crate::core::pipeline::store_buffer::StoreBuffer;
 use serde::Deserialize;
 
+/// Number of frontend stages: Fetch1, Fetch2, Decode and Rename.
+const FRONTEND_STAGES: usize = 4;
+
 /// Backend type selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -67,6 +71,7 @@ impl<E: ExecutionEngine> Pipeline<E> {
     /// Run one cycle of the entire pipeline.
     pub fn tick(&mut self, cpu: &mut crate::core::Cpu) {
         let pc_before = cpu.pc;
+        cpu.frontend_head_pc = self.frontend_head_pc();
 
         // Backend always runs (commit/writeback/memory must drain even during stalls)
         self.engine.tick(cpu, &mut self.rename_output);
@@ -90,6 +95,18 @@ impl<E: ExecutionEngine> Pipeline<E> {
         if needs_frontend_flush {
             self.frontend.flush();
             self.rename_output.clear();
+            // Deep frontends and simple timing charge a refetch penalty per
+            // mispredict.
+            self.frontend.fetch1_stall = std::mem::take(&mut cpu.redirect_penalty);
+            // The perfect predictor's corrections are free: run the stages
+            // ahead of this cycle's tick so the committed path reaches issue
+            // as soon as it would have had fetch followed it all along.
+            if std::mem::take(&mut cpu.oracle_redirect) && cpu.exit_code.is_none() {
+                for _ in 1..FRONTEND_STAGES {
+                    self.frontend
+                        .tick(cpu, &mut self.engine, &mut self.rename_output);
+                }
+            }
         }
 
         // Frontend runs every cycle (per-stage stalls are handled internally)
@@ -105,6 +122,51 @@ impl<E: ExecutionEngine> Pipeline<E> {
         self.rename_output.clear();
         self.engine.flush(cpu);
     }
+
+    /// Returns `true` if no instruction or store is in flight.
+    pub fn is_idle(&self) -> bool {
+        self.engine.rob().is_empty()
+            && self.engine.store_buffer().is_empty()
+            && self.rename_output.is_empty()
+            && self.frontend.fetch1_fetch2.is_empty()
+            && self.frontend.fetch2_decode.is_empty()
+            && self.frontend.decode_rename.is_empty()
+    }
+
+    /// PC of the oldest instruction fetched but not yet in the ROB.
+    fn frontend_head_pc(&self) -> Option<u64> {
+        self.rename_output
+            .first()
+            .map(|e| e.pc)
+            .or_else(|| self.frontend.decode_rename.first().map(|e| e.pc))
+            .or_else(|| self.frontend.fetch2_decode.first().map(|e| e.pc))
+            .or_else(|| self.frontend.fetch1_fetch2.first().map(|e| e.pc))
+    }
+
+    /// Discards all in-flight work so that `cpu` holds the complete
+    /// architectural state.
+    ///
+    /// Committed stores still in the store buffer are written to memory and
+    /// the PC is set to the oldest instruction that has not retired, so
+    /// execution resumes exactly where it left off.
+    pub fn quiesce(&mut self, cpu: &mut crate::core::Cpu) {
+        let resume_pc = self
+            .engine
+            .rob()
+            .peek_head()
+            .map(|e| e.pc)
+            .or_else(|| self.frontend_head_pc())
+            .unwrap_or(cpu.pc);
+        self.flush(cpu);
+        while let Some(store) = self.engine.store_buffer_mut().drain_one() {
+            if let Some(paddr) = store.paddr {
+                cpu.bus.bus.set_access_pc(store.pc);
+                let _ = write_store(cpu, paddr, store.width, store.data);
+            }
+        }
+        cpu.pc = resume_pc;
+        cpu.redirect_pending = false;
+    }
 }
 
 /// Type-erased pipeline for storage in the non-generic Cpu struct.
@@ -131,4 +193,20 @@ impl PipelineDispatch {
             Self::OutOfOrder => unimplemented!("out-of-order pipeline"),
         }
     }
+
+    /// Whether the pipeline is empty (see [`Pipeline::is_idle`]).
+    pub fn is_idle(&self) -> bool {
+        match self {
+            Self::InOrder(p) => p.is_idle(),
+            Self::OutOfOrder => unimplemented!("out-of-order pipeline"),
+        }
+    }
+
+    /// Drain to architectural state (see [`Pipeline::quiesce`]).
+    pub fn quiesce(&mut self, cpu: &mut crate::core::Cpu) {
+        match self {
+            Self::InOrder(p) => p.quiesce(cpu),
+            Self::OutOfOrder => unimplemented!("out-of-order pipeline"),
+        }
+    }
 }
@@ -11,8 +11,9 @@ use crate::common::error::{ExceptionStage, Trap};
 use crate::core::Cpu;
 use crate::core::pipeline::latches::{IdExEntry, IfIdEntry};
 use crate::core::pipeline::signals::{
-    AluOp, AtomicOp, ControlSignals, CsrOp, MemWidth, OpASrc, OpBSrc,
+    AluOp, AtomicOp, ControlSignals, CsrOp, MemWidth, OpASrc, OpBSrc, VectorOp,
 };
+use crate::core::units::lsu::ordering::Fence;
 use crate::isa::decode::decode as instruction_decode;
 use crate::isa::instruction::{Decoded, InstructionBits};
 use crate::isa::privileged::opcodes as sys_ops;
@@ -22,9 +23,8 @@ use crate::isa::rv64d::{funct7 as d_funct7, opcodes as d_opcodes};
 use crate::isa::rv64f::{funct3 as f_funct3, funct7 as f_funct7, opcodes as f_opcodes};
 use crate::isa::rv64i::{funct3 as i_funct3, funct7 as i_funct7, opcodes as i_opcodes};
 use crate::isa::rv64m::{funct3 as m_funct3, opcodes as m_opcodes};
-
-/// ADDI x0, x0, 0 instruction encoding (canonical NOP).
-const INSTRUCTION_NOP: u32 = 0x0000_0013;
+use crate::isa::rvb::{funct3 as b_funct3, funct7 as b_funct7, imm as b_imm};
+use crate::isa::rvv::{funct3 as v_funct3, funct6 as v_funct6, opcodes as v_opcodes};
 
 /// Zero instruction encoding (invalid instruction used as NOP).
 const INSTRUCTION_ZERO: u32 = 0;
@@ -45,7 +45,7 @@ const FP_FMT_SINGLE: u32 = 0;
 const FP_FMT_DOUBLE: u32 = 1;
 
 /// Decodes a single instruction into control signals.
-fn decode_instruction(inst: u32, pc: u64, d: &Decoded) -> Result<ControlSignals, Trap> {
+pub(crate) fn decode_instruction(inst: u32, pc: u64, d: &Decoded) -> Result<ControlSignals, Trap> {
     let mut c = ControlSignals {
         a_src: OpASrc::Reg1,
         b_src: OpBSrc::Imm,
@@ -107,22 +107,25 @@ fn decode_instruction(inst: u32, pc: u64, d: &Decoded) -> Result<ControlSignals,
         i_opcodes::OP_IMM | i_opcodes::OP_IMM_32 => {
             c.reg_write = true;
             c.is_rv32 = d.opcode == i_opcodes::OP_IMM_32;
-            c.alu = match d.funct3 {
-                i_funct3::ADD_SUB => AluOp::Add,
-                i_funct3::SLT => AluOp::Slt,
-                i_funct3::SLTU => AluOp::Sltu,
-                i_funct3::XOR => AluOp::Xor,
-                i_funct3::OR => AluOp::Or,
-                i_funct3::AND => AluOp::And,
-                i_funct3::SLL => AluOp::Sll,
-                i_funct3::SRL_SRA => {
-                    if (d.funct7 & FUNCT7_ALT_BIT) != 0 {
-                        AluOp::Sra
-                    } else {
-                        AluOp::Srl
+            c.alu = match decode_bitmanip_imm(inst, d, c.is_rv32)? {
+                Some(op) => op,
+                None => match d.funct3 {
+                    i_funct3::ADD_SUB => AluOp::Add,
+                    i_funct3::SLT => AluOp::Slt,
+                    i_funct3::SLTU => AluOp::Sltu,
+                    i_funct3::XOR => AluOp::Xor,
+                    i_funct3::OR => AluOp::Or,
+                    i_funct3::AND => AluOp::And,
+                    i_funct3::SLL => AluOp::Sll,
+                    i_funct3::SRL_SRA => {
+                        if (d.funct7 & FUNCT7_ALT_BIT) != 0 {
+                            AluOp::Sra
+                        } else {
+                            AluOp::Srl
+                        }
                     }
-                }
-                _ => return Err(Trap::IllegalInstruction(inst)),
+                    _ => return Err(Trap::IllegalInstruction(inst)),
+                },
             };
         }
         i_opcodes::OP_REG | i_opcodes::OP_REG_32 => {
@@ -142,6 +145,8 @@ fn decode_instruction(inst: u32, pc: u64, d: &Decoded) -> Result<ControlSignals,
                     m_funct3::REMU => AluOp::Remu,
                     _ => return Err(Trap::IllegalInstruction(inst)),
                 };
+            } else if let Some(op) = decode_bitmanip_reg(d, c.is_rv32) {
+                c.alu = op;
             } else {
                 c.alu = match (d.funct3, d.funct7) {
                     (i_funct3::ADD_SUB, i_funct7::DEFAULT) => AluOp::Add,
@@ -342,15 +347,98 @@ fn decode_instruction(inst: u32, pc: u64, d: &Decoded) -> Result<ControlSignals,
             }
         }
         i_opcodes::OP_MISC_MEM => match d.funct3 {
-            i_funct3::FENCE => {}
+            i_funct3::FENCE => c.is_fence = !Fence::decode(inst).is_nop(),
             i_funct3::FENCE_I => c.is_fence_i = true,
             _ => return Err(Trap::IllegalInstruction(inst)),
         },
+        v_opcodes::OP_V => match d.funct3 {
+            v_funct3::OPCFG => {
+                // vsetvli takes vtype from zimm, vsetvl from rs2. The
+                // vsetivli form (bits 31:30 = 11) is not supported.
+                c.b_src = if inst & v_opcodes::VSETVLI_SELECT_BIT == 0 {
+                    OpBSrc::Imm
+                } else if d.funct7 == v_opcodes::VSETVL {
+                    OpBSrc::Reg2
+                } else {
+                    return Err(Trap::IllegalInstruction(inst));
+                };
+                c.reg_write = true;
+                c.vector_op = VectorOp::SetVl;
+            }
+            v_funct3::OPIVV => {
+                // Masked forms (vm = 0) are not supported.
+                if inst & v_opcodes::VM_BIT == 0 {
+                    return Err(Trap::IllegalInstruction(inst));
+                }
+                c.vector_op = match (inst >> v_opcodes::FUNCT6_SHIFT) & v_opcodes::FUNCT6_MASK {
+                    v_funct6::VADD => VectorOp::Add,
+                    v_funct6::VSUB => VectorOp::Sub,
+                    _ => return Err(Trap::IllegalInstruction(inst)),
+                };
+                // Operands are vector registers, not integer ones.
+                c.a_src = OpASrc::Zero;
+                c.b_src = OpBSrc::Zero;
+            }
+            _ => return Err(Trap::IllegalInstruction(inst)),
+        },
         _ => return Err(Trap::IllegalInstruction(inst)),
     }
     Ok(c)
 }
 
+/// Decodes the Zba/Zbb register-register forms on `OP_REG`/`OP_REG_32`.
+///
+/// Returns `None` if the encoding is not a bit-manipulation instruction.
+/// On `OP_REG_32` (`is_word`) the shift-and-add forms are the `.uw`
+/// variants.
+fn decode_bitmanip_reg(d: &Decoded, is_word: bool) -> Option<AluOp> {
+    let op = match (d.funct7, d.funct3, is_word) {
+        (b_funct7::NEGATE, b_funct3::ANDN, false) => AluOp::Andn,
+        (b_funct7::NEGATE, b_funct3::ORN, false) => AluOp::Orn,
+        (b_funct7::NEGATE, b_funct3::XNOR, false) => AluOp::Xnor,
+        (b_funct7::MINMAX, b_funct3::MIN, false) => AluOp::Min,
+        (b_funct7::MINMAX, b_funct3::MINU, false) => AluOp::Minu,
+        (b_funct7::MINMAX, b_funct3::MAX, false) => AluOp::Max,
+        (b_funct7::MINMAX, b_funct3::MAXU, false) => AluOp::Maxu,
+        (b_funct7::ROTATE, b_funct3::ROL, _) => AluOp::Rol,
+        (b_funct7::ROTATE, b_funct3::ROR, _) => AluOp::Ror,
+        (b_funct7::SHADD, b_funct3::SH1ADD, _) => AluOp::Sh1Add,
+        (b_funct7::SHADD, b_funct3::SH2ADD, _) => AluOp::Sh2Add,
+        (b_funct7::SHADD, b_funct3::SH3ADD, _) => AluOp::Sh3Add,
+        (b_funct7::ADD_UW, b_funct3::ADD_UW, true) => AluOp::AddUw,
+        (b_funct7::ADD_UW, b_funct3::ZEXT_H, true) if d.rs2 == 0 => AluOp::ZextH,
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// Decodes the Zbb unary and Zba/Zbb immediate forms on `OP_IMM`/`OP_IMM_32`.
+///
+/// Returns `Ok(None)` if the encoding is not a bit-manipulation
+/// instruction, and an illegal-instruction trap for an unassigned unary
+/// selector.
+fn decode_bitmanip_imm(inst: u32, d: &Decoded, is_word: bool) -> Result<Option<AluOp>, Trap> {
+    let imm12 = inst >> 20;
+    let funct6 = inst >> b_imm::FUNCT6_SHIFT;
+    let op = match d.funct3 {
+        i_funct3::SLL if d.funct7 == b_funct7::ROTATE => match (d.rs2, is_word) {
+            (b_imm::CLZ, _) => AluOp::Clz,
+            (b_imm::CTZ, _) => AluOp::Ctz,
+            (b_imm::CPOP, _) => AluOp::Cpop,
+            (b_imm::SEXT_B, false) => AluOp::SextB,
+            (b_imm::SEXT_H, false) => AluOp::SextH,
+            _ => return Err(Trap::IllegalInstruction(inst)),
+        },
+        i_funct3::SLL if is_word && funct6 == b_imm::SLLI_UW => AluOp::SlliUw,
+        i_funct3::SRL_SRA if !is_word && imm12 == b_imm::REV8 => AluOp::Rev8,
+        i_funct3::SRL_SRA if !is_word && imm12 == b_imm::ORC_B => AluOp::OrcB,
+        i_funct3::SRL_SRA if !is_word && funct6 == b_imm::RORI => AluOp::Ror,
+        i_funct3::SRL_SRA if is_word && d.funct7 == b_funct7::ROTATE => AluOp::Ror,
+        _ => return Ok(None),
+    };
+    Ok(Some(op))
+}
+
 /// Executes the decode stage.
 ///
 /// Consumes Fetch2->Decode entries (IfIdEntry) and produces
@@ -360,11 +448,13 @@ pub fn decode_stage(cpu: &mut Cpu, input: &mut Vec<IfIdEntry>, output: &mut Vec<
     let mut bundle_writes: Vec<(usize, bool)> = Vec::with_capacity(cpu.pipeline_width);
     let mut broke_on_trap = false;
 
-    for if_entry in input.iter() {
+    // Decode is `pipeline_width` wide; a wider fetch leaves the rest queued.
+    for if_entry in input.iter().take(cpu.pipeline_width) {
         if let Some(trap) = &if_entry.trap {
             output.push(IdExEntry {
                 pc: if_entry.pc,
                 inst: if_entry.inst,
+                raw_inst: if_entry.raw_inst,
                 inst_size: if_entry.inst_size,
                 trap: Some(trap.clone()),
                 exception_stage: if_entry.exception_stage,
@@ -377,7 +467,8 @@ pub fn decode_stage(cpu: &mut Cpu, input: &mut Vec<IfIdEntry>, output: &mut Vec<
 
         let inst = if_entry.inst;
 
-        if inst == INSTRUCTION_NOP || inst == INSTRUCTION_ZERO {
+        // NOPs are not elided: they retire and must advance minstret.
+        if inst == INSTRUCTION_ZERO {
             consumed_count += 1;
             continue;
         }
@@ -438,6 +529,7 @@ pub fn decode_stage(cpu: &mut Cpu, input: &mut Vec<IfIdEntry>, output: &mut Vec<
         output.push(IdExEntry {
             pc: if_entry.pc,
             inst,
+            raw_inst: if_entry.raw_inst,
             inst_size: if_entry.inst_size,
             rs1: d.rs1,
             rs2: d.rs2,
@@ -453,6 +545,7 @@ pub fn decode_stage(cpu: &mut Cpu, input: &mut Vec<IfIdEntry>, output: &mut Vec<
             pred_taken: if_entry.pred_taken,
             pred_target: if_entry.pred_target,
             ghr_snapshot: if_entry.ghr_snapshot,
+            ras_checkpoint: if_entry.ras_checkpoint,
         });
 
         consumed_count += 1;
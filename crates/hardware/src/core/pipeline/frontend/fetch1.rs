@@ -13,8 +13,9 @@ use crate::core::Cpu;
 use crate::core::arch::csr;
 use crate::core::pipeline::latches::Fetch1Fetch2Entry;
 use crate::core::units::bru::BranchPredictor;
-use crate::isa::abi;
+use crate::core::units::bru::ras::ras_hint;
 use crate::isa::rv64i::opcodes;
+use crate::isa::rvc::expand::expand;
 
 /// Executes the Fetch1 stage: PC generation + I-TLB + branch prediction.
 ///
@@ -28,7 +29,7 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
     let c_enabled = (cpu.csrs.misa & csr::MISA_EXT_C) != 0;
     let align_mask: u64 = if c_enabled { 1 } else { 3 };
 
-    for _ in 0..cpu.pipeline_width {
+    for _ in 0..cpu.fetch_width {
         // Check alignment
         let mut fetch_trap = None;
         if (current_pc & align_mask) != 0 {
@@ -54,6 +55,7 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
             }
         };
         *stall_out += cycles;
+        cpu.stats.stalls_itlb += cycles;
 
         let trap_cause = fetch_trap.or(trap);
         if let Some(ref trap_cause) = trap_cause {
@@ -68,6 +70,7 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
                 trap: Some(trap_cause.clone()),
                 exception_stage: Some(ExceptionStage::Fetch),
                 ghr_snapshot: 0,
+                ras_checkpoint: cpu.branch_predictor.checkpoint_ras(),
             });
             break;
         }
@@ -82,7 +85,8 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
                 ptr.read_unaligned()
             }
         } else {
-            cpu.bus.bus.read_u16(phys_addr)
+            // Only used for prediction; fetch2 raises the access fault.
+            cpu.bus.bus.read_u16(phys_addr).unwrap_or(0)
         };
 
         let is_compressed =
@@ -94,36 +98,26 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
             INSTRUCTION_SIZE_32
         };
 
-        // Branch prediction (peek at opcode from half_word for 32-bit instructions)
+        // Branch prediction
         let mut next_pc_calc = current_pc.wrapping_add(step);
         let mut pred_taken = false;
         let mut pred_target = 0;
         let mut stop_fetch = false;
         let mut ghr_snapshot = 0u64;
 
-        if is_compressed {
-            // Compressed branch prediction: detect C.BEQZ / C.BNEZ
-            // Quadrant 1 (bits 1:0 = 01), funct3 = 110 or 111
-            let quadrant = half_word & 0x3;
-            let funct3_c = (half_word >> 13) & 0x7;
-            if quadrant == 0x01 && (funct3_c == 0b110 || funct3_c == 0b111) {
-                ghr_snapshot = cpu.branch_predictor.snapshot_history();
-                let (taken, target) = cpu.branch_predictor.predict_branch(current_pc);
-                cpu.branch_predictor.speculate(current_pc, taken);
-                if taken && let Some(tgt) = target {
-                    next_pc_calc = tgt;
-                    pred_taken = true;
-                    pred_target = tgt;
-                    stop_fetch = true;
-                }
-            }
+        // Compressed instructions are expanded up front so that the same
+        // 32-bit opcode checks drive prediction and RAS lookups for both
+        // encodings (C.J -> JAL, C.JR/C.JALR -> JALR, C.BEQZ/C.BNEZ -> BRANCH).
+        let full_inst = if is_compressed {
+            expand(half_word)
         } else {
-            // For 32-bit instructions, read full instruction for opcode extraction
+            // For 32-bit instructions, read the upper half-word as well
             let upper_va = current_pc.wrapping_add(2);
             let crosses_page = (current_pc >> 12) != (upper_va >> 12);
             let upper_phys = if crosses_page {
                 let result = cpu.translate(VirtAddr::new(upper_va), AccessType::Fetch);
                 *stall_out += result.cycles;
+                cpu.stats.stalls_itlb += result.cycles;
                 if result.trap.is_some() {
                     // Page crossing fault; let fetch2 handle it
                     output.push(Fetch1Fetch2Entry {
@@ -134,6 +128,7 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
                         trap: None,
                         exception_stage: None,
                         ghr_snapshot: 0,
+                        ras_checkpoint: cpu.branch_predictor.checkpoint_ras(),
                     });
                     cpu.pc = next_pc_calc;
                     break;
@@ -150,45 +145,62 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
                     ptr.read_unaligned()
                 }
             } else {
-                cpu.bus.bus.read_u16(upper_phys)
+                cpu.bus.bus.read_u16(upper_phys).unwrap_or(0)
             };
 
-            let full_inst = (upper_half as u32) << 16 | (half_word as u32);
-            let opcode = full_inst & OPCODE_MASK;
-            let rd = ((full_inst >> RD_SHIFT) & RD_MASK) as usize;
-            let rs1 = ((full_inst >> RS1_SHIFT) & RS1_MASK) as usize;
+            (upper_half as u32) << 16 | (half_word as u32)
+        };
 
-            if opcode == opcodes::OP_BRANCH {
-                ghr_snapshot = cpu.branch_predictor.snapshot_history();
-                let (taken, target) = cpu.branch_predictor.predict_branch(current_pc);
-                cpu.branch_predictor.speculate(current_pc, taken);
-                if taken && let Some(tgt) = target {
-                    next_pc_calc = tgt;
-                    pred_taken = true;
-                    pred_target = tgt;
-                    stop_fetch = true;
-                }
-            } else if opcode == opcodes::OP_JAL {
-                if let Some(tgt) = cpu.branch_predictor.predict_btb(current_pc) {
-                    next_pc_calc = tgt;
-                    pred_taken = true;
-                    pred_target = tgt;
-                    stop_fetch = true;
-                }
-            } else if opcode == opcodes::OP_JALR {
-                if rd == abi::REG_ZERO && rs1 == abi::REG_RA {
-                    if let Some(tgt) = cpu.branch_predictor.predict_return() {
-                        next_pc_calc = tgt;
-                        pred_taken = true;
-                        pred_target = tgt;
-                    }
-                } else if let Some(tgt) = cpu.branch_predictor.predict_btb(current_pc) {
+        let opcode = full_inst & OPCODE_MASK;
+        let rd = ((full_inst >> RD_SHIFT) & RD_MASK) as usize;
+        let rs1 = ((full_inst >> RS1_SHIFT) & RS1_MASK) as usize;
+        let (is_call, is_ret) = ras_hint(opcode == opcodes::OP_JALR, rd, rs1);
+
+        if opcode == opcodes::OP_BRANCH {
+            ghr_snapshot = cpu.branch_predictor.snapshot_history();
+            let (taken, target) = cpu.branch_predictor.predict_branch(current_pc);
+            // Speculate the direction fetch actually follows: a taken
+            // prediction without a BTB target falls through.
+            cpu.branch_predictor
+                .speculate(current_pc, taken && target.is_some());
+            if taken && let Some(tgt) = target {
+                next_pc_calc = tgt;
+                pred_taken = true;
+                pred_target = tgt;
+                stop_fetch = true;
+            }
+        } else if opcode == opcodes::OP_JAL {
+            ghr_snapshot = cpu.branch_predictor.snapshot_history();
+            if let Some(tgt) = cpu.branch_predictor.predict_btb(current_pc) {
+                next_pc_calc = tgt;
+                pred_taken = true;
+                pred_target = tgt;
+                stop_fetch = true;
+            }
+        } else if opcode == opcodes::OP_JALR {
+            ghr_snapshot = cpu.branch_predictor.snapshot_history();
+            if is_ret {
+                if let Some(tgt) = cpu.branch_predictor.predict_return() {
                     next_pc_calc = tgt;
                     pred_taken = true;
                     pred_target = tgt;
                 }
-                stop_fetch = true;
+                cpu.branch_predictor.on_return();
+            } else if let Some(tgt) = cpu.branch_predictor.predict_indirect(current_pc) {
+                next_pc_calc = tgt;
+                pred_taken = true;
+                pred_target = tgt;
             }
+            stop_fetch = true;
+        }
+
+        // Any jump that links into ra or t0 is a call (JAL, JALR, and the
+        // expanded C.JALR). The RAS is pushed speculatively here, after any
+        // pop, so that a return fetched before the call resolves still sees
+        // its return address; the BTB waits for the resolved target at
+        // execute.
+        if (opcode == opcodes::OP_JAL || opcode == opcodes::OP_JALR) && is_call {
+            cpu.branch_predictor.on_call(current_pc.wrapping_add(step));
         }
 
         output.push(Fetch1Fetch2Entry {
@@ -199,6 +211,7 @@ pub fn fetch1_stage(cpu: &mut Cpu, output: &mut Vec<Fetch1Fetch2Entry>, stall_ou
             trap: None,
             exception_stage: None,
             ghr_snapshot,
+            ras_checkpoint: cpu.branch_predictor.checkpoint_ras(),
         });
 
         current_pc = next_pc_calc;
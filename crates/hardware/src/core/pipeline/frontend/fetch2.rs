@@ -11,11 +11,13 @@ use crate::common::constants::{
 use crate::common::{AccessType, ExceptionStage, Trap, VirtAddr};
 use crate::core::Cpu;
 use crate::core::pipeline::latches::{Fetch1Fetch2Entry, IfIdEntry};
+use crate::isa::disasm;
 use crate::isa::rvc::expand::expand;
 
 /// Executes the Fetch2 stage: I-cache access + RVC expansion.
 ///
-/// Consumes Fetch1->Fetch2 entries and produces Fetch2->Decode entries.
+/// Consumes Fetch1->Fetch2 entries and appends Fetch2->Decode entries to
+/// the back of the fetch queue.
 pub fn fetch2_stage(
     cpu: &mut Cpu,
     input: &mut Vec<Fetch1Fetch2Entry>,
@@ -23,7 +25,7 @@ pub fn fetch2_stage(
     stall_out: &mut u64,
 ) {
     let entries = std::mem::take(input);
-    output.clear();
+    let queued = output.len();
 
     for f1 in entries {
         // Propagate traps from Fetch1
@@ -34,12 +36,14 @@ pub fn fetch2_stage(
             output.push(IfIdEntry {
                 pc: f1.pc,
                 inst: 0,
+                raw_inst: 0,
                 inst_size: 4,
                 pred_taken: f1.pred_taken,
                 pred_target: f1.pred_target,
                 trap: f1.trap,
                 exception_stage: f1.exception_stage,
                 ghr_snapshot: f1.ghr_snapshot,
+                ras_checkpoint: f1.ras_checkpoint,
             });
             break;
         }
@@ -51,19 +55,25 @@ pub fn fetch2_stage(
             let offset = (phys_addr - cpu.ram_start) as usize;
             unsafe {
                 let ptr = cpu.ram_ptr.add(offset) as *const u16;
-                ptr.read_unaligned()
+                Ok(ptr.read_unaligned())
             }
         } else {
             cpu.bus.bus.read_u16(phys_addr)
         };
+        let (half_word, fetch_fault) = match half_word {
+            Ok(half_word) => (half_word, None),
+            Err(_) => (0, Some(Trap::InstructionAccessFault(f1.pc))),
+        };
 
         let is_compressed =
             (half_word & COMPRESSED_INSTRUCTION_MASK) != COMPRESSED_INSTRUCTION_VALUE;
 
-        let (inst, step, inst_trap) = if is_compressed {
+        let (inst, step, inst_trap) = if let Some(t) = fetch_fault {
+            (0, INSTRUCTION_SIZE_16, Some(t))
+        } else if is_compressed {
             let expanded = expand(half_word);
             if expanded == 0 {
-                if output.is_empty() {
+                if output.len() == queued {
                     (
                         0,
                         INSTRUCTION_SIZE_16,
@@ -82,6 +92,7 @@ pub fn fetch2_stage(
             let (upper_phys, upper_fault) = if crosses_page {
                 let result = cpu.translate(VirtAddr::new(upper_va), AccessType::Fetch);
                 *stall_out += result.cycles;
+                cpu.stats.stalls_itlb += result.cycles;
                 (result.paddr.val(), result.trap)
             } else {
                 (phys_addr + 2, None)
@@ -94,14 +105,23 @@ pub fn fetch2_stage(
                     let offset = (upper_phys - cpu.ram_start) as usize;
                     unsafe {
                         let ptr = cpu.ram_ptr.add(offset) as *const u16;
-                        ptr.read_unaligned()
+                        Ok(ptr.read_unaligned())
                     }
                 } else {
                     cpu.bus.bus.read_u16(upper_phys)
                 };
 
-                let full_inst = (upper_half as u32) << 16 | (half_word as u32);
-                (full_inst, INSTRUCTION_SIZE_32, None)
+                match upper_half {
+                    Ok(upper_half) => {
+                        let full_inst = (upper_half as u32) << 16 | (half_word as u32);
+                        (full_inst, INSTRUCTION_SIZE_32, None)
+                    }
+                    Err(_) => (
+                        0,
+                        INSTRUCTION_SIZE_32,
+                        Some(Trap::InstructionAccessFault(upper_va)),
+                    ),
+                }
             }
         };
 
@@ -112,12 +132,14 @@ pub fn fetch2_stage(
             output.push(IfIdEntry {
                 pc: f1.pc,
                 inst: 0,
+                raw_inst: 0,
                 inst_size: step,
                 pred_taken: f1.pred_taken,
                 pred_target: f1.pred_target,
                 trap: Some(t),
                 exception_stage: Some(ExceptionStage::Fetch),
                 ghr_snapshot: f1.ghr_snapshot,
+                ras_checkpoint: f1.ras_checkpoint,
             });
             break;
         }
@@ -125,23 +147,40 @@ pub fn fetch2_stage(
         // I-cache access latency (only when I-cache is enabled; otherwise the
         // instruction bytes were already read directly from the bus above).
         if cpu.l1_i_cache.enabled {
-            *stall_out += cpu
+            let lat = cpu
                 .simulate_memory_access(crate::common::PhysAddr::new(phys_addr), AccessType::Fetch);
+            cpu.stats.record_mem_latency(f1.pc, phys_addr, lat);
+            *stall_out += lat;
+            cpu.stats.stalls_icache += lat;
         }
 
         if cpu.trace {
-            eprintln!("F2  pc={:#x} inst={:#010x} (sz={})", f1.pc, inst, step);
+            eprintln!(
+                "F2  pc={:#x} inst={:#010x} (sz={}) {}",
+                f1.pc,
+                inst,
+                step,
+                disasm(inst)
+            );
         }
 
+        let raw_inst = if step == INSTRUCTION_SIZE_16 {
+            half_word as u32
+        } else {
+            inst
+        };
+
         output.push(IfIdEntry {
             pc: f1.pc,
             inst,
+            raw_inst,
             inst_size: step,
             pred_taken: f1.pred_taken,
             pred_target: f1.pred_target,
             trap: None,
             exception_stage: None,
             ghr_snapshot: f1.ghr_snapshot,
+            ras_checkpoint: f1.ras_checkpoint,
         });
     }
 }
@@ -18,8 +18,11 @@ use std::marker::PhantomData;
 pub struct Frontend<E: ExecutionEngine> {
     /// Fetch1 -> Fetch2 latch.
     pub fetch1_fetch2: Vec<Fetch1Fetch2Entry>,
-    /// Fetch2 -> Decode latch (reuses IfIdEntry for I-cache result).
+    /// Fetch queue between Fetch2 and Decode (reuses IfIdEntry for the
+    /// I-cache result), oldest first.
     pub fetch2_decode: Vec<IfIdEntry>,
+    /// Fetch queue capacity; 0 holds a single fetch group.
+    pub fetch_queue_size: usize,
     /// Decode -> Rename latch (reuses IdExEntry for decoded signals).
     pub decode_rename: Vec<IdExEntry>,
     /// Fetch1 stall counter (I-TLB translation latency).
@@ -30,11 +33,13 @@ pub struct Frontend<E: ExecutionEngine> {
 }
 
 impl<E: ExecutionEngine> Frontend<E> {
-    /// Creates a new frontend with the given pipeline width.
-    pub fn new(width: usize) -> Self {
+    /// Creates a new frontend with the given pipeline width and fetch queue
+    /// capacity.
+    pub fn new(width: usize, fetch_queue_size: usize) -> Self {
         Self {
             fetch1_fetch2: Vec::with_capacity(width),
-            fetch2_decode: Vec::with_capacity(width),
+            fetch2_decode: Vec::with_capacity(width.max(fetch_queue_size)),
+            fetch_queue_size,
             decode_rename: Vec::with_capacity(width),
             fetch1_stall: 0,
             fetch2_stall: 0,
@@ -61,10 +66,12 @@ impl<E: ExecutionEngine> Frontend<E> {
             decode::decode_stage(cpu, &mut self.fetch2_decode, &mut self.decode_rename);
         }
 
-        // Fetch2: fetch1_fetch2 -> fetch2_decode (gated by fetch2_stall or backpressure)
+        // Fetch2: fetch1_fetch2 -> fetch queue (gated by fetch2_stall or a
+        // full queue). Without a queue, F2 waits for decode to drain the
+        // previous group.
         if self.fetch2_stall > 0 {
             self.fetch2_stall -= 1;
-        } else if self.fetch2_decode.is_empty() {
+        } else if self.fetch_queue_has_room() {
             fetch2::fetch2_stage(
                 cpu,
                 &mut self.fetch1_fetch2,
@@ -84,6 +91,12 @@ impl<E: ExecutionEngine> Frontend<E> {
         }
     }
 
+    /// Returns whether the fetch queue can take the group waiting in Fetch1.
+    fn fetch_queue_has_room(&self) -> bool {
+        self.fetch2_decode.is_empty()
+            || self.fetch2_decode.len() + self.fetch1_fetch2.len() <= self.fetch_queue_size
+    }
+
     /// Flushes all frontend latches and stall counters.
     pub fn flush(&mut self) {
         self.fetch1_fetch2.clear();
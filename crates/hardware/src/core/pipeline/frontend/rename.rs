@@ -46,6 +46,13 @@ pub fn rename_stage<E: ExecutionEngine>(
             }
         };
 
+        // The ROB carries the RAS checkpoint to commit, where it becomes the
+        // retirement state used to repair the RAS on a full flush.
+        engine
+            .rob_mut()
+            .set_ras_checkpoint(rob_tag, id.ras_checkpoint);
+        engine.rob_mut().set_raw_inst(rob_tag, id.raw_inst);
+
         // Capture source register tags BEFORE updating scoreboard for rd.
         // This ensures that if rs == rd, we get the PREVIOUS producer tag,
         // not the one we're about to set for ourselves.
@@ -67,7 +74,7 @@ pub fn rename_stage<E: ExecutionEngine>(
         // Allocate store buffer entry if this is a store (including atomic SC/AMO)
         if id.ctrl.mem_write {
             let width = id.ctrl.width;
-            if !engine.store_buffer_mut().allocate(rob_tag, width) {
+            if !engine.store_buffer_mut().allocate(rob_tag, id.pc, width) {
                 input.push(id);
                 break;
             }
@@ -96,6 +103,7 @@ pub fn rename_stage<E: ExecutionEngine>(
             pred_taken: id.pred_taken,
             pred_target: id.pred_target,
             ghr_snapshot: id.ghr_snapshot,
+            ras_checkpoint: id.ras_checkpoint,
         };
 
         if cpu.trace {
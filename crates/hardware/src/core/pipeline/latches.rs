@@ -10,6 +10,7 @@
 use crate::common::error::{ExceptionStage, Trap};
 use crate::core::pipeline::rob::RobTag;
 use crate::core::pipeline::signals::ControlSignals;
+use crate::core::units::bru::ras::RasCheckpoint;
 
 /// Entry in the IF/ID pipeline latch (Fetch to Decode stage).
 ///
@@ -21,6 +22,9 @@ pub struct IfIdEntry {
     pub pc: u64,
     /// 32-bit instruction encoding.
     pub inst: u32,
+    /// Encoding as fetched: the 16-bit parcel for compressed instructions,
+    /// otherwise equal to `inst`.
+    pub raw_inst: u32,
     /// Size of the instruction in bytes (2 for compressed, 4 for standard).
     pub inst_size: u64,
     /// Whether the branch predictor predicted this instruction as taken.
@@ -31,8 +35,12 @@ pub struct IfIdEntry {
     pub trap: Option<Trap>,
     /// Pipeline stage where the exception was first detected.
     pub exception_stage: Option<ExceptionStage>,
-    /// GHR snapshot captured at prediction time for speculative history repair.
+    /// GHR snapshot captured at prediction time (branches and indirect
+    /// jumps) for speculative history repair and indirect training.
     pub ghr_snapshot: u64,
+    /// RAS checkpoint taken after this instruction's own RAS update,
+    /// restored when younger instructions are flushed.
+    pub ras_checkpoint: RasCheckpoint,
 }
 
 /// Entry in the ID/EX pipeline latch (Decode to Execute stage).
@@ -45,6 +53,8 @@ pub struct IdExEntry {
     pub pc: u64,
     /// 32-bit instruction encoding.
     pub inst: u32,
+    /// Encoding as fetched (16-bit parcel for compressed instructions).
+    pub raw_inst: u32,
     /// Size of the instruction in bytes.
     pub inst_size: u64,
     /// First source register index (rs1).
@@ -73,8 +83,12 @@ pub struct IdExEntry {
     pub pred_taken: bool,
     /// Predicted target address for branch/jump instructions.
     pub pred_target: u64,
-    /// GHR snapshot captured at prediction time for speculative history repair.
+    /// GHR snapshot captured at prediction time (branches and indirect
+    /// jumps) for speculative history repair and indirect training.
     pub ghr_snapshot: u64,
+    /// RAS checkpoint taken after this instruction's own RAS update,
+    /// restored when younger instructions are flushed.
+    pub ras_checkpoint: RasCheckpoint,
 }
 
 /// Entry in the EX/MEM pipeline latch (Execute to Memory stage).
@@ -149,8 +163,12 @@ pub struct Fetch1Fetch2Entry {
     pub trap: Option<Trap>,
     /// Pipeline stage where the exception was detected.
     pub exception_stage: Option<ExceptionStage>,
-    /// GHR snapshot captured at prediction time for speculative history repair.
+    /// GHR snapshot captured at prediction time (branches and indirect
+    /// jumps) for speculative history repair and indirect training.
     pub ghr_snapshot: u64,
+    /// RAS checkpoint taken after this instruction's own RAS update,
+    /// restored when younger instructions are flushed.
+    pub ras_checkpoint: RasCheckpoint,
 }
 
 /// Entry from Rename -> Issue (also used as Issue -> Execute input).
@@ -199,8 +217,12 @@ pub struct RenameIssueEntry {
     pub pred_taken: bool,
     /// Branch prediction target.
     pub pred_target: u64,
-    /// GHR snapshot captured at prediction time for speculative history repair.
+    /// GHR snapshot captured at prediction time (branches and indirect
+    /// jumps) for speculative history repair and indirect training.
     pub ghr_snapshot: u64,
+    /// RAS checkpoint taken after this instruction's own RAS update,
+    /// restored when younger instructions are flushed.
+    pub ras_checkpoint: RasCheckpoint,
 }
 
 /// Entry from Execute -> Memory1 latch.
@@ -27,6 +27,9 @@ pub mod scoreboard;
 /// Store buffer with forwarding.
 pub mod store_buffer;
 
+/// Half-register dependency tracking for `.w` operations.
+pub mod subreg;
+
 /// Traits for pipeline stage components.
 pub mod traits;
 
@@ -12,6 +12,7 @@ use std::collections::HashMap;
 
 use crate::common::error::{ExceptionStage, Trap};
 use crate::core::pipeline::signals::ControlSignals;
+use crate::core::units::bru::ras::RasCheckpoint;
 
 /// Unique tag identifying an in-flight instruction in the ROB.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
@@ -49,6 +50,8 @@ pub struct RobEntry {
     pub pc: u64,
     /// Raw 32-bit instruction encoding.
     pub inst: u32,
+    /// Encoding as fetched (16-bit parcel for compressed instructions).
+    pub raw_inst: u32,
     /// Instruction size in bytes (2 or 4).
     pub inst_size: u64,
     /// Destination register index.
@@ -71,6 +74,11 @@ pub struct RobEntry {
     pub exception_stage: Option<ExceptionStage>,
     /// Deferred CSR write, if this is a CSR instruction.
     pub csr_update: Option<CsrUpdate>,
+    /// RAS checkpoint from fetch, committed as the retirement RAS state.
+    pub ras_checkpoint: RasCheckpoint,
+    /// Result published at execute by a low-half producer under
+    /// sub-register tracking, read by consumers of the high half only.
+    pub early_result: Option<u64>,
     /// Whether this entry is valid (occupied).
     pub valid: bool,
 }
@@ -160,6 +168,7 @@ impl Rob {
             tag,
             pc,
             inst,
+            raw_inst: inst,
             inst_size,
             rd,
             rd_fp,
@@ -171,6 +180,8 @@ impl Rob {
             trap: None,
             exception_stage: None,
             csr_update: None,
+            ras_checkpoint: RasCheckpoint::default(),
+            early_result: None,
             valid: true,
         };
 
@@ -209,6 +220,27 @@ impl Rob {
         }
     }
 
+    /// Sets the RAS checkpoint for a given entry.
+    pub fn set_ras_checkpoint(&mut self, tag: RobTag, cp: RasCheckpoint) {
+        if let Some(entry) = self.find_entry_mut(tag) {
+            entry.ras_checkpoint = cp;
+        }
+    }
+
+    /// Sets the as-fetched encoding for a given entry.
+    pub fn set_raw_inst(&mut self, tag: RobTag, raw_inst: u32) {
+        if let Some(entry) = self.find_entry_mut(tag) {
+            entry.raw_inst = raw_inst;
+        }
+    }
+
+    /// Publishes a result at execute, ahead of writeback.
+    pub fn set_early_result(&mut self, tag: RobTag, result: u64) {
+        if let Some(entry) = self.find_entry_mut(tag) {
+            entry.early_result = Some(result);
+        }
+    }
+
     /// Sets the store address and data for a given entry.
     pub fn set_store_info(&mut self, tag: RobTag, addr: u64, data: u64) {
         if let Some(entry) = self.find_entry_mut(tag) {
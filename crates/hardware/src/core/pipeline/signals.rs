@@ -64,6 +64,72 @@ pub enum AluOp {
     /// Integer remainder (unsigned).
     Remu,
 
+    /// AND with inverted second operand (Zbb `andn`).
+    Andn,
+
+    /// OR with inverted second operand (Zbb `orn`).
+    Orn,
+
+    /// Exclusive NOR (Zbb `xnor`).
+    Xnor,
+
+    /// Count leading zeros (Zbb `clz`, `clzw`).
+    Clz,
+
+    /// Count trailing zeros (Zbb `ctz`, `ctzw`).
+    Ctz,
+
+    /// Population count (Zbb `cpop`, `cpopw`).
+    Cpop,
+
+    /// Signed minimum (Zbb `min`).
+    Min,
+
+    /// Signed maximum (Zbb `max`).
+    Max,
+
+    /// Unsigned minimum (Zbb `minu`).
+    Minu,
+
+    /// Unsigned maximum (Zbb `maxu`).
+    Maxu,
+
+    /// Sign-extend byte (Zbb `sext.b`).
+    SextB,
+
+    /// Sign-extend half-word (Zbb `sext.h`).
+    SextH,
+
+    /// Zero-extend half-word (Zbb `zext.h`).
+    ZextH,
+
+    /// Rotate left (Zbb `rol`, `rolw`).
+    Rol,
+
+    /// Rotate right (Zbb `ror`, `rori`, `rorw`, `roriw`).
+    Ror,
+
+    /// Byte-reverse register (Zbb `rev8`).
+    Rev8,
+
+    /// OR-combine within each byte (Zbb `orc.b`).
+    OrcB,
+
+    /// Shift left by 1 and add (Zba `sh1add`, `sh1add.uw`).
+    Sh1Add,
+
+    /// Shift left by 2 and add (Zba `sh2add`, `sh2add.uw`).
+    Sh2Add,
+
+    /// Shift left by 3 and add (Zba `sh3add`, `sh3add.uw`).
+    Sh3Add,
+
+    /// Add zero-extended word (Zba `add.uw`).
+    AddUw,
+
+    /// Shift left zero-extended word (Zba `slli.uw`).
+    SlliUw,
+
     /// Floating-point addition.
     FAdd,
 
@@ -155,6 +221,35 @@ pub enum AluOp {
     FMvToF,
 }
 
+impl AluOp {
+    /// Returns the non-pipelined functional unit this operation occupies,
+    /// if any.
+    pub fn func_unit(self) -> Option<FuncUnit> {
+        match self {
+            Self::Div | Self::Divu | Self::Rem | Self::Remu => Some(FuncUnit::IntDiv),
+            Self::FDiv | Self::FSqrt => Some(FuncUnit::FpDivSqrt),
+            _ => None,
+        }
+    }
+}
+
+/// Non-pipelined functional units shared by long-latency operations.
+///
+/// An operation that needs a busy unit cannot issue until the unit frees
+/// up; everything else issues without a structural check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FuncUnit {
+    /// Integer divider (`div`, `divu`, `rem`, `remu` and their `.w` forms).
+    IntDiv,
+    /// Floating-point divide / square-root unit.
+    FpDivSqrt,
+}
+
+impl FuncUnit {
+    /// Number of tracked functional units.
+    pub const COUNT: usize = 2;
+}
+
 /// Atomic memory operation types (RISC-V A extension).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum AtomicOp {
@@ -196,6 +291,23 @@ pub enum AtomicOp {
     Maxu,
 }
 
+/// Vector operation types (vector extension subset).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VectorOp {
+    /// No vector operation.
+    #[default]
+    None,
+
+    /// Set `vl`/`vtype` (`vsetvli`, `vsetvl`).
+    SetVl,
+
+    /// Element-wise add (`vadd.vv`).
+    Add,
+
+    /// Element-wise subtract (`vsub.vv`).
+    Sub,
+}
+
 /// Memory access width for load and store operations.
 #[derive(Clone, Copy, Debug, Default)]
 pub enum MemWidth {
@@ -318,6 +430,10 @@ pub struct ControlSignals {
     pub rs3_fp: bool,
     /// Atomic memory operation type.
     pub atomic_op: AtomicOp,
+    /// Instruction is a `FENCE` with a non-empty ordering set.
+    pub is_fence: bool,
     /// Instruction is `FENCE.I`.
     pub is_fence_i: bool,
+    /// Vector operation type.
+    pub vector_op: VectorOp,
 }
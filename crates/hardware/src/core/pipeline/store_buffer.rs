@@ -39,6 +39,8 @@ pub enum StoreState {
 pub struct StoreBufferEntry {
     /// ROB tag of the store instruction.
     pub rob_tag: RobTag,
+    /// PC of the store instruction.
+    pub pc: u64,
     /// Virtual address of the store.
     pub vaddr: u64,
     /// Physical address (filled after translation).
@@ -101,6 +103,15 @@ impl StoreBuffer {
         self.count == self.entries.len()
     }
 
+    /// Returns true if a committed store has not yet drained to memory.
+    pub fn has_committed(&self) -> bool {
+        let cap = self.entries.len();
+        (0..self.count).any(|i| {
+            let entry = &self.entries[(self.head + i) % cap];
+            entry.valid && entry.state == StoreState::Committed
+        })
+    }
+
     /// Returns the number of free slots.
     #[inline]
     pub fn free_slots(&self) -> usize {
@@ -108,13 +119,14 @@ impl StoreBuffer {
     }
 
     /// Allocates a slot for a new store. Returns false if the buffer is full.
-    pub fn allocate(&mut self, rob_tag: RobTag, width: MemWidth) -> bool {
+    pub fn allocate(&mut self, rob_tag: RobTag, pc: u64, width: MemWidth) -> bool {
         if self.is_full() {
             return false;
         }
 
         self.entries[self.tail] = StoreBufferEntry {
             rob_tag,
+            pc,
             vaddr: 0,
             paddr: None,
             data: 0,
@@ -363,7 +375,7 @@ mod tests {
         assert!(sb.is_empty());
 
         let tag = RobTag(1);
-        assert!(sb.allocate(tag, MemWidth::Word));
+        assert!(sb.allocate(tag, 0, MemWidth::Word));
         assert_eq!(sb.len(), 1);
 
         // Can't drain yet (still Pending)
@@ -383,17 +395,17 @@ mod tests {
     #[test]
     fn test_full_buffer() {
         let mut sb = StoreBuffer::new(2);
-        assert!(sb.allocate(RobTag(1), MemWidth::Word));
-        assert!(sb.allocate(RobTag(2), MemWidth::Word));
+        assert!(sb.allocate(RobTag(1), 0, MemWidth::Word));
+        assert!(sb.allocate(RobTag(2), 0, MemWidth::Word));
         assert!(sb.is_full());
-        assert!(!sb.allocate(RobTag(3), MemWidth::Word));
+        assert!(!sb.allocate(RobTag(3), 0, MemWidth::Word));
     }
 
     #[test]
     fn test_forward_load() {
         let mut sb = StoreBuffer::new(4);
         let tag = RobTag(1);
-        sb.allocate(tag, MemWidth::Word);
+        sb.allocate(tag, 0, MemWidth::Word);
         sb.resolve(tag, 0x1000, 0x8000_0000, 0x12345678);
 
         // Forward should find the store
@@ -409,7 +421,7 @@ mod tests {
     fn test_forward_load_byte() {
         let mut sb = StoreBuffer::new(4);
         let tag = RobTag(1);
-        sb.allocate(tag, MemWidth::Word);
+        sb.allocate(tag, 0, MemWidth::Word);
         sb.resolve(tag, 0x1000, 0x8000_0000, 0x12345678);
 
         // Forward a byte from the same address
@@ -424,9 +436,9 @@ mod tests {
         let t2 = RobTag(2);
         let t3 = RobTag(3);
 
-        sb.allocate(t1, MemWidth::Word);
-        sb.allocate(t2, MemWidth::Word);
-        sb.allocate(t3, MemWidth::Word);
+        sb.allocate(t1, 0, MemWidth::Word);
+        sb.allocate(t2, 0, MemWidth::Word);
+        sb.allocate(t3, 0, MemWidth::Word);
 
         sb.resolve(t1, 0x1000, 0x8000_0000, 10);
         sb.mark_committed(t1);
@@ -445,8 +457,8 @@ mod tests {
     #[test]
     fn test_flush_all() {
         let mut sb = StoreBuffer::new(4);
-        sb.allocate(RobTag(1), MemWidth::Word);
-        sb.allocate(RobTag(2), MemWidth::Word);
+        sb.allocate(RobTag(1), 0, MemWidth::Word);
+        sb.allocate(RobTag(2), 0, MemWidth::Word);
 
         sb.flush_all();
         assert!(sb.is_empty());
@@ -457,7 +469,7 @@ mod tests {
         let mut sb = StoreBuffer::new(2);
         for i in 1..=10 {
             let tag = RobTag(i);
-            sb.allocate(tag, MemWidth::Word);
+            sb.allocate(tag, 0, MemWidth::Word);
             sb.resolve(tag, 0, 0x8000_0000, i as u64);
             sb.mark_committed(tag);
             let entry = sb.drain_one().unwrap();
@@ -0,0 +1,85 @@
+//! Sub-Register Dependency Tracking.
+//!
+//! Architecturally every integer write covers the full 64-bit register:
+//! `.w` operations compute a 32-bit result and sign-extend it. When
+//! `pipeline.subreg_tracking` is enabled, the pipeline models a
+//! microarchitecture whose integer registers are split into low and high
+//! 32-bit halves, and checks dependencies per half:
+//! 1. **Writes:** A `.w` operation writes the low half. Its high half is
+//!    only a copy of bit 31, which the split register file regenerates as
+//!    soon as the result leaves execute.
+//! 2. **Reads:** A `.w` operation reads the low half of its sources; a
+//!    right shift by 32 or more reads only the high half; everything else
+//!    reads both.
+//!
+//! The Zba `.uw` forms and `zext.h` share the `.w` opcodes but produce a
+//! zero-extended 64-bit result (and `add.uw`/`shNadd.uw` read all of
+//! `rs2`), so they are treated as full-width operations.
+//!
+//! A consumer whose halves are disjoint from those written by an in-flight
+//! producer does not wait for that producer's writeback.
+
+use crate::core::pipeline::signals::ControlSignals;
+use crate::isa::rv64i::{funct3, funct7, opcodes};
+use crate::isa::rvb::{funct7 as b_funct7, imm as b_imm};
+
+/// Low 32 bits of an integer register.
+pub const HALF_LOW: u8 = 0b01;
+
+/// High 32 bits of an integer register.
+pub const HALF_HIGH: u8 = 0b10;
+
+/// Both halves (the whole register).
+pub const HALF_FULL: u8 = HALF_LOW | HALF_HIGH;
+
+/// Shift amount at which a 64-bit right shift no longer reads the low half.
+const HIGH_HALF_SHIFT: u32 = 32;
+
+/// Returns the halves of `rd` written by an instruction.
+///
+/// # Arguments
+///
+/// * `inst` - The (expanded) 32-bit encoding.
+/// * `ctrl` - Decoded control signals for the instruction.
+pub fn write_halves(inst: u32, ctrl: &ControlSignals) -> u8 {
+    if ctrl.reg_write && !ctrl.fp_reg_write && !ctrl.mem_read && is_word_op(inst) {
+        HALF_LOW
+    } else {
+        HALF_FULL
+    }
+}
+
+/// Returns the halves an instruction reads from its integer sources.
+///
+/// # Arguments
+///
+/// * `inst` - The (expanded) 32-bit encoding.
+/// * `ctrl` - Decoded control signals for the instruction.
+pub fn source_halves(inst: u32, ctrl: &ControlSignals) -> u8 {
+    if ctrl.mem_read || ctrl.mem_write {
+        return HALF_FULL;
+    }
+    if is_word_op(inst) {
+        return HALF_LOW;
+    }
+    let opcode = inst & 0x7f;
+    let f3 = (inst >> 12) & 0x7;
+    let shamt = (inst >> 20) & 0x3f;
+    let is_shift = matches!((inst >> 25) & !1, funct7::DEFAULT | funct7::SRA);
+    if opcode == opcodes::OP_IMM && f3 == funct3::SRL_SRA && is_shift && shamt >= HIGH_HALF_SHIFT {
+        HALF_HIGH
+    } else {
+        HALF_FULL
+    }
+}
+
+/// Returns true for the RV64 `.w` integer operations (excluding the
+/// zero-extending Zba/Zbb forms that share their opcodes).
+fn is_word_op(inst: u32) -> bool {
+    let f7 = inst >> 25;
+    match inst & 0x7f {
+        opcodes::OP_REG_32 => !matches!(f7, b_funct7::ADD_UW | b_funct7::SHADD),
+        opcodes::OP_IMM_32 => inst >> b_imm::FUNCT6_SHIFT != b_imm::SLLI_UW,
+        _ => false,
+    }
+}
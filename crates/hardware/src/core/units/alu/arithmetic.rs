@@ -89,6 +89,8 @@ pub fn execute(op: AluOp, a: u64, b: u64, is32: bool) -> u64 {
                 (a as i64).wrapping_div(b as i64) as u64
             }
         }
+        // Spelled out like the signed arms: division by zero yields all ones.
+        #[allow(clippy::manual_checked_ops)]
         AluOp::Divu => {
             if is32 {
                 // Phase 0 fix: use u32 cast for unsigned zero-check, and
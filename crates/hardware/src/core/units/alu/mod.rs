@@ -9,10 +9,14 @@
 //! - [`arithmetic`]: Add, Sub, Mul, Mulh, Mulhsu, Mulhu, Div, Divu, Rem, Remu
 //! - [`logic`]:      Or, And, Xor, Slt, Sltu
 //! - [`shifts`]:     Sll, Srl, Sra
+//! - [`bitmanip`]:   Zba/Zbb (Andn, Clz, Min, Rol, Rev8, Sh1Add, AddUw, ...)
 
 /// Integer arithmetic operations (add, subtract, multiply, divide).
 pub mod arithmetic;
 
+/// Bit-manipulation operations (Zba address generation, Zbb bit ops).
+pub mod bitmanip;
+
 /// Bitwise logical and comparison operations (or, and, xor, slt).
 pub mod logic;
 
@@ -25,7 +29,8 @@ use crate::core::pipeline::signals::AluOp;
 ///
 /// Implements all RISC-V integer arithmetic and logical operations
 /// including addition, subtraction, shifts, comparisons, and
-/// multiply/divide operations from the I and M extensions.
+/// multiply/divide operations from the I and M extensions, and the
+/// bit-manipulation operations from Zba and Zbb.
 pub struct Alu;
 
 impl Alu {
@@ -95,6 +100,30 @@ impl Alu {
             // Shifts: sll, srl, sra
             AluOp::Sll | AluOp::Srl | AluOp::Sra => shifts::execute(op, a, b, is32),
 
+            // Bit manipulation: Zba, Zbb
+            AluOp::Andn
+            | AluOp::Orn
+            | AluOp::Xnor
+            | AluOp::Clz
+            | AluOp::Ctz
+            | AluOp::Cpop
+            | AluOp::Min
+            | AluOp::Max
+            | AluOp::Minu
+            | AluOp::Maxu
+            | AluOp::SextB
+            | AluOp::SextH
+            | AluOp::ZextH
+            | AluOp::Rol
+            | AluOp::Ror
+            | AluOp::Rev8
+            | AluOp::OrcB
+            | AluOp::Sh1Add
+            | AluOp::Sh2Add
+            | AluOp::Sh3Add
+            | AluOp::AddUw
+            | AluOp::SlliUw => bitmanip::execute(op, a, b, is32),
+
             // Non-integer operations (FP, etc.) are not handled here.
             _ => 0,
         }
@@ -5,6 +5,20 @@
 //! predicting conditional branches, indirect jumps (via BTB), and function
 //! returns (via RAS).
 
+use super::ras::RasCheckpoint;
+
+/// Training activity of a predictor that updates its weights only when the
+/// prediction was wrong or its confidence was below a threshold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrainingCounts {
+    /// Updates that adjusted the weights.
+    pub trained: u64,
+    /// Updates skipped because the prediction was correct and confident.
+    pub skipped: u64,
+    /// Current training threshold.
+    pub threshold: i32,
+}
+
 /// Trait for branch prediction algorithms.
 ///
 /// Defines the interface that all branch prediction implementations
@@ -47,17 +61,48 @@ pub trait BranchPredictor {
     /// The predicted target address if available in the BTB, `None` otherwise.
     fn predict_btb(&self, pc: u64) -> Option<u64>;
 
+    /// Predicts the target address for an indirect jump (non-return JALR).
+    ///
+    /// Predictors without a dedicated indirect target predictor fall back
+    /// to the BTB.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - Program counter of the jump instruction
+    fn predict_indirect(&self, pc: u64) -> Option<u64> {
+        self.predict_btb(pc)
+    }
+
+    /// Trains the indirect target predictor with a resolved jump target.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - Program counter of the jump instruction
+    /// * `target` - The resolved target address
+    /// * `history` - Snapshot from `snapshot_history` taken at prediction
+    fn update_indirect(&mut self, _pc: u64, _target: u64, _history: u64) {}
+
     /// Records a function call for return address prediction.
     ///
-    /// Called when a call instruction (JAL/JALR with rd=ra) is executed
-    /// to push the return address onto the return address stack.
+    /// Called when a call instruction (JAL/JALR with rd=ra) is fetched
+    /// to push the return address onto the return address stack. Only the
+    /// RAS is touched; the BTB is trained at resolution by `update_btb`.
     ///
     /// # Arguments
     ///
-    /// * `pc` - Program counter of the call instruction
     /// * `ret_addr` - Return address (pc + instruction_size)
-    /// * `target` - Target address of the call
-    fn on_call(&mut self, pc: u64, ret_addr: u64, target: u64);
+    fn on_call(&mut self, ret_addr: u64);
+
+    /// Trains the BTB with the resolved target of a call.
+    ///
+    /// Called at resolution (execute) so the BTB only ever learns targets
+    /// that were actually taken, never the fetch-time prediction.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - Program counter of the call instruction
+    /// * `target` - Resolved target address of the call
+    fn update_btb(&mut self, pc: u64, target: u64);
 
     /// Predicts the return address for a return instruction.
     ///
@@ -70,9 +115,27 @@ pub trait BranchPredictor {
     /// Records a function return for return address prediction.
     ///
     /// Called when a return instruction (JALR with rd=zero, rs1=ra) is
-    /// executed to pop the return address from the return address stack.
+    /// fetched to pop the return address from the return address stack.
     fn on_return(&mut self);
 
+    /// Returns a checkpoint of the return address stack.
+    ///
+    /// Called at fetch time after the instruction's own RAS update so the
+    /// checkpoint can be carried through the pipeline and restored when
+    /// younger instructions are flushed.
+    fn checkpoint_ras(&self) -> RasCheckpoint;
+
+    /// Restores the return address stack to a previously captured checkpoint.
+    ///
+    /// Called on a misprediction or pipeline flush to undo the RAS updates
+    /// of squashed wrong-path calls and returns.
+    fn restore_ras(&mut self, cp: RasCheckpoint);
+
+    /// Returns the live return address stack entries, oldest first.
+    ///
+    /// Used for diagnostics such as backtraces; does not affect prediction.
+    fn ras_entries(&self) -> &[u64];
+
     /// Speculatively updates the GHR with a predicted branch outcome.
     ///
     /// Called at fetch time after `predict_branch` to keep the GHR
@@ -93,4 +156,16 @@ pub trait BranchPredictor {
     /// Called at resolution time (execute) before `update_branch` so
     /// the predictor trains on the correct history state.
     fn repair_history(&mut self, _ghr: u64) {}
+
+    /// Returns cumulative training counts for threshold-trained predictors
+    /// (the perceptron), or `None` for predictors that always train.
+    fn training_counts(&self) -> Option<TrainingCounts> {
+        None
+    }
+
+    /// Returns `true` for the oracle predictor, whose wrong guesses are
+    /// corrected at execute without counting or charging a misprediction.
+    fn is_oracle(&self) -> bool {
+        false
+    }
 }
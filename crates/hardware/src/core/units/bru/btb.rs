@@ -16,6 +16,7 @@ struct BtbEntry {
 }
 
 /// Branch Target Buffer structure.
+#[derive(Clone)]
 pub struct Btb {
     /// The table of BTB entries.
     table: Vec<BtbEntry>,
@@ -4,29 +4,41 @@
 //! hash. This allows the predictor to distinguish the same branch instruction
 //! in different execution contexts.
 //!
+//! The table size, history length, and index function are set by
+//! `GShareConfig`. Besides the XOR hash, the index can be the PC alone
+//! (a bimodal predictor) or the history alone (GAg), for comparing the
+//! classic two-level schemes on one table.
+//!
 //! # Performance
 //!
 //! - **Time Complexity:**
 //!   - `predict()`: O(1)
 //!   - `update()`: O(1)
-//! - **Space Complexity:** O(2^N) where N is the history length (12 bits = 4KB for 2-bit counters)
+//! - **Space Complexity:** O(2^N) where N is `table_bits` (12 bits = 4K 2-bit counters by default)
 //! - **Hardware Cost:** Moderate - single PHT lookup, XOR, and counter update
 //! - **Best Case:** Correlated branches where outcome depends on recent history
 //! - **Worst Case:** Uncorrelated branches or history length too short/long for pattern
 
-use super::{BranchPredictor, btb::Btb, ras::Ras};
-
-/// Size of the Pattern History Table (2^12 entries).
-const TABLE_BITS: usize = 12;
-/// Total number of entries in the PHT.
-const TABLE_SIZE: usize = 1 << TABLE_BITS;
+use super::{
+    BranchPredictor,
+    btb::Btb,
+    ras::{Ras, RasCheckpoint},
+};
+use crate::config::{GShareConfig, GShareIndex};
 
 /// GShare Predictor structure.
+#[derive(Clone)]
 pub struct GSharePredictor {
     /// Global History Register storing recent branch outcomes.
     ghr: u64,
     /// Pattern History Table containing 2-bit saturating counters.
     pht: Vec<u8>,
+    /// Log2 of the PHT size.
+    table_bits: usize,
+    /// Mask selecting the `history_length` most recent outcomes.
+    history_mask: u64,
+    /// Index function.
+    index: GShareIndex,
     /// Branch Target Buffer.
     btb: Btb,
     /// Return Address Stack.
@@ -35,22 +47,57 @@ pub struct GSharePredictor {
 
 impl GSharePredictor {
     /// Creates a new GShare Predictor.
-    pub fn new(btb_size: usize, ras_size: usize) -> Self {
+    ///
+    /// `table_bits` is clamped to 1..=30 and `history_length` to 0..=64.
+    pub fn new(config: &GShareConfig, btb_size: usize, ras_size: usize) -> Self {
+        let table_bits = config.table_bits.clamp(1, 30);
+        let history_mask = match config.history_length {
+            0 => 0,
+            n if n >= 64 => u64::MAX,
+            n => (1 << n) - 1,
+        };
         Self {
             ghr: 0,
-            pht: vec![1; TABLE_SIZE],
+            pht: vec![1; 1 << table_bits],
+            table_bits,
+            history_mask,
+            index: config.index,
             btb: Btb::new(btb_size),
             ras: Ras::new(ras_size),
         }
     }
 
+    /// Folds the global history down to `table_bits` by XORing successive
+    /// `table_bits`-wide chunks.
+    fn folded_history(&self) -> u64 {
+        let mask = (1u64 << self.table_bits) - 1;
+        let mut h = self.ghr;
+        let mut folded = 0;
+        while h != 0 {
+            folded ^= h & mask;
+            h >>= self.table_bits;
+        }
+        folded
+    }
+
     /// Calculates the index into the Pattern History Table.
     ///
-    /// Computes the XOR of the PC (shifted) and the Global History Register.
+    /// Combines the PC (shifted past the instruction alignment) and the
+    /// folded Global History Register as selected by the index function.
     fn index(&self, pc: u64) -> usize {
-        let pc_part = (pc >> 2) & ((TABLE_SIZE as u64) - 1);
-        let ghr_part = self.ghr & ((TABLE_SIZE as u64) - 1);
-        (pc_part ^ ghr_part) as usize
+        let mask = (1u64 << self.table_bits) - 1;
+        let pc_part = (pc >> 2) & mask;
+        let idx = match self.index {
+            GShareIndex::Pc => pc_part,
+            GShareIndex::History => self.folded_history(),
+            GShareIndex::Xor => pc_part ^ self.folded_history(),
+        };
+        idx as usize
+    }
+
+    /// Shifts an outcome into the Global History Register.
+    fn push_history(&mut self, taken: bool) {
+        self.ghr = ((self.ghr << 1) | u64::from(taken)) & self.history_mask;
     }
 }
 
@@ -84,7 +131,7 @@ impl BranchPredictor for GSharePredictor {
             self.pht[idx] -= 1;
         }
 
-        self.ghr = ((self.ghr << 1) | if taken { 1 } else { 0 }) & ((TABLE_SIZE as u64) - 1);
+        self.push_history(taken);
 
         if let Some(tgt) = target {
             self.btb.update(pc, tgt);
@@ -97,8 +144,12 @@ impl BranchPredictor for GSharePredictor {
     }
 
     /// Handles a function call by pushing the return address to the RAS.
-    fn on_call(&mut self, pc: u64, ret_addr: u64, target: u64) {
+    fn on_call(&mut self, ret_addr: u64) {
         self.ras.push(ret_addr);
+    }
+
+    /// Trains the BTB with a resolved call target.
+    fn update_btb(&mut self, pc: u64, target: u64) {
         self.btb.update(pc, target);
     }
 
@@ -112,8 +163,20 @@ impl BranchPredictor for GSharePredictor {
         self.ras.pop();
     }
 
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        self.ras.checkpoint()
+    }
+
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        self.ras.restore(cp);
+    }
+
+    fn ras_entries(&self) -> &[u64] {
+        self.ras.entries()
+    }
+
     fn speculate(&mut self, _pc: u64, taken: bool) {
-        self.ghr = ((self.ghr << 1) | if taken { 1 } else { 0 }) & ((TABLE_SIZE as u64) - 1);
+        self.push_history(taken);
     }
 
     fn snapshot_history(&self) -> u64 {
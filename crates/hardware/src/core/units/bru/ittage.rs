@@ -0,0 +1,220 @@
+//! ITTAGE-style Indirect Target Predictor.
+//!
+//! Predicts targets of indirect jumps (`JALR` other than returns), where a
+//! single BTB entry per PC thrashes whenever the target varies. Targets are
+//! stored in a PC-indexed base table plus several tagged banks indexed with
+//! geometrically increasing lengths of the global branch history. The longest
+//! matching bank provides the prediction, so a dispatch branch whose target
+//! depends on the preceding path gets one entry per path.
+//!
+//! The predictor keeps no history of its own: it is indexed with the base
+//! predictor's speculative GHR, and the snapshot used for a prediction is
+//! carried down the pipeline so training hits the same entries.
+//!
+//! # Performance
+//!
+//! - **Time Complexity:**
+//!   - `predict()`: O(B) where B is the number of tagged banks (4)
+//!   - `update()`: O(B)
+//! - **Space Complexity:** O(T × B) where T is table size per bank
+//! - **Hardware Cost:** High - multiple tagged lookups, wide target storage
+//! - **Best Case:** Interpreter dispatch, virtual calls with path-correlated targets
+//! - **Worst Case:** Targets uncorrelated with recent control flow
+
+/// History lengths (in bits of global history) for each tagged bank.
+const ITTAGE_HISTORY_LENGTHS: [usize; 4] = [4, 12, 24, 48];
+
+/// Width of the partial tag stored in each tagged entry.
+const ITTAGE_TAG_BITS: usize = 12;
+
+/// Maximum value of the per-entry confidence counter.
+const ITTAGE_CONFIDENCE_MAX: u8 = 3;
+
+/// Maximum value of the per-entry useful counter.
+const ITTAGE_USEFUL_MAX: u8 = 3;
+
+/// An entry in the base or a tagged bank.
+#[derive(Clone, Copy, Default)]
+struct IttageEntry {
+    /// Partial tag (unused in the base table).
+    tag: u16,
+    /// Indicates if this entry contains valid data.
+    valid: bool,
+    /// Predicted target address.
+    target: u64,
+    /// Saturating confidence in `target`; the target is replaced at zero.
+    confidence: u8,
+    /// Saturating usefulness counter for replacement.
+    useful: u8,
+}
+
+/// ITTAGE Predictor structure.
+#[derive(Clone)]
+pub struct IttagePredictor {
+    /// PC-indexed base target table.
+    base: Vec<IttageEntry>,
+    /// Tagged banks, one per history length.
+    banks: Vec<Vec<IttageEntry>>,
+    /// Mask used to index each table.
+    table_mask: usize,
+}
+
+impl IttagePredictor {
+    /// Creates a new ITTAGE predictor.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_size` - Number of entries in the base table and in each
+    ///   tagged bank. Must be a power of 2.
+    pub fn new(table_size: usize) -> Self {
+        let safe_size = if table_size.is_power_of_two() {
+            table_size
+        } else {
+            256
+        };
+        Self {
+            base: vec![IttageEntry::default(); safe_size],
+            banks: vec![vec![IttageEntry::default(); safe_size]; ITTAGE_HISTORY_LENGTHS.len()],
+            table_mask: safe_size - 1,
+        }
+    }
+
+    /// Folds a wide value into `bits` width by XOR-compressing.
+    fn fold(val: u64, bits: usize) -> u64 {
+        let mask = (1u64 << bits) - 1;
+        let mut r = 0u64;
+        let mut v = val;
+        while v != 0 {
+            r ^= v & mask;
+            v >>= bits;
+        }
+        r
+    }
+
+    /// Masks `history` to the history length of a bank.
+    fn bank_history(history: u64, bank: usize) -> u64 {
+        let len = ITTAGE_HISTORY_LENGTHS[bank];
+        if len >= 64 {
+            history
+        } else {
+            history & ((1u64 << len) - 1)
+        }
+    }
+
+    /// Calculates the base table index for a PC.
+    fn base_index(&self, pc: u64) -> usize {
+        ((pc >> 1) as usize) & self.table_mask
+    }
+
+    /// Calculates the index for a tagged bank using PC and history.
+    fn index(&self, pc: u64, history: u64, bank: usize) -> usize {
+        let table_bits = (self.table_mask + 1).trailing_zeros().max(1) as usize;
+        let h = Self::fold(Self::bank_history(history, bank), table_bits);
+        ((pc >> 1) as usize ^ (pc >> (table_bits + 1)) as usize ^ h as usize) & self.table_mask
+    }
+
+    /// Calculates the partial tag for a tagged bank using PC and history.
+    fn tag(pc: u64, history: u64, bank: usize) -> u16 {
+        let h = Self::fold(Self::bank_history(history, bank), ITTAGE_TAG_BITS - 1);
+        (((pc >> 1) ^ (h << 1)) & ((1 << ITTAGE_TAG_BITS) - 1)) as u16
+    }
+
+    /// Finds the longest-history bank with a tag match.
+    fn provider(&self, pc: u64, history: u64) -> Option<(usize, usize)> {
+        (0..self.banks.len()).rev().find_map(|bank| {
+            let idx = self.index(pc, history, bank);
+            let e = &self.banks[bank][idx];
+            (e.valid && e.tag == Self::tag(pc, history, bank)).then_some((bank, idx))
+        })
+    }
+
+    /// Predicts the target of the indirect jump at `pc`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - Program counter of the jump.
+    /// * `history` - Current global branch history.
+    ///
+    /// # Returns
+    ///
+    /// The target from the longest matching tagged bank, else from the base
+    /// table, or `None` if neither has an entry.
+    pub fn predict(&self, pc: u64, history: u64) -> Option<u64> {
+        if let Some((bank, idx)) = self.provider(pc, history) {
+            return Some(self.banks[bank][idx].target);
+        }
+        let e = &self.base[self.base_index(pc)];
+        e.valid.then_some(e.target)
+    }
+
+    /// Trains the predictor with a resolved indirect jump.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - Program counter of the jump.
+    /// * `target` - The resolved target address.
+    /// * `history` - The global history used when the jump was predicted.
+    pub fn update(&mut self, pc: u64, target: u64, history: u64) {
+        let provider = self.provider(pc, history);
+        let base_idx = self.base_index(pc);
+
+        let alt_target = {
+            let e = &self.base[base_idx];
+            e.valid.then_some(e.target)
+        };
+        let predicted = match provider {
+            Some((bank, idx)) => Some(self.banks[bank][idx].target),
+            None => alt_target,
+        };
+
+        // Train the provider (or the base table when no bank matched).
+        let entry = match provider {
+            Some((bank, idx)) => &mut self.banks[bank][idx],
+            None => &mut self.base[base_idx],
+        };
+        if entry.valid && entry.target == target {
+            if entry.confidence < ITTAGE_CONFIDENCE_MAX {
+                entry.confidence += 1;
+            }
+            if provider.is_some() && alt_target != Some(target) && entry.useful < ITTAGE_USEFUL_MAX
+            {
+                entry.useful += 1;
+            }
+        } else if entry.valid && entry.confidence > 0 {
+            entry.confidence -= 1;
+        } else {
+            entry.valid = true;
+            entry.target = target;
+            entry.confidence = 0;
+        }
+
+        // On a target mispredict, allocate in a longer-history bank.
+        if predicted != Some(target) {
+            let start = provider.map_or(0, |(bank, _)| bank + 1);
+            let mut allocated = false;
+            for bank in start..self.banks.len() {
+                let idx = self.index(pc, history, bank);
+                let tag = Self::tag(pc, history, bank);
+                let e = &mut self.banks[bank][idx];
+                if !e.valid || e.useful == 0 {
+                    *e = IttageEntry {
+                        tag,
+                        valid: true,
+                        target,
+                        confidence: 0,
+                        useful: 0,
+                    };
+                    allocated = true;
+                    break;
+                }
+            }
+            if !allocated {
+                for bank in start..self.banks.len() {
+                    let idx = self.index(pc, history, bank);
+                    let e = &mut self.banks[bank][idx];
+                    e.useful = e.useful.saturating_sub(1);
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,12 @@
 //! Branch prediction unit (BRU) implementations.
 //!
 //! This module contains various branch prediction algorithms including
-//! static prediction, gshare, perceptron, TAGE, tournament predictors,
-//! branch target buffer (BTB), and return address stack (RAS).
+//! static prediction, gshare, perceptron, TAGE, tournament predictors, a
+//! perfect oracle for upper-bound studies,
+//! optional loop and indirect target predictors, branch target buffer (BTB), and return
+//! address stack (RAS).
 
-pub use self::branch_predictor::BranchPredictor;
+pub use self::branch_predictor::{BranchPredictor, TrainingCounts};
 
 /// Branch predictor trait and common functionality.
 pub mod branch_predictor;
@@ -15,9 +17,18 @@ pub mod btb;
 /// Global history branch predictor (gshare algorithm).
 pub mod gshare;
 
+/// ITTAGE-style indirect jump target predictor.
+pub mod ittage;
+
+/// Loop-exit predictor layered on top of the base predictor.
+pub mod loop_predictor;
+
 /// Perceptron-based neural branch predictor.
 pub mod perceptron;
 
+/// Perfect (oracle) branch predictor for upper-bound studies.
+pub mod perfect;
+
 /// Return Address Stack for predicting return addresses.
 pub mod ras;
 
@@ -31,23 +42,26 @@ pub mod tage;
 pub mod tournament;
 
 use self::{
-    gshare::GSharePredictor, perceptron::PerceptronPredictor, static_bp::StaticPredictor,
-    tage::TagePredictor, tournament::TournamentPredictor,
+    gshare::GSharePredictor, ittage::IttagePredictor, loop_predictor::LoopPredictor,
+    perceptron::PerceptronPredictor, perfect::PerfectPredictor, ras::RasCheckpoint,
+    static_bp::StaticPredictor, tage::TagePredictor, tournament::TournamentPredictor,
 };
 use crate::config::{BranchPredictor as BpType, Config};
 
-/// Enum wrapper for static dispatch of Branch Predictors.
+/// Enum wrapper for static dispatch of the base Branch Predictors.
 /// This avoids vtable lookups in the critical fetch loop.
-pub enum BranchPredictorWrapper {
+#[derive(Clone)]
+pub enum BasePredictor {
     Static(StaticPredictor),
     GShare(GSharePredictor),
     Tournament(TournamentPredictor),
     Tage(TagePredictor),
     Perceptron(PerceptronPredictor),
+    Perfect(PerfectPredictor),
 }
 
-impl BranchPredictorWrapper {
-    /// Creates a new branch predictor wrapper based on configuration.
+impl BasePredictor {
+    /// Creates a new base branch predictor based on configuration.
     ///
     /// Selects the appropriate branch prediction algorithm and initializes
     /// it with the configured BTB and RAS sizes.
@@ -57,7 +71,11 @@ impl BranchPredictorWrapper {
 
         match config.pipeline.branch_predictor {
             BpType::Static => Self::Static(StaticPredictor::new(btb_size, ras_size)),
-            BpType::GShare => Self::GShare(GSharePredictor::new(btb_size, ras_size)),
+            BpType::GShare => Self::GShare(GSharePredictor::new(
+                &config.pipeline.gshare,
+                btb_size,
+                ras_size,
+            )),
             BpType::Tournament => Self::Tournament(TournamentPredictor::new(
                 &config.pipeline.tournament,
                 btb_size,
@@ -73,11 +91,12 @@ impl BranchPredictorWrapper {
                 btb_size,
                 ras_size,
             )),
+            BpType::Perfect => Self::Perfect(PerfectPredictor::new(btb_size, ras_size)),
         }
     }
 }
 
-impl BranchPredictor for BranchPredictorWrapper {
+impl BranchPredictor for BasePredictor {
     /// Predicts whether a branch at the given PC will be taken and its target.
     ///
     /// Returns a tuple of (taken, target_opt) where target_opt is Some(target)
@@ -90,6 +109,7 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.predict_branch(pc),
             Self::Tage(bp) => bp.predict_branch(pc),
             Self::Perceptron(bp) => bp.predict_branch(pc),
+            Self::Perfect(bp) => bp.predict_branch(pc),
         }
     }
 
@@ -105,6 +125,7 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.update_branch(pc, taken, target),
             Self::Tage(bp) => bp.update_branch(pc, taken, target),
             Self::Perceptron(bp) => bp.update_branch(pc, taken, target),
+            Self::Perfect(bp) => bp.update_branch(pc, taken, target),
         }
     }
 
@@ -119,20 +140,35 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.predict_btb(pc),
             Self::Tage(bp) => bp.predict_btb(pc),
             Self::Perceptron(bp) => bp.predict_btb(pc),
+            Self::Perfect(bp) => bp.predict_btb(pc),
         }
     }
 
     /// Records a function call for return address prediction.
     ///
-    /// Pushes the return address onto the RAS when a call instruction is executed.
+    /// Pushes the return address onto the RAS when a call instruction is fetched.
     #[inline(always)]
-    fn on_call(&mut self, pc: u64, ret_addr: u64, target: u64) {
+    fn on_call(&mut self, ret_addr: u64) {
         match self {
-            Self::Static(bp) => bp.on_call(pc, ret_addr, target),
-            Self::GShare(bp) => bp.on_call(pc, ret_addr, target),
-            Self::Tournament(bp) => bp.on_call(pc, ret_addr, target),
-            Self::Tage(bp) => bp.on_call(pc, ret_addr, target),
-            Self::Perceptron(bp) => bp.on_call(pc, ret_addr, target),
+            Self::Static(bp) => bp.on_call(ret_addr),
+            Self::GShare(bp) => bp.on_call(ret_addr),
+            Self::Tournament(bp) => bp.on_call(ret_addr),
+            Self::Tage(bp) => bp.on_call(ret_addr),
+            Self::Perceptron(bp) => bp.on_call(ret_addr),
+            Self::Perfect(bp) => bp.on_call(ret_addr),
+        }
+    }
+
+    /// Trains the BTB with the resolved target of a call.
+    #[inline(always)]
+    fn update_btb(&mut self, pc: u64, target: u64) {
+        match self {
+            Self::Static(bp) => bp.update_btb(pc, target),
+            Self::GShare(bp) => bp.update_btb(pc, target),
+            Self::Tournament(bp) => bp.update_btb(pc, target),
+            Self::Tage(bp) => bp.update_btb(pc, target),
+            Self::Perceptron(bp) => bp.update_btb(pc, target),
+            Self::Perfect(bp) => bp.update_btb(pc, target),
         }
     }
 
@@ -148,6 +184,7 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.predict_return(),
             Self::Tage(bp) => bp.predict_return(),
             Self::Perceptron(bp) => bp.predict_return(),
+            Self::Perfect(bp) => bp.predict_return(),
         }
     }
 
@@ -162,6 +199,43 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.on_return(),
             Self::Tage(bp) => bp.on_return(),
             Self::Perceptron(bp) => bp.on_return(),
+            Self::Perfect(bp) => bp.on_return(),
+        }
+    }
+
+    #[inline(always)]
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        match self {
+            Self::Static(bp) => bp.checkpoint_ras(),
+            Self::GShare(bp) => bp.checkpoint_ras(),
+            Self::Tournament(bp) => bp.checkpoint_ras(),
+            Self::Tage(bp) => bp.checkpoint_ras(),
+            Self::Perceptron(bp) => bp.checkpoint_ras(),
+            Self::Perfect(bp) => bp.checkpoint_ras(),
+        }
+    }
+
+    #[inline(always)]
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        match self {
+            Self::Static(bp) => bp.restore_ras(cp),
+            Self::GShare(bp) => bp.restore_ras(cp),
+            Self::Tournament(bp) => bp.restore_ras(cp),
+            Self::Tage(bp) => bp.restore_ras(cp),
+            Self::Perceptron(bp) => bp.restore_ras(cp),
+            Self::Perfect(bp) => bp.restore_ras(cp),
+        }
+    }
+
+    #[inline(always)]
+    fn ras_entries(&self) -> &[u64] {
+        match self {
+            Self::Static(bp) => bp.ras_entries(),
+            Self::GShare(bp) => bp.ras_entries(),
+            Self::Tournament(bp) => bp.ras_entries(),
+            Self::Tage(bp) => bp.ras_entries(),
+            Self::Perceptron(bp) => bp.ras_entries(),
+            Self::Perfect(bp) => bp.ras_entries(),
         }
     }
 
@@ -173,6 +247,7 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.speculate(pc, taken),
             Self::Tage(bp) => bp.speculate(pc, taken),
             Self::Perceptron(bp) => bp.speculate(pc, taken),
+            Self::Perfect(bp) => bp.speculate(pc, taken),
         }
     }
 
@@ -184,6 +259,7 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.snapshot_history(),
             Self::Tage(bp) => bp.snapshot_history(),
             Self::Perceptron(bp) => bp.snapshot_history(),
+            Self::Perfect(bp) => bp.snapshot_history(),
         }
     }
 
@@ -195,6 +271,181 @@ impl BranchPredictor for BranchPredictorWrapper {
             Self::Tournament(bp) => bp.repair_history(ghr),
             Self::Tage(bp) => bp.repair_history(ghr),
             Self::Perceptron(bp) => bp.repair_history(ghr),
+            Self::Perfect(bp) => bp.repair_history(ghr),
+        }
+    }
+
+    fn training_counts(&self) -> Option<TrainingCounts> {
+        match self {
+            Self::Static(bp) => bp.training_counts(),
+            Self::GShare(bp) => bp.training_counts(),
+            Self::Tournament(bp) => bp.training_counts(),
+            Self::Tage(bp) => bp.training_counts(),
+            Self::Perceptron(bp) => bp.training_counts(),
+            Self::Perfect(bp) => bp.training_counts(),
+        }
+    }
+
+    #[inline(always)]
+    fn is_oracle(&self) -> bool {
+        matches!(self, Self::Perfect(_))
+    }
+}
+
+/// Branch predictor used by the CPU.
+///
+/// Combines a base predictor with optional loop and indirect predictors.
+/// When the loop predictor has a confident entry for a branch, its direction
+/// overrides the base prediction. Non-return `JALR` targets come from the
+/// indirect predictor when it has an entry; all other target, call and
+/// return prediction is delegated to the base predictor.
+#[derive(Clone)]
+pub struct BranchPredictorWrapper {
+    /// Base direction predictor, BTB and RAS.
+    pub base: BasePredictor,
+    /// Optional loop-exit predictor.
+    pub loop_predictor: Option<LoopPredictor>,
+    /// Optional indirect jump target predictor.
+    pub indirect_predictor: Option<IttagePredictor>,
+}
+
+impl BranchPredictorWrapper {
+    /// Creates a new branch predictor wrapper based on configuration.
+    ///
+    /// Builds the configured base predictor and, if `pipeline.loop_predictor`
+    /// is enabled, layers a loop predictor of `pipeline.loop_table_size`
+    /// entries on top of it. Likewise `pipeline.indirect_predictor` adds an
+    /// indirect predictor with `pipeline.indirect_table_size` entries per bank.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            base: BasePredictor::new(config),
+            loop_predictor: config
+                .pipeline
+                .loop_predictor
+                .then(|| LoopPredictor::new(config.pipeline.loop_table_size)),
+            indirect_predictor: config
+                .pipeline
+                .indirect_predictor
+                .then(|| IttagePredictor::new(config.pipeline.indirect_table_size)),
+        }
+    }
+}
+
+impl BranchPredictor for BranchPredictorWrapper {
+    /// Predicts whether a branch at the given PC will be taken and its target.
+    ///
+    /// A confident loop predictor entry overrides the base direction; the
+    /// target of a taken loop prediction comes from the BTB.
+    #[inline(always)]
+    fn predict_branch(&self, pc: u64) -> (bool, Option<u64>) {
+        if let Some(lp) = &self.loop_predictor
+            && let Some(taken) = lp.predict(pc)
+        {
+            let target = if taken {
+                self.base.predict_btb(pc)
+            } else {
+                None
+            };
+            return (taken, target);
+        }
+        self.base.predict_branch(pc)
+    }
+
+    /// Updates the branch predictor with the actual outcome of a branch.
+    ///
+    /// The loop predictor is trained first, using the base predictor's
+    /// direction (with history already repaired) to decide on allocation.
+    #[inline(always)]
+    fn update_branch(&mut self, pc: u64, taken: bool, target: Option<u64>) {
+        if let Some(lp) = &mut self.loop_predictor {
+            let (base_taken, _) = self.base.predict_branch(pc);
+            lp.update(pc, taken, base_taken);
         }
+        self.base.update_branch(pc, taken, target);
+    }
+
+    #[inline(always)]
+    fn predict_btb(&self, pc: u64) -> Option<u64> {
+        self.base.predict_btb(pc)
+    }
+
+    /// Predicts an indirect jump target, preferring the indirect predictor
+    /// (indexed with the base predictor's GHR) and falling back to the BTB
+    /// on a miss.
+    #[inline(always)]
+    fn predict_indirect(&self, pc: u64) -> Option<u64> {
+        self.indirect_predictor
+            .as_ref()
+            .and_then(|ip| ip.predict(pc, self.base.snapshot_history()))
+            .or_else(|| self.base.predict_btb(pc))
+    }
+
+    #[inline(always)]
+    fn update_indirect(&mut self, pc: u64, target: u64, history: u64) {
+        if let Some(ip) = &mut self.indirect_predictor {
+            ip.update(pc, target, history);
+        }
+    }
+
+    #[inline(always)]
+    fn on_call(&mut self, ret_addr: u64) {
+        self.base.on_call(ret_addr);
+    }
+
+    #[inline(always)]
+    fn update_btb(&mut self, pc: u64, target: u64) {
+        self.base.update_btb(pc, target);
+    }
+
+    #[inline(always)]
+    fn predict_return(&self) -> Option<u64> {
+        self.base.predict_return()
+    }
+
+    #[inline(always)]
+    fn on_return(&mut self) {
+        self.base.on_return();
+    }
+
+    #[inline(always)]
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        self.base.checkpoint_ras()
+    }
+
+    #[inline(always)]
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        self.base.restore_ras(cp);
+    }
+
+    #[inline(always)]
+    fn ras_entries(&self) -> &[u64] {
+        self.base.ras_entries()
+    }
+
+    #[inline(always)]
+    fn speculate(&mut self, pc: u64, taken: bool) {
+        if let Some(lp) = &mut self.loop_predictor {
+            lp.speculate(pc, taken);
+        }
+        self.base.speculate(pc, taken);
+    }
+
+    #[inline(always)]
+    fn snapshot_history(&self) -> u64 {
+        self.base.snapshot_history()
+    }
+
+    #[inline(always)]
+    fn repair_history(&mut self, ghr: u64) {
+        self.base.repair_history(ghr);
+    }
+
+    fn training_counts(&self) -> Option<TrainingCounts> {
+        self.base.training_counts()
+    }
+
+    #[inline(always)]
+    fn is_oracle(&self) -> bool {
+        self.base.is_oracle()
     }
 }
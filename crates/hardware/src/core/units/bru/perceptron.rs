@@ -3,16 +3,30 @@
 //! Uses a single-layer perceptron neural network to predict branch direction.
 //! Instead of saturating counters, it uses a table of weight vectors. The
 //! prediction is the dot product of the weights and the history vector.
-
-use super::{BranchPredictor, btb::Btb, ras::Ras};
+//!
+//! Weights are trained on a misprediction or when the output magnitude is at
+//! or below the threshold theta, which starts at `1.93 * h + 14`. With
+//! `adaptive_threshold`, theta follows the dynamic scheme of O-GEHL: a
+//! 7-bit counter rises on mispredictions and falls on correct low-confidence
+//! predictions, and theta moves by one each time the counter saturates,
+//! balancing the two kinds of training.
+
+use super::{
+    BranchPredictor, TrainingCounts,
+    btb::Btb,
+    ras::{Ras, RasCheckpoint},
+};
 use crate::config::PerceptronConfig;
 
 /// Coefficient used to calculate the training threshold.
 const THETA_COEFF: f64 = 1.93;
 /// Bias used to calculate the training threshold.
 const THETA_BIAS: f64 = 14.0;
+/// Saturation bound of the adaptive threshold counter (7-bit signed).
+const THETA_COUNTER_MAX: i32 = 63;
 
 /// Perceptron Predictor structure.
+#[derive(Clone)]
 pub struct PerceptronPredictor {
     /// Global History Register.
     ghr: u64,
@@ -26,6 +40,14 @@ pub struct PerceptronPredictor {
     row_size: usize,
     /// Training threshold (theta).
     threshold: i32,
+    /// Whether theta adapts to the training mix.
+    adaptive: bool,
+    /// Adaptive threshold counter, in `-THETA_COUNTER_MAX - 1..=THETA_COUNTER_MAX`.
+    theta_counter: i32,
+    /// Updates that trained the weights.
+    trained: u64,
+    /// Updates that left the weights unchanged.
+    skipped: u64,
     /// Branch Target Buffer.
     btb: Btb,
     /// Return Address Stack.
@@ -47,6 +69,10 @@ impl PerceptronPredictor {
             table_mask: table_entries - 1,
             row_size,
             threshold,
+            adaptive: config.adaptive_threshold,
+            theta_counter: 0,
+            trained: 0,
+            skipped: 0,
             btb: Btb::new(btb_size),
             ras: Ras::new(ras_size),
         }
@@ -72,6 +98,25 @@ impl PerceptronPredictor {
         }
         y
     }
+
+    /// Nudges theta after an update. Mispredictions push it up, so more
+    /// correct predictions train; correct low-confidence predictions push
+    /// it down.
+    fn adapt_threshold(&mut self, mispredicted: bool, low_confidence: bool) {
+        if mispredicted {
+            self.theta_counter += 1;
+            if self.theta_counter >= THETA_COUNTER_MAX {
+                self.threshold += 1;
+                self.theta_counter = 0;
+            }
+        } else if low_confidence {
+            self.theta_counter -= 1;
+            if self.theta_counter < -THETA_COUNTER_MAX {
+                self.threshold = (self.threshold - 1).max(0);
+                self.theta_counter = 0;
+            }
+        }
+    }
 }
 
 /// Clamps a weight value to the 8-bit signed integer range.
@@ -109,7 +154,11 @@ impl BranchPredictor for PerceptronPredictor {
         let y = self.output(idx);
         let t = if taken { 1 } else { -1 };
 
-        if y.abs() <= self.threshold || (y >= 0) != taken {
+        let mispredicted = (y >= 0) != taken;
+        let low_confidence = y.abs() <= self.threshold;
+
+        if low_confidence || mispredicted {
+            self.trained += 1;
             let base = idx * self.row_size;
 
             let v = self.table[base] as i32 + t;
@@ -121,6 +170,12 @@ impl BranchPredictor for PerceptronPredictor {
                 let v = self.table[w_idx] as i32 + t * x;
                 self.table[w_idx] = clamp_weight(v);
             }
+        } else {
+            self.skipped += 1;
+        }
+
+        if self.adaptive {
+            self.adapt_threshold(mispredicted, low_confidence);
         }
 
         self.ghr =
@@ -137,8 +192,12 @@ impl BranchPredictor for PerceptronPredictor {
     }
 
     /// Handles a function call by pushing the return address to the RAS.
-    fn on_call(&mut self, pc: u64, ret_addr: u64, target: u64) {
+    fn on_call(&mut self, ret_addr: u64) {
         self.ras.push(ret_addr);
+    }
+
+    /// Trains the BTB with a resolved call target.
+    fn update_btb(&mut self, pc: u64, target: u64) {
         self.btb.update(pc, target);
     }
 
@@ -152,6 +211,18 @@ impl BranchPredictor for PerceptronPredictor {
         self.ras.pop();
     }
 
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        self.ras.checkpoint()
+    }
+
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        self.ras.restore(cp);
+    }
+
+    fn ras_entries(&self) -> &[u64] {
+        self.ras.entries()
+    }
+
     fn speculate(&mut self, _pc: u64, taken: bool) {
         self.ghr =
             ((self.ghr << 1) | if taken { 1 } else { 0 }) & ((1u64 << self.history_length) - 1);
@@ -164,4 +235,12 @@ impl BranchPredictor for PerceptronPredictor {
     fn repair_history(&mut self, ghr: u64) {
         self.ghr = ghr;
     }
+
+    fn training_counts(&self) -> Option<TrainingCounts> {
+        Some(TrainingCounts {
+            trained: self.trained,
+            skipped: self.skipped,
+            threshold: self.threshold,
+        })
+    }
 }
@@ -0,0 +1,93 @@
+//! Perfect (Oracle) Branch Predictor.
+//!
+//! Establishes an upper bound on performance by never mispredicting. Fetch
+//! still follows a cheap guess (the last taken target from the BTB, and the
+//! RAS for returns), but the execute stage knows every branch outcome and
+//! treats this predictor as an oracle: whenever the guess was wrong, it
+//! steers fetch onto the committed path without counting a misprediction
+//! or charging a penalty, and the frontend refills immediately as though
+//! it had fetched that path from the start.
+
+use super::{
+    BranchPredictor,
+    btb::Btb,
+    ras::{Ras, RasCheckpoint},
+};
+
+/// Perfect Branch Predictor structure.
+#[derive(Clone)]
+pub struct PerfectPredictor {
+    /// Branch Target Buffer supplying fetch-time guesses.
+    btb: Btb,
+    /// Return Address Stack for function returns.
+    ras: Ras,
+}
+
+impl PerfectPredictor {
+    /// Creates a new Perfect Predictor.
+    ///
+    /// # Arguments
+    ///
+    /// * `btb_size` - Number of entries in the BTB.
+    /// * `ras_size` - Capacity of the RAS.
+    pub fn new(btb_size: usize, ras_size: usize) -> Self {
+        Self {
+            btb: Btb::new(btb_size),
+            ras: Ras::new(ras_size),
+        }
+    }
+}
+
+impl BranchPredictor for PerfectPredictor {
+    /// Guesses taken whenever the BTB holds a target for `pc`.
+    ///
+    /// The guess only decides which path fetch follows until the branch
+    /// resolves; it never costs a misprediction.
+    fn predict_branch(&self, pc: u64) -> (bool, Option<u64>) {
+        let target = self.btb.lookup(pc);
+        (target.is_some(), target)
+    }
+
+    /// Records the target of taken branches in the BTB.
+    fn update_branch(&mut self, pc: u64, _taken: bool, target: Option<u64>) {
+        if let Some(tgt) = target {
+            self.btb.update(pc, tgt);
+        }
+    }
+
+    fn predict_btb(&self, pc: u64) -> Option<u64> {
+        self.btb.lookup(pc)
+    }
+
+    fn on_call(&mut self, ret_addr: u64) {
+        self.ras.push(ret_addr);
+    }
+
+    fn update_btb(&mut self, pc: u64, target: u64) {
+        self.btb.update(pc, target);
+    }
+
+    fn predict_return(&self) -> Option<u64> {
+        self.ras.top()
+    }
+
+    fn on_return(&mut self) {
+        self.ras.pop();
+    }
+
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        self.ras.checkpoint()
+    }
+
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        self.ras.restore(cp);
+    }
+
+    fn ras_entries(&self) -> &[u64] {
+        self.ras.entries()
+    }
+
+    fn is_oracle(&self) -> bool {
+        true
+    }
+}
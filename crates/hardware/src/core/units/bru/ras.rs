@@ -3,8 +3,44 @@
 //! The RAS is a specialized predictor for function return addresses. It operates
 //! as a hardware stack that pushes addresses on function calls and pops them
 //! on returns to predict the execution flow.
+//!
+//! The stack is updated speculatively at fetch, so wrong-path calls and
+//! returns corrupt it. Each fetched instruction carries a `RasCheckpoint`
+//! (stack pointer plus top entry) that is restored when the pipeline
+//! flushes, repairing the common single-overwrite case as hardware does.
+
+use crate::isa::abi;
+
+/// Returns `true` if `reg` is a link register (`ra` or `t0`).
+fn is_link(reg: usize) -> bool {
+    reg == abi::REG_RA || reg == abi::REG_T0
+}
+
+/// Classifies a jump for the RAS, following the return-address stack hints
+/// of the RISC-V unprivileged spec: a JAL or JALR whose `rd` is a link
+/// register pushes, and a JALR whose `rs1` is a link register pops unless
+/// `rs1` and `rd` are the same register.
+///
+/// # Returns
+///
+/// `(push, pop)`. A JALR that does both (a coroutine swap) pops first.
+pub fn ras_hint(is_jalr: bool, rd: usize, rs1: usize) -> (bool, bool) {
+    let push = is_link(rd);
+    let pop = is_jalr && is_link(rs1) && rd != rs1;
+    (push, pop)
+}
+
+/// Snapshot of the RAS stack pointer and top-of-stack entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RasCheckpoint {
+    /// Stack pointer at the time of the checkpoint.
+    ptr: usize,
+    /// Entry at the top of the stack (0 if empty).
+    top: u64,
+}
 
 /// Return Address Stack structure.
+#[derive(Clone)]
 pub struct Ras {
     /// The stack storage.
     stack: Vec<u64>,
@@ -70,4 +106,28 @@ impl Ras {
             Some(self.stack[self.ptr - 1])
         }
     }
+
+    /// Returns the live entries, oldest (bottom of the stack) first.
+    pub fn entries(&self) -> &[u64] {
+        &self.stack[..self.ptr]
+    }
+
+    /// Captures the stack pointer and top entry for later repair.
+    pub fn checkpoint(&self) -> RasCheckpoint {
+        RasCheckpoint {
+            ptr: self.ptr,
+            top: self.top().unwrap_or(0),
+        }
+    }
+
+    /// Restores the stack pointer and top entry from a checkpoint.
+    ///
+    /// Entries below the top are not saved, so a wrong path that popped
+    /// and then pushed more than once may leave deeper entries corrupted.
+    pub fn restore(&mut self, cp: RasCheckpoint) {
+        self.ptr = cp.ptr.min(self.capacity);
+        if self.ptr > 0 {
+            self.stack[self.ptr - 1] = cp.top;
+        }
+    }
 }
@@ -4,9 +4,14 @@
 //! It utilizes a BTB for unconditional jumps and a RAS for function returns, but
 //! assumes all conditional branches will fall through.
 
-use super::{BranchPredictor, btb::Btb, ras::Ras};
+use super::{
+    BranchPredictor,
+    btb::Btb,
+    ras::{Ras, RasCheckpoint},
+};
 
 /// Static Branch Predictor structure.
+#[derive(Clone)]
 pub struct StaticPredictor {
     /// Branch Target Buffer for jump targets.
     btb: Btb,
@@ -53,8 +58,12 @@ impl BranchPredictor for StaticPredictor {
     }
 
     /// Handles a function call by pushing the return address to the RAS.
-    fn on_call(&mut self, pc: u64, ret_addr: u64, target: u64) {
+    fn on_call(&mut self, ret_addr: u64) {
         self.ras.push(ret_addr);
+    }
+
+    /// Trains the BTB with a resolved call target.
+    fn update_btb(&mut self, pc: u64, target: u64) {
         self.btb.update(pc, target);
     }
 
@@ -67,4 +76,16 @@ impl BranchPredictor for StaticPredictor {
     fn on_return(&mut self) {
         self.ras.pop();
     }
+
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        self.ras.checkpoint()
+    }
+
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        self.ras.restore(cp);
+    }
+
+    fn ras_entries(&self) -> &[u64] {
+        self.ras.entries()
+    }
 }
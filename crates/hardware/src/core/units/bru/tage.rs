@@ -15,7 +15,11 @@
 //! - **Best Case:** Complex history-correlated patterns with varying lengths
 //! - **Worst Case:** Random or completely uncorrelated branches (~50% accuracy)
 
-use super::{BranchPredictor, btb::Btb, ras::Ras};
+use super::{
+    BranchPredictor,
+    btb::Btb,
+    ras::{Ras, RasCheckpoint},
+};
 use crate::config::TageConfig;
 
 /// An entry in a TAGE bank.
@@ -30,6 +34,7 @@ struct TageEntry {
 }
 
 /// TAGE Predictor structure.
+#[derive(Clone)]
 pub struct TagePredictor {
     /// Branch Target Buffer.
     btb: Btb,
@@ -315,8 +320,12 @@ impl BranchPredictor for TagePredictor {
     }
 
     /// Handles a function call by pushing the return address to the RAS.
-    fn on_call(&mut self, pc: u64, ret_addr: u64, target: u64) {
+    fn on_call(&mut self, ret_addr: u64) {
         self.ras.push(ret_addr);
+    }
+
+    /// Trains the BTB with a resolved call target.
+    fn update_btb(&mut self, pc: u64, target: u64) {
         self.btb.update(pc, target);
     }
 
@@ -330,6 +339,18 @@ impl BranchPredictor for TagePredictor {
         self.ras.pop();
     }
 
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        self.ras.checkpoint()
+    }
+
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        self.ras.restore(cp);
+    }
+
+    fn ras_entries(&self) -> &[u64] {
+        self.ras.entries()
+    }
+
     fn speculate(&mut self, _pc: u64, taken: bool) {
         self.ghr = (self.ghr << 1) | (if taken { 1 } else { 0 });
     }
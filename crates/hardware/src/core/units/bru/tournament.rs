@@ -4,10 +4,15 @@
 //! between a Global predictor (GShare-like) and a Local predictor (PAg/PAp).
 //! This allows the predictor to adapt to different types of branch behaviors.
 
-use super::{BranchPredictor, btb::Btb, ras::Ras};
+use super::{
+    BranchPredictor,
+    btb::Btb,
+    ras::{Ras, RasCheckpoint},
+};
 use crate::config::TournamentConfig;
 
 /// Tournament Predictor structure.
+#[derive(Clone)]
 pub struct TournamentPredictor {
     /// Branch Target Buffer.
     btb: Btb,
@@ -161,8 +166,12 @@ impl BranchPredictor for TournamentPredictor {
     }
 
     /// Handles a function call by pushing the return address to the RAS.
-    fn on_call(&mut self, pc: u64, ret_addr: u64, target: u64) {
+    fn on_call(&mut self, ret_addr: u64) {
         self.ras.push(ret_addr);
+    }
+
+    /// Trains the BTB with a resolved call target.
+    fn update_btb(&mut self, pc: u64, target: u64) {
         self.btb.update(pc, target);
     }
 
@@ -176,6 +185,18 @@ impl BranchPredictor for TournamentPredictor {
         self.ras.pop();
     }
 
+    fn checkpoint_ras(&self) -> RasCheckpoint {
+        self.ras.checkpoint()
+    }
+
+    fn restore_ras(&mut self, cp: RasCheckpoint) {
+        self.ras.restore(cp);
+    }
+
+    fn ras_entries(&self) -> &[u64] {
+        self.ras.entries()
+    }
+
     fn speculate(&mut self, _pc: u64, taken: bool) {
         self.ghr = ((self.ghr << 1) | (taken as u64)) & (self.global_mask as u64);
     }
@@ -5,15 +5,18 @@
 //! hardware prefetchers. It models cache hits, misses, and write-back
 //! penalties to simulate memory hierarchy latency.
 
-/// Cache replacement policy implementations (FIFO, LRU, MRU, PLRU, Random).
+/// Cache replacement policy implementations (DIP, FIFO, LRU, MRU, PLRU, Random).
 pub mod policies;
 
 use self::policies::{
-    FifoPolicy, LruPolicy, MruPolicy, PlruPolicy, RandomPolicy, ReplacementPolicy,
+    DipPolicy, FifoPolicy, LruPolicy, MruPolicy, PlruPolicy, RandomPolicy, ReplacementPolicy,
+};
+use crate::config::{
+    CacheConfig, Prefetcher as PrefetcherType, ReplacementPolicy as PolicyType, defaults,
 };
-use crate::config::{CacheConfig, Prefetcher as PrefetcherType, ReplacementPolicy as PolicyType};
 use crate::core::units::prefetch::{
-    NextLinePrefetcher, Prefetcher, StreamPrefetcher, StridePrefetcher, TaggedPrefetcher,
+    MarkovPrefetcher, NextLinePrefetcher, Prefetcher, StreamPrefetcher, StridePrefetcher,
+    TaggedPrefetcher,
 };
 
 /// Cache line entry containing tag, validity, and dirty bits.
@@ -26,8 +29,8 @@ struct CacheLine {
 
 /// Cache simulator implementing a set-associative cache with configurable policies.
 ///
-/// Supports various replacement policies (FIFO, LRU, PLRU, Random, MRU) and prefetchers
-/// (Next-Line, Stride, Stream, Tagged). Models cache hits, misses, and write-back penalties.
+/// Supports various replacement policies (FIFO, LRU, PLRU, Random, MRU, DIP) and prefetchers
+/// (Next-Line, Stride, Stream, Tagged, Markov). Models cache hits, misses, and write-back penalties.
 pub struct CacheSim {
     /// Access latency in cycles (added on hit; miss adds next-level latency).
     pub latency: u64,
@@ -35,13 +38,47 @@ pub struct CacheSim {
     pub enabled: bool,
     /// Optional hardware prefetcher (boxed for dynamic dispatch; `Send + Sync` for thread safety).
     pub prefetcher: Option<Box<dyn Prefetcher + Send + Sync>>,
+    /// Byte range `[start, end)` that prefetches must not install: the
+    /// granule an LR reservation or an in-flight atomic holds.
+    pub no_prefetch: Option<(u64, u64)>,
     lines: Vec<CacheLine>,
     num_sets: usize,
     ways: usize,
     line_bytes: usize,
+    /// log2 of `line_bytes`.
+    line_shift: u32,
+    /// log2 of `num_sets`.
+    set_bits: u32,
+    banks: usize,
+    /// Demand accesses and misses per set; empty unless enabled in the config.
+    set_counts: Vec<(u64, u64)>,
+    /// Addresses of dirty lines evicted since the last `take_writebacks`.
+    writebacks: Vec<u64>,
     policy: Box<dyn ReplacementPolicy + Send + Sync>,
 }
 
+impl Clone for CacheSim {
+    /// Copies the tag array together with the replacement and prefetcher state.
+    fn clone(&self) -> Self {
+        Self {
+            latency: self.latency,
+            enabled: self.enabled,
+            prefetcher: self.prefetcher.as_ref().map(|p| p.clone_box()),
+            no_prefetch: self.no_prefetch,
+            lines: self.lines.clone(),
+            num_sets: self.num_sets,
+            ways: self.ways,
+            line_bytes: self.line_bytes,
+            line_shift: self.line_shift,
+            set_bits: self.set_bits,
+            banks: self.banks,
+            set_counts: self.set_counts.clone(),
+            writebacks: self.writebacks.clone(),
+            policy: self.policy.clone_box(),
+        }
+    }
+}
+
 impl CacheSim {
     /// Creates a new cache simulator with the specified configuration.
     ///
@@ -53,7 +90,24 @@ impl CacheSim {
     /// # Returns
     ///
     /// A new `CacheSim` instance initialized according to the configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size_bytes` is not a whole number of sets, i.e. not a
+    /// non-zero multiple of `line_bytes * ways`, or if the line size or the
+    /// resulting set count is not a power of two. Zero fields fall back to
+    /// their defaults before the check. The associativity may be any value.
     pub fn new(config: &CacheConfig) -> Self {
+        Self::with_seed(config, defaults::CACHE_RANDOM_SEED)
+    }
+
+    /// Creates a new cache simulator whose Random replacement policy, if
+    /// selected, starts from `seed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on the same invalid geometries as [`CacheSim::new`].
+    pub fn with_seed(config: &CacheConfig, seed: u64) -> Self {
         let safe_ways = if config.ways == 0 { 1 } else { config.ways };
         let safe_line = if config.line_bytes == 0 {
             64
@@ -66,15 +120,29 @@ impl CacheSim {
             config.size_bytes
         };
 
+        let set_bytes = safe_line * safe_ways;
+        assert!(
+            safe_size.is_multiple_of(set_bytes),
+            "invalid cache geometry: size_bytes ({safe_size}) must be a non-zero multiple of \
+             line_bytes ({safe_line}) * ways ({safe_ways}) = {set_bytes}"
+        );
+
         let num_lines = safe_size / safe_line;
         let num_sets = num_lines / safe_ways;
+        assert!(
+            safe_line.is_power_of_two() && num_sets.is_power_of_two(),
+            "invalid cache geometry: line_bytes ({safe_line}) and the number of sets \
+             ({num_sets} = size_bytes {safe_size} / (line_bytes * ways {safe_ways})) must be \
+             powers of two"
+        );
 
         let policy: Box<dyn ReplacementPolicy + Send + Sync> = match config.policy {
             PolicyType::Fifo => Box::new(FifoPolicy::new(num_sets, safe_ways)),
-            PolicyType::Random => Box::new(RandomPolicy::new(num_sets, safe_ways)),
+            PolicyType::Random => Box::new(RandomPolicy::new(num_sets, safe_ways, seed)),
             PolicyType::Plru => Box::new(PlruPolicy::new(num_sets, safe_ways)),
             PolicyType::Lru => Box::new(LruPolicy::new(num_sets, safe_ways)),
             PolicyType::Mru => Box::new(MruPolicy::new(num_sets, safe_ways)),
+            PolicyType::Dip => Box::new(DipPolicy::new(num_sets, safe_ways)),
         };
 
         let prefetcher: Option<Box<dyn Prefetcher + Send + Sync>> = match config.prefetcher {
@@ -95,6 +163,11 @@ impl CacheSim {
                 safe_line,
                 config.prefetch_degree,
             ))),
+            PrefetcherType::Markov => Some(Box::new(MarkovPrefetcher::new(
+                safe_line,
+                config.prefetch_table_size,
+                config.prefetch_degree,
+            ))),
             PrefetcherType::None => None,
         };
 
@@ -103,10 +176,20 @@ impl CacheSim {
             num_sets,
             ways: safe_ways,
             line_bytes: safe_line,
+            line_shift: safe_line.trailing_zeros(),
+            set_bits: num_sets.trailing_zeros(),
+            banks: config.banks,
+            set_counts: if config.set_stats {
+                vec![(0, 0); num_sets]
+            } else {
+                Vec::new()
+            },
+            writebacks: Vec::new(),
             latency: config.latency,
             enabled: config.enabled,
             policy,
             prefetcher,
+            no_prefetch: None,
         }
     }
 
@@ -123,7 +206,7 @@ impl CacheSim {
     /// # Panics
     ///
     /// This function will not panic. Array indexing is guaranteed safe because:
-    /// - `set_index` is always `< num_sets` (modulo operation)
+    /// - `set_index` is always `< num_sets` (masked to `num_sets - 1`)
     /// - `base_idx = set_index * ways` is always `< lines.len()`
     /// - `idx = base_idx + i` where `i < ways` ensures `idx < lines.len()`
     pub fn contains(&self, addr: u64) -> bool {
@@ -131,8 +214,7 @@ impl CacheSim {
             return false;
         }
 
-        let set_index = ((addr as usize) / self.line_bytes) % self.num_sets;
-        let tag = addr / (self.line_bytes * self.num_sets) as u64;
+        let (set_index, tag) = self.locate(addr);
         let base_idx = set_index * self.ways;
 
         for i in 0..self.ways {
@@ -144,11 +226,79 @@ impl CacheSim {
         false
     }
 
+    /// Returns the line size in bytes.
+    pub fn line_bytes(&self) -> usize {
+        self.line_bytes
+    }
+
+    /// Returns `(accesses, misses)` for each set, indexed by set, counting
+    /// demand accesses only (prefetch fills are excluded). Empty unless
+    /// `set_stats` was enabled in the cache configuration.
+    pub fn set_stats(&self) -> Vec<(u64, u64)> {
+        self.set_counts.clone()
+    }
+
+    /// Removes and returns the addresses of the dirty lines evicted since
+    /// the last call, oldest first.
+    pub fn take_writebacks(&mut self) -> Vec<u64> {
+        std::mem::take(&mut self.writebacks)
+    }
+
+    /// Zeroes the per-set counters, keeping them enabled.
+    pub fn clear_set_stats(&mut self) {
+        self.set_counts.fill((0, 0));
+    }
+
+    /// Splits an address into its set index (the line-number bits below
+    /// `set_bits`) and tag (every bit above them).
+    fn locate(&self, addr: u64) -> (usize, u64) {
+        let line = addr >> self.line_shift;
+        let set_index = (line & ((1 << self.set_bits) - 1)) as usize;
+        (set_index, line >> self.set_bits)
+    }
+
+    /// Returns the cycles each of a group of same-cycle accesses waits for
+    /// its bank.
+    ///
+    /// Banks are interleaved by line and each serves one line per cycle, so
+    /// the k-th distinct line to reach a bank waits k cycles. Accesses to a
+    /// line already being served share it. An unbanked or disabled cache
+    /// never conflicts.
+    ///
+    /// # Arguments
+    ///
+    /// * `addrs` - Physical address of each access, in issue order.
+    ///
+    /// # Returns
+    ///
+    /// The bank delay of each access, in the same order as `addrs`.
+    pub fn bank_delays(&self, addrs: &[u64]) -> Vec<u64> {
+        if self.banks == 0 || !self.enabled {
+            return vec![0; addrs.len()];
+        }
+        let mut served: Vec<(u64, u64)> = Vec::with_capacity(addrs.len());
+        let mut busy = vec![0u64; self.banks];
+        addrs
+            .iter()
+            .map(|&addr| {
+                let line = addr >> self.line_shift;
+                if let Some(&(_, delay)) = served.iter().find(|&&(l, _)| l == line) {
+                    return delay;
+                }
+                let bank = (line % self.banks as u64) as usize;
+                let delay = busy[bank];
+                busy[bank] += 1;
+                served.push((line, delay));
+                delay
+            })
+            .collect()
+    }
+
     /// Installs a cache line for the specified address.
     ///
     /// Selects a victim line using the replacement policy and installs
-    /// the new line. Returns the penalty for write-back if the victim
-    /// line was dirty.
+    /// the new line. A dirty victim costs the write-back penalty and its
+    /// address is recorded for `take_writebacks`.
     ///
     /// # Arguments
     ///
@@ -160,16 +310,18 @@ impl CacheSim {
     ///
     /// The penalty in cycles for writing back a dirty victim line.
     fn install_line(&mut self, addr: u64, is_write: bool, next_level_latency: u64) -> u64 {
-        let set_index = ((addr as usize) / self.line_bytes) % self.num_sets;
-        let tag = addr / (self.line_bytes * self.num_sets) as u64;
+        let (set_index, tag) = self.locate(addr);
         let base_idx = set_index * self.ways;
 
         let victim_way = self.policy.get_victim(set_index);
         let victim_idx = base_idx + victim_way;
         let mut penalty = 0;
 
-        if self.lines[victim_idx].valid && self.lines[victim_idx].dirty {
+        let victim = &self.lines[victim_idx];
+        if victim.valid && victim.dirty {
             penalty += next_level_latency;
+            let line = (victim.tag << self.set_bits) | set_index as u64;
+            self.writebacks.push(line << self.line_shift);
         }
 
         self.lines[victim_idx] = CacheLine {
@@ -177,7 +329,7 @@ impl CacheSim {
             valid: true,
             dirty: is_write,
         };
-        self.policy.update(set_index, victim_way);
+        self.policy.insert(set_index, victim_way);
 
         penalty
     }
@@ -204,8 +356,7 @@ impl CacheSim {
             return (false, 0);
         }
 
-        let set_index = ((addr as usize) / self.line_bytes) % self.num_sets;
-        let tag = addr / (self.line_bytes * self.num_sets) as u64;
+        let (set_index, tag) = self.locate(addr);
         let base_idx = set_index * self.ways;
 
         let mut hit = false;
@@ -223,6 +374,11 @@ impl CacheSim {
             }
         }
 
+        if let Some(counts) = self.set_counts.get_mut(set_index) {
+            counts.0 += 1;
+            counts.1 += u64::from(!hit);
+        }
+
         if !hit {
             penalty += self.install_line(addr, is_write, next_level_latency);
         }
@@ -233,7 +389,7 @@ impl CacheSim {
         }
 
         for target in prefetches {
-            if !self.contains(target) {
+            if !self.contains(target) && !self.prefetch_blocked(target) {
                 self.install_line(target, false, next_level_latency);
             }
         }
@@ -241,6 +397,43 @@ impl CacheSim {
         (hit, penalty)
     }
 
+    /// Returns `true` if the line holding `addr` overlaps `no_prefetch`.
+    fn prefetch_blocked(&self, addr: u64) -> bool {
+        self.no_prefetch.is_some_and(|(start, end)| {
+            let line = addr & !(self.line_bytes as u64 - 1);
+            line < end && line + self.line_bytes as u64 > start
+        })
+    }
+
+    /// Invalidates the line holding `addr`, if present.
+    ///
+    /// Returns `true` if a valid line was dropped. The line is not written
+    /// back; callers use this for caches that never hold dirty data, such as
+    /// the I-cache snooping stores.
+    pub fn invalidate_line(&mut self, addr: u64) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (set_index, tag) = self.locate(addr);
+        let base_idx = set_index * self.ways;
+        for line in &mut self.lines[base_idx..base_idx + self.ways] {
+            if line.valid && line.tag == tag {
+                line.valid = false;
+                line.dirty = false;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Invalidates every line, clean or dirty, without write-back.
+    pub fn invalidate_all(&mut self) {
+        for line in &mut self.lines {
+            line.valid = false;
+            line.dirty = false;
+        }
+    }
+
     /// Flushes all dirty cache lines, invalidating them.
     ///
     /// Marks all valid and dirty lines as invalid. Used for cache
@@ -0,0 +1,181 @@
+//! Dynamic Insertion Policy (DIP) Replacement.
+//!
+//! DIP keeps an LRU recency stack per set but chooses, through set dueling,
+//! where newly installed lines enter that stack:
+//!
+//! - **LRU insertion** places the new line at the MRU position, as plain LRU
+//!   does.
+//! - **BIP (Bimodal Insertion)** places it at the LRU position, promoting it
+//!   to MRU only on every 32nd insertion. A line must be re-referenced
+//!   before it displaces older lines, so a scan larger than the cache
+//!   thrashes a single way instead of the whole set.
+//!
+//! A few leader sets always use LRU insertion and a few always use BIP. A
+//! miss in an LRU leader increments a saturating selector counter (PSEL), a
+//! miss in a BIP leader decrements it, and every follower set uses BIP
+//! while PSEL is in its upper half. Caches with fewer than four sets have
+//! no leaders and behave as LRU.
+//!
+//! The first `ways` insertions into each set always go to MRU, standing in
+//! for the fill of invalid ways; otherwise BIP would keep reusing a single
+//! way of a cold set.
+//!
+//! Reference: Qureshi et al., "Adaptive Insertion Policies for High
+//! Performance Caching", ISCA 2007.
+//!
+//! # Performance
+//!
+//! - **Time Complexity:** O(W) per update and insertion, O(1) per victim.
+//! - **Space Complexity:** O(S × W) plus one 10-bit counter.
+//! - **Best Case:** Mixes of reused working sets and streaming scans.
+//! - **Worst Case:** Phases that flip between the two behaviours faster than
+//!   PSEL can follow.
+
+use super::ReplacementPolicy;
+
+/// Maximum number of leader sets dedicated to each insertion policy.
+const MAX_LEADERS: usize = 32;
+
+/// Largest PSEL value (10-bit counter).
+const PSEL_MAX: u16 = (1 << 10) - 1;
+
+/// Followers use BIP while PSEL is at or above this value.
+const PSEL_THRESHOLD: u16 = 1 << 9;
+
+/// BIP promotes one in this many insertions to the MRU position.
+const BIP_EPSILON: u32 = 32;
+
+/// Role of a set in the duel.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SetRole {
+    /// Always inserts at MRU.
+    LruLeader,
+    /// Always inserts bimodally.
+    BipLeader,
+    /// Follows the winner recorded in PSEL.
+    Follower,
+}
+
+/// DIP Policy state.
+#[derive(Clone)]
+pub struct DipPolicy {
+    /// A vector of usage stacks (one per set).
+    /// Index 0 is MRU, last index is LRU.
+    usage: Vec<Vec<usize>>,
+    /// Lines installed per set, saturating at the associativity.
+    filled: Vec<usize>,
+    /// Distance between leader sets; 0 when the cache is too small to duel.
+    leader_stride: usize,
+    /// Policy selector counter.
+    psel: u16,
+    /// Counts BIP insertions to pick which ones go to MRU.
+    bip_count: u32,
+}
+
+impl DipPolicy {
+    /// Creates a new DIP policy instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `sets` - The number of sets in the cache.
+    /// * `ways` - The associativity (number of ways) of the cache.
+    pub fn new(sets: usize, ways: usize) -> Self {
+        let leaders = (sets / 4).min(MAX_LEADERS);
+        Self {
+            usage: (0..sets).map(|_| (0..ways).collect()).collect(),
+            filled: vec![0; sets],
+            leader_stride: sets.checked_div(leaders).unwrap_or(0),
+            psel: PSEL_THRESHOLD - 1,
+            bip_count: 0,
+        }
+    }
+
+    /// Returns the current PSEL value. Values at or above the midpoint
+    /// (512) mean follower sets use BIP.
+    pub fn psel(&self) -> u16 {
+        self.psel
+    }
+
+    /// Returns whether the follower sets currently use BIP.
+    pub fn followers_use_bip(&self) -> bool {
+        self.psel >= PSEL_THRESHOLD
+    }
+
+    /// Leader sets sit at offset 0 (LRU) and at the midpoint (BIP) of each
+    /// stride-sized group, so both spread evenly across the index space.
+    fn role(&self, set: usize) -> SetRole {
+        if self.leader_stride == 0 {
+            return SetRole::Follower;
+        }
+        match set % self.leader_stride {
+            0 => SetRole::LruLeader,
+            o if o == self.leader_stride / 2 => SetRole::BipLeader,
+            _ => SetRole::Follower,
+        }
+    }
+
+    /// Moves `way` to the MRU position of `set`.
+    fn promote(&mut self, set: usize, way: usize) {
+        let stack = &mut self.usage[set];
+        if let Some(pos) = stack.iter().position(|&x| x == way) {
+            stack.remove(pos);
+        }
+        stack.insert(0, way);
+    }
+
+    /// Moves `way` to the LRU position of `set`.
+    fn demote(&mut self, set: usize, way: usize) {
+        let stack = &mut self.usage[set];
+        if let Some(pos) = stack.iter().position(|&x| x == way) {
+            stack.remove(pos);
+        }
+        stack.push(way);
+    }
+}
+
+impl ReplacementPolicy for DipPolicy {
+    /// Updates the policy state on a hit by promoting the line to MRU.
+    fn update(&mut self, set: usize, way: usize) {
+        self.promote(set, way);
+    }
+
+    /// Places a newly installed line according to the set's insertion
+    /// policy.
+    fn insert(&mut self, set: usize, way: usize) {
+        if self.filled[set] < self.usage[set].len() {
+            self.filled[set] += 1;
+            self.promote(set, way);
+            return;
+        }
+        let bip = match self.role(set) {
+            SetRole::LruLeader => false,
+            SetRole::BipLeader => true,
+            SetRole::Follower => self.followers_use_bip(),
+        };
+        if !bip {
+            self.promote(set, way);
+            return;
+        }
+        self.bip_count = (self.bip_count + 1) % BIP_EPSILON;
+        if self.bip_count == 0 {
+            self.promote(set, way);
+        } else {
+            self.demote(set, way);
+        }
+    }
+
+    /// Identifies the victim way to evict and charges the miss to the duel
+    /// if `set` is a leader.
+    fn get_victim(&mut self, set: usize) -> usize {
+        match self.role(set) {
+            SetRole::LruLeader => self.psel = (self.psel + 1).min(PSEL_MAX),
+            SetRole::BipLeader => self.psel = self.psel.saturating_sub(1),
+            SetRole::Follower => {}
+        }
+        *self.usage[set].last().unwrap()
+    }
+
+    fn clone_box(&self) -> Box<dyn ReplacementPolicy + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
@@ -17,6 +17,7 @@
 use super::ReplacementPolicy;
 
 /// FIFO Policy state.
+#[derive(Clone)]
 pub struct FifoPolicy {
     /// Tracks the next way to be evicted for each set.
     next_way: Vec<usize>,
@@ -57,4 +58,8 @@ impl ReplacementPolicy for FifoPolicy {
     fn get_victim(&mut self, set: usize) -> usize {
         self.next_way[set]
     }
+
+    fn clone_box(&self) -> Box<dyn ReplacementPolicy + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
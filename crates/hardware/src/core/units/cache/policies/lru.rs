@@ -18,6 +18,7 @@
 use super::ReplacementPolicy;
 
 /// LRU Policy state.
+#[derive(Clone)]
 pub struct LruPolicy {
     /// A vector of usage stacks (one per set).
     /// Index 0 is MRU, last index is LRU.
@@ -59,4 +60,8 @@ impl ReplacementPolicy for LruPolicy {
     fn get_victim(&mut self, set: usize) -> usize {
         *self.usage[set].last().unwrap()
     }
+
+    fn clone_box(&self) -> Box<dyn ReplacementPolicy + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
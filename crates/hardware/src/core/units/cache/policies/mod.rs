@@ -4,12 +4,16 @@
 //!
 //! # Policies
 //!
+//! - `Dip`: Dynamic Insertion Policy (set dueling between LRU and BIP).
 //! - `Fifo`: First-In, First-Out.
 //! - `Lru`: Least Recently Used.
 //! - `Mru`: Most Recently Used.
 //! - `Plru`: Pseudo-LRU (Tree-based).
 //! - `Random`: Random selection.
 
+/// Dynamic Insertion Policy (adaptive LRU/BIP) replacement policy.
+pub mod dip;
+
 /// First-In, First-Out replacement policy.
 pub mod fifo;
 
@@ -25,6 +29,7 @@ pub mod plru;
 /// Random replacement policy.
 pub mod random;
 
+pub use dip::DipPolicy;
 pub use fifo::FifoPolicy;
 pub use lru::LruPolicy;
 pub use mru::MruPolicy;
@@ -43,6 +48,18 @@ pub trait ReplacementPolicy: Send + Sync {
     /// * `way` - The way index within the set that was accessed.
     fn update(&mut self, set: usize, way: usize);
 
+    /// Updates the policy state when a line is installed into `way` after a
+    /// miss. Defaults to [`ReplacementPolicy::update`], i.e. insertion is
+    /// treated like an access.
+    ///
+    /// # Arguments
+    ///
+    /// * `set` - The cache set index.
+    /// * `way` - The way index the new line was installed into.
+    fn insert(&mut self, set: usize, way: usize) {
+        self.update(set, way);
+    }
+
     /// Selects a victim line to evict from a specific set.
     ///
     /// # Arguments
@@ -53,4 +70,7 @@ pub trait ReplacementPolicy: Send + Sync {
     ///
     /// The index of the way to evict.
     fn get_victim(&mut self, set: usize) -> usize;
+
+    /// Returns a boxed copy of this policy, including its usage state.
+    fn clone_box(&self) -> Box<dyn ReplacementPolicy + Send + Sync>;
 }
@@ -9,6 +9,7 @@
 use super::ReplacementPolicy;
 
 /// MRU Policy state.
+#[derive(Clone)]
 pub struct MruPolicy {
     /// A vector of usage stacks (one per set).
     /// Index 0 is the MRU position (victim), last index is LRU.
@@ -49,4 +50,8 @@ impl ReplacementPolicy for MruPolicy {
     fn get_victim(&mut self, set: usize) -> usize {
         *self.usage[set].first().unwrap()
     }
+
+    fn clone_box(&self) -> Box<dyn ReplacementPolicy + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
@@ -20,6 +20,7 @@
 use super::ReplacementPolicy;
 
 /// PLRU Policy state.
+#[derive(Clone)]
 pub struct PlruPolicy {
     /// Bitmask representing the tree state for each set.
     usage: Vec<u64>,
@@ -68,4 +69,8 @@ impl ReplacementPolicy for PlruPolicy {
         }
         0
     }
+
+    fn clone_box(&self) -> Box<dyn ReplacementPolicy + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
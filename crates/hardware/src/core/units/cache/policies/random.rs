@@ -1,12 +1,13 @@
 //! Random Replacement Policy.
 //!
-//! This policy evicts a random cache line from the set. It uses a simple
-//! Linear Feedback Shift Register (LFSR) to generate pseudo-random numbers,
-//! avoiding the overhead of a complex RNG.
+//! This policy evicts a random cache line from the set. It uses a seeded
+//! xorshift generator, so victim choices are reproducible for a given seed
+//! and access stream while avoiding the overhead of a complex RNG.
 
 use super::ReplacementPolicy;
 
 /// Random Policy state.
+#[derive(Clone)]
 pub struct RandomPolicy {
     /// Number of ways in the cache.
     ways: usize,
@@ -21,10 +22,16 @@ impl RandomPolicy {
     ///
     /// * `sets` - The number of sets (unused in this policy but required by interface).
     /// * `ways` - The associativity (number of ways) of the cache.
-    pub fn new(_sets: usize, ways: usize) -> Self {
+    /// * `seed` - Initial PRNG state. Xorshift never leaves the all-zero
+    ///   state, so a zero seed is replaced by a fixed non-zero one.
+    pub fn new(_sets: usize, ways: usize, seed: u64) -> Self {
         Self {
             ways,
-            state: 123456789,
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
         }
     }
 }
@@ -47,4 +54,8 @@ impl ReplacementPolicy for RandomPolicy {
         self.state = x;
         (x as usize) % self.ways
     }
+
+    fn clone_box(&self) -> Box<dyn ReplacementPolicy + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
@@ -664,7 +664,7 @@ impl Fpu {
             AluOp::FCvtSLU => (a as f64).to_bits(),
 
             // --- Conversions (single → double) ---
-            AluOp::FCvtDS => (unbox_f32(a) as f64).to_bits(),
+            AluOp::FCvtDS => canonicalize_f64_bits(unbox_f32(a) as f64),
 
             // --- Move operations ---
             AluOp::FMvToF => box_f32(f32::from_bits(a as u32)),
@@ -0,0 +1,58 @@
+//! Data byte order.
+//!
+//! Memory is stored little-endian and instruction fetch is always
+//! little-endian. When the hart runs with big-endian data accesses
+//! (`mstatus.MBE`/`SBE`/`UBE` set), loads and stores reverse the bytes of
+//! each access at the LSU boundary. This module provides:
+//! - Byte reversal of a value within its access width
+//! - Sign or zero extension of a loaded value to 64 bits
+
+use crate::core::pipeline::signals::MemWidth;
+
+/// Reverses the bytes of the low `width` bytes of `value`.
+///
+/// Converts between a register value and the value whose little-endian
+/// bytes are written to memory by a big-endian access. Bits above the
+/// access width are cleared.
+///
+/// # Arguments
+///
+/// * `value` - The value to convert.
+/// * `width` - The access width.
+///
+/// # Returns
+///
+/// The byte-reversed value, zero-extended to 64 bits.
+pub fn swap(value: u64, width: MemWidth) -> u64 {
+    match width {
+        MemWidth::Byte => value & 0xFF,
+        MemWidth::Half => (value as u16).swap_bytes() as u64,
+        MemWidth::Word => (value as u32).swap_bytes() as u64,
+        MemWidth::Double => value.swap_bytes(),
+        MemWidth::Nop => 0,
+    }
+}
+
+/// Extends the low `width` bytes of `value` to 64 bits.
+///
+/// # Arguments
+///
+/// * `value`  - The loaded value; bits above the access width are ignored.
+/// * `width`  - The access width.
+/// * `signed` - Sign-extend instead of zero-extend.
+///
+/// # Returns
+///
+/// The extended value.
+pub fn extend(value: u64, width: MemWidth, signed: bool) -> u64 {
+    match (width, signed) {
+        (MemWidth::Byte, true) => value as u8 as i8 as i64 as u64,
+        (MemWidth::Half, true) => value as u16 as i16 as i64 as u64,
+        (MemWidth::Word, true) => value as u32 as i32 as i64 as u64,
+        (MemWidth::Byte, false) => value as u8 as u64,
+        (MemWidth::Half, false) => value as u16 as u64,
+        (MemWidth::Word, false) => value as u32 as u64,
+        (MemWidth::Double, _) => value,
+        (MemWidth::Nop, _) => 0,
+    }
+}
@@ -3,13 +3,17 @@
 //! This module provides the Load/Store Unit, responsible for memory access
 //! operations. It includes:
 //! - [`atomic`]: Read-modify-write ALU for the RISC-V A extension.
-//! - [`ordering`]: Memory ordering / fence support (stub).
+//! - [`endian`]: Byte order of data accesses.
+//! - [`ordering`]: FENCE ordering-set decoding.
 //! - [`unaligned`]: Unaligned access handling (stub).
 
 /// Atomic memory operation ALU (RISC-V A extension).
 pub mod atomic;
 
-/// Memory ordering and fence operations (stub, Phase 3).
+/// Data byte order (big-endian data accesses).
+pub mod endian;
+
+/// Memory ordering and fence operations.
 pub mod ordering;
 
 /// Unaligned memory access handling (stub, Phase 3).
@@ -3,7 +3,8 @@
 //! This module implements the Memory Management Unit, responsible for
 //! virtual-to-physical address translation. It supports the RISC-V SV39
 //! paging scheme and includes Translation Lookaside Buffers (TLBs) for
-//! caching translations.
+//! caching translations. Accessed/dirty bits are either set by the page
+//! table walker or, with Svade, left to software through page faults.
 
 /// Physical Memory Protection (PMP).
 pub mod pmp;
@@ -30,6 +31,14 @@ pub struct Mmu {
     pub dtlb: Tlb,
     /// Instruction TLB for fetch address translation.
     pub itlb: Tlb,
+    /// Number of instruction TLB lookups that missed.
+    pub itlb_misses: u64,
+    /// Number of data TLB lookups that missed.
+    pub dtlb_misses: u64,
+    /// Fault on clear A/D bits instead of setting them (Svade).
+    pub svade: bool,
+    /// Page table entries are stored big-endian (`mstatus.SBE`).
+    pub big_endian: bool,
 }
 
 impl Mmu {
@@ -46,6 +55,10 @@ impl Mmu {
         Self {
             dtlb: Tlb::new(tlb_size),
             itlb: Tlb::new(tlb_size),
+            itlb_misses: 0,
+            dtlb_misses: 0,
+            svade: false,
+            big_endian: false,
         }
     }
 
@@ -130,13 +143,22 @@ impl Mmu {
 
         use crate::common::constants::{PAGE_SHIFT, VPN_MASK};
         let vpn = (vaddr.val() >> PAGE_SHIFT) & VPN_MASK;
+        let asid = satp_asid(satp);
 
         let tlb_entry = if access == AccessType::Fetch {
-            self.itlb.lookup(vpn)
+            self.itlb.lookup(vpn, asid)
         } else {
-            self.dtlb.lookup(vpn)
+            self.dtlb.lookup(vpn, asid)
         };
 
+        if tlb_entry.is_none() {
+            if access == AccessType::Fetch {
+                self.itlb_misses += 1;
+            } else {
+                self.dtlb_misses += 1;
+            }
+        }
+
         if let Some((ppn, r, w, x, u, d)) = tlb_entry {
             // If writing to a page with D=0, invalidate the TLB entry and
             // fall through to the page table walk so the PTW sets the dirty
@@ -186,6 +208,29 @@ impl Mmu {
 
         ptw::page_table_walk(self, vaddr, access, privilege, csrs, bus)
     }
+
+    /// Performs `SFENCE.VMA` on both TLBs.
+    ///
+    /// `vaddr` is `None` when rs1 is x0 (all addresses) and `asid` is `None`
+    /// when rs2 is x0 (all address spaces). Global entries are only removed
+    /// by a fence that covers all address spaces.
+    pub fn sfence_vma(&mut self, vaddr: Option<u64>, asid: Option<u16>) {
+        use crate::common::constants::{PAGE_SHIFT, VPN_MASK};
+        for tlb in [&mut self.itlb, &mut self.dtlb] {
+            match (vaddr.map(|va| (va >> PAGE_SHIFT) & VPN_MASK), asid) {
+                (None, None) => tlb.flush(),
+                (None, Some(asid)) => tlb.flush_asid(asid),
+                (Some(vpn), None) => tlb.invalidate(vpn),
+                (Some(vpn), Some(asid)) => tlb.invalidate_asid(vpn, asid),
+            }
+        }
+    }
+}
+
+/// Extracts the ASID field from a `satp` value.
+pub fn satp_asid(satp: u64) -> u16 {
+    use crate::core::arch::csr::{SATP_ASID_MASK, SATP_ASID_SHIFT};
+    ((satp >> SATP_ASID_SHIFT) & SATP_ASID_MASK) as u16
 }
 
 /// Creates an appropriate page fault trap for the access type.
@@ -9,7 +9,7 @@ use crate::common::{
 };
 use crate::core::arch::csr::{Csrs, SATP_PPN_MASK};
 use crate::core::arch::mode::PrivilegeMode;
-use crate::core::units::mmu::Mmu;
+use crate::core::units::mmu::{Mmu, satp_asid};
 use crate::soc::interconnect::Bus;
 
 /// Page Table Entry valid bit (bit 0).
@@ -27,6 +27,9 @@ const PTE_EXEC_BIT: u64 = 1 << 3;
 /// Page Table Entry user mode access bit (bit 4).
 const PTE_USER_BIT: u64 = 1 << 4;
 
+/// Page Table Entry global mapping bit (bit 5).
+const PTE_GLOBAL_BIT: u64 = 1 << 5;
+
 /// Page Table Entry accessed bit (bit 6).
 const PTE_ACCESSED_BIT: u64 = 1 << 6;
 
@@ -36,6 +39,13 @@ const PTE_DIRTY_BIT: u64 = 1 << 7;
 /// Bit shift to extract Physical Page Number from PTE (bits 10-53).
 const PTE_PPN_SHIFT: u64 = 10;
 
+/// Bits 63-54 of a PTE: reserved, or owned by Svnapot/Svpbmt, neither of
+/// which is implemented, so they must be zero.
+const PTE_RESERVED_MASK: u64 = 0xFFC0_0000_0000_0000;
+
+/// A, D and U are reserved in a non-leaf PTE.
+const PTE_NON_LEAF_RESERVED_MASK: u64 = PTE_ACCESSED_BIT | PTE_DIRTY_BIT | PTE_USER_BIT;
+
 /// A strongly-typed wrapper around a raw 64-bit SV39 Page Table Entry.
 #[derive(Clone, Copy, Debug)]
 struct PageTableEntry(u64);
@@ -76,6 +86,11 @@ impl PageTableEntry {
         self.0 & PTE_USER_BIT != 0
     }
 
+    /// Returns true if the Global (G) bit is set.
+    fn is_global(&self) -> bool {
+        self.0 & PTE_GLOBAL_BIT != 0
+    }
+
     /// Returns true if the Accessed (A) bit is set.
     fn is_accessed(&self) -> bool {
         self.0 & PTE_ACCESSED_BIT != 0
@@ -91,6 +106,14 @@ impl PageTableEntry {
         (self.0 >> PTE_PPN_SHIFT) & SATP_PPN_MASK
     }
 
+    /// Returns true if the entry sets reserved bits or uses a reserved
+    /// encoding: any of bits 63-54, W without R, or A/D/U in a pointer.
+    fn is_malformed(&self) -> bool {
+        self.0 & PTE_RESERVED_MASK != 0
+            || (self.can_write() && !self.can_read())
+            || (self.is_pointer() && self.0 & PTE_NON_LEAF_RESERVED_MASK != 0)
+    }
+
     /// Determines if this entry is a pointer to the next level page table.
     ///
     /// In SV39, an entry is a pointer if it is Valid but has R=0, W=0, and X=0.
@@ -148,6 +171,8 @@ pub fn page_table_walk(
     let satp = csrs.satp;
     let mut ppn = satp & SATP_PPN_MASK;
     let mut cycles = 0;
+    // G on a pointer makes every mapping below it global.
+    let mut global = false;
 
     for level in (0..SV39_LEVELS).rev() {
         let vpn_shift = PAGE_SHIFT + level as u64 * VPN_BITS_PER_LEVEL;
@@ -155,13 +180,24 @@ pub fn page_table_walk(
         let pte_addr = (ppn << PAGE_SHIFT) + (vpn_i * PTE_SIZE);
 
         cycles += bus.calculate_transit_time(8);
-        let raw_pte = bus.read_u64(pte_addr);
+        // A PTE that no device backs raises an access fault of the original type.
+        let Ok(raw_pte) = bus.read_u64(pte_addr) else {
+            return TranslationResult::fault(Trap::access_fault(access, vaddr.val()), cycles);
+        };
+        // Implicit page table accesses follow the supervisor byte order.
+        let raw_pte = if mmu.big_endian {
+            raw_pte.swap_bytes()
+        } else {
+            raw_pte
+        };
         let pte = PageTableEntry::new(raw_pte);
 
-        if !pte.is_valid() {
+        if !pte.is_valid() || pte.is_malformed() {
             return TranslationResult::fault(page_fault(vaddr.val(), access), cycles);
         }
 
+        global |= pte.is_global();
+
         if pte.is_pointer() {
             if level == 0 {
                 return TranslationResult::fault(page_fault(vaddr.val(), access), cycles);
@@ -181,10 +217,21 @@ pub fn page_table_walk(
             return TranslationResult::fault(page_fault(vaddr.val(), access), cycles);
         }
 
+        // Svade: software owns A/D, so a walk that would set either faults.
+        if mmu.svade && (!pte.is_accessed() || (access == AccessType::Write && !pte.is_dirty())) {
+            return TranslationResult::fault(page_fault(vaddr.val(), access), cycles);
+        }
+
         let (new_pte, updated) = update_access_bits(pte, access);
 
         if updated {
-            bus.write_u64(pte_addr, new_pte.raw());
+            // The PTE was just read from this address, so it is mapped.
+            let raw = if mmu.big_endian {
+                new_pte.raw().swap_bytes()
+            } else {
+                new_pte.raw()
+            };
+            let _ = bus.write_u64(pte_addr, raw);
             cycles += PTE_UPDATE_CYCLES;
         }
 
@@ -196,10 +243,13 @@ pub fn page_table_walk(
         let specific_4kb_ppn = final_paddr >> PAGE_SHIFT;
         let vpn = (vaddr.val() >> PAGE_SHIFT) & VPN_MASK;
 
+        let asid = satp_asid(satp);
         if access == AccessType::Fetch {
-            mmu.itlb.insert(vpn, specific_4kb_ppn, new_pte.raw());
+            mmu.itlb
+                .insert(vpn, specific_4kb_ppn, new_pte.raw(), asid, global);
         } else {
-            mmu.dtlb.insert(vpn, specific_4kb_ppn, new_pte.raw());
+            mmu.dtlb
+                .insert(vpn, specific_4kb_ppn, new_pte.raw(), asid, global);
         }
 
         return TranslationResult::success(PhysAddr::new(final_paddr), cycles);
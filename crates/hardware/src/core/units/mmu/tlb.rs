@@ -3,6 +3,10 @@
 //! A fully associative cache for page table entries. It stores the mapping
 //! between Virtual Page Numbers (VPN) and Physical Page Numbers (PPN), along
 //! with permission bits (R/W/X/U) to speed up address translation.
+//!
+//! Entries are tagged with the ASID they were filled under and only hit for
+//! that ASID, unless the PTE was global (G bit), in which case they hit for
+//! every ASID and survive ASID-specific flushes.
 
 /// A single entry in the TLB.
 #[derive(Clone, Copy, Default)]
@@ -23,9 +27,21 @@ struct TlbEntry {
     u: bool,
     /// Dirty bit from PTE.
     d: bool,
+    /// Global mapping (G bit from PTE): matches every ASID.
+    global: bool,
+    /// Address space the entry was filled under.
+    asid: u16,
+}
+
+impl TlbEntry {
+    /// Returns true if the entry is valid and translates `vpn` in `asid`.
+    fn matches(&self, vpn: u64, asid: u16) -> bool {
+        self.valid && self.vpn == vpn && (self.global || self.asid == asid)
+    }
 }
 
 /// Translation Lookaside Buffer structure.
+#[derive(Clone)]
 pub struct Tlb {
     /// Vector of TLB entries.
     entries: Vec<TlbEntry>,
@@ -57,6 +73,7 @@ impl Tlb {
     /// # Arguments
     ///
     /// * `vpn` - The Virtual Page Number to look up.
+    /// * `asid` - The current address space identifier.
     ///
     /// # Returns
     ///
@@ -68,7 +85,7 @@ impl Tlb {
     /// - `idx = vpn & self.mask` where `mask = size - 1` (size is power of 2)
     /// - This ensures `idx` is always `< size` and within bounds of `entries`
     #[inline(always)]
-    pub fn lookup(&self, vpn: u64) -> Option<(u64, bool, bool, bool, bool, bool)> {
+    pub fn lookup(&self, vpn: u64, asid: u16) -> Option<(u64, bool, bool, bool, bool, bool)> {
         let idx = (vpn as usize) & self.mask;
 
         // SAFETY: idx is guaranteed to be < entries.len() by the mask operation above.
@@ -76,7 +93,7 @@ impl Tlb {
         // ensuring idx is always a valid index.
         let entry = unsafe { self.entries.get_unchecked(idx) };
 
-        if entry.valid && entry.vpn == vpn {
+        if entry.matches(vpn, asid) {
             return Some((entry.ppn, entry.r, entry.w, entry.x, entry.u, entry.d));
         }
         None
@@ -89,7 +106,10 @@ impl Tlb {
     /// * `vpn` - Virtual Page Number.
     /// * `ppn` - Physical Page Number.
     /// * `pte` - Raw Page Table Entry (used to extract permissions).
-    pub fn insert(&mut self, vpn: u64, ppn: u64, pte: u64) {
+    /// * `asid` - The address space the translation belongs to.
+    /// * `global` - Whether the leaf or any pointer above it had the G bit
+    ///   set, so the mapping exists in every address space.
+    pub fn insert(&mut self, vpn: u64, ppn: u64, pte: u64, asid: u16, global: bool) {
         let r = (pte >> 1) & 1 != 0;
         let w = (pte >> 2) & 1 != 0;
         let x = (pte >> 3) & 1 != 0;
@@ -107,10 +127,12 @@ impl Tlb {
             x,
             u,
             d,
+            global,
+            asid,
         };
     }
 
-    /// Invalidates a single TLB entry by VPN.
+    /// Invalidates a single TLB entry by VPN, in any address space.
     pub fn invalidate(&mut self, vpn: u64) {
         let idx = (vpn as usize) & self.mask;
         if self.entries[idx].valid && self.entries[idx].vpn == vpn {
@@ -118,9 +140,32 @@ impl Tlb {
         }
     }
 
+    /// Invalidates the non-global entry for `vpn` in `asid`.
+    ///
+    /// Called on `SFENCE.VMA` with both an address and an ASID.
+    pub fn invalidate_asid(&mut self, vpn: u64, asid: u16) {
+        let idx = (vpn as usize) & self.mask;
+        let e = &mut self.entries[idx];
+        if e.valid && e.vpn == vpn && !e.global && e.asid == asid {
+            e.valid = false;
+        }
+    }
+
+    /// Flushes every non-global entry of `asid`; global entries survive.
+    ///
+    /// Called on `SFENCE.VMA` with an ASID and no address.
+    pub fn flush_asid(&mut self, asid: u16) {
+        for e in &mut self.entries {
+            if !e.global && e.asid == asid {
+                e.valid = false;
+            }
+        }
+    }
+
     /// Flushes all entries from the TLB.
     ///
-    /// Called on `SFENCE.VMA` instructions or SATP writes.
+    /// Called on `SFENCE.VMA` without an ASID, and on reset or checkpoint
+    /// restore.
     pub fn flush(&mut self) {
         for e in &mut self.entries {
             e.valid = false;
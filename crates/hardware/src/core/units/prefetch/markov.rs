@@ -0,0 +1,169 @@
+//! Markov (Correlation) Prefetcher.
+//!
+//! A prefetcher that learns address correlations from the miss stream. For
+//! each recently-missed cache line it remembers the lines that missed
+//! immediately afterwards, along with a small frequency counter. When the
+//! same line misses again, the most frequently observed successors are
+//! prefetched.
+//!
+//! Unlike stride or stream prefetchers, this captures irregular but
+//! repeatable patterns such as pointer chasing and graph traversal.
+//!
+//! # Performance
+//!
+//! - **Time Complexity:**
+//!   - `observe()`: O(S) where S is the number of successors per entry (4)
+//! - **Space Complexity:** O(T × S) where T is the correlation table size
+//! - **Hardware Cost:** High - large correlation table, tag compare per lookup
+//! - **Best Case:** Repeated irregular traversals (linked structures, graphs)
+//! - **Worst Case:** Never-repeating miss streams (streaming over new data)
+
+use super::Prefetcher;
+
+/// Number of successor slots tracked per correlation table entry.
+const MARKOV_SUCCESSORS: usize = 4;
+
+/// Maximum value of the per-successor frequency counter.
+const MARKOV_COUNTER_MAX: u8 = 3;
+
+/// Entry in the correlation table.
+#[derive(Default, Clone, Copy)]
+struct MarkovEntry {
+    /// Line address of the miss this entry describes.
+    tag: u64,
+    /// Indicates if this entry contains valid data.
+    valid: bool,
+    /// Line addresses that followed this miss.
+    successors: [u64; MARKOV_SUCCESSORS],
+    /// Saturating frequency counters for each successor (0 = empty slot).
+    counts: [u8; MARKOV_SUCCESSORS],
+}
+
+impl MarkovEntry {
+    /// Records `next` as a successor, strengthening it if already present.
+    ///
+    /// A new successor replaces the slot with the lowest frequency count.
+    fn record(&mut self, next: u64) {
+        if let Some(i) =
+            (0..MARKOV_SUCCESSORS).find(|&i| self.counts[i] > 0 && self.successors[i] == next)
+        {
+            if self.counts[i] < MARKOV_COUNTER_MAX {
+                self.counts[i] += 1;
+            }
+            return;
+        }
+
+        let mut victim = 0;
+        for i in 1..MARKOV_SUCCESSORS {
+            if self.counts[i] < self.counts[victim] {
+                victim = i;
+            }
+        }
+        self.successors[victim] = next;
+        self.counts[victim] = 1;
+    }
+}
+
+/// Markov Prefetcher state.
+#[derive(Clone)]
+pub struct MarkovPrefetcher {
+    /// Correlation table indexed by miss line address.
+    table: Vec<MarkovEntry>,
+    /// Mask used to index the table.
+    table_mask: usize,
+    /// Size of a cache line in bytes.
+    line_bytes: u64,
+    /// Maximum number of successors to prefetch per trigger.
+    degree: usize,
+    /// Line address of the previous miss, used to train the table.
+    last_miss: Option<u64>,
+}
+
+impl MarkovPrefetcher {
+    /// Creates a new Markov prefetcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `line_bytes` - The size of a cache line in bytes.
+    /// * `table_size` - Number of entries in the correlation table (must be power of 2).
+    /// * `degree` - The maximum number of successors to prefetch per miss.
+    pub fn new(line_bytes: usize, table_size: usize, degree: usize) -> Self {
+        let safe_size = if table_size > 0 && (table_size & (table_size - 1)) == 0 {
+            table_size
+        } else {
+            64
+        };
+
+        Self {
+            table: vec![MarkovEntry::default(); safe_size],
+            table_mask: safe_size - 1,
+            line_bytes: line_bytes as u64,
+            degree: degree.clamp(1, MARKOV_SUCCESSORS),
+            last_miss: None,
+        }
+    }
+
+    /// Calculates the correlation table index for a line address.
+    fn index(&self, line: u64) -> usize {
+        ((line / self.line_bytes) as usize) & self.table_mask
+    }
+}
+
+impl Prefetcher for MarkovPrefetcher {
+    /// Observes a memory access and generates prefetch candidates.
+    ///
+    /// Only misses train and trigger the prefetcher. Each miss is recorded
+    /// as a successor of the previous miss; if the missing line already has
+    /// an entry, its strongest successors are returned in order of frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The memory address being accessed.
+    /// * `hit` - Whether the access was a cache hit.
+    ///
+    /// # Returns
+    ///
+    /// A vector of addresses to prefetch.
+    fn observe(&mut self, addr: u64, hit: bool) -> Vec<u64> {
+        if hit {
+            return Vec::new();
+        }
+
+        let line = addr & !(self.line_bytes - 1);
+
+        if let Some(prev) = self.last_miss
+            && prev != line
+        {
+            let idx = self.index(prev);
+            let entry = &mut self.table[idx];
+            if !entry.valid || entry.tag != prev {
+                *entry = MarkovEntry {
+                    tag: prev,
+                    valid: true,
+                    ..MarkovEntry::default()
+                };
+            }
+            entry.record(line);
+        }
+        self.last_miss = Some(line);
+
+        let entry = &self.table[self.index(line)];
+        if !entry.valid || entry.tag != line {
+            return Vec::new();
+        }
+
+        let mut slots: Vec<usize> = (0..MARKOV_SUCCESSORS)
+            .filter(|&i| entry.counts[i] > 0)
+            .collect();
+        slots.sort_by(|&a, &b| entry.counts[b].cmp(&entry.counts[a]));
+        slots
+            .into_iter()
+            .take(self.degree)
+            .map(|i| entry.successors[i])
+            .collect()
+    }
+
+    fn clone_box(&self) -> Box<dyn Prefetcher + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
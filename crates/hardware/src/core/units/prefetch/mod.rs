@@ -3,6 +3,9 @@
 //! This module contains the interface and implementations for various
 //! hardware prefetchers used to hide memory latency.
 
+/// Markov prefetcher (correlates successive miss addresses).
+pub mod markov;
+
 /// Next-line prefetcher (prefetches sequential cache lines).
 pub mod next_line;
 
@@ -15,6 +18,7 @@ pub mod stride;
 /// Tagged prefetcher (prefetches on demand misses and prefetch hits).
 pub mod tagged;
 
+pub use self::markov::MarkovPrefetcher;
 pub use self::next_line::NextLinePrefetcher;
 pub use self::stream::StreamPrefetcher;
 pub use self::stride::StridePrefetcher;
@@ -39,4 +43,7 @@ pub trait Prefetcher: Send + Sync {
     ///
     /// A vector of addresses to prefetch. Empty if no prefetches are needed.
     fn observe(&mut self, addr: u64, hit: bool) -> Vec<u64>;
+
+    /// Returns a boxed copy of this prefetcher, including its training state.
+    fn clone_box(&self) -> Box<dyn Prefetcher + Send + Sync>;
 }
@@ -7,6 +7,7 @@
 use super::Prefetcher;
 
 /// Next-Line Prefetcher state.
+#[derive(Clone)]
 pub struct NextLinePrefetcher {
     /// Size of a cache line in bytes.
     line_bytes: u64,
@@ -53,4 +54,8 @@ impl Prefetcher for NextLinePrefetcher {
         }
         prefetches
     }
+
+    fn clone_box(&self) -> Box<dyn Prefetcher + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
@@ -22,6 +22,7 @@ enum Direction {
 }
 
 /// Stream Prefetcher state.
+#[derive(Clone)]
 pub struct StreamPrefetcher {
     /// Size of a cache line in bytes.
     line_bytes: u64,
@@ -112,4 +113,8 @@ impl Prefetcher for StreamPrefetcher {
         self.last_addr = addr;
         prefetches
     }
+
+    fn clone_box(&self) -> Box<dyn Prefetcher + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
@@ -31,6 +31,7 @@ struct StreamEntry {
 }
 
 /// Stride Prefetcher state.
+#[derive(Clone)]
 pub struct StridePrefetcher {
     /// Reference Prediction Table.
     table: Vec<StreamEntry>,
@@ -109,4 +110,8 @@ impl Prefetcher for StridePrefetcher {
         entry.last_addr = addr;
         prefetches
     }
+
+    fn clone_box(&self) -> Box<dyn Prefetcher + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
@@ -12,6 +12,7 @@
 use super::Prefetcher;
 
 /// Tagged Prefetcher state.
+#[derive(Clone)]
 pub struct TaggedPrefetcher {
     /// Size of a cache line in bytes.
     line_bytes: u64,
@@ -84,4 +85,8 @@ impl Prefetcher for TaggedPrefetcher {
 
         prefetches
     }
+
+    fn clone_box(&self) -> Box<dyn Prefetcher + Send + Sync> {
+        Box::new(self.clone())
+    }
 }
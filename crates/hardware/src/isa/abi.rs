@@ -9,11 +9,17 @@ pub const REG_ZERO: usize = 0;
 pub const REG_RA: usize = 1;
 /// Register x2 (stack pointer, sp).
 pub const REG_SP: usize = 2;
+/// Register x5 (alternate link register, t0).
+pub const REG_T0: usize = 5;
+/// Register x8 (frame pointer, s0/fp).
+pub const REG_FP: usize = 8;
 /// Register x10 (first argument/return value, a0).
 pub const REG_A0: usize = 10;
 /// Register x11 (second argument, a1).
 pub const REG_A1: usize = 11;
 /// Register x12 (third argument, a2).
 pub const REG_A2: usize = 12;
+/// Register x16 (SBI function ID, a6).
+pub const REG_A6: usize = 16;
 /// Register x17 (system call number, a7).
 pub const REG_A7: usize = 17;
@@ -8,6 +8,7 @@
 use crate::isa::instruction::{Decoded, InstructionBits};
 use crate::isa::rv64f::opcodes as fp_opcodes;
 use crate::isa::rv64i::opcodes;
+use crate::isa::rvv::opcodes as v_opcodes;
 
 /// Total width of a RISC-V instruction in bits.
 const INSTRUCTION_WIDTH: u32 = 32;
@@ -155,6 +156,7 @@ pub fn decode(inst: u32) -> Decoded {
         opcodes::OP_BRANCH => decode_b_type_imm(inst),
         opcodes::OP_LUI | opcodes::OP_AUIPC => decode_u_type_imm(inst),
         opcodes::OP_JAL => decode_j_type_imm(inst),
+        v_opcodes::OP_V => decode_vtype_imm(inst),
 
         _ => 0,
     };
@@ -232,6 +234,14 @@ fn decode_j_type_imm(inst: u32) -> i64 {
     sign_extend(combined, J_IMM_BITS)
 }
 
+/// Decodes the `zimm[10:0]` vtype immediate of a vector instruction.
+///
+/// vsetvli format: `0 | zimm[10:0] | rs1 | 111 | rd | opcode`
+/// Meaningful only for `vsetvli`; the field is zero-extended.
+fn decode_vtype_imm(inst: u32) -> i64 {
+    ((inst >> v_opcodes::VSETVLI_ZIMM_SHIFT) & v_opcodes::VSETVLI_ZIMM_MASK) as i64
+}
+
 /// Sign extends a value of `bits` width to a 64-bit signed integer.
 ///
 /// # Arguments
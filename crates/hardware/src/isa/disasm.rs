@@ -8,9 +8,11 @@
 //! - RV64I (base integer)
 //! - RV64M (multiply/divide)
 //! - RV64A (atomic)
+//! - Zba / Zbb (bit manipulation)
 //! - RV64F (single-precision float)
 //! - RV64D (double-precision float)
 //! - Privileged (ECALL, EBREAK, xRET, CSR, FENCE, WFI)
+//! - RVV subset (vsetvli, vsetvl, vadd.vv, vsub.vv)
 //!
 //! # Usage
 //!
@@ -27,7 +29,9 @@ use crate::isa::rv64d::funct7 as d_f7;
 use crate::isa::rv64f::{funct3 as f_f3, funct7 as f_f7, opcodes as f_op};
 use crate::isa::rv64i::{funct3 as i_f3, funct7 as i_f7, opcodes as i_op};
 use crate::isa::rv64m::{funct3 as m_f3, opcodes as m_op};
+use crate::isa::rvb::{funct3 as b_f3, funct7 as b_f7, imm as b_imm};
 use crate::isa::rvc;
+use crate::isa::rvv::{funct3 as v_f3, funct6 as v_f6, opcodes as v_op};
 
 /// ABI register names for x0–x31.
 const REG_NAMES: [&str; 32] = [
@@ -244,6 +248,9 @@ pub fn disassemble(inst: u32) -> String {
 
         sys_op::OP_SYSTEM => disasm_system(inst, rd, rs1, f3),
 
+        // ── Vector ────────────────────────────────────────
+        v_op::OP_V => disasm_vector(inst, rd, rs1, rs2, f3, f7),
+
         _ => format!("unknown ({inst:#010x})"),
     }
 }
@@ -268,6 +275,30 @@ fn disasm_op_reg(rd: usize, rs1: usize, rs2: usize, f3: u32, f7: u32, is_w: bool
         return format!("{mn}{suffix} {}, {}, {}", xreg(rd), xreg(rs1), xreg(rs2));
     }
 
+    // Zba / Zbb
+    let bitmanip = match (f7, f3, is_w) {
+        (b_f7::NEGATE, b_f3::ANDN, false) => Some("andn"),
+        (b_f7::NEGATE, b_f3::ORN, false) => Some("orn"),
+        (b_f7::NEGATE, b_f3::XNOR, false) => Some("xnor"),
+        (b_f7::MINMAX, b_f3::MIN, false) => Some("min"),
+        (b_f7::MINMAX, b_f3::MINU, false) => Some("minu"),
+        (b_f7::MINMAX, b_f3::MAX, false) => Some("max"),
+        (b_f7::MINMAX, b_f3::MAXU, false) => Some("maxu"),
+        (b_f7::ROTATE, b_f3::ROL, _) => Some(if is_w { "rolw" } else { "rol" }),
+        (b_f7::ROTATE, b_f3::ROR, _) => Some(if is_w { "rorw" } else { "ror" }),
+        (b_f7::SHADD, b_f3::SH1ADD, _) => Some(if is_w { "sh1add.uw" } else { "sh1add" }),
+        (b_f7::SHADD, b_f3::SH2ADD, _) => Some(if is_w { "sh2add.uw" } else { "sh2add" }),
+        (b_f7::SHADD, b_f3::SH3ADD, _) => Some(if is_w { "sh3add.uw" } else { "sh3add" }),
+        (b_f7::ADD_UW, b_f3::ADD_UW, true) => Some("add.uw"),
+        (b_f7::ADD_UW, b_f3::ZEXT_H, true) if rs2 == 0 => {
+            return format!("zext.h {}, {}", xreg(rd), xreg(rs1));
+        }
+        _ => None,
+    };
+    if let Some(mn) = bitmanip {
+        return format!("{mn} {}, {}, {}", xreg(rd), xreg(rs1), xreg(rs2));
+    }
+
     let mn = match (f3, f7) {
         (i_f3::ADD_SUB, i_f7::DEFAULT) => "add",
         (i_f3::ADD_SUB, i_f7::SUB) => "sub",
@@ -288,6 +319,9 @@ fn disasm_op_reg(rd: usize, rs1: usize, rs2: usize, f3: u32, f7: u32, is_w: bool
 fn disasm_op_imm(rd: usize, rs1: usize, f3: u32, imm: i64, is_w: bool) -> String {
     let suffix = if is_w { "w" } else { "" };
     let shamt = imm & 0x3F;
+    if let Some(text) = disasm_bitmanip_imm(rd, rs1, f3, imm as u32 & 0xFFF, is_w) {
+        return text;
+    }
     let mn = match f3 {
         i_f3::ADD_SUB => "addi",
         i_f3::SLT => "slti",
@@ -305,6 +339,38 @@ fn disasm_op_imm(rd: usize, rs1: usize, f3: u32, imm: i64, is_w: bool) -> String
     format!("{mn}{suffix} {}, {}, {imm}", xreg(rd), xreg(rs1))
 }
 
+/// Disassemble the Zbb unary and Zba/Zbb immediate forms, if `imm12`
+/// encodes one.
+fn disasm_bitmanip_imm(rd: usize, rs1: usize, f3: u32, imm12: u32, is_w: bool) -> Option<String> {
+    let f7 = imm12 >> 5;
+    let funct6 = imm12 >> 6;
+    let shamt = imm12 & 0x3F;
+    let (d, s) = (xreg(rd), xreg(rs1));
+    let text = match f3 {
+        i_f3::SLL if f7 == b_f7::ROTATE => {
+            let mn = match ((imm12 & 0x1F) as usize, is_w) {
+                (b_imm::CLZ, _) => "clz",
+                (b_imm::CTZ, _) => "ctz",
+                (b_imm::CPOP, _) => "cpop",
+                (b_imm::SEXT_B, false) => "sext.b",
+                (b_imm::SEXT_H, false) => "sext.h",
+                _ => return None,
+            };
+            let suffix = if is_w { "w" } else { "" };
+            format!("{mn}{suffix} {d}, {s}")
+        }
+        i_f3::SLL if is_w && funct6 == b_imm::SLLI_UW => format!("slli.uw {d}, {s}, {shamt}"),
+        i_f3::SRL_SRA if !is_w && imm12 == b_imm::REV8 => format!("rev8 {d}, {s}"),
+        i_f3::SRL_SRA if !is_w && imm12 == b_imm::ORC_B => format!("orc.b {d}, {s}"),
+        i_f3::SRL_SRA if !is_w && funct6 == b_imm::RORI => format!("rori {d}, {s}, {shamt}"),
+        i_f3::SRL_SRA if is_w && f7 == b_f7::ROTATE => {
+            format!("roriw {d}, {s}, {}", shamt & 0x1F)
+        }
+        _ => return None,
+    };
+    Some(text)
+}
+
 /// Disassemble OP_FP (floating-point arithmetic).
 fn disasm_op_fp(inst: u32, rd: usize, rs1: usize, rs2: usize, f3: u32, f7: u32) -> String {
     // Determine precision from format bits (bits 26:25 of funct7)
@@ -426,6 +492,51 @@ fn disasm_amo(rd: usize, rs1: usize, rs2: usize, f3: u32, f7: u32) -> String {
     )
 }
 
+/// Disassemble vector instructions (supported subset only).
+fn disasm_vector(inst: u32, rd: usize, rs1: usize, rs2: usize, f3: u32, f7: u32) -> String {
+    match f3 {
+        v_f3::OPCFG if inst & v_op::VSETVLI_SELECT_BIT == 0 => {
+            let zimm = (inst >> v_op::VSETVLI_ZIMM_SHIFT) & v_op::VSETVLI_ZIMM_MASK;
+            format!("vsetvli {}, {}, {}", xreg(rd), xreg(rs1), vtype_str(zimm))
+        }
+        v_f3::OPCFG if f7 == v_op::VSETVL => {
+            format!("vsetvl {}, {}, {}", xreg(rd), xreg(rs1), xreg(rs2))
+        }
+        v_f3::OPIVV => {
+            let mn = match (inst >> v_op::FUNCT6_SHIFT) & v_op::FUNCT6_MASK {
+                v_f6::VADD => "vadd.vv",
+                v_f6::VSUB => "vsub.vv",
+                _ => return format!("vector?? ({inst:#010x})"),
+            };
+            let mask = if inst & v_op::VM_BIT == 0 {
+                ", v0.t"
+            } else {
+                ""
+            };
+            format!("{mn} v{rd}, v{rs2}, v{rs1}{mask}")
+        }
+        _ => format!("vector?? ({inst:#010x})"),
+    }
+}
+
+/// Formats a vtype immediate as `e<sew>, m<lmul>, t<a|u>, m<a|u>`.
+fn vtype_str(vtype: u32) -> String {
+    let sew = 8u32 << ((vtype >> 3) & 0x7);
+    let lmul = match vtype & 0x7 {
+        0 => "m1",
+        1 => "m2",
+        2 => "m4",
+        3 => "m8",
+        5 => "mf8",
+        6 => "mf4",
+        7 => "mf2",
+        _ => "m??",
+    };
+    let ta = if vtype & (1 << 6) != 0 { "ta" } else { "tu" };
+    let ma = if vtype & (1 << 7) != 0 { "ma" } else { "mu" };
+    format!("e{sew}, {lmul}, {ta}, {ma}")
+}
+
 /// Disassemble system instructions.
 fn disasm_system(inst: u32, rd: usize, rs1: usize, f3: u32) -> String {
     // Fixed-encoding system instructions
@@ -10,7 +10,9 @@
 //! * `rv64a`: Standard Extension for Atomic Instructions.
 //! * `rv64f`: Standard Extension for Single-Precision Floating-Point.
 //! * `rv64d`: Standard Extension for Double-Precision Floating-Point.
+//! * `rvb`: Bit-Manipulation Extension (Zba and Zbb).
 //! * `rvc`: Standard Extension for Compressed Instructions.
+//! * `rvv`: Vector Extension (configuration and integer add/subtract subset).
 //! * `privileged`: Privileged Architecture (CSRs, Traps).
 
 /// Application Binary Interface (ABI) register name mappings.
@@ -22,6 +24,9 @@ pub mod decode;
 /// Instruction disassembler for debug tracing and diagnostics.
 pub mod disasm;
 
+/// Renders one instruction encoding (32-bit or compressed) as assembly text.
+pub use disasm::disassemble as disasm;
+
 /// Instruction encoding structures and bit extraction utilities.
 pub mod instruction;
 
@@ -43,5 +48,11 @@ pub mod rv64i;
 /// Integer multiply/divide extension (MUL, DIV, REM instructions).
 pub mod rv64m;
 
+/// Bit-manipulation extension (Zba address generation, Zbb basic bit ops).
+pub mod rvb;
+
 /// Compressed instruction extension (16-bit instruction encoding).
 pub mod rvc;
+
+/// Vector extension subset (vsetvl family, vadd.vv, vsub.vv).
+pub mod rvv;
@@ -70,3 +70,17 @@ pub mod exception {
     /// Hardware error (18) - Reserved in standard, often used for bus errors.
     pub const HARDWARE_ERROR: u64 = 18;
 }
+
+/// Returns `true` if `cause` is a trap cause this hart can report, i.e. a
+/// legal value for `mcause`/`scause`. Interrupts are limited to the M- and
+/// S-level ones in `MIE_IMPLEMENTED`.
+pub fn is_implemented(cause: u64) -> bool {
+    use crate::common::constants::CAUSE_INTERRUPT_BIT;
+
+    let code = cause & !CAUSE_INTERRUPT_BIT;
+    if cause & CAUSE_INTERRUPT_BIT != 0 {
+        matches!(code, 1 | 3 | 5 | 7 | 9 | 11)
+    } else {
+        matches!(code, 0..=9 | 11..=13 | 15 | 18)
+    }
+}
@@ -0,0 +1,45 @@
+//! RISC-V Bit-Manipulation Extension (B) Function Codes (funct3).
+//!
+//! Codes are only meaningful together with the matching `funct7` group.
+
+/// `add.uw` (with `funct7::ADD_UW`).
+pub const ADD_UW: u32 = 0b000;
+
+/// `zext.h` (with `funct7::ADD_UW`, `OP_REG_32`, `rs2 = 0`).
+pub const ZEXT_H: u32 = 0b100;
+
+/// `sh1add` / `sh1add.uw` (with `funct7::SHADD`).
+pub const SH1ADD: u32 = 0b010;
+
+/// `sh2add` / `sh2add.uw` (with `funct7::SHADD`).
+pub const SH2ADD: u32 = 0b100;
+
+/// `sh3add` / `sh3add.uw` (with `funct7::SHADD`).
+pub const SH3ADD: u32 = 0b110;
+
+/// `min` (with `funct7::MINMAX`).
+pub const MIN: u32 = 0b100;
+
+/// `minu` (with `funct7::MINMAX`).
+pub const MINU: u32 = 0b101;
+
+/// `max` (with `funct7::MINMAX`).
+pub const MAX: u32 = 0b110;
+
+/// `maxu` (with `funct7::MINMAX`).
+pub const MAXU: u32 = 0b111;
+
+/// `rol` / `rolw` (with `funct7::ROTATE`); also the unary count group.
+pub const ROL: u32 = 0b001;
+
+/// `ror` / `rorw` / `rori` / `roriw` (with `funct7::ROTATE`).
+pub const ROR: u32 = 0b101;
+
+/// `xnor` (with `funct7::NEGATE`).
+pub const XNOR: u32 = 0b100;
+
+/// `orn` (with `funct7::NEGATE`).
+pub const ORN: u32 = 0b110;
+
+/// `andn` (with `funct7::NEGATE`).
+pub const ANDN: u32 = 0b111;
@@ -0,0 +1,18 @@
+//! RISC-V Bit-Manipulation Extension (B) Function Codes (funct7).
+//!
+//! The `funct7` field (bits 31-25) selects the B instruction group.
+
+/// Logic with negated operand (`andn`, `orn`, `xnor`); shares SUB's encoding.
+pub const NEGATE: u32 = 0b0100000;
+
+/// Integer minimum/maximum (`min`, `minu`, `max`, `maxu`).
+pub const MINMAX: u32 = 0b0000101;
+
+/// Rotates (`rol`, `ror`, `roriw`) and the unary count/extend group.
+pub const ROTATE: u32 = 0b0110000;
+
+/// Shift-and-add (`sh1add`, `sh2add`, `sh3add` and `.uw` forms).
+pub const SHADD: u32 = 0b0010000;
+
+/// `add.uw` and `zext.h` (on `OP_REG_32`).
+pub const ADD_UW: u32 = 0b0000100;
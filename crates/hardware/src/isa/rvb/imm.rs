@@ -0,0 +1,36 @@
+//! RISC-V Bit-Manipulation Extension (B) Immediate Encodings.
+//!
+//! Unary Zbb instructions live in the `OP_IMM`/`OP_IMM_32` space with
+//! `funct7::ROTATE` and select the operation through the `rs2` field.
+//! `rev8`, `orc.b` are identified by their whole 12-bit immediate, and
+//! `rori`/`slli.uw` by the upper six bits (`funct6`) of the immediate.
+
+/// `clz` / `clzw` (`rs2` selector).
+pub const CLZ: usize = 0b00000;
+
+/// `ctz` / `ctzw` (`rs2` selector).
+pub const CTZ: usize = 0b00001;
+
+/// `cpop` / `cpopw` (`rs2` selector).
+pub const CPOP: usize = 0b00010;
+
+/// `sext.b` (`rs2` selector).
+pub const SEXT_B: usize = 0b00100;
+
+/// `sext.h` (`rs2` selector).
+pub const SEXT_H: usize = 0b00101;
+
+/// Full 12-bit immediate of `rev8` on RV64.
+pub const REV8: u32 = 0x6B8;
+
+/// Full 12-bit immediate of `orc.b`.
+pub const ORC_B: u32 = 0x287;
+
+/// Shift of the `funct6` field in an instruction (bits 31-26).
+pub const FUNCT6_SHIFT: u32 = 26;
+
+/// `funct6` of `rori` (6-bit shift amount).
+pub const RORI: u32 = 0b011000;
+
+/// `funct6` of `slli.uw`.
+pub const SLLI_UW: u32 = 0b000010;
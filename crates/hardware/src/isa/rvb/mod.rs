@@ -0,0 +1,23 @@
+//! RISC-V Bit-Manipulation Extension (B = Zba + Zbb).
+//!
+//! Zba adds address-generation instructions (`sh[123]add`, `add.uw` and
+//! their `.uw` forms) and Zbb adds basic bit manipulation (logic-with-negate,
+//! counts, min/max, sign/zero extension, rotates, `rev8`, `orc.b`). They
+//! share the `OP_REG`, `OP_REG_32`, `OP_IMM` and `OP_IMM_32` opcodes with
+//! the base ISA and are distinguished by `funct7` (or the upper immediate
+//! bits for the immediate forms).
+//!
+//! # Structure
+//!
+//! - `funct3`: Function codes for register-register B instructions.
+//! - `funct7`: Function codes selecting the B instruction groups.
+//! - `imm`: Immediate-field encodings of the unary and immediate forms.
+
+/// Function code 3 definitions for bit-manipulation operations.
+pub mod funct3;
+
+/// Function code 7 definitions for bit-manipulation operation groups.
+pub mod funct7;
+
+/// Immediate-field encodings for unary and immediate bit-manipulation forms.
+pub mod imm;
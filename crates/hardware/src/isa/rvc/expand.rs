@@ -9,6 +9,13 @@ use crate::isa::rv64f::opcodes as fp_opcodes;
 use crate::isa::rv64i::{funct3, funct7, opcodes};
 
 /// Expands a 16-bit RVC instruction into its 32-bit equivalent.
+///
+/// Returns 0 for reserved encodings, which the caller raises as
+/// `IllegalInstruction`: C.ADDI4SPN with a zero immediate, C.ADDIW,
+/// C.LWSP or C.LDSP with rd = x0, C.LUI/C.ADDI16SP with a zero immediate,
+/// C.JR with rs1 = x0, and the unallocated funct3/funct2 slots. HINT
+/// encodings (rd = x0 for C.LI, C.LUI, C.SLLI, C.MV, C.ADD, and so on)
+/// expand normally and write nothing.
 pub fn expand(inst: u16) -> u32 {
     let op = inst & 0x3;
     let funct3 = (inst >> 13) & 0x7;
@@ -301,11 +308,9 @@ pub fn expand(inst: u16) -> u32 {
 
         QUADRANT_2 => match funct3 {
             q2::C_SLLI => {
+                // rd = x0 is a HINT, not reserved.
                 let imm = ((inst >> 2) & 0x1F) | ((inst >> 12) & 1) << 5;
                 let rd = ((inst >> 7) & 0x1F) as u32;
-                if rd == 0 {
-                    return 0;
-                }
                 (imm as u32) << 20 | (rd << 15) | (funct3::SLL << 12) | (rd << 7) | opcodes::OP_IMM
             }
             q2::C_FLDSP => {
@@ -0,0 +1,9 @@
+//! RISC-V Vector Extension (V) Function Codes (funct3).
+//!
+//! The `funct3` field in `OP_V` instructions selects the operand category.
+
+/// Integer vector-vector operation (`.vv`).
+pub const OPIVV: u32 = 0b000;
+
+/// Configuration-setting instruction (`vsetvli`, `vsetvl`).
+pub const OPCFG: u32 = 0b111;
@@ -0,0 +1,9 @@
+//! RISC-V Vector Extension (V) Function Codes (funct6).
+//!
+//! The `funct6` field (bits 31-26) specifies the vector arithmetic operation.
+
+/// Vector integer add.
+pub const VADD: u32 = 0b000000;
+
+/// Vector integer subtract.
+pub const VSUB: u32 = 0b000010;
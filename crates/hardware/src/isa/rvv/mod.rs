@@ -0,0 +1,22 @@
+//! RISC-V Vector Extension (V), "lite" subset.
+//!
+//! Defines constants for the vector configuration instructions (`vsetvli`,
+//! `vsetvl`) and the integer vector-vector arithmetic that the simulator
+//! implements (`vadd.vv`, `vsub.vv`). All vector instructions share the
+//! `OP_V` major opcode and are told apart by `funct3` (operand category)
+//! and `funct6` (operation).
+//!
+//! # Structure
+//!
+//! - `opcodes`: The `OP_V` opcode and configuration-instruction selectors.
+//! - `funct3`: Operand categories (vector-vector, configuration).
+//! - `funct6`: Arithmetic operation codes.
+
+/// Function code 3 definitions for vector operand categories.
+pub mod funct3;
+
+/// Function code 6 definitions for vector arithmetic operations.
+pub mod funct6;
+
+/// Vector extension opcodes.
+pub mod opcodes;
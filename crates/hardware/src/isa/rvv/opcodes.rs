@@ -0,0 +1,26 @@
+//! RISC-V Vector Extension (V) Opcodes.
+
+/// Vector operation opcode (0b1010111).
+/// Used for vector arithmetic and the `vsetvl` family.
+pub const OP_V: u32 = 0b1010111;
+
+/// `funct7` value selecting `vsetvl` (vtype taken from `rs2`).
+pub const VSETVL: u32 = 0b1000000;
+
+/// Bit 31 of a configuration instruction; clear for `vsetvli`.
+pub const VSETVLI_SELECT_BIT: u32 = 1 << 31;
+
+/// Shift of the `zimm[10:0]` vtype immediate in `vsetvli` (bits 30-20).
+pub const VSETVLI_ZIMM_SHIFT: u32 = 20;
+
+/// Mask of the `zimm[10:0]` vtype immediate in `vsetvli`.
+pub const VSETVLI_ZIMM_MASK: u32 = 0x7FF;
+
+/// Bit 25 (`vm`) of a vector arithmetic instruction; set when unmasked.
+pub const VM_BIT: u32 = 1 << 25;
+
+/// Shift of the `funct6` field (bits 31-26).
+pub const FUNCT6_SHIFT: u32 = 26;
+
+/// Mask of the `funct6` field.
+pub const FUNCT6_MASK: u32 = 0x3F;
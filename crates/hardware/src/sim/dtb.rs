@@ -0,0 +1,148 @@
+//! Device Tree Generation.
+//!
+//! Builds a device tree blob describing the simulated machine so a kernel
+//! can boot without a hand-maintained `.dtb`. It performs the following:
+//! 1. **CPU:** One RV64 hart (`general.hartid`) with its local interrupt controller and the
+//!    timebase the CLINT counts at.
+//! 2. **Memory:** A single bank at `system.ram_base` of `memory.ram_size` bytes.
+//! 3. **Devices:** CLINT, PLIC, UART, VirtIO block, and Goldfish RTC at the
+//!    same addresses and interrupt numbers `System::new` wires up.
+
+use crate::config::{Config, defaults};
+use crate::sim::fdt::FdtWriter;
+
+/// Input clock of the UART, used by the kernel to derive divisor latches.
+const UART_CLOCK_FREQUENCY: u32 = 10_000_000;
+
+/// Phandle of the hart's local interrupt controller.
+const CPU_INTC_PHANDLE: u32 = 1;
+
+/// Phandle of the PLIC.
+const PLIC_PHANDLE: u32 = 2;
+
+/// Number of interrupt sources the PLIC exposes.
+const PLIC_NDEV: u32 = 0x35;
+
+/// Local interrupt numbers used in `interrupts-extended`.
+const IRQ_M_SOFT: u32 = 3;
+const IRQ_M_TIMER: u32 = 7;
+const IRQ_S_EXT: u32 = 9;
+const IRQ_M_EXT: u32 = 11;
+
+/// PLIC sources of the peripherals (see each device's `get_irq_id`).
+const UART_IRQ: u32 = 10;
+const VIRTIO_IRQ: u32 = 1;
+const RTC_IRQ: u32 = 11;
+
+/// MMIO region sizes (see each device's `address_range`).
+const CLINT_SIZE: u64 = 0x10000;
+const PLIC_SIZE: u64 = 0x400_0000;
+const UART_SIZE: u64 = 0x100;
+const VIRTIO_SIZE: u64 = 0x1000;
+const RTC_SIZE: u64 = 0x1000;
+
+/// Generates a device tree blob for the machine `config` describes.
+///
+/// `/chosen` carries boot arguments that mount the VirtIO disk as root and
+/// direct the console (and early console) to the UART.
+pub fn generate(config: &Config) -> Vec<u8> {
+    let sys = &config.system;
+    let mut w = FdtWriter::new();
+
+    w.begin_node("");
+    w.prop_u32("#address-cells", 2);
+    w.prop_u32("#size-cells", 2);
+    w.prop_str("compatible", "riscv-virtio");
+    w.prop_str("model", "riscv-virtio,rvsim");
+
+    w.begin_node("chosen");
+    w.prop_str(
+        "bootargs",
+        &format!(
+            "root=/dev/vda rw console=ttyS0 earlycon=uart8250,mmio,{:#x} rootwait",
+            sys.uart_base
+        ),
+    );
+    w.prop_str("stdout-path", &format!("/soc/uart@{:x}", sys.uart_base));
+    w.end_node();
+
+    w.begin_node("cpus");
+    w.prop_u32("#address-cells", 1);
+    w.prop_u32("#size-cells", 0);
+    w.prop_u32("timebase-frequency", sys.timebase_frequency as u32);
+    w.begin_node(&format!("cpu@{}", config.general.hartid));
+    w.prop_str("device_type", "cpu");
+    w.prop_u32("reg", config.general.hartid as u32);
+    w.prop_str("status", "okay");
+    w.prop_str("compatible", "riscv");
+    w.prop_str("riscv,isa", "rv64imafdc");
+    w.prop_str("mmu-type", "riscv,sv39");
+    w.begin_node("interrupt-controller");
+    w.prop_u32("#interrupt-cells", 1);
+    w.prop_empty("interrupt-controller");
+    w.prop_str("compatible", "riscv,cpu-intc");
+    w.prop_u32("phandle", CPU_INTC_PHANDLE);
+    w.end_node();
+    w.end_node();
+    w.end_node();
+
+    w.begin_node(&format!("memory@{:x}", sys.ram_base));
+    w.prop_str("device_type", "memory");
+    w.prop_reg(sys.ram_base, config.memory.ram_size as u64);
+    w.end_node();
+
+    w.begin_node("soc");
+    w.prop_u32("#address-cells", 2);
+    w.prop_u32("#size-cells", 2);
+    w.prop_str("compatible", "simple-bus");
+    w.prop_empty("ranges");
+
+    w.begin_node(&format!("clint@{:x}", sys.clint_base));
+    w.prop_str("compatible", "riscv,clint0");
+    w.prop_reg(sys.clint_base, CLINT_SIZE);
+    w.prop_cells(
+        "interrupts-extended",
+        &[CPU_INTC_PHANDLE, IRQ_M_SOFT, CPU_INTC_PHANDLE, IRQ_M_TIMER],
+    );
+    w.end_node();
+
+    w.begin_node(&format!("uart@{:x}", sys.uart_base));
+    w.prop_str("compatible", "ns16550a");
+    w.prop_reg(sys.uart_base, UART_SIZE);
+    w.prop_u32("clock-frequency", UART_CLOCK_FREQUENCY);
+    w.prop_u32("interrupt-parent", PLIC_PHANDLE);
+    w.prop_u32("interrupts", UART_IRQ);
+    w.prop_str("status", "okay");
+    w.end_node();
+
+    w.begin_node(&format!("virtio_mmio@{:x}", sys.disk_base));
+    w.prop_str("compatible", "virtio,mmio");
+    w.prop_reg(sys.disk_base, VIRTIO_SIZE);
+    w.prop_u32("interrupt-parent", PLIC_PHANDLE);
+    w.prop_u32("interrupts", VIRTIO_IRQ);
+    w.end_node();
+
+    w.begin_node(&format!("rtc@{:x}", sys.rtc_base));
+    w.prop_str("compatible", "google,goldfish-rtc");
+    w.prop_reg(sys.rtc_base, RTC_SIZE);
+    w.prop_u32("interrupt-parent", PLIC_PHANDLE);
+    w.prop_u32("interrupts", RTC_IRQ);
+    w.end_node();
+
+    w.begin_node(&format!("interrupt-controller@{:x}", defaults::PLIC_BASE));
+    w.prop_str("compatible", "riscv,plic0");
+    w.prop_reg(defaults::PLIC_BASE, PLIC_SIZE);
+    w.prop_u32("#interrupt-cells", 1);
+    w.prop_empty("interrupt-controller");
+    w.prop_cells(
+        "interrupts-extended",
+        &[CPU_INTC_PHANDLE, IRQ_M_EXT, CPU_INTC_PHANDLE, IRQ_S_EXT],
+    );
+    w.prop_u32("riscv,ndev", PLIC_NDEV);
+    w.prop_u32("phandle", PLIC_PHANDLE);
+    w.end_node();
+
+    w.end_node();
+    w.end_node();
+    w.finish(0)
+}
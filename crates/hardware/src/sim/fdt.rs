@@ -0,0 +1,362 @@
+//! Flattened Device Tree Patching.
+//!
+//! Edits properties of the `/chosen` node in a device tree blob (DTB) so the
+//! loader can hand boot-time information, such as the initrd location, to
+//! the kernel. It performs the following:
+//! 1. **Parsing:** Validates the FDT header and walks the structure block.
+//! 2. **Patching:** Replaces or adds properties in `/chosen`, creating the
+//!    node under the root if the blob has none.
+//! 3. **Re-encoding:** Rebuilds the blob with an updated strings block and
+//!    header, keeping the memory reservation map.
+//! 4. **Building:** `FdtWriter` emits a new blob node by node.
+
+/// FDT header magic.
+const FDT_MAGIC: u32 = 0xd00d_feed;
+/// Size of the version 17 FDT header in bytes.
+const FDT_HEADER_SIZE: usize = 40;
+/// Start of a node; followed by its NUL-terminated name.
+const FDT_BEGIN_NODE: u32 = 1;
+/// End of a node.
+const FDT_END_NODE: u32 = 2;
+/// A property: value length, name offset, then the value.
+const FDT_PROP: u32 = 3;
+/// No-op token.
+const FDT_NOP: u32 = 4;
+/// End of the structure block.
+const FDT_END: u32 = 9;
+
+/// Reads a big-endian `u32` at `offset`, if in bounds.
+fn read_be32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// Appends `bytes` and pads the buffer to a 4-byte boundary.
+fn push_padded(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(bytes);
+    out.resize(out.len().next_multiple_of(4), 0);
+}
+
+/// Returns the offset of `name` in the strings block, appending it if absent.
+fn string_offset(strings: &mut Vec<u8>, name: &str) -> u32 {
+    let mut offset = 0;
+    for entry in strings.split(|&b| b == 0) {
+        if entry == name.as_bytes() && offset + entry.len() < strings.len() {
+            return offset as u32;
+        }
+        offset += entry.len() + 1;
+    }
+    let offset = strings.len() as u32;
+    strings.extend_from_slice(name.as_bytes());
+    strings.push(0);
+    offset
+}
+
+/// Emits `FDT_PROP` tokens for `props`.
+fn push_props(out: &mut Vec<u8>, strings: &mut Vec<u8>, props: &[(&str, Vec<u8>)]) {
+    for (name, value) in props {
+        out.extend_from_slice(&FDT_PROP.to_be_bytes());
+        out.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        out.extend_from_slice(&string_offset(strings, name).to_be_bytes());
+        push_padded(out, value);
+    }
+}
+
+/// Sets properties of the `/chosen` node, replacing any with the same name.
+///
+/// Creates `/chosen` if the blob has none.
+///
+/// # Errors
+///
+/// Returns a message if `dtb` is not a well-formed flattened device tree.
+pub fn set_chosen_props(dtb: &[u8], props: &[(&str, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    let header = |i: usize| read_be32(dtb, i * 4).ok_or("truncated FDT header");
+    if header(0)? != FDT_MAGIC {
+        return Err("bad FDT magic".to_string());
+    }
+    let total_size = header(1)? as usize;
+    let struct_off = header(2)? as usize;
+    let strings_off = header(3)? as usize;
+    let rsvmap_off = header(4)? as usize;
+    let boot_cpuid = header(7)?;
+    let strings_size = header(8)? as usize;
+    if total_size > dtb.len() {
+        return Err("FDT larger than the blob".to_string());
+    }
+
+    let mut strings = dtb
+        .get(strings_off..strings_off + strings_size)
+        .ok_or("FDT strings block out of bounds")?
+        .to_vec();
+    let prop_name = |nameoff: u32| -> Option<&[u8]> {
+        let start = strings_off + nameoff as usize;
+        let len = dtb.get(start..)?.iter().position(|&b| b == 0)?;
+        Some(&dtb[start..start + len])
+    };
+
+    // Memory reservation map: 16-byte entries up to an all-zero terminator.
+    let mut rsvmap_end = rsvmap_off;
+    loop {
+        let entry = dtb
+            .get(rsvmap_end..rsvmap_end + 16)
+            .ok_or("FDT reservation map out of bounds")?;
+        rsvmap_end += 16;
+        if entry.iter().all(|&b| b == 0) {
+            break;
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut pos = struct_off;
+    let mut depth = 0usize;
+    let mut chosen_depth = None;
+    let mut found_chosen = false;
+    loop {
+        let token = read_be32(dtb, pos).ok_or("FDT structure block truncated")?;
+        let start = pos;
+        pos += 4;
+        match token {
+            FDT_BEGIN_NODE => {
+                let len = dtb[pos..]
+                    .iter()
+                    .position(|&b| b == 0)
+                    .ok_or("unterminated FDT node name")?;
+                let name = &dtb[pos..pos + len];
+                pos = (pos + len + 1).next_multiple_of(4);
+                depth += 1;
+                if depth == 2 && name == b"chosen" {
+                    chosen_depth = Some(depth);
+                    found_chosen = true;
+                }
+                out.extend_from_slice(&dtb[start..pos]);
+            }
+            FDT_END_NODE => {
+                if chosen_depth == Some(depth) {
+                    push_props(&mut out, &mut strings, props);
+                    chosen_depth = None;
+                } else if depth == 1 && !found_chosen {
+                    out.extend_from_slice(&FDT_BEGIN_NODE.to_be_bytes());
+                    push_padded(&mut out, b"chosen\0");
+                    push_props(&mut out, &mut strings, props);
+                    out.extend_from_slice(&FDT_END_NODE.to_be_bytes());
+                }
+                depth = depth.checked_sub(1).ok_or("unbalanced FDT nodes")?;
+                out.extend_from_slice(&dtb[start..pos]);
+            }
+            FDT_PROP => {
+                let len = read_be32(dtb, pos).ok_or("FDT property truncated")? as usize;
+                let nameoff = read_be32(dtb, pos + 4).ok_or("FDT property truncated")?;
+                pos = (pos + 8 + len).next_multiple_of(4);
+                if pos > dtb.len() {
+                    return Err("FDT property out of bounds".to_string());
+                }
+                let replaced = chosen_depth == Some(depth)
+                    && prop_name(nameoff)
+                        .is_some_and(|n| props.iter().any(|(name, _)| n == name.as_bytes()));
+                if !replaced {
+                    out.extend_from_slice(&dtb[start..pos]);
+                }
+            }
+            FDT_NOP => {}
+            FDT_END => {
+                out.extend_from_slice(&dtb[start..pos]);
+                break;
+            }
+            _ => return Err(format!("unknown FDT token {token:#x}")),
+        }
+    }
+    if depth != 0 {
+        return Err("unbalanced FDT nodes".to_string());
+    }
+
+    Ok(assemble(
+        &dtb[rsvmap_off..rsvmap_end],
+        &out,
+        &strings,
+        boot_cpuid,
+    ))
+}
+
+/// Encodes a version 17 blob: header, reservation map (including its
+/// terminator), structure block, then strings block.
+fn assemble(rsvmap: &[u8], structure: &[u8], strings: &[u8], boot_cpuid: u32) -> Vec<u8> {
+    let rsvmap_off = FDT_HEADER_SIZE.next_multiple_of(8);
+    let struct_off = rsvmap_off + rsvmap.len();
+    let strings_off = struct_off + structure.len();
+    let total = strings_off + strings.len();
+
+    let mut blob = Vec::with_capacity(total);
+    for field in [
+        FDT_MAGIC,
+        total as u32,
+        struct_off as u32,
+        strings_off as u32,
+        rsvmap_off as u32,
+        17,
+        16,
+        boot_cpuid,
+        strings.len() as u32,
+        structure.len() as u32,
+    ] {
+        blob.extend_from_slice(&field.to_be_bytes());
+    }
+    blob.resize(rsvmap_off, 0);
+    blob.extend_from_slice(rsvmap);
+    blob.extend_from_slice(structure);
+    blob.extend_from_slice(strings);
+    blob
+}
+
+/// Builds a flattened device tree node by node.
+///
+/// Nodes are opened and closed in order; properties apply to the most
+/// recently opened node. The root node is opened with the name `""`.
+#[derive(Debug, Default)]
+pub struct FdtWriter {
+    /// Structure block emitted so far.
+    structure: Vec<u8>,
+    /// Strings block of property names.
+    strings: Vec<u8>,
+}
+
+impl FdtWriter {
+    /// Creates an empty writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a node named `name` (e.g. `uart@10000000`).
+    pub fn begin_node(&mut self, name: &str) {
+        self.structure
+            .extend_from_slice(&FDT_BEGIN_NODE.to_be_bytes());
+        push_padded(&mut self.structure, name.as_bytes());
+        if name.len().is_multiple_of(4) {
+            // The name needs a NUL terminator even when already aligned.
+            self.structure.extend_from_slice(&[0; 4]);
+        }
+    }
+
+    /// Closes the most recently opened node.
+    pub fn end_node(&mut self) {
+        self.structure
+            .extend_from_slice(&FDT_END_NODE.to_be_bytes());
+    }
+
+    /// Adds a property with a raw value.
+    pub fn prop(&mut self, name: &str, value: &[u8]) {
+        push_props(
+            &mut self.structure,
+            &mut self.strings,
+            &[(name, value.to_vec())],
+        );
+    }
+
+    /// Adds a property with no value (e.g. `interrupt-controller`).
+    pub fn prop_empty(&mut self, name: &str) {
+        self.prop(name, &[]);
+    }
+
+    /// Adds a single-cell property.
+    pub fn prop_u32(&mut self, name: &str, value: u32) {
+        self.prop_cells(name, &[value]);
+    }
+
+    /// Adds a property of 32-bit cells.
+    pub fn prop_cells(&mut self, name: &str, cells: &[u32]) {
+        let value: Vec<u8> = cells.iter().flat_map(|c| c.to_be_bytes()).collect();
+        self.prop(name, &value);
+    }
+
+    /// Adds a `reg` of one 64-bit address and 64-bit size
+    /// (`#address-cells = <2>`, `#size-cells = <2>`).
+    pub fn prop_reg(&mut self, addr: u64, size: u64) {
+        self.prop_cells(
+            "reg",
+            &[
+                (addr >> 32) as u32,
+                addr as u32,
+                (size >> 32) as u32,
+                size as u32,
+            ],
+        );
+    }
+
+    /// Adds a NUL-terminated string property.
+    pub fn prop_str(&mut self, name: &str, value: &str) {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        self.prop(name, &bytes);
+    }
+
+    /// Ends the structure block and encodes the blob, with an empty
+    /// memory reservation map.
+    pub fn finish(mut self, boot_cpuid: u32) -> Vec<u8> {
+        self.structure.extend_from_slice(&FDT_END.to_be_bytes());
+        assemble(&[0; 16], &self.structure, &self.strings, boot_cpuid)
+    }
+}
+
+/// Points `/chosen` at an initrd occupying `start..end` by setting
+/// `linux,initrd-start` and `linux,initrd-end` (64-bit values).
+///
+/// # Errors
+///
+/// Returns a message if `dtb` is not a well-formed flattened device tree.
+pub fn set_initrd(dtb: &[u8], start: u64, end: u64) -> Result<Vec<u8>, String> {
+    set_chosen_props(
+        dtb,
+        &[
+            ("linux,initrd-start", start.to_be_bytes().to_vec()),
+            ("linux,initrd-end", end.to_be_bytes().to_vec()),
+        ],
+    )
+}
+
+/// Looks up a property of the `/chosen` node.
+///
+/// Returns `None` if the blob is malformed or the property is absent.
+pub fn chosen_prop<'a>(dtb: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    if read_be32(dtb, 0)? != FDT_MAGIC {
+        return None;
+    }
+    let struct_off = read_be32(dtb, 8)? as usize;
+    let strings_off = read_be32(dtb, 12)? as usize;
+    let mut pos = struct_off;
+    let mut depth = 0usize;
+    let mut chosen_depth = None;
+    loop {
+        let token = read_be32(dtb, pos)?;
+        pos += 4;
+        match token {
+            FDT_BEGIN_NODE => {
+                let len = dtb.get(pos..)?.iter().position(|&b| b == 0)?;
+                depth += 1;
+                if depth == 2 && &dtb[pos..pos + len] == b"chosen" {
+                    chosen_depth = Some(depth);
+                }
+                pos = (pos + len + 1).next_multiple_of(4);
+            }
+            FDT_END_NODE => {
+                if chosen_depth == Some(depth) {
+                    return None;
+                }
+                depth = depth.checked_sub(1)?;
+            }
+            FDT_PROP => {
+                let len = read_be32(dtb, pos)? as usize;
+                let nameoff = read_be32(dtb, pos + 4)? as usize;
+                let value = dtb.get(pos + 8..pos + 8 + len)?;
+                pos = (pos + 8 + len).next_multiple_of(4);
+                let name_start = strings_off + nameoff;
+                let name_len = dtb.get(name_start..)?.iter().position(|&b| b == 0)?;
+                if chosen_depth == Some(depth)
+                    && &dtb[name_start..name_start + name_len] == name.as_bytes()
+                {
+                    return Some(value);
+                }
+            }
+            FDT_NOP => {}
+            _ => return None,
+        }
+    }
+}
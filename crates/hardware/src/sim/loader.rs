@@ -3,6 +3,8 @@
 //! This module provides utilities for loading binaries and setting up the initial CPU state. It performs:
 //! 1. **Binary loading:** Reads kernel, firmware, or bare-metal binaries from disk into a byte buffer.
 //! 2. **Kernel boot:** Loads OpenSBI, kernel image, and DTB at fixed addresses and sets PC and privilege.
+//!    Without a DTB file, one describing the configured machine is generated.
+//!    An optional initrd is loaded at `initrd_offset` and recorded in the DTB's `/chosen` node.
 //! 3. **Bare-metal fallback:** When no OpenSBI is present, sets up MRET trampoline and MEPC for direct boot.
 
 use crate::config::Config;
@@ -11,6 +13,8 @@ use crate::core::arch::csr;
 use crate::core::arch::mode::PrivilegeMode;
 use crate::isa::abi;
 use crate::isa::privileged::opcodes as sys_ops;
+use crate::sim::symbols::SymbolTable;
+use crate::sim::{dtb, fdt};
 use crate::soc::interconnect::Bus;
 use object::{Object, ObjectSymbol};
 use std::fs;
@@ -44,25 +48,47 @@ pub fn load_binary(path: &str) -> Vec<u8> {
 /// * `cpu` - Mutable reference to the CPU state.
 /// * `config` - System configuration (RAM base, kernel offset).
 /// * `_disk_path` - Reserved for disk path; currently unused.
-/// * `dtb_path` - Optional path to the device tree blob; if provided, loaded at DTB address.
+/// * `dtb_path` - Optional path to the device tree blob to load at the DTB address. When `None`, one is
+///   generated from `config` by `dtb::generate`.
 /// * `kernel_path_override` - Optional kernel image path; overrides default `software/linux/output/Image`.
+/// * `initrd_path` - Optional initrd (initramfs) image, loaded at `ram_base + initrd_offset`. Its range is
+///   written to `linux,initrd-start`/`linux,initrd-end` in the DTB's `/chosen` node.
+///
+/// # Returns
+///
+/// The device tree blob placed in RAM, as loaded or generated and with any initrd recorded.
 pub fn setup_kernel_load(
     cpu: &mut Cpu,
     config: &Config,
     _disk_path: &str,
     dtb_path: Option<String>,
     kernel_path_override: Option<String>,
-) {
+    initrd_path: Option<String>,
+) -> Vec<u8> {
     let ram_base = config.system.ram_base;
 
     let opensbi_addr = ram_base;
     let kernel_addr = ram_base + 0x200000;
     let dtb_addr = ram_base + 0x2200000;
 
-    if let Some(path) = dtb_path {
-        let dtb_data = load_binary(&path);
-        cpu.bus.load_binary_at(&dtb_data, dtb_addr);
+    let initrd = initrd_path.map(|path| {
+        let data = load_binary(&path);
+        let start = ram_base + config.system.initrd_offset;
+        cpu.bus.load_binary_at(&data, start);
+        (start, start + data.len() as u64)
+    });
+
+    let (mut dtb_data, dtb_source) = match dtb_path {
+        Some(path) => (load_binary(&path), path),
+        None => (dtb::generate(config), "generated".to_string()),
+    };
+    if let Some((start, end)) = initrd {
+        match fdt::set_initrd(&dtb_data, start, end) {
+            Ok(patched) => dtb_data = patched,
+            Err(e) => eprintln!("[Loader] WARNING: cannot record initrd in DTB {dtb_source}: {e}"),
+        }
     }
+    cpu.bus.load_binary_at(&dtb_data, dtb_addr);
 
     let sbi_path = "software/linux/output/fw_jump.bin";
 
@@ -84,7 +110,7 @@ pub fn setup_kernel_load(
 
         cpu.pc = opensbi_addr;
         cpu.privilege = PrivilegeMode::Machine;
-        cpu.regs.write(abi::REG_A0, 0);
+        cpu.regs.write(abi::REG_A0, cpu.hartid);
         cpu.regs.write(abi::REG_A1, dtb_addr);
         cpu.regs.write(abi::REG_A2, 0);
     } else {
@@ -95,9 +121,10 @@ pub fn setup_kernel_load(
         cpu.pc = ram_base;
         cpu.privilege = PrivilegeMode::Machine;
         cpu.csr_write(csr::MEPC, load_addr);
-        cpu.regs.write(abi::REG_A0, 0);
+        cpu.regs.write(abi::REG_A0, cpu.hartid);
         cpu.regs.write(abi::REG_A1, dtb_addr);
     }
+    dtb_data
 }
 
 /// Result of loading an ELF file.
@@ -106,12 +133,15 @@ pub struct ElfLoadResult {
     pub entry: u64,
     /// Address of the `tohost` symbol, if present.
     pub tohost_addr: Option<u64>,
+    /// Function symbols, for symbolizing PCs.
+    pub symbols: SymbolTable,
 }
 
 /// Attempts to load an ELF file into memory via the bus.
 ///
 /// If the file starts with the ELF magic (`\x7fELF`), parses the ELF,
-/// loads all `PT_LOAD` segments, and extracts the `tohost` symbol address.
+/// loads all `PT_LOAD` segments, and extracts the `tohost` symbol address
+/// and the function symbols.
 /// Returns `None` if the data is not a valid ELF.
 pub fn try_load_elf(data: &[u8], bus: &mut Bus) -> Option<ElfLoadResult> {
     if data.len() < 4 || &data[..4] != b"\x7fELF" {
@@ -142,5 +172,9 @@ pub fn try_load_elf(data: &[u8], bus: &mut Bus) -> Option<ElfLoadResult> {
         .find(|s| s.name() == Ok("tohost"))
         .map(|s| s.address());
 
-    Some(ElfLoadResult { entry, tohost_addr })
+    Some(ElfLoadResult {
+        entry,
+        tohost_addr,
+        symbols: SymbolTable::from_elf(data),
+    })
 }
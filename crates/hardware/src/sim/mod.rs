@@ -1,8 +1,12 @@
 //! Simulation utilities, program loading, and the top-level `Simulator`.
 //!
 //! Provides utilities for loading binaries into memory, setting up
-//! the initial system state, and the `Simulator` struct that owns
-//! both the CPU and the pipeline.
+//! the initial system state, generating and patching the device tree
+//! handed to the kernel, resolving addresses to ELF symbols, and the
+//! `Simulator` struct that owns both the CPU and the pipeline.
 
+pub mod dtb;
+pub mod fdt;
 pub mod loader;
 pub mod simulator;
+pub mod symbols;
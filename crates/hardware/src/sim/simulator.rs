@@ -5,17 +5,37 @@
 
 use crate::config::Config;
 use crate::core::Cpu;
+use crate::core::cpu::checkpoint::Checkpoint;
 use crate::core::pipeline::backend::inorder::InOrderEngine;
 use crate::core::pipeline::engine::{Pipeline, PipelineDispatch};
 use crate::core::pipeline::frontend::Frontend;
 use crate::soc::System;
 
+/// Why the simulated machine stopped or restarted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The program exited with the given code.
+    Exit(u64),
+    /// The machine rebooted (e.g., SysCon reset) and is running again.
+    Reset,
+    /// An `EBREAK` at this PC halted the hart (`general.ebreak_halt`); it
+    /// resumes after the `EBREAK` on the next tick.
+    Halt(u64),
+}
+
+/// Re-runs the boot loader after a reboot.
+pub type BootFn = Box<dyn FnMut(&mut Cpu)>;
+
 /// Top-level simulator: CPU architectural state + pipeline.
 pub struct Simulator {
     /// CPU architectural state (registers, caches, MMU, bus, stats).
     pub cpu: Cpu,
     /// Pipeline implementation (frontend + backend engine).
     pub pipeline: PipelineDispatch,
+    /// Loader setup replayed after each reboot, if any.
+    boot: Option<BootFn>,
+    /// Set when a reboot happened and has not been reported yet.
+    reset_pending: bool,
 }
 
 unsafe impl Send for Simulator {}
@@ -26,26 +46,111 @@ impl Simulator {
     pub fn new(system: System, config: &Config) -> Self {
         let cpu = Cpu::new(system, config);
         let pipeline = PipelineDispatch::InOrder(Box::new(Pipeline {
-            frontend: Frontend::new(config.pipeline.width),
+            frontend: Frontend::new(config.pipeline.width, config.pipeline.fetch_queue_size),
             engine: InOrderEngine::new(config),
             rename_output: Vec::with_capacity(config.pipeline.width),
         }));
-        Self { cpu, pipeline }
+        Self {
+            cpu,
+            pipeline,
+            boot: None,
+            reset_pending: false,
+        }
+    }
+
+    /// Sets the loader setup replayed after a reboot.
+    ///
+    /// Without one, a reboot restarts the hart at its reset PC with memory
+    /// left as it was.
+    pub fn set_boot(&mut self, boot: impl FnMut(&mut Cpu) + 'static) {
+        self.boot = Some(Box::new(boot));
+    }
+
+    /// Reboots the machine: drains the pipeline, returns the CPU to its
+    /// power-on architectural state, and re-runs the boot loader.
+    pub fn reset(&mut self) {
+        self.pipeline.quiesce(&mut self.cpu);
+        self.cpu.reset();
+        if let Some(boot) = self.boot.as_mut() {
+            boot(&mut self.cpu);
+        }
+        self.reset_pending = true;
     }
 
     /// Advances the simulator by one clock cycle.
+    ///
+    /// A pending reboot request takes the whole cycle: the machine resets
+    /// and starts fetching from the boot PC on the next tick. A hart halted
+    /// by `EBREAK` does nothing until the halt is taken. The retired-
+    /// instruction logs are flushed once the program exits or halts.
     pub fn tick(&mut self) -> Result<(), String> {
+        if self.cpu.halted_at.is_some() {
+            return Ok(());
+        }
+        if self.cpu.bus.take_reset() {
+            self.reset();
+            return Ok(());
+        }
         let prev_priv = self.cpu.privilege;
         let skip = self.cpu.pre_tick()?;
         if !skip {
-            self.pipeline.tick(&mut self.cpu);
+            if !self.cpu.is_functional() {
+                self.pipeline.tick(&mut self.cpu);
+            } else {
+                // Fast-forward: drain anything left from detailed mode first.
+                if !self.pipeline.is_idle() {
+                    self.pipeline.quiesce(&mut self.cpu);
+                }
+                self.cpu.step_functional();
+            }
         }
         self.cpu.post_tick(prev_priv);
+        if self.cpu.exit_code.is_some() || self.cpu.halted_at.is_some() {
+            self.cpu.flush_logs();
+        }
         Ok(())
     }
 
+    /// Drains the pipeline and captures the architectural state.
+    ///
+    /// In-flight instructions are discarded and will be refetched, so the
+    /// simulation can continue after saving; only timing is perturbed.
+    pub fn save_checkpoint(&mut self) -> Checkpoint {
+        self.pipeline.quiesce(&mut self.cpu);
+        self.cpu.save_checkpoint()
+    }
+
+    /// Discards in-flight work and replaces the architectural state with
+    /// `ckpt`.
+    ///
+    /// # Returns
+    ///
+    /// An error if the checkpoint does not fit this system.
+    pub fn restore_checkpoint(&mut self, ckpt: &Checkpoint) -> Result<(), String> {
+        self.pipeline.quiesce(&mut self.cpu);
+        self.cpu.restore_checkpoint(ckpt)
+    }
+
     /// Retrieves the exit code if the simulation has finished.
     pub fn take_exit(&mut self) -> Option<u64> {
         self.cpu.take_exit()
     }
+
+    /// Retrieves the PC of the `EBREAK` the hart halted at, resuming it.
+    pub fn take_halt(&mut self) -> Option<u64> {
+        self.cpu.take_halt()
+    }
+
+    /// Retrieves the next outcome to report: a reboot that has happened
+    /// since the last call, an `EBREAK` halt (which resumes the hart), or
+    /// the exit code if the simulation has finished.
+    pub fn take_outcome(&mut self) -> Option<Outcome> {
+        if std::mem::take(&mut self.reset_pending) {
+            return Some(Outcome::Reset);
+        }
+        if let Some(pc) = self.take_halt() {
+            return Some(Outcome::Halt(pc));
+        }
+        self.take_exit().map(Outcome::Exit)
+    }
 }
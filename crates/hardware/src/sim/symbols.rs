@@ -0,0 +1,105 @@
+//! ELF Symbol Resolution.
+//!
+//! Maps code addresses back to the functions that contain them so traces,
+//! trap reports, and state dumps can show `<func+0x1c>` instead of a raw PC.
+//! It performs the following:
+//! 1. **Extraction:** Collects function symbols (and untyped labels in
+//!    executable sections, as hand-written assembly produces) from `.symtab`.
+//! 2. **Lookup:** Finds the nearest preceding symbol for an address by binary
+//!    search, respecting symbol sizes when the ELF records them.
+
+use object::{Object, ObjectSection, ObjectSymbol, SectionKind, SymbolKind, SymbolSection};
+
+/// A code symbol: start address, size in bytes (0 if unknown), and name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Symbol {
+    addr: u64,
+    size: u64,
+    name: String,
+}
+
+/// Sorted table of code symbols for address-to-name lookups.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolTable {
+    /// Symbols sorted by address.
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    /// Builds a table from `(name, address, size)` triples.
+    ///
+    /// A `size` of 0 means unknown: such a symbol covers everything up to
+    /// the next symbol.
+    pub fn new(symbols: impl IntoIterator<Item = (String, u64, u64)>) -> Self {
+        let mut symbols: Vec<Symbol> = symbols
+            .into_iter()
+            .map(|(name, addr, size)| Symbol { addr, size, name })
+            .collect();
+        symbols.sort_by(|a, b| a.addr.cmp(&b.addr).then(b.size.cmp(&a.size)));
+        // Keep one name per address, preferring the one with a known size.
+        symbols.dedup_by_key(|s| s.addr);
+        Self { symbols }
+    }
+
+    /// Extracts the code symbols from an ELF image.
+    ///
+    /// Returns an empty table if `data` is not an ELF or has no symbols.
+    pub fn from_elf(data: &[u8]) -> Self {
+        let Ok(file) = object::File::parse(data) else {
+            return Self::default();
+        };
+        let symbols = file.symbols().filter_map(|sym| {
+            let is_code = match sym.kind() {
+                SymbolKind::Text => true,
+                SymbolKind::Unknown | SymbolKind::Label => match sym.section() {
+                    SymbolSection::Section(index) => file
+                        .section_by_index(index)
+                        .is_ok_and(|s| s.kind() == SectionKind::Text),
+                    _ => false,
+                },
+                _ => false,
+            };
+            let name = sym.name().ok()?;
+            // Skip compiler-local labels and RISC-V mapping symbols.
+            if !is_code || name.is_empty() || name.starts_with(".L") || name.starts_with('$') {
+                return None;
+            }
+            Some((name.to_string(), sym.address(), sym.size()))
+        });
+        Self::new(symbols)
+    }
+
+    /// Returns the number of symbols.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns `true` if the table holds no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Resolves `addr` to the nearest preceding symbol and the offset into it.
+    ///
+    /// Returns `None` before the first symbol or past the end of a symbol
+    /// whose size is known.
+    pub fn resolve(&self, addr: u64) -> Option<(&str, u64)> {
+        let idx = self.symbols.partition_point(|s| s.addr <= addr);
+        let sym = self.symbols.get(idx.checked_sub(1)?)?;
+        let offset = addr - sym.addr;
+        if sym.size != 0 && offset >= sym.size {
+            return None;
+        }
+        Some((&sym.name, offset))
+    }
+
+    /// Formats `addr` as `<func+0x..>` (or `<func>` at offset 0), or an
+    /// empty string if it does not resolve.
+    pub fn describe(&self, addr: u64) -> String {
+        match self.resolve(addr) {
+            Some((name, 0)) => format!("<{name}>"),
+            Some((name, offset)) => format!("<{name}+{offset:#x}>"),
+            None => String::new(),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Bus Access Log.
+//!
+//! Records bus transactions for debugging device interactions (e.g. a driver
+//! writing the wrong MMIO register). It provides:
+//! 1. **Recording:** Each read or write routed through the `Bus` is stored as
+//!    a `BusAccess` with the PC of the instruction that caused it.
+//! 2. **Bounded history:** Entries are kept in a ring buffer; once full, the
+//!    oldest entry is dropped for each new one.
+//! 3. **Filtering:** An optional physical address range limits recording to
+//!    the region of interest.
+//!
+//! RAM accesses served through the CPU's direct RAM pointer never reach the
+//! bus and are not recorded; MMIO and other device accesses always are.
+
+use std::collections::VecDeque;
+
+/// One recorded bus transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BusAccess {
+    /// PC of the instruction in the memory stage (or the store draining
+    /// from the store buffer) when the access was made.
+    pub pc: u64,
+    /// Physical address accessed.
+    pub addr: u64,
+    /// Access size in bytes (1, 2, 4, or 8).
+    pub size: u8,
+    /// `true` for writes, `false` for reads.
+    pub is_write: bool,
+    /// Value read or written, zero-extended.
+    pub value: u64,
+}
+
+/// Ring buffer of recent bus accesses.
+#[derive(Clone, Debug)]
+pub struct AccessLog {
+    /// Recorded accesses, oldest first.
+    entries: VecDeque<BusAccess>,
+    /// Maximum number of entries kept.
+    capacity: usize,
+    /// Half-open `[start, end)` physical range to record; `None` records all.
+    filter: Option<(u64, u64)>,
+}
+
+impl AccessLog {
+    /// Creates an empty log holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(4096)),
+            capacity,
+            filter: None,
+        }
+    }
+
+    /// Restricts recording to accesses within `[start, end)`, or lifts the
+    /// restriction with `None`.
+    pub fn set_filter(&mut self, filter: Option<(u64, u64)>) {
+        self.filter = filter;
+    }
+
+    /// Records `access`, dropping the oldest entry if the log is full.
+    pub fn record(&mut self, access: BusAccess) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((start, end)) = self.filter
+            && (access.addr < start || access.addr >= end)
+        {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            let _ = self.entries.pop_front();
+        }
+        self.entries.push_back(access);
+    }
+
+    /// Removes and returns all recorded accesses, oldest first.
+    pub fn drain(&mut self) -> Vec<BusAccess> {
+        self.entries.drain(..).collect()
+    }
+
+    /// Returns the number of recorded accesses.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
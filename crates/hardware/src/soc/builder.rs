@@ -2,12 +2,15 @@
 //!
 //! This module builds the complete SoC from configuration. It performs:
 //! 1. **Bus setup:** Creates the interconnect with configured width and latency.
-//! 2. **Device registration:** Instantiates RAM, UART, VirtIO disk, CLINT, PLIC, SysCon, and RTC.
+//! 2. **Device registration:** Instantiates RAM, UART, VirtIO disk, CLINT, PLIC, SysCon, RTC, and
+//!    the optional framebuffer, and applies each device's clock-domain-crossing latency.
 //! 3. **Memory controller:** Selects simple or DRAM controller based on config.
 //! 4. **Binary loading:** Optionally loads a disk image from path and kernel via `load_binary_at`.
 
-use crate::config::{Config, MemoryController as MemControllerType};
-use crate::soc::devices::{Clint, GoldfishRtc, Htif, Plic, SysCon, Uart, VirtioBlock};
+use crate::config::{Config, MemoryController as MemControllerType, defaults};
+use crate::soc::devices::clint::ClintIrqs;
+use crate::soc::devices::syscon::RESET_REQUEST;
+use crate::soc::devices::{Clint, Framebuffer, GoldfishRtc, Htif, Plic, SysCon, Uart, VirtioBlock};
 use crate::soc::interconnect::Bus;
 use crate::soc::memory::Memory;
 use crate::soc::memory::buffer::DramBuffer;
@@ -25,7 +28,8 @@ pub struct System {
     pub bus: Bus,
     /// Main memory controller (boxed for dynamic dispatch; `Send + Sync` for multi-threaded simulation).
     pub mem_controller: Box<dyn MemoryController + Send + Sync>,
-    /// Atomic exit code: when not `u64::MAX`, simulation should stop and use this as exit code.
+    /// Atomic exit code: when not `u64::MAX`, simulation should stop and use this as exit code
+    /// (or reboot, if it holds `RESET_REQUEST`).
     pub exit_request: Arc<AtomicU64>,
 }
 
@@ -33,7 +37,7 @@ impl System {
     /// Builds a new system from configuration and optional disk image path.
     ///
     /// Creates the bus, RAM, UART, VirtIO disk (loading `disk_path` if non-empty), CLINT, PLIC,
-    /// SysCon, and Goldfish RTC. The memory controller is chosen from `config.memory.controller`.
+    /// SysCon, Goldfish RTC, and (if enabled) the framebuffer. The memory controller is chosen from `config.memory.controller`.
     ///
     /// # Arguments
     ///
@@ -50,6 +54,9 @@ impl System {
         let ram_base = config.system.ram_base;
         let ram_size = config.memory.ram_size;
         let ram_buffer = Arc::new(DramBuffer::new(ram_size));
+        if config.memory.fill_pattern != 0 {
+            ram_buffer.fill(config.memory.fill_pattern);
+        }
         let mem = Memory::new(ram_buffer.clone(), ram_base);
 
         let uart_base = config.system.uart_base;
@@ -60,9 +67,16 @@ impl System {
         );
 
         let clint_addr = config.system.clint_base;
-        let clint = Clint::new(clint_addr, config.system.clint_divider);
+        let clint = Clint::with_harts(
+            clint_addr,
+            config.system.clint_divider,
+            config
+                .system
+                .num_harts
+                .max(config.general.hartid as usize + 1),
+        );
 
-        let plic_addr = 0x0c00_0000;
+        let plic_addr = defaults::PLIC_BASE;
         let plic = Plic::new(plic_addr);
 
         let disk_base = config.system.disk_base;
@@ -77,7 +91,12 @@ impl System {
         let syscon_addr = config.system.syscon_base;
         let syscon = SysCon::new(syscon_addr, exit_request.clone());
 
-        let rtc = GoldfishRtc::new(0x101000);
+        let rtc_addr = config.system.rtc_base;
+        let rtc = GoldfishRtc::new(
+            rtc_addr,
+            config.system.clint_divider,
+            config.system.timebase_frequency,
+        );
 
         bus.add_device(Box::new(mem));
         bus.add_device(Box::new(uart));
@@ -87,18 +106,43 @@ impl System {
         bus.add_device(Box::new(syscon));
         bus.add_device(Box::new(rtc));
 
+        let fb = &config.system.framebuffer;
+        if fb.enabled {
+            bus.add_device(Box::new(Framebuffer::new(fb.base, fb.width, fb.height)));
+        }
+
         if config.system.tohost_addr != 0 {
             let htif = Htif::new(config.system.tohost_addr, exit_request.clone());
             bus.add_device(Box::new(htif));
         }
 
+        let cdc = &config.system.cdc_latency;
+        bus.set_cdc_latency(uart_base, cdc.uart);
+        bus.set_cdc_latency(disk_base, cdc.disk);
+        bus.set_cdc_latency(clint_addr, cdc.clint);
+        bus.set_cdc_latency(plic_addr, cdc.plic);
+        bus.set_cdc_latency(syscon_addr, cdc.syscon);
+        bus.set_cdc_latency(rtc_addr, cdc.rtc);
+
         let mem_controller: Box<dyn MemoryController + Send + Sync> = match config.memory.controller
         {
-            MemControllerType::Dram => Box::new(DramController::new(
-                config.memory.t_cas,
-                config.memory.t_ras,
-                config.memory.t_pre,
-            )),
+            MemControllerType::Dram => Box::new(
+                DramController::with_geometry(
+                    config.memory.t_cas,
+                    config.memory.t_ras,
+                    config.memory.t_pre,
+                    config.memory.num_banks,
+                    config.memory.num_channels,
+                    config.memory.interleave_granularity,
+                )
+                .with_write_timing(
+                    config.memory.t_cwl.unwrap_or(config.memory.t_cas),
+                    config.memory.t_wr,
+                    config.memory.t_wtr,
+                )
+                .with_page_policy(config.memory.page_policy)
+                .with_refresh(config.memory.t_refi, config.memory.t_rfc),
+            ),
             MemControllerType::Simple => {
                 Box::new(SimpleController::new(config.memory.row_miss_latency))
             }
@@ -121,12 +165,12 @@ impl System {
         self.bus.load_binary_at(data, addr);
     }
 
-    /// Advances all devices by one tick; returns (timer_irq, meip, seip).
+    /// Advances all devices by one tick; returns (clint, meip, seip).
     ///
     /// # Returns
     ///
-    /// A tuple of (machine timer IRQ active, machine external IRQ pending, supervisor external IRQ pending).
-    pub fn tick(&mut self) -> (bool, bool, bool) {
+    /// A tuple of (per-hart CLINT timer/software IRQs, machine external IRQ pending, supervisor external IRQ pending).
+    pub fn tick(&mut self) -> (ClintIrqs, bool, bool) {
         self.bus.tick()
     }
 
@@ -134,10 +178,31 @@ impl System {
     ///
     /// # Returns
     ///
-    /// `Some(exit_code)` if exit was requested, otherwise `None`.
+    /// `Some(exit_code)` if exit was requested, otherwise `None` (including
+    /// when a reboot is pending).
     pub fn check_exit(&self) -> Option<u64> {
         let val = self.exit_request.load(std::sync::atomic::Ordering::Relaxed);
-        if val != u64::MAX { Some(val) } else { None }
+        if val != u64::MAX && val != RESET_REQUEST {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
+    /// Consumes a pending reboot request (e.g., from SysCon).
+    ///
+    /// # Returns
+    ///
+    /// `true` if a device requested a reboot since the last call.
+    pub fn take_reset(&self) -> bool {
+        self.exit_request
+            .compare_exchange(
+                RESET_REQUEST,
+                u64::MAX,
+                std::sync::atomic::Ordering::Relaxed,
+                std::sync::atomic::Ordering::Relaxed,
+            )
+            .is_ok()
     }
 
     /// Checks whether the kernel has signaled panic via UART (e.g., for test harnesses).
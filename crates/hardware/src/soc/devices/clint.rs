@@ -6,29 +6,61 @@
 //!
 //! # Memory Map
 //!
-//! * `0x0000`: MSIP (Machine Software Interrupt Pending)
-//! * `0x4000`: MTIMECMP (Machine Time Compare)
-//! * `0xBFF8`: MTIME (Machine Time)
+//! * `0x0000 + 4*hart`: MSIP (Machine Software Interrupt Pending)
+//! * `0x4000 + 8*hart`: MTIMECMP (Machine Time Compare)
+//! * `0xBFF8`: MTIME (Machine Time, shared by all harts)
 
 use crate::soc::devices::Device;
 
-/// Offset for the Machine Software Interrupt Pending register.
+/// Offset for hart 0's Machine Software Interrupt Pending register.
 const MSIP_OFFSET: u64 = 0x0000;
-/// Offset for the Machine Time Compare register.
+/// Offset for hart 0's Machine Time Compare register.
 const MTIMECMP_OFFSET: u64 = 0x4000;
 /// Offset for the Machine Time register.
 const MTIME_OFFSET: u64 = 0xBFF8;
 
+/// Largest hart count the memory map has room for (the last MTIMECMP slot
+/// ends where MTIME begins).
+pub const MAX_HARTS: usize = ((MTIME_OFFSET - MTIMECMP_OFFSET) / 8) as usize;
+
+/// Per-hart interrupt lines driven by the CLINT; bit `h` belongs to hart `h`.
+///
+/// Harts beyond 63 are not represented.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClintIrqs {
+    /// Machine timer interrupt pending (`mtime >= mtimecmp[h]`).
+    pub mtip: u64,
+    /// Machine software interrupt pending (`msip[h]` bit 0).
+    pub msip: u64,
+}
+
+impl ClintIrqs {
+    /// Returns whether hart `hart` has a pending timer interrupt.
+    pub fn mtip(&self, hart: usize) -> bool {
+        hart < 64 && (self.mtip >> hart) & 1 != 0
+    }
+
+    /// Returns whether hart `hart` has a pending software interrupt.
+    pub fn msip(&self, hart: usize) -> bool {
+        hart < 64 && (self.msip >> hart) & 1 != 0
+    }
+
+    /// Returns whether any hart has either interrupt pending.
+    pub fn any(&self) -> bool {
+        (self.mtip | self.msip) != 0
+    }
+}
+
 /// CLINT device structure.
 pub struct Clint {
     /// Base physical address of the device.
     base_addr: u64,
     /// Current machine time counter.
     mtime: u64,
-    /// Machine time compare register.
-    mtimecmp: u64,
-    /// Machine software interrupt pending register.
-    msip: u32,
+    /// Machine time compare registers, one per hart.
+    mtimecmp: Vec<u64>,
+    /// Machine software interrupt pending registers, one per hart.
+    msip: Vec<u32>,
     /// Divider to scale CPU cycles to timer ticks.
     divider: u64,
     /// Internal counter for the divider.
@@ -36,22 +68,69 @@ pub struct Clint {
 }
 
 impl Clint {
-    /// Creates a new CLINT device.
+    /// Creates a new single-hart CLINT device.
     ///
     /// # Arguments
     ///
     /// * `base_addr` - The base physical address.
     /// * `divider` - The ratio of CPU cycles to timer ticks (e.g., 10 means timer increments every 10 cycles).
     pub fn new(base_addr: u64, divider: u64) -> Self {
+        Self::with_harts(base_addr, divider, 1)
+    }
+
+    /// Creates a CLINT with one MSIP and one MTIMECMP register per hart.
+    ///
+    /// `num_harts` is clamped to `1..=MAX_HARTS`.
+    pub fn with_harts(base_addr: u64, divider: u64, num_harts: usize) -> Self {
+        let num_harts = num_harts.clamp(1, MAX_HARTS);
         Self {
             base_addr,
             mtime: 0,
-            mtimecmp: u64::MAX,
-            msip: 0,
+            mtimecmp: vec![u64::MAX; num_harts],
+            msip: vec![0; num_harts],
             divider: if divider == 0 { 1 } else { divider },
             counter: 0,
         }
     }
+
+    /// Returns the number of harts this CLINT serves.
+    pub fn num_harts(&self) -> usize {
+        self.msip.len()
+    }
+
+    /// Returns the current machine time, as the `time` CSR reads it.
+    pub fn mtime(&self) -> u64 {
+        self.mtime
+    }
+
+    /// Returns the per-hart timer and software interrupt lines.
+    pub fn pending(&self) -> ClintIrqs {
+        let mut irqs = ClintIrqs::default();
+        for hart in 0..self.num_harts().min(64) {
+            if self.mtime >= self.mtimecmp[hart] {
+                irqs.mtip |= 1 << hart;
+            }
+            if self.msip[hart] & 1 != 0 {
+                irqs.msip |= 1 << hart;
+            }
+        }
+        irqs
+    }
+
+    /// Maps `offset` to the hart whose MSIP register it addresses.
+    fn msip_hart(&self, offset: u64) -> Option<usize> {
+        let rel = offset.checked_sub(MSIP_OFFSET)?;
+        let hart = (rel / 4) as usize;
+        (rel % 4 == 0 && hart < self.num_harts()).then_some(hart)
+    }
+
+    /// Maps `offset` to the hart whose MTIMECMP register it addresses and
+    /// whether it names the upper word.
+    fn mtimecmp_hart(&self, offset: u64) -> Option<(usize, bool)> {
+        let rel = offset.checked_sub(MTIMECMP_OFFSET)?;
+        let hart = (rel / 8) as usize;
+        (rel % 4 == 0 && hart < self.num_harts()).then_some((hart, rel % 8 == 4))
+    }
 }
 
 impl Device for Clint {
@@ -84,22 +163,38 @@ impl Device for Clint {
     /// Handles reads to MSIP, and the lower/upper halves of MTIME and MTIMECMP.
     fn read_u32(&mut self, offset: u64) -> u32 {
         match offset {
-            MSIP_OFFSET => self.msip,
-            MTIMECMP_OFFSET => self.mtimecmp as u32,
-            val if val == MTIMECMP_OFFSET + 4 => (self.mtimecmp >> 32) as u32,
             MTIME_OFFSET => self.mtime as u32,
             val if val == MTIME_OFFSET + 4 => (self.mtime >> 32) as u32,
-            _ => 0,
+            _ => {
+                if let Some(hart) = self.msip_hart(offset) {
+                    self.msip[hart]
+                } else if let Some((hart, upper)) = self.mtimecmp_hart(offset) {
+                    let cmp = self.mtimecmp[hart];
+                    if upper {
+                        (cmp >> 32) as u32
+                    } else {
+                        cmp as u32
+                    }
+                } else {
+                    0
+                }
+            }
         }
     }
 
     /// Reads a double-word (64-bit) from the device.
     fn read_u64(&mut self, offset: u64) -> u64 {
         match offset {
-            MSIP_OFFSET => self.msip as u64,
-            MTIMECMP_OFFSET => self.mtimecmp,
             MTIME_OFFSET => self.mtime,
-            _ => 0,
+            _ => {
+                if let Some(hart) = self.msip_hart(offset) {
+                    self.msip[hart] as u64
+                } else if let Some((hart, false)) = self.mtimecmp_hart(offset) {
+                    self.mtimecmp[hart]
+                } else {
+                    0
+                }
+            }
         }
     }
 
@@ -113,35 +208,44 @@ impl Device for Clint {
     /// Handles writes to MSIP, and the lower/upper halves of MTIME and MTIMECMP.
     fn write_u32(&mut self, offset: u64, val: u32) {
         match offset {
-            MSIP_OFFSET => self.msip = val & 1,
-            MTIMECMP_OFFSET => {
-                self.mtimecmp = (self.mtimecmp & 0xFFFF_FFFF_0000_0000) | (val as u64)
-            }
-            o if o == MTIMECMP_OFFSET + 4 => {
-                self.mtimecmp = (self.mtimecmp & 0x0000_0000_FFFF_FFFF) | ((val as u64) << 32)
-            }
             MTIME_OFFSET => self.mtime = (self.mtime & 0xFFFF_FFFF_0000_0000) | (val as u64),
             o if o == MTIME_OFFSET + 4 => {
                 self.mtime = (self.mtime & 0x0000_0000_FFFF_FFFF) | ((val as u64) << 32)
             }
-            _ => {}
+            _ => {
+                if let Some(hart) = self.msip_hart(offset) {
+                    self.msip[hart] = val & 1;
+                } else if let Some((hart, upper)) = self.mtimecmp_hart(offset) {
+                    let cmp = &mut self.mtimecmp[hart];
+                    *cmp = if upper {
+                        (*cmp & 0x0000_0000_FFFF_FFFF) | ((val as u64) << 32)
+                    } else {
+                        (*cmp & 0xFFFF_FFFF_0000_0000) | (val as u64)
+                    };
+                }
+            }
         }
     }
 
     /// Writes a double-word (64-bit) to the device.
     fn write_u64(&mut self, offset: u64, val: u64) {
         match offset {
-            MSIP_OFFSET => self.msip = (val as u32) & 1,
-            MTIMECMP_OFFSET => self.mtimecmp = val,
             MTIME_OFFSET => self.mtime = val,
-            _ => {}
+            _ => {
+                if let Some(hart) = self.msip_hart(offset) {
+                    self.msip[hart] = (val as u32) & 1;
+                } else if let Some((hart, false)) = self.mtimecmp_hart(offset) {
+                    self.mtimecmp[hart] = val;
+                }
+            }
         }
     }
 
     /// Advances the device state by one cycle.
     ///
     /// Increments the `mtime` counter based on the configured divider.
-    /// Returns `true` if an interrupt condition is met (timer or software).
+    /// Returns `true` if any hart has a timer or software interrupt pending;
+    /// `pending` says which.
     fn tick(&mut self) -> bool {
         self.counter += 1;
         if self.counter >= self.divider {
@@ -149,6 +253,32 @@ impl Device for Clint {
             self.counter = 0;
         }
 
-        self.mtime >= self.mtimecmp || (self.msip & 1) != 0
+        self.pending().any()
+    }
+
+    fn as_clint(&self) -> Option<&Clint> {
+        Some(self)
+    }
+
+    /// Saves `mtime`, `mtimecmp`, `msip`, and the divider phase.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        serde_json::to_vec(&(self.mtime, &self.mtimecmp, &self.msip, self.counter)).ok()
+    }
+
+    /// Restores the timer registers saved by `save_state`.
+    ///
+    /// Fails if the snapshot was taken with a different hart count.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        let (mtime, mtimecmp, msip, counter): (u64, Vec<u64>, Vec<u32>, u64) =
+            serde_json::from_slice(state).map_err(|e| format!("CLINT state: {e}"))?;
+        if mtimecmp.len() != self.num_harts() || msip.len() != self.num_harts() {
+            return Err(format!(
+                "CLINT state: snapshot has {} harts, device has {}",
+                msip.len(),
+                self.num_harts()
+            ));
+        }
+        (self.mtime, self.mtimecmp, self.msip, self.counter) = (mtime, mtimecmp, msip, counter);
+        Ok(())
     }
 }
@@ -0,0 +1,190 @@
+//! Linear Framebuffer.
+//!
+//! A plain memory-mapped pixel buffer the guest can draw into. There is no
+//! display controller and no acceleration: the host snapshots the contents
+//! with `write_ppm`/`dump_ppm`.
+//!
+//! # Memory Layout
+//!
+//! Pixels are 32-bit little-endian `0x00RRGGBB` words stored row-major, so the
+//! pixel at `(x, y)` lives at offset `(y * width + x) * 4`. The top byte is
+//! ignored.
+
+use crate::soc::devices::Device;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Bytes per pixel.
+pub const BYTES_PER_PIXEL: u64 = 4;
+
+/// Linear framebuffer device structure.
+pub struct Framebuffer {
+    /// Base physical address of the device.
+    base_addr: u64,
+    /// Width in pixels.
+    width: u32,
+    /// Height in pixels.
+    height: u32,
+    /// Pixel storage, `width * height * 4` bytes.
+    pixels: Vec<u8>,
+}
+
+impl Framebuffer {
+    /// Creates a new, black framebuffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_addr` - The base physical address.
+    /// * `width` - Width in pixels.
+    /// * `height` - Height in pixels.
+    pub fn new(base_addr: u64, width: u32, height: u32) -> Self {
+        let size = width as u64 * height as u64 * BYTES_PER_PIXEL;
+        Self {
+            base_addr,
+            width,
+            height,
+            pixels: vec![0; size as usize],
+        }
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the raw pixel bytes.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the `0x00RRGGBB` value of the pixel at `(x, y)`.
+    pub fn pixel(&self, x: u32, y: u32) -> u32 {
+        let offset = (y as usize * self.width as usize + x as usize) * BYTES_PER_PIXEL as usize;
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&self.pixels[offset..offset + 4]);
+        u32::from_le_bytes(bytes) & 0x00FF_FFFF
+    }
+
+    /// Writes the contents as a binary (P6) PPM image.
+    pub fn write_ppm(&self, out: &mut impl Write) -> std::io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        let rgb: Vec<u8> = self
+            .pixels
+            .chunks_exact(BYTES_PER_PIXEL as usize)
+            .flat_map(|px| [px[2], px[1], px[0]])
+            .collect();
+        out.write_all(&rgb)
+    }
+
+    /// Writes the contents to `path` as a PPM image.
+    pub fn dump_ppm(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_ppm(&mut out)?;
+        out.flush()
+    }
+
+    /// Reads `N` bytes at `offset`, or zeros if out of range.
+    fn read<const N: usize>(&self, offset: u64) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        if let Some(src) = self.pixels.get(offset as usize..offset as usize + N) {
+            bytes.copy_from_slice(src);
+        }
+        bytes
+    }
+
+    /// Writes `bytes` at `offset`, ignoring writes out of range.
+    fn write(&mut self, offset: u64, bytes: &[u8]) {
+        if let Some(dst) = self
+            .pixels
+            .get_mut(offset as usize..offset as usize + bytes.len())
+        {
+            dst.copy_from_slice(bytes);
+        }
+    }
+}
+
+impl Device for Framebuffer {
+    /// Returns the device name.
+    fn name(&self) -> &str {
+        "Framebuffer"
+    }
+
+    /// Returns the address range (Base, Size).
+    fn address_range(&self) -> (u64, u64) {
+        (self.base_addr, self.pixels.len() as u64)
+    }
+
+    /// Reads a byte from the pixel buffer.
+    fn read_u8(&mut self, offset: u64) -> u8 {
+        self.read::<1>(offset)[0]
+    }
+
+    /// Reads a half-word from the pixel buffer.
+    fn read_u16(&mut self, offset: u64) -> u16 {
+        u16::from_le_bytes(self.read(offset))
+    }
+
+    /// Reads a word (one pixel when aligned) from the pixel buffer.
+    fn read_u32(&mut self, offset: u64) -> u32 {
+        u32::from_le_bytes(self.read(offset))
+    }
+
+    /// Reads a double-word from the pixel buffer.
+    fn read_u64(&mut self, offset: u64) -> u64 {
+        u64::from_le_bytes(self.read(offset))
+    }
+
+    /// Writes a byte to the pixel buffer.
+    fn write_u8(&mut self, offset: u64, val: u8) {
+        self.write(offset, &[val]);
+    }
+
+    /// Writes a half-word to the pixel buffer.
+    fn write_u16(&mut self, offset: u64, val: u16) {
+        self.write(offset, &val.to_le_bytes());
+    }
+
+    /// Writes a word (one pixel when aligned) to the pixel buffer.
+    fn write_u32(&mut self, offset: u64, val: u32) {
+        self.write(offset, &val.to_le_bytes());
+    }
+
+    /// Writes a double-word to the pixel buffer.
+    fn write_u64(&mut self, offset: u64, val: u64) {
+        self.write(offset, &val.to_le_bytes());
+    }
+
+    /// Writes a contiguous byte slice to the pixel buffer.
+    fn write_bytes(&mut self, offset: u64, data: &[u8]) {
+        self.write(offset, data);
+    }
+
+    /// Saves the pixel buffer.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        Some(self.pixels.clone())
+    }
+
+    /// Restores a pixel buffer saved by `save_state`.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        if state.len() != self.pixels.len() {
+            return Err(format!(
+                "framebuffer size mismatch: checkpoint {} bytes, device {} bytes",
+                state.len(),
+                self.pixels.len()
+            ));
+        }
+        self.pixels.copy_from_slice(state);
+        Ok(())
+    }
+
+    /// Returns a mutable reference to this framebuffer.
+    fn as_framebuffer_mut(&mut self) -> Option<&mut Framebuffer> {
+        Some(self)
+    }
+}
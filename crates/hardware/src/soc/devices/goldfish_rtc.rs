@@ -1,34 +1,83 @@
 //! Goldfish Real-Time Clock (RTC).
 //!
 //! A virtual RTC device commonly used in Android emulators (QEMU).
-//! It provides the current system time in nanoseconds.
+//! It provides the current time in nanoseconds since the Unix epoch.
+//!
+//! The clock starts at the host's wall-clock time when the device is created
+//! and then advances with simulated time: it counts `mtime` ticks with the
+//! same cycle divider as the CLINT, so guest time stays consistent with the
+//! timer and is independent of how fast the host runs the simulation.
 //!
 //! # Memory Map
 //!
-//! * `0x00`: Time (Low 32 bits)
-//! * `0x04`: Time (High 32 bits)
+//! * `0x00`: TIME_LOW (Low 32 bits; reading latches the high word)
+//! * `0x04`: TIME_HIGH (High 32 bits as of the last TIME_LOW read)
 
 use crate::soc::devices::Device;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Offset of the low 32 bits of the time.
+const TIME_LOW: u64 = 0x00;
+/// Offset of the high 32 bits of the time.
+const TIME_HIGH: u64 = 0x04;
+
+/// Nanoseconds per second.
+const NS_PER_SEC: u128 = 1_000_000_000;
+
 /// Goldfish RTC device structure.
 pub struct GoldfishRtc {
     /// Base physical address of the device.
     base_addr: u64,
+    /// Time in nanoseconds when `mtime` was 0.
+    epoch_ns: u64,
+    /// Machine time counter, mirroring the CLINT's `mtime`.
+    mtime: u64,
+    /// Divider to scale CPU cycles to timer ticks.
+    divider: u64,
+    /// `mtime` ticks per second (the `timebase-frequency` in the DTB).
+    timebase_frequency: u64,
+    /// Internal counter for the divider.
+    counter: u64,
+    /// High word latched by a TIME_LOW read, or staged by a TIME_HIGH write.
+    high_latch: u32,
 }
 
 impl GoldfishRtc {
-    /// Creates a new Goldfish RTC device.
-    pub fn new(base_addr: u64) -> Self {
-        Self { base_addr }
+    /// Creates a new Goldfish RTC device starting at the host's current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_addr` - The base physical address.
+    /// * `divider` - The ratio of CPU cycles to timer ticks (the CLINT divider).
+    /// * `timebase_frequency` - Timer ticks per second.
+    pub fn new(base_addr: u64, divider: u64, timebase_frequency: u64) -> Self {
+        let epoch_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Self {
+            base_addr,
+            epoch_ns,
+            mtime: 0,
+            divider: if divider == 0 { 1 } else { divider },
+            timebase_frequency: timebase_frequency.max(1),
+            counter: 0,
+            high_latch: 0,
+        }
     }
 
-    /// Retrieves the current system time in nanoseconds.
-    fn get_time_ns(&self) -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64
+    /// Returns the current time in nanoseconds since the Unix epoch.
+    pub fn time_ns(&self) -> u64 {
+        self.epoch_ns.wrapping_add(self.elapsed_ns())
+    }
+
+    /// Sets the current time in nanoseconds since the Unix epoch.
+    pub fn set_time_ns(&mut self, time_ns: u64) {
+        self.epoch_ns = time_ns.wrapping_sub(self.elapsed_ns());
+    }
+
+    /// Returns the nanoseconds `mtime` represents at the timebase frequency.
+    fn elapsed_ns(&self) -> u64 {
+        (self.mtime as u128 * NS_PER_SEC / self.timebase_frequency as u128) as u64
     }
 }
 
@@ -54,12 +103,16 @@ impl Device for GoldfishRtc {
 
     /// Reads a word (32-bit) from the device.
     ///
-    /// Returns the lower or upper 32 bits of the current nanosecond timestamp.
+    /// Reading TIME_LOW latches the high word so that a following TIME_HIGH
+    /// read forms a consistent 64-bit timestamp.
     fn read_u32(&mut self, offset: u64) -> u32 {
-        let time = self.get_time_ns();
         match offset {
-            0x00 => time as u32,
-            0x04 => (time >> 32) as u32,
+            TIME_LOW => {
+                let time = self.time_ns();
+                self.high_latch = (time >> 32) as u32;
+                time as u32
+            }
+            TIME_HIGH => self.high_latch,
             _ => 0,
         }
     }
@@ -68,9 +121,8 @@ impl Device for GoldfishRtc {
     ///
     /// Returns the full 64-bit nanosecond timestamp.
     fn read_u64(&mut self, offset: u64) -> u64 {
-        let time = self.get_time_ns();
         match offset {
-            0x00 => time,
+            TIME_LOW => self.time_ns(),
             _ => 0,
         }
     }
@@ -79,13 +131,53 @@ impl Device for GoldfishRtc {
     fn write_u8(&mut self, _offset: u64, _val: u8) {}
     /// Writes a half-word (unimplemented).
     fn write_u16(&mut self, _offset: u64, _val: u16) {}
-    /// Writes a word (unimplemented).
-    fn write_u32(&mut self, _offset: u64, _val: u32) {}
-    /// Writes a double-word (unimplemented).
-    fn write_u64(&mut self, _offset: u64, _val: u64) {}
+
+    /// Writes a word (32-bit) to the device.
+    ///
+    /// Sets the time: TIME_HIGH stages the high word, and the following
+    /// TIME_LOW write applies both halves.
+    fn write_u32(&mut self, offset: u64, val: u32) {
+        match offset {
+            TIME_HIGH => self.high_latch = val,
+            TIME_LOW => self.set_time_ns(((self.high_latch as u64) << 32) | val as u64),
+            _ => {}
+        }
+    }
+
+    /// Writes a double-word (64-bit) to the device.
+    fn write_u64(&mut self, offset: u64, val: u64) {
+        if offset == TIME_LOW {
+            self.set_time_ns(val);
+        }
+    }
+
+    /// Advances the clock by one cycle.
+    ///
+    /// Increments `mtime` based on the configured divider. Never raises an
+    /// interrupt (alarms are not modeled).
+    fn tick(&mut self) -> bool {
+        self.counter += 1;
+        if self.counter >= self.divider {
+            self.mtime = self.mtime.wrapping_add(1);
+            self.counter = 0;
+        }
+        false
+    }
 
     /// Returns the Interrupt Request (IRQ) ID associated with this device.
     fn get_irq_id(&self) -> Option<u32> {
         Some(11)
     }
+
+    /// Saves the epoch, `mtime`, and the divider phase.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        serde_json::to_vec(&(self.epoch_ns, self.mtime, self.counter, self.high_latch)).ok()
+    }
+
+    /// Restores the clock saved by `save_state`.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        (self.epoch_ns, self.mtime, self.counter, self.high_latch) =
+            serde_json::from_slice(state).map_err(|e| format!("RTC state: {e}"))?;
+        Ok(())
+    }
 }
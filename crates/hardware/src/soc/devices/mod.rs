@@ -2,11 +2,14 @@
 //!
 //! This module contains implementations of various hardware devices
 //! found in the SoC, such as timers (CLINT), interrupt controllers (PLIC),
-//! serial ports (UART), and block devices (VirtIO).
+//! serial ports (UART), block devices (VirtIO), and a linear framebuffer.
 
 /// Core Local Interruptor (timer and software interrupt controller).
 pub mod clint;
 
+/// Linear framebuffer with PPM snapshots.
+pub mod framebuffer;
+
 /// Goldfish RTC (Real-Time Clock) device.
 pub mod goldfish_rtc;
 
@@ -26,6 +29,7 @@ pub mod uart;
 pub mod virtio_disk;
 
 pub use clint::Clint;
+pub use framebuffer::Framebuffer;
 pub use goldfish_rtc::GoldfishRtc;
 pub use htif::Htif;
 pub use plic::Plic;
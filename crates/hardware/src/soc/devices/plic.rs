@@ -3,6 +3,11 @@
 //! The PLIC arbitrates global external interrupts and distributes them to
 //! interrupt targets (HART contexts). It complies with the RISC-V PLIC specification.
 //!
+//! Contexts are numbered per hart: context `2 * hart` is the hart's M-mode
+//! target and `2 * hart + 1` its S-mode target. Each context has its own
+//! enables, threshold, and claim register, and its own external interrupt
+//! line.
+//!
 //! # Memory Map
 //!
 //! * `0x000000`: Interrupt Priorities
@@ -24,8 +29,14 @@ const PLIC_ENABLE_BASE: u64 = 0x002000;
 /// Base offset for PLIC context-specific registers (threshold, claim/complete).
 const PLIC_CONTEXT_BASE: u64 = 0x200000;
 
-/// Number of interrupt contexts (M-mode + S-mode per HART).
-const NUM_CONTEXTS: usize = 2;
+/// Interrupt contexts per HART (M-mode + S-mode).
+const CONTEXTS_PER_HART: usize = 2;
+
+/// Stride between per-context enable blocks.
+const PLIC_ENABLE_STRIDE: usize = 0x80;
+
+/// Stride between per-context threshold/claim blocks.
+const PLIC_CONTEXT_STRIDE: usize = 0x1000;
 
 /// Number of 32-bit enable words per context (covers 1024 interrupt sources).
 const ENABLE_WORDS_PER_CONTEXT: usize = 32;
@@ -44,21 +55,41 @@ pub struct Plic {
     thresholds: Vec<u32>,
     /// Claim/Complete registers per context.
     claims: Vec<u32>,
+    /// External interrupt line per context, as of the last update.
+    lines: Vec<bool>,
 }
 
 impl Plic {
-    /// Creates a new PLIC device.
+    /// Creates a new PLIC device serving a single HART (two contexts).
     pub fn new(base_addr: u64) -> Self {
+        Self::with_contexts(base_addr, CONTEXTS_PER_HART)
+    }
+
+    /// Creates a new PLIC device with the given number of contexts.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_addr` - The base physical address.
+    /// * `num_contexts` - Number of interrupt targets, normally two per HART
+    ///   (at least 1).
+    pub fn with_contexts(base_addr: u64, num_contexts: usize) -> Self {
+        let num_contexts = num_contexts.max(1);
         Self {
             base_addr,
             priorities: vec![0; 1024],
             pending: vec![0; 32],
-            enables: vec![vec![0u32; ENABLE_WORDS_PER_CONTEXT]; NUM_CONTEXTS],
-            thresholds: vec![0; NUM_CONTEXTS],
-            claims: vec![0; NUM_CONTEXTS],
+            enables: vec![vec![0u32; ENABLE_WORDS_PER_CONTEXT]; num_contexts],
+            thresholds: vec![0; num_contexts],
+            claims: vec![0; num_contexts],
+            lines: vec![false; num_contexts],
         }
     }
 
+    /// Returns the number of interrupt contexts.
+    pub fn num_contexts(&self) -> usize {
+        self.claims.len()
+    }
+
     /// Updates the pending status of interrupts based on external signals.
     ///
     /// # Arguments
@@ -69,57 +100,44 @@ impl Plic {
         self.pending[1] = (mask >> 32) as u32;
     }
 
+    /// Recomputes every context's claimable interrupt and external
+    /// interrupt line.
+    ///
+    /// Each context's claim register is set to its highest-priority pending,
+    /// enabled interrupt above its threshold (0 if none), and its line is
+    /// asserted when such an interrupt exists.
+    pub fn update_contexts(&mut self) {
+        for ctx in 0..self.num_contexts() {
+            self.claims[ctx] = self.calc_max_id(ctx);
+            self.lines[ctx] = self.claims[ctx] != 0;
+        }
+    }
+
+    /// Returns whether a context's external interrupt line is asserted, as of
+    /// the last update (false for an unknown context).
+    pub fn context_interrupt(&self, ctx: usize) -> bool {
+        self.lines.get(ctx).copied().unwrap_or(false)
+    }
+
+    /// Returns a HART's `(meip, seip)` lines, as of the last update.
+    pub fn hart_interrupts(&self, hart: usize) -> (bool, bool) {
+        (
+            self.context_interrupt(CONTEXTS_PER_HART * hart),
+            self.context_interrupt(CONTEXTS_PER_HART * hart + 1),
+        )
+    }
+
     /// Checks for pending interrupts that exceed the priority threshold.
     ///
+    /// Updates all contexts and reports HART 0's lines.
+    ///
     /// # Returns
     ///
     /// A tuple `(meip, seip)` indicating if a Machine External Interrupt
     /// or Supervisor External Interrupt is pending.
     pub fn check_interrupts(&mut self) -> (bool, bool) {
-        let mut meip = false;
-        let mut seip = false;
-
-        if self.has_qualified_irq(0) {
-            meip = true;
-            self.claims[0] = self.calc_max_id(0);
-        } else {
-            self.claims[0] = 0;
-        }
-
-        if self.has_qualified_irq(1) {
-            seip = true;
-            self.claims[1] = self.calc_max_id(1);
-        } else {
-            self.claims[1] = 0;
-        }
-
-        (meip, seip)
-    }
-
-    /// Determines if a context has any pending interrupt above its threshold.
-    fn has_qualified_irq(&self, ctx: usize) -> bool {
-        let threshold = self.thresholds[ctx];
-        let num_words = std::cmp::min(self.pending.len(), self.enables[ctx].len());
-
-        for word in 0..num_words {
-            let active = self.pending[word] & self.enables[ctx][word];
-            if active == 0 {
-                continue;
-            }
-            for bit in 0..32 {
-                let irq_id = word * 32 + bit;
-                if irq_id == 0 {
-                    continue; // IRQ 0 is reserved
-                }
-                if (active & (1 << bit)) != 0
-                    && irq_id < self.priorities.len()
-                    && self.priorities[irq_id] > threshold
-                {
-                    return true;
-                }
-            }
-        }
-        false
+        self.update_contexts();
+        self.hart_interrupts(0)
     }
 
     /// Calculates the ID of the highest priority pending interrupt for a context.
@@ -180,15 +198,15 @@ impl Device for Plic {
             }
         } else if (PLIC_ENABLE_BASE..PLIC_CONTEXT_BASE).contains(&offset) {
             let rel = (offset - PLIC_ENABLE_BASE) as usize;
-            let ctx = rel / 0x80;
-            let word_idx = (rel % 0x80) / 4;
-            if ctx < NUM_CONTEXTS && word_idx < ENABLE_WORDS_PER_CONTEXT {
+            let ctx = rel / PLIC_ENABLE_STRIDE;
+            let word_idx = (rel % PLIC_ENABLE_STRIDE) / 4;
+            if ctx < self.num_contexts() && word_idx < ENABLE_WORDS_PER_CONTEXT {
                 return self.enables[ctx][word_idx];
             }
         } else if offset >= PLIC_CONTEXT_BASE {
-            let ctx = (offset - PLIC_CONTEXT_BASE) as usize / 0x1000;
+            let ctx = (offset - PLIC_CONTEXT_BASE) as usize / PLIC_CONTEXT_STRIDE;
             let reg = offset & 0xFFF;
-            if ctx < 2 {
+            if ctx < self.num_contexts() {
                 if reg == 0 {
                     return self.thresholds[ctx];
                 }
@@ -218,15 +236,15 @@ impl Device for Plic {
             }
         } else if (PLIC_ENABLE_BASE..PLIC_CONTEXT_BASE).contains(&offset) {
             let rel = (offset - PLIC_ENABLE_BASE) as usize;
-            let ctx = rel / 0x80;
-            let word_idx = (rel % 0x80) / 4;
-            if ctx < NUM_CONTEXTS && word_idx < ENABLE_WORDS_PER_CONTEXT {
+            let ctx = rel / PLIC_ENABLE_STRIDE;
+            let word_idx = (rel % PLIC_ENABLE_STRIDE) / 4;
+            if ctx < self.num_contexts() && word_idx < ENABLE_WORDS_PER_CONTEXT {
                 self.enables[ctx][word_idx] = val;
             }
         } else if offset >= PLIC_CONTEXT_BASE {
-            let ctx = (offset - PLIC_CONTEXT_BASE) as usize / 0x1000;
+            let ctx = (offset - PLIC_CONTEXT_BASE) as usize / PLIC_CONTEXT_STRIDE;
             let reg = offset & 0xFFF;
-            if ctx < 2 {
+            if ctx < self.num_contexts() {
                 if reg == 0 {
                     self.thresholds[ctx] = val;
                 }
@@ -265,10 +283,34 @@ impl Device for Plic {
 
     /// Advances the device state.
     ///
-    /// Checks for pending interrupts and returns true if any are asserted.
+    /// Updates every context and returns true if any line is asserted.
     fn tick(&mut self) -> bool {
-        let (meip, seip) = self.check_interrupts();
-        meip || seip
+        self.update_contexts();
+        self.lines.iter().any(|&line| line)
+    }
+
+    /// Saves priorities, pending bits, enables, thresholds, and claims.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        serde_json::to_vec(&(
+            &self.priorities,
+            &self.pending,
+            &self.enables,
+            &self.thresholds,
+            &self.claims,
+        ))
+        .ok()
+    }
+
+    /// Restores the registers saved by `save_state`.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        (
+            self.priorities,
+            self.pending,
+            self.enables,
+            self.thresholds,
+            self.claims,
+        ) = serde_json::from_slice(state).map_err(|e| format!("PLIC state: {e}"))?;
+        Ok(())
     }
 
     /// Returns a mutable reference to the PLIC if this device is one.
@@ -8,13 +8,23 @@
 //!
 //! * `0x00`: Command Register (Write Only)
 //!   * `0x5555`: Power Off
-//!   * `0x7777`: Reset
+//!   * `0x7777`: Reset (reboot)
 //!   * `0x3333`: Failure/Panic
+//!
+//! Power-off and failure store an exit code in the shared exit signal; reset
+//! stores [`RESET_REQUEST`] instead, which the simulator turns into a reboot
+//! rather than an exit.
 
 use crate::soc::devices::Device;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+/// Value stored in the exit signal to request a reboot instead of an exit.
+///
+/// Distinct from every exit code a guest can report (HTIF codes fit in 63
+/// bits) and from `u64::MAX`, which means "no request".
+pub const RESET_REQUEST: u64 = u64::MAX - 1;
+
 /// SysCon device structure.
 pub struct SysCon {
     /// Base physical address of the device.
@@ -82,8 +92,8 @@ impl Device for SysCon {
                     self.exit_signal.store(0, Ordering::Relaxed)
                 }
                 0x7777 => {
-                    println!("[SysCon] Reset signal received (Simulated as Exit).");
-                    self.exit_signal.store(0, Ordering::Relaxed)
+                    println!("[SysCon] Reset signal received.");
+                    self.exit_signal.store(RESET_REQUEST, Ordering::Relaxed)
                 }
                 0x3333 => {
                     println!("[SysCon] Failure signal received.");
@@ -1,8 +1,15 @@
 //! Universal Asynchronous Receiver-Transmitter (UART).
 //!
 //! Implements a 16550-compatible UART device for serial communication.
-//! Handles standard registers (RBR, THR, IER, IIR, LCR, LSR) and integrates
-//! with stdin/stdout for console I/O.
+//! Handles the full register map (RBR/THR, IER, IIR/FCR, LCR, MCR, LSR, MSR,
+//! SCR) and integrates with stdin/stdout for console I/O:
+//! 1. **Receive:** A background thread reads stdin into a channel, which is
+//!    drained without blocking into the RX FIFO.
+//! 2. **Transmit:** THR writes are buffered and flushed to stdout/stderr.
+//! 3. **Interrupts:** Received data and an empty THR raise the UART's IRQ
+//!    (via the PLIC) when enabled in IER.
+//! 4. **Loopback:** With MCR.LOOP set, transmitted bytes are received back and
+//!    MSR reflects the MCR outputs.
 
 use crate::soc::devices::Device;
 use std::collections::VecDeque;
@@ -41,8 +48,29 @@ const IIR_THRE: u8 = 0x02;
 /// Interrupt Identity Register: Receiver Data Available interrupt.
 const IIR_RDA: u8 = 0x04;
 
-/// Interrupt Identity Register: Interrupt ID mask (bits 7:6).
-const IIR_ID_MASK: u8 = 0xC0;
+/// Interrupt Identity Register: FIFOs enabled (bits 7:6).
+const IIR_FIFO_ENABLED: u8 = 0xC0;
+
+/// FIFO Control Register: Enable FIFOs.
+const FCR_ENABLE: u8 = 0x01;
+
+/// FIFO Control Register: Clear the receive FIFO.
+const FCR_CLEAR_RX: u8 = 0x02;
+
+/// FIFO Control Register: Clear the transmit FIFO.
+const FCR_CLEAR_TX: u8 = 0x04;
+
+/// Modem Control Register: Loopback mode.
+const MCR_LOOP: u8 = 0x10;
+
+/// Modem Status Register: Clear To Send.
+const MSR_CTS: u8 = 0x10;
+
+/// Modem Status Register: Data Set Ready.
+const MSR_DSR: u8 = 0x20;
+
+/// Modem Status Register: Data Carrier Detect.
+const MSR_DCD: u8 = 0x80;
 
 /// Line Status Register: Data ready bit (receiver has data).
 const LSR_DATA_READY: u8 = 0x01;
@@ -65,6 +93,9 @@ const IER_RDA: u8 = 0x01;
 /// Interrupt Enable Register: Transmitter Holding Register Empty interrupt enable.
 const IER_THRE: u8 = 0x02;
 
+/// Interrupt Enable Register: Writable bits (RDA, THRE, line status, modem status).
+const IER_MASK: u8 = 0x0F;
+
 /// Threshold for flushing transmit buffer to stdout (4 KiB).
 const TX_BUFFER_FLUSH_THRESHOLD: usize = 4096;
 
@@ -81,6 +112,8 @@ pub struct Uart {
     rx_receiver: Mutex<Receiver<u8>>,
     /// Interrupt Enable Register.
     ier: u8,
+    /// FIFO Control Register (only the enable bit is retained).
+    fcr: u8,
     /// Line Control Register.
     lcr: u8,
     /// Modem Control Register.
@@ -132,6 +165,7 @@ impl Uart {
             rx_queue: VecDeque::new(),
             rx_receiver: Mutex::new(rx),
             ier: 0,
+            fcr: 0,
             lcr: 0,
             mcr: 0,
             scr: 0,
@@ -155,6 +189,20 @@ impl Uart {
         }
     }
 
+    /// Queues bytes as if they had been received on the serial line.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Bytes to append to the receive FIFO.
+    pub fn push_input(&mut self, data: &[u8]) {
+        self.rx_queue.extend(data);
+    }
+
+    /// Returns true if MCR loopback mode is enabled.
+    fn loopback(&self) -> bool {
+        (self.mcr & MCR_LOOP) != 0
+    }
+
     /// Calculates the Interrupt Identity Register (IIR) value.
     ///
     /// Determines the highest priority pending interrupt.
@@ -250,16 +298,38 @@ impl Uart {
         if iir == IIR_THRE {
             self.thre_ip = false;
         }
-        IIR_ID_MASK | iir
+        if (self.fcr & FCR_ENABLE) != 0 {
+            IIR_FIFO_ENABLED | iir
+        } else {
+            iir
+        }
+    }
+
+    /// Reads Modem Status Register (MSR).
+    ///
+    /// In loopback mode the modem inputs follow the MCR outputs (DTR→DSR,
+    /// RTS→CTS, OUT1→RI, OUT2→DCD); otherwise the line is always connected
+    /// and clear to send.
+    fn read_msr(&self) -> u8 {
+        if self.loopback() {
+            ((self.mcr & 0x01) << 5)
+                | ((self.mcr & 0x02) << 3)
+                | ((self.mcr & 0x04) << 4)
+                | ((self.mcr & 0x08) << 4)
+        } else {
+            MSR_DCD | MSR_DSR | MSR_CTS
+        }
     }
 
     /// Reads Line Status Register (LSR).
     ///
     /// Indicates if data is ready or if the transmitter is empty.
     /// Also flushes the TX buffer, since a guest polling for input has
-    /// likely finished its output (e.g. a prompt without a trailing newline).
+    /// likely finished its output (e.g. a prompt without a trailing newline),
+    /// and polls stdin so that polling drivers see input promptly.
     fn read_lsr(&mut self) -> u8 {
         self.flush_buffer();
+        self.check_stdin();
         let mut lsr = LSR_DEFAULT;
         if !self.rx_queue.is_empty() {
             lsr |= LSR_DATA_READY;
@@ -273,6 +343,9 @@ impl Uart {
     fn write_thr_or_dll(&mut self, val: u8) {
         if self.dlab_set() {
             self.div = (self.div & 0xFF00) | (val as u16);
+        } else if self.loopback() {
+            self.rx_queue.push_back(val);
+            self.thre_ip = true;
         } else {
             if self.check_char_for_panic(val) {
                 self.flush_buffer();
@@ -296,12 +369,27 @@ impl Uart {
         if self.dlab_set() {
             self.div = (self.div & 0x00FF) | ((val as u16) << 8);
         } else {
-            self.ier = val;
+            self.ier = val & IER_MASK;
             if (self.ier & IER_THRE) != 0 {
                 self.thre_ip = true;
             }
         }
     }
+
+    /// Writes FIFO Control Register (FCR).
+    ///
+    /// Clearing the receive FIFO discards unread input; clearing the
+    /// transmit FIFO flushes pending output, since transmission is
+    /// instantaneous.
+    fn write_fcr(&mut self, val: u8) {
+        self.fcr = val & FCR_ENABLE;
+        if (val & FCR_CLEAR_RX) != 0 {
+            self.rx_queue.clear();
+        }
+        if (val & FCR_CLEAR_TX) != 0 {
+            self.flush_buffer();
+        }
+    }
 }
 
 impl Drop for Uart {
@@ -330,7 +418,7 @@ impl Device for Uart {
             REG_LCR => self.lcr,
             REG_MCR => self.mcr,
             REG_LSR => self.read_lsr(),
-            REG_MSR => 0,
+            REG_MSR => self.read_msr(),
             REG_SCR => self.scr,
             _ => 0,
         }
@@ -354,7 +442,7 @@ impl Device for Uart {
         match offset {
             REG_THR => self.write_thr_or_dll(val),
             REG_IER => self.write_ier_or_dlm(val),
-            REG_FCR => {}
+            REG_FCR => self.write_fcr(val),
             REG_LCR => self.lcr = val,
             REG_MCR => self.mcr = val,
             REG_SCR => self.scr = val,
@@ -393,6 +481,41 @@ impl Device for Uart {
         Some(10)
     }
 
+    /// Saves the register file and any received bytes not yet read.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        serde_json::to_vec(&(
+            &self.rx_queue,
+            self.ier,
+            self.fcr,
+            self.lcr,
+            self.mcr,
+            self.scr,
+            self.div,
+            self.tick_count,
+            self.thre_ip,
+        ))
+        .ok()
+    }
+
+    /// Restores the registers saved by `save_state`.
+    ///
+    /// Output already written to the host and the panic detector are not
+    /// part of the checkpoint.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        (
+            self.rx_queue,
+            self.ier,
+            self.fcr,
+            self.lcr,
+            self.mcr,
+            self.scr,
+            self.div,
+            self.tick_count,
+            self.thre_ip,
+        ) = serde_json::from_slice(state).map_err(|e| format!("UART state: {e}"))?;
+        Ok(())
+    }
+
     /// Returns a mutable reference to the UART if this device is one.
     fn as_uart_mut(&mut self) -> Option<&mut Uart> {
         Some(self)
@@ -164,6 +164,22 @@ pub struct VirtioBlock {
     driver_features_sel: u32,
 }
 
+/// Transport registers saved in a checkpoint: status, queue size, ready,
+/// notify, descriptor/available/used ring addresses (low, high), interrupt
+/// status, last available index, and feature selectors (device, driver).
+type VirtioRegisters = (
+    u32,
+    u32,
+    u32,
+    u32,
+    [u32; 2],
+    [u32; 2],
+    [u32; 2],
+    u32,
+    u16,
+    [u32; 2],
+);
+
 unsafe impl Send for VirtioBlock {}
 unsafe impl Sync for VirtioBlock {}
 
@@ -397,6 +413,46 @@ impl VirtioBlock {
         }
         self.interrupt_status |= 1;
     }
+
+    /// Returns the transport registers for checkpointing.
+    fn registers(&self) -> VirtioRegisters {
+        (
+            self.status,
+            self.queue_num,
+            self.queue_ready,
+            self.queue_notify,
+            [self.queue_desc_low, self.queue_desc_high],
+            [self.queue_avail_low, self.queue_avail_high],
+            [self.queue_used_low, self.queue_used_high],
+            self.interrupt_status,
+            self.last_avail_idx,
+            [self.device_features_sel, self.driver_features_sel],
+        )
+    }
+
+    /// Loads transport registers captured by `registers`.
+    fn set_registers(&mut self, regs: VirtioRegisters) {
+        let desc;
+        let avail;
+        let used;
+        let features;
+        (
+            self.status,
+            self.queue_num,
+            self.queue_ready,
+            self.queue_notify,
+            desc,
+            avail,
+            used,
+            self.interrupt_status,
+            self.last_avail_idx,
+            features,
+        ) = regs;
+        [self.queue_desc_low, self.queue_desc_high] = desc;
+        [self.queue_avail_low, self.queue_avail_high] = avail;
+        [self.queue_used_low, self.queue_used_high] = used;
+        [self.device_features_sel, self.driver_features_sel] = features;
+    }
 }
 
 impl Device for VirtioBlock {
@@ -508,4 +564,30 @@ impl Device for VirtioBlock {
     fn get_irq_id(&self) -> Option<u32> {
         Some(1)
     }
+
+    /// Saves the transport registers followed by the disk image.
+    ///
+    /// Layout: an 8-byte little-endian length, the registers as JSON, then
+    /// the raw disk image.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        let regs = serde_json::to_vec(&self.registers()).ok()?;
+        let mut out = Vec::with_capacity(8 + regs.len() + self.disk_image.len());
+        out.extend_from_slice(&(regs.len() as u64).to_le_bytes());
+        out.extend_from_slice(&regs);
+        out.extend_from_slice(&self.disk_image);
+        Some(out)
+    }
+
+    /// Restores the registers and disk image saved by `save_state`.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        let malformed = || "VirtIO state: truncated".to_string();
+        let len_bytes = state.get(..8).ok_or_else(malformed)?;
+        let len = u64::from_le_bytes(len_bytes.try_into().map_err(|_| malformed())?) as usize;
+        let regs = state.get(8..8 + len).ok_or_else(malformed)?;
+        let regs: VirtioRegisters =
+            serde_json::from_slice(regs).map_err(|e| format!("VirtIO state: {e}"))?;
+        self.set_registers(regs);
+        self.disk_image = state[8 + len..].to_vec();
+        Ok(())
+    }
 }
@@ -2,11 +2,36 @@
 //!
 //! This module implements the bus that routes physical address accesses to devices. It provides:
 //! 1. **Device registration:** Devices are added by address range and sorted for lookup.
-//! 2. **Access routing:** Read/write by address with last-device hint for throughput.
+//! 2. **Access routing:** Read/write by address; an address no device claims is a
+//!    `BusError`. RAM is matched first, without touching any other device; MMIO uses a
+//!    last-device hint, falling back to a binary search over the sorted ranges.
 //! 3. **Tick and IRQ:** Each device is ticked; PLIC aggregates IRQs for timer and external.
 //! 4. **Load and RAM pointer:** Binary loading and raw RAM pointer for CPU DMA-style access.
+//! 5. **Access log:** Optionally records every read and write in a ring buffer.
 
-use super::devices::Device;
+use super::access_log::{AccessLog, BusAccess};
+use super::devices::clint::ClintIrqs;
+use super::devices::{Clint, Device};
+use crate::common::BusError;
+
+/// Address range of a registered device, cached for lookup without a virtual call.
+#[derive(Clone, Copy, Debug)]
+struct DeviceRange {
+    /// First address claimed by the device.
+    start: u64,
+    /// One past the last address claimed by the device.
+    end: u64,
+    /// Largest `end` among this and all lower-based devices; bounds the
+    /// backward search for a range that encloses a later one.
+    reach: u64,
+}
+
+impl DeviceRange {
+    #[inline]
+    fn contains(&self, paddr: u64) -> bool {
+        paddr >= self.start && paddr < self.end
+    }
+}
 
 /// System bus connecting CPU and devices; routes accesses by physical address.
 ///
@@ -15,6 +40,8 @@ use super::devices::Device;
 pub struct Bus {
     /// Registered MMIO and memory devices (boxed for dynamic dispatch; `Send + Sync` for thread safety).
     devices: Vec<Box<dyn Device + Send + Sync>>,
+    /// Address range of each device, parallel to `devices`.
+    ranges: Vec<DeviceRange>,
     /// Bus width in bytes (e.g., 8 for 64-bit); used to compute transfer cycles.
     pub width_bytes: u64,
     /// Base latency in cycles per transaction.
@@ -23,6 +50,13 @@ pub struct Bus {
     ram_idx: Option<usize>,
     uart_idx: Option<usize>,
     htif_idx: Option<usize>,
+    clint_idx: Option<usize>,
+    /// Clock-domain-crossing latency as (start, end, cycles) device ranges.
+    cdc_latency: Vec<(u64, u64, u64)>,
+    /// Recorded accesses, when logging is enabled.
+    access_log: Option<AccessLog>,
+    /// PC attributed to subsequent accesses in the access log.
+    access_pc: u64,
 }
 
 impl Bus {
@@ -39,12 +73,64 @@ impl Bus {
     pub fn new(width_bytes: u64, latency_cycles: u64) -> Self {
         Self {
             devices: Vec::new(),
+            ranges: Vec::new(),
             width_bytes,
             latency_cycles,
             last_device_idx: 0,
             ram_idx: None,
             uart_idx: None,
             htif_idx: None,
+            clint_idx: None,
+            cdc_latency: Vec::new(),
+            access_log: None,
+            access_pc: 0,
+        }
+    }
+
+    /// Starts recording accesses in a ring buffer of `capacity` entries,
+    /// replacing any existing log.
+    pub fn enable_access_log(&mut self, capacity: usize) {
+        self.access_log = Some(AccessLog::new(capacity));
+    }
+
+    /// Stops recording accesses and discards the log.
+    pub fn disable_access_log(&mut self) {
+        self.access_log = None;
+    }
+
+    /// Restricts the access log to `[start, end)`, or records every address
+    /// with `None`. Has no effect while logging is disabled.
+    pub fn set_access_log_filter(&mut self, filter: Option<(u64, u64)>) {
+        if let Some(log) = &mut self.access_log {
+            log.set_filter(filter);
+        }
+    }
+
+    /// Sets the PC recorded with subsequent accesses.
+    #[inline]
+    pub fn set_access_pc(&mut self, pc: u64) {
+        self.access_pc = pc;
+    }
+
+    /// Removes and returns the recorded accesses, oldest first; empty when
+    /// logging is disabled.
+    pub fn drain_access_log(&mut self) -> Vec<BusAccess> {
+        self.access_log
+            .as_mut()
+            .map(AccessLog::drain)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn log_access(&mut self, addr: u64, size: u8, is_write: bool, value: u64) {
+        if let Some(log) = &mut self.access_log {
+            log.record(BusAccess {
+                pc: self.access_pc,
+                addr,
+                size,
+                is_write,
+                value,
+            });
         }
     }
 
@@ -56,12 +142,34 @@ impl Bus {
     pub fn add_device(&mut self, dev: Box<dyn Device + Send + Sync>) {
         self.devices.push(dev);
         self.devices.sort_by_key(|d| d.address_range().0);
+        let mut reach = 0;
+        self.ranges = self
+            .devices
+            .iter()
+            .map(|d| {
+                let (start, size) = d.address_range();
+                let end = start.saturating_add(size);
+                reach = reach.max(end);
+                DeviceRange { start, end, reach }
+            })
+            .collect();
         self.ram_idx = self.devices.iter().position(|d| d.name() == "DRAM");
         self.uart_idx = self.devices.iter().position(|d| d.name() == "UART0");
         self.htif_idx = self.devices.iter().position(|d| d.name() == "HTIF");
+        self.clint_idx = self.devices.iter().position(|d| d.name() == "CLINT");
         self.last_device_idx = 0;
     }
 
+    /// Returns every registered device as `(name, start, end)` in address
+    /// order, where `end` is one past the last address it claims.
+    pub fn memory_map(&self) -> Vec<(String, u64, u64)> {
+        self.devices
+            .iter()
+            .zip(&self.ranges)
+            .map(|(dev, range)| (dev.name().to_string(), range.start, range.end))
+            .collect()
+    }
+
     /// Returns the number of cycles to transfer the given number of bytes on this bus.
     ///
     /// # Arguments
@@ -76,24 +184,64 @@ impl Bus {
         self.latency_cycles + transfers
     }
 
+    /// Sets the clock-domain-crossing latency of the device mapped at `base`.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - Any address inside the device's range (usually its base).
+    /// * `cycles` - Synchronizer cycles added to every access to the device.
+    pub fn set_cdc_latency(&mut self, base: u64, cycles: u64) {
+        let Some(range) = self
+            .devices
+            .iter()
+            .map(|d| d.address_range())
+            .find(|&(start, size)| base >= start && base < start + size)
+        else {
+            return;
+        };
+        let (start, size) = range;
+        self.cdc_latency.retain(|&(s, _, _)| s != start);
+        if cycles > 0 {
+            self.cdc_latency.push((start, start + size, cycles));
+        }
+    }
+
+    /// Returns the number of cycles for an MMIO access to a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `paddr` - Physical address of the access.
+    /// * `bytes` - Number of bytes transferred.
+    ///
+    /// # Returns
+    ///
+    /// Cycles = bus transit time plus the clock-domain-crossing latency of
+    /// the device at `paddr` (0 if none is configured).
+    pub fn device_access_time(&self, paddr: u64, bytes: usize) -> u64 {
+        let cdc = self
+            .cdc_latency
+            .iter()
+            .find(|&&(start, end, _)| paddr >= start && paddr < end)
+            .map_or(0, |&(_, _, cycles)| cycles);
+        self.calculate_transit_time(bytes) + cdc
+    }
+
     /// Writes a binary blob into memory at the given physical address.
     ///
-    /// If a device claims the range, writes via that device; otherwise falls back to byte-by-byte write.
+    /// If a device claims the range, writes via that device; otherwise falls back to byte-by-byte write,
+    /// dropping bytes that no device claims.
     ///
     /// # Arguments
     ///
     /// * `data` - Bytes to write.
     /// * `addr` - Physical base address.
     pub fn load_binary_at(&mut self, data: &[u8], addr: u64) {
-        if let Some((dev, offset)) = self.find_device(addr) {
-            let (_, size) = dev.address_range();
-            if offset + (data.len() as u64) <= size {
-                dev.write_bytes(offset, data);
-                return;
-            }
+        if let Ok((dev, offset)) = self.find_device(addr, data.len() as u64) {
+            dev.write_bytes(offset, data);
+            return;
         }
         for (i, byte) in data.iter().enumerate() {
-            self.write_u8(addr + i as u64, *byte);
+            let _ = self.write_u8(addr + i as u64, *byte);
         }
     }
 
@@ -107,44 +255,72 @@ impl Bus {
     ///
     /// `true` if some device's range contains `paddr`.
     pub fn is_valid_address(&self, paddr: u64) -> bool {
-        if let Some(idx) = self.ram_idx {
-            let (start, size) = self.devices[idx].address_range();
-            if paddr >= start && paddr < start + size {
-                return true;
-            }
+        if let Some(idx) = self.ram_idx
+            && self.ranges[idx].contains(paddr)
+        {
+            return true;
+        }
+        self.device_index(paddr).is_some()
+    }
+
+    /// Checks that a `len`-byte access at `paddr` lies entirely within one
+    /// device, without performing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BusError::Unmapped` if no device claims `paddr`, or
+    /// `BusError::OutOfBounds` if the access runs past the end of the device.
+    pub fn check_access(&self, paddr: u64, len: u64) -> Result<(), BusError> {
+        let idx = self.device_index(paddr).ok_or(BusError::Unmapped(paddr))?;
+        if self.ranges[idx].end - paddr < len {
+            return Err(BusError::OutOfBounds(paddr));
         }
-        for dev in &self.devices {
-            let (start, size) = dev.address_range();
-            if paddr >= start && paddr < start + size {
-                return true;
+        Ok(())
+    }
+
+    /// Returns the index of the device claiming `paddr` by binary search.
+    ///
+    /// Where ranges nest (HTIF inside RAM), the innermost device wins.
+    fn device_index(&self, paddr: u64) -> Option<usize> {
+        let mut i = self.ranges.partition_point(|r| r.start <= paddr);
+        while i > 0 {
+            i -= 1;
+            let range = &self.ranges[i];
+            if range.contains(paddr) {
+                return Some(i);
+            }
+            if range.reach <= paddr {
+                return None;
             }
         }
-        false
+        None
     }
 
     /// Advances all devices by one tick and updates PLIC; returns IRQ flags.
     ///
     /// # Returns
     ///
-    /// (timer_irq, meip, seip) for machine timer, machine external, and supervisor external interrupt.
-    pub fn tick(&mut self) -> (bool, bool, bool) {
-        let mut timer_irq = false;
+    /// (clint, meip, seip): the CLINT's per-hart timer and software interrupt
+    /// bitmaps, then the machine and supervisor external interrupt lines.
+    pub fn tick(&mut self) -> (ClintIrqs, bool, bool) {
         let mut active_irqs = 0u64;
 
         for i in 0..self.devices.len() {
             let dev = &mut self.devices[i];
-            if dev.tick() {
-                if let Some(id) = dev.get_irq_id()
-                    && id < 64
-                {
-                    active_irqs |= 1 << id;
-                }
-                if dev.name() == "CLINT" {
-                    timer_irq = true;
-                }
+            if dev.tick()
+                && let Some(id) = dev.get_irq_id()
+                && id < 64
+            {
+                active_irqs |= 1 << id;
             }
         }
 
+        let clint = self
+            .clint_idx
+            .and_then(|idx| self.devices[idx].as_clint())
+            .map(Clint::pending)
+            .unwrap_or_default();
+
         let (meip, seip) = if let Some(plic) = self.find_plic() {
             plic.update_irqs(active_irqs);
             plic.check_interrupts()
@@ -152,7 +328,14 @@ impl Bus {
             (false, false)
         };
 
-        (timer_irq, meip, seip)
+        (clint, meip, seip)
+    }
+
+    /// Returns the CLINT's `mtime`, or `None` if no CLINT is registered.
+    pub fn mtime(&self) -> Option<u64> {
+        self.clint_idx
+            .and_then(|idx| self.devices[idx].as_clint())
+            .map(Clint::mtime)
     }
 
     /// Returns whether the UART device has detected a kernel panic pattern (for test harnesses).
@@ -187,6 +370,72 @@ impl Bus {
         None
     }
 
+    /// Collects the checkpoint state of every stateful device.
+    ///
+    /// # Returns
+    ///
+    /// `(name, base_address, state)` for each device whose `save_state`
+    /// returns data, in address order.
+    pub fn save_device_states(&self) -> Vec<(String, u64, Vec<u8>)> {
+        self.devices
+            .iter()
+            .filter_map(|dev| {
+                let state = dev.save_state()?;
+                Some((dev.name().to_string(), dev.address_range().0, state))
+            })
+            .collect()
+    }
+
+    /// Restores device state saved by `save_device_states`.
+    ///
+    /// Each entry is matched to the device with the same name and base
+    /// address. The restore is all-or-nothing: every entry is matched before
+    /// any device is touched, and if a device rejects its state the devices
+    /// already restored are put back as they were.
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - `(name, base_address, state)` entries.
+    ///
+    /// # Returns
+    ///
+    /// An error if an entry has no matching device or the device rejects it.
+    pub fn restore_device_states(
+        &mut self,
+        states: &[(String, u64, Vec<u8>)],
+    ) -> Result<(), String> {
+        let mut targets = Vec::with_capacity(states.len());
+        for (name, base, state) in states {
+            let idx = self
+                .devices
+                .iter()
+                .position(|d| d.name() == name && d.address_range().0 == *base)
+                .ok_or_else(|| format!("no device {name} at {base:#x}"))?;
+            targets.push((idx, state));
+        }
+        let mut undo: Vec<(usize, Option<Vec<u8>>)> = Vec::with_capacity(targets.len());
+        for (idx, state) in targets {
+            let previous = self.devices[idx].save_state();
+            if let Err(e) = self.devices[idx].restore_state(state) {
+                for (idx, previous) in undo.into_iter().rev() {
+                    if let Some(previous) = previous {
+                        let _ = self.devices[idx].restore_state(&previous);
+                    }
+                }
+                return Err(e);
+            }
+            undo.push((idx, previous));
+        }
+        Ok(())
+    }
+
+    /// Returns the framebuffer device, if one is registered.
+    pub fn framebuffer(&mut self) -> Option<&mut crate::soc::devices::Framebuffer> {
+        self.devices
+            .iter_mut()
+            .find_map(|dev| dev.as_framebuffer_mut())
+    }
+
     fn find_plic(&mut self) -> Option<&mut crate::soc::devices::Plic> {
         for dev in &mut self.devices {
             if let Some(plic) = dev.as_plic_mut() {
@@ -196,96 +445,126 @@ impl Bus {
         None
     }
 
-    fn find_device(&mut self, paddr: u64) -> Option<(&mut Box<dyn Device + Send + Sync>, u64)> {
-        // HTIF sits inside the RAM range so must be checked before any RAM
-        // fast-path (last_device_idx cache or ram_idx shortcut).
-        if let Some(idx) = self.htif_idx {
-            let (start, size) = self.devices[idx].address_range();
-            if paddr >= start && paddr < start + size {
-                self.last_device_idx = idx;
-                return Some((&mut self.devices[idx], paddr - start));
-            }
+    /// Returns the RAM device index if `paddr` is ordinary RAM.
+    ///
+    /// HTIF sits inside the RAM range, so its window is excluded.
+    #[inline]
+    fn ram_hit(&self, paddr: u64) -> Option<usize> {
+        let idx = self.ram_idx?;
+        if !self.ranges[idx].contains(paddr) {
+            return None;
         }
-
-        if self.last_device_idx < self.devices.len() {
-            let (start, size) = self.devices[self.last_device_idx].address_range();
-            if paddr >= start && paddr < start + size {
-                return Some((&mut self.devices[self.last_device_idx], paddr - start));
-            }
+        if let Some(htif) = self.htif_idx
+            && self.ranges[htif].contains(paddr)
+        {
+            return None;
         }
+        Some(idx)
+    }
 
-        if let Some(idx) = self.ram_idx {
-            let (start, size) = self.devices[idx].address_range();
-            if paddr >= start && paddr < start + size {
-                self.last_device_idx = idx;
-                return Some((&mut self.devices[idx], paddr - start));
+    /// Returns the device claiming `paddr` and the device-relative offset,
+    /// failing if no device claims it or the `len`-byte access would run past
+    /// the end of the device.
+    fn find_device(
+        &mut self,
+        paddr: u64,
+        len: u64,
+    ) -> Result<(&mut Box<dyn Device + Send + Sync>, u64), BusError> {
+        // RAM takes the overwhelming majority of accesses; route it without
+        // disturbing the last-device hint, which then stays on the MMIO device.
+        if let Some(idx) = self.ram_hit(paddr) {
+            let range = &self.ranges[idx];
+            if range.end - paddr < len {
+                return Err(BusError::OutOfBounds(paddr));
             }
+            let start = range.start;
+            return Ok((&mut self.devices[idx], paddr - start));
         }
 
-        for (i, dev) in self.devices.iter_mut().enumerate() {
-            let (start, size) = dev.address_range();
-            if paddr >= start && paddr < start + size {
-                self.last_device_idx = i;
-                return Some((dev, paddr - start));
-            }
+        let idx = if let Some(idx) = self.htif_idx
+            && self.ranges[idx].contains(paddr)
+        {
+            idx
+        } else if self.last_device_idx < self.ranges.len()
+            && self.ranges[self.last_device_idx].contains(paddr)
+        {
+            self.last_device_idx
+        } else {
+            self.device_index(paddr).ok_or(BusError::Unmapped(paddr))?
+        };
+        self.last_device_idx = idx;
+        let range = &self.ranges[idx];
+        if range.end - paddr < len {
+            return Err(BusError::OutOfBounds(paddr));
         }
-        None
+        let start = range.start;
+        Ok((&mut self.devices[idx], paddr - start))
     }
 
-    /// Reads one byte at the given physical address; returns 0 if no device claims the address.
-    pub fn read_u8(&mut self, paddr: u64) -> u8 {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.read_u8(offset)
-        } else {
-            0
-        }
+    /// Reads one byte at the given physical address; fails if no device claims the address.
+    pub fn read_u8(&mut self, paddr: u64) -> Result<u8, BusError> {
+        let val = self
+            .find_device(paddr, 1)
+            .map(|(dev, offset)| dev.read_u8(offset));
+        self.log_access(paddr, 1, false, val.unwrap_or(0) as u64);
+        val
     }
-    /// Reads two bytes (little-endian) at the given physical address; returns 0 if unclaimed.
-    pub fn read_u16(&mut self, paddr: u64) -> u16 {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.read_u16(offset)
-        } else {
-            0
-        }
+    /// Reads two bytes (little-endian) at the given physical address; fails if unclaimed
+    /// or if the access runs past the end of the device.
+    pub fn read_u16(&mut self, paddr: u64) -> Result<u16, BusError> {
+        let val = self
+            .find_device(paddr, 2)
+            .map(|(dev, offset)| dev.read_u16(offset));
+        self.log_access(paddr, 2, false, val.unwrap_or(0) as u64);
+        val
     }
-    /// Reads four bytes (little-endian) at the given physical address; returns 0 if unclaimed.
-    pub fn read_u32(&mut self, paddr: u64) -> u32 {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.read_u32(offset)
-        } else {
-            0
-        }
+    /// Reads four bytes (little-endian) at the given physical address; fails if unclaimed
+    /// or if the access runs past the end of the device.
+    pub fn read_u32(&mut self, paddr: u64) -> Result<u32, BusError> {
+        let val = self
+            .find_device(paddr, 4)
+            .map(|(dev, offset)| dev.read_u32(offset));
+        self.log_access(paddr, 4, false, val.unwrap_or(0) as u64);
+        val
     }
-    /// Reads eight bytes (little-endian) at the given physical address; returns 0 if unclaimed.
-    pub fn read_u64(&mut self, paddr: u64) -> u64 {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.read_u64(offset)
-        } else {
-            0
-        }
+    /// Reads eight bytes (little-endian) at the given physical address; fails if unclaimed
+    /// or if the access runs past the end of the device.
+    pub fn read_u64(&mut self, paddr: u64) -> Result<u64, BusError> {
+        let val = self
+            .find_device(paddr, 8)
+            .map(|(dev, offset)| dev.read_u64(offset));
+        self.log_access(paddr, 8, false, val.unwrap_or(0));
+        val
     }
-    /// Writes one byte at the given physical address; no-op if no device claims it.
-    pub fn write_u8(&mut self, paddr: u64, val: u8) {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.write_u8(offset, val);
-        }
+    /// Writes one byte at the given physical address; fails if no device claims it.
+    pub fn write_u8(&mut self, paddr: u64, val: u8) -> Result<(), BusError> {
+        self.log_access(paddr, 1, true, val as u64);
+        let (dev, offset) = self.find_device(paddr, 1)?;
+        dev.write_u8(offset, val);
+        Ok(())
     }
-    /// Writes two bytes (little-endian) at the given physical address; no-op if unclaimed.
-    pub fn write_u16(&mut self, paddr: u64, val: u16) {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.write_u16(offset, val);
-        }
+    /// Writes two bytes (little-endian) at the given physical address; fails if unclaimed
+    /// or if the access runs past the end of the device.
+    pub fn write_u16(&mut self, paddr: u64, val: u16) -> Result<(), BusError> {
+        self.log_access(paddr, 2, true, val as u64);
+        let (dev, offset) = self.find_device(paddr, 2)?;
+        dev.write_u16(offset, val);
+        Ok(())
     }
-    /// Writes four bytes (little-endian) at the given physical address; no-op if unclaimed.
-    pub fn write_u32(&mut self, paddr: u64, val: u32) {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.write_u32(offset, val);
-        }
+    /// Writes four bytes (little-endian) at the given physical address; fails if unclaimed
+    /// or if the access runs past the end of the device.
+    pub fn write_u32(&mut self, paddr: u64, val: u32) -> Result<(), BusError> {
+        self.log_access(paddr, 4, true, val as u64);
+        let (dev, offset) = self.find_device(paddr, 4)?;
+        dev.write_u32(offset, val);
+        Ok(())
     }
-    /// Writes eight bytes (little-endian) at the given physical address; no-op if unclaimed.
-    pub fn write_u64(&mut self, paddr: u64, val: u64) {
-        if let Some((dev, offset)) = self.find_device(paddr) {
-            dev.write_u64(offset, val);
-        }
+    /// Writes eight bytes (little-endian) at the given physical address; fails if unclaimed
+    /// or if the access runs past the end of the device.
+    pub fn write_u64(&mut self, paddr: u64, val: u64) -> Result<(), BusError> {
+        self.log_access(paddr, 8, true, val);
+        let (dev, offset) = self.find_device(paddr, 8)?;
+        dev.write_u64(offset, val);
+        Ok(())
     }
 }
@@ -107,6 +107,18 @@ impl DramBuffer {
         }
     }
 
+    /// Fills the whole buffer with `pattern` repeated in little-endian order.
+    ///
+    /// Touches every page, so it gives up the lazy allocation of `mmap`.
+    pub fn fill(&self, pattern: u32) {
+        let bytes = pattern.to_le_bytes();
+        // SAFETY: `ptr` is valid for `size` bytes for the buffer's lifetime.
+        let buf = unsafe { slice::from_raw_parts_mut(self.ptr, self.size) };
+        for chunk in buf.chunks_mut(4) {
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
     /// Reads a slice of memory safely.
     pub fn read_slice(&self, offset: usize, len: usize) -> &[u8] {
         assert!(offset + len <= self.size, "DRAM read out of bounds");
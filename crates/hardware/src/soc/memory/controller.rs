@@ -2,10 +2,28 @@
 //!
 //! This module provides:
 //! 1. **SimpleController:** Fixed latency per access (no row-buffer modeling).
-//! 2. **DramController:** Row-buffer-aware latency (CAS, RAS, precharge) for DRAM-style timing.
+//! 2. **DramController:** Multi-channel, multi-bank DRAM timing with per-bank row buffers
+//!    (CAS, RAS, precharge), separate write timing (CWL, write recovery, and write-to-read
+//!    turnaround), open- or closed-page row buffer policy, periodic refresh, and
+//!    bank-level parallelism.
+//! 3. **Request Scheduling:** Batches of outstanding requests are scheduled together
+//!    (FR-FCFS for DRAM), so independent misses overlap instead of costing their sum.
 //!
 //! Controllers are `Send + Sync` for use with the Python bindings and multi-threaded simulation.
 
+use crate::config::PagePolicy;
+
+/// A request waiting in the memory controller's queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemRequest {
+    /// Physical address being accessed.
+    pub addr: u64,
+    /// `true` for a write, `false` for a read.
+    pub is_write: bool,
+    /// Cycle at which the request reaches the controller.
+    pub arrival: u64,
+}
+
 /// Trait for memory controller implementations that report access latency in cycles.
 ///
 /// Implementors must be `Send + Sync` for thread-safe use with the bus and Python bindings.
@@ -15,14 +33,42 @@ pub trait MemoryController: Send + Sync {
     /// # Arguments
     ///
     /// * `addr` - Physical address being accessed (may be used for row-buffer modeling).
+    /// * `is_write` - `true` for a write, which may be timed differently from a read.
+    /// * `now` - Current cycle, used to model contention with earlier accesses.
+    ///
+    /// # Returns
+    ///
+    /// Latency in simulation cycles, including any wait for a busy resource.
+    fn access_latency(&mut self, addr: u64, is_write: bool, now: u64) -> u64;
+
+    /// Schedules a batch of outstanding requests and returns the cycle at
+    /// which each one completes.
+    ///
+    /// The default implementation serves requests first-come first-served
+    /// through [`access_latency`](Self::access_latency). Controllers with a
+    /// request queue override it to reorder the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - Requests in flight together, in any order.
     ///
     /// # Returns
     ///
-    /// Latency in simulation cycles.
-    fn access_latency(&mut self, addr: u64) -> u64;
+    /// Completion cycles, in the same order as `requests`.
+    fn schedule(&mut self, requests: &[MemRequest]) -> Vec<u64> {
+        let mut order: Vec<usize> = (0..requests.len()).collect();
+        order.sort_by_key(|&i| requests[i].arrival);
+        let mut done = vec![0; requests.len()];
+        for i in order {
+            let req = requests[i];
+            done[i] = req.arrival + self.access_latency(req.addr, req.is_write, req.arrival);
+        }
+        done
+    }
 }
 
-/// Fixed-latency memory controller; every access takes the same number of cycles.
+/// Fixed-latency memory controller; every access, read or write, takes the
+/// same number of cycles.
 pub struct SimpleController {
     latency: u64,
 }
@@ -43,22 +89,76 @@ impl SimpleController {
 }
 
 impl MemoryController for SimpleController {
-    fn access_latency(&mut self, _addr: u64) -> u64 {
+    fn access_latency(&mut self, _addr: u64, _is_write: bool, _now: u64) -> u64 {
         self.latency
     }
 }
 
-/// DRAM-style controller with row buffer; models CAS, RAS, and precharge latencies.
+/// Size of a DRAM row (one row buffer's worth of data) in bytes.
+const ROW_BYTES: u64 = 2048;
+
+/// Row-buffer and timing state of a single DRAM bank.
+#[derive(Clone, Copy, Default)]
+struct Bank {
+    /// Row currently latched in the row buffer, if any.
+    open_row: Option<u64>,
+    /// Cycle at which the bank finishes its current access.
+    ready_at: u64,
+    /// Cycle at which the bank's current access started.
+    started_at: u64,
+}
+
+/// DRAM-style controller with per-bank row buffers; models CAS, RAS, and
+/// precharge latencies.
+///
+/// Writes use the column write latency (CWL) in place of CAS and keep their
+/// bank busy for the write recovery time afterwards. A read in the same
+/// channel cannot start until the write-to-read turnaround has passed since
+/// the channel's last write completed.
+///
+/// Addresses are interleaved across channels, then banks, in blocks of
+/// `interleave_granularity` bytes. Under the open-page policy each bank
+/// keeps its own open row; under the closed-page policy it precharges right
+/// after each access, so every access pays activate + CAS. A bank is busy
+/// for the duration of an access (and any precharge after it); accesses to
+/// a busy bank wait for it, while accesses to other banks (in the same or
+/// another channel) proceed in parallel.
+///
+/// With a refresh interval set, every `t_refi` cycles all banks refresh for
+/// `t_rfc` cycles: an access that would start during a refresh waits for it
+/// to finish, and a refresh closes every open row.
+///
+/// Batches passed to [`MemoryController::schedule`] are served FR-FCFS: each
+/// bank picks, among the requests that have arrived by the time it is free,
+/// row hits first, then reads before writes, then the oldest.
 pub struct DramController {
-    last_row: Option<u64>,
+    /// Banks, indexed by `channel * num_banks + bank`.
+    banks: Vec<Bank>,
+    num_banks: u64,
+    num_channels: u64,
+    interleave_granularity: u64,
     t_cas: u64,
     t_ras: u64,
     t_pre: u64,
-    row_mask: u64,
+    /// Column write latency, used for writes in place of `t_cas`.
+    t_cwl: u64,
+    /// Write recovery: cycles a bank stays busy after a write completes.
+    t_wr: u64,
+    /// Write-to-read turnaround within a channel.
+    t_wtr: u64,
+    /// Per channel, the earliest cycle a read may start after the last write.
+    read_ready_at: Vec<u64>,
+    /// Whether banks leave their row open or precharge after each access.
+    page_policy: PagePolicy,
+    /// Refresh interval (tREFI) in cycles; 0 disables refresh.
+    t_refi: u64,
+    /// Refresh cycle time (tRFC): cycles each refresh blocks every bank.
+    t_rfc: u64,
 }
 
 impl DramController {
-    /// Creates a DRAM controller with the given timing parameters (in cycles).
+    /// Creates a single-channel, single-bank DRAM controller with the given
+    /// timing parameters (in cycles).
     ///
     /// # Arguments
     ///
@@ -70,29 +170,225 @@ impl DramController {
     ///
     /// A new `DramController` with no row currently open.
     pub fn new(t_cas: u64, t_ras: u64, t_pre: u64) -> Self {
+        Self::with_geometry(t_cas, t_ras, t_pre, 1, 1, ROW_BYTES)
+    }
+
+    /// Creates a DRAM controller with `num_channels` channels of `num_banks`
+    /// banks each.
+    ///
+    /// # Arguments
+    ///
+    /// * `t_cas` - Column access strobe latency.
+    /// * `t_ras` - Row access strobe latency.
+    /// * `t_pre` - Precharge latency.
+    /// * `num_banks` - Banks per channel (at least 1).
+    /// * `num_channels` - Independent channels (at least 1).
+    /// * `interleave_granularity` - Bytes mapped to one bank before moving to
+    ///   the next channel/bank (at least 1).
+    ///
+    /// # Returns
+    ///
+    /// A new `DramController` with every bank idle and no row open.
+    pub fn with_geometry(
+        t_cas: u64,
+        t_ras: u64,
+        t_pre: u64,
+        num_banks: usize,
+        num_channels: usize,
+        interleave_granularity: u64,
+    ) -> Self {
+        let num_banks = num_banks.max(1);
+        let num_channels = num_channels.max(1);
         Self {
-            last_row: None,
+            banks: vec![Bank::default(); num_banks * num_channels],
+            num_banks: num_banks as u64,
+            num_channels: num_channels as u64,
+            interleave_granularity: interleave_granularity.max(1),
             t_cas,
             t_ras,
             t_pre,
-            row_mask: !2047,
+            t_cwl: t_cas,
+            t_wr: 0,
+            t_wtr: 0,
+            read_ready_at: vec![0; num_channels],
+            page_policy: PagePolicy::Open,
+            t_refi: 0,
+            t_rfc: 0,
         }
     }
+
+    /// Sets the write timing; by default writes are timed like reads.
+    ///
+    /// # Arguments
+    ///
+    /// * `t_cwl` - Column write latency, used for writes in place of CAS.
+    /// * `t_wr` - Write recovery: cycles a bank stays busy after a write.
+    /// * `t_wtr` - Write-to-read turnaround: cycles after a write completes
+    ///   before a read in the same channel may start.
+    ///
+    /// # Returns
+    ///
+    /// The controller with the given write timing.
+    pub fn with_write_timing(mut self, t_cwl: u64, t_wr: u64, t_wtr: u64) -> Self {
+        self.t_cwl = t_cwl;
+        self.t_wr = t_wr;
+        self.t_wtr = t_wtr;
+        self
+    }
+
+    /// Sets the row buffer policy; banks use the open-page policy by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_policy` - `Open` keeps the row open after an access; `Closed`
+    ///   precharges the bank right away.
+    ///
+    /// # Returns
+    ///
+    /// The controller with the given policy.
+    pub fn with_page_policy(mut self, page_policy: PagePolicy) -> Self {
+        self.page_policy = page_policy;
+        self
+    }
+
+    /// Enables periodic refresh; refresh is off by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `t_refi` - Cycles between refreshes (0 disables refresh).
+    /// * `t_rfc` - Cycles each refresh blocks every bank.
+    ///
+    /// # Returns
+    ///
+    /// The controller with the given refresh timing.
+    pub fn with_refresh(mut self, t_refi: u64, t_rfc: u64) -> Self {
+        self.t_refi = t_refi;
+        self.t_rfc = t_rfc;
+        self
+    }
+
+    /// Moves `start` past the refresh in progress at that cycle, if any.
+    /// Refreshes begin at every nonzero multiple of `t_refi`.
+    fn after_refresh(&self, start: u64) -> u64 {
+        if self.t_refi == 0 || start < self.t_refi {
+            return start;
+        }
+        let offset = start % self.t_refi;
+        if offset < self.t_rfc {
+            start - offset + self.t_rfc
+        } else {
+            start
+        }
+    }
+
+    /// Returns `true` if a refresh began after cycle `from` and by `to`,
+    /// closing any row opened by an access started at `from`.
+    fn refreshed_between(&self, from: u64, to: u64) -> bool {
+        self.t_refi != 0 && to / self.t_refi > from / self.t_refi
+    }
+
+    /// Maps an address to `(bank index, row)`.
+    ///
+    /// Consecutive `interleave_granularity` blocks rotate across channels
+    /// first, then across the banks of a channel. The row is taken from the
+    /// bank-local address, i.e. with the channel and bank bits removed.
+    fn map(&self, addr: u64) -> (usize, u64) {
+        let block = addr / self.interleave_granularity;
+        let channel = block % self.num_channels;
+        let bank = (block / self.num_channels) % self.num_banks;
+        let local_block = block / (self.num_channels * self.num_banks);
+        let local_addr =
+            local_block * self.interleave_granularity + addr % self.interleave_granularity;
+        (
+            (channel * self.num_banks + bank) as usize,
+            local_addr / ROW_BYTES,
+        )
+    }
 }
 
 impl MemoryController for DramController {
-    fn access_latency(&mut self, addr: u64) -> u64 {
-        let row = addr & self.row_mask;
-        match self.last_row {
-            Some(open_row) if open_row == row => self.t_cas,
-            Some(_) => {
-                self.last_row = Some(row);
-                self.t_pre + self.t_ras + self.t_cas
-            }
-            None => {
-                self.last_row = Some(row);
-                self.t_ras + self.t_cas
+    fn access_latency(&mut self, addr: u64, is_write: bool, now: u64) -> u64 {
+        let done = self.schedule(&[MemRequest {
+            addr,
+            is_write,
+            arrival: now,
+        }]);
+        done[0] - now
+    }
+
+    fn schedule(&mut self, requests: &[MemRequest]) -> Vec<u64> {
+        let mut done = vec![0; requests.len()];
+        // (request index, bank index, row)
+        let mut pending: Vec<(usize, usize, u64)> = requests
+            .iter()
+            .enumerate()
+            .map(|(i, req)| {
+                let (bank, row) = self.map(req.addr);
+                (i, bank, row)
+            })
+            .collect();
+
+        // Banks are independent, so each one drains its own queue.
+        while let Some(&(_, index, _)) = pending.first() {
+            let (mut queue, rest): (Vec<_>, Vec<_>) =
+                pending.into_iter().partition(|&(_, bank, _)| bank == index);
+            pending = rest;
+
+            while let Some(first_arrival) = queue.iter().map(|&(i, _, _)| requests[i].arrival).min()
+            {
+                let mut bank = self.banks[index];
+                let start = self.after_refresh(bank.ready_at.max(first_arrival));
+                if self.refreshed_between(bank.started_at, start) {
+                    bank.open_row = None;
+                }
+                let Some(pick) = (0..queue.len())
+                    .filter(|&q| requests[queue[q].0].arrival <= start)
+                    .min_by_key(|&q| {
+                        let (i, _, row) = queue[q];
+                        (
+                            bank.open_row != Some(row),
+                            requests[i].is_write,
+                            requests[i].arrival,
+                            i,
+                        )
+                    })
+                else {
+                    break;
+                };
+                let (i, _, row) = queue.swap_remove(pick);
+                let is_write = requests[i].is_write;
+                let channel = index / self.num_banks as usize;
+                let (column, start) = if is_write {
+                    (self.t_cwl, start)
+                } else {
+                    let start = start.max(self.read_ready_at[channel]);
+                    (self.t_cas, self.after_refresh(start))
+                };
+                if self.refreshed_between(bank.started_at, start) {
+                    bank.open_row = None;
+                }
+                let service = match bank.open_row {
+                    Some(open_row) if open_row == row => column,
+                    Some(_) => self.t_pre + self.t_ras + column,
+                    None => self.t_ras + column,
+                };
+                let finish = start + service;
+                let bank = &mut self.banks[index];
+                bank.open_row = Some(row);
+                bank.started_at = start;
+                bank.ready_at = finish;
+                if is_write {
+                    bank.ready_at += self.t_wr;
+                    let ready = &mut self.read_ready_at[channel];
+                    *ready = (*ready).max(finish + self.t_wtr);
+                }
+                if self.page_policy == PagePolicy::Closed {
+                    bank.open_row = None;
+                    bank.ready_at += self.t_pre;
+                }
+                done[i] = finish;
             }
         }
+        done
     }
 }
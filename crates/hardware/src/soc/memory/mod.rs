@@ -119,6 +119,24 @@ impl Device for Memory {
         self.load(data, offset as usize);
     }
 
+    /// Saves the full RAM contents.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        Some(self.buffer.read_slice(0, self.buffer.len()).to_vec())
+    }
+
+    /// Overwrites RAM with a saved image of the same size.
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        if state.len() != self.buffer.len() {
+            return Err(format!(
+                "RAM size mismatch: checkpoint has {} bytes, system has {}",
+                state.len(),
+                self.buffer.len()
+            ));
+        }
+        self.buffer.write_slice(0, state);
+        Ok(())
+    }
+
     /// Downcasts the device to a mutable Memory reference.
     fn as_memory_mut(&mut self) -> Option<&mut Memory> {
         Some(self)
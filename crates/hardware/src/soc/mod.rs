@@ -4,6 +4,9 @@
 //! including the system bus, memory controllers, devices, and the builder
 //! for assembling the system.
 
+/// Ring buffer of recorded bus transactions.
+pub mod access_log;
+
 /// System builder for assembling SoC components.
 pub mod builder;
 
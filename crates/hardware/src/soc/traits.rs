@@ -4,11 +4,12 @@
 //! 1. **Identification:** `name` and `address_range` for bus routing.
 //! 2. **Access:** Byte, half, word, and doubleword read/write at device-relative offsets.
 //! 3. **Lifecycle:** Optional `tick` and IRQ reporting for timer and interrupt devices.
-//! 4. **Downcasting:** Optional casts to `Plic`, `Uart`, or `Memory` for device-specific access.
+//! 4. **Downcasting:** Optional casts to `Clint`, `Plic`, `Uart`, `Framebuffer`, or `Memory` for device-specific access.
+//! 5. **Checkpointing:** Optional save/restore of device state as an opaque byte blob.
 //!
 //! All implementors must be `Send + Sync` for use with the Python bindings and multi-threaded simulation.
 
-use crate::soc::devices::{Plic, Uart};
+use crate::soc::devices::{Clint, Framebuffer, Plic, Uart};
 use crate::soc::memory::Memory;
 
 /// Trait for memory-mapped I/O devices attached to the system bus.
@@ -53,6 +54,24 @@ pub trait Device: Send + Sync {
         None
     }
 
+    /// Serializes the device's guest-visible state for a checkpoint.
+    ///
+    /// Returns `None` for stateless devices (the default); such devices are
+    /// skipped when saving and restoring.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+    /// Restores state produced by `save_state` on a device of the same type.
+    ///
+    /// Returns an error if the blob is malformed or does not fit this device.
+    fn restore_state(&mut self, _state: &[u8]) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Returns a reference as `Clint` if this device is the CLINT; otherwise `None`.
+    fn as_clint(&self) -> Option<&Clint> {
+        None
+    }
     /// Returns a mutable reference as `Plic` if this device is the PLIC; otherwise `None`.
     fn as_plic_mut(&mut self) -> Option<&mut Plic> {
         None
@@ -61,6 +80,10 @@ pub trait Device: Send + Sync {
     fn as_uart_mut(&mut self) -> Option<&mut Uart> {
         None
     }
+    /// Returns a mutable reference as `Framebuffer` if this device is a framebuffer; otherwise `None`.
+    fn as_framebuffer_mut(&mut self) -> Option<&mut Framebuffer> {
+        None
+    }
     /// Returns a mutable reference as `Memory` if this device is RAM; otherwise `None`.
     fn as_memory_mut(&mut self) -> Option<&mut Memory> {
         None
@@ -2,11 +2,20 @@
 //!
 //! This module tracks performance metrics for the RISC-V simulator. It provides:
 //! 1. **Cycle and IPC:** Total cycles, retired instructions, and derived metrics (CPI, MIPS).
-//! 2. **Instruction mix:** Counts by category (ALU, load, store, branch, system, FP).
-//! 3. **Branch prediction:** Lookups, mispredictions, and accuracy.
-//! 4. **Stalls:** Memory, control, and data hazard stall counts.
-//! 5. **Cache hierarchy:** Hit/miss counts for L1-I, L1-D, L2, and L3.
+//! 2. **Instruction mix:** Counts by category (ALU, load, store, branch, jump, atomic, system, FP).
+//! 3. **Branch prediction:** Lookups, mispredictions, and accuracy, overall and
+//!    per branch type (conditional, direct jump, indirect jump, return).
+//! 4. **Stalls:** Memory, control, and data hazard stall counts, plus the
+//!    latency charged to I-cache, D-cache, and TLB accesses.
+//! 5. **CPI stack:** CPI split into base, branch, I-cache, D-cache, TLB, and
+//!    memory components (`cpi_stack`).
+//! 6. **Cache hierarchy:** Hit/miss counts for L1-I, L1-D, L2, and L3.
+//! 7. **Tail latency:** The single slowest memory access, with its PC and address.
+//! 8. **Export:** Every counter plus derived metrics as JSON or CSV (`to_json`, `to_csv`).
 
+use crate::core::arch::mode::PrivilegeMode;
+use crate::core::pipeline::signals::{AluOp, AtomicOp, ControlSignals};
+use std::fmt;
 use std::io::IsTerminal;
 use std::time::Instant;
 
@@ -26,8 +35,12 @@ pub struct SimStats {
     pub inst_load: u64,
     /// Count of integer store instructions retired.
     pub inst_store: u64,
-    /// Count of branch/jump instructions retired.
+    /// Count of conditional branch instructions retired.
     pub inst_branch: u64,
+    /// Count of unconditional jump instructions (JAL, JALR) retired.
+    pub inst_jump: u64,
+    /// Count of atomic instructions (LR, SC, AMO) retired.
+    pub inst_atomic: u64,
     /// Count of ALU (non-load/store/branch/system) instructions retired.
     pub inst_alu: u64,
     /// Count of system (CSR, ECALL, etc.) instructions retired.
@@ -48,6 +61,31 @@ pub struct SimStats {
     pub branch_predictions: u64,
     /// Number of branch predictions that were wrong (mispredictions).
     pub branch_mispredictions: u64,
+    /// Correctly predicted conditional branches.
+    pub cond_branch_predictions: u64,
+    /// Mispredicted conditional branches.
+    pub cond_branch_mispredictions: u64,
+    /// Correctly predicted direct jumps (JAL, including direct calls).
+    pub direct_jump_predictions: u64,
+    /// Mispredicted direct jumps.
+    pub direct_jump_mispredictions: u64,
+    /// Correctly predicted indirect jumps (non-return JALR, including indirect calls).
+    pub indirect_jump_predictions: u64,
+    /// Mispredicted indirect jumps.
+    pub indirect_jump_mispredictions: u64,
+    /// Correctly predicted returns.
+    pub return_predictions: u64,
+    /// Mispredicted returns.
+    pub return_mispredictions: u64,
+    /// Conditional branch updates that trained a threshold-trained
+    /// predictor (the perceptron): mispredicted or `|y| <= threshold`.
+    pub bp_trained: u64,
+    /// Conditional branch updates that skipped training because the
+    /// prediction was correct and above the threshold.
+    pub bp_train_skipped: u64,
+    /// Training threshold after the latest update (0 for predictors
+    /// without one).
+    pub bp_threshold: u64,
 
     /// Cycles spent in user (U) mode.
     pub cycles_user: u64,
@@ -55,6 +93,14 @@ pub struct SimStats {
     pub cycles_kernel: u64,
     /// Cycles spent in machine (M) mode.
     pub cycles_machine: u64,
+    /// Cycles the hart spent asleep in WFI, waiting for an interrupt.
+    pub cycles_wfi: u64,
+    /// Instructions retired in user (U) mode.
+    pub insts_user: u64,
+    /// Instructions retired in supervisor (S) mode.
+    pub insts_kernel: u64,
+    /// Instructions retired in machine (M) mode.
+    pub insts_machine: u64,
 
     /// Stall cycles due to memory (cache/memory not ready).
     pub stalls_mem: u64,
@@ -62,6 +108,19 @@ pub struct SimStats {
     pub stalls_control: u64,
     /// Stall cycles due to data hazards (RAW dependencies).
     pub stalls_data: u64,
+    /// Cycles an instruction with ready operands waited at issue for a busy
+    /// non-pipelined functional unit (structural hazards).
+    pub stalls_struct: u64,
+    /// Issue stall cycles on a half-register dependency (sub-register tracking).
+    pub subreg_dependency_stalls: u64,
+    /// Fetch stall cycles charged for L1 instruction cache access latency.
+    pub stalls_icache: u64,
+    /// Memory-stage stall cycles charged for data cache (and beyond) latency.
+    pub stalls_dcache: u64,
+    /// Fetch stall cycles charged for instruction address translation.
+    pub stalls_itlb: u64,
+    /// Memory-stage stall cycles charged for data address translation.
+    pub stalls_dtlb: u64,
 
     /// Number of traps (exceptions or interrupts) taken.
     pub traps_taken: u64,
@@ -70,10 +129,14 @@ pub struct SimStats {
     pub icache_hits: u64,
     /// L1 instruction cache miss count.
     pub icache_misses: u64,
+    /// L1 instruction cache lines invalidated by this hart's stores.
+    pub icache_invalidations: u64,
     /// L1 data cache hit count.
     pub dcache_hits: u64,
     /// L1 data cache miss count.
     pub dcache_misses: u64,
+    /// Same-cycle L1 data accesses delayed by another access to their bank.
+    pub dcache_bank_conflicts: u64,
     /// L2 cache hit count.
     pub l2_hits: u64,
     /// L2 cache miss count.
@@ -82,6 +145,19 @@ pub struct SimStats {
     pub l3_hits: u64,
     /// L3 cache miss count.
     pub l3_misses: u64,
+
+    /// Largest latency (cycles) of any single simulated memory access.
+    pub max_mem_latency: u64,
+    /// PC of the instruction that incurred `max_mem_latency`.
+    pub max_mem_latency_pc: u64,
+    /// Physical address of the access that incurred `max_mem_latency`.
+    pub max_mem_latency_addr: u64,
+
+    /// Cycles run before the last `reset`, not counted in `cycles`.
+    pub warmup_cycles: u64,
+    /// Instructions retired before the last `reset`, not counted in
+    /// `instructions_retired`.
+    pub warmup_instructions: u64,
 }
 
 impl Default for SimStats {
@@ -94,6 +170,8 @@ impl Default for SimStats {
             inst_load: 0,
             inst_store: 0,
             inst_branch: 0,
+            inst_jump: 0,
+            inst_atomic: 0,
             inst_alu: 0,
             inst_system: 0,
             inst_fp_load: 0,
@@ -103,36 +181,263 @@ impl Default for SimStats {
             inst_fp_div_sqrt: 0,
             branch_predictions: 0,
             branch_mispredictions: 0,
+            cond_branch_predictions: 0,
+            cond_branch_mispredictions: 0,
+            direct_jump_predictions: 0,
+            direct_jump_mispredictions: 0,
+            indirect_jump_predictions: 0,
+            indirect_jump_mispredictions: 0,
+            return_predictions: 0,
+            return_mispredictions: 0,
+            bp_trained: 0,
+            bp_train_skipped: 0,
+            bp_threshold: 0,
             cycles_user: 0,
             cycles_kernel: 0,
             cycles_machine: 0,
+            cycles_wfi: 0,
+            insts_user: 0,
+            insts_kernel: 0,
+            insts_machine: 0,
             stalls_mem: 0,
             stalls_control: 0,
             stalls_data: 0,
+            stalls_struct: 0,
+            subreg_dependency_stalls: 0,
+            stalls_icache: 0,
+            stalls_dcache: 0,
+            stalls_itlb: 0,
+            stalls_dtlb: 0,
             traps_taken: 0,
             icache_hits: 0,
             icache_misses: 0,
+            icache_invalidations: 0,
             dcache_hits: 0,
             dcache_misses: 0,
+            dcache_bank_conflicts: 0,
             l2_hits: 0,
             l2_misses: 0,
             l3_hits: 0,
             l3_misses: 0,
+            max_mem_latency: 0,
+            max_mem_latency_pc: 0,
+            max_mem_latency_addr: 0,
+            warmup_cycles: 0,
+            warmup_instructions: 0,
         }
     }
 }
 
 /// Section names for selective stats output.
 ///
-/// Valid section identifiers: `"summary"`, `"core"`, `"instruction_mix"`, `"branch"`, `"memory"`.
-/// Pass an empty slice to `print_sections` to print all sections.
-pub const STATS_SECTIONS: &[&str] = &["summary", "core", "instruction_mix", "branch", "memory"];
+/// A single exported statistic: a raw counter or a derived metric.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatValue {
+    /// Raw event or cycle count.
+    Count(u64),
+    /// Derived ratio or percentage.
+    Ratio(f64),
+}
+
+impl fmt::Display for StatValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Count(n) => write!(f, "{n}"),
+            Self::Ratio(x) => write!(f, "{x:.6}"),
+        }
+    }
+}
+
+/// CPI broken down by where the cycles went.
+///
+/// Each field is cycles per retired instruction, and the fields sum to the
+/// measured CPI. Built by [`SimStats::cpi_stack`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CpiStack {
+    /// Cycles not attributed to any stall: useful work, data dependencies,
+    /// and structural limits.
+    pub base: f64,
+    /// Branch and jump misprediction recovery.
+    pub branch: f64,
+    /// L1 instruction cache access latency.
+    pub icache: f64,
+    /// Data cache access latency, including the levels and DRAM behind it.
+    pub dcache: f64,
+    /// Instruction and data address translation (TLB misses and page walks).
+    pub tlb: f64,
+    /// Other memory-stage stalls: device accesses and split misaligned accesses.
+    pub memory: f64,
+}
+
+impl CpiStack {
+    /// Returns the components as `(name, cpi)` pairs, base first.
+    pub fn components(&self) -> [(&'static str, f64); 6] {
+        [
+            ("base", self.base),
+            ("branch", self.branch),
+            ("icache", self.icache),
+            ("dcache", self.dcache),
+            ("tlb", self.tlb),
+            ("memory", self.memory),
+        ]
+    }
+
+    /// Returns the sum of all components (the measured CPI).
+    pub fn total(&self) -> f64 {
+        self.components().iter().map(|(_, cpi)| cpi).sum()
+    }
+}
+
+/// Valid section identifiers: `"summary"`, `"core"`, `"cpi_stack"`, `"instruction_mix"`,
+/// `"branch"`, `"memory"`. Pass an empty slice to `print_sections` to print all sections.
+pub const STATS_SECTIONS: &[&str] = &[
+    "summary",
+    "core",
+    "cpi_stack",
+    "instruction_mix",
+    "branch",
+    "memory",
+];
 
 impl SimStats {
+    /// Returns the cycles since simulation start, including warmup. This is
+    /// the architectural cycle count behind `mcycle` and `time`.
+    pub fn total_cycles(&self) -> u64 {
+        self.warmup_cycles.wrapping_add(self.cycles)
+    }
+
+    /// Returns the instructions retired since simulation start, including
+    /// warmup. This is the architectural count behind `minstret`.
+    pub fn total_instructions(&self) -> u64 {
+        self.warmup_instructions
+            .wrapping_add(self.instructions_retired)
+    }
+
+    /// Sets the architectural cycle count, as a write to `mcycle` does.
+    pub fn set_total_cycles(&mut self, val: u64) {
+        self.cycles = val.wrapping_sub(self.warmup_cycles);
+    }
+
+    /// Sets the architectural retired-instruction count, as a write to
+    /// `minstret` does.
+    pub fn set_total_instructions(&mut self, val: u64) {
+        self.instructions_retired = val.wrapping_sub(self.warmup_instructions);
+    }
+
+    /// Clears every counter to start a measurement, folding the cycles and
+    /// instructions so far into `warmup_cycles`/`warmup_instructions` so the
+    /// architectural counts keep advancing monotonically. `bp_threshold`
+    /// describes predictor state rather than an event count and is kept.
+    pub fn reset(&mut self) {
+        *self = Self {
+            warmup_cycles: self.total_cycles(),
+            warmup_instructions: self.total_instructions(),
+            bp_threshold: self.bp_threshold,
+            ..Self::default()
+        };
+    }
+
+    /// Records the latency of a single memory access, keeping the worst one seen.
+    ///
+    /// Ties keep the earliest access.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - PC of the instruction performing the access.
+    /// * `addr` - Physical address accessed.
+    /// * `latency` - Latency in cycles returned by the memory model.
+    pub fn record_mem_latency(&mut self, pc: u64, addr: u64, latency: u64) {
+        if latency > self.max_mem_latency {
+            self.max_mem_latency = latency;
+            self.max_mem_latency_pc = pc;
+            self.max_mem_latency_addr = addr;
+        }
+    }
+
+    /// Counts `count` retired instructions, attributing them to the
+    /// privilege mode they retired in.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Privilege mode at commit.
+    /// * `count` - Instructions retired.
+    pub fn record_retired(&mut self, mode: PrivilegeMode, count: u64) {
+        self.instructions_retired += count;
+        match mode {
+            PrivilegeMode::User => self.insts_user += count,
+            PrivilegeMode::Supervisor => self.insts_kernel += count,
+            PrivilegeMode::Machine => self.insts_machine += count,
+        }
+    }
+
+    /// Classifies a retired instruction into the instruction mix.
+    ///
+    /// Called once per committed instruction. Atomics are counted before
+    /// loads and stores, and jumps separately from conditional branches.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctrl` - Control signals of the committed instruction.
+    pub fn record_instruction(&mut self, ctrl: &ControlSignals) {
+        if ctrl.atomic_op != AtomicOp::None {
+            self.inst_atomic += 1;
+        } else if ctrl.mem_read {
+            if ctrl.fp_reg_write {
+                self.inst_fp_load += 1;
+            } else {
+                self.inst_load += 1;
+            }
+        } else if ctrl.mem_write {
+            if ctrl.rs2_fp {
+                self.inst_fp_store += 1;
+            } else {
+                self.inst_store += 1;
+            }
+        } else if ctrl.jump {
+            self.inst_jump += 1;
+        } else if ctrl.branch {
+            self.inst_branch += 1;
+        } else if ctrl.is_system {
+            self.inst_system += 1;
+        } else {
+            match ctrl.alu {
+                AluOp::FAdd
+                | AluOp::FSub
+                | AluOp::FMul
+                | AluOp::FMin
+                | AluOp::FMax
+                | AluOp::FSgnJ
+                | AluOp::FSgnJN
+                | AluOp::FSgnJX
+                | AluOp::FEq
+                | AluOp::FLt
+                | AluOp::FLe
+                | AluOp::FClass
+                | AluOp::FCvtWS
+                | AluOp::FCvtWUS
+                | AluOp::FCvtLS
+                | AluOp::FCvtLUS
+                | AluOp::FCvtSW
+                | AluOp::FCvtSWU
+                | AluOp::FCvtSL
+                | AluOp::FCvtSLU
+                | AluOp::FCvtSD
+                | AluOp::FCvtDS
+                | AluOp::FMvToX
+                | AluOp::FMvToF => self.inst_fp_arith += 1,
+                AluOp::FDiv | AluOp::FSqrt => self.inst_fp_div_sqrt += 1,
+                AluOp::FMAdd | AluOp::FMSub | AluOp::FNMAdd | AluOp::FNMSub => {
+                    self.inst_fp_fma += 1
+                }
+                _ => self.inst_alu += 1,
+            }
+        }
+    }
+
     /// Prints only the requested statistics sections to stdout.
     ///
-    /// Each element of `sections` should be one of `"summary"`, `"core"`, `"instruction_mix"`,
-    /// `"branch"`, or `"memory"`. Pass an empty slice to print all sections (same as `print()`).
+    /// Each element of `sections` should be one of `"summary"`, `"core"`, `"cpi_stack"`,
+    /// `"instruction_mix"`, `"branch"`, or `"memory"`. Pass an empty slice to print all sections (same as `print()`).
     ///
     /// # Arguments
     ///
@@ -199,6 +504,29 @@ impl SimStats {
                 self.cycles_machine,
                 (self.cycles_machine as f64 / cyc as f64) * 100.0
             );
+            println!(
+                "  cycles.wfi             {} ({:.2}%)",
+                self.cycles_wfi,
+                (self.cycles_wfi as f64 / cyc as f64) * 100.0
+            );
+            for (name, insts, cycles) in [
+                ("user", self.insts_user, self.cycles_user),
+                ("kernel", self.insts_kernel, self.cycles_kernel),
+                ("machine", self.insts_machine, self.cycles_machine),
+            ] {
+                let ipc = if cycles == 0 {
+                    0.0
+                } else {
+                    insts as f64 / cycles as f64
+                };
+                println!(
+                    "  insts.{:<15} {} ({:.2}%, ipc {:.4})",
+                    name,
+                    insts,
+                    (insts as f64 / instr as f64) * 100.0,
+                    ipc
+                );
+            }
             println!(
                 "  stalls.memory          {} ({:.2}%)",
                 self.stalls_mem,
@@ -214,6 +542,44 @@ impl SimStats {
                 self.stalls_data,
                 (self.stalls_data as f64 / cyc as f64) * 100.0
             );
+            println!(
+                "  stalls.struct          {} ({:.2}%)",
+                self.stalls_struct,
+                (self.stalls_struct as f64 / cyc as f64) * 100.0
+            );
+            if self.subreg_dependency_stalls > 0 {
+                println!(
+                    "  stalls.subreg          {} ({:.2}%)",
+                    self.subreg_dependency_stalls,
+                    (self.subreg_dependency_stalls as f64 / cyc as f64) * 100.0
+                );
+            }
+            println!(
+                "  stalls.total           {} ({:.2}%)",
+                self.total_stalls(),
+                (self.total_stalls() as f64 / cyc as f64) * 100.0
+            );
+            println!(
+                "  cycles.ideal           {} ({:.2}%)",
+                self.ideal_cycles(),
+                (self.ideal_cycles() as f64 / cyc as f64) * 100.0
+            );
+            println!("  stall_overhead         {:.2}%", self.stall_overhead_pct());
+            println!("{sep}");
+        }
+        if want("cpi_stack") {
+            let stack = self.cpi_stack();
+            let total = stack.total();
+            println!("{bold}CPI STACK{rst}");
+            for (name, cpi) in stack.components() {
+                let pct = if total > 0.0 {
+                    cpi / total * 100.0
+                } else {
+                    0.0
+                };
+                println!("  cpi.{:<19} {:.4} ({:.2}%)", name, cpi, pct);
+            }
+            println!("  cpi.total              {:.4}", total);
             println!("{sep}");
         }
         if want("instruction_mix") {
@@ -239,16 +605,46 @@ impl SimStats {
                 self.inst_branch,
                 (self.inst_branch as f64 / total_inst) * 100.0
             );
+            println!(
+                "  op.jump                {} ({:.2}%)",
+                self.inst_jump,
+                (self.inst_jump as f64 / total_inst) * 100.0
+            );
+            println!(
+                "  op.atomic              {} ({:.2}%)",
+                self.inst_atomic,
+                (self.inst_atomic as f64 / total_inst) * 100.0
+            );
             println!(
                 "  op.system              {} ({:.2}%)",
                 self.inst_system,
                 (self.inst_system as f64 / total_inst) * 100.0
             );
+            println!(
+                "  op.fp_load             {} ({:.2}%)",
+                self.inst_fp_load,
+                (self.inst_fp_load as f64 / total_inst) * 100.0
+            );
+            println!(
+                "  op.fp_store            {} ({:.2}%)",
+                self.inst_fp_store,
+                (self.inst_fp_store as f64 / total_inst) * 100.0
+            );
             println!(
                 "  op.fp_arith            {} ({:.2}%)",
                 self.inst_fp_arith,
                 (self.inst_fp_arith as f64 / total_inst) * 100.0
             );
+            println!(
+                "  op.fp_fma              {} ({:.2}%)",
+                self.inst_fp_fma,
+                (self.inst_fp_fma as f64 / total_inst) * 100.0
+            );
+            println!(
+                "  op.fp_div_sqrt         {} ({:.2}%)",
+                self.inst_fp_div_sqrt,
+                (self.inst_fp_div_sqrt as f64 / total_inst) * 100.0
+            );
             println!("{sep}");
         }
         if want("branch") {
@@ -260,10 +656,48 @@ impl SimStats {
             } else {
                 0.0
             };
+            let print_kind = |name: &str, correct: u64, miss: u64| {
+                let total = correct + miss;
+                let acc = if total > 0 {
+                    100.0 * (correct as f64 / total as f64)
+                } else {
+                    0.0
+                };
+                println!(
+                    "  {:<22} {:.2}% ({} / {} mispredicted)",
+                    name, acc, miss, total
+                );
+            };
             println!("{bold}BRANCH PREDICTION{rst}");
             println!("  bp.lookups             {}", bp_total);
             println!("  bp.mispredicts         {}", bp_miss);
             println!("  bp.accuracy            {:.2}%", bp_acc);
+            print_kind(
+                "bp.cond.accuracy",
+                self.cond_branch_predictions,
+                self.cond_branch_mispredictions,
+            );
+            print_kind(
+                "bp.jump.accuracy",
+                self.direct_jump_predictions,
+                self.direct_jump_mispredictions,
+            );
+            print_kind(
+                "bp.indirect.accuracy",
+                self.indirect_jump_predictions,
+                self.indirect_jump_mispredictions,
+            );
+            print_kind(
+                "bp.return.accuracy",
+                self.return_predictions,
+                self.return_mispredictions,
+            );
+            if self.bp_trained + self.bp_train_skipped > 0 {
+                println!(
+                    "  bp.trained             {} ({} skipped, threshold {})",
+                    self.bp_trained, self.bp_train_skipped, self.bp_threshold
+                );
+            }
             println!("{sep}");
         }
         if want("memory") {
@@ -287,10 +721,219 @@ impl SimStats {
             print_cache("L1-D", self.dcache_hits, self.dcache_misses);
             print_cache("L2", self.l2_hits, self.l2_misses);
             print_cache("L3", self.l3_hits, self.l3_misses);
+            println!(
+                "  mem.max_latency        {} cycles (pc={:#x} addr={:#x})",
+                self.max_mem_latency, self.max_mem_latency_pc, self.max_mem_latency_addr
+            );
         }
         println!("{rule}");
     }
 
+    /// Returns the total stall cycles across the memory, control, data, and
+    /// structural categories.
+    pub fn total_stalls(&self) -> u64 {
+        self.stalls_mem + self.stalls_control + self.stalls_data + self.stalls_struct
+    }
+
+    /// Returns the cycles the run would take with no stalls: total cycles
+    /// minus all stall cycles.
+    pub fn ideal_cycles(&self) -> u64 {
+        self.cycles.saturating_sub(self.total_stalls())
+    }
+
+    /// Returns the stall overhead in percent: stall cycles relative to the
+    /// ideal (stall-free) cycle count, or 0 if there are no ideal cycles.
+    pub fn stall_overhead_pct(&self) -> f64 {
+        match self.ideal_cycles() {
+            0 => 0.0,
+            ideal => self.total_stalls() as f64 / ideal as f64 * 100.0,
+        }
+    }
+
+    /// Returns the CPI stack: CPI split into base, branch, I-cache, D-cache,
+    /// TLB, and memory components that sum to the measured CPI.
+    ///
+    /// Each stall component is the latency charged to that cause; `memory`
+    /// is the memory-stage stall time not already charged to the D-cache or
+    /// D-TLB. Frontend and backend stalls can overlap, so if the stall
+    /// cycles exceed the total they are scaled down to fit and `base` is 0.
+    /// Returns all zeros before any instruction retires.
+    pub fn cpi_stack(&self) -> CpiStack {
+        if self.instructions_retired == 0 {
+            return CpiStack::default();
+        }
+        let memory = self
+            .stalls_mem
+            .saturating_sub(self.stalls_dcache + self.stalls_dtlb);
+        let stalls = [
+            self.stalls_control,
+            self.stalls_icache,
+            self.stalls_dcache,
+            self.stalls_itlb + self.stalls_dtlb,
+            memory,
+        ];
+        let cycles = self.cycles as f64;
+        let stall_total: u64 = stalls.iter().sum();
+        let scale = if stall_total as f64 > cycles {
+            cycles / stall_total as f64
+        } else {
+            1.0
+        };
+        let insts = self.instructions_retired as f64;
+        let [branch, icache, dcache, tlb, memory] = stalls.map(|c| c as f64 * scale / insts);
+        CpiStack {
+            base: (cycles - stall_total as f64 * scale).max(0.0) / insts,
+            branch,
+            icache,
+            dcache,
+            tlb,
+            memory,
+        }
+    }
+
+    /// Returns every counter and derived metric as `(name, value)` pairs, in
+    /// a stable order.
+    ///
+    /// Names match the keys of the Python stats dict. This is the single
+    /// source for `to_json` and `to_csv`.
+    pub fn entries(&self) -> Vec<(&'static str, StatValue)> {
+        use StatValue::{Count, Ratio};
+
+        let ratio = |num: u64, den: u64| {
+            if den == 0 {
+                0.0
+            } else {
+                num as f64 / den as f64
+            }
+        };
+        let bp_total = self.branch_predictions + self.branch_mispredictions;
+        let cpi = self.cpi_stack();
+        vec![
+            ("cycles", Count(self.cycles)),
+            ("instructions_retired", Count(self.instructions_retired)),
+            ("ipc", Ratio(ratio(self.instructions_retired, self.cycles))),
+            ("cpi", Ratio(ratio(self.cycles, self.instructions_retired))),
+            ("inst_load", Count(self.inst_load)),
+            ("inst_store", Count(self.inst_store)),
+            ("inst_branch", Count(self.inst_branch)),
+            ("inst_jump", Count(self.inst_jump)),
+            ("inst_atomic", Count(self.inst_atomic)),
+            ("inst_alu", Count(self.inst_alu)),
+            ("inst_system", Count(self.inst_system)),
+            ("inst_fp_load", Count(self.inst_fp_load)),
+            ("inst_fp_store", Count(self.inst_fp_store)),
+            ("inst_fp_arith", Count(self.inst_fp_arith)),
+            ("inst_fp_fma", Count(self.inst_fp_fma)),
+            ("inst_fp_div_sqrt", Count(self.inst_fp_div_sqrt)),
+            ("branch_predictions", Count(self.branch_predictions)),
+            ("branch_mispredictions", Count(self.branch_mispredictions)),
+            (
+                "branch_accuracy_pct",
+                Ratio(100.0 * ratio(self.branch_predictions, bp_total)),
+            ),
+            (
+                "cond_branch_predictions",
+                Count(self.cond_branch_predictions),
+            ),
+            (
+                "cond_branch_mispredictions",
+                Count(self.cond_branch_mispredictions),
+            ),
+            (
+                "direct_jump_predictions",
+                Count(self.direct_jump_predictions),
+            ),
+            (
+                "direct_jump_mispredictions",
+                Count(self.direct_jump_mispredictions),
+            ),
+            (
+                "indirect_jump_predictions",
+                Count(self.indirect_jump_predictions),
+            ),
+            (
+                "indirect_jump_mispredictions",
+                Count(self.indirect_jump_mispredictions),
+            ),
+            ("return_predictions", Count(self.return_predictions)),
+            ("return_mispredictions", Count(self.return_mispredictions)),
+            ("bp_trained", Count(self.bp_trained)),
+            ("bp_train_skipped", Count(self.bp_train_skipped)),
+            ("bp_threshold", Count(self.bp_threshold)),
+            ("cycles_user", Count(self.cycles_user)),
+            ("cycles_kernel", Count(self.cycles_kernel)),
+            ("cycles_machine", Count(self.cycles_machine)),
+            ("cycles_wfi", Count(self.cycles_wfi)),
+            ("insts_user", Count(self.insts_user)),
+            ("insts_kernel", Count(self.insts_kernel)),
+            ("insts_machine", Count(self.insts_machine)),
+            ("ipc_user", Ratio(ratio(self.insts_user, self.cycles_user))),
+            (
+                "ipc_kernel",
+                Ratio(ratio(self.insts_kernel, self.cycles_kernel)),
+            ),
+            (
+                "ipc_machine",
+                Ratio(ratio(self.insts_machine, self.cycles_machine)),
+            ),
+            ("stalls_mem", Count(self.stalls_mem)),
+            ("stalls_control", Count(self.stalls_control)),
+            ("stalls_data", Count(self.stalls_data)),
+            ("stalls_struct", Count(self.stalls_struct)),
+            (
+                "subreg_dependency_stalls",
+                Count(self.subreg_dependency_stalls),
+            ),
+            ("stalls_icache", Count(self.stalls_icache)),
+            ("stalls_dcache", Count(self.stalls_dcache)),
+            ("stalls_itlb", Count(self.stalls_itlb)),
+            ("stalls_dtlb", Count(self.stalls_dtlb)),
+            ("ideal_cycles", Count(self.ideal_cycles())),
+            ("stall_overhead_pct", Ratio(self.stall_overhead_pct())),
+            ("cpi_base", Ratio(cpi.base)),
+            ("cpi_branch", Ratio(cpi.branch)),
+            ("cpi_icache", Ratio(cpi.icache)),
+            ("cpi_dcache", Ratio(cpi.dcache)),
+            ("cpi_tlb", Ratio(cpi.tlb)),
+            ("cpi_memory", Ratio(cpi.memory)),
+            ("traps_taken", Count(self.traps_taken)),
+            ("icache_hits", Count(self.icache_hits)),
+            ("icache_misses", Count(self.icache_misses)),
+            ("icache_invalidations", Count(self.icache_invalidations)),
+            ("dcache_hits", Count(self.dcache_hits)),
+            ("dcache_misses", Count(self.dcache_misses)),
+            ("dcache_bank_conflicts", Count(self.dcache_bank_conflicts)),
+            ("l2_hits", Count(self.l2_hits)),
+            ("l2_misses", Count(self.l2_misses)),
+            ("l3_hits", Count(self.l3_hits)),
+            ("l3_misses", Count(self.l3_misses)),
+            ("max_mem_latency", Count(self.max_mem_latency)),
+            ("max_mem_latency_pc", Count(self.max_mem_latency_pc)),
+            ("max_mem_latency_addr", Count(self.max_mem_latency_addr)),
+            ("warmup_cycles", Count(self.warmup_cycles)),
+            ("warmup_instructions", Count(self.warmup_instructions)),
+        ]
+    }
+
+    /// Serializes all statistics as a flat JSON object.
+    pub fn to_json(&self) -> String {
+        let body: Vec<String> = self
+            .entries()
+            .iter()
+            .map(|(name, value)| format!("  \"{name}\": {value}"))
+            .collect();
+        format!("{{\n{}\n}}\n", body.join(",\n"))
+    }
+
+    /// Serializes all statistics as CSV: a header row of names and one row
+    /// of values.
+    pub fn to_csv(&self) -> String {
+        let entries = self.entries();
+        let header: Vec<&str> = entries.iter().map(|(name, _)| *name).collect();
+        let values: Vec<String> = entries.iter().map(|(_, value)| value.to_string()).collect();
+        format!("{}\n{}\n", header.join(","), values.join(","))
+    }
+
     /// Prints all statistics sections to stdout.
     ///
     /// Equivalent to `print_sections(&[])`.
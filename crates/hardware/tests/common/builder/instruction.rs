@@ -1,3 +1,5 @@
+use rvsim_core::isa::privileged::opcodes::OP_SYSTEM;
+use rvsim_core::isa::rv64a::opcodes::OP_AMO;
 use rvsim_core::isa::rv64i::opcodes::*;
 
 pub struct InstructionBuilder {
@@ -17,6 +19,11 @@ impl Default for InstructionBuilder {
 }
 
 impl InstructionBuilder {
+    /// `c.addi x10, 1` in the low half and `c.nop` in the high half.
+    pub const C_ADDI_C_NOP: u32 = 0x0001_0505;
+    /// `c.ebreak` in the low half and `c.nop` in the high half.
+    pub const C_EBREAK_C_NOP: u32 = 0x0001_9002;
+
     pub fn new() -> Self {
         Self {
             opcode: 0,
@@ -330,6 +337,69 @@ impl InstructionBuilder {
         self
     }
 
+    pub fn srli(mut self, rd: u32, rs1: u32, shamt: u32) -> Self {
+        self.opcode = OP_IMM;
+        self.rd = rd;
+        self.rs1 = rs1;
+        self.funct3 = 0b101;
+        self.imm = (shamt & 0x3f) as i32;
+        self
+    }
+
+    // M extension: MUL, DIV, REM
+
+    pub fn mul(mut self, rd: u32, rs1: u32, rs2: u32) -> Self {
+        self.opcode = OP_REG;
+        self.rd = rd;
+        self.rs1 = rs1;
+        self.rs2 = rs2;
+        self.funct3 = 0b000;
+        self.funct7 = 0b0000001;
+        self
+    }
+
+    pub fn div(mut self, rd: u32, rs1: u32, rs2: u32) -> Self {
+        self.opcode = OP_REG;
+        self.rd = rd;
+        self.rs1 = rs1;
+        self.rs2 = rs2;
+        self.funct3 = 0b100;
+        self.funct7 = 0b0000001;
+        self
+    }
+
+    pub fn rem(mut self, rd: u32, rs1: u32, rs2: u32) -> Self {
+        self.opcode = OP_REG;
+        self.rd = rd;
+        self.rs1 = rs1;
+        self.rs2 = rs2;
+        self.funct3 = 0b110;
+        self.funct7 = 0b0000001;
+        self
+    }
+
+    // A extension: AMOADD.W, AMOADD.D
+
+    pub fn amoadd_w(mut self, rd: u32, rs1: u32, rs2: u32) -> Self {
+        self.opcode = OP_AMO;
+        self.rd = rd;
+        self.rs1 = rs1;
+        self.rs2 = rs2;
+        self.funct3 = 0b010;
+        self.funct7 = 0b0000000;
+        self
+    }
+
+    pub fn amoadd_d(mut self, rd: u32, rs1: u32, rs2: u32) -> Self {
+        self.opcode = OP_AMO;
+        self.rd = rd;
+        self.rs1 = rs1;
+        self.rs2 = rs2;
+        self.funct3 = 0b011;
+        self.funct7 = 0b0000000;
+        self
+    }
+
     // Branch variants: BNE, BLT, BGE, BLTU, BGEU
 
     pub fn bne(mut self, rs1: u32, rs2: u32, imm: i32) -> Self {
@@ -377,6 +447,13 @@ impl InstructionBuilder {
         self
     }
 
+    /// EBREAK is the SYSTEM opcode with imm = 1
+    pub fn ebreak(mut self) -> Self {
+        self.opcode = OP_SYSTEM;
+        self.imm = 1;
+        self
+    }
+
     /// NOP is ADDI x0, x0, 0
     pub fn nop(self) -> Self {
         self.addi(0, 0, 0)
@@ -391,11 +468,11 @@ impl InstructionBuilder {
         let funct7 = (self.funct7 & 0x7F) << 25;
 
         match opcode {
-            OP_REG | OP_REG_32 => {
+            OP_REG | OP_REG_32 | OP_AMO => {
                 // R-type: funct7 | rs2 | rs1 | funct3 | rd | opcode
                 funct7 | rs2 | rs1 | funct3 | rd | opcode
             }
-            OP_IMM | OP_IMM_32 | OP_LOAD | OP_JALR => {
+            OP_IMM | OP_IMM_32 | OP_LOAD | OP_JALR | OP_SYSTEM => {
                 // I-type: imm[11:0] | rs1 | funct3 | rd | opcode
                 let imm_val = (self.imm as u32) & 0xFFF;
                 (imm_val << 20) | rs1 | funct3 | rd | opcode
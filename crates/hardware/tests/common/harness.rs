@@ -1,3 +1,4 @@
+use crate::common::builder::instruction::InstructionBuilder;
 use crate::common::mocks::memory::{MockMemory, MockMemoryController};
 use rvsim_core::Simulator;
 use rvsim_core::config::Config;
@@ -7,6 +8,19 @@ use rvsim_core::soc::interconnect::Bus;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 
+/// Address `TestContext::program` loads its program at.
+pub const PROGRAM_BASE: u64 = 0x8000_0000;
+/// Size of the memory `TestContext::program` maps at `PROGRAM_BASE`.
+pub const PROGRAM_MEM_SIZE: usize = 0x1000;
+/// Base of the CLINT, as in `Config::default().system.clint_base`.
+pub const CLINT_BASE: u64 = 0x0200_0000;
+/// M-mode trap handler installed by `TestContext::with_trap_handlers`:
+/// `x9 = 1; j .`.
+pub const M_HANDLER: u64 = PROGRAM_BASE + 0x200;
+/// S-mode trap handler installed by `TestContext::with_trap_handlers`:
+/// `x8 = 1; j .`.
+pub const S_HANDLER: u64 = PROGRAM_BASE + 0x300;
+
 pub struct TestContext {
     pub sim: Simulator,
 }
@@ -19,9 +33,13 @@ impl Default for TestContext {
 
 impl TestContext {
     pub fn new() -> Self {
+        Self::with_config(&Config::default())
+    }
+
+    /// Build a context from an explicit configuration.
+    pub fn with_config(config: &Config) -> Self {
         let _ = env_logger::builder().is_test(true).try_init();
 
-        let config = Config::default();
         let bus = Bus::new(8, 0);
 
         let system = System {
@@ -30,7 +48,7 @@ impl TestContext {
             exit_request: Arc::new(AtomicU64::new(u64::MAX)),
         };
 
-        let mut sim = Simulator::new(system, &config);
+        let mut sim = Simulator::new(system, config);
 
         // In tests, bypass the expensive simulate_memory_access path.
         // The default mmio_base == ram_base (0x8000_0000), which routes all
@@ -43,6 +61,14 @@ impl TestContext {
         Self { sim }
     }
 
+    /// Build a context from `config` with `PROGRAM_MEM_SIZE` bytes of memory
+    /// at `PROGRAM_BASE`, and load `program` there.
+    pub fn program(config: &Config, program: &[u32]) -> Self {
+        Self::with_config(config)
+            .with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE)
+            .load_program(PROGRAM_BASE, program)
+    }
+
     /// Convenience accessor for the CPU.
     pub fn cpu(&self) -> &Cpu {
         &self.sim.cpu
@@ -63,12 +89,27 @@ impl TestContext {
     pub fn load_program(mut self, addr: u64, instructions: &[u32]) -> Self {
         for (i, inst) in instructions.iter().enumerate() {
             let offset = addr + (i as u64) * 4;
-            self.sim.cpu.bus.bus.write_u32(offset, *inst);
+            self.sim.cpu.bus.bus.write_u32(offset, *inst).unwrap();
         }
         self.sim.cpu.pc = addr;
         self
     }
 
+    /// Write the M- and S-mode trap handlers to `M_HANDLER` and `S_HANDLER`
+    /// and point `mtvec` and `stvec` at them.
+    pub fn with_trap_handlers(mut self) -> Self {
+        for (handler, rd) in [(M_HANDLER, 9), (S_HANDLER, 8)] {
+            let bus = &mut self.sim.cpu.bus.bus;
+            bus.write_u32(handler, InstructionBuilder::new().addi(rd, 0, 1).build())
+                .unwrap();
+            bus.write_u32(handler + 4, InstructionBuilder::new().jal(0, 0).build())
+                .unwrap();
+        }
+        self.sim.cpu.csrs.mtvec = M_HANDLER;
+        self.sim.cpu.csrs.stvec = S_HANDLER;
+        self
+    }
+
     /// Set a general-purpose register value.
     pub fn set_reg(&mut self, reg: usize, val: u64) {
         self.sim.cpu.regs.write(reg, val);
@@ -91,4 +132,19 @@ impl TestContext {
             }
         }
     }
+
+    /// Run until the program exits, for at most 10 000 cycles, and assert
+    /// that it exited cleanly.
+    pub fn run_to_exit(&mut self) {
+        self.run(10_000);
+        assert_eq!(self.cpu().exit_code, Some(0), "program should exit cleanly");
+    }
+}
+
+/// Build a context from `config`, load `program` at `PROGRAM_BASE` and run
+/// it to a clean exit.
+pub fn run_program(config: &Config, program: &[u32]) -> TestContext {
+    let mut tc = TestContext::program(config, program);
+    tc.run_to_exit();
+    tc
 }
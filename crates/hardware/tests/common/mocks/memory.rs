@@ -18,7 +18,7 @@ impl MockMemoryController {
 }
 
 impl MemoryController for MockMemoryController {
-    fn access_latency(&mut self, _addr: u64) -> u64 {
+    fn access_latency(&mut self, _addr: u64, _is_write: bool, _now: u64) -> u64 {
         self.latency
     }
 }
@@ -127,6 +127,18 @@ impl Device for MockMemory {
         }
     }
 
+    fn save_state(&self) -> Option<Vec<u8>> {
+        Some(self.data.clone())
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> Result<(), String> {
+        if state.len() != self.data.len() {
+            return Err("MockMemory size mismatch".to_string());
+        }
+        self.data.copy_from_slice(state);
+        Ok(())
+    }
+
     fn as_memory_mut(&mut self) -> Option<&mut Memory> {
         // We cannot downcast to real Memory because we are not it.
         // Return None.
@@ -4,6 +4,7 @@
 //! defaults, and validation.
 
 use rvsim_core::config::*;
+use rvsim_core::core::arch::mode::PrivilegeMode;
 
 #[test]
 fn test_config_default() {
@@ -21,6 +22,28 @@ fn test_general_config_defaults() {
     assert_eq!(general.start_pc, 0x8000_0000);
     assert!(general.direct_mode);
     assert_eq!(general.initial_sp, None);
+    assert_eq!(general.reset_mstatus, None);
+    assert_eq!(general.reset_medeleg, 0);
+    assert_eq!(general.reset_mideleg, 0);
+    assert_eq!(general.reset_privilege, PrivilegeMode::Machine);
+}
+
+#[test]
+fn test_reset_state_options() {
+    let json = r#"{
+        "reset_mstatus": 6144,
+        "reset_medeleg": 45321,
+        "reset_mideleg": 546,
+        "reset_privilege": "S"
+    }"#;
+    let general: GeneralConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(general.reset_mstatus, Some(0x1800));
+    assert_eq!(general.reset_medeleg, 0xb109);
+    assert_eq!(general.reset_mideleg, 0x222);
+    assert_eq!(general.reset_privilege, PrivilegeMode::Supervisor);
+
+    let general: GeneralConfig = serde_json::from_str(r#"{"reset_privilege": "User"}"#).unwrap();
+    assert_eq!(general.reset_privilege, PrivilegeMode::User);
 }
 
 #[test]
@@ -31,11 +54,18 @@ fn test_system_config_defaults() {
     assert_eq!(system.ram_base, 0x8000_0000);
     assert_eq!(system.clint_base, 0x0200_0000);
     assert_eq!(system.syscon_base, 0x0010_0000);
+    assert_eq!(system.rtc_base, 0x0010_1000);
     assert_eq!(system.kernel_offset, 0x0020_0000);
     assert_eq!(system.bus_width, 8);
     assert_eq!(system.bus_latency, 4);
     assert_eq!(system.clint_divider, 10);
     assert!(!system.uart_to_stderr);
+    assert!(!system.framebuffer.enabled);
+    assert_eq!(system.framebuffer.base, 0x4000_0000);
+    assert_eq!(
+        (system.framebuffer.width, system.framebuffer.height),
+        (640, 480)
+    );
 }
 
 #[test]
@@ -47,6 +77,9 @@ fn test_memory_config_defaults() {
     assert_eq!(memory.t_ras, 14);
     assert_eq!(memory.t_pre, 14);
     assert_eq!(memory.row_miss_latency, 120);
+    assert_eq!(memory.num_banks, 1);
+    assert_eq!(memory.num_channels, 1);
+    assert_eq!(memory.interleave_granularity, 2048);
     assert_eq!(memory.tlb_size, 32);
 }
 
@@ -81,6 +114,10 @@ fn test_pipeline_config_defaults() {
     assert_eq!(pipeline.btb_size, 256);
     assert_eq!(pipeline.ras_size, 8);
     assert_eq!(pipeline.misa_override, None);
+    assert_eq!(pipeline.mul_latency, 3);
+    assert_eq!(pipeline.div_latency, 20);
+    assert_eq!(pipeline.fdiv_latency, 20);
+    assert_eq!(pipeline.fsqrt_latency, 20);
 }
 
 #[test]
@@ -99,6 +136,7 @@ fn test_perceptron_config_defaults() {
     let perceptron = PerceptronConfig::default();
     assert_eq!(perceptron.history_length, 0);
     assert_eq!(perceptron.table_bits, 0);
+    assert!(!perceptron.adaptive_threshold);
 }
 
 #[test]
@@ -465,7 +503,7 @@ fn test_json_dram_controller() {
 
 #[test]
 fn test_json_all_replacement_policies() {
-    for policy in &["LRU", "FIFO", "RANDOM", "MRU", "PLRU"] {
+    for policy in &["LRU", "FIFO", "RANDOM", "MRU", "PLRU", "DIP"] {
         let json = format!(
             r#"{{
             "general": {{"trace_instructions": false, "start_pc": 2147483648, "direct_mode": true}},
@@ -488,7 +526,7 @@ fn test_json_all_replacement_policies() {
 
 #[test]
 fn test_json_all_prefetchers() {
-    for prefetcher in &["None", "NextLine", "Stride", "Stream", "Tagged"] {
+    for prefetcher in &["None", "NextLine", "Stride", "Stream", "Tagged", "Markov"] {
         let json = format!(
             r#"{{
             "general": {{"trace_instructions": false, "start_pc": 2147483648, "direct_mode": true}},
@@ -511,7 +549,14 @@ fn test_json_all_prefetchers() {
 
 #[test]
 fn test_json_all_branch_predictors() {
-    for predictor in &["Static", "GShare", "Perceptron", "Tage", "Tournament"] {
+    for predictor in &[
+        "Static",
+        "GShare",
+        "Perceptron",
+        "Tage",
+        "Tournament",
+        "Perfect",
+    ] {
         let json = format!(
             r#"{{
             "general": {{"trace_instructions": false, "start_pc": 2147483648, "direct_mode": true}},
@@ -990,6 +1035,9 @@ fn test_custom_dram_timings() {
             "t_ras": 45,
             "t_pre": 20,
             "row_miss_latency": 200,
+            "num_banks": 8,
+            "num_channels": 2,
+            "interleave_granularity": 64,
             "tlb_size": 64
         },
         "cache": {
@@ -1068,5 +1116,42 @@ fn test_custom_dram_timings() {
     assert_eq!(config.memory.t_ras, 45);
     assert_eq!(config.memory.t_pre, 20);
     assert_eq!(config.memory.row_miss_latency, 200);
+    assert_eq!(config.memory.num_banks, 8);
+    assert_eq!(config.memory.num_channels, 2);
+    assert_eq!(config.memory.interleave_granularity, 64);
     assert_eq!(config.memory.tlb_size, 64);
 }
+
+#[test]
+fn test_cache_random_seed() {
+    let defaults = CacheHierarchyConfig::default();
+    let json = r#"{
+        "l1_i": {}, "l1_d": {}, "l2": {}, "l3": {}
+    }"#;
+    let cache: CacheHierarchyConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(cache.random_seed, defaults.random_seed);
+
+    let json = r#"{
+        "l1_i": {}, "l1_d": {}, "l2": {}, "l3": {}, "random_seed": 42
+    }"#;
+    let cache: CacheHierarchyConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(cache.random_seed, 42);
+}
+
+#[test]
+fn test_gshare_config() {
+    let pipeline = PipelineConfig::default();
+    assert_eq!(pipeline.gshare.table_bits, 12);
+    assert_eq!(pipeline.gshare.history_length, 12);
+    assert_eq!(pipeline.gshare.index, GShareIndex::Xor);
+
+    let json = r#"{"table_bits": 14, "history_length": 20, "index": "pc"}"#;
+    let gshare: GShareConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(gshare.table_bits, 14);
+    assert_eq!(gshare.history_length, 20);
+    assert_eq!(gshare.index, GShareIndex::Pc);
+
+    let gshare: GShareConfig = serde_json::from_str(r#"{"index": "History"}"#).unwrap();
+    assert_eq!(gshare.table_bits, 12);
+    assert_eq!(gshare.index, GShareIndex::History);
+}
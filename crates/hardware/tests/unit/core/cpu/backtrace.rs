@@ -0,0 +1,111 @@
+//! Backtrace Tests.
+//!
+//! Verifies that the call stack is rebuilt from saved `ra`/`fp` frames in
+//! RAM, that malformed chains stop the walk, and that the return address
+//! stack is used when there are no frames to walk.
+
+use crate::common::harness::TestContext;
+use rvsim_core::core::units::bru::BranchPredictor;
+use rvsim_core::isa::abi::{REG_FP, REG_RA};
+use rvsim_core::sim::symbols::SymbolTable;
+use rvsim_core::soc::memory::Memory;
+use rvsim_core::soc::memory::buffer::DramBuffer;
+use std::sync::Arc;
+
+const RAM_BASE: u64 = 0x8000_0000;
+const RAM_SIZE: usize = 0x1_0000;
+const STACK_TOP: u64 = RAM_BASE + 0x8000;
+
+/// A context backed by real DRAM so the CPU's RAM fast path is live.
+fn context() -> TestContext {
+    let mut tc = TestContext::new();
+    let mem = Memory::new(Arc::new(DramBuffer::new(RAM_SIZE)), RAM_BASE);
+    tc.sim.cpu.bus.bus.add_device(Box::new(mem));
+    let (ptr, start, end) = tc.sim.cpu.bus.bus.get_ram_info().expect("DRAM");
+    let cpu = tc.cpu_mut();
+    cpu.ram_ptr = ptr;
+    cpu.ram_start = start;
+    cpu.ram_end = end;
+    tc
+}
+
+/// Writes a frame record (`ra` at `fp - 8`, caller `fp` at `fp - 16`).
+fn write_frame(tc: &mut TestContext, fp: u64, ra: u64, prev_fp: u64) {
+    tc.sim.cpu.bus.bus.write_u64(fp - 8, ra).unwrap();
+    tc.sim.cpu.bus.bus.write_u64(fp - 16, prev_fp).unwrap();
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Frame walk
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn walks_frame_pointer_chain() {
+    let mut tc = context();
+    let (inner, middle, outer) = (STACK_TOP - 0x100, STACK_TOP - 0x80, STACK_TOP);
+    write_frame(&mut tc, inner, RAM_BASE + 0x40, middle);
+    write_frame(&mut tc, middle, RAM_BASE + 0x20, outer);
+    write_frame(&mut tc, outer, RAM_BASE + 0x10, 0);
+    tc.set_reg(REG_FP, inner);
+
+    assert_eq!(
+        tc.cpu().backtrace(),
+        vec![RAM_BASE + 0x40, RAM_BASE + 0x20, RAM_BASE + 0x10]
+    );
+}
+
+#[test]
+fn stops_at_non_ascending_frame() {
+    // A corrupted chain pointing back down the stack must not loop.
+    let mut tc = context();
+    let fp = STACK_TOP - 0x100;
+    write_frame(&mut tc, fp, RAM_BASE + 0x40, fp - 0x40);
+    tc.set_reg(REG_FP, fp);
+
+    assert_eq!(tc.cpu().backtrace(), vec![RAM_BASE + 0x40]);
+}
+
+#[test]
+fn ignores_frame_pointer_outside_ram() {
+    let mut tc = context();
+    tc.set_reg(REG_FP, 0x1000);
+    assert!(tc.cpu().backtrace().is_empty());
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. RAS fallback
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn falls_back_to_ras_innermost_first() {
+    let mut tc = context();
+    tc.set_reg(REG_FP, 0);
+    let bp = &mut tc.cpu_mut().branch_predictor;
+    bp.on_call(RAM_BASE + 0x4);
+    bp.on_call(RAM_BASE + 0x104);
+
+    assert_eq!(tc.cpu().backtrace(), vec![RAM_BASE + 0x104, RAM_BASE + 0x4]);
+}
+
+#[test]
+fn frames_take_precedence_over_ras() {
+    let mut tc = context();
+    let fp = STACK_TOP;
+    write_frame(&mut tc, fp, RAM_BASE + 0x40, 0);
+    tc.set_reg(REG_FP, fp);
+    tc.set_reg(REG_RA, RAM_BASE + 0x80);
+    tc.cpu_mut().branch_predictor.on_call(RAM_BASE + 0x4);
+
+    assert_eq!(tc.cpu().backtrace(), vec![RAM_BASE + 0x40]);
+}
+
+#[test]
+fn backtrace_addresses_symbolize() {
+    let mut tc = context();
+    write_frame(&mut tc, STACK_TOP, RAM_BASE + 0x24, 0);
+    tc.set_reg(REG_FP, STACK_TOP);
+    tc.cpu_mut().symbols = SymbolTable::new([("main".to_string(), RAM_BASE + 0x20, 0)]);
+
+    let frames = tc.cpu().backtrace();
+    assert_eq!(tc.cpu().describe_pc(frames[0]), "0x80000024 <main+0x4>");
+}
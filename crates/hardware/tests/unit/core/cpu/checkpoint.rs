@@ -0,0 +1,260 @@
+//! Architectural Checkpoint Tests.
+//!
+//! Verifies that a checkpoint taken mid-run captures registers, CSRs,
+//! privilege, memory, and device state, that resuming from it (in the same
+//! or a fresh simulator, directly or through a file) reproduces the original
+//! run, and that malformed or mismatched checkpoints are rejected.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{CLINT_BASE, PROGRAM_BASE, PROGRAM_MEM_SIZE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::core::cpu::checkpoint::Checkpoint;
+use rvsim_core::soc::devices::Clint;
+
+/// Loop trip count.
+const ITERATIONS: i32 = 40;
+
+/// Stores `0..ITERATIONS` to consecutive words, then exits.
+fn program() -> Vec<u32> {
+    // Program:
+    //   0: x1 = 0
+    //   4: x2 = ITERATIONS
+    //   8: AUIPC x3, 0
+    //  12: x3 = x3 + 0x400       (data buffer)
+    //  16: SW x1, 0(x3)
+    //  20: x3 = x3 + 4
+    //  24: x1 = x1 + 1
+    //  28: BNE x1, x2, -12
+    //  32: (zero word: exit)
+    vec![
+        InstructionBuilder::new().addi(1, 0, 0).build(),
+        InstructionBuilder::new().addi(2, 0, ITERATIONS).build(),
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new().addi(3, 3, 0x400).build(),
+        InstructionBuilder::new().sw(3, 1, 0).build(),
+        InstructionBuilder::new().addi(3, 3, 4).build(),
+        InstructionBuilder::new().addi(1, 1, 1).build(),
+        InstructionBuilder::new().bne(1, 2, -12).build(),
+    ]
+}
+
+fn context() -> TestContext {
+    TestContext::program(&Config::default(), &program())
+}
+
+/// Architectural results of a finished run.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    exit_code: Option<u64>,
+    regs: Vec<u64>,
+    data: Vec<u32>,
+    instret: u64,
+}
+
+/// Runs to completion and collects the results.
+fn finish(tc: &mut TestContext) -> Outcome {
+    tc.run(2000);
+    let data_base = PROGRAM_BASE + 8 + 0x400;
+    let data = (0..ITERATIONS as u64)
+        .map(|i| tc.sim.cpu.bus.bus.read_u32(data_base + 4 * i).unwrap())
+        .collect();
+    Outcome {
+        exit_code: tc.cpu().exit_code,
+        regs: (0..32).map(|i| tc.get_reg(i)).collect(),
+        data,
+        instret: tc.cpu().stats.instructions_retired,
+    }
+}
+
+/// Runs partway into the loop and takes a checkpoint.
+fn checkpoint_mid_loop(tc: &mut TestContext) -> Checkpoint {
+    tc.run(60);
+    assert!(tc.cpu().exit_code.is_none());
+    let x1 = tc.get_reg(1);
+    assert!(x1 > 0 && x1 < ITERATIONS as u64, "x1 = {x1}");
+    tc.sim.save_checkpoint()
+}
+
+#[test]
+fn saving_does_not_change_the_outcome() {
+    let reference = finish(&mut context());
+
+    let mut tc = context();
+    let _ = checkpoint_mid_loop(&mut tc);
+    let outcome = finish(&mut tc);
+    assert_eq!(outcome.exit_code, Some(0));
+    assert_eq!(outcome, reference);
+}
+
+#[test]
+fn restore_in_same_simulator_replays_the_tail() {
+    let mut tc = context();
+    let ckpt = checkpoint_mid_loop(&mut tc);
+    let first = finish(&mut tc);
+
+    tc.sim.restore_checkpoint(&ckpt).unwrap();
+    assert_eq!(tc.cpu().pc, ckpt.pc());
+    assert!(tc.cpu().exit_code.is_none());
+    assert_eq!(finish(&mut tc), first);
+}
+
+#[test]
+fn restore_into_fresh_simulator_through_file() {
+    let mut tc = context();
+    let ckpt = checkpoint_mid_loop(&mut tc);
+    let path = std::env::temp_dir().join(format!("rvsim_ckpt_{}.ckpt", std::process::id()));
+    ckpt.save(&path).unwrap();
+    let expected = finish(&mut tc);
+
+    let loaded = Checkpoint::load(&path);
+    let _ = std::fs::remove_file(&path);
+    let mut fresh = TestContext::new().with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE);
+    fresh.sim.restore_checkpoint(&loaded.unwrap()).unwrap();
+    assert_eq!(finish(&mut fresh), expected);
+}
+
+#[test]
+fn csrs_privilege_and_fp_registers_are_restored() {
+    let mut tc = context();
+    tc.cpu_mut().csrs.mscratch = 0x1234;
+    tc.cpu_mut().csrs.satp = 0x8000_0000_0000_0042;
+    tc.cpu_mut().csrs.mhpmcounter[5] = 77;
+    tc.cpu_mut().privilege = PrivilegeMode::Supervisor;
+    tc.cpu_mut().regs.write_f(7, 0xFFFF_FFFF_3F80_0000);
+    tc.cpu_mut().stats.cycles = 500;
+    let ckpt = tc.sim.save_checkpoint();
+
+    let mut fresh = TestContext::new().with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE);
+    fresh.cpu_mut().load_reservation = Some(PROGRAM_BASE);
+    fresh.sim.restore_checkpoint(&ckpt).unwrap();
+    let cpu = fresh.cpu();
+    assert_eq!(cpu.csrs.mscratch, 0x1234);
+    assert_eq!(cpu.csrs.satp, 0x8000_0000_0000_0042);
+    assert_eq!(cpu.csrs.mhpmcounter[5], 77);
+    assert_eq!(cpu.privilege, PrivilegeMode::Supervisor);
+    assert_eq!(cpu.regs.read_f(7), 0xFFFF_FFFF_3F80_0000);
+    assert_eq!(cpu.stats.cycles, 500);
+    assert_eq!(cpu.load_reservation, None);
+}
+
+#[test]
+fn device_state_is_restored() {
+    let mut tc = context();
+    tc.sim
+        .cpu
+        .bus
+        .bus
+        .add_device(Box::new(Clint::new(CLINT_BASE, 1)));
+    tc.sim
+        .cpu
+        .bus
+        .bus
+        .write_u64(CLINT_BASE + 0x4000, 0xABCD)
+        .unwrap();
+    let ckpt = tc.sim.save_checkpoint();
+
+    tc.sim
+        .cpu
+        .bus
+        .bus
+        .write_u64(CLINT_BASE + 0x4000, 1)
+        .unwrap();
+    tc.sim.restore_checkpoint(&ckpt).unwrap();
+    assert_eq!(
+        tc.sim.cpu.bus.bus.read_u64(CLINT_BASE + 0x4000).unwrap(),
+        0xABCD
+    );
+}
+
+#[test]
+fn mismatched_memory_map_is_rejected() {
+    let mut tc = context();
+    let ckpt = tc.sim.save_checkpoint();
+
+    let mut other = TestContext::new().with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE + 0x1_0000);
+    assert!(other.sim.restore_checkpoint(&ckpt).is_err());
+
+    let mut smaller = TestContext::new().with_memory(PROGRAM_MEM_SIZE / 2, PROGRAM_BASE);
+    assert!(smaller.sim.restore_checkpoint(&ckpt).is_err());
+}
+
+#[test]
+fn failed_device_restore_leaves_devices_untouched() {
+    const MTIMECMP: u64 = CLINT_BASE + 0x4000;
+    let mut tc = context();
+    let bus = &mut tc.sim.cpu.bus.bus;
+    bus.add_device(Box::new(Clint::new(CLINT_BASE, 1)));
+    bus.write_u64(MTIMECMP, 0xABCD).unwrap();
+    let ckpt = tc.sim.save_checkpoint();
+
+    // The CLINT sorts before RAM, so it is restored before RAM is rejected.
+    let mut smaller = TestContext::new().with_memory(PROGRAM_MEM_SIZE / 2, PROGRAM_BASE);
+    let bus = &mut smaller.sim.cpu.bus.bus;
+    bus.add_device(Box::new(Clint::new(CLINT_BASE, 1)));
+    bus.write_u64(MTIMECMP, 1).unwrap();
+    assert!(smaller.sim.restore_checkpoint(&ckpt).is_err());
+    assert_eq!(smaller.sim.cpu.bus.bus.read_u64(MTIMECMP).unwrap(), 1);
+}
+
+#[test]
+fn malformed_register_file_is_rejected_without_touching_state() {
+    let mut tc = context();
+    let path = std::env::temp_dir().join(format!("rvsim_bad_ckpt_{}.ckpt", std::process::id()));
+    tc.sim.save_checkpoint().save(&path).unwrap();
+    // After the 12-byte header come `pc` (8 bytes), `privilege` (1 byte),
+    // and the `gpr` length; drop the last integer register.
+    let mut bytes = std::fs::read(&path).unwrap();
+    let gpr_len = 12 + 8 + 1;
+    bytes[gpr_len..gpr_len + 8].copy_from_slice(&31u64.to_le_bytes());
+    bytes.drain(gpr_len + 8..gpr_len + 16);
+    std::fs::write(&path, &bytes).unwrap();
+    let ckpt = Checkpoint::load(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let mut fresh = context();
+    fresh.set_reg(5, 0x55);
+    let err = fresh.sim.restore_checkpoint(&ckpt.unwrap()).err().unwrap();
+    assert!(err.contains("31 integer register words"), "got {err}");
+    assert_eq!(fresh.get_reg(5), 0x55);
+    assert_eq!(fresh.cpu().pc, PROGRAM_BASE);
+}
+
+#[test]
+fn load_rejects_truncated_files() {
+    let mut tc = context();
+    let path = std::env::temp_dir().join(format!("rvsim_short_ckpt_{}.ckpt", std::process::id()));
+    tc.sim.save_checkpoint().save(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+    let err = Checkpoint::load(&path).err().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_rejects_other_files() {
+    let path = std::env::temp_dir().join(format!("rvsim_not_ckpt_{}.bin", std::process::id()));
+    std::fs::write(&path, b"definitely not a checkpoint").unwrap();
+    let err = Checkpoint::load(&path).err().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_rejects_other_versions() {
+    let mut tc = context();
+    let path = std::env::temp_dir().join(format!("rvsim_new_ckpt_{}.ckpt", std::process::id()));
+    tc.sim.save_checkpoint().save(&path).unwrap();
+    // The version follows the 8-byte magic.
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[8..12].copy_from_slice(&2u32.to_le_bytes());
+    std::fs::write(&path, &bytes).unwrap();
+    let err = Checkpoint::load(&path).err().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(
+        err.to_string().contains("unsupported checkpoint version 2"),
+        "got {err}"
+    );
+}
@@ -0,0 +1,135 @@
+//! EBREAK Halt Tests.
+//!
+//! Verifies that with `general.ebreak_halt` an `EBREAK` stops the hart and
+//! hands control to the host instead of trapping: the halt is reported as
+//! an outcome, the simulator idles until it is taken, and execution then
+//! resumes after the `EBREAK`. Without the flag, `EBREAK` still traps.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::cpu::semihosting::{
+    ADP_STOPPED_APPLICATION_EXIT, SEMIHOST_ENTRY, SEMIHOST_EXIT, SYS_EXIT,
+};
+use rvsim_core::isa::abi::{REG_A0, REG_A1};
+use rvsim_core::isa::privileged::cause::exception;
+use rvsim_core::sim::simulator::Outcome;
+
+/// Runs `x5 = 1; <marker>; x6 = 1; j .` for 100 cycles, where `marker` is
+/// one or two instruction words.
+fn run(ebreak_halt: bool, marker: &[u32]) -> TestContext {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.general.ebreak_halt = ebreak_halt;
+    let mut program = vec![InstructionBuilder::new().addi(5, 0, 1).build()];
+    program.extend_from_slice(marker);
+    program.push(InstructionBuilder::new().addi(6, 0, 1).build());
+    program.push(InstructionBuilder::new().jal(0, 0).build());
+    let mut tc = TestContext::program(&config, &program).with_trap_handlers();
+    tc.run(100);
+    tc
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Trap vs. halt
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn ebreak_traps_by_default() {
+    assert!(!Config::default().general.ebreak_halt);
+    let tc = run(false, &[InstructionBuilder::new().ebreak().build()]);
+    assert_eq!(tc.get_reg(9), 1, "guest handler runs");
+    assert_eq!(tc.cpu().csrs.mcause, exception::BREAKPOINT);
+    assert_eq!(tc.cpu().halted_at, None);
+}
+
+#[test]
+fn ebreak_halts_without_trapping() {
+    let tc = run(true, &[InstructionBuilder::new().ebreak().build()]);
+    assert_eq!(tc.cpu().halted_at, Some(PROGRAM_BASE + 4));
+    assert_eq!(tc.get_reg(5), 1, "instructions before the ebreak retire");
+    assert_eq!(tc.get_reg(6), 0, "instructions after it do not");
+    assert_eq!(tc.get_reg(9), 0, "no guest trap");
+    assert_eq!(tc.cpu().stats.traps_taken, 0);
+}
+
+#[test]
+fn halted_hart_does_not_advance() {
+    let mut tc = run(true, &[InstructionBuilder::new().ebreak().build()]);
+    let cycles = tc.cpu().stats.total_cycles();
+    tc.run(50);
+    assert_eq!(tc.cpu().stats.total_cycles(), cycles);
+    assert_eq!(tc.get_reg(6), 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Resuming
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn halt_is_reported_once_and_resumes() {
+    let mut tc = run(true, &[InstructionBuilder::new().ebreak().build()]);
+    assert_eq!(tc.sim.take_outcome(), Some(Outcome::Halt(PROGRAM_BASE + 4)));
+    assert_eq!(tc.sim.take_outcome(), None);
+    tc.run(100);
+    assert_eq!(tc.get_reg(6), 1, "execution continues after the ebreak");
+    assert_eq!(tc.get_reg(9), 0);
+}
+
+#[test]
+fn compressed_ebreak_resumes_after_two_bytes() {
+    let mut tc = run(true, &[InstructionBuilder::C_EBREAK_C_NOP]);
+    assert_eq!(tc.sim.take_halt(), Some(PROGRAM_BASE + 4));
+    tc.run(100);
+    assert_eq!(tc.get_reg(6), 1);
+    assert_eq!(tc.get_reg(9), 0);
+}
+
+#[test]
+fn functional_mode_halts() {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.general.ebreak_halt = true;
+    let program = [
+        InstructionBuilder::new().ebreak().build(),
+        InstructionBuilder::new().addi(6, 0, 1).build(),
+        InstructionBuilder::new().jal(0, 0).build(),
+    ];
+    let mut tc = TestContext::program(&config, &program);
+    tc.cpu_mut().set_functional(true);
+    tc.run(20);
+    assert_eq!(tc.sim.take_halt(), Some(PROGRAM_BASE));
+    tc.run(20);
+    assert_eq!(tc.get_reg(6), 1);
+}
+
+#[test]
+fn reset_clears_halt() {
+    let mut tc = run(true, &[InstructionBuilder::new().ebreak().build()]);
+    tc.sim.reset();
+    assert_eq!(tc.cpu().halted_at, None);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Semihosting
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn semihosting_is_still_serviced() {
+    let mut config = Config::default();
+    config.general.ebreak_halt = true;
+    config.general.semihosting = true;
+    let mut tc = TestContext::program(
+        &config,
+        &[
+            SEMIHOST_ENTRY,
+            InstructionBuilder::new().ebreak().build(),
+            SEMIHOST_EXIT,
+        ],
+    );
+    tc.set_reg(REG_A0, SYS_EXIT);
+    tc.set_reg(REG_A1, ADP_STOPPED_APPLICATION_EXIT);
+    tc.run(100);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.cpu().halted_at, None);
+}
@@ -0,0 +1,175 @@
+//! Functional Execution Mode Tests.
+//!
+//! Verifies that functional (fast-forward) mode produces the same
+//! architectural results as detailed simulation, retires exactly one
+//! instruction per tick, leaves caches and predictors untouched, and can be
+//! switched on and off in the middle of a run.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+
+/// Loop trip count.
+const ITERATIONS: i32 = 20;
+
+/// Offset of the data buffer from the program start.
+const DATA_OFFSET: u64 = 0x400;
+
+/// Number of instructions the program retires before the exit word.
+const RETIRED: u64 = 4 + 8 * ITERATIONS as u64 + 4;
+
+const MSCRATCH: u32 = 0x340;
+
+/// `csrrw`/`csrrs rd, csr, rs1`.
+fn csr(funct3: u32, rd: u32, csr: u32, rs1: u32) -> u32 {
+    (csr << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | 0x73
+}
+
+/// Squares `0..ITERATIONS` into memory through loads, stores, and AMOs, then
+/// exercises CSRs and a jump before exiting.
+fn program() -> Vec<u32> {
+    // Program:
+    //   0: AUIPC x3, 0
+    //   4: x3 = x3 + DATA_OFFSET
+    //   8: x1 = 0
+    //  12: x2 = ITERATIONS
+    //  16: x5 = x1 * x1
+    //  20: SW x5, 0(x3)
+    //  24: LW x6, 0(x3)
+    //  28: x7 = x7 + x6
+    //  32: AMOADD.W x8, x1, (x3)     (word becomes x1 * x1 + x1)
+    //  36: x3 = x3 + 4
+    //  40: x1 = x1 + 1
+    //  44: BNE x1, x2, -28
+    //  48: CSRRW x9, mscratch, x7
+    //  52: CSRRS x10, mscratch, x0
+    //  56: JAL x1, 8
+    //  60: x11 = 99                  (skipped)
+    //  64: x12 = x1
+    //  68: (zero word: exit)
+    vec![
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new()
+            .addi(3, 3, DATA_OFFSET as i32)
+            .build(),
+        InstructionBuilder::new().addi(1, 0, 0).build(),
+        InstructionBuilder::new().addi(2, 0, ITERATIONS).build(),
+        InstructionBuilder::new().mul(5, 1, 1).build(),
+        InstructionBuilder::new().sw(3, 5, 0).build(),
+        InstructionBuilder::new().lw(6, 3, 0).build(),
+        InstructionBuilder::new().add(7, 7, 6).build(),
+        InstructionBuilder::new().amoadd_w(8, 3, 1).build(),
+        InstructionBuilder::new().addi(3, 3, 4).build(),
+        InstructionBuilder::new().addi(1, 1, 1).build(),
+        InstructionBuilder::new().bne(1, 2, -28).build(),
+        csr(0b001, 9, MSCRATCH, 7),
+        csr(0b010, 10, MSCRATCH, 0),
+        InstructionBuilder::new().jal(1, 8).build(),
+        InstructionBuilder::new().addi(11, 0, 99).build(),
+        InstructionBuilder::new().addi(12, 1, 0).build(),
+    ]
+}
+
+fn context() -> TestContext {
+    let mut config = Config::default();
+    config.cache.l1_i.enabled = true;
+    config.cache.l1_d.enabled = true;
+    TestContext::program(&config, &program())
+}
+
+/// Architectural results of a finished run.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    exit_code: Option<u64>,
+    regs: Vec<u64>,
+    mscratch: u64,
+    data: Vec<u32>,
+    instret: u64,
+}
+
+/// Runs to completion and collects the results.
+fn finish(tc: &mut TestContext) -> Outcome {
+    tc.run(5000);
+    let data_base = PROGRAM_BASE + DATA_OFFSET;
+    let data = (0..ITERATIONS as u64)
+        .map(|i| tc.sim.cpu.bus.bus.read_u32(data_base + 4 * i).unwrap())
+        .collect();
+    Outcome {
+        exit_code: tc.cpu().exit_code,
+        regs: (0..32).map(|i| tc.get_reg(i)).collect(),
+        mscratch: tc.cpu().csrs.mscratch,
+        data,
+        instret: tc.cpu().stats.instructions_retired,
+    }
+}
+
+#[test]
+fn functional_matches_detailed() {
+    let detailed = finish(&mut context());
+    assert_eq!(detailed.exit_code, Some(0));
+    assert_eq!(detailed.instret, RETIRED);
+    assert_eq!(detailed.data[5], 30);
+    assert_eq!(detailed.regs[11], 0);
+    assert_eq!(detailed.regs[12], PROGRAM_BASE + 60);
+
+    let mut tc = context();
+    tc.cpu_mut().set_functional(true);
+    assert_eq!(finish(&mut tc), detailed);
+}
+
+#[test]
+fn one_instruction_per_tick() {
+    let mut tc = context();
+    tc.cpu_mut().set_functional(true);
+    tc.run(25);
+    assert_eq!(tc.cpu().stats.instructions_retired, 25);
+    assert_eq!(tc.cpu().stats.cycles, 25);
+
+    tc.run(1000);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.cpu().stats.instructions_retired, RETIRED);
+}
+
+#[test]
+fn caches_and_predictors_are_bypassed() {
+    let mut tc = context();
+    tc.cpu_mut().set_functional(true);
+    finish(&mut tc);
+    let stats = &tc.cpu().stats;
+    assert_eq!(stats.icache_hits + stats.icache_misses, 0);
+    assert_eq!(stats.dcache_hits + stats.dcache_misses, 0);
+    assert_eq!(stats.branch_predictions + stats.branch_mispredictions, 0);
+}
+
+#[test]
+fn switching_modes_mid_run() {
+    let reference = finish(&mut context());
+
+    let mut tc = context();
+    tc.run(40);
+    assert!(tc.cpu().stats.instructions_retired > 0);
+    tc.cpu_mut().set_functional(true);
+    tc.run(60);
+    tc.cpu_mut().set_functional(false);
+    tc.run(30);
+    tc.cpu_mut().set_functional(true);
+    tc.run(5);
+    tc.cpu_mut().set_functional(false);
+    assert!(tc.cpu().exit_code.is_none());
+    assert_eq!(finish(&mut tc), reference);
+}
+
+#[test]
+fn ecall_traps_with_architectural_registers() {
+    // a0 = 42, a7 = 93 (exit), ECALL
+    let program = [
+        InstructionBuilder::new().addi(10, 0, 42).build(),
+        InstructionBuilder::new().addi(17, 0, 93).build(),
+        0x0000_0073,
+    ];
+    let mut tc = TestContext::program(&Config::default(), &program);
+    tc.cpu_mut().set_functional(true);
+    tc.run(10);
+    assert_eq!(tc.cpu().exit_code, Some(42));
+    assert_eq!(tc.cpu().stats.instructions_retired, 2);
+}
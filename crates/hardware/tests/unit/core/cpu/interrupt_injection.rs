@@ -0,0 +1,69 @@
+//! Interrupt Injection Tests.
+//!
+//! Verifies that `Cpu::inject_interrupt_at` raises an interrupt at an exact
+//! retired-instruction count, in detailed (including superscalar) and
+//! functional mode, that it stays pending while masked, and that taking it
+//! lowers the pending bit.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::common::Trap;
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr::{MIP_MEIP, MSTATUS_MIE};
+use rvsim_core::isa::privileged::cause::interrupt;
+
+/// Eight `addi x5, x5, 1`, with traps going to `M_HANDLER`.
+fn context(width: usize, functional: bool) -> TestContext {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.pipeline.width = width;
+    let program = [InstructionBuilder::new().addi(5, 5, 1).build(); 8];
+    let mut tc = TestContext::program(&config, &program).with_trap_handlers();
+    tc.cpu_mut().set_functional(functional);
+    tc
+}
+
+#[test]
+fn interrupt_taken_at_exact_instruction_count() {
+    for (width, functional) in [(1, false), (4, false), (1, true)] {
+        let mut tc = context(width, functional);
+        tc.cpu_mut().csrs.mie = MIP_MEIP;
+        tc.cpu_mut().csrs.mstatus |= MSTATUS_MIE;
+        tc.cpu_mut()
+            .inject_interrupt_at(3, Trap::MachineExternalInterrupt)
+            .unwrap();
+        tc.run(500);
+
+        let label = format!("width={width} functional={functional}");
+        assert_eq!(tc.cpu().csrs.mcause, interrupt::MACHINE_EXTERNAL, "{label}");
+        assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE + 12, "{label}");
+        assert_eq!(tc.get_reg(5), 3, "{label}");
+        assert_eq!(tc.cpu().csrs.mip & MIP_MEIP, 0, "taken: {label}");
+    }
+}
+
+#[test]
+fn masked_interrupt_stays_pending() {
+    let mut tc = context(1, false);
+    tc.cpu_mut()
+        .inject_interrupt_at(2, Trap::MachineExternalInterrupt)
+        .unwrap();
+    tc.run(500);
+    assert_eq!(tc.cpu().csrs.mip & MIP_MEIP, MIP_MEIP);
+    assert_ne!(
+        tc.cpu().csrs.mcause,
+        interrupt::MACHINE_EXTERNAL,
+        "not delivered while disabled"
+    );
+}
+
+#[test]
+fn exception_cannot_be_injected() {
+    let mut tc = context(1, false);
+    assert!(
+        tc.cpu_mut()
+            .inject_interrupt_at(1, Trap::IllegalInstruction(0))
+            .is_err()
+    );
+    assert!(tc.cpu().injected_irqs.is_empty());
+}
@@ -0,0 +1,172 @@
+//! Interrupt Priority Tests.
+//!
+//! Verifies which of several simultaneously pending interrupts is taken:
+//! the privileged spec's default order (MEI > MSI > MTI > SEI > SSI > STI),
+//! a custom `general.interrupt_priority`, and the rule that interrupts
+//! destined for M-mode come before those delegated to S-mode.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::TestContext;
+use rvsim_core::common::Trap;
+use rvsim_core::common::constants::CAUSE_INTERRUPT_BIT;
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr::{MIE_IMPLEMENTED, MSTATUS_MIE, MSTATUS_SIE};
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::core::arch::trap::{STANDARD_INTERRUPT_PRIORITY, TrapHandler};
+use rvsim_core::isa::privileged::cause::interrupt;
+
+const MEI: u64 = interrupt::MACHINE_EXTERNAL & !CAUSE_INTERRUPT_BIT;
+const MSI: u64 = interrupt::MACHINE_SOFTWARE & !CAUSE_INTERRUPT_BIT;
+const MTI: u64 = interrupt::MACHINE_TIMER & !CAUSE_INTERRUPT_BIT;
+const SEI: u64 = interrupt::SUPERVISOR_EXTERNAL & !CAUSE_INTERRUPT_BIT;
+const SSI: u64 = interrupt::SUPERVISOR_SOFTWARE & !CAUSE_INTERRUPT_BIT;
+const STI: u64 = interrupt::SUPERVISOR_TIMER & !CAUSE_INTERRUPT_BIT;
+
+fn trap_for(code: u64) -> Trap {
+    TrapHandler::irq_to_trap(1 << code)
+}
+
+/// Makes every code in `pending` pending at once, with all interrupts
+/// enabled, and returns the context after the first one is taken.
+fn take_first(
+    config: &Config,
+    pending: &[u64],
+    setup: impl FnOnce(&mut TestContext),
+) -> TestContext {
+    let program = [InstructionBuilder::new().addi(5, 5, 1).build(); 8];
+    let mut tc = TestContext::program(config, &program).with_trap_handlers();
+    let cpu = tc.cpu_mut();
+    cpu.csrs.mie = MIE_IMPLEMENTED;
+    cpu.csrs.mstatus |= MSTATUS_MIE | MSTATUS_SIE;
+    for &code in pending {
+        cpu.inject_interrupt_at(2, trap_for(code)).unwrap();
+    }
+    setup(&mut tc);
+    tc.run(200);
+    tc
+}
+
+fn config() -> Config {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Default order
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn default_order_matches_privileged_spec() {
+    assert_eq!(STANDARD_INTERRUPT_PRIORITY, [MEI, MSI, MTI, SEI, SSI, STI]);
+    assert_eq!(
+        Config::default().general.interrupt_priority,
+        STANDARD_INTERRUPT_PRIORITY.to_vec()
+    );
+}
+
+#[test]
+fn default_order_takes_highest_pending_first() {
+    let order = STANDARD_INTERRUPT_PRIORITY;
+    for first in 0..order.len() {
+        let tc = take_first(&config(), &order[first..], |_| {});
+        assert_eq!(
+            tc.cpu().csrs.mcause,
+            CAUSE_INTERRUPT_BIT | order[first],
+            "pending {:?}",
+            &order[first..]
+        );
+    }
+}
+
+#[test]
+fn default_order_every_pair() {
+    let order = STANDARD_INTERRUPT_PRIORITY;
+    for (i, &high) in order.iter().enumerate() {
+        for &low in &order[i + 1..] {
+            let tc = take_first(&config(), &[low, high], |_| {});
+            assert_eq!(
+                tc.cpu().csrs.mcause,
+                CAUSE_INTERRUPT_BIT | high,
+                "{high} vs {low}"
+            );
+        }
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Custom order
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn custom_order_is_followed() {
+    let mut config = config();
+    config.general.interrupt_priority = vec![STI, SSI, SEI, MTI, MSI, MEI];
+    let order = config.general.interrupt_priority.clone();
+    for first in 0..order.len() {
+        let tc = take_first(&config, &order[first..], |_| {});
+        assert_eq!(tc.cpu().csrs.mcause, CAUSE_INTERRUPT_BIT | order[first]);
+    }
+}
+
+#[test]
+fn partial_order_keeps_the_rest_in_standard_order() {
+    assert_eq!(
+        TrapHandler::interrupt_priority(&[MTI]),
+        [MTI, MEI, MSI, SEI, SSI, STI]
+    );
+    assert_eq!(
+        TrapHandler::interrupt_priority(&[STI, 42, STI, 0, SEI]),
+        [STI, SEI, MEI, MSI, MTI, SSI],
+        "unknown codes and repeats are dropped"
+    );
+    assert_eq!(
+        TrapHandler::interrupt_priority(&[]),
+        STANDARD_INTERRUPT_PRIORITY
+    );
+
+    let mut config = config();
+    config.general.interrupt_priority = vec![MTI];
+    let tc = take_first(&config, &[MEI, MTI], |_| {});
+    assert_eq!(tc.cpu().csrs.mcause, interrupt::MACHINE_TIMER);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Destination privilege
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn machine_destined_interrupt_beats_delegated_one() {
+    // SEI outranks STI, but SEI is delegated to S-mode and STI is not, so
+    // the M-mode STI is taken first.
+    let tc = take_first(&config(), &[SEI, STI], |tc| {
+        let cpu = tc.cpu_mut();
+        cpu.csrs.mideleg = 1 << SEI;
+        cpu.privilege = PrivilegeMode::Supervisor;
+    });
+    assert_eq!(tc.cpu().csrs.mcause, interrupt::SUPERVISOR_TIMER);
+    assert_eq!(tc.cpu().privilege, PrivilegeMode::Machine);
+}
+
+#[test]
+fn custom_order_does_not_override_destination() {
+    let mut config = config();
+    config.general.interrupt_priority = vec![SSI];
+    let tc = take_first(&config, &[SSI, MTI], |tc| {
+        let cpu = tc.cpu_mut();
+        cpu.csrs.mideleg = 1 << SSI;
+        cpu.privilege = PrivilegeMode::Supervisor;
+    });
+    assert_eq!(tc.cpu().csrs.mcause, interrupt::MACHINE_TIMER);
+}
+
+#[test]
+fn delegated_interrupts_follow_priority_among_themselves() {
+    let tc = take_first(&config(), &[STI, SSI, SEI], |tc| {
+        let cpu = tc.cpu_mut();
+        cpu.csrs.mideleg = (1 << SEI) | (1 << SSI) | (1 << STI);
+        cpu.privilege = PrivilegeMode::Supervisor;
+    });
+    assert_eq!(tc.cpu().csrs.scause, interrupt::SUPERVISOR_EXTERNAL);
+    assert_eq!(tc.cpu().privilege, PrivilegeMode::Supervisor);
+}
@@ -444,3 +444,144 @@ fn test_cache_disabled_no_stats_update() {
     assert_eq!(cpu.stats.icache_hits, initial_icache_hits);
     assert_eq!(cpu.stats.dcache_hits, initial_dcache_hits);
 }
+
+/// Latencies of a batch of reads issued together to a 2-bank DRAM with
+/// 64-byte interleaving.
+fn batched_read_latencies(addrs: &[u64]) -> Vec<u64> {
+    let mut config = Config::default();
+    config.memory.controller = rvsim_core::config::MemoryController::Dram;
+    config.memory.num_banks = 2;
+    config.memory.interleave_granularity = 64;
+    let system = rvsim_core::soc::System::new(&config, "");
+    let mut cpu = Cpu::new(system, &config);
+    cpu.direct_mode = true;
+    cpu.l1_d_cache.enabled = true;
+    let accesses: Vec<_> = addrs
+        .iter()
+        .map(|&a| (rvsim_core::common::PhysAddr::new(a), AccessType::Read))
+        .collect();
+    let latencies = cpu.simulate_memory_accesses(&accesses);
+    assert_eq!(cpu.stats.dcache_misses, addrs.len() as u64);
+    latencies
+}
+
+#[test]
+fn test_batched_misses_to_different_banks_overlap() {
+    let latencies = batched_read_latencies(&[0x8000_0000, 0x8000_0040]);
+    assert_eq!(
+        latencies[0], latencies[1],
+        "independent misses should not queue behind each other"
+    );
+}
+
+#[test]
+fn test_batched_misses_to_same_bank_serialize() {
+    let latencies = batched_read_latencies(&[0x8000_0000, 0x8000_0080]);
+    assert!(latencies[1] > latencies[0]);
+}
+
+#[test]
+fn test_l1_hit_latency_adds_cycles_beyond_the_first() {
+    let paddr = rvsim_core::common::PhysAddr::new(0x8000_0000u64);
+    let mut hit_latency = Vec::new();
+    for latency in [1, 4] {
+        let mut cpu = create_test_cpu();
+        cpu.l1_d_cache.enabled = true;
+        cpu.l1_d_cache.latency = latency;
+        cpu.l1_i_cache.enabled = true;
+        cpu.l1_i_cache.latency = latency;
+        cpu.simulate_memory_access(paddr, AccessType::Read);
+        cpu.simulate_memory_access(paddr, AccessType::Fetch);
+        hit_latency.push((
+            cpu.simulate_memory_access(paddr, AccessType::Read),
+            cpu.simulate_memory_access(paddr, AccessType::Fetch),
+        ));
+    }
+    assert_eq!(hit_latency[0], (0, 0), "a 1-cycle L1 hit is free");
+    assert_eq!(hit_latency[1], (3, 3));
+}
+
+#[test]
+fn test_l1_latency_also_charged_on_miss() {
+    let paddr = rvsim_core::common::PhysAddr::new(0x8000_0000u64);
+    let mut miss_latency = Vec::new();
+    for latency in [1, 4] {
+        let mut cpu = create_test_cpu();
+        cpu.l1_d_cache.enabled = true;
+        cpu.l1_d_cache.latency = latency;
+        miss_latency.push(cpu.simulate_memory_access(paddr, AccessType::Read));
+    }
+    assert_eq!(miss_latency[1], miss_latency[0] + 3);
+}
+
+#[test]
+fn test_dcache_bank_conflict_stalls_and_is_counted() {
+    let a = rvsim_core::common::PhysAddr::new(0x8000_0000u64);
+    let b = rvsim_core::common::PhysAddr::new(0x8000_0080u64);
+    let mut latencies = Vec::new();
+    for banks in [0, 2] {
+        let mut cpu = create_test_cpu();
+        cpu.l1_d_cache =
+            rvsim_core::core::units::cache::CacheSim::new(&rvsim_core::config::CacheConfig {
+                enabled: true,
+                banks,
+                ..Default::default()
+            });
+        // Warm both lines so the only extra cost is the bank.
+        cpu.simulate_memory_access(a, AccessType::Read);
+        cpu.simulate_memory_access(b, AccessType::Read);
+        latencies
+            .push(cpu.simulate_memory_accesses(&[(a, AccessType::Read), (b, AccessType::Write)]));
+        assert_eq!(cpu.stats.dcache_bank_conflicts, banks as u64 / 2);
+    }
+    assert_eq!(latencies[0], vec![0, 0]);
+    assert_eq!(latencies[1], vec![0, 1], "lines 0 and 2 share bank 0");
+}
+
+/// Latency of a read to `0x8000_0080` after `before`, with a direct-mapped
+/// 1 KiB L1-D as the only cache level and the given DRAM write-to-read
+/// turnaround.
+fn read_latency_after(before: &[(u64, AccessType)], t_wtr: u64) -> u64 {
+    let mut config = Config::default();
+    config.memory.controller = rvsim_core::config::MemoryController::Dram;
+    config.memory.t_wtr = t_wtr;
+    config.cache.l1_d.enabled = true;
+    config.cache.l1_d.size_bytes = 1024;
+    config.cache.l1_d.ways = 1;
+    config.cache.l2.enabled = false;
+    config.cache.l3.enabled = false;
+    let system = rvsim_core::soc::System::new(&config, "");
+    let mut cpu = Cpu::new(system, &config);
+    cpu.direct_mode = true;
+    for &(addr, access) in before {
+        cpu.simulate_memory_access(rvsim_core::common::PhysAddr::new(addr), access);
+    }
+    cpu.simulate_memory_access(
+        rvsim_core::common::PhysAddr::new(0x8000_0080),
+        AccessType::Read,
+    )
+}
+
+#[test]
+fn test_store_miss_fills_line_with_a_read() {
+    let before = [(0x8000_0000, AccessType::Write)];
+    assert_eq!(
+        read_latency_after(&before, 100),
+        read_latency_after(&before, 0),
+        "a write-allocate store miss is not a DRAM write"
+    );
+}
+
+#[test]
+fn test_dirty_eviction_is_written_back_to_dram() {
+    // The load to 0x8000_0400 shares the dirty line's set and evicts it.
+    let before = [
+        (0x8000_0000, AccessType::Write),
+        (0x8000_0400, AccessType::Read),
+    ];
+    assert_eq!(
+        read_latency_after(&before, 100),
+        read_latency_after(&before, 0) + 100,
+        "the write-back delays the next read by the turnaround"
+    );
+}
@@ -0,0 +1,167 @@
+//! Microarchitectural State Export/Import Tests.
+//!
+//! Verifies that a snapshot of the caches, branch predictor, and TLBs taken
+//! from a warmed CPU makes a fresh CPU at the same architectural point
+//! behave exactly like the warmed run, and leaves architectural state alone.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::{BranchPredictor, Config};
+use rvsim_core::core::cpu::microarch::MicroarchState;
+
+/// Loop trip count; longer than the GShare history so the loop branch
+/// trains to taken.
+const ITERATIONS: i32 = 40;
+
+/// Bytes between consecutive loads (four loads per 64-byte line).
+const STRIDE: i32 = 16;
+
+/// Walks a buffer of `ITERATIONS * STRIDE` bytes in a counted loop.
+fn program() -> Vec<u32> {
+    // Program:
+    //   0: x1 = 0
+    //   4: x2 = ITERATIONS
+    //   8: AUIPC x3, 0
+    //  12: x3 = x3 + 0x400       (data buffer)
+    //  16: LW x4, 0(x3)
+    //  20: x3 = x3 + STRIDE
+    //  24: x1 = x1 + 1
+    //  28: BNE x1, x2, -12
+    vec![
+        InstructionBuilder::new().addi(1, 0, 0).build(),
+        InstructionBuilder::new().addi(2, 0, ITERATIONS).build(),
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new().addi(3, 3, 0x400).build(),
+        InstructionBuilder::new().lw(4, 3, 0).build(),
+        InstructionBuilder::new().addi(3, 3, STRIDE).build(),
+        InstructionBuilder::new().addi(1, 1, 1).build(),
+        InstructionBuilder::new().bne(1, 2, -12).build(),
+    ]
+}
+
+/// A context with L1 caches enabled and simulated for RAM accesses, and a
+/// history-based predictor so that training carries over.
+fn context() -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.branch_predictor = BranchPredictor::GShare;
+    config.cache.l1_i.enabled = true;
+    config.cache.l1_d.enabled = true;
+    let mut tc = TestContext::program(&config, &program());
+    tc.cpu_mut().mmio_base = PROGRAM_BASE;
+    tc
+}
+
+/// Timing-relevant counters accumulated over one run of the program.
+#[derive(Debug, PartialEq)]
+struct RunStats {
+    cycles: u64,
+    icache_misses: u64,
+    dcache_misses: u64,
+    dcache_hits: u64,
+    branch_mispredictions: u64,
+}
+
+/// Runs the program from the start to its exit and returns the counters
+/// accumulated during that run.
+fn run_once(tc: &mut TestContext) -> RunStats {
+    let before = RunStats::from(tc);
+    tc.run_to_exit();
+    let after = RunStats::from(tc);
+    RunStats {
+        cycles: after.cycles - before.cycles,
+        icache_misses: after.icache_misses - before.icache_misses,
+        dcache_misses: after.dcache_misses - before.dcache_misses,
+        dcache_hits: after.dcache_hits - before.dcache_hits,
+        branch_mispredictions: after.branch_mispredictions - before.branch_mispredictions,
+    }
+}
+
+impl RunStats {
+    fn from(tc: &TestContext) -> Self {
+        let s = &tc.cpu().stats;
+        Self {
+            cycles: s.cycles,
+            icache_misses: s.icache_misses,
+            dcache_misses: s.dcache_misses,
+            dcache_hits: s.dcache_hits,
+            branch_mispredictions: s.branch_mispredictions,
+        }
+    }
+}
+
+/// Drains the pipeline and points the CPU back at the program start.
+fn restart(tc: &mut TestContext) {
+    tc.sim.pipeline.flush(&mut tc.sim.cpu);
+    let cpu = tc.cpu_mut();
+    cpu.exit_code = None;
+    cpu.pc = PROGRAM_BASE;
+}
+
+/// Warms a CPU with one run, then returns it restarted with its snapshot.
+fn warmed() -> (TestContext, MicroarchState) {
+    let mut tc = context();
+    run_once(&mut tc);
+    restart(&mut tc);
+    let state = tc.cpu().export_microarch();
+    (tc, state)
+}
+
+#[test]
+fn imported_state_matches_warmed_run() {
+    let (mut warm, state) = warmed();
+    let warm_stats = run_once(&mut warm);
+
+    let mut fresh = context();
+    fresh.cpu_mut().import_microarch(&state);
+    let imported_stats = run_once(&mut fresh);
+
+    assert_eq!(imported_stats, warm_stats);
+}
+
+#[test]
+fn imported_state_is_warmer_than_cold_start() {
+    let (_, state) = warmed();
+
+    let cold_stats = run_once(&mut context());
+
+    let mut fresh = context();
+    fresh.cpu_mut().import_microarch(&state);
+    let imported_stats = run_once(&mut fresh);
+
+    let buffer_lines = (ITERATIONS * STRIDE / 64) as u64;
+    assert!(
+        cold_stats.dcache_misses >= buffer_lines,
+        "each buffer line misses cold"
+    );
+    assert_eq!(imported_stats.dcache_misses, 0, "buffer lines stay cached");
+    assert_eq!(imported_stats.icache_misses, 0, "code stays cached");
+    assert!(imported_stats.branch_mispredictions < cold_stats.branch_mispredictions);
+    assert!(imported_stats.cycles < cold_stats.cycles);
+}
+
+#[test]
+fn import_leaves_architectural_state_alone() {
+    let (_, state) = warmed();
+    let mut fresh = context();
+    fresh.set_reg(9, 0x1234);
+    let retired = fresh.cpu().stats.instructions_retired;
+
+    fresh.cpu_mut().import_microarch(&state);
+
+    assert_eq!(fresh.get_reg(9), 0x1234);
+    assert_eq!(fresh.cpu().pc, PROGRAM_BASE);
+    assert_eq!(fresh.cpu().stats.instructions_retired, retired);
+}
+
+#[test]
+fn snapshot_is_independent_of_later_execution() {
+    let (mut warm, state) = warmed();
+    // Thrash the live caches after taking the snapshot.
+    warm.cpu_mut().l1_d_cache.flush();
+    warm.cpu_mut().l1_i_cache.flush();
+    warm.cpu_mut().mmu.dtlb.flush();
+
+    let mut fresh = context();
+    fresh.cpu_mut().import_microarch(&state);
+    assert_eq!(run_once(&mut fresh).dcache_misses, 0);
+}
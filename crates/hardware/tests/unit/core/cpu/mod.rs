@@ -6,8 +6,44 @@
 /// Tests for trap and exception handling.
 pub mod trap_handling;
 
+/// Tests for saving and restoring architectural checkpoints.
+pub mod checkpoint;
+
+/// Tests for functional (fast-forward) execution.
+pub mod functional;
+
 /// Tests for CPU execution and pipeline coordination.
 pub mod execution;
 
 /// Tests for memory access and cache simulation.
 pub mod memory;
+
+/// Tests for exporting and importing microarchitectural state.
+pub mod microarch;
+
+/// Tests for semihosting calls.
+pub mod semihosting;
+
+/// Tests for WFI sleep and wake-up.
+pub mod wfi;
+
+/// Tests for call-stack reconstruction.
+pub mod backtrace;
+
+/// Tests for interrupts injected at an instruction count.
+pub mod interrupt_injection;
+
+/// Tests for precise interrupt delivery.
+pub mod precise_interrupts;
+
+/// Tests for the order simultaneous interrupts are taken in.
+pub mod interrupt_priority;
+
+/// Tests for the built-in SBI firmware.
+pub mod sbi;
+
+/// Tests for the taken-trap log.
+pub mod trap_log;
+
+/// Tests for halting to the host on EBREAK.
+pub mod ebreak_halt;
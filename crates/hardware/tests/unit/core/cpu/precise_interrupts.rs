@@ -0,0 +1,98 @@
+//! Precise Interrupt Tests.
+//!
+//! Verifies that a CLINT timer interrupt is precise wherever it lands: the
+//! trap's `mepc` names the oldest instruction that has not retired, every
+//! older instruction has retired, and no younger one has. The interrupt is
+//! swept across every cycle of a program that drains the ROB (a redirect
+//! refetch) and stalls execute (a long divide).
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{CLINT_BASE, M_HANDLER, PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr::{MIP_MTIP, MSTATUS_MIE};
+use rvsim_core::isa::privileged::cause::interrupt;
+use rvsim_core::soc::devices::Clint;
+
+const MTIMECMP: u64 = CLINT_BASE + 0x4000;
+
+/// Program index of the instruction the jump skips.
+const SKIPPED_IDX: usize = 5;
+/// Program index of the divide.
+const DIV_IDX: usize = 10;
+
+/// `addi x10, x10, 1`: each retired copy counts one.
+fn count() -> u32 {
+    InstructionBuilder::new().addi(10, 10, 1).build()
+}
+
+/// Counting instructions around a taken jump and a divide. The jump is
+/// predicted not-taken on first sight, so its redirect refetches with the
+/// ROB empty; the divide holds execute for `div_latency` cycles.
+fn program() -> Vec<u32> {
+    let mut p = vec![count(); 4];
+    p.push(InstructionBuilder::new().jal(0, 8).build());
+    p.push(InstructionBuilder::new().addi(11, 11, 1).build()); // skipped
+    p.extend([count(); 3]);
+    p.push(InstructionBuilder::new().addi(13, 0, 3).build());
+    p.push(InstructionBuilder::new().div(12, 10, 13).build());
+    p.extend([count(); 8]);
+    p.push(InstructionBuilder::new().jal(0, 0).build());
+    p
+}
+
+/// Runs `program` with the timer firing when `mtime` reaches `when`.
+fn run(width: usize, when: u64) -> TestContext {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.pipeline.width = width;
+    config.pipeline.div_latency = 20;
+    let mut tc = TestContext::program(&config, &program()).with_trap_handlers();
+    let bus = &mut tc.cpu_mut().bus.bus;
+    bus.add_device(Box::new(Clint::new(CLINT_BASE, 1)));
+    bus.write_u64(MTIMECMP, when).unwrap();
+    tc.cpu_mut().csrs.mie = MIP_MTIP;
+    tc.cpu_mut().csrs.mstatus |= MSTATUS_MIE;
+    tc.run(400);
+    tc
+}
+
+/// Number of `count()` instructions before program index `idx`, following
+/// the jump over the skipped instruction.
+fn counts_before(idx: usize) -> u64 {
+    let program = program();
+    (0..idx)
+        .filter(|&i| i != SKIPPED_IDX && program[i] == count())
+        .count() as u64
+}
+
+#[test]
+fn timer_interrupt_is_precise_at_every_cycle() {
+    for width in [1, 2] {
+        for when in 1..80 {
+            let tc = run(width, when);
+            let label = format!("width={width} mtimecmp={when}");
+            let mepc = tc.cpu().csrs.mepc;
+            assert_eq!(
+                tc.cpu().csrs.mcause,
+                interrupt::MACHINE_TIMER,
+                "trap taken: {label}"
+            );
+            let idx = ((mepc - PROGRAM_BASE) / 4) as usize;
+            assert!(
+                (PROGRAM_BASE..M_HANDLER).contains(&mepc) && idx != SKIPPED_IDX,
+                "mepc {mepc:#x} is a program instruction: {label}"
+            );
+            assert_eq!(
+                tc.get_reg(10),
+                counts_before(idx),
+                "retired exactly the instructions before mepc {mepc:#x}: {label}"
+            );
+            assert_eq!(tc.get_reg(11), 0, "skipped instruction never ran: {label}");
+            assert_eq!(
+                tc.get_reg(12),
+                if idx > DIV_IDX { 7 / 3 } else { 0 },
+                "divide retired whole or not at all: {label}"
+            );
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Built-in SBI Tests.
+//!
+//! Verifies that with `general.sbi` set, an `ECALL` from S-mode is serviced
+//! as an SBI call (Base, TIME, IPI, SRST, and the legacy extension) and
+//! returns to S-mode, while with it clear the `ECALL` still traps to M-mode.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{CLINT_BASE, PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr;
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::core::cpu::sbi::{
+    EXT_BASE, EXT_IPI, EXT_SRST, EXT_TIME, LEGACY_CLEAR_IPI, LEGACY_SEND_IPI, LEGACY_SHUTDOWN,
+    SBI_ERR_INVALID_PARAM, SBI_ERR_NOT_SUPPORTED, SBI_SPEC_VERSION, SBI_SUCCESS,
+};
+use rvsim_core::isa::abi::{REG_A0, REG_A1, REG_A2, REG_A6, REG_A7};
+use rvsim_core::isa::privileged::cause::{exception, interrupt};
+use rvsim_core::sim::simulator::Outcome;
+use rvsim_core::soc::devices::Clint;
+
+/// Hart mask read by the legacy `send_ipi`.
+const HART_MASK: u64 = PROGRAM_BASE + 0x800;
+
+/// `ecall`.
+const ECALL: u32 = 0x0000_0073;
+
+/// `mtime` value the timer tests arm for.
+const DEADLINE: u64 = 60;
+
+/// Builds an S-mode context that makes SBI call `(eid, fid)` with `args`,
+/// then counts in `x6` until something stops it.
+fn context(sbi: bool, eid: u64, fid: u64, args: &[u64]) -> TestContext {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.general.sbi = sbi;
+    let program = [
+        ECALL,
+        InstructionBuilder::new().addi(6, 6, 1).build(),
+        InstructionBuilder::new().jal(0, -4).build(),
+    ];
+    let mut tc = TestContext::program(&config, &program).with_trap_handlers();
+    tc.cpu_mut()
+        .bus
+        .bus
+        .add_device(Box::new(Clint::new(CLINT_BASE, 1)));
+    let cpu = tc.cpu_mut();
+    cpu.privilege = PrivilegeMode::Supervisor;
+    for (&reg, &val) in [REG_A0, REG_A1, REG_A2].iter().zip(args) {
+        cpu.regs.write(reg, val);
+    }
+    cpu.regs.write(REG_A6, fid);
+    cpu.regs.write(REG_A7, eid);
+    tc
+}
+
+/// Makes SBI call `(eid, fid)` with `args` and returns the context once the
+/// program has moved on past the `ECALL`.
+fn call(eid: u64, fid: u64, args: &[u64]) -> TestContext {
+    let mut tc = context(true, eid, fid, args);
+    tc.run(100);
+    tc
+}
+
+/// Asserts the call returned to S-mode without trapping.
+fn assert_returned(tc: &TestContext) {
+    assert_eq!(tc.get_reg(9), 0, "no M-mode trap");
+    assert_eq!(tc.get_reg(8), 0, "no S-mode trap");
+    assert_eq!(tc.cpu().privilege, PrivilegeMode::Supervisor);
+    assert!(tc.get_reg(6) > 0, "execution resumed after the ecall");
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Interception
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn disabled_by_default() {
+    assert!(!Config::default().general.sbi);
+    let mut tc = context(false, EXT_BASE, 0, &[]);
+    tc.run(100);
+    assert_eq!(tc.get_reg(9), 1, "ecall traps to M-mode");
+    assert_eq!(
+        tc.cpu().csrs.mcause,
+        exception::ENVIRONMENT_CALL_FROM_S_MODE
+    );
+    assert_eq!(tc.cpu().privilege, PrivilegeMode::Machine);
+    assert_eq!(tc.get_reg(6), 0);
+}
+
+#[test]
+fn machine_mode_ecall_is_not_intercepted() {
+    let mut tc = context(true, EXT_BASE, 0, &[]);
+    tc.cpu_mut().privilege = PrivilegeMode::Machine;
+    tc.run(100);
+    assert_eq!(tc.get_reg(9), 1, "M-mode ecall still traps");
+    assert_eq!(tc.get_reg(REG_A1), 0);
+}
+
+#[test]
+fn unknown_extension_is_not_supported() {
+    let tc = call(0x0123_4567, 0, &[]);
+    assert_returned(&tc);
+    assert_eq!(tc.get_reg(REG_A0), SBI_ERR_NOT_SUPPORTED as u64);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Base extension
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn base_reports_spec_version() {
+    let tc = call(EXT_BASE, 0, &[]);
+    assert_returned(&tc);
+    assert_eq!(tc.get_reg(REG_A0), SBI_SUCCESS as u64);
+    assert_eq!(tc.get_reg(REG_A1), SBI_SPEC_VERSION);
+}
+
+#[test]
+fn base_probes_extensions() {
+    for (eid, present) in [
+        (EXT_TIME, 1),
+        (EXT_IPI, 1),
+        (EXT_SRST, 1),
+        (LEGACY_SHUTDOWN, 1),
+        (0x5246_4E43, 0), // RFENCE
+    ] {
+        let tc = call(EXT_BASE, 3, &[eid]);
+        assert_eq!(tc.get_reg(REG_A0), SBI_SUCCESS as u64);
+        assert_eq!(tc.get_reg(REG_A1), present, "probe {eid:#x}");
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Timer
+// ══════════════════════════════════════════════════════════
+
+/// Arms the timer for `DEADLINE` with STIP delegated and enabled.
+fn run_timer(eid: u64) -> TestContext {
+    let mut tc = context(true, eid, 0, &[DEADLINE]);
+    let cpu = tc.cpu_mut();
+    cpu.csrs.mideleg = csr::MIP_STIP;
+    cpu.csrs.mie = csr::MIE_STIE;
+    cpu.csrs.mstatus |= csr::MSTATUS_SIE;
+    tc.run(400);
+    tc
+}
+
+#[test]
+fn time_set_timer_raises_supervisor_timer_interrupt() {
+    let tc = run_timer(EXT_TIME);
+    assert_eq!(tc.get_reg(9), 0, "no M-mode trap");
+    assert_eq!(tc.get_reg(8), 1, "S-mode handler runs");
+    assert_eq!(tc.cpu().csrs.scause, interrupt::SUPERVISOR_TIMER);
+    assert_eq!(tc.get_reg(REG_A0), SBI_SUCCESS as u64);
+    assert!(tc.get_reg(6) > 0, "interrupt waits for the deadline");
+    assert_eq!(tc.cpu().sbi_timer, DEADLINE);
+}
+
+#[test]
+fn legacy_set_timer_raises_supervisor_timer_interrupt() {
+    let tc = run_timer(0);
+    assert_eq!(tc.get_reg(8), 1);
+    assert_eq!(tc.cpu().csrs.scause, interrupt::SUPERVISOR_TIMER);
+}
+
+#[test]
+fn set_timer_in_the_future_clears_pending_interrupt() {
+    let mut tc = context(true, EXT_TIME, 0, &[u64::MAX]);
+    tc.cpu_mut().csrs.mip |= csr::MIP_STIP;
+    tc.run(100);
+    assert_returned(&tc);
+    assert_eq!(tc.cpu().csrs.mip & csr::MIP_STIP, 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 4. IPIs
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn ipi_to_this_hart_sets_ssip() {
+    let tc = call(EXT_IPI, 0, &[1, 0]);
+    assert_returned(&tc);
+    assert_eq!(tc.get_reg(REG_A0), SBI_SUCCESS as u64);
+    assert_ne!(tc.cpu().csrs.mip & csr::MIP_SSIP, 0);
+}
+
+#[test]
+fn ipi_to_all_harts_sets_ssip() {
+    let tc = call(EXT_IPI, 0, &[0, u64::MAX]);
+    assert_eq!(tc.get_reg(REG_A0), SBI_SUCCESS as u64);
+    assert_ne!(tc.cpu().csrs.mip & csr::MIP_SSIP, 0);
+}
+
+#[test]
+fn ipi_to_missing_hart_is_invalid() {
+    let tc = call(EXT_IPI, 0, &[0b10, 0]);
+    assert_eq!(tc.get_reg(REG_A0), SBI_ERR_INVALID_PARAM as u64);
+    assert_eq!(tc.cpu().csrs.mip & csr::MIP_SSIP, 0);
+}
+
+#[test]
+fn legacy_ipi_reads_hart_mask_from_memory() {
+    let mut tc = context(true, LEGACY_SEND_IPI, 0, &[HART_MASK]);
+    tc.cpu_mut().bus.bus.write_u64(HART_MASK, 1).unwrap();
+    tc.run(100);
+    assert_returned(&tc);
+    assert_eq!(tc.get_reg(REG_A0), SBI_SUCCESS as u64);
+    assert_ne!(tc.cpu().csrs.mip & csr::MIP_SSIP, 0);
+}
+
+#[test]
+fn legacy_clear_ipi_clears_ssip() {
+    let mut tc = context(true, LEGACY_CLEAR_IPI, 0, &[]);
+    tc.cpu_mut().csrs.mip |= csr::MIP_SSIP;
+    tc.run(100);
+    assert_returned(&tc);
+    assert_eq!(tc.cpu().csrs.mip & csr::MIP_SSIP, 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 5. System reset
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn srst_shutdown_exits() {
+    let tc = call(EXT_SRST, 0, &[0, 0]);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+}
+
+#[test]
+fn srst_shutdown_on_failure_exits_with_error() {
+    let tc = call(EXT_SRST, 0, &[0, 1]);
+    assert_eq!(tc.cpu().exit_code, Some(1));
+}
+
+#[test]
+fn srst_reboot_resets_the_machine() {
+    let mut tc = context(true, EXT_SRST, 0, &[1, 0]);
+    let mut outcome = None;
+    for _ in 0..100 {
+        tc.sim.tick().unwrap();
+        outcome = tc.sim.take_outcome();
+        if outcome.is_some() {
+            break;
+        }
+    }
+    assert_eq!(outcome, Some(Outcome::Reset));
+    assert!(tc.cpu().exit_code.is_none());
+    assert_eq!(tc.cpu().privilege, PrivilegeMode::Machine);
+}
+
+#[test]
+fn srst_unknown_type_is_invalid() {
+    let tc = call(EXT_SRST, 0, &[0x1000, 0]);
+    assert_returned(&tc);
+    assert_eq!(tc.get_reg(REG_A0), SBI_ERR_INVALID_PARAM as u64);
+    assert!(tc.cpu().exit_code.is_none());
+}
+
+#[test]
+fn legacy_shutdown_exits() {
+    let tc = call(LEGACY_SHUTDOWN, 0, &[]);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+}
@@ -0,0 +1,298 @@
+//! Semihosting Tests.
+//!
+//! Verifies that an `EBREAK` wrapped in the semihosting sequence is serviced
+//! as a host call (console, files, and exit) while a plain `EBREAK` still
+//! traps, and that the sequence traps as usual when semihosting is disabled
+//! or the hart is in U-mode.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::core::cpu::semihosting::{
+    ADP_STOPPED_APPLICATION_EXIT, SEMIHOST_ENTRY, SEMIHOST_EXIT, SYS_CLOSE, SYS_EXIT,
+    SYS_EXIT_EXTENDED, SYS_OPEN, SYS_READ, SYS_WRITE, SYS_WRITE0, SYS_WRITEC,
+};
+use rvsim_core::isa::privileged::cause::exception;
+
+/// Address of the `EBREAK` in the semihosting sequence loaded at `PROGRAM_BASE`.
+const EBREAK_PC: u64 = PROGRAM_BASE + 4;
+
+/// Parameter block.
+const PARAMS: u64 = PROGRAM_BASE + 0x400;
+/// Scratch buffer for strings and file data.
+const BUFFER: u64 = PROGRAM_BASE + 0x800;
+
+const A0: usize = 10;
+const A1: usize = 11;
+
+/// A configuration with semihosting enabled.
+fn semihosting_config() -> Config {
+    let mut config = Config::default();
+    config.general.semihosting = true;
+    config
+}
+
+/// A context with semihosting enabled and the semihosting sequence at
+/// `PROGRAM_BASE`.
+fn context() -> TestContext {
+    TestContext::program(
+        &semihosting_config(),
+        &[
+            SEMIHOST_ENTRY,
+            InstructionBuilder::new().ebreak().build(),
+            SEMIHOST_EXIT,
+        ],
+    )
+}
+
+/// Runs the semihosting sequence with `config` outside direct mode, with an
+/// M-mode handler (`x9 = 1; j .`) to catch the breakpoint.
+fn run_sequence(mut config: Config, privilege: PrivilegeMode) -> TestContext {
+    config.general.direct_mode = false;
+    let mut tc = TestContext::program(
+        &config,
+        &[
+            SEMIHOST_ENTRY,
+            InstructionBuilder::new().ebreak().build(),
+            SEMIHOST_EXIT,
+        ],
+    );
+    let handler = PROGRAM_BASE + 0x200;
+    let bus = &mut tc.cpu_mut().bus.bus;
+    bus.write_u32(handler, InstructionBuilder::new().addi(9, 0, 1).build())
+        .unwrap();
+    bus.write_u32(handler + 4, InstructionBuilder::new().jal(0, 0).build())
+        .unwrap();
+    tc.cpu_mut().csrs.mtvec = handler;
+    tc.cpu_mut().privilege = privilege;
+    tc.set_reg(A0, SYS_EXIT);
+    tc.set_reg(A1, ADP_STOPPED_APPLICATION_EXIT);
+    tc.run(100);
+    tc
+}
+
+/// Writes `words` as the parameter block.
+fn set_params(tc: &mut TestContext, words: &[u64]) {
+    for (i, &word) in words.iter().enumerate() {
+        tc.sim
+            .cpu
+            .bus
+            .bus
+            .write_u64(PARAMS + 8 * i as u64, word)
+            .unwrap();
+    }
+}
+
+/// Copies `bytes` to `addr`.
+fn write_bytes(tc: &mut TestContext, addr: u64, bytes: &[u8]) {
+    for (i, &byte) in bytes.iter().enumerate() {
+        tc.sim.cpu.bus.bus.write_u8(addr + i as u64, byte).unwrap();
+    }
+}
+
+/// Issues semihosting call `op` with parameter `param` and returns `a0`.
+fn call(tc: &mut TestContext, op: u64, param: u64) -> u64 {
+    tc.set_reg(A0, op);
+    tc.set_reg(A1, param);
+    assert!(tc.cpu_mut().semihosting_call(EBREAK_PC));
+    assert_eq!(tc.cpu().pc, EBREAK_PC + 4);
+    tc.get_reg(A0)
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Call detection
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn plain_ebreak_is_not_semihosting() {
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            0x0000_0013,
+            InstructionBuilder::new().ebreak().build(),
+            SEMIHOST_EXIT,
+        ],
+    );
+    tc.set_reg(A0, SYS_WRITEC);
+    assert!(!tc.cpu_mut().semihosting_call(EBREAK_PC));
+    assert_eq!(tc.get_reg(A0), SYS_WRITEC);
+
+    // In direct mode a plain EBREAK is still a fatal trap.
+    tc.run(100);
+    assert_eq!(tc.cpu().exit_code, Some(1));
+}
+
+#[test]
+fn sequence_traps_when_disabled() {
+    assert!(!Config::default().general.semihosting);
+    let tc = run_sequence(Config::default(), PrivilegeMode::Machine);
+    assert_eq!(tc.get_reg(9), 1, "guest handler runs");
+    assert_eq!(tc.cpu().csrs.mcause, exception::BREAKPOINT);
+    assert_eq!(tc.cpu().csrs.mepc, EBREAK_PC);
+    assert_eq!(tc.cpu().exit_code, None);
+    assert_eq!(tc.get_reg(A0), SYS_EXIT);
+}
+
+#[test]
+fn user_mode_sequence_traps() {
+    let tc = run_sequence(semihosting_config(), PrivilegeMode::User);
+    assert_eq!(tc.get_reg(9), 1, "guest handler runs");
+    assert_eq!(tc.cpu().csrs.mcause, exception::BREAKPOINT);
+    assert_eq!(tc.cpu().exit_code, None);
+}
+
+#[test]
+fn supervisor_mode_is_serviced() {
+    let tc = run_sequence(semihosting_config(), PrivilegeMode::Supervisor);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.get_reg(9), 0, "no guest trap");
+}
+
+#[test]
+fn pipeline_resumes_after_semihosting_call() {
+    // Program:
+    //   0: a0 = SYS_WRITEC
+    //   4: AUIPC a1, 0
+    //   8: a1 = a1 + 0x100          (a '!' byte)
+    //  12: SLLI x0, x0, 0x1f
+    //  16: EBREAK
+    //  20: SRAI x0, x0, 7
+    //  24: x5 = a0 + 5              (a0 is the call's result, 0)
+    //  28: (zero word: exit)
+    let program = [
+        InstructionBuilder::new()
+            .addi(A0 as u32, 0, SYS_WRITEC as i32)
+            .build(),
+        InstructionBuilder::new().auipc(A1 as u32, 0).build(),
+        InstructionBuilder::new()
+            .addi(A1 as u32, A1 as u32, 0x100 - 4)
+            .build(),
+        SEMIHOST_ENTRY,
+        InstructionBuilder::new().ebreak().build(),
+        SEMIHOST_EXIT,
+        InstructionBuilder::new().addi(5, A0 as u32, 5).build(),
+    ];
+    let mut tc = TestContext::program(&semihosting_config(), &program);
+    write_bytes(&mut tc, PROGRAM_BASE + 0x100, b"!");
+    tc.run(200);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.get_reg(5), 5);
+}
+
+#[test]
+fn unknown_operation_returns_minus_one() {
+    let mut tc = context();
+    assert_eq!(call(&mut tc, 0x99, 0), u64::MAX);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Console and files
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn console_writes_succeed() {
+    let mut tc = context();
+    write_bytes(&mut tc, BUFFER, b"semihosting\n\0");
+    assert_eq!(call(&mut tc, SYS_WRITE0, BUFFER), 0);
+    assert_eq!(call(&mut tc, SYS_WRITEC, BUFFER), 0);
+}
+
+#[test]
+fn file_round_trip() {
+    let path = std::env::temp_dir().join(format!("rvsim_semihost_{}.txt", std::process::id()));
+    let name = path.to_str().unwrap().as_bytes().to_vec();
+    let mut tc = context();
+    write_bytes(&mut tc, BUFFER, &name);
+    write_bytes(&mut tc, BUFFER + 0x200, b"hello");
+
+    // Open for writing ("w"), write, close.
+    set_params(&mut tc, &[BUFFER, 4, name.len() as u64]);
+    let handle = call(&mut tc, SYS_OPEN, PARAMS);
+    assert_ne!(handle, u64::MAX);
+    set_params(&mut tc, &[handle, BUFFER + 0x200, 5]);
+    assert_eq!(call(&mut tc, SYS_WRITE, PARAMS), 0);
+    set_params(&mut tc, &[handle]);
+    assert_eq!(call(&mut tc, SYS_CLOSE, PARAMS), 0);
+    assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+
+    // Reopen for reading ("r") and read more than the file holds.
+    set_params(&mut tc, &[BUFFER, 0, name.len() as u64]);
+    let handle = call(&mut tc, SYS_OPEN, PARAMS);
+    assert_ne!(handle, u64::MAX);
+    set_params(&mut tc, &[handle, BUFFER + 0x400, 8]);
+    assert_eq!(call(&mut tc, SYS_READ, PARAMS), 3);
+    for (i, &byte) in b"hello".iter().enumerate() {
+        assert_eq!(
+            tc.sim
+                .cpu
+                .bus
+                .bus
+                .read_u8(BUFFER + 0x400 + i as u64)
+                .unwrap(),
+            byte
+        );
+    }
+    let _ = std::fs::remove_file(&path);
+
+    // Closing twice fails.
+    set_params(&mut tc, &[handle]);
+    assert_eq!(call(&mut tc, SYS_CLOSE, PARAMS), 0);
+    assert_eq!(call(&mut tc, SYS_CLOSE, PARAMS), u64::MAX);
+}
+
+#[test]
+fn tt_opens_the_console() {
+    let mut tc = context();
+    write_bytes(&mut tc, BUFFER, b":tt");
+    set_params(&mut tc, &[BUFFER, 4, 3]);
+    let handle = call(&mut tc, SYS_OPEN, PARAMS);
+    assert_ne!(handle, u64::MAX);
+
+    write_bytes(&mut tc, BUFFER + 0x200, b"tt\n");
+    set_params(&mut tc, &[handle, BUFFER + 0x200, 3]);
+    assert_eq!(call(&mut tc, SYS_WRITE, PARAMS), 0);
+}
+
+#[test]
+fn open_missing_file_fails() {
+    let mut tc = context();
+    let name = b"/nonexistent/rvsim/semihost";
+    write_bytes(&mut tc, BUFFER, name);
+    set_params(&mut tc, &[BUFFER, 0, name.len() as u64]);
+    assert_eq!(call(&mut tc, SYS_OPEN, PARAMS), u64::MAX);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Exit
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn exit_reports_subcode() {
+    let mut tc = context();
+    set_params(&mut tc, &[ADP_STOPPED_APPLICATION_EXIT, 7]);
+    call(&mut tc, SYS_EXIT, PARAMS);
+    assert_eq!(tc.cpu().exit_code, Some(7));
+
+    let mut tc = context();
+    call(&mut tc, SYS_EXIT, ADP_STOPPED_APPLICATION_EXIT);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+}
+
+#[test]
+fn abnormal_exit_reason_fails() {
+    let mut tc = context();
+    set_params(&mut tc, &[0x2_0023, 0]);
+    call(&mut tc, SYS_EXIT_EXTENDED, PARAMS);
+    assert_eq!(tc.cpu().exit_code, Some(1));
+}
+
+#[test]
+fn exit_through_the_pipeline() {
+    let mut tc = context();
+    set_params(&mut tc, &[ADP_STOPPED_APPLICATION_EXIT, 42]);
+    tc.set_reg(A0, SYS_EXIT_EXTENDED);
+    tc.set_reg(A1, PARAMS);
+    tc.run(100);
+    assert_eq!(tc.cpu().exit_code, Some(42));
+}
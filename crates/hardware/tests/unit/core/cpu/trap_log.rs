@@ -0,0 +1,184 @@
+//! Trap Log Tests.
+//!
+//! Verifies that taken traps are recorded with their PC, cause, `tval`, and
+//! target mode in a bounded ring buffer, and that calls the simulator
+//! services itself are left out.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::common::Trap;
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::core::cpu::trap_log::{TrapLog, TrapRecord};
+use rvsim_core::isa::privileged::cause::{exception, interrupt};
+
+const EPC: u64 = PROGRAM_BASE + 0x40;
+
+fn record(pc: u64) -> TrapRecord {
+    TrapRecord {
+        pc,
+        cause: 0,
+        tval: 0,
+        privilege: PrivilegeMode::Machine,
+    }
+}
+
+/// Builds a context from `config` with the shared trap handlers installed
+/// and `direct_mode` off, so traps are taken rather than exiting.
+fn context(config: &Config) -> TestContext {
+    let mut config = config.clone();
+    config.general.direct_mode = false;
+    TestContext::program(&config, &[]).with_trap_handlers()
+}
+
+fn logging_context() -> TestContext {
+    let mut tc = context(&Config::default());
+    tc.cpu_mut().enable_trap_log(8);
+    tc
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Ring buffer
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn full_log_drops_oldest() {
+    let mut log = TrapLog::new(2);
+    for pc in 1..=3 {
+        log.record(record(pc));
+    }
+    assert_eq!(log.len(), 2);
+    let pcs: Vec<u64> = log.drain().iter().map(|t| t.pc).collect();
+    assert_eq!(pcs, [2, 3]);
+    assert!(log.is_empty());
+}
+
+#[test]
+fn zero_capacity_records_nothing() {
+    let mut log = TrapLog::new(0);
+    log.record(record(1));
+    assert!(log.is_empty());
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Recorded traps
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn disabled_by_default() {
+    let mut tc = context(&Config::default());
+    let cpu = tc.cpu_mut();
+    cpu.trap(Trap::IllegalInstruction(0x1234), EPC);
+    assert!(cpu.trap_log.is_none());
+    assert!(cpu.drain_trap_log().is_empty());
+}
+
+#[test]
+fn exception_to_machine_mode_is_recorded() {
+    let mut tc = logging_context();
+    let cpu = tc.cpu_mut();
+    cpu.trap(Trap::LoadPageFault(0xdead_b000), EPC);
+    assert_eq!(
+        cpu.drain_trap_log(),
+        [TrapRecord {
+            pc: EPC,
+            cause: exception::LOAD_PAGE_FAULT,
+            tval: 0xdead_b000,
+            privilege: PrivilegeMode::Machine,
+        }]
+    );
+    assert!(cpu.drain_trap_log().is_empty(), "draining empties the log");
+}
+
+#[test]
+fn delegated_exception_records_supervisor_target() {
+    let mut tc = logging_context();
+    let cpu = tc.cpu_mut();
+    cpu.privilege = PrivilegeMode::User;
+    cpu.csrs.medeleg = 1 << exception::ILLEGAL_INSTRUCTION;
+    cpu.trap(Trap::IllegalInstruction(0x1234), EPC);
+    let log = cpu.drain_trap_log();
+    assert_eq!(log[0].cause, exception::ILLEGAL_INSTRUCTION);
+    assert_eq!(log[0].tval, 0x1234);
+    assert_eq!(log[0].privilege, PrivilegeMode::Supervisor);
+}
+
+#[test]
+fn interrupt_cause_has_interrupt_bit() {
+    let mut tc = logging_context();
+    let cpu = tc.cpu_mut();
+    cpu.trap(Trap::MachineTimerInterrupt, EPC);
+    let log = cpu.drain_trap_log();
+    assert_eq!(log[0].cause, interrupt::MACHINE_TIMER);
+    assert_eq!(log[0].tval, 0);
+}
+
+#[test]
+fn traps_are_kept_in_order() {
+    let mut tc = logging_context();
+    let cpu = tc.cpu_mut();
+    cpu.trap(Trap::IllegalInstruction(0), EPC);
+    cpu.trap(Trap::MachineTimerInterrupt, EPC + 4);
+    let log = cpu.drain_trap_log();
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0].pc, EPC);
+    assert_eq!(log[1].pc, EPC + 4);
+    assert_eq!(cpu.stats.traps_taken, 2);
+}
+
+#[test]
+fn serviced_calls_are_not_recorded() {
+    let mut config = Config::default();
+    config.general.sbi = true;
+    let mut tc = context(&config);
+    let cpu = tc.cpu_mut();
+    cpu.enable_trap_log(8);
+    cpu.privilege = PrivilegeMode::Supervisor;
+    cpu.trap(Trap::EnvironmentCallFromSMode, EPC);
+    assert!(cpu.drain_trap_log().is_empty());
+}
+
+#[test]
+fn disable_discards_log() {
+    let mut tc = logging_context();
+    let cpu = tc.cpu_mut();
+    cpu.trap(Trap::IllegalInstruction(0), EPC);
+    cpu.disable_trap_log();
+    assert!(cpu.drain_trap_log().is_empty());
+}
+
+#[test]
+fn config_enables_log() {
+    let mut config = Config::default();
+    config.general.trap_log_size = 4;
+    let mut tc = context(&config);
+    let cpu = tc.cpu_mut();
+    cpu.trap(Trap::IllegalInstruction(0), EPC);
+    assert_eq!(cpu.drain_trap_log().len(), 1);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Pipeline
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn pipeline_trap_records_faulting_instruction() {
+    const ILLEGAL: u32 = 0xFFFF_FFFF;
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.general.trap_log_size = 4;
+    let program = [InstructionBuilder::new().addi(5, 0, 1).build(), ILLEGAL];
+    let mut tc = TestContext::program(&config, &program).with_trap_handlers();
+    tc.run(100);
+
+    let log = tc.cpu_mut().drain_trap_log();
+    assert_eq!(
+        log,
+        [TrapRecord {
+            pc: PROGRAM_BASE + 4,
+            cause: exception::ILLEGAL_INSTRUCTION,
+            tval: ILLEGAL as u64,
+            privilege: PrivilegeMode::Machine,
+        }]
+    );
+}
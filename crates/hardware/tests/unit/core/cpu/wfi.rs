@@ -0,0 +1,83 @@
+//! WFI Tests.
+//!
+//! Verifies that `WFI` sleeps the hart, without fetching or retiring, while
+//! the bus keeps advancing `mtime`, and that it wakes once an enabled
+//! interrupt becomes pending.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{CLINT_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr::MIP_MTIP;
+use rvsim_core::soc::devices::clint::Clint;
+
+const MTIMECMP: u64 = CLINT_BASE + 0x4000;
+const MTIME: u64 = CLINT_BASE + 0xBFF8;
+
+/// `wfi`.
+const WFI: u32 = 0x1050_0073;
+
+/// `wfi; addi x5, x0, 1`, then the direct-mode exit, with a CLINT ticking
+/// `mtime` once per cycle.
+fn context() -> TestContext {
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[WFI, InstructionBuilder::new().addi(5, 0, 1).build()],
+    );
+    tc.sim
+        .cpu
+        .bus
+        .bus
+        .add_device(Box::new(Clint::new(CLINT_BASE, 1)));
+    tc
+}
+
+#[test]
+fn wfi_sleeps_until_timer_fires() {
+    let mut tc = context();
+    tc.sim.cpu.bus.bus.write_u64(MTIMECMP, 300).unwrap();
+    tc.cpu_mut().csrs.mie = MIP_MTIP;
+
+    // Asleep: time advances but nothing is fetched or retired.
+    tc.run(100);
+    assert!(tc.cpu().wfi_waiting);
+    let retired = tc.cpu().stats.instructions_retired;
+    let icache = tc.cpu().stats.icache_hits + tc.cpu().stats.icache_misses;
+    let asleep = tc.cpu().stats.cycles_wfi;
+    tc.run(50);
+    assert!(tc.cpu().wfi_waiting);
+    assert_eq!(tc.cpu().stats.instructions_retired, retired);
+    assert_eq!(
+        tc.cpu().stats.icache_hits + tc.cpu().stats.icache_misses,
+        icache
+    );
+    assert_eq!(tc.cpu().stats.cycles_wfi, asleep + 50);
+
+    // mstatus.MIE is clear, so the timer wakes the hart without a trap and
+    // execution resumes after the WFI.
+    tc.run(1_000);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.get_reg(5), 1);
+    assert!(tc.sim.cpu.bus.bus.read_u64(MTIME).unwrap() >= 300);
+    assert!(tc.cpu().stats.cycles_wfi > 250);
+    assert_eq!(tc.cpu().stats.traps_taken, 0);
+}
+
+#[test]
+fn wfi_without_wake_sources_is_a_nop() {
+    let mut tc = context();
+    tc.run(1_000);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.get_reg(5), 1);
+    assert_eq!(tc.cpu().stats.cycles_wfi, 0);
+}
+
+#[test]
+fn wfi_with_pending_interrupt_does_not_sleep() {
+    let mut tc = context();
+    tc.sim.cpu.bus.bus.write_u64(MTIMECMP, 0).unwrap();
+    tc.cpu_mut().csrs.mie = MIP_MTIP;
+    tc.run(1_000);
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.get_reg(5), 1);
+    assert!(tc.cpu().stats.cycles_wfi <= 1);
+}
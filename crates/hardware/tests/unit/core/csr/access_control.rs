@@ -31,10 +31,18 @@ fn csr_write_and_read_mstatus() {
     assert_eq!(csrs.read(csr::MSTATUS), 0x0000_0000_000A_0000);
 }
 
-/// Verifies that the `misa` (Machine ISA) register can be written to and read back correctly.
+/// Verifies that writes to the `misa` (Machine ISA) register switch only C and read back correctly.
 #[test]
 fn csr_write_and_read_misa() {
-    let mut csrs = Csrs::default();
+    let mut csrs = Csrs {
+        misa: csr::MISA_DEFAULT_RV64IMAFDC,
+        ..Csrs::default()
+    };
+    csrs.write(csr::MISA, 0);
+    assert_eq!(
+        csrs.read(csr::MISA),
+        csr::MISA_DEFAULT_RV64IMAFDC & !csr::MISA_EXT_C
+    );
     csrs.write(csr::MISA, csr::MISA_DEFAULT_RV64IMAFDC);
     assert_eq!(csrs.read(csr::MISA), csr::MISA_DEFAULT_RV64IMAFDC);
 }
@@ -26,6 +26,38 @@ fn test_csr_read_machine_info() {
     assert_eq!(cpu.csr_read(csr::MHARTID), 0);
 }
 
+#[test]
+fn test_csr_mhartid_from_config() {
+    let mut config = Config::default();
+    config.general.hartid = 5;
+    let system = rvsim_core::soc::System::new(&config, "");
+    let mut cpu = Cpu::new(system, &config);
+    assert_eq!(cpu.csr_read(csr::MHARTID), 5);
+
+    // Read-only: writes are ignored.
+    cpu.csr_write(csr::MHARTID, 0);
+    assert_eq!(cpu.csr_read(csr::MHARTID), 5);
+}
+
+#[test]
+fn test_clint_interrupts_follow_hartid() {
+    let mut config = Config::default();
+    config.general.hartid = 1;
+    let clint = config.system.clint_base;
+    let system = rvsim_core::soc::System::new(&config, "");
+    let mut cpu = Cpu::new(system, &config);
+
+    // Hart 0's MSIP does not reach hart 1.
+    cpu.bus.bus.write_u32(clint, 1).unwrap();
+    let _ = cpu.pre_tick();
+    assert_eq!(cpu.csrs.mip & csr::MIP_MSIP, 0);
+
+    cpu.bus.bus.write_u32(clint, 0).unwrap();
+    cpu.bus.bus.write_u32(clint + 4, 1).unwrap();
+    let _ = cpu.pre_tick();
+    assert_ne!(cpu.csrs.mip & csr::MIP_MSIP, 0);
+}
+
 #[test]
 fn test_csr_read_write_mstatus() {
     let mut cpu = create_test_cpu();
@@ -265,8 +297,8 @@ fn test_csr_stval() {
 fn test_csr_stimecmp_clears_stip() {
     let mut cpu = create_test_cpu();
 
-    // Set STIP bit
-    cpu.csr_write(csr::MIP, csr::MIP_STIP);
+    // With Sstc enabled, stimecmp = 0 <= mtime raises STIP
+    cpu.csr_write(csr::MENVCFG, csr::MENVCFG_STCE);
     assert_ne!(cpu.csr_read(csr::MIP) & csr::MIP_STIP, 0);
 
     // Write to STIMECMP should clear STIP
@@ -275,6 +307,17 @@ fn test_csr_stimecmp_clears_stip() {
     assert_eq!(cpu.csr_read(csr::STIMECMP), 1000);
 }
 
+#[test]
+fn test_csr_stimecmp_without_sstc_leaves_stip_to_software() {
+    let mut cpu = create_test_cpu();
+
+    cpu.csr_write(csr::MIP, csr::MIP_STIP);
+    cpu.csr_write(csr::STIMECMP, 1000);
+    assert_ne!(cpu.csr_read(csr::MIP) & csr::MIP_STIP, 0);
+    cpu.csr_write(csr::MIP, 0);
+    assert_eq!(cpu.csr_read(csr::MIP) & csr::MIP_STIP, 0);
+}
+
 #[test]
 fn test_csr_satp_sv39_mode() {
     let mut cpu = create_test_cpu();
@@ -319,12 +362,19 @@ fn test_csr_cycle_counter() {
 
 #[test]
 fn test_csr_time_counter() {
-    let cpu = create_test_cpu();
+    let mut cpu = create_test_cpu();
+    let config = Config::default();
+    let mtime_addr = config.system.clint_base + 0xBFF8;
+
+    // TIME reads the CLINT's mtime, including software writes to it
+    cpu.bus.bus.write_u64(mtime_addr, 12345).unwrap();
+    assert_eq!(cpu.csr_read(csr::TIME), 12345);
 
-    // TIME should be cycles divided by clint_divider
-    let time = cpu.csr_read(csr::TIME);
-    let cycles = cpu.csr_read(csr::CYCLE);
-    assert_eq!(time, cycles / cpu.clint_divider);
+    for _ in 0..config.system.clint_divider {
+        let _ = cpu.bus.bus.tick();
+    }
+    assert_eq!(cpu.csr_read(csr::TIME), 12346);
+    assert_eq!(cpu.csr_read(csr::CYCLE), 0, "time is independent of cycles");
 }
 
 #[test]
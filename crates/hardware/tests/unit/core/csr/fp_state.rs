@@ -0,0 +1,77 @@
+//! # FP State (`mstatus.FS`) Tests
+//!
+//! Verifies that FP instructions and FP CSR accesses raise an illegal
+//! instruction exception while `mstatus.FS` is Off, and that writing FP
+//! state marks it Dirty, in both detailed and functional mode.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr;
+use rvsim_core::isa::privileged::cause::exception;
+
+/// `fadd.d f3, f1, f2`.
+const FADD_D: u32 = (0b000_0001 << 25) | (2 << 20) | (1 << 15) | (3 << 7) | 0x53;
+
+/// `csrr x5, fcsr`.
+const CSRR_FCSR: u32 = (csr::FCSR << 20) | (0b010 << 12) | (5 << 7) | 0x73;
+
+/// `csrw frm, x6`.
+const CSRW_FRM: u32 = (csr::FRM << 20) | (6 << 15) | (0b001 << 12) | 0x73;
+
+/// Runs `body` after `addi x6, x0, 1` with `mstatus.FS` set to `fs`.
+fn run(body: &[u32], fs: u64, functional: bool) -> TestContext {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    let mut program = vec![InstructionBuilder::new().addi(6, 0, 1).build()];
+    program.extend_from_slice(body);
+    let mut tc = TestContext::program(&config, &program).with_trap_handlers();
+    let cpu = tc.cpu_mut();
+    cpu.csrs.mstatus = (cpu.csrs.mstatus & !csr::MSTATUS_FS) | fs;
+    cpu.set_functional(functional);
+    tc.run(1_000);
+    tc
+}
+
+#[test]
+fn fp_instruction_traps_when_fs_off() {
+    for functional in [false, true] {
+        let tc = run(&[FADD_D], csr::MSTATUS_FS_OFF, functional);
+        assert_eq!(
+            tc.cpu().csrs.mcause,
+            exception::ILLEGAL_INSTRUCTION,
+            "functional={functional}"
+        );
+        assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE + 4);
+    }
+}
+
+#[test]
+fn fp_csr_access_traps_when_fs_off() {
+    for functional in [false, true] {
+        for inst in [CSRR_FCSR, CSRW_FRM] {
+            let tc = run(&[inst], csr::MSTATUS_FS_OFF, functional);
+            assert_eq!(
+                tc.cpu().csrs.mcause,
+                exception::ILLEGAL_INSTRUCTION,
+                "{inst:#010x} functional={functional}"
+            );
+            assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE + 4);
+        }
+    }
+}
+
+#[test]
+fn fp_writes_mark_state_dirty() {
+    for functional in [false, true] {
+        for inst in [FADD_D, CSRW_FRM] {
+            let tc = run(&[inst], csr::MSTATUS_FS_INIT, functional);
+            assert_eq!(
+                tc.cpu().csrs.mstatus & csr::MSTATUS_FS,
+                csr::MSTATUS_FS_DIRTY,
+                "{inst:#010x} functional={functional}"
+            );
+            assert_ne!(tc.cpu().csrs.mepc, PROGRAM_BASE + 4, "did not trap");
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! # Hardware Performance Counter Tests
+//!
+//! Verifies the Zihpm counters: `mhpmevent` selection (including WARL
+//! handling of unknown events), per-cycle counting of the selected event,
+//! the `hpmcounter` user aliases, `mcounteren`/`scounteren` gating of
+//! user-level counter reads, and freezing counters through `mcountinhibit`.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext, run_program};
+use rvsim_core::config::Config;
+use rvsim_core::core::Cpu;
+use rvsim_core::core::arch::csr;
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::isa::privileged::cause::exception;
+
+/// `csrrw x0, mhpmevent3, x5`
+const CSRW_MHPMEVENT3_X5: u32 = 0x3232_9073;
+/// `csrrs x6, cycle, x0`
+const CSRR_X6_CYCLE: u32 = 0xC000_2373;
+/// `csrrs x10, hpmcounter3, x0`
+const CSRR_X10_HPMCOUNTER3: u32 = 0xC030_2573;
+/// `csrrw x0, mcountinhibit, x5`
+const CSRW_MCOUNTINHIBIT_X5: u32 = 0x3202_9073;
+/// `csrrs x6, mcycle, x0`
+const CSRR_X6_MCYCLE: u32 = 0xB000_2373;
+/// `csrrs x7, minstret, x0`
+const CSRR_X7_MINSTRET: u32 = 0xB020_23F3;
+/// `csrrs x8, mcycle, x0`
+const CSRR_X8_MCYCLE: u32 = 0xB000_2473;
+/// `csrrs x9, minstret, x0`
+const CSRR_X9_MINSTRET: u32 = 0xB020_24F3;
+
+fn create_test_cpu() -> Cpu {
+    let config = Config::default();
+    let system = rvsim_core::soc::System::new(&config, "");
+    Cpu::new(system, &config)
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. CSR access
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn mhpmevent_accepts_known_events() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MHPMEVENT3, csr::HPM_EVENT_DCACHE_MISS);
+    cpu.csr_write(csr::MHPMEVENT31, csr::HPM_EVENT_DTLB_MISS);
+    assert_eq!(cpu.csr_read(csr::MHPMEVENT3), csr::HPM_EVENT_DCACHE_MISS);
+    assert_eq!(cpu.csr_read(csr::MHPMEVENT31), csr::HPM_EVENT_DTLB_MISS);
+}
+
+#[test]
+fn mhpmevent_unknown_event_reads_as_none() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MHPMEVENT3 + 1, 0xFFFF);
+    assert_eq!(cpu.csr_read(csr::MHPMEVENT3 + 1), csr::HPM_EVENT_NONE);
+}
+
+#[test]
+fn hpmcounter_aliases_mhpmcounter() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MHPMCOUNTER3 + 2, 1234);
+    assert_eq!(cpu.csr_read(csr::HPMCOUNTER3 + 2), 1234);
+    assert_eq!(cpu.csrs.mhpmcounter[2], 1234);
+}
+
+#[test]
+fn counter_without_event_does_not_count() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MHPMCOUNTER3, 7);
+    cpu.stats.dcache_misses += 5;
+    cpu.hpm_tick();
+    assert_eq!(cpu.csr_read(csr::MHPMCOUNTER3), 7);
+}
+
+#[test]
+fn counter_accumulates_selected_event_per_tick() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MHPMEVENT3, csr::HPM_EVENT_BRANCH_MISPREDICT);
+    cpu.csr_write(csr::MHPMEVENT3 + 1, csr::HPM_EVENT_ITLB_MISS);
+
+    cpu.stats.branch_mispredictions += 2;
+    cpu.hpm_tick();
+    cpu.stats.branch_mispredictions += 1;
+    cpu.mmu.itlb_misses += 4;
+    cpu.hpm_tick();
+
+    assert_eq!(cpu.csr_read(csr::MHPMCOUNTER3), 3);
+    assert_eq!(cpu.csr_read(csr::MHPMCOUNTER3 + 1), 4);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Counting in the pipeline
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn software_programmed_counter_counts_mispredicts() {
+    // Program:
+    //   0: x5 = BRANCH_MISPREDICT
+    //   4: CSRW mhpmevent3, x5
+    //   8: x1 = 0
+    //  12: x2 = 6
+    //  16: x1 = x1 + 1
+    //  20: BNE x1, x2, -4     (loop six times)
+    //  24: CSRR x10, hpmcounter3
+    let program = [
+        InstructionBuilder::new()
+            .addi(5, 0, csr::HPM_EVENT_BRANCH_MISPREDICT as i32)
+            .build(),
+        CSRW_MHPMEVENT3_X5,
+        InstructionBuilder::new().addi(1, 0, 0).build(),
+        InstructionBuilder::new().addi(2, 0, 6).build(),
+        InstructionBuilder::new().addi(1, 1, 1).build(),
+        InstructionBuilder::new().bne(1, 2, -4).build(),
+        CSRR_X10_HPMCOUNTER3,
+    ];
+    let tc = run_program(&Config::default(), &program);
+
+    let mispredicts = tc.cpu().stats.branch_mispredictions;
+    assert!(mispredicts > 0, "the loop should mispredict at least once");
+    assert_eq!(tc.get_reg(1), 6);
+    assert_eq!(tc.cpu().csrs.mhpmcounter[0], mispredicts);
+    assert_eq!(
+        tc.get_reg(10),
+        mispredicts,
+        "hpmcounter3 read after the loop sees every mispredict"
+    );
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. mcounteren / scounteren
+// ══════════════════════════════════════════════════════════
+
+/// Reads `cycle` from `privilege` with the given counter-enable registers.
+/// Returns the context after the read (or its trap) has completed.
+fn read_cycle_from(privilege: PrivilegeMode, mcounteren: u64, scounteren: u64) -> TestContext {
+    // Program:
+    //   0: CSRR x6, cycle
+    //   4: x7 = 1
+    //   8: JAL x0, 0           (spin)
+    //  12: handler: x8 = 1
+    //  16: JAL x0, 0           (spin)
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            CSRR_X6_CYCLE,
+            InstructionBuilder::new().addi(7, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+            InstructionBuilder::new().addi(8, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+        ],
+    );
+    let cpu = tc.cpu_mut();
+    cpu.direct_mode = false;
+    cpu.privilege = privilege;
+    cpu.csrs.mtvec = PROGRAM_BASE + 12;
+    cpu.csrs.mcounteren = mcounteren;
+    cpu.csrs.scounteren = scounteren;
+    for _ in 0..200 {
+        tc.sim.tick().unwrap();
+        if tc.get_reg(7) == 1 || tc.get_reg(8) == 1 {
+            break;
+        }
+    }
+    tc
+}
+
+#[test]
+fn user_read_of_disabled_counter_traps() {
+    let tc = read_cycle_from(PrivilegeMode::User, 0, 0);
+    assert_eq!(tc.get_reg(8), 1, "handler should run");
+    assert_eq!(tc.cpu().csrs.mcause, exception::ILLEGAL_INSTRUCTION);
+    assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE);
+    assert_eq!(tc.get_reg(7), 0);
+}
+
+#[test]
+fn user_read_needs_both_enable_bits() {
+    let tc = read_cycle_from(PrivilegeMode::User, 1, 0);
+    assert_eq!(tc.get_reg(8), 1, "scounteren.CY clear must trap");
+
+    let tc = read_cycle_from(PrivilegeMode::User, 1, 1);
+    assert_eq!(tc.get_reg(7), 1);
+    assert_eq!(tc.get_reg(8), 0);
+    assert_ne!(tc.get_reg(6), 0, "cycle should be readable");
+}
+
+#[test]
+fn supervisor_read_needs_only_mcounteren() {
+    let tc = read_cycle_from(PrivilegeMode::Supervisor, 0, 1);
+    assert_eq!(tc.get_reg(8), 1, "mcounteren.CY clear must trap");
+
+    let tc = read_cycle_from(PrivilegeMode::Supervisor, 1, 0);
+    assert_eq!(tc.get_reg(7), 1);
+    assert_eq!(tc.get_reg(8), 0);
+}
+
+#[test]
+fn machine_read_ignores_counter_enables() {
+    let tc = read_cycle_from(PrivilegeMode::Machine, 0, 0);
+    assert_eq!(tc.get_reg(7), 1);
+    assert_eq!(tc.get_reg(8), 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 4. mcountinhibit
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn mcountinhibit_time_bit_is_read_only_zero() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MCOUNTINHIBIT, u64::MAX);
+    assert_eq!(cpu.csr_read(csr::MCOUNTINHIBIT), 0xFFFF_FFFD);
+}
+
+#[test]
+fn inhibited_hpm_counter_holds() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MHPMEVENT3, csr::HPM_EVENT_DCACHE_MISS);
+    cpu.csr_write(csr::MHPMEVENT3 + 1, csr::HPM_EVENT_DCACHE_MISS);
+    cpu.csr_write(csr::MCOUNTINHIBIT, 1 << 3);
+
+    cpu.stats.dcache_misses += 5;
+    cpu.hpm_tick();
+
+    assert_eq!(cpu.csr_read(csr::MHPMCOUNTER3), 0);
+    assert_eq!(cpu.csr_read(csr::MHPMCOUNTER3 + 1), 5);
+}
+
+#[test]
+fn mcycle_write_while_inhibited_reads_back() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(
+        csr::MCOUNTINHIBIT,
+        csr::MCOUNTINHIBIT_CY | csr::MCOUNTINHIBIT_IR,
+    );
+    cpu.csrs.inhibited_cycles = 40;
+    cpu.csrs.inhibited_instret = 30;
+    cpu.csr_write(csr::MCYCLE, 1000);
+    cpu.csr_write(csr::MINSTRET, 500);
+    assert_eq!(cpu.csr_read(csr::MCYCLE), 1000);
+    assert_eq!(cpu.csr_read(csr::MINSTRET), 500);
+}
+
+#[test]
+fn inhibited_cycle_and_instret_hold_in_pipeline() {
+    // Program:
+    //   0: x5 = CY | IR
+    //   4: CSRW mcountinhibit, x5
+    //   8: CSRR x6, mcycle
+    //  12: CSRR x7, minstret
+    //  16: x1 = 0
+    //  20: x2 = 10
+    //  24: x1 = x1 + 1
+    //  28: BNE x1, x2, -4     (loop ten times)
+    //  32: CSRR x8, mcycle
+    //  36: CSRR x9, minstret
+    let program = [
+        InstructionBuilder::new()
+            .addi(5, 0, (csr::MCOUNTINHIBIT_CY | csr::MCOUNTINHIBIT_IR) as i32)
+            .build(),
+        CSRW_MCOUNTINHIBIT_X5,
+        CSRR_X6_MCYCLE,
+        CSRR_X7_MINSTRET,
+        InstructionBuilder::new().addi(1, 0, 0).build(),
+        InstructionBuilder::new().addi(2, 0, 10).build(),
+        InstructionBuilder::new().addi(1, 1, 1).build(),
+        InstructionBuilder::new().bne(1, 2, -4).build(),
+        CSRR_X8_MCYCLE,
+        CSRR_X9_MINSTRET,
+    ];
+    let tc = run_program(&Config::default(), &program);
+
+    assert_eq!(tc.get_reg(1), 10);
+    assert_eq!(tc.get_reg(8), tc.get_reg(6), "mcycle held");
+    assert_eq!(tc.get_reg(9), tc.get_reg(7), "minstret held");
+
+    let stats = &tc.cpu().stats;
+    assert!(
+        stats.total_cycles() > tc.get_reg(8) + 20,
+        "stats keep counting"
+    );
+    assert!(stats.total_instructions() > tc.get_reg(9) + 20);
+}
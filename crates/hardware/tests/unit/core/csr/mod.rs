@@ -27,3 +27,33 @@ pub mod trap_setup;
 /// This module verifies the CPU-level CSR operations including side effects
 /// like TLB flushes, interrupt inhibition, and MSTATUS/SSTATUS synchronization.
 pub mod cpu_csr_operations;
+
+/// Unit tests for the Zihpm hardware performance counters.
+///
+/// This module verifies event selection, per-cycle counting, and the
+/// `mcounteren`/`scounteren` gating of user-level counter reads.
+pub mod hpm;
+
+/// Unit tests for FP state tracking through `mstatus.FS`.
+///
+/// This module verifies that FP instructions and FP CSRs trap while FS is
+/// Off and that writing FP state marks it Dirty.
+pub mod fp_state;
+
+/// Unit tests for accesses to unimplemented CSRs.
+///
+/// This module verifies the lenient read-zero/ignore-write default and the
+/// `strict_csrs` illegal-instruction policy.
+pub mod unimplemented;
+
+/// Unit tests for WARL legalization of CSR writes.
+///
+/// This module verifies that `misa`, the trap vectors, the cause registers,
+/// and the delegation and enable registers hold only legal values.
+pub mod warl;
+
+/// Unit tests for the Sstc supervisor timer extension.
+///
+/// This module verifies the `stimecmp` comparator and its gating by
+/// `menvcfg.STCE` and `mcounteren.TM`.
+pub mod sstc;
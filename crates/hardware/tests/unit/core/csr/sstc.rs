@@ -0,0 +1,117 @@
+//! # Sstc Tests
+//!
+//! Verifies the Sstc extension: with `menvcfg.STCE` set, `stimecmp` drives
+//! the supervisor timer interrupt from `mtime` and S-mode may program it
+//! (given `mcounteren.TM`); with STCE clear, S-mode access traps and STIP
+//! stays under M-mode software control.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{CLINT_BASE, PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr;
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::isa::privileged::cause::{exception, interrupt};
+use rvsim_core::soc::devices::Clint;
+
+/// `mtime` value the S-mode program arms the timer for.
+const DEADLINE: u64 = 60;
+
+/// `csrrw x0, stimecmp, x5`
+const CSRW_STIMECMP_X5: u32 = (csr::STIMECMP << 20) | (5 << 15) | (1 << 12) | 0x73;
+
+/// Runs an S-mode program that arms `stimecmp` for `DEADLINE` and counts
+/// in `x6` until interrupted, with the STIP interrupt delegated to S-mode.
+fn run(menvcfg: u64, mcounteren: u64) -> TestContext {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    let program = [
+        CSRW_STIMECMP_X5,
+        InstructionBuilder::new().addi(6, 6, 1).build(),
+        InstructionBuilder::new().jal(0, -4).build(),
+    ];
+    let mut tc = TestContext::program(&config, &program).with_trap_handlers();
+    tc.cpu_mut()
+        .bus
+        .bus
+        .add_device(Box::new(Clint::new(CLINT_BASE, 1)));
+    let cpu = tc.cpu_mut();
+    cpu.csrs.mideleg = csr::MIP_STIP;
+    cpu.csrs.mie = csr::MIE_STIE;
+    cpu.csrs.mstatus |= csr::MSTATUS_SIE;
+    cpu.csrs.mcounteren = mcounteren;
+    // Park the comparator, as firmware does before enabling Sstc.
+    cpu.csrs.stimecmp = u64::MAX;
+    cpu.csr_write(csr::MENVCFG, menvcfg);
+    cpu.privilege = PrivilegeMode::Supervisor;
+    cpu.regs.write(5, DEADLINE);
+    tc.run(400);
+    tc
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Supervisor timer from stimecmp
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn supervisor_programs_its_own_timer() {
+    let tc = run(csr::MENVCFG_STCE, csr::COUNTEREN_TM);
+    assert_eq!(tc.get_reg(9), 0, "no M-mode trap");
+    assert_eq!(tc.get_reg(8), 1, "S-mode handler runs");
+    assert_eq!(tc.cpu().csrs.scause, interrupt::SUPERVISOR_TIMER);
+    assert_eq!(tc.cpu().csrs.stimecmp, DEADLINE);
+    assert!(tc.get_reg(6) > 0, "interrupt waits for the deadline");
+    assert!(tc.cpu().mtime() >= DEADLINE);
+}
+
+#[test]
+fn stip_tracks_comparator() {
+    let mut tc = run(csr::MENVCFG_STCE, csr::COUNTEREN_TM);
+    let cpu = tc.cpu_mut();
+    let stip = |cpu: &rvsim_core::core::Cpu| cpu.csr_read(csr::MIP) & csr::MIP_STIP != 0;
+    assert!(stip(cpu));
+    cpu.csr_write(csr::STIMECMP, u64::MAX);
+    assert!(!stip(cpu), "a future deadline lowers STIP");
+    cpu.csr_write(csr::MIP, csr::MIP_STIP);
+    assert!(!stip(cpu), "STIP is read-only under Sstc");
+    cpu.csr_write(csr::STIMECMP, 0);
+    assert!(stip(cpu), "a past deadline raises STIP");
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. menvcfg.STCE and mcounteren.TM gating
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn supervisor_access_without_stce_traps() {
+    let tc = run(0, csr::COUNTEREN_TM);
+    assert_eq!(tc.get_reg(9), 1, "M-mode handler runs");
+    assert_eq!(tc.cpu().csrs.mcause, exception::ILLEGAL_INSTRUCTION);
+    assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE);
+    assert_eq!(tc.cpu().csrs.stimecmp, u64::MAX);
+}
+
+#[test]
+fn supervisor_access_without_tm_traps() {
+    let tc = run(csr::MENVCFG_STCE, 0);
+    assert_eq!(tc.get_reg(9), 1, "M-mode handler runs");
+    assert_eq!(tc.cpu().csrs.mcause, exception::ILLEGAL_INSTRUCTION);
+}
+
+#[test]
+fn comparator_is_inert_without_stce() {
+    let mut tc = run(0, csr::COUNTEREN_TM);
+    tc.cpu_mut().csrs.stimecmp = 1;
+    tc.cpu_mut().csrs.mip = 0;
+    tc.run(50);
+    assert_eq!(tc.cpu().csrs.mip & csr::MIP_STIP, 0);
+}
+
+#[test]
+fn menvcfg_holds_only_stce() {
+    let config = Config::default();
+    let system = rvsim_core::soc::System::new(&config, "");
+    let mut cpu = rvsim_core::core::Cpu::new(system, &config);
+    assert_eq!(cpu.csr_read(csr::MENVCFG), 0, "Sstc is off at reset");
+    cpu.csr_write(csr::MENVCFG, u64::MAX);
+    assert_eq!(cpu.csr_read(csr::MENVCFG), csr::MENVCFG_STCE);
+}
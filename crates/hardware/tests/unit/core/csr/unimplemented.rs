@@ -0,0 +1,153 @@
+//! # Unimplemented CSR Tests
+//!
+//! Verifies the two policies for CSRs the hart does not implement: the
+//! lenient default (reads return zero, writes are ignored) and
+//! `general.strict_csrs` (any access raises an illegal-instruction
+//! exception), in both the pipeline and functional mode.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::Cpu;
+use rvsim_core::core::arch::csr;
+use rvsim_core::isa::privileged::cause::exception;
+
+/// An address in the custom read/write machine range that nothing decodes.
+const UNIMPLEMENTED: u32 = 0x7C0;
+
+/// `csrrs x6, <addr>, x0`
+fn csrr_x6(addr: u32) -> u32 {
+    (addr << 20) | (2 << 12) | (6 << 7) | 0x73
+}
+
+/// `csrrw x0, <addr>, x5`
+fn csrw_x5(addr: u32) -> u32 {
+    (addr << 20) | (5 << 15) | (1 << 12) | 0x73
+}
+
+/// Runs `inst` followed by `x7 = 1` with a handler that sets `x8 = 1`.
+fn run(inst: u32, strict: bool, functional: bool) -> TestContext {
+    // Program:
+    //   0: x5 = 0x55
+    //   4: inst
+    //   8: x7 = 1
+    //  12: JAL x0, 0           (spin)
+    //  16: handler: x8 = 1
+    //  20: JAL x0, 0           (spin)
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.general.strict_csrs = strict;
+    let mut tc = TestContext::program(
+        &config,
+        &[
+            InstructionBuilder::new().addi(5, 0, 0x55).build(),
+            inst,
+            InstructionBuilder::new().addi(7, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+            InstructionBuilder::new().addi(8, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+        ],
+    );
+    tc.cpu_mut().set_functional(functional);
+    tc.cpu_mut().csrs.mtvec = PROGRAM_BASE + 16;
+    tc.cpu_mut().regs.write(6, 0xFFFF);
+    for _ in 0..200 {
+        tc.sim.tick().unwrap();
+        if tc.get_reg(7) == 1 || tc.get_reg(8) == 1 {
+            break;
+        }
+    }
+    tc
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Lenient default
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn lenient_read_returns_zero() {
+    for functional in [false, true] {
+        let tc = run(csrr_x6(UNIMPLEMENTED), false, functional);
+        assert_eq!(tc.get_reg(8), 0, "no trap (functional={functional})");
+        assert_eq!(tc.get_reg(7), 1);
+        assert_eq!(tc.get_reg(6), 0);
+    }
+}
+
+#[test]
+fn lenient_write_is_ignored() {
+    for functional in [false, true] {
+        let tc = run(csrw_x5(UNIMPLEMENTED), false, functional);
+        assert_eq!(tc.get_reg(8), 0, "no trap (functional={functional})");
+        assert_eq!(tc.get_reg(7), 1);
+        assert_eq!(tc.cpu().csr_read(UNIMPLEMENTED), 0);
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Strict mode
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn strict_read_traps() {
+    for functional in [false, true] {
+        let tc = run(csrr_x6(UNIMPLEMENTED), true, functional);
+        assert_eq!(tc.get_reg(8), 1, "handler runs (functional={functional})");
+        assert_eq!(tc.get_reg(7), 0);
+        assert_eq!(tc.get_reg(6), 0xFFFF, "rd is not written");
+        assert_eq!(tc.cpu().csrs.mcause, exception::ILLEGAL_INSTRUCTION);
+        assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE + 4);
+        assert_eq!(tc.cpu().csrs.mtval, csrr_x6(UNIMPLEMENTED) as u64);
+    }
+}
+
+#[test]
+fn strict_write_traps() {
+    for functional in [false, true] {
+        let tc = run(csrw_x5(UNIMPLEMENTED), true, functional);
+        assert_eq!(tc.get_reg(8), 1, "handler runs (functional={functional})");
+        assert_eq!(tc.cpu().csrs.mcause, exception::ILLEGAL_INSTRUCTION);
+    }
+}
+
+#[test]
+fn strict_allows_implemented_csrs() {
+    for functional in [false, true] {
+        let tc = run(csrw_x5(csr::MSCRATCH), true, functional);
+        assert_eq!(tc.get_reg(8), 0, "no trap (functional={functional})");
+        assert_eq!(tc.cpu().csrs.mscratch, 0x55);
+        let tc = run(csrr_x6(csr::MHARTID), true, functional);
+        assert_eq!(tc.get_reg(8), 0, "no trap (functional={functional})");
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Stubbed CSRs
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn mtval2_and_htval_are_read_only_zero_stubs() {
+    for addr in [csr::MTVAL2, csr::HTVAL] {
+        let tc = run(csrr_x6(addr), true, false);
+        assert_eq!(tc.get_reg(8), 0, "{addr:#x} is implemented");
+        assert_eq!(tc.get_reg(6), 0);
+        let tc = run(csrw_x5(addr), true, false);
+        assert_eq!(tc.get_reg(8), 0, "{addr:#x} write is accepted");
+        assert_eq!(tc.cpu().csr_read(addr), 0);
+    }
+}
+
+#[test]
+fn implemented_set_matches_readable_csrs() {
+    let config = Config::default();
+    let cpu = Cpu::new(rvsim_core::soc::System::new(&config, ""), &config);
+    assert!(cpu.csr_implemented(csr::MSTATUS));
+    assert!(cpu.csr_implemented(csr::CYCLE));
+    assert!(cpu.csr_implemented(csr::MHPMCOUNTER3 + 5));
+    assert!(cpu.csr_implemented(0x3B7), "pmpaddr7");
+    assert!(
+        !cpu.csr_implemented(0x3A1),
+        "pmpcfg1 does not exist on RV64"
+    );
+    assert!(!cpu.csr_implemented(UNIMPLEMENTED));
+}
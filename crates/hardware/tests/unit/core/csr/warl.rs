@@ -0,0 +1,240 @@
+//! # WARL CSR Field Tests
+//!
+//! Verifies that CSR writes are legalized rather than stored verbatim: the
+//! `misa` base and MXL are fixed (and clearing C is suppressed when the next
+//! instruction is not 4-byte aligned), `mtvec`/`stvec` reserved modes fall back
+//! to a legal mode, `mcause`/`scause` keep their value on an unsupported
+//! code, and the delegation and enable registers hold only implemented bits.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::TestContext;
+use rvsim_core::config::Config;
+use rvsim_core::core::Cpu;
+use rvsim_core::core::arch::csr::{self, Csrs};
+use rvsim_core::isa::privileged::cause::{exception, interrupt};
+
+/// Helper function to create a test CPU instance.
+fn create_test_cpu() -> Cpu {
+    let config = Config::default();
+    let system = rvsim_core::soc::System::new(&config, "");
+    Cpu::new(system, &config)
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. misa
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn misa_garbage_write_keeps_base_and_mxl() {
+    let mut cpu = create_test_cpu();
+    let before = cpu.csr_read(csr::MISA);
+    cpu.csr_write(csr::MISA, 0x1234_5678_9ABC_DEF0);
+    let after = cpu.csr_read(csr::MISA);
+    assert_eq!(after & !csr::MISA_EXTENSIONS_MASK, csr::MISA_XLEN_64);
+    assert_ne!(after & csr::MISA_EXT_I, 0, "I cannot be disabled");
+    assert_eq!(
+        after & !csr::MISA_WRITABLE,
+        before & !csr::MISA_WRITABLE,
+        "only switchable extensions follow the write"
+    );
+}
+
+#[test]
+fn misa_zero_write_only_clears_compressed() {
+    let mut cpu = create_test_cpu();
+    let before = cpu.csr_read(csr::MISA);
+    cpu.csr_write(csr::MISA, 0);
+    assert_eq!(cpu.csr_read(csr::MISA), before & !csr::MISA_EXT_C);
+    cpu.csr_write(csr::MISA, before);
+    assert_eq!(cpu.csr_read(csr::MISA), before, "C can be re-enabled");
+}
+
+#[test]
+fn misa_cannot_enable_unconfigured_extension() {
+    let mut config = Config::default();
+    config.pipeline.misa_override = Some("8000000000141101".to_string());
+    let system = rvsim_core::soc::System::new(&config, "");
+    let mut cpu = Cpu::new(system, &config);
+    let before = cpu.csr_read(csr::MISA);
+    assert_eq!(before & csr::MISA_EXT_C, 0);
+    cpu.csr_write(csr::MISA, before | csr::MISA_EXT_C);
+    assert_eq!(cpu.csr_read(csr::MISA), before);
+}
+
+#[test]
+fn reset_restores_configured_misa() {
+    let mut cpu = create_test_cpu();
+    let configured = cpu.csr_read(csr::MISA);
+    cpu.csr_write(csr::MISA, configured & !csr::MISA_EXT_C);
+    cpu.reset();
+    assert_eq!(cpu.csr_read(csr::MISA), configured);
+}
+
+#[test]
+fn csrs_write_legalizes_misa() {
+    let mut csrs = Csrs::default();
+    csrs.write(csr::MISA, u64::MAX);
+    let misa = csrs.read(csr::MISA);
+    assert_eq!(misa >> 62, 2, "MXL is 64-bit");
+    assert_eq!(misa & (1 << 4), 0, "E cannot coexist with I");
+    assert_eq!(misa & ((1 << 62) - 1) & !csr::MISA_EXTENSIONS_MASK, 0);
+    assert_eq!(
+        misa & csr::MISA_EXTENSIONS_MASK,
+        csr::MISA_EXT_I | csr::MISA_EXT_C,
+        "only C follows the write"
+    );
+}
+
+/// `csrw misa, x0`, as two halfwords.
+const CSRW_MISA_ZERO: [u16; 2] = [0x1073, 0x3010];
+/// `c.nop`.
+const C_NOP: u16 = 0x0001;
+/// `c.j .`.
+const C_J_SELF: u16 = 0xA001;
+
+/// Runs `halves` (packed little-endian into words) from `PROGRAM_BASE` and
+/// returns the final `misa`.
+fn misa_after(halves: &[u16], functional: bool) -> u64 {
+    let words: Vec<u32> = halves
+        .chunks(2)
+        .map(|pair| u32::from(pair[0]) | u32::from(*pair.get(1).unwrap_or(&0)) << 16)
+        .collect();
+    let mut tc = TestContext::program(&Config::default(), &words);
+    tc.cpu_mut().set_functional(functional);
+    tc.run(100);
+    tc.cpu().csr_read(csr::MISA)
+}
+
+#[test]
+fn misa_clearing_c_before_misaligned_instruction_is_suppressed() {
+    // The csrw sits at PROGRAM_BASE + 2, so the next instruction is at + 6.
+    let program = [C_NOP, CSRW_MISA_ZERO[0], CSRW_MISA_ZERO[1], C_J_SELF];
+    for functional in [false, true] {
+        let misa = misa_after(&program, functional);
+        assert_ne!(misa & csr::MISA_EXT_C, 0, "functional: {functional}");
+    }
+}
+
+#[test]
+fn misa_clearing_c_before_aligned_instruction_takes_effect() {
+    let jal_self = InstructionBuilder::new().jal(0, 0).build();
+    let program = [
+        CSRW_MISA_ZERO[0],
+        CSRW_MISA_ZERO[1],
+        jal_self as u16,
+        (jal_self >> 16) as u16,
+    ];
+    for functional in [false, true] {
+        let misa = misa_after(&program, functional);
+        assert_eq!(misa & csr::MISA_EXT_C, 0, "functional: {functional}");
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. mtvec / stvec
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn tvec_reserved_modes_fall_back() {
+    let mut cpu = create_test_cpu();
+    for (addr, mode, legal) in [
+        (csr::MTVEC, 0, 0),
+        (csr::MTVEC, 1, 1),
+        (csr::MTVEC, 2, 0),
+        (csr::MTVEC, 3, 1),
+        (csr::STVEC, 2, 0),
+        (csr::STVEC, 3, 1),
+    ] {
+        cpu.csr_write(addr, 0x8000_0100 | mode);
+        assert_eq!(
+            cpu.csr_read(addr),
+            0x8000_0100 | legal,
+            "{addr:#x} mode {mode}"
+        );
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. mcause / scause
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn cause_accepts_implemented_codes() {
+    let mut cpu = create_test_cpu();
+    for code in [
+        exception::ILLEGAL_INSTRUCTION,
+        exception::STORE_PAGE_FAULT,
+        interrupt::MACHINE_TIMER,
+        interrupt::SUPERVISOR_EXTERNAL,
+    ] {
+        cpu.csr_write(csr::MCAUSE, code);
+        assert_eq!(cpu.csr_read(csr::MCAUSE), code);
+        cpu.csr_write(csr::SCAUSE, code);
+        assert_eq!(cpu.csr_read(csr::SCAUSE), code);
+    }
+}
+
+#[test]
+fn cause_rejects_unsupported_codes() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MCAUSE, exception::LOAD_PAGE_FAULT);
+    for garbage in [
+        10,
+        14,
+        16,
+        0xFFFF,
+        (1 << 63) | 2,
+        (1 << 63) | 16,
+        u64::MAX,
+        interrupt::USER_SOFTWARE,
+        interrupt::USER_TIMER,
+        interrupt::USER_EXTERNAL,
+    ] {
+        cpu.csr_write(csr::MCAUSE, garbage);
+        assert_eq!(
+            cpu.csr_read(csr::MCAUSE),
+            exception::LOAD_PAGE_FAULT,
+            "{garbage:#x} is not a legal cause"
+        );
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 4. Delegation, enables, and counter enables
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn medeleg_cannot_delegate_m_mode_ecall() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MEDELEG, u64::MAX);
+    let medeleg = cpu.csr_read(csr::MEDELEG);
+    assert_eq!(medeleg, csr::MEDELEG_WRITABLE);
+    assert_eq!(medeleg & (1 << exception::ENVIRONMENT_CALL_FROM_M_MODE), 0);
+    assert_ne!(medeleg & (1 << exception::ENVIRONMENT_CALL_FROM_U_MODE), 0);
+}
+
+#[test]
+fn mideleg_holds_only_supervisor_interrupts() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MIDELEG, u64::MAX);
+    assert_eq!(
+        cpu.csr_read(csr::MIDELEG),
+        csr::MIP_SSIP | csr::MIP_STIP | csr::MIP_SEIP
+    );
+}
+
+#[test]
+fn mie_holds_only_implemented_interrupts() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MIE, u64::MAX);
+    assert_eq!(cpu.csr_read(csr::MIE), csr::MIE_IMPLEMENTED);
+}
+
+#[test]
+fn counteren_is_32_bits() {
+    let mut cpu = create_test_cpu();
+    cpu.csr_write(csr::MCOUNTEREN, u64::MAX);
+    cpu.csr_write(csr::SCOUNTEREN, u64::MAX);
+    assert_eq!(cpu.csr_read(csr::MCOUNTEREN), 0xFFFF_FFFF);
+    assert_eq!(cpu.csr_read(csr::SCOUNTEREN), 0xFFFF_FFFF);
+}
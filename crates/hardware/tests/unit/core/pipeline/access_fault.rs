@@ -0,0 +1,104 @@
+//! Access Fault Tests.
+//!
+//! Verifies that loads, stores, and atomics to physical addresses no device
+//! claims, or that run past the end of a device, raise access faults in both
+//! detailed and functional mode rather than reading zeros, dropping the
+//! write, or aborting the simulator.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, PROGRAM_MEM_SIZE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::isa::privileged::cause::exception;
+
+/// An address outside every mapped device.
+const UNMAPPED: u64 = 0x2000_0000;
+
+/// Runs `body` after a prologue pointing x3 at `UNMAPPED`, trapping through
+/// `mtvec` instead of exiting.
+fn run(body: &[u32], functional: bool) -> TestContext {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    let mut program = vec![
+        InstructionBuilder::new()
+            .lui(3, (UNMAPPED >> 12) as i32)
+            .build(),
+        InstructionBuilder::new().addi(5, 0, 0x55).build(),
+    ];
+    program.extend_from_slice(body);
+    let mut tc = TestContext::program(&config, &program).with_trap_handlers();
+    tc.cpu_mut().set_functional(functional);
+    tc.run(2_000);
+    tc
+}
+
+#[test]
+fn unmapped_load_faults() {
+    for functional in [false, true] {
+        let tc = run(&[InstructionBuilder::new().lw(5, 3, 8).build()], functional);
+        assert_eq!(
+            tc.cpu().csrs.mcause,
+            exception::LOAD_ACCESS_FAULT,
+            "functional={functional}"
+        );
+        assert_eq!(tc.cpu().csrs.mtval, UNMAPPED + 8);
+        assert_eq!(tc.get_reg(5), 0x55, "destination is not written");
+    }
+}
+
+#[test]
+fn unmapped_store_faults() {
+    for functional in [false, true] {
+        let tc = run(&[InstructionBuilder::new().sw(3, 5, 4).build()], functional);
+        assert_eq!(
+            tc.cpu().csrs.mcause,
+            exception::STORE_ACCESS_FAULT,
+            "functional={functional}"
+        );
+        assert_eq!(tc.cpu().csrs.mtval, UNMAPPED + 4);
+    }
+}
+
+#[test]
+fn unmapped_atomic_faults_as_store() {
+    for functional in [false, true] {
+        let tc = run(
+            &[InstructionBuilder::new().amoadd_w(6, 3, 5).build()],
+            functional,
+        );
+        assert_eq!(
+            tc.cpu().csrs.mcause,
+            exception::STORE_ACCESS_FAULT,
+            "functional={functional}"
+        );
+        assert_eq!(tc.cpu().csrs.mtval, UNMAPPED);
+        assert_eq!(tc.get_reg(6), 0);
+    }
+}
+
+#[test]
+fn access_running_past_end_of_memory_faults() {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    let program = [
+        InstructionBuilder::new().auipc(3, 1).build(),
+        InstructionBuilder::new().addi(5, 0, 0x55).build(),
+        InstructionBuilder::new().ld(5, 3, 0).build(),
+    ];
+    for functional in [false, true] {
+        // 4 bytes of RAM past `PROGRAM_BASE + 0x1000`, so the `ld` there is
+        // aligned but half outside memory.
+        let mut tc = TestContext::with_config(&config)
+            .with_memory(PROGRAM_MEM_SIZE + 4, PROGRAM_BASE)
+            .load_program(PROGRAM_BASE, &program)
+            .with_trap_handlers();
+        tc.cpu_mut().set_functional(functional);
+        tc.run(2_000);
+        assert_eq!(
+            tc.cpu().csrs.mcause,
+            exception::LOAD_ACCESS_FAULT,
+            "functional={functional}"
+        );
+        assert_eq!(tc.cpu().csrs.mtval, PROGRAM_BASE + PROGRAM_MEM_SIZE as u64);
+        assert_eq!(tc.get_reg(5), 0x55);
+    }
+}
@@ -0,0 +1,122 @@
+//! Assembly Dump Tests.
+//!
+//! Verifies that `general.asm_dump` writes one assembly line per retired
+//! instruction, in commit order, and that each line disassembles back to
+//! the instruction that was actually executed at that PC. The dump is
+//! flushed when the program exits, without waiting for the CPU to drop.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext, run_program};
+use rvsim_core::config::Config;
+use rvsim_core::isa::disasm::disassemble;
+
+/// One parsed line of the dump: (assembly text, pc, encoding, compressed).
+type DumpLine = (String, u64, u32, bool);
+
+fn parse_dump(text: &str) -> Vec<DumpLine> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('.'))
+        .map(|l| {
+            let (asm, comment) = l.split_once('#').expect("line should carry a pc comment");
+            let mut fields = comment.split_whitespace();
+            let pc = fields.next().unwrap().trim_end_matches(':');
+            let inst = fields.next().unwrap();
+            (
+                asm.trim().to_string(),
+                u64::from_str_radix(pc.trim_start_matches("0x"), 16).unwrap(),
+                u32::from_str_radix(inst, 16).unwrap(),
+                fields.next() == Some("rvc"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn dump_matches_executed_instructions_in_commit_order() {
+    let path = std::env::temp_dir().join(format!("rvsim_asm_dump_{}.S", std::process::id()));
+    let mut config = Config::default();
+    config.general.asm_dump = Some(path.to_string_lossy().into_owned());
+
+    // Program:
+    //   0: x1 = 0
+    //   4: x2 = 3
+    //   8: x1 = x1 + 1
+    //  12: BNE x1, x2, -4     (loop three times)
+    //  16: C.ADDI x10, 1
+    //  18: C.NOP
+    //  20: JAL x0, 0          (spin)
+    let program = [
+        InstructionBuilder::new().addi(1, 0, 0).build(),
+        InstructionBuilder::new().addi(2, 0, 3).build(),
+        InstructionBuilder::new().addi(1, 1, 1).build(),
+        InstructionBuilder::new().bne(1, 2, -4).build(),
+        InstructionBuilder::C_ADDI_C_NOP,
+        InstructionBuilder::new().jal(0, 0).build(),
+    ];
+    let mut tc = TestContext::program(&config, &program);
+
+    tc.run(200);
+    let retired = tc.cpu().stats.instructions_retired;
+    drop(tc);
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let lines = parse_dump(&text);
+
+    assert_eq!(
+        lines.len() as u64,
+        retired,
+        "one line per retired instruction"
+    );
+
+    let expected_pcs = [0, 4, 8, 12, 8, 12, 8, 12, 16, 18, 20, 20];
+    let pcs: Vec<u64> = lines.iter().map(|l| l.1 - PROGRAM_BASE).collect();
+    assert_eq!(&pcs[..expected_pcs.len()], &expected_pcs);
+
+    for (asm, pc, inst, rvc) in &lines {
+        assert_eq!(
+            *asm,
+            disassemble(*inst),
+            "line at {pc:#x} should reassemble to {inst:#x}"
+        );
+        let offset = (pc - PROGRAM_BASE) as usize;
+        if *rvc {
+            assert!(
+                offset == 16 || offset == 18,
+                "only the C.ADDI/C.NOP pair is compressed"
+            );
+        } else {
+            assert_eq!(*inst, program[offset / 4], "encoding at {pc:#x}");
+        }
+    }
+    assert_eq!(
+        lines[8].0,
+        disassemble(InstructionBuilder::new().addi(10, 10, 1).build())
+    );
+}
+
+#[test]
+fn dump_is_flushed_on_exit() {
+    let path = std::env::temp_dir().join(format!("rvsim_asm_flush_{}.S", std::process::id()));
+    let mut config = Config::default();
+    config.general.asm_dump = Some(path.to_string_lossy().into_owned());
+
+    // Three ADDIs, then a zero word to exit.
+    let program = [
+        InstructionBuilder::new().addi(1, 0, 1).build(),
+        InstructionBuilder::new().addi(2, 0, 2).build(),
+        InstructionBuilder::new().addi(3, 0, 3).build(),
+    ];
+    let tc = run_program(&config, &program);
+
+    // The CPU is still alive, so only the flush on exit can have written
+    // the lines out.
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(
+        parse_dump(&text).len() as u64,
+        tc.cpu().stats.instructions_retired
+    );
+    assert!(tc.cpu().stats.instructions_retired >= 3);
+}
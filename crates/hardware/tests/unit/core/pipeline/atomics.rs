@@ -0,0 +1,366 @@
+//! Atomic Memory Operation Pipeline Tests.
+//!
+//! Verifies that AMOs perform their read-modify-write in the memory stage:
+//! the old value is returned to `rd`, the combined value is written back,
+//! and `.w` forms are sign-extended and touch only their own word. Covers
+//! back-to-back AMOs and stores to the same address, whose old value is
+//! forwarded from the store buffer, LR/SC reservation invalidation by
+//! stores, SCs, and traps, and keeping prefetchers off the reserved line.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, PROGRAM_MEM_SIZE, TestContext};
+use rvsim_core::config::Config;
+
+/// Offset of the data word from the program start.
+const DATA_OFFSET: u64 = 0x400;
+
+const AMOADD: u32 = 0b00000;
+const AMOSWAP: u32 = 0b00001;
+const LR: u32 = 0b00010;
+const SC: u32 = 0b00011;
+const AMOMIN: u32 = 0b10000;
+const AMOMAXU: u32 = 0b11100;
+
+const WIDTH_W: u32 = 0b010;
+const WIDTH_D: u32 = 0b011;
+
+/// AMO encoding `funct5 rd, rs2, (rs1)` without aq/rl.
+fn amo(funct5: u32, width: u32, rd: u32, rs1: u32, rs2: u32) -> u32 {
+    (funct5 << 27) | (rs2 << 20) | (rs1 << 15) | (width << 12) | (rd << 7) | 0x2F
+}
+
+/// Prologue that points x3 at the data word.
+fn data_ptr() -> [u32; 2] {
+    [
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new()
+            .addi(3, 3, DATA_OFFSET as i32)
+            .build(),
+    ]
+}
+
+/// Runs `body` after the data pointer prologue, with the data doubleword
+/// preset to `init`. Caches are enabled so forwarding and drain timing match
+/// a realistic configuration.
+fn run(body: &[u32], init: u64) -> TestContext {
+    let mut program = data_ptr().to_vec();
+    program.extend_from_slice(body);
+    let mut config = Config::default();
+    config.cache.l1_i.enabled = true;
+    config.cache.l1_d.enabled = true;
+    let mut tc = TestContext::program(&config, &program);
+    tc.cpu_mut().mmio_base = PROGRAM_BASE;
+    tc.cpu_mut()
+        .bus
+        .bus
+        .write_u64(PROGRAM_BASE + DATA_OFFSET, init)
+        .unwrap();
+    tc.run_to_exit();
+    tc
+}
+
+fn data(tc: &mut TestContext) -> u64 {
+    tc.cpu_mut()
+        .bus
+        .bus
+        .read_u64(PROGRAM_BASE + DATA_OFFSET)
+        .unwrap()
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Read-modify-write
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn amoadd_w_counter_loop_totals() {
+    // Program:
+    //   x1 = 0; x2 = 20; x5 = 1
+    //   loop: AMOADD.W x4, x5, (x3); x1 += 1; BNE x1, x2, loop
+    //   LW x6, 0(x3)
+    let body = [
+        InstructionBuilder::new().addi(1, 0, 0).build(),
+        InstructionBuilder::new().addi(2, 0, 20).build(),
+        InstructionBuilder::new().addi(5, 0, 1).build(),
+        amo(AMOADD, WIDTH_W, 4, 3, 5),
+        InstructionBuilder::new().addi(1, 1, 1).build(),
+        InstructionBuilder::new().bne(1, 2, -8).build(),
+        InstructionBuilder::new().lw(6, 3, 0).build(),
+    ];
+    let mut tc = run(&body, 0);
+    assert_eq!(tc.get_reg(6), 20);
+    assert_eq!(tc.get_reg(4), 19, "rd holds the value before the last add");
+    assert_eq!(data(&mut tc), 20);
+}
+
+#[test]
+fn amoswap_d_returns_old_and_writes_new() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, -7).build(),
+        amo(AMOSWAP, WIDTH_D, 4, 3, 5),
+    ];
+    let mut tc = run(&body, 0x1234_5678_9ABC_DEF0);
+    assert_eq!(tc.get_reg(4), 0x1234_5678_9ABC_DEF0);
+    assert_eq!(data(&mut tc), -7_i64 as u64);
+}
+
+#[test]
+fn amo_w_leaves_upper_word_untouched() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 1).build(),
+        amo(AMOADD, WIDTH_W, 4, 3, 5),
+    ];
+    let mut tc = run(&body, 0xAAAA_BBBB_FFFF_FFFF);
+    assert_eq!(tc.get_reg(4), u64::MAX, "old word is sign-extended");
+    assert_eq!(data(&mut tc), 0xAAAA_BBBB_0000_0000);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Forwarding from in-flight stores
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn back_to_back_amoadd_w_sign_extends_forwarded_value() {
+    // The second AMO reads the first one's result from the store buffer.
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 1).build(),
+        amo(AMOADD, WIDTH_W, 4, 3, 5),
+        amo(AMOADD, WIDTH_W, 8, 3, 5),
+    ];
+    let mut tc = run(&body, (-3_i64 as u64) & 0xFFFF_FFFF);
+    assert_eq!(tc.get_reg(4), -3_i64 as u64);
+    assert_eq!(tc.get_reg(8), -2_i64 as u64);
+    assert_eq!(data(&mut tc), 0xFFFF_FFFF);
+}
+
+#[test]
+fn amo_after_store_sees_stored_value() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 5).build(),
+        InstructionBuilder::new().addi(7, 0, -2).build(),
+        InstructionBuilder::new().sw(3, 7, 0).build(),
+        amo(AMOMIN, WIDTH_W, 4, 3, 5),
+        amo(AMOMAXU, WIDTH_W, 8, 3, 5),
+    ];
+    let mut tc = run(&body, 0);
+    assert_eq!(tc.get_reg(4), -2_i64 as u64);
+    assert_eq!(tc.get_reg(8), -2_i64 as u64, "min kept the negative value");
+    assert_eq!(data(&mut tc), 0xFFFF_FFFE, "maxu keeps 0xFFFFFFFE over 5");
+}
+
+#[test]
+fn lr_w_after_store_sign_extends() {
+    let body = [
+        InstructionBuilder::new().addi(7, 0, -9).build(),
+        InstructionBuilder::new().sw(3, 7, 0).build(),
+        lr_w(4, 3),
+    ];
+    let tc = run(&body, 0);
+    assert_eq!(tc.get_reg(4), -9_i64 as u64);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. LR/SC reservations
+// ══════════════════════════════════════════════════════════
+
+/// `sc.w rd, rs2, (rs1)`
+fn sc_w(rd: u32, rs1: u32, rs2: u32) -> u32 {
+    amo(SC, WIDTH_W, rd, rs1, rs2)
+}
+
+/// `lr.w rd, (rs1)`
+fn lr_w(rd: u32, rs1: u32) -> u32 {
+    amo(LR, WIDTH_W, rd, rs1, 0)
+}
+
+#[test]
+fn sc_after_lr_succeeds_and_stores() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 42).build(),
+        lr_w(4, 3),
+        sc_w(10, 3, 5),
+    ];
+    let mut tc = run(&body, 0);
+    assert_eq!(tc.get_reg(10), 0);
+    assert_eq!(data(&mut tc), 42);
+    assert_eq!(tc.cpu().load_reservation, None);
+}
+
+#[test]
+fn sc_without_reservation_fails() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 42).build(),
+        sc_w(10, 3, 5),
+    ];
+    let mut tc = run(&body, 7);
+    assert_eq!(tc.get_reg(10), 1);
+    assert_eq!(data(&mut tc), 7, "failed SC must not write memory");
+}
+
+#[test]
+fn store_to_reserved_granule_breaks_reservation() {
+    // The store hits a different word in the same 64-byte granule.
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 42).build(),
+        lr_w(4, 3),
+        InstructionBuilder::new().sw(3, 5, 32).build(),
+        sc_w(10, 3, 5),
+    ];
+    let mut tc = run(&body, 7);
+    assert_eq!(tc.get_reg(10), 1);
+    assert_eq!(data(&mut tc), 7);
+}
+
+#[test]
+fn store_to_other_granule_keeps_reservation() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 42).build(),
+        lr_w(4, 3),
+        InstructionBuilder::new().sw(3, 5, 64).build(),
+        sc_w(10, 3, 5),
+    ];
+    let tc = run(&body, 0);
+    assert_eq!(tc.get_reg(10), 0);
+}
+
+#[test]
+fn amo_to_reserved_granule_breaks_reservation() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 1).build(),
+        lr_w(4, 3),
+        amo(AMOADD, WIDTH_W, 6, 3, 5),
+        sc_w(10, 3, 5),
+    ];
+    let tc = run(&body, 0);
+    assert_eq!(tc.get_reg(10), 1);
+}
+
+#[test]
+fn sc_invalidates_reservation_even_when_it_fails() {
+    // The first SC targets another granule and fails; the second must fail
+    // too, since any SC consumes the reservation.
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 42).build(),
+        InstructionBuilder::new().addi(6, 3, 128).build(),
+        lr_w(4, 3),
+        sc_w(10, 6, 5),
+        sc_w(11, 3, 5),
+    ];
+    let mut tc = run(&body, 7);
+    assert_eq!(tc.get_reg(10), 1);
+    assert_eq!(tc.get_reg(11), 1);
+    assert_eq!(data(&mut tc), 7);
+}
+
+#[test]
+fn second_sc_after_success_fails() {
+    let body = [
+        InstructionBuilder::new().addi(5, 0, 42).build(),
+        lr_w(4, 3),
+        sc_w(10, 3, 5),
+        sc_w(11, 3, 0),
+    ];
+    let mut tc = run(&body, 0);
+    assert_eq!(tc.get_reg(10), 0);
+    assert_eq!(tc.get_reg(11), 1);
+    assert_eq!(data(&mut tc), 42);
+}
+
+#[test]
+fn snooped_store_from_another_agent_breaks_reservation() {
+    let mut tc = TestContext::new();
+    let reserved = PROGRAM_BASE + DATA_OFFSET;
+    tc.cpu_mut().load_reservation = Some(reserved);
+
+    tc.cpu_mut().snoop_store(reserved + 64, 8);
+    assert!(tc.cpu().load_reservation.is_some(), "other granule");
+
+    // A misaligned store straddling into the granule still counts.
+    tc.cpu_mut().snoop_store(reserved - 4, 8);
+    assert_eq!(tc.cpu().load_reservation, None);
+}
+
+#[test]
+fn trap_clears_reservation() {
+    // Program (after the data pointer prologue):
+    //   8: LR.W x4, (x3)
+    //  12: (illegal)
+    //  16: x7 = 1
+    //  20: handler: x5 = 42
+    //  24: SC.W x10, x5, (x3)
+    //  28: JAL x0, 0           (spin)
+    let mut program = data_ptr().to_vec();
+    program.extend_from_slice(&[
+        lr_w(4, 3),
+        0xFFFF_FFFF,
+        InstructionBuilder::new().addi(7, 0, 1).build(),
+        InstructionBuilder::new().addi(5, 0, 42).build(),
+        sc_w(10, 3, 5),
+        InstructionBuilder::new().jal(0, 0).build(),
+    ]);
+    let mut tc = TestContext::program(&Config::default(), &program);
+    tc.cpu_mut().direct_mode = false;
+    tc.cpu_mut().csrs.mtvec = PROGRAM_BASE + 20;
+    tc.set_reg(10, 0xDEAD);
+    for _ in 0..200 {
+        tc.sim.tick().unwrap();
+        if tc.get_reg(10) != 0xDEAD {
+            break;
+        }
+    }
+    assert_eq!(tc.get_reg(7), 0);
+    assert_eq!(tc.get_reg(10), 1, "SC in the handler must fail");
+}
+
+// ══════════════════════════════════════════════════════════
+// 4. Prefetch isolation
+// ══════════════════════════════════════════════════════════
+
+/// Runs a demand read of the line just below the data word with a
+/// next-line L1-D prefetcher, optionally while the data line is reserved,
+/// and reports whether the data line ended up in the L1-D.
+fn prefetches_data_line(reserved: bool) -> bool {
+    let mut config = Config::default();
+    config.cache.l1_d.enabled = true;
+    config.cache.l1_d.prefetcher = rvsim_core::config::Prefetcher::NextLine;
+    let mut tc = TestContext::with_config(&config).with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE);
+    let data_addr = PROGRAM_BASE + DATA_OFFSET;
+    let cpu = tc.cpu_mut();
+    cpu.simple_timing = false;
+    if reserved {
+        cpu.load_reservation = Some(data_addr);
+    }
+    let below = data_addr - cpu.l1_d_cache.line_bytes() as u64;
+    let _ = cpu.simulate_memory_access(
+        rvsim_core::common::PhysAddr::new(below),
+        rvsim_core::common::AccessType::Read,
+    );
+    cpu.l1_d_cache.contains(data_addr)
+}
+
+#[test]
+fn prefetch_does_not_install_reserved_line() {
+    assert!(
+        prefetches_data_line(false),
+        "next line is prefetched normally"
+    );
+    assert!(!prefetches_data_line(true), "reserved line is left alone");
+}
+
+#[test]
+fn atomic_prefetch_lock_is_released_after_lr_sc() {
+    // Program:
+    //   LR.W x4, (x3)
+    //   ADDI x4, x4, 1
+    //   SC.W x6, x4, (x3)
+    //   BNE  x6, x0, -12       (retry on failure)
+    let body = [
+        lr_w(4, 3),
+        InstructionBuilder::new().addi(4, 4, 1).build(),
+        sc_w(6, 3, 4),
+        InstructionBuilder::new().bne(6, 0, -12).build(),
+    ];
+    let mut tc = run(&body, 41);
+    assert_eq!(tc.get_reg(6), 0, "SC succeeded");
+    assert_eq!(data(&mut tc), 42);
+    assert!(tc.cpu().atomic_line.is_none(), "atomic lock released");
+}
@@ -0,0 +1,159 @@
+//! Big-Endian Data Tests.
+//!
+//! Verifies that with `memory.big_endian` stores place the most significant
+//! byte at the lowest address, loads and atomics read the same layout back,
+//! sign extension applies to the byte-swapped value, instruction fetch stays
+//! little-endian, both execution modes agree, and `mstatus.MBE`/`SBE`/`UBE`
+//! are fixed to the configured byte order.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr;
+
+/// Offset of the data word from the program base.
+const DATA: i32 = 0x400;
+
+/// I-type load `funct3 rd, imm(rs1)`.
+fn load(funct3: u32, rd: u32, rs1: u32, imm: i32) -> u32 {
+    ((imm as u32 & 0xFFF) << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | 0x03
+}
+
+const LB: u32 = 0b000;
+const LH: u32 = 0b001;
+const LBU: u32 = 0b100;
+
+/// `csrrw rd, csr, rs1`.
+fn csrrw(rd: u32, csr: u32, rs1: u32) -> u32 {
+    (csr << 20) | (rs1 << 15) | (0b001 << 12) | (rd << 7) | 0x73
+}
+
+/// `csrrs rd, csr, x0`.
+fn csrr(rd: u32, csr: u32) -> u32 {
+    (csr << 20) | (0b010 << 12) | (rd << 7) | 0x73
+}
+
+/// x5 = data base, x6 = 0x8182_0304, `sw x6, DATA(x5)`, then `tail`.
+fn store_word_then(tail: &[u32]) -> Vec<u32> {
+    let mut program = vec![
+        InstructionBuilder::new().auipc(5, 0).build(),
+        InstructionBuilder::new().lui(6, 0x81820).build(),
+        InstructionBuilder::new().addi(6, 6, 0x304).build(),
+        InstructionBuilder::new().sw(5, 6, DATA).build(),
+    ];
+    program.extend_from_slice(tail);
+    program.push(InstructionBuilder::new().nop().build());
+    program
+}
+
+fn context(program: &[u32], big_endian: bool) -> TestContext {
+    let mut config = Config::default();
+    config.memory.big_endian = big_endian;
+    TestContext::program(&config, program)
+}
+
+/// Runs `program` to the direct-mode exit, optionally in functional mode.
+fn run(program: &[u32], big_endian: bool, functional: bool) -> TestContext {
+    let mut tc = context(program, big_endian);
+    tc.cpu_mut().set_functional(functional);
+    tc.run_to_exit();
+    tc
+}
+
+/// The four bytes at the data word, lowest address first.
+fn data_bytes(tc: &mut TestContext) -> [u8; 4] {
+    let word = tc
+        .sim
+        .cpu
+        .bus
+        .bus
+        .read_u32(PROGRAM_BASE + DATA as u64)
+        .unwrap();
+    word.to_le_bytes()
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Memory layout
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn store_places_most_significant_byte_first() {
+    for functional in [false, true] {
+        let mut tc = run(&store_word_then(&[]), true, functional);
+        assert_eq!(data_bytes(&mut tc), [0x81, 0x82, 0x03, 0x04]);
+    }
+}
+
+#[test]
+fn little_endian_is_the_default() {
+    let mut tc = run(&store_word_then(&[]), false, false);
+    assert_eq!(data_bytes(&mut tc), [0x04, 0x03, 0x82, 0x81]);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Loads
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn loads_read_big_endian_layout() {
+    // Forwarded from the store buffer and, after a fence, read from memory.
+    for fence in [None, Some(0x0FF0_000F)] {
+        let mut tail: Vec<u32> = fence.into_iter().collect();
+        tail.extend([
+            InstructionBuilder::new().lw(7, 5, DATA).build(),
+            load(LBU, 8, 5, DATA),
+            load(LBU, 9, 5, DATA + 3),
+            load(LH, 10, 5, DATA),
+            load(LB, 11, 5, DATA + 2),
+        ]);
+        for functional in [false, true] {
+            let tc = run(&store_word_then(&tail), true, functional);
+            assert_eq!(tc.get_reg(7), 0xFFFF_FFFF_8182_0304, "lw sign-extends");
+            assert_eq!(tc.get_reg(8), 0x81);
+            assert_eq!(tc.get_reg(9), 0x04);
+            assert_eq!(tc.get_reg(10), 0xFFFF_FFFF_FFFF_8182, "lh sign-extends");
+            assert_eq!(tc.get_reg(11), 0x03);
+        }
+    }
+}
+
+#[test]
+fn amo_reads_and_writes_big_endian() {
+    let tail = [
+        InstructionBuilder::new().addi(7, 0, 1).build(),
+        InstructionBuilder::new().addi(5, 5, DATA).build(),
+        InstructionBuilder::new().amoadd_w(8, 5, 7).build(),
+    ];
+    for functional in [false, true] {
+        let mut tc = run(&store_word_then(&tail), true, functional);
+        assert_eq!(tc.get_reg(8), 0xFFFF_FFFF_8182_0304);
+        assert_eq!(data_bytes(&mut tc), [0x81, 0x82, 0x03, 0x05]);
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. mstatus endianness bits
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn mstatus_reports_configured_byte_order() {
+    let program = [
+        csrr(7, csr::MSTATUS),
+        InstructionBuilder::new().nop().build(),
+    ];
+    let be = run(&program, true, false).get_reg(7);
+    assert_eq!(be & csr::MSTATUS_BE, csr::MSTATUS_BE);
+    let le = run(&program, false, false).get_reg(7);
+    assert_eq!(le & csr::MSTATUS_BE, 0);
+}
+
+#[test]
+fn mstatus_endianness_bits_are_read_only() {
+    let program = [
+        csrrw(0, csr::MSTATUS, 0),
+        csrr(7, csr::MSTATUS),
+        InstructionBuilder::new().nop().build(),
+    ];
+    let tc = run(&program, true, false);
+    assert_eq!(tc.get_reg(7) & csr::MSTATUS_BE, csr::MSTATUS_BE);
+}
@@ -0,0 +1,169 @@
+//! Commit Log Tests.
+//!
+//! Verifies that `general.commit_log` writes one Spike `--log-commits` line
+//! per retired instruction, with the as-fetched encoding, destination
+//! register and CSR writes, and memory access addresses and store data.
+//! The log is flushed when the program exits, without waiting for the CPU
+//! to drop.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext, run_program};
+use rvsim_core::config::Config;
+
+/// `csrrw x0, mscratch, x5`
+const CSRW_MSCRATCH_X5: u32 = 0x3402_9073;
+/// `amoadd.w x7, x5, (x3)`
+const AMOADD_W_X7_X5_X3: u32 = 0x0051_A3AF;
+
+/// Runs `program` with the commit log enabled and returns the log lines.
+fn log_of(name: &str, program: &[u32]) -> Vec<String> {
+    let path = std::env::temp_dir().join(format!(
+        "rvsim_commit_log_{name}_{}.log",
+        std::process::id()
+    ));
+    let mut config = Config::default();
+    config.general.commit_log = Some(path.to_string_lossy().into_owned());
+    let mut tc = TestContext::program(&config, program);
+    tc.run(200);
+    let retired = tc.cpu().stats.instructions_retired;
+    drop(tc);
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let lines: Vec<String> = text.lines().map(str::to_string).collect();
+    assert_eq!(
+        lines.len() as u64,
+        retired,
+        "one line per retired instruction"
+    );
+    lines
+}
+
+#[test]
+fn lines_use_spike_commit_format() {
+    // Program:
+    //   0: x5 = 0x55
+    //   4: x0 = 1               (write to x0 is not logged)
+    //   8: C.ADDI x10, 1
+    //  10: C.NOP
+    //  12: AUIPC x3, 0
+    //  16: x3 = x3 + 0x400
+    //  20: SW x5, 0(x3)
+    //  24: LW x6, 0(x3)
+    //  28: CSRW mscratch, x5
+    //  32: AMOADD.W x7, x5, (x3)
+    //  36: JAL x0, 0            (spin)
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 0x55).build(),
+        InstructionBuilder::new().addi(0, 0, 1).build(),
+        InstructionBuilder::C_ADDI_C_NOP,
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new().addi(3, 3, 0x400).build(),
+        InstructionBuilder::new().sw(3, 5, 0).build(),
+        InstructionBuilder::new().lw(6, 3, 0).build(),
+        CSRW_MSCRATCH_X5,
+        AMOADD_W_X7_X5_X3,
+        InstructionBuilder::new().jal(0, 0).build(),
+    ];
+    let lines = log_of("format", &program);
+    let data = PROGRAM_BASE + 12 + 0x400;
+
+    let expected = [
+        format!(
+            "core   0: 3 0x{PROGRAM_BASE:016x} (0x{:08x}) x5  0x0000000000000055",
+            program[0]
+        ),
+        format!(
+            "core   0: 3 0x{:016x} (0x{:08x})",
+            PROGRAM_BASE + 4,
+            program[1]
+        ),
+        format!(
+            "core   0: 3 0x{:016x} (0x0505) x10 0x0000000000000001",
+            PROGRAM_BASE + 8
+        ),
+        format!("core   0: 3 0x{:016x} (0x0001)", PROGRAM_BASE + 10),
+        format!(
+            "core   0: 3 0x{:016x} (0x{:08x}) x3  0x{:016x}",
+            PROGRAM_BASE + 12,
+            program[3],
+            PROGRAM_BASE + 12
+        ),
+        format!(
+            "core   0: 3 0x{:016x} (0x{:08x}) x3  0x{data:016x}",
+            PROGRAM_BASE + 16,
+            program[4]
+        ),
+        format!(
+            "core   0: 3 0x{:016x} (0x{:08x}) mem 0x{data:016x} 0x00000055",
+            PROGRAM_BASE + 20,
+            program[5]
+        ),
+        format!(
+            "core   0: 3 0x{:016x} (0x{:08x}) x6  0x0000000000000055 mem 0x{data:016x}",
+            PROGRAM_BASE + 24,
+            program[6]
+        ),
+        format!(
+            "core   0: 3 0x{:016x} (0x{CSRW_MSCRATCH_X5:08x}) c832_mscratch 0x0000000000000055",
+            PROGRAM_BASE + 28
+        ),
+        format!(
+            "core   0: 3 0x{:016x} (0x{AMOADD_W_X7_X5_X3:08x}) x7  0x0000000000000055 \
+             mem 0x{data:016x} mem 0x{data:016x} 0x000000aa",
+            PROGRAM_BASE + 32
+        ),
+    ];
+    for (i, want) in expected.iter().enumerate() {
+        assert_eq!(lines[i], *want, "line {i}");
+    }
+    let spin = format!("(0x{:08x})", program[9]);
+    assert!(lines[expected.len()..].iter().all(|l| l.contains(&spin)));
+}
+
+#[test]
+fn failed_sc_logs_no_store() {
+    // Program:
+    //   0: AUIPC x3, 0
+    //   4: x3 = x3 + 0x400
+    //   8: SC.W x10, x0, (x3)   (no reservation: fails)
+    let program = [
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new().addi(3, 3, 0x400).build(),
+        0x1801_A52F,
+    ];
+    let lines = log_of("failed_sc", &program);
+    assert_eq!(
+        lines[2],
+        format!(
+            "core   0: 3 0x{:016x} (0x1801a52f) x10 0x0000000000000001",
+            PROGRAM_BASE + 8
+        )
+    );
+}
+
+#[test]
+fn log_is_flushed_on_exit() {
+    let path =
+        std::env::temp_dir().join(format!("rvsim_commit_log_flush_{}.log", std::process::id()));
+    let mut config = Config::default();
+    config.general.commit_log = Some(path.to_string_lossy().into_owned());
+
+    // Three ADDIs, then a zero word to exit.
+    let program = [
+        InstructionBuilder::new().addi(1, 0, 1).build(),
+        InstructionBuilder::new().addi(2, 0, 2).build(),
+        InstructionBuilder::new().addi(3, 0, 3).build(),
+    ];
+    let tc = run_program(&config, &program);
+
+    // The CPU is still alive, so only the flush on exit can have written
+    // the lines out.
+    let text = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(
+        text.lines().count() as u64,
+        tc.cpu().stats.instructions_retired
+    );
+    assert!(tc.cpu().stats.instructions_retired >= 3);
+}
@@ -0,0 +1,169 @@
+//! Execution Latency Tests.
+//!
+//! Verifies that `pipeline.mul_latency` and `pipeline.div_latency` hold the
+//! execute unit for the configured number of cycles, so the total cycle
+//! count grows by the extra latency per long operation, that a divide by
+//! zero still completes in a single cycle, and that divides issued together
+//! serialize on the non-pipelined divider.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{TestContext, run_program};
+use rvsim_core::config::Config;
+
+/// Runs `program` to the direct-mode exit with the given multiply and
+/// divide latencies and returns the context.
+fn run(program: &[u32], mul_latency: u64, div_latency: u64) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.mul_latency = mul_latency;
+    config.pipeline.div_latency = div_latency;
+    run_program(&config, program)
+}
+
+/// x5 = 100, x6 = `divisor`, then `count` independent copies of `op`.
+fn program(divisor: i32, count: usize, op: u32) -> Vec<u32> {
+    let mut program = vec![
+        InstructionBuilder::new().addi(5, 0, 100).build(),
+        InstructionBuilder::new().addi(6, 0, divisor).build(),
+    ];
+    program.extend(std::iter::repeat_n(op, count));
+    program
+}
+
+/// Cycles added by one more copy of `op` in a run of `count`.
+fn marginal_cycles(divisor: i32, count: usize, op: u32, mul: u64, div: u64) -> u64 {
+    let shorter = run(&program(divisor, count, op), mul, div);
+    let longer = run(&program(divisor, count + 1, op), mul, div);
+    longer.cpu().stats.cycles - shorter.cpu().stats.cycles
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Long operations stall the pipeline
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn divide_holds_execute_for_div_latency() {
+    let div = InstructionBuilder::new().div(7, 5, 6).build();
+    assert_eq!(run(&program(7, 4, div), 1, 20).get_reg(7), 14);
+    assert_eq!(marginal_cycles(7, 4, div, 1, 1), 1);
+    assert_eq!(marginal_cycles(7, 4, div, 1, 20), 20);
+}
+
+#[test]
+fn remainder_uses_divide_latency() {
+    let rem = InstructionBuilder::new().rem(7, 5, 6).build();
+    assert_eq!(run(&program(7, 3, rem), 1, 10).get_reg(7), 2);
+    assert_eq!(marginal_cycles(7, 3, rem, 1, 10), 10);
+}
+
+#[test]
+fn multiply_holds_execute_for_mul_latency() {
+    let mul = InstructionBuilder::new().mul(7, 5, 6).build();
+    assert_eq!(run(&program(7, 5, mul), 4, 1).get_reg(7), 700);
+    assert_eq!(marginal_cycles(7, 5, mul, 4, 1), 4);
+    assert_eq!(
+        marginal_cycles(7, 5, mul, 4, 30),
+        4,
+        "multiply ignores the divide latency"
+    );
+}
+
+#[test]
+fn divide_heavy_code_runs_slower_than_alu_code() {
+    let div = InstructionBuilder::new().div(7, 5, 6).build();
+    let add = InstructionBuilder::new().add(7, 5, 6).build();
+    let divs = run(&program(7, 8, div), 3, 20);
+    let adds = run(&program(7, 8, add), 3, 20);
+    assert!(divs.cpu().stats.cycles > adds.cpu().stats.cycles + 7 * 19);
+}
+
+#[test]
+fn divide_then_add_in_one_group_keeps_divide_latency() {
+    // The add issues after the divide in the same group and must not cut
+    // short its hold on execute, so the order within the group is irrelevant.
+    let add = |b: InstructionBuilder, rd| b.add(rd, 5, 6);
+    let divide_first = run_wide(&independent(&[div, add]), 2);
+    let add_first = run_wide(&independent(&[add, div]), 2);
+    assert_eq!(divide_first.get_reg(7), 14);
+    assert_eq!(
+        divide_first.cpu().stats.cycles,
+        add_first.cpu().stats.cycles
+    );
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Divide by zero
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn divide_by_zero_is_single_cycle() {
+    let div = InstructionBuilder::new().div(7, 5, 6).build();
+    assert_eq!(
+        run(&program(0, 4, div), 1, 20).get_reg(7),
+        u64::MAX,
+        "x / 0 = -1"
+    );
+    assert_eq!(marginal_cycles(0, 4, div, 1, 20), 1);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Structural hazards on non-pipelined units
+// ══════════════════════════════════════════════════════════
+
+/// Runs `program` at `width` with a 20-cycle divide and 3-cycle multiply.
+fn run_wide(program: &[u32], width: usize) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.width = width;
+    config.pipeline.mul_latency = 3;
+    config.pipeline.div_latency = 20;
+    run_program(&config, program)
+}
+
+/// x5 = 100, x6 = 7, then `ops` writing x7, x8, ... in turn.
+fn independent(ops: &[fn(InstructionBuilder, u32) -> InstructionBuilder]) -> Vec<u32> {
+    let mut program = vec![
+        InstructionBuilder::new().addi(5, 0, 100).build(),
+        InstructionBuilder::new().addi(6, 0, 7).build(),
+    ];
+    for (i, op) in ops.iter().enumerate() {
+        program.push(op(InstructionBuilder::new(), 7 + i as u32).build());
+    }
+    program
+}
+
+fn div(b: InstructionBuilder, rd: u32) -> InstructionBuilder {
+    b.div(rd, 5, 6)
+}
+
+fn mul(b: InstructionBuilder, rd: u32) -> InstructionBuilder {
+    b.mul(rd, 5, 6)
+}
+
+#[test]
+fn independent_divides_serialize_on_the_divider() {
+    let two = run_wide(&independent(&[div, div]), 4);
+    let three = run_wide(&independent(&[div, div, div]), 4);
+    assert_eq!(three.get_reg(9), 14);
+    assert_eq!(three.cpu().stats.cycles - two.cpu().stats.cycles, 20);
+    assert_eq!(three.cpu().stats.stalls_struct, 2, "one per held divide");
+}
+
+#[test]
+fn single_issue_divides_have_no_structural_stalls() {
+    let tc = run_wide(&independent(&[div, div, div]), 1);
+    assert_eq!(tc.cpu().stats.stalls_struct, 0, "execute already waits");
+}
+
+#[test]
+fn multiply_does_not_use_the_divider() {
+    let tc = run_wide(&independent(&[div, mul, mul]), 4);
+    assert_eq!(tc.get_reg(8), 700);
+    assert_eq!(tc.cpu().stats.stalls_struct, 0);
+}
+
+#[test]
+fn divide_after_a_short_op_in_the_same_group_still_holds_execute() {
+    let add = |b: InstructionBuilder, rd| b.add(rd, 5, 6);
+    let alone = run_wide(&independent(&[div, add]), 4);
+    let longer = run_wide(&independent(&[div, add, div]), 4);
+    assert!(longer.cpu().stats.cycles >= alone.cpu().stats.cycles + 20);
+}
@@ -0,0 +1,141 @@
+//! FENCE and FENCE.I Tests.
+//!
+//! Verifies that FENCE.I makes code written by earlier stores visible to
+//! fetch, that both fences wait for older stores to drain from the store
+//! buffer and then hold execute for `pipeline.fence_latency` cycles, that
+//! a FENCE with empty ordering sets is an ordinary NOP, and that a store to
+//! a line held in the L1 I-cache invalidates it.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{TestContext, run_program};
+use rvsim_core::config::Config;
+
+/// Offset of the data word from the program base.
+const DATA: i32 = 0x400;
+
+/// `fence iorw, iorw`
+const FENCE: u32 = 0x0FF0_000F;
+/// `fence` with empty predecessor and successor sets.
+const FENCE_NONE: u32 = 0x0000_000F;
+/// `fence.i`
+const FENCE_I: u32 = 0x0000_100F;
+
+/// Runs `program` to the direct-mode exit with the given fence latency.
+fn run(program: &[u32], fence_latency: u64) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.fence_latency = fence_latency;
+    run_program(&config, program)
+}
+
+/// x5 = program base, x6 = 0x123, `sw x6, DATA(x5)`, then `fence`.
+fn store_then(fence: u32) -> Vec<u32> {
+    vec![
+        InstructionBuilder::new().auipc(5, 0).build(),
+        InstructionBuilder::new().addi(6, 0, 0x123).build(),
+        InstructionBuilder::new().sw(5, 6, DATA).build(),
+        fence,
+        InstructionBuilder::new().nop().build(),
+    ]
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. FENCE.I and self-modifying code
+// ══════════════════════════════════════════════════════════
+
+/// Overwrites the instruction after `fence` with `addi x7, x0, 42`; the
+/// original instruction there is `addi x7, x0, 1`.
+fn patch_then(fence: u32) -> Vec<u32> {
+    let patch = InstructionBuilder::new().addi(7, 0, 42).build() as i32;
+    vec![
+        InstructionBuilder::new().auipc(5, 0).build(),
+        InstructionBuilder::new()
+            .lui(6, patch.wrapping_add(0x800) >> 12)
+            .build(),
+        InstructionBuilder::new()
+            .addi(6, 6, (patch << 20) >> 20)
+            .build(),
+        InstructionBuilder::new().sw(5, 6, 20).build(),
+        fence,
+        InstructionBuilder::new().addi(7, 0, 1).build(),
+        InstructionBuilder::new().nop().build(),
+    ]
+}
+
+#[test]
+fn fence_i_fetches_freshly_written_code() {
+    assert_eq!(run(&patch_then(FENCE_I), 1).get_reg(7), 42);
+}
+
+#[test]
+fn fence_i_observes_patch_with_long_latency() {
+    assert_eq!(run(&patch_then(FENCE_I), 8).get_reg(7), 42);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Fence cost
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn fence_holds_execute_for_fence_latency() {
+    let fast = run(&store_then(FENCE), 1);
+    let slow = run(&store_then(FENCE), 10);
+    assert_eq!(slow.cpu().stats.cycles - fast.cpu().stats.cycles, 9);
+}
+
+#[test]
+fn fence_i_holds_execute_for_fence_latency() {
+    let fast = run(&store_then(FENCE_I), 1);
+    let slow = run(&store_then(FENCE_I), 10);
+    assert_eq!(slow.cpu().stats.cycles - fast.cpu().stats.cycles, 9);
+}
+
+#[test]
+fn fence_waits_for_store_buffer_drain() {
+    let fenced = run(&store_then(FENCE), 1);
+    let unfenced = run(&store_then(FENCE_NONE), 1);
+    assert!(
+        fenced.cpu().stats.stalls_mem > unfenced.cpu().stats.stalls_mem,
+        "fence should stall until the store drains ({} vs {})",
+        fenced.cpu().stats.stalls_mem,
+        unfenced.cpu().stats.stalls_mem
+    );
+    assert!(fenced.cpu().stats.cycles > unfenced.cpu().stats.cycles);
+}
+
+#[test]
+fn empty_fence_is_a_nop() {
+    let fast = run(&store_then(FENCE_NONE), 1);
+    let slow = run(&store_then(FENCE_NONE), 10);
+    assert_eq!(slow.cpu().stats.cycles, fast.cpu().stats.cycles);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. I-cache snooping
+// ══════════════════════════════════════════════════════════
+
+/// Runs `patch_then(fence)` with the L1 I-cache enabled.
+fn run_with_icache(fence: u32) -> TestContext {
+    let mut config = Config::default();
+    config.cache.l1_i.enabled = true;
+    run_program(&config, &patch_then(fence))
+}
+
+#[test]
+fn store_to_cached_code_invalidates_icache_line() {
+    let tc = run_with_icache(FENCE);
+    assert_eq!(tc.cpu().stats.icache_invalidations, 1);
+}
+
+#[test]
+fn store_to_data_leaves_icache_alone() {
+    let mut config = Config::default();
+    config.cache.l1_i.enabled = true;
+    let mut tc = TestContext::program(&config, &store_then(FENCE));
+    tc.run(10_000);
+    assert_eq!(tc.cpu().stats.icache_invalidations, 0);
+}
+
+#[test]
+fn patched_code_runs_with_icache_enabled() {
+    assert_eq!(run_with_icache(FENCE_I).get_reg(7), 42);
+}
@@ -0,0 +1,129 @@
+//! Fetch Width and Fetch Queue Tests.
+//!
+//! Verifies that `pipeline.fetch_width` defaults to the pipeline width,
+//! that a wider fetch with a fetch queue leaves architectural results
+//! unchanged and decode still limited to `width`, and that the queue lets
+//! fetch (and its I-cache misses) run ahead while the backend is stalled.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, PROGRAM_MEM_SIZE, TestContext};
+use rvsim_core::config::Config;
+
+/// Runs `program` to the direct-mode exit with the given front-end shape.
+fn run(
+    program: &[u32],
+    width: usize,
+    fetch_width: usize,
+    fetch_queue_size: usize,
+    tweak: impl FnOnce(&mut Config),
+) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.width = width;
+    config.pipeline.fetch_width = fetch_width;
+    config.pipeline.fetch_queue_size = fetch_queue_size;
+    tweak(&mut config);
+    let mut tc = TestContext::program(&config, program);
+    tc.run_to_exit();
+    tc
+}
+
+/// Sums 1..=10 into x7 with a backward branch.
+fn sum_loop() -> Vec<u32> {
+    vec![
+        InstructionBuilder::new().addi(5, 0, 10).build(),
+        InstructionBuilder::new().addi(7, 0, 0).build(),
+        InstructionBuilder::new().add(7, 7, 5).build(),
+        InstructionBuilder::new().addi(5, 5, -1).build(),
+        InstructionBuilder::new().bne(5, 0, -8).build(),
+        InstructionBuilder::new().nop().build(),
+    ]
+}
+
+/// A divide followed by `nops` straight-line instructions.
+fn div_then_nops(nops: usize) -> Vec<u32> {
+    let mut program = vec![
+        InstructionBuilder::new().addi(5, 0, 100).build(),
+        InstructionBuilder::new().addi(6, 0, 7).build(),
+        InstructionBuilder::new().div(7, 5, 6).build(),
+        InstructionBuilder::new().add(8, 7, 7).build(),
+    ];
+    program.extend(std::iter::repeat_n(
+        InstructionBuilder::new().nop().build(),
+        nops,
+    ));
+    program
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Configuration
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn fetch_width_defaults_to_pipeline_width() {
+    let mut config = Config::default();
+    config.pipeline.width = 3;
+    let tc = TestContext::with_config(&config).with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE);
+    assert_eq!(tc.cpu().fetch_width, 3);
+
+    config.pipeline.fetch_width = 5;
+    let tc = TestContext::with_config(&config).with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE);
+    assert_eq!(tc.cpu().fetch_width, 5);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Correctness and decode bandwidth
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn wide_fetch_with_queue_preserves_results() {
+    for (width, fetch_width, queue) in [(1, 4, 8), (2, 4, 16), (4, 2, 4), (2, 8, 0)] {
+        let tc = run(&sum_loop(), width, fetch_width, queue, |_| {});
+        assert_eq!(
+            tc.get_reg(7),
+            55,
+            "width={width} fetch={fetch_width} q={queue}"
+        );
+    }
+}
+
+#[test]
+fn decode_limited_to_pipeline_width() {
+    let tc = run(&div_then_nops(64), 1, 4, 16, |_| {});
+    let stats = &tc.cpu().stats;
+    assert!(
+        stats.cycles >= stats.instructions_retired,
+        "a 1-wide backend cannot retire more than one instruction a cycle \
+         ({} instructions in {} cycles)",
+        stats.instructions_retired,
+        stats.cycles
+    );
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Decoupling
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn fetch_queue_hides_icache_misses_behind_backend_stall() {
+    let program = div_then_nops(40);
+    let tweak = |c: &mut Config| {
+        c.cache.l1_i.enabled = true;
+        c.pipeline.div_latency = 60;
+        // A small ROB makes the divide back up into the front end.
+        c.pipeline.rob_size = 4;
+    };
+    let coupled = run(&program, 1, 1, 0, tweak).cpu().stats.cycles;
+    let decoupled = run(&program, 1, 1, 32, tweak).cpu().stats.cycles;
+    assert!(
+        decoupled < coupled,
+        "fetch should run ahead during the divide: {decoupled} vs {coupled} cycles"
+    );
+}
+
+#[test]
+fn no_queue_matches_default_timing() {
+    let program = div_then_nops(16);
+    let default = run(&program, 2, 0, 0, |_| {}).cpu().stats.cycles;
+    let explicit = run(&program, 2, 2, 0, |_| {}).cpu().stats.cycles;
+    assert_eq!(default, explicit);
+}
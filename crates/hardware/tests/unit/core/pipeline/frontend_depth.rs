@@ -0,0 +1,66 @@
+//! Frontend Depth Tests.
+//!
+//! Verifies that `pipeline.frontend_depth` sets the misprediction penalty:
+//! each mispredict counts `frontend_depth` control-stall cycles, and depths
+//! beyond the modeled frontend stages add one refetch bubble per extra stage.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{TestContext, run_program};
+use rvsim_core::config::Config;
+
+/// A taken branch the cold predictor gets wrong.
+fn mispredict_program() -> [u32; 5] {
+    [
+        InstructionBuilder::new().addi(5, 0, 1).build(),
+        InstructionBuilder::new().beq(0, 0, 8).build(), // taken, cold predictor
+        InstructionBuilder::new().addi(6, 0, 1).build(), // skipped
+        InstructionBuilder::new().addi(7, 0, 1).build(),
+        InstructionBuilder::new().nop().build(),
+    ]
+}
+
+/// Runs the mispredict program with `depth` and returns the context.
+fn run_with_depth(depth: u64) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.frontend_depth = depth;
+    let tc = run_program(&config, &mispredict_program());
+    assert_eq!(tc.get_reg(6), 0, "wrong-path instruction must not commit");
+    assert_eq!(tc.get_reg(7), 1);
+    tc
+}
+
+#[test]
+fn default_depth_is_two() {
+    assert_eq!(Config::default().pipeline.frontend_depth, 2);
+}
+
+#[test]
+fn control_stalls_scale_with_depth() {
+    for depth in [1, 2, 5, 10] {
+        let tc = run_with_depth(depth);
+        let stats = &tc.cpu().stats;
+        assert_eq!(stats.branch_mispredictions, 1);
+        assert_eq!(stats.stalls_control, depth * stats.branch_mispredictions);
+    }
+}
+
+#[test]
+fn deeper_frontend_adds_refetch_bubbles() {
+    let base = run_with_depth(2);
+    let base_cycles = base.cpu().stats.cycles;
+    for depth in [3, 6, 10] {
+        let tc = run_with_depth(depth);
+        assert_eq!(
+            tc.cpu().stats.cycles,
+            base_cycles + (depth - 2) * tc.cpu().stats.branch_mispredictions,
+            "depth={depth}"
+        );
+    }
+}
+
+#[test]
+fn shallow_frontend_does_not_shorten_refill() {
+    let base = run_with_depth(2);
+    let tc = run_with_depth(1);
+    assert_eq!(tc.cpu().stats.cycles, base.cpu().stats.cycles);
+}
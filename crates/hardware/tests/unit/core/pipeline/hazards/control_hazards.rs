@@ -3,21 +3,17 @@
 //! Verifies that branch mispredictions cause proper pipeline flushing
 //! by running short instruction sequences through the full pipeline
 //! and checking PC redirection, latch clearing, and stat counters.
+//! Global history repair is checked by resolving a single branch in
+//! execute directly.
 
 use crate::common::builder::instruction::InstructionBuilder;
-use crate::common::harness::TestContext;
-
-// ══════════════════════════════════════════════════════════
-// Helper constants
-// ══════════════════════════════════════════════════════════
-
-const BASE_ADDR: u64 = 0x8000_0000;
-const MEM_SIZE: usize = 0x1000;
-
-/// Build a TestContext with memory at BASE_ADDR.
-fn ctx() -> TestContext {
-    TestContext::new().with_memory(MEM_SIZE, BASE_ADDR)
-}
+use crate::common::harness::{PROGRAM_BASE, PROGRAM_MEM_SIZE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::pipeline::backend::inorder::execute::execute_inorder;
+use rvsim_core::core::pipeline::latches::RenameIssueEntry;
+use rvsim_core::core::pipeline::rob::Rob;
+use rvsim_core::core::pipeline::signals::{ControlSignals, OpBSrc};
+use rvsim_core::core::units::bru::BranchPredictor;
 
 // ══════════════════════════════════════════════════════════
 // 1. Taken branch flushes speculated instructions
@@ -35,8 +31,8 @@ fn taken_branch_redirects_pc() {
     //  24: NOP
     //  28: NOP
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().addi(1, 0, 10).build(), // x1 = 10
             InstructionBuilder::new().addi(2, 0, 20).build(), // x2 = 20
@@ -74,8 +70,8 @@ fn not_taken_branch_continues() {
     //  12: x3 = 33          (should execute)
     //  16: NOP ...
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().addi(1, 0, 10).build(),
             InstructionBuilder::new().addi(2, 0, 20).build(),
@@ -108,8 +104,8 @@ fn jal_redirects_and_links() {
     //
     // Note: avoid x2 (sp) — it is pre-initialised in direct mode.
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().jal(1, 12).build(),
             InstructionBuilder::new().addi(6, 0, 99).build(),
@@ -124,7 +120,11 @@ fn jal_redirects_and_links() {
 
     tc.run(60);
 
-    assert_eq!(tc.get_reg(1), BASE_ADDR + 4, "x1 = return address (PC+4)");
+    assert_eq!(
+        tc.get_reg(1),
+        PROGRAM_BASE + 4,
+        "x1 = return address (PC+4)"
+    );
     assert_eq!(tc.get_reg(6), 0, "x6 should NOT execute (after JAL)");
     assert_eq!(tc.get_reg(4), 55, "x4 should be 55 (jump target)");
 }
@@ -136,8 +136,8 @@ fn jal_redirects_and_links() {
 #[test]
 fn jalr_indirect_jump() {
     // Program:
-    //   0: AUIPC x5, 0      (x5 = PC = BASE_ADDR, avoids LUI sign-extension)
-    //   4: ADDI  x5, x5, 16 (x5 = BASE_ADDR + 16 = target)
+    //   0: AUIPC x5, 0      (x5 = PC = PROGRAM_BASE, avoids LUI sign-extension)
+    //   4: ADDI  x5, x5, 16 (x5 = PROGRAM_BASE + 16 = target)
     //   8: JALR  x1, x5, 0  (jump to x5, link x1)
     //  12: x6 = 99           (should NOT execute)
     //  16: x3 = 77           (target)
@@ -147,8 +147,8 @@ fn jalr_indirect_jump() {
     // AUIPC adds PC (already 64-bit) + 0, giving the correct 0x8000_0000.
     // Note: avoid x2 (sp) — it is pre-initialised in direct mode.
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().auipc(5, 0).build(), // x5 = PC = 0x8000_0000
             InstructionBuilder::new().addi(5, 5, 16).build(), // x5 = 0x8000_0010
@@ -164,7 +164,7 @@ fn jalr_indirect_jump() {
     tc.run(60);
 
     assert_eq!(tc.get_reg(3), 77, "x3 should be 77 (JALR target)");
-    assert_eq!(tc.get_reg(1), BASE_ADDR + 12, "x1 = return address");
+    assert_eq!(tc.get_reg(1), PROGRAM_BASE + 12, "x1 = return address");
     assert_eq!(tc.get_reg(6), 0, "x6 should NOT execute (flushed)");
 }
 
@@ -182,8 +182,8 @@ fn backward_branch_loop() {
     //  16: x3 = 100            (post-loop)
     //  20: NOP...
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().addi(1, 0, 0).build(), // x1 = 0
             InstructionBuilder::new().addi(2, 0, 3).build(), // x2 = 3
@@ -209,8 +209,8 @@ fn backward_branch_loop() {
 #[test]
 fn blt_taken() {
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().addi(1, 0, 5).build(),
             InstructionBuilder::new().addi(2, 0, 10).build(),
@@ -230,8 +230,8 @@ fn blt_taken() {
 #[test]
 fn bge_taken() {
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().addi(1, 0, 10).build(),
             InstructionBuilder::new().addi(2, 0, 10).build(),
@@ -252,8 +252,8 @@ fn bge_taken() {
 fn bltu_taken() {
     let nop = InstructionBuilder::new().nop().build();
     // Use -1 (u64::MAX) as unsigned value > 5
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().addi(1, 0, 5).build(),
             InstructionBuilder::new().addi(2, 0, -1).build(), // x2 = 0xFFFF...FFFF (unsigned large)
@@ -273,8 +273,8 @@ fn bltu_taken() {
 #[test]
 fn bgeu_not_taken() {
     let nop = InstructionBuilder::new().nop().build();
-    let mut tc = ctx().load_program(
-        BASE_ADDR,
+    let mut tc = TestContext::program(
+        &Config::default(),
         &[
             InstructionBuilder::new().addi(1, 0, 5).build(),
             InstructionBuilder::new().addi(2, 0, 10).build(),
@@ -289,3 +289,348 @@ fn bgeu_not_taken() {
     tc.run(60);
     assert_eq!(tc.get_reg(3), 33, "BGEU not taken → sequential execution");
 }
+
+// ══════════════════════════════════════════════════════════
+// 7. Compressed call/return pair drives the RAS
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn compressed_call_return_uses_ras() {
+    use rvsim_core::core::units::bru::BranchPredictor;
+
+    // Program (RV64 has no C.JAL, so C.JALR is the compressed call form):
+    //   0: AUIPC x10, 0
+    //   4: x10 = x10 + 16   (function address)
+    //   8: C.JALR x10       (call, x1 = 10)
+    //  10: C.LI x6, 7       (runs after return)
+    //  12: JAL x0, +12      (skip over the function body)
+    //  16: C.LI x7, 9       (function body)
+    //  18: C.JR ra          (return, predicted from the RAS)
+    //  20: NOP...
+    let nop = InstructionBuilder::new().nop().build();
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            InstructionBuilder::new().auipc(10, 0).build(),
+            InstructionBuilder::new().addi(10, 10, 16).build(),
+            0x431D_9502, // C.LI x6, 7 | C.JALR x10
+            InstructionBuilder::new().jal(0, 12).build(),
+            0x8082_43A5, // C.JR ra | C.LI x7, 9
+            nop,
+            nop,
+            nop,
+            nop,
+            nop,
+            nop,
+        ],
+    );
+
+    tc.run(80);
+
+    assert_eq!(tc.get_reg(1), PROGRAM_BASE + 10, "C.JALR should link x1");
+    assert_eq!(tc.get_reg(7), 9, "function body should execute");
+    assert_eq!(tc.get_reg(6), 7, "execution should resume after the call");
+
+    let bp = &tc.cpu().branch_predictor;
+    assert_eq!(
+        bp.predict_btb(PROGRAM_BASE + 8),
+        Some(PROGRAM_BASE + 16),
+        "compressed call should train the BTB"
+    );
+    assert_eq!(bp.predict_return(), None, "C.JR ra should pop the RAS");
+
+    // Only the cold C.JALR and the cold JAL mispredict; the return target
+    // comes from the RAS entry pushed by the compressed call.
+    assert_eq!(tc.cpu().stats.branch_mispredictions, 2);
+
+    let stats = &tc.cpu().stats;
+    assert_eq!(stats.indirect_jump_mispredictions, 1, "cold C.JALR call");
+    assert_eq!(stats.direct_jump_mispredictions, 1, "cold JAL");
+    assert_eq!(stats.return_predictions, 1);
+    assert_eq!(stats.return_mispredictions, 0);
+}
+
+#[test]
+fn t0_call_return_uses_ras() {
+    // x5 (t0) is the alternate link register, so a JAL/JALR pair through
+    // it is a call and a return just like one through ra:
+    //   0: JAL x5, +12      (call, x5 = 4)
+    //   4: x6 = 7           (runs after return)
+    //   8: JAL x0, +12      (skip over the function body)
+    //  12: x7 = 9           (function body)
+    //  16: JALR x0, 0(x5)   (return, predicted from the RAS)
+    //  20: NOP...
+    let nop = InstructionBuilder::new().nop().build();
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            InstructionBuilder::new().jal(5, 12).build(),
+            InstructionBuilder::new().addi(6, 0, 7).build(),
+            InstructionBuilder::new().jal(0, 12).build(),
+            InstructionBuilder::new().addi(7, 0, 9).build(),
+            InstructionBuilder::new().jalr(0, 5, 0).build(),
+            nop,
+            nop,
+            nop,
+            nop,
+            nop,
+        ],
+    );
+
+    tc.run(80);
+
+    assert_eq!(tc.get_reg(5), PROGRAM_BASE + 4, "JAL should link x5");
+    assert_eq!(tc.get_reg(7), 9, "function body should execute");
+    assert_eq!(tc.get_reg(6), 7, "execution should resume after the call");
+    assert_eq!(
+        tc.cpu().branch_predictor.predict_return(),
+        None,
+        "JALR x0, 0(x5) should pop the RAS"
+    );
+
+    // Only the two cold JALs mispredict; the return target comes from the
+    // RAS entry pushed by the t0 call.
+    let stats = &tc.cpu().stats;
+    assert_eq!(stats.branch_mispredictions, 2);
+    assert_eq!(stats.return_predictions, 1);
+    assert_eq!(stats.return_mispredictions, 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 8. Indirect predictor on a two-way dispatch
+// ══════════════════════════════════════════════════════════
+
+/// Runs a loop whose single `JALR x0` alternates between two handlers and
+/// returns the number of mispredictions.
+fn run_dispatch_loop(indirect_predictor: bool) -> u64 {
+    let mut config = rvsim_core::config::Config::default();
+    config.pipeline.branch_predictor = rvsim_core::config::BranchPredictor::GShare;
+    config.pipeline.indirect_predictor = indirect_predictor;
+
+    // Program:
+    //   0: x10 = 100           (iterations)
+    //   4: AUIPC x5, 0
+    //   8: x6 = x5 + 32        (handler A at 36)
+    //  12: x7 = x5 + 40        (handler B at 44)
+    //  16: loop: x8 = x10 & 1
+    //  20: x9 = x6
+    //  24: BEQ x8, x0, +8      (even → keep A)
+    //  28: x9 = x7
+    //  32: JALR x0, 0(x9)      (dispatch)
+    //  36: x11 += 1; JAL +12   (handler A)
+    //  44: x12 += 1; NOP       (handler B)
+    //  52: x10 -= 1
+    //  56: BNE x10, x0, loop
+    let nop = InstructionBuilder::new().nop().build();
+    let mut tc = TestContext::program(
+        &config,
+        &[
+            InstructionBuilder::new().addi(10, 0, 100).build(),
+            InstructionBuilder::new().auipc(5, 0).build(),
+            InstructionBuilder::new().addi(6, 5, 32).build(),
+            InstructionBuilder::new().addi(7, 5, 40).build(),
+            InstructionBuilder::new().andi(8, 10, 1).build(),
+            InstructionBuilder::new().addi(9, 6, 0).build(),
+            InstructionBuilder::new().beq(8, 0, 8).build(),
+            InstructionBuilder::new().addi(9, 7, 0).build(),
+            InstructionBuilder::new().jalr(0, 9, 0).build(),
+            InstructionBuilder::new().addi(11, 11, 1).build(),
+            InstructionBuilder::new().jal(0, 12).build(),
+            InstructionBuilder::new().addi(12, 12, 1).build(),
+            nop,
+            InstructionBuilder::new().addi(10, 10, -1).build(),
+            InstructionBuilder::new().bne(10, 0, -40).build(),
+            nop,
+            nop,
+            nop,
+            nop,
+        ],
+    );
+
+    tc.run(5000);
+
+    assert_eq!(tc.get_reg(10), 0, "loop should complete");
+    assert_eq!(tc.get_reg(11), 50);
+    assert_eq!(tc.get_reg(12), 50);
+    tc.cpu().stats.branch_mispredictions
+}
+
+#[test]
+fn indirect_predictor_learns_alternating_targets() {
+    let btb_only = run_dispatch_loop(false);
+    let with_indirect = run_dispatch_loop(true);
+
+    // The BTB is never trained by a non-call JALR, so every dispatch misses.
+    assert!(btb_only >= 100, "btb_only = {btb_only}");
+    assert!(
+        with_indirect + 40 <= btb_only,
+        "indirect predictor should remove most dispatch mispredictions \
+         (with = {with_indirect}, without = {btb_only})"
+    );
+}
+
+// ══════════════════════════════════════════════════════════
+// 9. Per-type prediction counters partition the totals
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn branch_type_counters_sum_to_totals() {
+    // Five-iteration BNE loop followed by a JAL over one instruction.
+    let nop = InstructionBuilder::new().nop().build();
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            InstructionBuilder::new().addi(1, 0, 0).build(),
+            InstructionBuilder::new().addi(2, 0, 5).build(),
+            InstructionBuilder::new().addi(1, 1, 1).build(),
+            InstructionBuilder::new().bne(1, 2, -4).build(),
+            InstructionBuilder::new().jal(0, 8).build(),
+            nop,
+            InstructionBuilder::new().addi(3, 0, 1).build(),
+            nop,
+            nop,
+            nop,
+        ],
+    );
+
+    tc.run(300);
+    assert_eq!(tc.get_reg(3), 1);
+
+    let s = &tc.cpu().stats;
+    assert_eq!(s.cond_branch_predictions + s.cond_branch_mispredictions, 5);
+    assert_eq!(s.direct_jump_predictions + s.direct_jump_mispredictions, 1);
+    assert_eq!(
+        s.indirect_jump_predictions + s.indirect_jump_mispredictions,
+        0
+    );
+    assert_eq!(s.return_predictions + s.return_mispredictions, 0);
+    assert_eq!(
+        s.branch_predictions,
+        s.cond_branch_predictions
+            + s.direct_jump_predictions
+            + s.indirect_jump_predictions
+            + s.return_predictions
+    );
+    assert_eq!(
+        s.branch_mispredictions,
+        s.cond_branch_mispredictions
+            + s.direct_jump_mispredictions
+            + s.indirect_jump_mispredictions
+            + s.return_mispredictions
+    );
+}
+
+// ══════════════════════════════════════════════════════════
+// 10. Speculative RAS across a recursive call chain
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn recursive_returns_predicted_by_speculative_ras() {
+    // Program (recursion depth 6, within the 8-entry RAS; ra saved on the stack):
+    //   0: x10 = 6
+    //   4: JAL ra, +8          (call f)
+    //   8: JAL x0, 0           (spin)
+    //  12: f: BEQ x10, x0, +28 (base case → ret)
+    //  16: sp -= 8
+    //  20: SD ra, 0(sp)
+    //  24: x10 -= 1
+    //  28: JAL ra, -16         (recurse)
+    //  32: LD ra, 0(sp)
+    //  36: sp += 8
+    //  40: JALR x0, 0(ra)      (ret)
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            InstructionBuilder::new().addi(10, 0, 6).build(),
+            InstructionBuilder::new().jal(1, 8).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+            InstructionBuilder::new().beq(10, 0, 28).build(),
+            InstructionBuilder::new().addi(2, 2, -8).build(),
+            InstructionBuilder::new().sd(2, 1, 0).build(),
+            InstructionBuilder::new().addi(10, 10, -1).build(),
+            InstructionBuilder::new().jal(1, -16).build(),
+            InstructionBuilder::new().ld(1, 2, 0).build(),
+            InstructionBuilder::new().addi(2, 2, 8).build(),
+            InstructionBuilder::new().jalr(0, 1, 0).build(),
+        ],
+    );
+    tc.set_reg(2, PROGRAM_BASE + PROGRAM_MEM_SIZE as u64);
+
+    tc.run(600);
+
+    assert_eq!(tc.get_reg(1), PROGRAM_BASE + 8, "outermost return reached");
+    assert_eq!(
+        tc.get_reg(2),
+        PROGRAM_BASE + PROGRAM_MEM_SIZE as u64,
+        "stack unwound"
+    );
+
+    // Each return is fetched before the previous one resolves; the RAS is
+    // popped at fetch, and restored across the base-case branch flush, so
+    // every return target is predicted.
+    let s = &tc.cpu().stats;
+    assert_eq!(s.return_predictions + s.return_mispredictions, 7);
+    assert_eq!(s.return_mispredictions, 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 11. Global history repair at branch resolution
+// ══════════════════════════════════════════════════════════
+
+/// History when the branch was fetched.
+const GHR_AT_FETCH: u64 = 0b101;
+/// History after fetch speculated the branch taken and a younger branch
+/// not taken.
+const GHR_SPECULATED: u64 = 0b10110;
+
+/// Resolves an always-taken `BEQ x0, x0, +16` that fetch predicted as
+/// `pred_taken`, with a younger not-taken branch already speculated, and
+/// returns the GShare history afterwards.
+fn ghr_after_resolving(pred_taken: bool) -> u64 {
+    let mut config = rvsim_core::config::Config::default();
+    config.pipeline.branch_predictor = rvsim_core::config::BranchPredictor::GShare;
+    let mut tc = TestContext::with_config(&config).with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE);
+    let cpu = tc.cpu_mut();
+    cpu.branch_predictor.repair_history(GHR_AT_FETCH);
+    cpu.branch_predictor.speculate(PROGRAM_BASE, true);
+    cpu.branch_predictor.speculate(PROGRAM_BASE + 16, false);
+    assert_eq!(cpu.branch_predictor.snapshot_history(), GHR_SPECULATED);
+
+    let inst = InstructionBuilder::new().beq(0, 0, 16).build();
+    let ctrl = ControlSignals {
+        branch: true,
+        b_src: OpBSrc::Reg2,
+        ..Default::default()
+    };
+    let mut rob = Rob::new(8);
+    let rob_tag = rob.allocate(PROGRAM_BASE, inst, 4, 0, false, ctrl).unwrap();
+    let entry = RenameIssueEntry {
+        rob_tag,
+        pc: PROGRAM_BASE,
+        inst,
+        inst_size: 4,
+        imm: 16,
+        ctrl,
+        pred_taken,
+        pred_target: if pred_taken { PROGRAM_BASE + 16 } else { 0 },
+        ghr_snapshot: GHR_AT_FETCH,
+        ..Default::default()
+    };
+
+    let _ = execute_inorder(cpu, vec![entry], &mut rob);
+    cpu.branch_predictor.snapshot_history()
+}
+
+#[test]
+fn correct_prediction_keeps_younger_speculative_history() {
+    // The branch trains against GHR_AT_FETCH, then the speculated history
+    // (its own taken bit plus the younger branch) is put back untouched.
+    assert_eq!(ghr_after_resolving(true), GHR_SPECULATED);
+}
+
+#[test]
+fn misprediction_rewinds_history_to_the_actual_outcome() {
+    // The younger branch was on the wrong path: history is rewound to
+    // GHR_AT_FETCH with only the branch's real (taken) outcome shifted in.
+    assert_eq!(ghr_after_resolving(false), (GHR_AT_FETCH << 1) | 1);
+}
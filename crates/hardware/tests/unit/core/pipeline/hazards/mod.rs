@@ -1 +1,2 @@
 pub mod control_hazards;
+pub mod subreg_dependencies;
@@ -0,0 +1,97 @@
+//! Instruction Retirement Counting Tests.
+//!
+//! Verifies the `minstret` increment policy at commit: a trapping
+//! instruction does not retire, a compressed instruction retires as one,
+//! NOPs retire, and the trap handler's instructions are counted.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::isa::privileged::cause::exception;
+
+/// `csrrw x0, mtvec, x5`
+const CSRW_MTVEC_X5: u32 = 0x3052_9073;
+
+#[test]
+fn faulting_instruction_does_not_advance_minstret() {
+    // Program:
+    //   0: AUIPC x5, 0
+    //   4: x5 = x5 + 24        (handler address)
+    //   8: CSRW mtvec, x5
+    //  12: C.ADDI x10, 1
+    //  14: C.NOP
+    //  16: EBREAK              (traps, does not retire)
+    //  20: x11 = 99            (never executes)
+    //  24: handler: x12 = 1
+    //  28: x12 = x12 + 1
+    //  32: JAL x0, 0           (spin)
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            InstructionBuilder::new().auipc(5, 0).build(),
+            InstructionBuilder::new().addi(5, 5, 24).build(),
+            CSRW_MTVEC_X5,
+            InstructionBuilder::C_ADDI_C_NOP,
+            InstructionBuilder::new().ebreak().build(),
+            InstructionBuilder::new().addi(11, 0, 99).build(),
+            InstructionBuilder::new().addi(12, 0, 1).build(),
+            InstructionBuilder::new().addi(12, 12, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+        ],
+    );
+    tc.cpu_mut().direct_mode = false;
+
+    // Step until the breakpoint has been taken.
+    let mut retired_at_trap = None;
+    for _ in 0..200 {
+        tc.sim.tick().unwrap();
+        if tc.cpu().csrs.mcause == exception::BREAKPOINT {
+            retired_at_trap = Some(tc.cpu().stats.instructions_retired);
+            break;
+        }
+    }
+    assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE + 16);
+    assert_eq!(
+        retired_at_trap,
+        Some(5),
+        "AUIPC, ADDI, CSRW, C.ADDI and C.NOP retire; EBREAK does not"
+    );
+
+    // Step until both handler instructions have committed.
+    for _ in 0..200 {
+        tc.sim.tick().unwrap();
+        if tc.get_reg(12) == 2 {
+            break;
+        }
+    }
+    assert_eq!(tc.get_reg(12), 2, "handler should run");
+    assert_eq!(tc.get_reg(10), 1);
+    assert_eq!(tc.get_reg(11), 0, "instruction after EBREAK must not run");
+    assert_eq!(
+        tc.cpu().stats.instructions_retired,
+        7,
+        "each handler instruction advances minstret by one"
+    );
+}
+
+#[test]
+fn minstret_counts_every_committed_instruction() {
+    let nop = InstructionBuilder::new().nop().build();
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            InstructionBuilder::new().addi(1, 0, 1).build(),
+            nop,
+            InstructionBuilder::C_ADDI_C_NOP,
+            nop,
+            InstructionBuilder::new().addi(2, 0, 2).build(),
+        ],
+    );
+    tc.run(100);
+
+    // 4 full-width instructions and 2 compressed ones; the zero word that
+    // ends the program traps and is not counted.
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert_eq!(tc.cpu().stats.instructions_retired, 6);
+    assert_eq!(tc.cpu().csr_read(0xB02), 6, "minstret reads the same count");
+}
@@ -0,0 +1,122 @@
+//! Instruction Mix Tests.
+//!
+//! Verifies that every committed instruction is classified into exactly one
+//! instruction-mix category, with jumps counted apart from conditional
+//! branches and atomics apart from loads and stores.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::run_program;
+use rvsim_core::config::Config;
+use rvsim_core::core::pipeline::signals::{AluOp, AtomicOp, ControlSignals};
+use rvsim_core::stats::SimStats;
+
+/// `amoadd.w x5, x6, (x7)`
+const AMOADD_W: u32 = 0x0063_A2AF;
+/// `csrrs x8, mscratch, x0`
+const CSRR_MSCRATCH: u32 = 0x3400_2473;
+
+// ══════════════════════════════════════════════════════════
+// 1. Classification of control signals
+// ══════════════════════════════════════════════════════════
+
+fn classify(ctrl: ControlSignals) -> SimStats {
+    let mut stats = SimStats::default();
+    stats.record_instruction(&ctrl);
+    stats
+}
+
+#[test]
+fn atomic_is_not_a_load_or_store() {
+    let stats = classify(ControlSignals {
+        mem_read: true,
+        mem_write: true,
+        atomic_op: AtomicOp::Add,
+        ..Default::default()
+    });
+    assert_eq!(stats.inst_atomic, 1);
+    assert_eq!(stats.inst_load + stats.inst_store, 0);
+}
+
+#[test]
+fn jump_is_not_a_branch() {
+    let stats = classify(ControlSignals {
+        jump: true,
+        ..Default::default()
+    });
+    assert_eq!(stats.inst_jump, 1);
+    assert_eq!(stats.inst_branch, 0);
+
+    let stats = classify(ControlSignals {
+        branch: true,
+        ..Default::default()
+    });
+    assert_eq!(stats.inst_branch, 1);
+    assert_eq!(stats.inst_jump, 0);
+}
+
+#[test]
+fn fp_operations_use_fp_categories() {
+    let fma = classify(ControlSignals {
+        alu: AluOp::FMAdd,
+        fp_reg_write: true,
+        ..Default::default()
+    });
+    assert_eq!(fma.inst_fp_fma, 1);
+    let div = classify(ControlSignals {
+        alu: AluOp::FDiv,
+        fp_reg_write: true,
+        ..Default::default()
+    });
+    assert_eq!(div.inst_fp_div_sqrt, 1);
+    assert_eq!(div.inst_alu, 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Retired instruction stream
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn committed_instructions_fill_each_category() {
+    // Program:
+    //   0: AUIPC x7, 0
+    //   4: x7 = x7 + 0x400     (data word)
+    //   8: x6 = 1
+    //  12: SW x6, 0(x7)
+    //  16: LW x9, 0(x7)
+    //  20: AMOADD.W x5, x6, (x7)
+    //  24: BEQ x0, x0, 8
+    //  28: (skipped)
+    //  32: JAL x1, 4
+    //  36: CSRR x8, mscratch
+    let program = [
+        InstructionBuilder::new().auipc(7, 0).build(),
+        InstructionBuilder::new().addi(7, 7, 0x400).build(),
+        InstructionBuilder::new().addi(6, 0, 1).build(),
+        InstructionBuilder::new().sw(7, 6, 0).build(),
+        InstructionBuilder::new().lw(9, 7, 0).build(),
+        AMOADD_W,
+        InstructionBuilder::new().beq(0, 0, 8).build(),
+        InstructionBuilder::new().addi(10, 0, 1).build(),
+        InstructionBuilder::new().jal(1, 4).build(),
+        CSRR_MSCRATCH,
+    ];
+    let tc = run_program(&Config::default(), &program);
+    assert_eq!(tc.get_reg(10), 0, "branch should skip the addi");
+
+    let s = &tc.cpu().stats;
+    assert_eq!(s.inst_alu, 3, "AUIPC and two ADDIs");
+    assert_eq!(s.inst_store, 1);
+    assert_eq!(s.inst_load, 1);
+    assert_eq!(s.inst_atomic, 1);
+    assert_eq!(s.inst_branch, 1);
+    assert_eq!(s.inst_jump, 1);
+    assert_eq!(s.inst_system, 1);
+    let mix = s.inst_alu
+        + s.inst_load
+        + s.inst_store
+        + s.inst_branch
+        + s.inst_jump
+        + s.inst_atomic
+        + s.inst_system;
+    assert_eq!(mix, s.instructions_retired);
+}
@@ -0,0 +1,183 @@
+//! Misaligned Access Tests.
+//!
+//! Verifies that misaligned loads and stores are performed as split byte
+//! accesses by default, in both detailed and functional mode, and that
+//! `memory.misaligned_access = false` makes them raise the misaligned
+//! exceptions instead. Atomics always trap when misaligned.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, PROGRAM_MEM_SIZE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::isa::privileged::cause::exception;
+
+/// Offset of the data buffer from the program start.
+const DATA_OFFSET: i32 = 0x400;
+const DATA: u64 = PROGRAM_BASE + DATA_OFFSET as u64;
+
+/// Runs `body` after a prologue pointing x3 at the data buffer, with the
+/// bytes `0x10, 0x11, ...` pre-loaded there.
+fn run(config: &Config, body: &[u32], functional: bool) -> TestContext {
+    let mut program = vec![
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new().addi(3, 3, DATA_OFFSET).build(),
+    ];
+    program.extend_from_slice(body);
+    let mut tc = TestContext::program(config, &program).with_trap_handlers();
+    for i in 0..16 {
+        tc.sim
+            .cpu
+            .bus
+            .bus
+            .write_u8(DATA + i, 0x10 + i as u8)
+            .unwrap();
+    }
+    tc.cpu_mut().set_functional(functional);
+    tc.run(2_000);
+    tc
+}
+
+/// A configuration that traps through `mtvec` instead of exiting.
+fn trapping(misaligned_access: bool) -> Config {
+    let mut config = Config::default();
+    config.general.direct_mode = false;
+    config.memory.misaligned_access = misaligned_access;
+    config
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Split accesses
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn misaligned_load_returns_correct_bytes() {
+    for functional in [false, true] {
+        let tc = run(
+            &Config::default(),
+            &[
+                InstructionBuilder::new().ld(5, 3, 1).build(),
+                InstructionBuilder::new().lw(6, 3, 6).build(),
+            ],
+            functional,
+        );
+        assert_eq!(tc.cpu().exit_code, Some(0));
+        assert_eq!(
+            tc.get_reg(5),
+            0x1817_1615_1413_1211,
+            "functional={functional}"
+        );
+        assert_eq!(tc.get_reg(6), 0x1918_1716, "functional={functional}");
+    }
+}
+
+#[test]
+fn misaligned_store_writes_each_byte() {
+    for functional in [false, true] {
+        let mut tc = run(
+            &Config::default(),
+            &[
+                InstructionBuilder::new().addi(5, 0, -1).build(),
+                InstructionBuilder::new().sd(3, 5, 3).build(),
+                InstructionBuilder::new().ld(6, 3, 3).build(),
+            ],
+            functional,
+        );
+        assert_eq!(tc.cpu().exit_code, Some(0));
+        assert_eq!(tc.get_reg(6), u64::MAX, "functional={functional}");
+        let bytes: Vec<u8> = (0..12)
+            .map(|i| tc.sim.cpu.bus.bus.read_u8(DATA + i).unwrap())
+            .collect();
+        assert_eq!(
+            bytes,
+            [
+                0x10, 0x11, 0x12, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x1B
+            ]
+        );
+    }
+}
+
+#[test]
+fn misaligned_access_past_end_of_memory_faults() {
+    let end_offset = PROGRAM_MEM_SIZE as i32 - DATA_OFFSET - 4;
+    let tc = run(
+        &Config::default(),
+        &[InstructionBuilder::new().ld(5, 3, end_offset).build()],
+        false,
+    );
+    assert_eq!(tc.cpu().exit_code, Some(1));
+    assert_eq!(tc.get_reg(5), 0);
+}
+
+#[test]
+fn misaligned_access_costs_extra_cycles() {
+    let cycles = |offset| {
+        run(
+            &Config::default(),
+            &[
+                InstructionBuilder::new().ld(5, 3, offset).build(),
+                InstructionBuilder::new().ld(6, 3, offset + 16).build(),
+                InstructionBuilder::new().ld(7, 3, offset + 32).build(),
+                InstructionBuilder::new().ld(8, 3, offset + 48).build(),
+            ],
+            false,
+        )
+        .cpu()
+        .stats
+        .cycles
+    };
+    assert!(cycles(1) > cycles(0), "{} vs {}", cycles(1), cycles(0));
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Trapping
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn misaligned_load_traps_when_unsupported() {
+    for functional in [false, true] {
+        let tc = run(
+            &trapping(false),
+            &[InstructionBuilder::new().ld(5, 3, 1).build()],
+            functional,
+        );
+        assert_eq!(tc.cpu().csrs.mcause, exception::LOAD_ADDRESS_MISALIGNED);
+        assert_eq!(tc.cpu().csrs.mtval, DATA + 1);
+        assert_eq!(tc.get_reg(5), 0);
+    }
+}
+
+#[test]
+fn misaligned_store_traps_when_unsupported() {
+    for functional in [false, true] {
+        let mut tc = run(
+            &trapping(false),
+            &[
+                InstructionBuilder::new().addi(5, 0, -1).build(),
+                InstructionBuilder::new().sw(3, 5, 2).build(),
+            ],
+            functional,
+        );
+        assert_eq!(tc.cpu().csrs.mcause, exception::STORE_ADDRESS_MISALIGNED);
+        assert_eq!(tc.cpu().csrs.mtval, DATA + 2);
+        assert_eq!(tc.sim.cpu.bus.bus.read_u8(DATA + 2).unwrap(), 0x12);
+    }
+}
+
+#[test]
+fn misaligned_atomic_always_traps() {
+    let mut tc = run(
+        &trapping(true),
+        &[
+            InstructionBuilder::new().addi(3, 3, 4).build(),
+            InstructionBuilder::new().amoadd_d(5, 3, 0).build(),
+        ],
+        false,
+    );
+    assert_eq!(tc.cpu().csrs.mcause, exception::STORE_ADDRESS_MISALIGNED);
+    assert_eq!(tc.cpu().csrs.mtval, DATA + 4);
+    assert_eq!(tc.sim.cpu.bus.bus.read_u8(DATA + 4).unwrap(), 0x14);
+}
+
+#[test]
+fn misaligned_access_enabled_by_default() {
+    assert!(Config::default().memory.misaligned_access);
+}
@@ -1 +1,17 @@
+pub mod access_fault;
+pub mod asm_dump;
+pub mod atomics;
+pub mod big_endian;
+pub mod commit_log;
+pub mod exec_latency;
+pub mod fence;
+pub mod fetch_queue;
+pub mod frontend_depth;
 pub mod hazards;
+pub mod instret;
+pub mod instruction_mix;
+pub mod misaligned;
+pub mod perfect_predictor;
+pub mod simple_timing;
+pub mod store_forwarding;
+pub mod vector;
@@ -0,0 +1,79 @@
+//! Perfect Branch Predictor Tests.
+//!
+//! Verifies that `BranchPredictor::Perfect` never counts a misprediction or
+//! charges a control stall, still commits only the correct path, and runs a
+//! branchy loop in no more cycles than any real predictor.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::TestContext;
+use rvsim_core::config::{BranchPredictor, Config};
+
+/// Counts odd values of a 40-iteration down-counter into x6, then calls a
+/// leaf that sets x8 and returns.
+fn loop_program() -> Vec<u32> {
+    vec![
+        InstructionBuilder::new().addi(5, 0, 40).build(),
+        // loop:
+        InstructionBuilder::new().andi(7, 5, 1).build(),
+        InstructionBuilder::new().beq(7, 0, 8).build(),
+        InstructionBuilder::new().addi(6, 6, 1).build(),
+        InstructionBuilder::new().addi(5, 5, -1).build(),
+        InstructionBuilder::new().bne(5, 0, -16).build(),
+        InstructionBuilder::new().jal(1, 12).build(), // call leaf
+        InstructionBuilder::new().addi(9, 0, 1).build(),
+        InstructionBuilder::new().jal(0, 12).build(), // to exit
+        // leaf:
+        InstructionBuilder::new().addi(8, 0, 1).build(),
+        InstructionBuilder::new().jalr(0, 1, 0).build(),
+        InstructionBuilder::new().nop().build(),
+    ]
+}
+
+fn run(predictor: BranchPredictor, program: &[u32]) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.branch_predictor = predictor;
+    let mut tc = TestContext::program(&config, program);
+    tc.run(10_000);
+    assert_eq!(tc.cpu().exit_code, Some(0), "{predictor:?} should exit");
+    tc
+}
+
+#[test]
+fn perfect_never_mispredicts() {
+    let tc = run(BranchPredictor::Perfect, &loop_program());
+    let stats = &tc.cpu().stats;
+    assert_eq!(stats.branch_mispredictions, 0);
+    assert_eq!(stats.cond_branch_mispredictions, 0);
+    assert_eq!(stats.direct_jump_mispredictions, 0);
+    assert_eq!(stats.return_mispredictions, 0);
+    assert_eq!(stats.stalls_control, 0);
+    assert_eq!(stats.cond_branch_predictions, 80);
+}
+
+#[test]
+fn perfect_commits_only_the_correct_path() {
+    let tc = run(BranchPredictor::Perfect, &loop_program());
+    assert_eq!(tc.get_reg(5), 0);
+    assert_eq!(tc.get_reg(6), 20, "odd counter values");
+    assert_eq!(tc.get_reg(8), 1, "leaf ran");
+    assert_eq!(tc.get_reg(9), 1, "returned to the call site");
+}
+
+#[test]
+fn perfect_is_an_upper_bound() {
+    let perfect = run(BranchPredictor::Perfect, &loop_program());
+    let cycles = perfect.cpu().stats.cycles;
+    for predictor in [
+        BranchPredictor::Static,
+        BranchPredictor::GShare,
+        BranchPredictor::Tournament,
+    ] {
+        let tc = run(predictor, &loop_program());
+        assert!(tc.cpu().stats.branch_mispredictions > 0, "{predictor:?}");
+        assert!(
+            cycles < tc.cpu().stats.cycles,
+            "{predictor:?}: perfect {cycles} vs {}",
+            tc.cpu().stats.cycles
+        );
+    }
+}
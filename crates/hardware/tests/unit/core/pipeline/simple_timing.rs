@@ -0,0 +1,115 @@
+//! Simple Timing Model Tests.
+//!
+//! Verifies that `general.simple_timing` replaces the cache/controller
+//! model with fixed per-access and per-mispredict costs, so that total
+//! cycle counts are a closed-form function of the instruction mix.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::TestContext;
+use rvsim_core::config::Config;
+
+// ══════════════════════════════════════════════════════════
+// Helpers
+// ══════════════════════════════════════════════════════════
+
+const DATA_OFFSET: i32 = 0x400;
+
+/// Cycles spent filling and draining the pipeline for a straight-line
+/// program that ends on the zero word (direct-mode exit).
+const PIPELINE_OVERHEAD: u64 = 10;
+
+/// Builds a simple-timing context running `program`.
+///
+/// Caches are enabled and `mmio_base` covers RAM, so any access that
+/// reached the cache model would show up in the hit/miss counters.
+fn simple_ctx(program: &[u32], mem_latency: u64, mispredict_penalty: u64) -> TestContext {
+    let mut config = Config::default();
+    config.general.simple_timing = true;
+    config.general.simple_mem_latency = mem_latency;
+    config.general.simple_mispredict_penalty = mispredict_penalty;
+    config.cache.l1_i.enabled = true;
+    config.cache.l1_d.enabled = true;
+
+    let mut tc = TestContext::program(&config, program);
+    tc.cpu_mut().mmio_base = 0;
+    tc
+}
+
+/// AUIPC base, `alu` independent ADDIs, `loads` loads, and a trailing ADDI.
+fn alu_load_program(alu: usize, loads: usize) -> Vec<u32> {
+    let mut program = vec![InstructionBuilder::new().auipc(10, 0).build()];
+    for i in 0..alu {
+        program.push(
+            InstructionBuilder::new()
+                .addi(5 + (i % 2) as u32, 0, 1)
+                .build(),
+        );
+    }
+    for i in 0..loads {
+        program.push(
+            InstructionBuilder::new()
+                .lw(11 + (i % 4) as u32, 10, DATA_OFFSET)
+                .build(),
+        );
+    }
+    program.push(InstructionBuilder::new().addi(7, 0, 1).build());
+    program
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Closed-form cycle count
+// ══════════════════════════════════════════════════════════
+
+/// cycles = overhead + instructions + loads × latency.
+#[test]
+fn cycles_are_closed_form() {
+    for (alu, loads, latency) in [(2, 0, 10), (4, 1, 0), (4, 4, 7), (8, 3, 3), (6, 5, 20)] {
+        let mut tc = simple_ctx(&alu_load_program(alu, loads), latency, 0);
+        tc.run_to_exit();
+        let cycles = tc.cpu().stats.cycles;
+        let instructions = (alu + loads + 2) as u64;
+        assert_eq!(
+            cycles,
+            PIPELINE_OVERHEAD + instructions + loads as u64 * latency,
+            "alu={alu} loads={loads} latency={latency}"
+        );
+    }
+}
+
+/// The cache hierarchy is bypassed even when enabled.
+#[test]
+fn caches_are_bypassed() {
+    let mut tc = simple_ctx(&alu_load_program(2, 4), 5, 0);
+    tc.run_to_exit();
+    let stats = &tc.cpu().stats;
+    assert_eq!(stats.dcache_hits + stats.dcache_misses, 0);
+    assert_eq!(stats.icache_hits + stats.icache_misses, 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Fixed mispredict penalty
+// ══════════════════════════════════════════════════════════
+
+/// Each mispredict adds exactly the configured penalty.
+#[test]
+fn mispredict_adds_fixed_penalty() {
+    let nop = InstructionBuilder::new().nop().build();
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 1).build(),
+        InstructionBuilder::new().beq(0, 0, 8).build(), // taken, cold predictor
+        InstructionBuilder::new().addi(6, 0, 1).build(), // skipped
+        InstructionBuilder::new().addi(7, 0, 1).build(),
+        nop,
+    ];
+
+    let mut base = simple_ctx(&program, 0, 0);
+    base.run_to_exit();
+    let base_cycles = base.cpu().stats.cycles;
+    assert_eq!(base.cpu().stats.branch_mispredictions, 1);
+
+    for penalty in [1, 5, 12] {
+        let mut tc = simple_ctx(&program, 0, penalty);
+        tc.run_to_exit();
+        assert_eq!(tc.cpu().stats.cycles, base_cycles + penalty);
+    }
+}
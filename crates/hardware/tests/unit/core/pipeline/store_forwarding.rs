@@ -0,0 +1,83 @@
+//! Store-to-Load Forwarding Tests.
+//!
+//! Verifies that a load fully covered by a pending store takes its data from
+//! the store buffer and pays `pipeline.store_forward_latency` instead of a
+//! D-cache access, that loads to other addresses are unaffected by that
+//! latency, and that a load only partially covered by a store stalls until
+//! the store drains and then reads the merged value from memory.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{TestContext, run_program};
+use rvsim_core::config::Config;
+
+/// Offset of the data word from the program base.
+const DATA: i32 = 0x400;
+
+/// Runs `program` to the direct-mode exit with the given forwarding latency.
+fn run(program: &[u32], forward_latency: u64) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.store_forward_latency = forward_latency;
+    run_program(&config, program)
+}
+
+/// x5 = data base, x6 = 0x123, `sw x6, DATA(x5)`, then `load`.
+fn store_then(load: u32) -> Vec<u32> {
+    vec![
+        InstructionBuilder::new().auipc(5, 0).build(),
+        InstructionBuilder::new().addi(6, 0, 0x123).build(),
+        InstructionBuilder::new().sw(5, 6, DATA).build(),
+        load,
+        InstructionBuilder::new().nop().build(),
+    ]
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Full overlap forwards
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn forwarded_load_returns_stored_value() {
+    let lw = InstructionBuilder::new().lw(7, 5, DATA).build();
+    assert_eq!(run(&store_then(lw), 1).get_reg(7), 0x123);
+}
+
+#[test]
+fn forwarded_load_costs_store_forward_latency() {
+    let lw = InstructionBuilder::new().lw(7, 5, DATA).build();
+    let fast = run(&store_then(lw), 1);
+    let slow = run(&store_then(lw), 9);
+    assert_eq!(slow.get_reg(7), 0x123);
+    assert_eq!(slow.cpu().stats.cycles - fast.cpu().stats.cycles, 8);
+}
+
+#[test]
+fn unrelated_load_ignores_store_forward_latency() {
+    let lw = InstructionBuilder::new().lw(7, 5, DATA + 8).build();
+    let fast = run(&store_then(lw), 1);
+    let slow = run(&store_then(lw), 9);
+    assert_eq!(slow.get_reg(7), 0);
+    assert_eq!(slow.cpu().stats.cycles, fast.cpu().stats.cycles);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Partial overlap stalls
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn partial_overlap_reads_merged_value_after_drain() {
+    let ld = InstructionBuilder::new().ld(7, 5, DATA).build();
+    assert_eq!(run(&store_then(ld), 1).get_reg(7), 0x123);
+}
+
+#[test]
+fn partial_overlap_does_not_forward() {
+    let ld = InstructionBuilder::new().ld(7, 5, DATA).build();
+    let fast = run(&store_then(ld), 1);
+    let slow = run(&store_then(ld), 9);
+    assert_eq!(slow.get_reg(7), 0x123);
+    assert_eq!(
+        slow.cpu().stats.cycles,
+        fast.cpu().stats.cycles,
+        "a partially covered load reads memory, not the store buffer"
+    );
+}
@@ -0,0 +1,249 @@
+//! Vector Extension (RVV-lite) Tests.
+//!
+//! Verifies `vsetvli`/`vsetvl` updates of `vl` and `vtype` (including `vill`
+//! for unsupported configurations), the `vlenb` CSR under a configured VLEN,
+//! and element-wise `vadd.vv`/`vsub.vv` over the vector register file.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr;
+use rvsim_core::isa::disasm::disassemble;
+use rvsim_core::isa::privileged::cause::exception;
+
+/// vtype for SEW=32, LMUL=1, tail/mask undisturbed.
+const VTYPE_E32_M1: u32 = 0b010 << 3;
+/// vtype for SEW=64, LMUL=1 (unsupported).
+const VTYPE_E64_M1: u32 = 0b011 << 3;
+
+/// `vsetvli rd, rs1, vtype`
+fn vsetvli(rd: u32, rs1: u32, vtype: u32) -> u32 {
+    (vtype << 20) | (rs1 << 15) | (0b111 << 12) | (rd << 7) | 0x57
+}
+
+/// `vsetvl rd, rs1, rs2`
+fn vsetvl(rd: u32, rs1: u32, rs2: u32) -> u32 {
+    (0b100_0000 << 25) | (rs2 << 20) | (rs1 << 15) | (0b111 << 12) | (rd << 7) | 0x57
+}
+
+/// Unmasked OPIVV instruction `vd = vs2 op vs1` with the given funct6.
+fn opivv(funct6: u32, vd: u32, vs2: u32, vs1: u32) -> u32 {
+    (funct6 << 26) | (1 << 25) | (vs2 << 20) | (vs1 << 15) | (vd << 7) | 0x57
+}
+
+/// `vadd.vv vd, vs2, vs1`
+fn vadd_vv(vd: u32, vs2: u32, vs1: u32) -> u32 {
+    opivv(0b000000, vd, vs2, vs1)
+}
+
+/// `vsub.vv vd, vs2, vs1`
+fn vsub_vv(vd: u32, vs2: u32, vs1: u32) -> u32 {
+    opivv(0b000010, vd, vs2, vs1)
+}
+
+/// `csrrs rd, csr, x0`
+fn csrr(rd: u32, csr: u32) -> u32 {
+    (csr << 20) | (0b010 << 12) | (rd << 7) | 0x73
+}
+
+/// Runs `program` to its exit with the given VLEN.
+fn run(program: &[u32], vlen: usize) -> TestContext {
+    let mut config = Config::default();
+    config.pipeline.vlen = vlen;
+    let mut tc = TestContext::program(&config, program);
+    fill_sources(&mut tc);
+    tc.run_to_exit();
+    tc
+}
+
+/// Loads `v2[i] = 100 + i` and `v3[i] = i` for every element of the register.
+fn fill_sources(tc: &mut TestContext) {
+    let elems = tc.cpu().regs.vlenb() / 4;
+    let regs = &mut tc.cpu_mut().regs;
+    for i in 0..elems {
+        regs.write_v32(2, i, 100 + i as u32);
+        regs.write_v32(3, i, i as u32);
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Configuration
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn vlenb_reflects_configured_vlen() {
+    let tc = run(&[csrr(10, csr::VLENB)], 256);
+    assert_eq!(tc.get_reg(10), 32);
+    assert_eq!(tc.cpu().csrs.vlenb, 32);
+}
+
+#[test]
+fn vtype_starts_illegal() {
+    let tc = run(&[csrr(10, csr::VTYPE)], 128);
+    assert_eq!(tc.get_reg(10), csr::VTYPE_VILL);
+}
+
+#[test]
+fn vsetvli_clamps_avl_to_vlmax() {
+    // VLEN=128 at SEW=32 gives VLMAX=4.
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 3).build(),
+        vsetvli(10, 5, VTYPE_E32_M1),
+        InstructionBuilder::new().addi(5, 0, 9).build(),
+        vsetvli(11, 5, VTYPE_E32_M1),
+    ];
+    let tc = run(&program, 128);
+    assert_eq!(tc.get_reg(10), 3);
+    assert_eq!(tc.get_reg(11), 4);
+    assert_eq!(tc.cpu().csrs.vl, 4);
+    assert_eq!(tc.cpu().csrs.vtype, VTYPE_E32_M1 as u64);
+}
+
+#[test]
+fn vsetvli_x0_avl_requests_vlmax() {
+    let tc = run(&[vsetvli(10, 0, VTYPE_E32_M1)], 512);
+    assert_eq!(tc.get_reg(10), 16);
+    assert_eq!(tc.cpu().csrs.vl, 16);
+}
+
+#[test]
+fn vsetvli_x0_x0_keeps_vl() {
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 2).build(),
+        vsetvli(0, 5, VTYPE_E32_M1),
+        vsetvli(0, 0, VTYPE_E32_M1),
+        csrr(10, csr::VL),
+    ];
+    let tc = run(&program, 128);
+    assert_eq!(tc.get_reg(10), 2);
+}
+
+#[test]
+fn vsetvl_reads_vtype_from_register() {
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 2).build(),
+        InstructionBuilder::new()
+            .addi(6, 0, VTYPE_E32_M1 as i32)
+            .build(),
+        vsetvl(10, 5, 6),
+        csrr(11, csr::VTYPE),
+    ];
+    let tc = run(&program, 128);
+    assert_eq!(tc.get_reg(10), 2);
+    assert_eq!(tc.get_reg(11), VTYPE_E32_M1 as u64);
+}
+
+#[test]
+fn unsupported_sew_sets_vill() {
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 2).build(),
+        vsetvli(10, 5, VTYPE_E64_M1),
+    ];
+    let tc = run(&program, 128);
+    assert_eq!(tc.get_reg(10), 0);
+    assert_eq!(tc.cpu().csrs.vl, 0);
+    assert_eq!(tc.cpu().csrs.vtype, csr::VTYPE_VILL);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Arithmetic
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn vadd_vv_adds_active_elements_only() {
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 3).build(),
+        vsetvli(0, 5, VTYPE_E32_M1),
+        vadd_vv(1, 2, 3),
+    ];
+    let tc = run(&program, 128);
+    let regs = &tc.cpu().regs;
+    for i in 0..3 {
+        assert_eq!(regs.read_v32(1, i), 100 + 2 * i as u32);
+    }
+    assert_eq!(regs.read_v32(1, 3), 0, "tail element is undisturbed");
+    assert_eq!(tc.cpu().csrs.vstart, 0);
+}
+
+#[test]
+fn vsub_vv_subtracts_vs1_from_vs2() {
+    let program = [vsetvli(5, 0, VTYPE_E32_M1), vsub_vv(4, 2, 3)];
+    let tc = run(&program, 256);
+    let regs = &tc.cpu().regs;
+    for i in 0..8 {
+        assert_eq!(regs.read_v32(4, i), 100);
+    }
+}
+
+#[test]
+fn vector_results_chain_in_program_order() {
+    // v1 = v2 + v3; v1 = v1 - v3  =>  v1 == v2
+    let program = [
+        vsetvli(5, 0, VTYPE_E32_M1),
+        vadd_vv(1, 2, 3),
+        vsub_vv(1, 1, 3),
+    ];
+    let tc = run(&program, 128);
+    let regs = &tc.cpu().regs;
+    for i in 0..4 {
+        assert_eq!(regs.read_v32(1, i), regs.read_v32(2, i));
+    }
+}
+
+#[test]
+fn vadd_wraps_at_32_bits() {
+    let mut config = Config::default();
+    config.pipeline.vlen = 128;
+    let mut tc = TestContext::program(&config, &[vsetvli(5, 0, VTYPE_E32_M1), vadd_vv(1, 2, 3)]);
+    tc.cpu_mut().regs.write_v32(2, 0, u32::MAX);
+    tc.cpu_mut().regs.write_v32(3, 0, 2);
+    tc.run(1_000);
+    assert_eq!(tc.cpu().regs.read_v32(1, 0), 1);
+}
+
+#[test]
+fn vadd_with_vill_traps() {
+    // Program:
+    //   0: VADD.VV v1, v2, v3   (vtype still vill)
+    //   4: x7 = 1
+    //   8: JAL x0, 0           (spin)
+    //  12: handler: x8 = 1
+    //  16: JAL x0, 0           (spin)
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            vadd_vv(1, 2, 3),
+            InstructionBuilder::new().addi(7, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+            InstructionBuilder::new().addi(8, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+        ],
+    );
+    tc.cpu_mut().direct_mode = false;
+    tc.cpu_mut().csrs.mtvec = PROGRAM_BASE + 12;
+    for _ in 0..200 {
+        tc.sim.tick().unwrap();
+        if tc.get_reg(8) == 1 {
+            break;
+        }
+    }
+    assert_eq!(tc.get_reg(8), 1, "handler should run");
+    assert_eq!(tc.get_reg(7), 0);
+    assert_eq!(tc.cpu().csrs.mcause, exception::ILLEGAL_INSTRUCTION);
+    assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Disassembly
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn disassembles_vector_subset() {
+    assert_eq!(
+        disassemble(vsetvli(10, 11, VTYPE_E32_M1 | 0xC0)),
+        "vsetvli a0, a1, e32, m1, ta, ma"
+    );
+    assert_eq!(disassemble(vsetvl(10, 11, 12)), "vsetvl a0, a1, a2");
+    assert_eq!(disassemble(vadd_vv(1, 2, 3)), "vadd.vv v1, v2, v3");
+    assert_eq!(disassemble(vsub_vv(4, 5, 6)), "vsub.vv v4, v5, v6");
+}
@@ -0,0 +1,298 @@
+//! ALU Bit-Manipulation Tests (Zba / Zbb).
+//!
+//! Edge-case vectors for the Zba address-generation and Zbb basic
+//! bit-manipulation operations, including the `.w`/`.uw` variants, followed
+//! by end-to-end checks that the decoder accepts the encodings emitted for
+//! `-march=rv64gcb` and that `misa` advertises B.
+//!
+//! Reference: RISC-V Bit-Manipulation ISA-extensions, Version 1.0.0.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr;
+use rvsim_core::core::pipeline::signals::AluOp;
+use rvsim_core::core::units::alu::Alu;
+use rvsim_core::isa::disasm::disassemble;
+use rvsim_core::isa::privileged::cause::exception;
+
+const OP_IMM: u32 = 0b0010011;
+const OP_IMM_32: u32 = 0b0011011;
+const OP_REG: u32 = 0b0110011;
+const OP_REG_32: u32 = 0b0111011;
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+fn alu(op: AluOp, a: u64, b: u64, is32: bool) -> u64 {
+    Alu::execute(op, a, b, 0, is32)
+}
+
+/// R-type encoding.
+fn r_type(f7: u32, rs2: u32, rs1: u32, f3: u32, rd: u32, opcode: u32) -> u32 {
+    (f7 << 25) | (rs2 << 20) | (rs1 << 15) | (f3 << 12) | (rd << 7) | opcode
+}
+
+/// I-type encoding with a raw 12-bit immediate.
+fn i_type(imm12: u32, rs1: u32, f3: u32, rd: u32, opcode: u32) -> u32 {
+    (imm12 << 20) | (rs1 << 15) | (f3 << 12) | (rd << 7) | opcode
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+//  Zbb: logic with negate, min/max
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn andn_orn_xnor() {
+    let a = 0xF0F0_0000_FFFF_1234;
+    let b = 0x0FF0_FFFF_0000_1200;
+    assert_eq!(alu(AluOp::Andn, a, b, false), a & !b);
+    assert_eq!(alu(AluOp::Orn, a, b, false), a | !b);
+    assert_eq!(alu(AluOp::Xnor, a, b, false), !(a ^ b));
+    assert_eq!(alu(AluOp::Xnor, a, a, false), u64::MAX);
+}
+
+#[test]
+fn min_max_signed_and_unsigned() {
+    let neg = -5_i64 as u64;
+    assert_eq!(alu(AluOp::Min, neg, 3, false), neg);
+    assert_eq!(alu(AluOp::Max, neg, 3, false), 3);
+    assert_eq!(alu(AluOp::Minu, neg, 3, false), 3);
+    assert_eq!(alu(AluOp::Maxu, neg, 3, false), neg);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+//  Zbb: counts
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn clz_ctz_of_zero_are_register_width() {
+    assert_eq!(alu(AluOp::Clz, 0, 0, false), 64);
+    assert_eq!(alu(AluOp::Ctz, 0, 0, false), 64);
+    assert_eq!(alu(AluOp::Clz, 0, 0, true), 32);
+    assert_eq!(alu(AluOp::Ctz, 0, 0, true), 32);
+}
+
+#[test]
+fn word_counts_ignore_upper_half() {
+    let a = 0xFFFF_FFFF_0000_0100;
+    assert_eq!(alu(AluOp::Clz, a, 0, false), 0);
+    assert_eq!(alu(AluOp::Clz, a, 0, true), 23);
+    assert_eq!(alu(AluOp::Ctz, a, 0, true), 8);
+    assert_eq!(alu(AluOp::Cpop, a, 0, false), 33);
+    assert_eq!(alu(AluOp::Cpop, a, 0, true), 1);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+//  Zbb: extensions, byte ops
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn sign_and_zero_extension() {
+    assert_eq!(
+        alu(AluOp::SextB, 0x1234_0080, 0, false),
+        0xFFFF_FFFF_FFFF_FF80
+    );
+    assert_eq!(alu(AluOp::SextB, 0x7F, 0, false), 0x7F);
+    assert_eq!(
+        alu(AluOp::SextH, 0x1234_8000, 0, false),
+        0xFFFF_FFFF_FFFF_8000
+    );
+    assert_eq!(alu(AluOp::ZextH, 0xFFFF_FFFF_FFFF_8000, 0, true), 0x8000);
+}
+
+#[test]
+fn rev8_and_orc_b() {
+    assert_eq!(
+        alu(AluOp::Rev8, 0x0102_0304_0506_0708, 0, false),
+        0x0807_0605_0403_0201
+    );
+    assert_eq!(
+        alu(AluOp::OrcB, 0x0100_2000_0000_0080, 0, false),
+        0xFF00_FF00_0000_00FF
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+//  Zbb: rotates
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn rotate_rv64() {
+    assert_eq!(alu(AluOp::Rol, 0x8000_0000_0000_0001, 1, false), 3);
+    assert_eq!(alu(AluOp::Ror, 3, 1, false), 0x8000_0000_0000_0001);
+    // Amount masked to 6 bits.
+    assert_eq!(alu(AluOp::Rol, 1, 64, false), 1);
+}
+
+#[test]
+fn rotate_word_sign_extends() {
+    assert_eq!(alu(AluOp::Ror, 1, 1, true), 0xFFFF_FFFF_8000_0000);
+    assert_eq!(
+        alu(AluOp::Rol, 0xFFFF_FFFF_4000_0000, 1, true),
+        0xFFFF_FFFF_8000_0000
+    );
+    // Amount masked to 5 bits.
+    assert_eq!(alu(AluOp::Rol, 1, 32, true), 1);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+//  Zba: address generation
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn shift_and_add() {
+    assert_eq!(alu(AluOp::Sh1Add, 5, 100, false), 110);
+    assert_eq!(alu(AluOp::Sh2Add, 5, 100, false), 120);
+    assert_eq!(alu(AluOp::Sh3Add, 5, 100, false), 140);
+}
+
+#[test]
+fn uw_forms_zero_extend_rs1() {
+    let a = 0xFFFF_FFFF_8000_0000;
+    assert_eq!(alu(AluOp::AddUw, a, 1, true), 0x8000_0001);
+    assert_eq!(alu(AluOp::Sh1Add, a, 0, true), 0x1_0000_0000);
+    assert_eq!(alu(AluOp::Sh3Add, a, 8, true), 0x4_0000_0008);
+    assert_eq!(alu(AluOp::SlliUw, a, 4, true), 0x8_0000_0000);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+//  Decode and execute through the pipeline
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// Runs `program` with `x1 = a` and `x2 = b` and returns the context.
+fn run(program: &[u32], a: u64, b: u64) -> TestContext {
+    let mut tc = TestContext::program(&Config::default(), program);
+    tc.set_reg(1, a);
+    tc.set_reg(2, b);
+    tc.run_to_exit();
+    tc
+}
+
+#[test]
+fn pipeline_executes_zbb_register_and_unary_forms() {
+    let program = [
+        i_type(0x602, 1, 0b001, 10, OP_IMM),           // cpop x10, x1
+        i_type(0x600, 1, 0b001, 11, OP_IMM),           // clz x11, x1
+        i_type(0x601, 1, 0b001, 12, OP_IMM_32),        // ctzw x12, x1
+        i_type(0x604, 1, 0b001, 13, OP_IMM),           // sext.b x13, x1
+        i_type(0x6B8, 1, 0b101, 14, OP_IMM),           // rev8 x14, x1
+        i_type(0x287, 1, 0b101, 15, OP_IMM),           // orc.b x15, x1
+        i_type(0x604, 1, 0b101, 16, OP_IMM),           // rori x16, x1, 4
+        r_type(0b0100000, 2, 1, 0b111, 17, OP_REG),    // andn x17, x1, x2
+        r_type(0b0000101, 2, 1, 0b100, 18, OP_REG),    // min x18, x1, x2
+        r_type(0b0110000, 2, 1, 0b001, 19, OP_REG),    // rol x19, x1, x2
+        r_type(0b0000100, 0, 1, 0b100, 20, OP_REG_32), // zext.h x20, x1
+    ];
+    let a = 0x0000_0000_0000_F0F0;
+    let tc = run(&program, a, 4);
+    assert_eq!(tc.get_reg(10), 8);
+    assert_eq!(tc.get_reg(11), 48);
+    assert_eq!(tc.get_reg(12), 4);
+    assert_eq!(tc.get_reg(13), 0xFFFF_FFFF_FFFF_FFF0);
+    assert_eq!(tc.get_reg(14), 0xF0F0_0000_0000_0000);
+    assert_eq!(tc.get_reg(15), 0xFFFF);
+    assert_eq!(tc.get_reg(16), 0x0000_0000_0000_0F0F);
+    assert_eq!(tc.get_reg(17), a & !4);
+    assert_eq!(tc.get_reg(18), 4);
+    assert_eq!(tc.get_reg(19), a << 4);
+    assert_eq!(tc.get_reg(20), a);
+}
+
+#[test]
+fn pipeline_executes_zba_forms() {
+    let program = [
+        r_type(0b0010000, 2, 1, 0b010, 10, OP_REG), // sh1add x10, x1, x2
+        r_type(0b0010000, 2, 1, 0b110, 11, OP_REG), // sh3add x11, x1, x2
+        r_type(0b0000100, 2, 1, 0b000, 12, OP_REG_32), // add.uw x12, x1, x2
+        r_type(0b0010000, 2, 1, 0b100, 13, OP_REG_32), // sh2add.uw x13, x1, x2
+        i_type(0x083, 1, 0b001, 14, OP_IMM_32),     // slli.uw x14, x1, 3
+    ];
+    let a = 0xFFFF_FFFF_0000_0010;
+    let tc = run(&program, a, 0x1000);
+    assert_eq!(tc.get_reg(10), (a << 1).wrapping_add(0x1000));
+    assert_eq!(tc.get_reg(11), (a << 3).wrapping_add(0x1000));
+    assert_eq!(tc.get_reg(12), 0x1010);
+    assert_eq!(tc.get_reg(13), 0x1040);
+    assert_eq!(tc.get_reg(14), 0x80);
+}
+
+#[test]
+fn unassigned_unary_selector_is_illegal() {
+    // Program:
+    //   0: (funct7 = 0110000, rs2 = 3: not a Zbb instruction)
+    //   4: x7 = 1
+    //   8: JAL x0, 0           (spin)
+    //  12: handler: x8 = 1
+    //  16: JAL x0, 0           (spin)
+    let mut tc = TestContext::program(
+        &Config::default(),
+        &[
+            i_type(0x603, 1, 0b001, 10, OP_IMM),
+            InstructionBuilder::new().addi(7, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+            InstructionBuilder::new().addi(8, 0, 1).build(),
+            InstructionBuilder::new().jal(0, 0).build(),
+        ],
+    );
+    tc.cpu_mut().direct_mode = false;
+    tc.cpu_mut().csrs.mtvec = PROGRAM_BASE + 12;
+    for _ in 0..200 {
+        tc.sim.tick().unwrap();
+        if tc.get_reg(8) == 1 {
+            break;
+        }
+    }
+    assert_eq!(tc.get_reg(8), 1, "handler should run");
+    assert_eq!(tc.get_reg(7), 0);
+    assert_eq!(tc.cpu().csrs.mcause, exception::ILLEGAL_INSTRUCTION);
+    assert_eq!(tc.cpu().csrs.mepc, PROGRAM_BASE);
+}
+
+#[test]
+fn base_shifts_still_decode() {
+    let program = [
+        InstructionBuilder::new().srli(10, 1, 40).build(),
+        i_type(0x420, 1, 0b101, 11, OP_IMM), // srai x11, x1, 32
+    ];
+    let tc = run(&program, 0x8000_0000_0000_0000, 0);
+    assert_eq!(tc.get_reg(10), 0x80_0000);
+    assert_eq!(tc.get_reg(11), 0xFFFF_FFFF_8000_0000);
+}
+
+#[test]
+fn misa_advertises_b() {
+    let tc = TestContext::new();
+    assert_ne!(tc.cpu().csrs.misa & csr::MISA_EXT_B, 0);
+}
+
+#[test]
+fn disassembles_bitmanip() {
+    assert_eq!(
+        disassemble(i_type(0x602, 11, 0b001, 10, OP_IMM)),
+        "cpop a0, a1"
+    );
+    assert_eq!(
+        disassemble(i_type(0x600, 11, 0b001, 10, OP_IMM_32)),
+        "clzw a0, a1"
+    );
+    assert_eq!(
+        disassemble(i_type(0x6B8, 11, 0b101, 10, OP_IMM)),
+        "rev8 a0, a1"
+    );
+    assert_eq!(
+        disassemble(i_type(0x605, 11, 0b101, 10, OP_IMM)),
+        "rori a0, a1, 5"
+    );
+    assert_eq!(
+        disassemble(r_type(0b0010000, 12, 11, 0b100, 10, OP_REG_32)),
+        "sh2add.uw a0, a1, a2"
+    );
+    assert_eq!(
+        disassemble(r_type(0b0100000, 12, 11, 0b111, 10, OP_REG)),
+        "andn a0, a1, a2"
+    );
+    assert_eq!(
+        disassemble(r_type(0b0000100, 0, 11, 0b100, 10, OP_REG_32)),
+        "zext.h a0, a1"
+    );
+}
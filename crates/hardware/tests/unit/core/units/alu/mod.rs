@@ -1,3 +1,4 @@
 pub mod arithmetic;
+pub mod bitmanip;
 pub mod logic;
 pub mod shifts;
@@ -0,0 +1,90 @@
+//! ITTAGE Indirect Predictor Tests.
+//!
+//! Verifies target learning in the base table, history-correlated targets
+//! in the tagged banks, and the BTB fallback through `BranchPredictorWrapper`.
+
+use rvsim_core::config::Config;
+use rvsim_core::core::units::bru::ittage::IttagePredictor;
+use rvsim_core::core::units::bru::{BranchPredictor, BranchPredictorWrapper};
+
+const PC: u64 = 0x8000_0200;
+const TARGET_A: u64 = 0x8000_1000;
+const TARGET_B: u64 = 0x8000_2040;
+
+#[test]
+fn no_prediction_when_cold() {
+    let ip = IttagePredictor::new(256);
+    assert_eq!(ip.predict(PC, 0), None);
+}
+
+#[test]
+fn learns_single_target() {
+    let mut ip = IttagePredictor::new(256);
+    ip.update(PC, TARGET_A, 0);
+    assert_eq!(ip.predict(PC, 0), Some(TARGET_A));
+    assert_eq!(
+        ip.predict(PC, 0b1011),
+        Some(TARGET_A),
+        "base table is history-independent"
+    );
+}
+
+#[test]
+fn stray_target_does_not_evict_confident_base_entry() {
+    let mut ip = IttagePredictor::new(256);
+    for _ in 0..4 {
+        ip.update(PC, TARGET_A, 0);
+    }
+    // The stray target is allocated in a tagged bank for its own history
+    // instead of replacing the well-established base entry.
+    ip.update(PC, TARGET_B, 0b1);
+    assert_eq!(ip.predict(PC, 0), Some(TARGET_A));
+    assert_eq!(ip.predict(PC, 0b1), Some(TARGET_B));
+}
+
+#[test]
+fn learns_history_correlated_targets() {
+    let mut ip = IttagePredictor::new(256);
+    let mut ghr = 0u64;
+    let mut mispredicts_late = 0;
+
+    // The target is determined by the direction of the preceding branch.
+    for i in 0..64 {
+        let taken = i % 3 == 0;
+        ghr = (ghr << 1) | taken as u64;
+        let target = if taken { TARGET_A } else { TARGET_B };
+        if i >= 32 && ip.predict(PC, ghr) != Some(target) {
+            mispredicts_late += 1;
+        }
+        ip.update(PC, target, ghr);
+    }
+
+    assert_eq!(mispredicts_late, 0);
+}
+
+#[test]
+fn wrapper_falls_back_to_btb() {
+    let mut config = Config::default();
+    config.pipeline.indirect_predictor = true;
+    let mut bp = BranchPredictorWrapper::new(&config);
+
+    // A resolved call trains the BTB; with no indirect entry the BTB target is used.
+    bp.update_btb(PC, TARGET_A);
+    assert_eq!(bp.predict_indirect(PC), Some(TARGET_A));
+
+    let h = bp.snapshot_history();
+    bp.update_indirect(PC, TARGET_B, h);
+    assert_eq!(bp.predict_indirect(PC), Some(TARGET_B));
+    assert_eq!(bp.predict_btb(PC), Some(TARGET_A), "BTB is left untouched");
+}
+
+#[test]
+fn wrapper_without_indirect_predictor_uses_btb() {
+    let mut bp = BranchPredictorWrapper::new(&Config::default());
+    assert!(bp.indirect_predictor.is_none());
+
+    bp.update_btb(PC, TARGET_A);
+    let h = bp.snapshot_history();
+    bp.update_indirect(PC, TARGET_B, h);
+    assert_eq!(bp.predict_indirect(PC), Some(TARGET_A));
+}
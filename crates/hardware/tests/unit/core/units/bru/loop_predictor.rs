@@ -0,0 +1,155 @@
+//! Loop Predictor Tests.
+//!
+//! Verifies allocation, trip-count learning, confidence, and the override
+//! of the base predictor when the loop predictor is layered on top of it
+//! through `BranchPredictorWrapper`.
+
+use rvsim_core::config::{BranchPredictor as BpType, Config};
+use rvsim_core::core::units::bru::loop_predictor::LoopPredictor;
+use rvsim_core::core::units::bru::{BranchPredictor, BranchPredictorWrapper};
+
+const PC: u64 = 0x8000_0100;
+const TARGET: u64 = 0x8000_00F0;
+
+/// Drives one instance of a loop with `trip` taken outcomes followed by an
+/// exit, predicting and speculating each outcome as fetch would.
+/// Returns the number of direction mispredictions.
+fn run_loop(lp: &mut LoopPredictor, trip: usize, base_taken: bool) -> usize {
+    let mut mispredicts = 0;
+    for i in 0..=trip {
+        let taken = i < trip;
+        let predicted = lp.predict(PC).unwrap_or(base_taken);
+        lp.speculate(PC, predicted);
+        if predicted != taken {
+            mispredicts += 1;
+        }
+        lp.update(PC, taken, base_taken);
+    }
+    mispredicts
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Standalone loop predictor
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn no_prediction_before_allocation() {
+    let lp = LoopPredictor::new(64);
+    assert_eq!(lp.predict(PC), None);
+}
+
+#[test]
+fn no_allocation_when_base_predicted_exit() {
+    let mut lp = LoopPredictor::new(64);
+    for _ in 0..4 {
+        run_loop(&mut lp, 5, false);
+    }
+    assert_eq!(lp.predict(PC), None, "base never mispredicted an exit");
+}
+
+#[test]
+fn learns_constant_trip_count() {
+    let mut lp = LoopPredictor::new(64);
+    // The first instance allocates on the exit, the second records the trip
+    // count, and the next two confirm it.
+    for _ in 0..4 {
+        run_loop(&mut lp, 5, true);
+    }
+    assert_eq!(
+        run_loop(&mut lp, 5, true),
+        0,
+        "confident loop predictor should predict every iteration including the exit"
+    );
+}
+
+#[test]
+fn trip_count_change_drops_confidence() {
+    let mut lp = LoopPredictor::new(64);
+    for _ in 0..4 {
+        run_loop(&mut lp, 5, true);
+    }
+    assert!(lp.predict(PC).is_some());
+
+    // A longer instance mispredicts the exit once and resets confidence.
+    run_loop(&mut lp, 7, true);
+    assert_eq!(lp.predict(PC), None);
+
+    // The new trip count becomes confident after two more repeats.
+    for _ in 0..2 {
+        run_loop(&mut lp, 7, true);
+    }
+    assert_eq!(run_loop(&mut lp, 7, true), 0);
+}
+
+#[test]
+fn speculative_count_tracks_in_flight_iterations() {
+    let mut lp = LoopPredictor::new(64);
+    for _ in 0..4 {
+        run_loop(&mut lp, 3, true);
+    }
+
+    // Predict three taken iterations ahead of resolution; the fourth
+    // in-flight instance must be the exit.
+    for _ in 0..3 {
+        assert_eq!(lp.predict(PC), Some(true));
+        lp.speculate(PC, true);
+    }
+    assert_eq!(lp.predict(PC), Some(false));
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Layered on a base predictor
+// ══════════════════════════════════════════════════════════
+
+fn wrapper(loop_predictor: bool) -> BranchPredictorWrapper {
+    let mut config = Config::default();
+    config.pipeline.branch_predictor = BpType::GShare;
+    config.pipeline.loop_predictor = loop_predictor;
+    BranchPredictorWrapper::new(&config)
+}
+
+/// Runs `instances` of a loop through the wrapper and returns the
+/// mispredictions in the final instance.
+fn run_wrapped(bp: &mut BranchPredictorWrapper, trip: usize, instances: usize) -> usize {
+    let mut mispredicts = 0;
+    for _ in 0..instances {
+        mispredicts = 0;
+        for i in 0..=trip {
+            let taken = i < trip;
+            let ghr = bp.snapshot_history();
+            let (predicted, _) = bp.predict_branch(PC);
+            bp.speculate(PC, predicted);
+            if predicted != taken {
+                mispredicts += 1;
+            }
+            bp.repair_history(ghr);
+            bp.update_branch(PC, taken, taken.then_some(TARGET));
+        }
+    }
+    mispredicts
+}
+
+#[test]
+fn wrapper_without_loop_predictor_has_none() {
+    assert!(wrapper(false).loop_predictor.is_none());
+    assert!(wrapper(true).loop_predictor.is_some());
+}
+
+#[test]
+fn wrapper_overrides_base_on_loop_exit() {
+    // A trip count longer than GShare's history saturates it to taken, so
+    // only the loop predictor can catch the exit.
+    let trip = 40;
+    let mut base_only = wrapper(false);
+    let mut layered = wrapper(true);
+
+    assert_eq!(run_wrapped(&mut base_only, trip, 8), 1);
+    assert_eq!(run_wrapped(&mut layered, trip, 8), 0);
+}
+
+#[test]
+fn wrapper_uses_btb_target_for_loop_prediction() {
+    let mut layered = wrapper(true);
+    run_wrapped(&mut layered, 4, 6);
+    assert_eq!(layered.predict_branch(PC), (true, Some(TARGET)));
+}
@@ -1,3 +1,5 @@
 pub mod btb;
+pub mod ittage;
+pub mod loop_predictor;
 pub mod predictors;
 pub mod ras;
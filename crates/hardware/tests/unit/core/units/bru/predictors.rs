@@ -7,7 +7,9 @@
 //!
 //! Reference: Phase 2 — Pipeline Logic & Hazards.
 
-use rvsim_core::config::{PerceptronConfig, TageConfig, TournamentConfig};
+use rvsim_core::config::{
+    GShareConfig, GShareIndex, PerceptronConfig, TageConfig, TournamentConfig,
+};
 use rvsim_core::core::units::bru::BranchPredictor;
 use rvsim_core::core::units::bru::gshare::GSharePredictor;
 use rvsim_core::core::units::bru::perceptron::PerceptronPredictor;
@@ -36,6 +38,7 @@ fn default_perceptron() -> PerceptronPredictor {
         &PerceptronConfig {
             history_length: 8,
             table_bits: 6, // 64 entries
+            adaptive_threshold: false,
         },
         64,
         8,
@@ -102,7 +105,7 @@ fn static_updates_btb() {
 /// GShare initial prediction — counters initialized to 1 (weakly not-taken).
 #[test]
 fn gshare_initial_not_taken() {
-    let bp = GSharePredictor::new(64, 8);
+    let bp = GSharePredictor::new(&GShareConfig::default(), 64, 8);
     let (taken, _) = bp.predict_branch(0x1000);
     assert!(!taken, "Initial counter=1 → not taken (< 2)");
 }
@@ -110,11 +113,11 @@ fn gshare_initial_not_taken() {
 /// GShare learns taken after repeated taken updates.
 /// The GHR shift-register means each training step may hit a different PHT
 /// entry until the GHR saturates (all 1s for all-taken, after ~12 steps
-/// with TABLE_BITS=12). After saturation, further training reinforces the
+/// with the default 12-bit history). After saturation, further training reinforces the
 /// same entry. We use 20 steps to ensure convergence.
 #[test]
 fn gshare_learns_taken() {
-    let mut bp = GSharePredictor::new(64, 8);
+    let mut bp = GSharePredictor::new(&GShareConfig::default(), 64, 8);
     let pc = 0x1000;
     train(&mut bp, pc, true, 0x2000, 20);
 
@@ -125,7 +128,7 @@ fn gshare_learns_taken() {
 /// GShare learns not-taken after repeated not-taken updates.
 #[test]
 fn gshare_learns_not_taken() {
-    let mut bp = GSharePredictor::new(64, 8);
+    let mut bp = GSharePredictor::new(&GShareConfig::default(), 64, 8);
     let pc = 0x1000;
 
     // First push counters up to taken...
@@ -140,7 +143,7 @@ fn gshare_learns_not_taken() {
 /// GShare uses GHR XOR PC for indexing — different histories produce different predictions.
 #[test]
 fn gshare_context_sensitive() {
-    let mut bp = GSharePredictor::new(256, 8);
+    let mut bp = GSharePredictor::new(&GShareConfig::default(), 256, 8);
     let pc = 0x1000;
 
     // Create two different history contexts by feeding different branches.
@@ -149,7 +152,7 @@ fn gshare_context_sensitive() {
     let (pred_a, _) = bp.predict_branch(pc);
 
     // Context B: branch at pc=0x100 not-taken, then predict pc=0x1000.
-    let mut bp2 = GSharePredictor::new(256, 8);
+    let mut bp2 = GSharePredictor::new(&GShareConfig::default(), 256, 8);
     bp2.update_branch(0x100, false, None);
     let (pred_b, _) = bp2.predict_branch(pc);
 
@@ -159,6 +162,68 @@ fn gshare_context_sensitive() {
     let _ = (pred_a, pred_b);
 }
 
+fn gshare(table_bits: usize, history_length: usize, index: GShareIndex) -> GSharePredictor {
+    let config = GShareConfig {
+        table_bits,
+        history_length,
+        index,
+    };
+    GSharePredictor::new(&config, 64, 8)
+}
+
+/// PC indexing (bimodal) ignores history, so unrelated branches that change
+/// the GHR do not move the entry.
+#[test]
+fn gshare_pc_index_ignores_history() {
+    let mut bp = gshare(12, 12, GShareIndex::Pc);
+    bp.update_branch(0x1000, true, Some(0x2000));
+    bp.update_branch(0x1000, true, Some(0x2000));
+    for _ in 0..5 {
+        bp.update_branch(0x3000, false, None);
+    }
+    assert!(bp.predict_branch(0x1000).0);
+}
+
+/// History indexing (GAg) ignores the PC: with no history bits, every
+/// branch shares one counter.
+#[test]
+fn gshare_history_index_shares_entries_across_pcs() {
+    let mut bp = gshare(12, 0, GShareIndex::History);
+    bp.update_branch(0x1000, true, Some(0x2000));
+    bp.update_branch(0x1000, true, Some(0x2000));
+    assert!(bp.predict_branch(0x5678).0);
+}
+
+/// The table size is configurable: PCs 2^table_bits instructions apart
+/// alias in a small table but not in the default one.
+#[test]
+fn gshare_table_bits_set_pht_size() {
+    let far = 0x1000 + (16 << 2);
+    for (bits, aliases) in [(4, true), (12, false)] {
+        let mut bp = gshare(bits, 0, GShareIndex::Pc);
+        bp.update_branch(0x1000, true, Some(0x2000));
+        bp.update_branch(0x1000, true, Some(0x2000));
+        assert_eq!(bp.predict_branch(far).0, aliases, "table_bits={bits}");
+    }
+}
+
+/// History longer than the index is folded in rather than truncated.
+#[test]
+fn gshare_long_history_is_folded() {
+    let mut bp = gshare(8, 20, GShareIndex::History);
+    for _ in 0..2 {
+        bp.repair_history(1 << 15);
+        bp.update_branch(0x1000, true, Some(0x2000));
+    }
+    bp.repair_history(1 << 15);
+    assert!(bp.predict_branch(0x1000).0);
+    bp.repair_history(0);
+    assert!(
+        !bp.predict_branch(0x1000).0,
+        "bit 15 distinguishes the entry"
+    );
+}
+
 // ══════════════════════════════════════════════════════════
 // 3. Perceptron Predictor
 // ══════════════════════════════════════════════════════════
@@ -207,6 +272,70 @@ fn perceptron_retrains() {
     assert!(!t2, "Should retrain to not-taken");
 }
 
+/// Perceptron: training stops once the output clears the threshold, and
+/// every update is counted as trained or skipped.
+#[test]
+fn perceptron_counts_trained_and_skipped_updates() {
+    let mut bp = default_perceptron();
+    train(&mut bp, 0x1000, true, 0x2000, 50);
+    let counts = bp.training_counts().unwrap();
+    assert_eq!(counts.trained + counts.skipped, 50);
+    assert!(counts.trained > 0 && counts.skipped > 0, "{counts:?}");
+    // theta = 1.93 * 8 + 14
+    assert_eq!(counts.threshold, 29);
+}
+
+/// A perceptron without history, so each PC has just a bias weight.
+fn bias_only_perceptron(adaptive: bool) -> PerceptronPredictor {
+    let config = PerceptronConfig {
+        history_length: 0,
+        table_bits: 10,
+        adaptive_threshold: adaptive,
+    };
+    PerceptronPredictor::new(&config, 64, 8)
+}
+
+/// Adaptive theta: every 63 mispredictions raise it by one.
+#[test]
+fn perceptron_adaptive_threshold_rises_on_mispredictions() {
+    for (adaptive, expected) in [(false, 14), (true, 24)] {
+        let mut bp = bias_only_perceptron(adaptive);
+        for _ in 0..630 {
+            let (predicted, _) = bp.predict_branch(0x1000);
+            bp.update_branch(0x1000, !predicted, None);
+        }
+        assert_eq!(
+            bp.training_counts().unwrap().threshold,
+            expected,
+            "adaptive={adaptive}"
+        );
+    }
+}
+
+/// Adaptive theta: every 64 correct low-confidence predictions lower it by
+/// one. Each fresh entry predicts taken with output 0.
+#[test]
+fn perceptron_adaptive_threshold_falls_on_weak_correct_predictions() {
+    let mut bp = bias_only_perceptron(true);
+    for i in 0..128 {
+        bp.update_branch(0x1000 + i * 4, true, None);
+    }
+    let counts = bp.training_counts().unwrap();
+    assert_eq!(counts.threshold, 12);
+    assert_eq!(counts.trained, 128);
+}
+
+/// Only the perceptron reports training counts.
+#[test]
+fn counter_predictors_have_no_training_counts() {
+    assert!(
+        GSharePredictor::new(&GShareConfig::default(), 64, 8)
+            .training_counts()
+            .is_none()
+    );
+    assert!(default_tage().training_counts().is_none());
+}
+
 // ══════════════════════════════════════════════════════════
 // 4. TAGE Predictor
 // ══════════════════════════════════════════════════════════
@@ -328,7 +457,7 @@ fn all_predictors_use_btb() {
     static_bp.update_branch(pc, true, Some(target));
     assert_eq!(static_bp.predict_btb(pc), Some(target));
 
-    let mut gshare = GSharePredictor::new(64, 8);
+    let mut gshare = GSharePredictor::new(&GShareConfig::default(), 64, 8);
     gshare.update_branch(pc, true, Some(target));
     assert_eq!(gshare.predict_btb(pc), Some(target));
 
@@ -352,37 +481,58 @@ fn all_predictors_use_btb() {
 /// All predictors correctly push/pop the RAS via on_call/on_return/predict_return.
 #[test]
 fn all_predictors_use_ras() {
-    let call_pc = 0x1000;
     let ret_addr = 0x1004;
-    let call_target = 0x2000;
 
     let mut static_bp = StaticPredictor::new(64, 8);
-    static_bp.on_call(call_pc, ret_addr, call_target);
+    static_bp.on_call(ret_addr);
     assert_eq!(static_bp.predict_return(), Some(ret_addr));
     static_bp.on_return();
     assert_eq!(static_bp.predict_return(), None);
 
-    let mut gshare = GSharePredictor::new(64, 8);
-    gshare.on_call(call_pc, ret_addr, call_target);
+    let mut gshare = GSharePredictor::new(&GShareConfig::default(), 64, 8);
+    gshare.on_call(ret_addr);
     assert_eq!(gshare.predict_return(), Some(ret_addr));
     gshare.on_return();
     assert_eq!(gshare.predict_return(), None);
 
     let mut perceptron = default_perceptron();
-    perceptron.on_call(call_pc, ret_addr, call_target);
+    perceptron.on_call(ret_addr);
     assert_eq!(perceptron.predict_return(), Some(ret_addr));
     perceptron.on_return();
     assert_eq!(perceptron.predict_return(), None);
 
     let mut tage = default_tage();
-    tage.on_call(call_pc, ret_addr, call_target);
+    tage.on_call(ret_addr);
     assert_eq!(tage.predict_return(), Some(ret_addr));
     tage.on_return();
     assert_eq!(tage.predict_return(), None);
 
     let mut tournament = default_tournament();
-    tournament.on_call(call_pc, ret_addr, call_target);
+    tournament.on_call(ret_addr);
     assert_eq!(tournament.predict_return(), Some(ret_addr));
     tournament.on_return();
     assert_eq!(tournament.predict_return(), None);
 }
+
+/// Pushing a call onto the RAS leaves the BTB alone; only the resolved
+/// target handed to update_btb is learned.
+#[test]
+fn on_call_does_not_train_btb() {
+    let call_pc = 0x1000;
+    let ret_addr = 0x1004;
+    let call_target = 0x2000;
+
+    let mut gshare = GSharePredictor::new(&GShareConfig::default(), 64, 8);
+    gshare.on_call(ret_addr);
+    assert_eq!(gshare.predict_btb(call_pc), None);
+    gshare.update_btb(call_pc, call_target);
+    assert_eq!(gshare.predict_btb(call_pc), Some(call_target));
+    assert_eq!(gshare.predict_return(), Some(ret_addr));
+
+    let mut tage = default_tage();
+    tage.on_call(ret_addr);
+    assert_eq!(tage.predict_btb(call_pc), None);
+    tage.update_btb(call_pc, call_target);
+    assert_eq!(tage.predict_btb(call_pc), Some(call_target));
+    assert_eq!(tage.predict_return(), Some(ret_addr));
+}
@@ -3,7 +3,7 @@
 //! Verifies push/pop/top semantics, overflow behaviour, underflow safety,
 //! and correct LIFO ordering for return address prediction.
 
-use rvsim_core::core::units::bru::ras::Ras;
+use rvsim_core::core::units::bru::ras::{Ras, ras_hint};
 
 // ══════════════════════════════════════════════════════════
 // 1. Basic push/pop
@@ -179,3 +179,74 @@ fn push_max_address() {
     ras.push(u64::MAX);
     assert_eq!(ras.pop(), Some(u64::MAX));
 }
+
+// ══════════════════════════════════════════════════════════
+// 7. Checkpoint / restore
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn restore_undoes_wrong_path_pushes() {
+    let mut ras = Ras::new(8);
+    ras.push(0xA);
+    let cp = ras.checkpoint();
+    ras.push(0xB);
+    ras.push(0xC);
+    ras.restore(cp);
+    assert_eq!(ras.pop(), Some(0xA));
+    assert_eq!(ras.pop(), None);
+}
+
+#[test]
+fn restore_repairs_pop_then_push_overwrite() {
+    // A wrong-path return pops 0xB and a wrong-path call overwrites its
+    // slot; the checkpoint restores both the pointer and the top entry.
+    let mut ras = Ras::new(8);
+    ras.push(0xA);
+    ras.push(0xB);
+    let cp = ras.checkpoint();
+    ras.pop();
+    ras.push(0xDEAD);
+    ras.restore(cp);
+    assert_eq!(ras.pop(), Some(0xB));
+    assert_eq!(ras.pop(), Some(0xA));
+}
+
+#[test]
+fn restore_to_empty() {
+    let mut ras = Ras::new(4);
+    let cp = ras.checkpoint();
+    ras.push(0x100);
+    ras.restore(cp);
+    assert_eq!(ras.top(), None);
+}
+
+#[test]
+fn entries_lists_live_stack_oldest_first() {
+    let mut ras = Ras::new(4);
+    assert!(ras.entries().is_empty());
+    ras.push(0xA);
+    ras.push(0xB);
+    ras.push(0xC);
+    ras.pop();
+    assert_eq!(ras.entries(), &[0xA, 0xB]);
+}
+
+// ══════════════════════════════════════════════════════════
+// 8. Link-register hints
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn ras_hint_follows_spec_table() {
+    // (is_jalr, rd, rs1) -> (push, pop)
+    assert_eq!(ras_hint(false, 0, 0), (false, false), "JAL x0");
+    assert_eq!(ras_hint(false, 1, 0), (true, false), "JAL ra");
+    assert_eq!(ras_hint(false, 5, 0), (true, false), "JAL t0");
+    assert_eq!(ras_hint(true, 0, 10), (false, false), "JALR x0, a0");
+    assert_eq!(ras_hint(true, 0, 1), (false, true), "JALR x0, ra");
+    assert_eq!(ras_hint(true, 0, 5), (false, true), "JALR x0, t0");
+    assert_eq!(ras_hint(true, 1, 10), (true, false), "JALR ra, a0");
+    assert_eq!(ras_hint(true, 1, 5), (true, true), "JALR ra, t0");
+    assert_eq!(ras_hint(true, 5, 1), (true, true), "JALR t0, ra");
+    assert_eq!(ras_hint(true, 1, 1), (true, false), "JALR ra, ra");
+    assert_eq!(ras_hint(true, 5, 5), (true, false), "JALR t0, t0");
+}
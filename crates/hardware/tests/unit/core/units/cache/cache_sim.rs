@@ -40,6 +40,8 @@ fn test_config() -> CacheConfig {
         prefetcher: PrefetcherType::None,
         prefetch_table_size: 64,
         prefetch_degree: 1,
+        banks: 0,
+        set_stats: false,
     }
 }
 
@@ -314,6 +316,8 @@ fn different_line_size_32b() {
         prefetcher: PrefetcherType::None,
         prefetch_table_size: 64,
         prefetch_degree: 1,
+        banks: 0,
+        set_stats: false,
     };
     // num_lines = 256/32 = 8, num_sets = 8/2 = 4, line_bytes = 32.
     let mut cache = CacheSim::new(&config);
@@ -341,6 +345,8 @@ fn different_line_size_128b() {
         prefetcher: PrefetcherType::None,
         prefetch_table_size: 64,
         prefetch_degree: 1,
+        banks: 0,
+        set_stats: false,
     };
     // num_lines = 1024/128 = 8, num_sets = 8/2 = 4, line_bytes = 128.
     let mut cache = CacheSim::new(&config);
@@ -354,3 +360,265 @@ fn different_line_size_128b() {
     let (hit, _) = cache.access(0x200 + 128, false, NEXT_LEVEL_LATENCY);
     assert!(!hit, "Different 128-byte line should miss");
 }
+
+// ══════════════════════════════════════════════════════════
+// 10. Banks
+// ══════════════════════════════════════════════════════════
+
+/// An unbanked cache serves any number of lines per cycle.
+#[test]
+fn unbanked_cache_never_conflicts() {
+    let cache = CacheSim::new(&test_config());
+    assert_eq!(cache.bank_delays(&[0x000, 0x040, 0x080]), vec![0, 0, 0]);
+}
+
+/// Lines interleave across banks; the k-th line to reach a bank waits k
+/// cycles, and accesses to a line already being served share it.
+#[test]
+fn same_bank_accesses_serialize() {
+    let cache = CacheSim::new(&CacheConfig {
+        banks: 2,
+        ..test_config()
+    });
+    // Lines 0, 2, 4 are in bank 0; line 1 is in bank 1.
+    assert_eq!(
+        cache.bank_delays(&[0x000, 0x040, 0x080, 0x008, 0x100]),
+        vec![0, 0, 1, 0, 2]
+    );
+}
+
+// ══════════════════════════════════════════════════════════
+// 11. Geometry
+// ══════════════════════════════════════════════════════════
+
+/// Three ways of four sets: the associativity need not be a power of two,
+/// and every set still holds exactly `ways` lines.
+#[test]
+fn three_way_geometry() {
+    let mut cache = CacheSim::new(&CacheConfig {
+        size_bytes: 768,
+        line_bytes: 64,
+        ways: 3,
+        ..test_config()
+    });
+    // Lines 0, 4, 8 map to set 0.
+    for line in [0u64, 4, 8] {
+        cache.access(line * 64, false, NEXT_LEVEL_LATENCY);
+    }
+    for line in [0u64, 4, 8] {
+        assert!(cache.contains(line * 64), "line {line} fits in set 0");
+    }
+    cache.access(12 * 64, false, NEXT_LEVEL_LATENCY);
+    assert!(
+        !cache.contains(0),
+        "a fourth line in set 0 evicts the LRU one"
+    );
+    assert!(cache.contains(12 * 64));
+}
+
+/// Addresses that differ only in their top bits share a set but must not
+/// share a tag.
+#[test]
+fn high_address_bits_are_part_of_the_tag() {
+    let mut cache = CacheSim::new(&test_config());
+    cache.access(0x100, false, NEXT_LEVEL_LATENCY);
+    assert!(!cache.contains(0x100 | (1 << 63)));
+    assert!(!cache.contains(0x100 | (1 << 40)));
+    assert!(cache.contains(0x100));
+}
+
+#[test]
+#[should_panic(expected = "must be powers of two")]
+fn non_power_of_two_sets_are_rejected() {
+    // Three sets of three ways.
+    CacheSim::new(&CacheConfig {
+        size_bytes: 576,
+        line_bytes: 64,
+        ways: 3,
+        ..test_config()
+    });
+}
+
+#[test]
+#[should_panic(expected = "must be powers of two")]
+fn non_power_of_two_line_is_rejected() {
+    CacheSim::new(&CacheConfig {
+        size_bytes: 192,
+        line_bytes: 48,
+        ways: 2,
+        ..test_config()
+    });
+}
+
+#[test]
+#[should_panic(expected = "invalid cache geometry")]
+fn partial_set_is_rejected() {
+    CacheSim::new(&CacheConfig {
+        size_bytes: 1000,
+        ..test_config()
+    });
+}
+
+#[test]
+#[should_panic(expected = "invalid cache geometry")]
+fn size_smaller_than_one_set_is_rejected() {
+    CacheSim::new(&CacheConfig {
+        size_bytes: 64,
+        ..test_config()
+    });
+}
+
+// ══════════════════════════════════════════════════════════
+// 12. Random Replacement Seed
+// ══════════════════════════════════════════════════════════
+
+/// Returns which of 64 conflicting lines survive in an 8-way Random cache.
+fn random_survivors(seed: u64) -> Vec<bool> {
+    let mut cache = CacheSim::with_seed(
+        &CacheConfig {
+            size_bytes: 512,
+            ways: 8,
+            policy: PolicyType::Random,
+            ..test_config()
+        },
+        seed,
+    );
+    for line in 0..64u64 {
+        cache.access(line * 64, false, NEXT_LEVEL_LATENCY);
+    }
+    (0..64u64).map(|line| cache.contains(line * 64)).collect()
+}
+
+/// The same seed and access stream give the same evictions.
+#[test]
+fn random_policy_is_reproducible_for_a_seed() {
+    assert_eq!(random_survivors(7), random_survivors(7));
+    assert_ne!(random_survivors(7), random_survivors(8));
+}
+
+// ══════════════════════════════════════════════════════════
+// 13. DIP Scan Resistance
+// ══════════════════════════════════════════════════════════
+
+/// Hits from cycling over 1.25x the capacity of a 16-set, 4-way cache.
+fn cyclic_hits(policy: PolicyType) -> usize {
+    let mut cache = CacheSim::new(&CacheConfig {
+        size_bytes: 4096,
+        ways: 4,
+        policy,
+        ..test_config()
+    });
+    let lines = 5 * 16;
+    (0..20)
+        .flat_map(|_| 0..lines)
+        .filter(|&line| cache.access(line * 64, false, NEXT_LEVEL_LATENCY).0)
+        .count()
+}
+
+/// LRU thrashes on a loop slightly larger than the cache, while DIP learns
+/// to insert at LRU and keeps three of each set's five lines resident. The
+/// four LRU leader sets keep missing, which bounds the hit rate below 60%.
+#[test]
+fn dip_resists_thrashing_where_lru_misses_everything() {
+    assert_eq!(cyclic_hits(PolicyType::Lru), 0);
+    let total = 20 * 5 * 16;
+    let dip = cyclic_hits(PolicyType::Dip);
+    assert!(dip > total / 3, "DIP hit {dip} of {total}");
+}
+
+// ══════════════════════════════════════════════════════════
+// 14. Per-Set Statistics
+// ══════════════════════════════════════════════════════════
+
+/// Per-set counters are off unless configured.
+#[test]
+fn set_stats_disabled_by_default() {
+    let mut cache = CacheSim::new(&test_config());
+    cache.access(0x0, false, NEXT_LEVEL_LATENCY);
+    assert!(cache.set_stats().is_empty());
+}
+
+/// Each demand access is charged to its set, and misses are counted
+/// separately.
+#[test]
+fn set_stats_count_accesses_and_misses_per_set() {
+    let mut cache = CacheSim::new(&CacheConfig {
+        set_stats: true,
+        ..test_config()
+    });
+    // Set 0: miss, hit, miss. Set 1: miss.
+    for addr in [0x000, 0x000, 0x080, 0x040] {
+        cache.access(addr, false, NEXT_LEVEL_LATENCY);
+    }
+    assert_eq!(cache.set_stats(), vec![(3, 2), (1, 1)]);
+
+    cache.clear_set_stats();
+    assert_eq!(cache.set_stats(), vec![(0, 0), (0, 0)]);
+}
+
+/// Prefetch fills do not count as accesses.
+#[test]
+fn set_stats_exclude_prefetches() {
+    let mut cache = CacheSim::new(&CacheConfig {
+        set_stats: true,
+        prefetcher: PrefetcherType::NextLine,
+        ..test_config()
+    });
+    cache.access(0x000, false, NEXT_LEVEL_LATENCY);
+    assert!(cache.contains(0x040), "next line was prefetched");
+    assert_eq!(cache.set_stats(), vec![(1, 1), (0, 0)]);
+}
+
+// ══════════════════════════════════════════════════════════
+// 15. Prefetch Exclusion
+// ══════════════════════════════════════════════════════════
+
+/// A prefetch never installs a line overlapping `no_prefetch`.
+#[test]
+fn prefetch_skips_excluded_line() {
+    let mut cache = CacheSim::new(&CacheConfig {
+        prefetcher: PrefetcherType::NextLine,
+        ..test_config()
+    });
+    cache.no_prefetch = Some((0x040, 0x080));
+    cache.access(0x000, false, NEXT_LEVEL_LATENCY);
+    assert!(!cache.contains(0x040), "excluded line was not prefetched");
+
+    cache.no_prefetch = None;
+    cache.access(0x000, false, NEXT_LEVEL_LATENCY);
+    assert!(cache.contains(0x040), "prefetching resumes once released");
+}
+
+/// Demand accesses to the excluded line still install it.
+#[test]
+fn demand_access_ignores_exclusion() {
+    let mut cache = CacheSim::new(&test_config());
+    cache.no_prefetch = Some((0x040, 0x080));
+    cache.access(0x040, false, NEXT_LEVEL_LATENCY);
+    assert!(cache.contains(0x040));
+}
+
+// ══════════════════════════════════════════════════════════
+// 16. Invalidation
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn invalidate_line_drops_only_that_line() {
+    let mut cache = CacheSim::new(&test_config());
+    cache.access(0x000, false, NEXT_LEVEL_LATENCY);
+    cache.access(0x040, false, NEXT_LEVEL_LATENCY);
+    assert!(cache.invalidate_line(0x008), "any address in the line hits");
+    assert!(!cache.contains(0x000));
+    assert!(cache.contains(0x040));
+    assert!(!cache.invalidate_line(0x000), "already gone");
+}
+
+#[test]
+fn invalidate_all_drops_clean_lines() {
+    let mut cache = CacheSim::new(&test_config());
+    cache.access(0x000, false, NEXT_LEVEL_LATENCY);
+    cache.flush();
+    assert!(cache.contains(0x000), "flush keeps clean lines");
+    cache.invalidate_all();
+    assert!(!cache.contains(0x000));
+}
@@ -1,13 +1,13 @@
 //! Cache Replacement Policy Tests.
 //!
-//! Verifies the victim selection logic for LRU, FIFO, PLRU, MRU, and Random policies.
+//! Verifies the victim selection logic for LRU, FIFO, PLRU, MRU, Random, and DIP policies.
 //! Each policy implements `ReplacementPolicy` with `update(set, way)` and
 //! `get_victim(set) -> usize`. Tests exercise them in isolation with edge cases.
 //!
 //! Reference: Phase 3 — Memory Subsystem Verification.
 
 use rvsim_core::core::units::cache::policies::{
-    FifoPolicy, LruPolicy, MruPolicy, PlruPolicy, RandomPolicy, ReplacementPolicy,
+    DipPolicy, FifoPolicy, LruPolicy, MruPolicy, PlruPolicy, RandomPolicy, ReplacementPolicy,
 };
 
 // ══════════════════════════════════════════════════════════
@@ -330,7 +330,7 @@ fn mru_opposite_of_lru() {
 #[test]
 fn random_victim_always_in_range() {
     let ways = 4;
-    let mut policy = RandomPolicy::new(1, ways);
+    let mut policy = RandomPolicy::new(1, ways, 1);
 
     for _ in 0..200 {
         let victim = policy.get_victim(0);
@@ -347,7 +347,7 @@ fn random_victim_always_in_range() {
 #[test]
 fn random_victim_various_way_counts() {
     for ways in [1, 2, 3, 4, 8, 16] {
-        let mut policy = RandomPolicy::new(1, ways);
+        let mut policy = RandomPolicy::new(1, ways, 1);
         for _ in 0..50 {
             let victim = policy.get_victim(0);
             assert!(
@@ -363,7 +363,7 @@ fn random_victim_various_way_counts() {
 /// Random: update is a no-op; victims should still change (LFSR advances on get_victim).
 #[test]
 fn random_update_is_noop() {
-    let mut policy = RandomPolicy::new(1, 4);
+    let mut policy = RandomPolicy::new(1, 4, 1);
 
     let v1 = policy.get_victim(0);
     policy.update(0, v1);
@@ -377,7 +377,7 @@ fn random_update_is_noop() {
 /// Random: produces more than one distinct value over many calls (not stuck).
 #[test]
 fn random_not_stuck() {
-    let mut policy = RandomPolicy::new(1, 8);
+    let mut policy = RandomPolicy::new(1, 8, 1);
     let mut seen = std::collections::HashSet::new();
     for _ in 0..100 {
         seen.insert(policy.get_victim(0));
@@ -388,3 +388,151 @@ fn random_not_stuck() {
         seen.len()
     );
 }
+
+/// Random: the same seed reproduces the same victim sequence.
+#[test]
+fn random_same_seed_is_deterministic() {
+    let victims = |seed| {
+        let mut policy = RandomPolicy::new(1, 8, seed);
+        (0..64).map(|_| policy.get_victim(0)).collect::<Vec<_>>()
+    };
+    assert_eq!(victims(42), victims(42));
+    assert_ne!(victims(42), victims(43), "seed changes the sequence");
+}
+
+/// Random: a zero seed does not lock xorshift at zero.
+#[test]
+fn random_zero_seed_not_stuck() {
+    let mut policy = RandomPolicy::new(1, 8, 0);
+    let seen: std::collections::HashSet<_> = (0..100).map(|_| policy.get_victim(0)).collect();
+    assert!(seen.len() > 1);
+}
+
+// ══════════════════════════════════════════════════════════
+// 6. DIP Policy
+// ══════════════════════════════════════════════════════════
+
+/// DIP: with 64 sets there are 16 leaders per policy, one group every four
+/// sets: set 0 leads for LRU and set 2 for BIP.
+const DIP_SETS: usize = 64;
+const DIP_LRU_LEADER: usize = 0;
+const DIP_BIP_LEADER: usize = 2;
+const DIP_FOLLOWER: usize = 1;
+
+/// Returns a 4-way DIP policy whose sets have all been filled once.
+fn warm_dip() -> DipPolicy {
+    let mut policy = DipPolicy::new(DIP_SETS, 4);
+    for set in 0..DIP_SETS {
+        for way in 0..4 {
+            policy.insert(set, way);
+        }
+    }
+    policy
+}
+
+/// DIP: the first fill of each set inserts at MRU even in a BIP leader, so
+/// a cold set uses all of its ways.
+#[test]
+fn dip_cold_fill_uses_every_way() {
+    let mut policy = DipPolicy::new(DIP_SETS, 4);
+    let mut victims = Vec::new();
+    for _ in 0..4 {
+        let victim = policy.get_victim(DIP_BIP_LEADER);
+        policy.insert(DIP_BIP_LEADER, victim);
+        victims.push(victim);
+    }
+    victims.sort();
+    assert_eq!(victims, [0, 1, 2, 3]);
+}
+
+/// DIP: LRU leaders insert at MRU, so the new line is not the next victim.
+#[test]
+fn dip_lru_leader_inserts_at_mru() {
+    let mut policy = warm_dip();
+    let victim = policy.get_victim(DIP_LRU_LEADER);
+    policy.insert(DIP_LRU_LEADER, victim);
+    assert_ne!(policy.get_victim(DIP_LRU_LEADER), victim);
+}
+
+/// DIP: BIP leaders insert at LRU, so the same way is evicted again until
+/// the line is re-referenced.
+#[test]
+fn dip_bip_leader_inserts_at_lru() {
+    let mut policy = warm_dip();
+    let victim = policy.get_victim(DIP_BIP_LEADER);
+    policy.insert(DIP_BIP_LEADER, victim);
+    assert_eq!(policy.get_victim(DIP_BIP_LEADER), victim);
+
+    policy.update(DIP_BIP_LEADER, victim);
+    assert_ne!(
+        policy.get_victim(DIP_BIP_LEADER),
+        victim,
+        "a hit promotes to MRU"
+    );
+}
+
+/// DIP: BIP still promotes one insertion in 32 so new lines can stick.
+#[test]
+fn dip_bip_occasionally_inserts_at_mru() {
+    let mut policy = warm_dip();
+    let promoted = (0..64)
+        .filter(|_| {
+            let victim = policy.get_victim(DIP_BIP_LEADER);
+            policy.insert(DIP_BIP_LEADER, victim);
+            policy.get_victim(DIP_BIP_LEADER) != victim
+        })
+        .count();
+    assert_eq!(promoted, 2);
+}
+
+/// DIP: misses in LRU leaders push followers to BIP, misses in BIP leaders
+/// pull them back.
+#[test]
+fn dip_psel_steers_followers() {
+    let mut policy = warm_dip();
+    assert!(!policy.followers_use_bip(), "starts as LRU");
+
+    for _ in 0..4 {
+        policy.get_victim(DIP_LRU_LEADER);
+    }
+    assert!(policy.followers_use_bip());
+    let victim = policy.get_victim(DIP_FOLLOWER);
+    policy.insert(DIP_FOLLOWER, victim);
+    assert_eq!(policy.get_victim(DIP_FOLLOWER), victim, "follower uses BIP");
+
+    for _ in 0..8 {
+        policy.get_victim(DIP_BIP_LEADER);
+    }
+    assert!(!policy.followers_use_bip());
+    policy.get_victim(DIP_FOLLOWER);
+    assert!(!policy.followers_use_bip(), "follower misses do not vote");
+}
+
+/// DIP: PSEL saturates at 10 bits.
+#[test]
+fn dip_psel_saturates() {
+    let mut policy = DipPolicy::new(DIP_SETS, 2);
+    for _ in 0..5_000 {
+        policy.get_victim(DIP_LRU_LEADER);
+    }
+    assert_eq!(policy.psel(), 1023);
+    for _ in 0..5_000 {
+        policy.get_victim(DIP_BIP_LEADER);
+    }
+    assert_eq!(policy.psel(), 0);
+}
+
+/// DIP: a cache too small to duel behaves as LRU.
+#[test]
+fn dip_tiny_cache_is_lru() {
+    let mut dip = DipPolicy::new(2, 4);
+    let mut lru = LruPolicy::new(2, 4);
+    for step in 0..40 {
+        let set = step % 2;
+        assert_eq!(dip.get_victim(set), lru.get_victim(set));
+        let way = (step * 7) % 4;
+        dip.insert(set, way);
+        lru.insert(set, way);
+    }
+    assert_eq!(dip.psel(), 511);
+}
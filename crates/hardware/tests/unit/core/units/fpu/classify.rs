@@ -0,0 +1,112 @@
+//! FCLASS Tests.
+//!
+//! Verifies the 10-bit classification mask for every category in single
+//! and double precision, that an improperly NaN-boxed single operand
+//! classifies as the canonical quiet NaN, and that `fclass.s`/`fclass.d`
+//! deliver the mask to the integer register through the pipeline.
+
+use crate::common::harness::TestContext;
+use rvsim_core::config::Config;
+use rvsim_core::core::pipeline::signals::AluOp;
+use rvsim_core::core::units::fpu::Fpu;
+
+const NEG_INF: u64 = 1 << 0;
+const NEG_NORMAL: u64 = 1 << 1;
+const NEG_SUBNORMAL: u64 = 1 << 2;
+const NEG_ZERO: u64 = 1 << 3;
+const POS_ZERO: u64 = 1 << 4;
+const POS_SUBNORMAL: u64 = 1 << 5;
+const POS_NORMAL: u64 = 1 << 6;
+const POS_INF: u64 = 1 << 7;
+const SIGNALING_NAN: u64 = 1 << 8;
+const QUIET_NAN: u64 = 1 << 9;
+
+fn fclass_s(bits: u32) -> u64 {
+    Fpu::execute(
+        AluOp::FClass,
+        0xFFFF_FFFF_0000_0000 | bits as u64,
+        0,
+        0,
+        true,
+    )
+}
+
+fn fclass_d(bits: u64) -> u64 {
+    Fpu::execute(AluOp::FClass, bits, 0, 0, false)
+}
+
+/// `fclass.{s,d} rd, rs1`: funct7 `0b111000x`, funct3 `0b001`.
+fn fclass(rd: u32, rs1: u32, double: bool) -> u32 {
+    let funct7 = if double { 0b111_0001 } else { 0b111_0000 };
+    (funct7 << 25) | (rs1 << 15) | (0b001 << 12) | (rd << 7) | 0x53
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Categories
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn single_precision_categories() {
+    for (bits, class) in [
+        (0xFF80_0000, NEG_INF),
+        (0xBF80_0000, NEG_NORMAL),
+        (0x8000_0001, NEG_SUBNORMAL),
+        (0x8000_0000, NEG_ZERO),
+        (0x0000_0000, POS_ZERO),
+        (0x0000_0001, POS_SUBNORMAL),
+        (0x3F80_0000, POS_NORMAL),
+        (0x7F80_0000, POS_INF),
+        (0x7F80_0001, SIGNALING_NAN),
+        (0x7FC0_0000, QUIET_NAN),
+    ] {
+        assert_eq!(fclass_s(bits), class, "{bits:#010x}");
+    }
+}
+
+#[test]
+fn double_precision_categories() {
+    for (bits, class) in [
+        (0xFFF0_0000_0000_0000, NEG_INF),
+        (0xBFF0_0000_0000_0000, NEG_NORMAL),
+        (0x8000_0000_0000_0001, NEG_SUBNORMAL),
+        (0x8000_0000_0000_0000, NEG_ZERO),
+        (0x0000_0000_0000_0000, POS_ZERO),
+        (0x0000_0000_0000_0001, POS_SUBNORMAL),
+        (0x3FF0_0000_0000_0000, POS_NORMAL),
+        (0x7FF0_0000_0000_0000, POS_INF),
+        (0x7FF0_0000_0000_0001, SIGNALING_NAN),
+        (0x7FF8_0000_0000_0000, QUIET_NAN),
+    ] {
+        assert_eq!(fclass_d(bits), class, "{bits:#018x}");
+    }
+}
+
+#[test]
+fn unboxed_single_is_quiet_nan() {
+    let one = 0x3F80_0000u64;
+    assert_eq!(Fpu::execute(AluOp::FClass, one, 0, 0, true), QUIET_NAN);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Pipeline
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn fclass_writes_mask_to_integer_register() {
+    for functional in [false, true] {
+        let mut tc = TestContext::program(
+            &Config::default(),
+            &[fclass(5, 1, true), fclass(6, 2, false), fclass(7, 3, false)],
+        );
+        tc.cpu_mut().set_functional(functional);
+        tc.cpu_mut().regs.write_f(1, (-f64::INFINITY).to_bits());
+        tc.cpu_mut()
+            .regs
+            .write_f(2, 0xFFFF_FFFF_0000_0000 | 0x8000_0001);
+        tc.cpu_mut().regs.write_f(3, 1.0f64.to_bits());
+        tc.run(1_000);
+        assert_eq!(tc.get_reg(5), NEG_INF, "functional={functional}");
+        assert_eq!(tc.get_reg(6), NEG_SUBNORMAL, "functional={functional}");
+        assert_eq!(tc.get_reg(7), QUIET_NAN, "unboxed single");
+    }
+}
@@ -1,3 +1,5 @@
+pub mod classify;
 pub mod exception_flags;
+pub mod moves;
 pub mod nan_handling;
 pub mod rounding_modes;
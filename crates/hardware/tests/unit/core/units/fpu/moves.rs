@@ -0,0 +1,56 @@
+//! FP Move Tests.
+//!
+//! Verifies that moves between integer and FP registers dispatch on the
+//! format: `fmv.x.d`/`fmv.d.x` copy all 64 bits, `fmv.w.x` NaN-boxes, and
+//! `fmv.x.w` sign-extends the low 32 bits, in detailed and functional mode.
+
+use crate::common::harness::TestContext;
+
+/// `fmv.x.w rd, rs1`.
+fn fmv_x_w(rd: u32, rs1: u32) -> u32 {
+    (0b111_0000 << 25) | (rs1 << 15) | (rd << 7) | 0x53
+}
+
+/// `fmv.x.d rd, rs1`.
+fn fmv_x_d(rd: u32, rs1: u32) -> u32 {
+    (0b111_0001 << 25) | (rs1 << 15) | (rd << 7) | 0x53
+}
+
+/// `fmv.d.x rd, rs1`.
+fn fmv_d_x(rd: u32, rs1: u32) -> u32 {
+    (0b111_1001 << 25) | (rs1 << 15) | (rd << 7) | 0x53
+}
+
+/// `fmv.w.x rd, rs1`.
+fn fmv_w_x(rd: u32, rs1: u32) -> u32 {
+    (0b111_1000 << 25) | (rs1 << 15) | (rd << 7) | 0x53
+}
+
+#[test]
+fn fp_moves_dispatch_on_format() {
+    let pattern = 0xC008_0000_8765_4321u64;
+    for functional in [false, true] {
+        let mut tc = TestContext::new()
+            .with_memory(0x1000, 0x8000_0000)
+            .load_program(
+                0x8000_0000,
+                &[fmv_d_x(1, 5), fmv_x_d(6, 1), fmv_w_x(2, 5), fmv_x_w(7, 1)],
+            );
+        tc.cpu_mut().set_functional(functional);
+        tc.set_reg(5, pattern);
+        tc.run(1_000);
+        let label = format!("functional={functional}");
+        assert_eq!(tc.cpu().regs.read_f(1), pattern, "fmv.d.x: {label}");
+        assert_eq!(tc.get_reg(6), pattern, "fmv.x.d: {label}");
+        assert_eq!(
+            tc.cpu().regs.read_f(2),
+            0xFFFF_FFFF_8765_4321,
+            "fmv.w.x boxes: {label}"
+        );
+        assert_eq!(
+            tc.get_reg(7),
+            0xFFFF_FFFF_8765_4321,
+            "fmv.x.w sign-extends: {label}"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::common::harness::TestContext;
 use rvsim_core::core::pipeline::signals::AluOp;
 use rvsim_core::core::units::fpu::Fpu;
 
@@ -91,3 +92,46 @@ fn test_f64_nan_boxing_not_applicable() {
     let res = Fpu::execute(AluOp::FAdd, d_val1, d_val2, 0, false);
     assert_eq!(f64::from_bits(res), 3.0);
 }
+
+/// `fcvt.d.s rd, rs1` (RNE).
+fn fcvt_d_s(rd: u32, rs1: u32) -> u32 {
+    (0b010_0001 << 25) | (rs1 << 15) | (rd << 7) | 0x53
+}
+
+/// `fadd.s rd, rs1, rs2` (RNE).
+fn fadd_s(rd: u32, rs1: u32, rs2: u32) -> u32 {
+    (rs2 << 20) | (rs1 << 15) | (rd << 7) | 0x53
+}
+
+/// `fmv.x.w rd, rs1`.
+fn fmv_x_w(rd: u32, rs1: u32) -> u32 {
+    (0b111_0000 << 25) | (rs1 << 15) | (rd << 7) | 0x53
+}
+
+#[test]
+fn unboxed_single_operand_reads_as_canonical_nan_in_pipeline() {
+    for functional in [false, true] {
+        let mut tc = TestContext::new()
+            .with_memory(0x1000, 0x8000_0000)
+            .load_program(
+                0x8000_0000,
+                &[fcvt_d_s(2, 1), fadd_s(3, 1, 1), fmv_x_w(5, 1)],
+            );
+        tc.cpu_mut().set_functional(functional);
+        // 1.0f32 with the upper half clear: not a valid single.
+        tc.cpu_mut().regs.write_f(1, 0x3F80_0000);
+        tc.run(1_000);
+        assert_eq!(
+            tc.cpu().regs.read_f(2),
+            0x7FF8_0000_0000_0000,
+            "fcvt.d.s: functional={functional}"
+        );
+        assert_eq!(
+            tc.cpu().regs.read_f(3),
+            0xFFFF_FFFF_7FC0_0000,
+            "fadd.s: functional={functional}"
+        );
+        // FMV.X.W moves the raw low bits; boxing is not checked.
+        assert_eq!(tc.get_reg(5), 0x3F80_0000);
+    }
+}
@@ -0,0 +1,53 @@
+//! Data Byte Order Unit Tests.
+//!
+//! Verifies byte reversal within each access width and sign/zero extension
+//! of loaded values.
+
+use rvsim_core::core::pipeline::signals::MemWidth;
+use rvsim_core::core::units::lsu::endian;
+
+// ══════════════════════════════════════════════════════════
+// 1. Byte reversal
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn swap_reverses_within_width() {
+    let v = 0x1122_3344_5566_7788;
+    assert_eq!(endian::swap(v, MemWidth::Byte), 0x88);
+    assert_eq!(endian::swap(v, MemWidth::Half), 0x8877);
+    assert_eq!(endian::swap(v, MemWidth::Word), 0x8877_6655);
+    assert_eq!(endian::swap(v, MemWidth::Double), 0x8877_6655_4433_2211);
+}
+
+#[test]
+fn swap_is_an_involution() {
+    for width in [MemWidth::Half, MemWidth::Word, MemWidth::Double] {
+        let v = endian::swap(0xDEAD_BEEF_CAFE_F00D, width);
+        assert_eq!(endian::swap(endian::swap(v, width), width), v);
+    }
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Extension
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn extend_sign_extends_signed_loads() {
+    assert_eq!(endian::extend(0x80, MemWidth::Byte, true), u64::MAX << 7);
+    assert_eq!(endian::extend(0x8000, MemWidth::Half, true), u64::MAX << 15);
+    assert_eq!(
+        endian::extend(0xFFFF_FFFF_8000_0000, MemWidth::Word, true),
+        u64::MAX << 31
+    );
+}
+
+#[test]
+fn extend_zero_extends_unsigned_loads() {
+    assert_eq!(endian::extend(0xFFFF_FF80, MemWidth::Byte, false), 0x80);
+    assert_eq!(endian::extend(0xFFFF_8000, MemWidth::Half, false), 0x8000);
+    assert_eq!(
+        endian::extend(0xFFFF_FFFF_8000_0000, MemWidth::Word, false),
+        0x8000_0000
+    );
+    assert_eq!(endian::extend(u64::MAX, MemWidth::Double, true), u64::MAX);
+}
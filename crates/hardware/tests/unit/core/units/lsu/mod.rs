@@ -1,3 +1,4 @@
 pub mod atomic;
+pub mod endian;
 pub mod ordering;
 pub mod unaligned;
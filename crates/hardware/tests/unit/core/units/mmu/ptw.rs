@@ -7,6 +7,7 @@
 //! - Accessed/Dirty bit updates
 //! - Canonical address checks
 //! - Bare mode bypass
+//! - Global mappings inherited from non-leaf PTEs
 
 use crate::common::harness::TestContext;
 use rvsim_core::common::{AccessType, Trap, VirtAddr};
@@ -29,7 +30,6 @@ const R: u64 = 1 << 1;
 const W: u64 = 1 << 2;
 const X: u64 = 1 << 3;
 const U: u64 = 1 << 4;
-#[allow(dead_code)]
 const G: u64 = 1 << 5;
 const A: u64 = 1 << 6;
 const D: u64 = 1 << 7;
@@ -60,7 +60,7 @@ fn setup_mmu() -> (Mmu, Csrs, TestContext) {
 /// `base_ppn` is the PPN of the page table at this level.
 fn write_pte(bus: &mut Bus, base_ppn: u64, vpn_index: u64, pte: u64) {
     let addr = (base_ppn << 12) + (vpn_index * 8);
-    bus.write_u64(addr, pte);
+    bus.write_u64(addr, pte).unwrap();
 }
 
 // ══════════════════════════════════════════════════════════
@@ -314,6 +314,68 @@ fn misaligned_superpage_causes_fault() {
     );
 }
 
+/// Walks 0x8000_0000 for a read, with `pte` as the level-2 entry and, when
+/// it is a pointer, a valid 4 KiB leaf below it.
+fn walk_with_root_pte(pte: u64) -> Option<Trap> {
+    let (mut mmu, csrs, mut tc) = setup_mmu();
+    let bus = &mut tc.cpu_mut().bus.bus;
+    let l2_idx = (0x8000_0000 >> 30) & 0x1FF;
+    let l1_ppn = ROOT_PPN + 1;
+    let l0_ppn = ROOT_PPN + 2;
+    write_pte(bus, ROOT_PPN, l2_idx, pte);
+    write_pte(bus, l1_ppn, 0, make_pte(l0_ppn, 0));
+    write_pte(bus, l0_ppn, 0, make_pte(ROOT_PPN + 10, R | W | A | D));
+    mmu.translate(
+        VirtAddr::new(0x8000_0000),
+        AccessType::Read,
+        PrivilegeMode::Supervisor,
+        &csrs,
+        bus,
+    )
+    .trap
+}
+
+#[test]
+fn reserved_high_bits_cause_fault() {
+    let leaf = make_pte(ROOT_PPN + 0x40000, R | W | X | A | D);
+    assert_eq!(walk_with_root_pte(leaf), None);
+    for bit in [54, 60, 61, 62, 63] {
+        assert_eq!(
+            walk_with_root_pte(leaf | (1 << bit)),
+            Some(Trap::LoadPageFault(0x8000_0000)),
+            "bit {bit}"
+        );
+    }
+    let pointer = make_pte(ROOT_PPN + 1, 0);
+    assert_eq!(
+        walk_with_root_pte(pointer | (1 << 63)),
+        Some(Trap::LoadPageFault(0x8000_0000)),
+        "reserved bits in a pointer"
+    );
+}
+
+#[test]
+fn write_without_read_is_reserved() {
+    assert_eq!(
+        walk_with_root_pte(make_pte(ROOT_PPN + 0x40000, W | X | A | D)),
+        Some(Trap::LoadPageFault(0x8000_0000)),
+        "W without R"
+    );
+}
+
+#[test]
+fn non_leaf_with_a_d_or_u_causes_fault() {
+    let pointer = make_pte(ROOT_PPN + 1, 0);
+    assert_eq!(walk_with_root_pte(pointer), None);
+    for bit in [A, D, U] {
+        assert_eq!(
+            walk_with_root_pte(pointer | bit),
+            Some(Trap::LoadPageFault(0x8000_0000)),
+            "bit {bit:#x}"
+        );
+    }
+}
+
 // ══════════════════════════════════════════════════════════
 // 5. Access Permissions & A/D Bits
 // ══════════════════════════════════════════════════════════
@@ -341,7 +403,7 @@ fn write_to_clean_page_sets_dirty() {
     assert!(res.trap.is_none(), "Trap: {:?}", res.trap);
 
     // Check if Dirty bit was updated in memory
-    let new_pte = bus.read_u64(ROOT_PPN << 12 | (l2_idx * 8));
+    let new_pte = bus.read_u64(ROOT_PPN << 12 | (l2_idx * 8)).unwrap();
     assert_eq!(new_pte & D, D, "Dirty bit should be set");
 }
 
@@ -368,7 +430,7 @@ fn read_from_unaccessed_page_sets_accessed() {
     assert!(res.trap.is_none(), "Trap: {:?}", res.trap);
 
     // Check if Accessed bit was updated in memory
-    let new_pte = bus.read_u64(ROOT_PPN << 12 | (l2_idx * 8));
+    let new_pte = bus.read_u64(ROOT_PPN << 12 | (l2_idx * 8)).unwrap();
     assert_eq!(new_pte & A, A, "Accessed bit should be set");
 }
 
@@ -557,3 +619,121 @@ fn non_canonical_address_faults() {
         res.trap
     );
 }
+
+// ══════════════════════════════════════════════════════════
+// 8. Svade (software-managed A/D bits)
+// ══════════════════════════════════════════════════════════
+
+/// Maps the 1 GiB page at 0x8000_0000 with `perms` and returns the PTE address.
+fn map_gigapage(bus: &mut Bus, perms: u64) -> u64 {
+    let l2_idx = (0x8000_0000 >> 30) & 0x1FF;
+    write_pte(bus, ROOT_PPN, l2_idx, make_pte(ROOT_PPN + 0x40000, perms));
+    (ROOT_PPN << 12) | (l2_idx * 8)
+}
+
+fn translate(mmu: &mut Mmu, csrs: &Csrs, bus: &mut Bus, access: AccessType) -> Option<Trap> {
+    mmu.translate(
+        VirtAddr::new(0x8000_0000),
+        access,
+        PrivilegeMode::Supervisor,
+        csrs,
+        bus,
+    )
+    .trap
+}
+
+#[test]
+fn svade_faults_on_clear_accessed_bit() {
+    let (mut mmu, csrs, mut tc) = setup_mmu();
+    mmu.svade = true;
+    let bus = &mut tc.cpu_mut().bus.bus;
+    let pte_addr = map_gigapage(bus, R | W | X);
+
+    for (access, trap) in [
+        (AccessType::Read, Trap::LoadPageFault(0x8000_0000)),
+        (AccessType::Write, Trap::StorePageFault(0x8000_0000)),
+        (AccessType::Fetch, Trap::InstructionPageFault(0x8000_0000)),
+    ] {
+        assert_eq!(translate(&mut mmu, &csrs, bus, access), Some(trap));
+    }
+    assert_eq!(
+        bus.read_u64(pte_addr).unwrap() & (A | D),
+        0,
+        "PTE untouched"
+    );
+}
+
+#[test]
+fn svade_faults_on_store_to_clean_page() {
+    let (mut mmu, csrs, mut tc) = setup_mmu();
+    mmu.svade = true;
+    let bus = &mut tc.cpu_mut().bus.bus;
+    let pte_addr = map_gigapage(bus, R | W | X | A);
+
+    assert_eq!(translate(&mut mmu, &csrs, bus, AccessType::Read), None);
+    // The read cached the clean translation; the store must still fault.
+    assert_eq!(
+        translate(&mut mmu, &csrs, bus, AccessType::Write),
+        Some(Trap::StorePageFault(0x8000_0000))
+    );
+    assert_eq!(bus.read_u64(pte_addr).unwrap() & D, 0);
+
+    // Once software sets D, the store goes through.
+    bus.write_u64(pte_addr, make_pte(ROOT_PPN + 0x40000, R | W | X | A | D))
+        .unwrap();
+    assert_eq!(translate(&mut mmu, &csrs, bus, AccessType::Write), None);
+}
+
+#[test]
+fn svade_config_reaches_mmu() {
+    let mut config = rvsim_core::config::Config::default();
+    assert!(!config.memory.svade, "hardware A/D update by default");
+    config.memory.svade = true;
+    let tc = TestContext::with_config(&config);
+    assert!(tc.cpu().mmu.svade);
+}
+
+// ══════════════════════════════════════════════════════════
+// 9. Global Mappings
+// ══════════════════════════════════════════════════════════
+
+/// Walks a 4 KiB read of 0x4000_1234 under ASID 1, with `root_perms` on the
+/// level-2 pointer and a non-global leaf, then flushes ASID 1 from the D-TLB.
+/// Returns whether the translation survived the flush.
+fn survives_asid_flush(root_perms: u64) -> bool {
+    let (mut mmu, mut csrs, mut tc) = setup_mmu();
+    let satp = (csr::SATP_MODE_SV39 << 60) | (1 << csr::SATP_ASID_SHIFT) | ROOT_PPN;
+    csrs.write(csr::SATP, satp);
+    let bus = &mut tc.cpu_mut().bus.bus;
+
+    let l1_table_ppn = ROOT_PPN + 1;
+    let l0_table_ppn = ROOT_PPN + 2;
+    write_pte(bus, ROOT_PPN, 1, make_pte(l1_table_ppn, root_perms));
+    write_pte(bus, l1_table_ppn, 0, make_pte(l0_table_ppn, 0));
+    write_pte(bus, l0_table_ppn, 1, make_pte(ROOT_PPN + 10, R | A));
+
+    let res = mmu.translate(
+        VirtAddr::new(0x4000_1234),
+        AccessType::Read,
+        PrivilegeMode::Supervisor,
+        &csrs,
+        bus,
+    );
+    assert!(res.trap.is_none(), "Trap: {:?}", res.trap);
+
+    mmu.dtlb.flush_asid(1);
+    mmu.dtlb.lookup(0x4_0001, 2).is_some()
+}
+
+#[test]
+fn global_pointer_makes_leaf_global() {
+    assert!(
+        survives_asid_flush(G),
+        "G on a pointer covers every mapping below it"
+    );
+}
+
+#[test]
+fn non_global_walk_is_asid_private() {
+    assert!(!survives_asid_flush(0));
+}
@@ -0,0 +1,68 @@
+//! Markov Prefetcher Tests.
+//!
+//! Verifies that the Markov prefetcher:
+//! - Stays idle on first-time misses and on hits.
+//! - Learns the successor of a miss and prefetches it on a repeat miss.
+//! - Orders multiple successors by observed frequency.
+
+use rvsim_core::core::units::prefetch::MarkovPrefetcher;
+use rvsim_core::core::units::prefetch::Prefetcher;
+
+// ══════════════════════════════════════════════════════════
+// 1. Cold start — no prefetching
+// ══════════════════════════════════════════════════════════
+
+/// Misses with no recorded history never trigger a prefetch.
+#[test]
+fn no_prefetch_without_history() {
+    let mut pf = MarkovPrefetcher::new(64, 64, 1);
+    assert!(pf.observe(0x1000, false).is_empty());
+    assert!(pf.observe(0x5340, false).is_empty());
+}
+
+/// Hits neither train nor trigger the prefetcher.
+#[test]
+fn hits_are_ignored() {
+    let mut pf = MarkovPrefetcher::new(64, 64, 1);
+    pf.observe(0x1000, false);
+    pf.observe(0x5340, false);
+    assert!(pf.observe(0x1000, true).is_empty());
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Correlation learning
+// ══════════════════════════════════════════════════════════
+
+/// An irregular miss sequence is replayed once it repeats.
+#[test]
+fn repeat_miss_prefetches_successor() {
+    let mut pf = MarkovPrefetcher::new(64, 64, 1);
+    for addr in [0x1000, 0x9F80, 0x2440] {
+        pf.observe(addr, false);
+    }
+
+    assert_eq!(pf.observe(0x1000, false), vec![0x9F80]);
+    assert_eq!(pf.observe(0x9F80, false), vec![0x2440]);
+}
+
+/// Prefetch targets are line aligned regardless of the access offset.
+#[test]
+fn successors_are_line_aligned() {
+    let mut pf = MarkovPrefetcher::new(64, 64, 1);
+    pf.observe(0x1008, false);
+    pf.observe(0x3F7C, false);
+    assert_eq!(pf.observe(0x1030, false), vec![0x3F40]);
+}
+
+/// With degree > 1 the most frequent successor is returned first.
+#[test]
+fn successors_ordered_by_frequency() {
+    let mut pf = MarkovPrefetcher::new(64, 64, 2);
+    // Addresses map to distinct table indices ((addr >> 6) & 63).
+    // 0x1000 -> 0x2040 once, 0x1000 -> 0x3080 twice.
+    for addr in [0x1000, 0x2040, 0x1000, 0x3080, 0x1000, 0x3080] {
+        pf.observe(addr, false);
+    }
+
+    assert_eq!(pf.observe(0x1000, false), vec![0x3080, 0x2040]);
+}
@@ -1,3 +1,4 @@
+pub mod markov;
 pub mod next_line;
 pub mod stream;
 pub mod stride;
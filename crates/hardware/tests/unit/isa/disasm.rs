@@ -299,3 +299,21 @@ fn disasm_unknown() {
         text
     );
 }
+
+// ══════════════════════════════════════════════════════════
+// 13. `isa::disasm` entry point
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn disasm_reexport_matches_disassemble() {
+    assert_eq!(
+        rvsim_core::isa::disasm(0x00A0_0513),
+        disassemble(0x00A0_0513)
+    );
+}
+
+#[test]
+fn disasm_compressed_renders_expansion() {
+    // C.LI a0, 10 expands to ADDI a0, zero, 10
+    assert_eq!(rvsim_core::isa::disasm(0x4529), "addi a0, zero, 10");
+}
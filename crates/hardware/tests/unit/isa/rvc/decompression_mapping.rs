@@ -363,11 +363,13 @@ fn rvc_c_slli() {
 }
 
 #[test]
-fn rvc_c_slli_rd0_illegal() {
-    // C.SLLI with rd=0 is reserved
+fn rvc_c_slli_rd0_is_hint() {
+    // C.SLLI with rd=0 is a HINT: it expands and writes nothing
     let cinst: u16 = 0b0000_0000_0001_0010;
-    let expanded = expand(cinst);
-    assert_eq!(expanded, 0);
+    let d = expand_and_decode(cinst);
+    assert_eq!(d.opcode, i_op::OP_IMM);
+    assert_eq!(d.funct3, i_f3::SLL);
+    assert_eq!(d.rd, 0);
 }
 
 #[test]
@@ -559,3 +561,70 @@ fn rvc_all_register_mappings_q0_rs1() {
         );
     }
 }
+
+// ══════════════════════════════════════════════════════════
+// Reserved encodings and compiler output
+// ══════════════════════════════════════════════════════════
+
+/// Returns whether the RV64C encoding `c` is reserved (must trap).
+fn is_reserved(c: u16) -> bool {
+    let op = c & 0x3;
+    let f3 = (c >> 13) & 0x7;
+    let rd = (c >> 7) & 0x1F;
+    let rs2 = (c >> 2) & 0x1F;
+    let bit12 = (c >> 12) & 1;
+    match (op, f3) {
+        (0b00, 0b000) => (c >> 5) & 0xFF == 0,
+        (0b00, 0b100) => true,
+        (0b01, 0b001) => rd == 0,
+        (0b01, 0b011) => bit12 == 0 && rs2 == 0,
+        (0b01, 0b100) => (c >> 10) & 0x3 == 0x3 && bit12 == 1 && (c >> 5) & 0x3 >= 2,
+        (0b10, 0b010) | (0b10, 0b011) => rd == 0,
+        (0b10, 0b100) => bit12 == 0 && rd == 0 && rs2 == 0,
+        _ => false,
+    }
+}
+
+#[test]
+fn rvc_reserved_encodings_exhaustive() {
+    for c in 0..=u16::MAX {
+        if c & 0x3 == 0x3 {
+            continue;
+        }
+        assert_eq!(
+            expand(c) == 0,
+            is_reserved(c),
+            "{c:#06x}: reserved={} but expand={:#010x}",
+            is_reserved(c),
+            expand(c)
+        );
+    }
+}
+
+#[test]
+fn rvc_valid_encodings_expand_to_32_bit_form() {
+    for c in (0..=u16::MAX).filter(|c| c & 0x3 != 0x3 && !is_reserved(*c)) {
+        assert_eq!(
+            expand(c) & 0x3,
+            0x3,
+            "{c:#06x} expanded to a non-32-bit form"
+        );
+    }
+}
+
+#[test]
+fn rvc_c_addi16sp_compiler_encodings() {
+    // Prologue/epilogue encodings as emitted by GCC/LLVM for -march=rv64gc
+    for (cinst, imm) in [
+        (0x7139u16, -64),
+        (0x6121, 64),
+        (0x717d, -16),
+        (0x7101, -512),
+        (0x6145, 48),
+    ] {
+        let d = expand_and_decode(cinst);
+        assert_eq!(d.opcode, i_op::OP_IMM, "{cinst:#06x}");
+        assert_eq!((d.rd, d.rs1), (2, 2), "{cinst:#06x}");
+        assert_eq!(d.imm, imm, "{cinst:#06x}");
+    }
+}
@@ -65,7 +65,7 @@ fn test_setup_kernel_load_without_opensbi() {
     let config = Config::default();
 
     // Setup without OpenSBI (default case when fw_jump.bin doesn't exist)
-    loader::setup_kernel_load(&mut cpu, &config, "", None, None);
+    loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
 
     // Verify PC is set to RAM base
     assert_eq!(cpu.pc, config.system.ram_base);
@@ -90,7 +90,7 @@ fn test_setup_kernel_load_dtb_address() {
     let mut cpu = create_test_cpu();
     let config = Config::default();
 
-    loader::setup_kernel_load(&mut cpu, &config, "", None, None);
+    loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
 
     // DTB should be loaded at RAM base + 0x2200000
     let expected_dtb_addr = config.system.ram_base + 0x2200000;
@@ -107,11 +107,18 @@ fn test_setup_kernel_load_with_dtb_file() {
     let temp_dtb = create_temp_binary(&dtb_data);
     let dtb_path = temp_dtb.path().to_str().unwrap();
 
-    loader::setup_kernel_load(&mut cpu, &config, "", Some(dtb_path.to_string()), None);
+    loader::setup_kernel_load(
+        &mut cpu,
+        &config,
+        "",
+        Some(dtb_path.to_string()),
+        None,
+        None,
+    );
 
     // Verify DTB was loaded into memory at expected address
     let dtb_addr = config.system.ram_base + 0x2200000;
-    let loaded_byte = cpu.bus.bus.read_u8(dtb_addr);
+    let loaded_byte = cpu.bus.bus.read_u8(dtb_addr).unwrap();
     assert_eq!(loaded_byte, 0xd0);
 }
 
@@ -120,7 +127,7 @@ fn test_setup_kernel_load_register_a2_is_zero() {
     let mut cpu = create_test_cpu();
     let config = Config::default();
 
-    loader::setup_kernel_load(&mut cpu, &config, "", None, None);
+    loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
 
     // a2 register should be 0
     assert_eq!(cpu.regs.read(abi::REG_A2), 0);
@@ -133,7 +140,7 @@ fn test_setup_kernel_load_preserves_config() {
     let kernel_offset_before = config.system.kernel_offset;
 
     let mut cpu = create_test_cpu();
-    loader::setup_kernel_load(&mut cpu, &config, "", None, None);
+    loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
 
     // Config should not be modified
     assert_eq!(config.system.ram_base, ram_base_before);
@@ -145,11 +152,11 @@ fn test_setup_kernel_load_mret_instruction_at_ram_base() {
     let mut cpu = create_test_cpu();
     let config = Config::default();
 
-    loader::setup_kernel_load(&mut cpu, &config, "", None, None);
+    loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
 
     // MRET instruction (0x30200073) should be loaded at RAM base
     let ram_base = config.system.ram_base;
-    let instruction = cpu.bus.bus.read_u32(ram_base);
+    let instruction = cpu.bus.bus.read_u32(ram_base).unwrap();
 
     // MRET opcode is 0x30200073
     assert_eq!(instruction, 0x30200073);
@@ -161,11 +168,11 @@ fn test_setup_kernel_load_multiple_calls() {
     let config = Config::default();
 
     // First setup
-    loader::setup_kernel_load(&mut cpu, &config, "", None, None);
+    loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
     let pc_first = cpu.pc;
 
     // Second setup (should overwrite)
-    loader::setup_kernel_load(&mut cpu, &config, "", None, None);
+    loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
     let pc_second = cpu.pc;
 
     // Both should set the same PC
@@ -183,11 +190,11 @@ fn test_setup_kernel_load_different_ram_bases() {
 
     let system1 = rvsim_core::soc::System::new(&config1, "");
     let mut cpu1 = Cpu::new(system1, &config1);
-    loader::setup_kernel_load(&mut cpu1, &config1, "", None, None);
+    loader::setup_kernel_load(&mut cpu1, &config1, "", None, None, None);
 
     let system2 = rvsim_core::soc::System::new(&config2, "");
     let mut cpu2 = Cpu::new(system2, &config2);
-    loader::setup_kernel_load(&mut cpu2, &config2, "", None, None);
+    loader::setup_kernel_load(&mut cpu2, &config2, "", None, None, None);
 
     // PC should match the respective RAM bases
     assert_eq!(cpu1.pc, 0x80000000);
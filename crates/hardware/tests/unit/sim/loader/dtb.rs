@@ -0,0 +1,186 @@
+//! # Device Tree Generation Tests
+//!
+//! Verifies that the generated device tree is well formed, describes the
+//! devices at the addresses `System::new` maps them, and is handed to the
+//! kernel when no DTB file is given.
+
+use rvsim_core::config::Config;
+use rvsim_core::core::Cpu;
+use rvsim_core::isa::abi;
+use rvsim_core::sim::{dtb, fdt, loader};
+use rvsim_core::soc::System;
+use std::collections::HashMap;
+
+const FDT_BEGIN_NODE: u32 = 1;
+const FDT_END_NODE: u32 = 2;
+const FDT_PROP: u32 = 3;
+const FDT_NOP: u32 = 4;
+const FDT_END: u32 = 9;
+
+fn be32(bytes: &[u8], off: usize) -> u32 {
+    u32::from_be_bytes(bytes[off..off + 4].try_into().unwrap())
+}
+
+/// Walks the structure block and returns every property keyed by its full
+/// path (e.g. `/soc/uart@10000000/reg`). Panics on a malformed blob.
+fn properties(blob: &[u8]) -> HashMap<String, Vec<u8>> {
+    assert_eq!(be32(blob, 0), 0xd00d_feed, "magic");
+    let struct_off = be32(blob, 8) as usize;
+    let strings_off = be32(blob, 12) as usize;
+    let mut props = HashMap::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut pos = struct_off;
+    loop {
+        let token = be32(blob, pos);
+        pos += 4;
+        match token {
+            FDT_BEGIN_NODE => {
+                let len = blob[pos..].iter().position(|&b| b == 0).unwrap();
+                path.push(String::from_utf8(blob[pos..pos + len].to_vec()).unwrap());
+                pos = (pos + len + 1).next_multiple_of(4);
+            }
+            FDT_END_NODE => {
+                path.pop().expect("balanced nodes");
+            }
+            FDT_PROP => {
+                let len = be32(blob, pos) as usize;
+                let name_off = strings_off + be32(blob, pos + 4) as usize;
+                let name_len = blob[name_off..].iter().position(|&b| b == 0).unwrap();
+                let name = std::str::from_utf8(&blob[name_off..name_off + name_len]).unwrap();
+                let value = blob[pos + 8..pos + 8 + len].to_vec();
+                let node: String = path[1..].iter().map(|n| format!("/{n}")).collect();
+                props.insert(format!("{node}/{name}"), value);
+                pos = (pos + 8 + len).next_multiple_of(4);
+            }
+            FDT_NOP => {}
+            FDT_END => break,
+            other => panic!("unexpected token {other:#x}"),
+        }
+    }
+    assert!(path.is_empty(), "every node closed");
+    props
+}
+
+/// Decodes a `reg` of one 64-bit address and one 64-bit size.
+fn reg(props: &HashMap<String, Vec<u8>>, node: &str) -> (u64, u64) {
+    let value = props
+        .get(&format!("/{node}/reg"))
+        .unwrap_or_else(|| panic!("{node} has a reg"));
+    let cell = |i: usize| be32(value, i * 4) as u64;
+    ((cell(0) << 32) | cell(1), (cell(2) << 32) | cell(3))
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Contents
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn generated_blob_is_well_formed() {
+    let blob = dtb::generate(&Config::default());
+    assert_eq!(be32(&blob, 4) as usize, blob.len(), "totalsize");
+    let props = properties(&blob);
+    assert_eq!(props["/compatible"], b"riscv-virtio\0");
+    assert_eq!(props["/cpus/cpu@0/riscv,isa"], b"rv64imafdc\0");
+}
+
+#[test]
+fn timebase_frequency_matches_config() {
+    let mut config = Config::default();
+    config.system.timebase_frequency = 25_000_000;
+    let props = properties(&dtb::generate(&config));
+    assert_eq!(
+        props["/cpus/timebase-frequency"],
+        25_000_000u32.to_be_bytes()
+    );
+}
+
+#[test]
+fn cpu_node_uses_configured_hartid() {
+    let mut config = Config::default();
+    config.general.hartid = 3;
+    let props = properties(&dtb::generate(&config));
+    assert_eq!(props["/cpus/cpu@3/reg"], 3u32.to_be_bytes());
+    assert!(!props.contains_key("/cpus/cpu@0/reg"));
+}
+
+#[test]
+fn memory_node_matches_config() {
+    let mut config = Config::default();
+    config.system.ram_base = 0x4_0000_0000;
+    config.memory.ram_size = 64 * 1024 * 1024;
+    let props = properties(&dtb::generate(&config));
+    assert_eq!(
+        reg(&props, "memory@400000000"),
+        (0x4_0000_0000, 64 * 1024 * 1024)
+    );
+}
+
+#[test]
+fn device_regs_match_system_bus() {
+    let mut config = Config::default();
+    config.system.uart_base = 0x1800_0000;
+    let props = properties(&dtb::generate(&config));
+    let system = System::new(&config, "");
+
+    let sys = &config.system;
+    for (node, base) in [
+        (format!("soc/clint@{:x}", sys.clint_base), sys.clint_base),
+        (format!("soc/uart@{:x}", sys.uart_base), sys.uart_base),
+        (
+            format!("soc/virtio_mmio@{:x}", sys.disk_base),
+            sys.disk_base,
+        ),
+        (format!("soc/rtc@{:x}", sys.rtc_base), sys.rtc_base),
+        ("soc/interrupt-controller@c000000".to_string(), 0x0c00_0000),
+    ] {
+        let (addr, size) = reg(&props, &node);
+        assert_eq!(addr, base, "{node} base");
+        assert!(system.bus.is_valid_address(addr), "{node} start is mapped");
+        assert!(
+            system.bus.is_valid_address(addr + size - 1),
+            "{node} end is mapped"
+        );
+    }
+}
+
+#[test]
+fn chosen_points_console_at_uart() {
+    let mut config = Config::default();
+    config.system.uart_base = 0x1800_0000;
+    let blob = dtb::generate(&config);
+    let bootargs = fdt::chosen_prop(&blob, "bootargs").expect("bootargs");
+    let bootargs = std::str::from_utf8(bootargs).unwrap();
+    assert!(bootargs.contains("earlycon=uart8250,mmio,0x18000000"));
+    assert_eq!(
+        fdt::chosen_prop(&blob, "stdout-path"),
+        Some(&b"/soc/uart@18000000\0"[..])
+    );
+}
+
+#[test]
+fn generated_blob_accepts_initrd() {
+    let blob = fdt::set_initrd(&dtb::generate(&Config::default()), 0x100, 0x200)
+        .expect("generated DTB is patchable");
+    assert!(fdt::chosen_prop(&blob, "bootargs").is_some());
+    assert!(fdt::chosen_prop(&blob, "linux,initrd-start").is_some());
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Kernel loader
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn kernel_load_without_dtb_uses_generated_blob() {
+    let config = Config::default();
+    let system = System::new(&config, "");
+    let mut cpu = Cpu::new(system, &config);
+    let placed = loader::setup_kernel_load(&mut cpu, &config, "", None, None, None);
+
+    let expected = dtb::generate(&config);
+    assert_eq!(placed, expected, "returns the blob it placed");
+    let dtb_addr = cpu.regs.read(abi::REG_A1);
+    let loaded: Vec<u8> = (0..expected.len() as u64)
+        .map(|i| cpu.bus.bus.read_u8(dtb_addr + i).unwrap())
+        .collect();
+    assert_eq!(loaded, expected);
+}
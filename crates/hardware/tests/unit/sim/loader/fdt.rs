@@ -0,0 +1,195 @@
+//! # Device Tree Patching Tests
+//!
+//! Verifies that `/chosen` properties are added, replaced, or created in a
+//! flattened device tree, and that the kernel loader records an initrd there.
+
+use rvsim_core::config::Config;
+use rvsim_core::core::Cpu;
+use rvsim_core::sim::{fdt, loader};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const FDT_BEGIN_NODE: u32 = 1;
+const FDT_END_NODE: u32 = 2;
+const FDT_PROP: u32 = 3;
+const FDT_END: u32 = 9;
+
+/// Appends a big-endian word.
+fn word(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// Appends bytes padded to a 4-byte boundary.
+fn padded(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(bytes);
+    out.resize(out.len().next_multiple_of(4), 0);
+}
+
+/// Builds a DTB with a root `model` property and, if `bootargs` is given,
+/// a `/chosen` node holding it.
+fn build_dtb(bootargs: Option<&str>) -> Vec<u8> {
+    let strings = b"model\0bootargs\0";
+    let mut structure = Vec::new();
+    word(&mut structure, FDT_BEGIN_NODE);
+    padded(&mut structure, b"\0");
+    word(&mut structure, FDT_PROP);
+    word(&mut structure, 5);
+    word(&mut structure, 0);
+    padded(&mut structure, b"test\0");
+    if let Some(args) = bootargs {
+        word(&mut structure, FDT_BEGIN_NODE);
+        padded(&mut structure, b"chosen\0");
+        word(&mut structure, FDT_PROP);
+        word(&mut structure, args.len() as u32 + 1);
+        word(&mut structure, 6);
+        padded(&mut structure, format!("{args}\0").as_bytes());
+        word(&mut structure, FDT_END_NODE);
+    }
+    word(&mut structure, FDT_END_NODE);
+    word(&mut structure, FDT_END);
+
+    let rsvmap_off = 40;
+    let struct_off = rsvmap_off + 16;
+    let strings_off = struct_off + structure.len();
+    let total = strings_off + strings.len();
+    let mut dtb = Vec::new();
+    for field in [
+        0xd00d_feed,
+        total as u32,
+        struct_off as u32,
+        strings_off as u32,
+        rsvmap_off as u32,
+        17,
+        16,
+        0,
+        strings.len() as u32,
+        structure.len() as u32,
+    ] {
+        word(&mut dtb, field);
+    }
+    dtb.resize(struct_off, 0);
+    dtb.extend_from_slice(&structure);
+    dtb.extend_from_slice(strings);
+    dtb
+}
+
+fn be64(value: Option<&[u8]>) -> u64 {
+    u64::from_be_bytes(value.expect("property present").try_into().unwrap())
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. /chosen patching
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn initrd_added_to_existing_chosen() {
+    let dtb = fdt::set_initrd(&build_dtb(Some("console=ttyS0")), 0x8400_0000, 0x8410_0000)
+        .expect("valid DTB");
+    assert_eq!(
+        be64(fdt::chosen_prop(&dtb, "linux,initrd-start")),
+        0x8400_0000
+    );
+    assert_eq!(
+        be64(fdt::chosen_prop(&dtb, "linux,initrd-end")),
+        0x8410_0000
+    );
+    assert_eq!(
+        fdt::chosen_prop(&dtb, "bootargs"),
+        Some(&b"console=ttyS0\0"[..])
+    );
+}
+
+#[test]
+fn chosen_created_when_missing() {
+    let original = build_dtb(None);
+    assert_eq!(fdt::chosen_prop(&original, "linux,initrd-start"), None);
+
+    let dtb = fdt::set_initrd(&original, 0x100, 0x200).expect("valid DTB");
+    assert_eq!(be64(fdt::chosen_prop(&dtb, "linux,initrd-start")), 0x100);
+    assert_eq!(be64(fdt::chosen_prop(&dtb, "linux,initrd-end")), 0x200);
+}
+
+#[test]
+fn existing_initrd_properties_are_replaced() {
+    let once = fdt::set_initrd(&build_dtb(Some("quiet")), 0x100, 0x200).unwrap();
+    let twice = fdt::set_initrd(&once, 0x300, 0x400).unwrap();
+    assert_eq!(
+        twice.len(),
+        once.len(),
+        "no duplicate properties or strings"
+    );
+    assert_eq!(be64(fdt::chosen_prop(&twice, "linux,initrd-start")), 0x300);
+    assert_eq!(be64(fdt::chosen_prop(&twice, "linux,initrd-end")), 0x400);
+}
+
+#[test]
+fn header_sizes_match_rebuilt_blob() {
+    let dtb = fdt::set_initrd(&build_dtb(None), 0x100, 0x200).unwrap();
+    let field = |i: usize| u32::from_be_bytes(dtb[i * 4..i * 4 + 4].try_into().unwrap()) as usize;
+    assert_eq!(field(1), dtb.len(), "totalsize");
+    assert_eq!(
+        field(3) + field(8),
+        dtb.len(),
+        "strings block ends the blob"
+    );
+    assert_eq!(
+        field(2) + field(9),
+        field(3),
+        "structure block precedes strings"
+    );
+}
+
+#[test]
+fn non_fdt_is_rejected() {
+    assert!(fdt::set_initrd(b"not a device tree at all, no magic", 0, 0).is_err());
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Kernel loader
+// ══════════════════════════════════════════════════════════
+
+fn temp_file(data: &[u8]) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(data).unwrap();
+    file.flush().unwrap();
+    file
+}
+
+#[test]
+fn kernel_load_places_initrd_and_patches_dtb() {
+    let config = Config::default();
+    let system = rvsim_core::soc::System::new(&config, "");
+    let mut cpu = Cpu::new(system, &config);
+
+    let initrd = temp_file(&[0x07, 0x07, 0x01, 0x30, 0x30, 0x30]); // "070701" cpio magic
+    let dtb = temp_file(&build_dtb(Some("console=ttyS0")));
+    loader::setup_kernel_load(
+        &mut cpu,
+        &config,
+        "",
+        Some(dtb.path().to_str().unwrap().to_string()),
+        None,
+        Some(initrd.path().to_str().unwrap().to_string()),
+    );
+
+    let start = config.system.ram_base + config.system.initrd_offset;
+    assert_eq!(cpu.bus.bus.read_u8(start).unwrap(), 0x07);
+    assert_eq!(cpu.bus.bus.read_u8(start + 5).unwrap(), 0x30);
+
+    let dtb_addr = cpu.regs.read(rvsim_core::isa::abi::REG_A1);
+    let total = u32::from_be_bytes(
+        (0..4)
+            .map(|i| cpu.bus.bus.read_u8(dtb_addr + 4 + i).unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    );
+    let loaded: Vec<u8> = (0..total as u64)
+        .map(|i| cpu.bus.bus.read_u8(dtb_addr + i).unwrap())
+        .collect();
+    assert_eq!(be64(fdt::chosen_prop(&loaded, "linux,initrd-start")), start);
+    assert_eq!(
+        be64(fdt::chosen_prop(&loaded, "linux,initrd-end")),
+        start + 6
+    );
+}
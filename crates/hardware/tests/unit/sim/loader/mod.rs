@@ -4,3 +4,12 @@
 
 /// Tests for binary loading from disk and memory initialization.
 pub mod binary_loading;
+
+/// Tests for ELF symbol extraction and address resolution.
+pub mod symbols;
+
+/// Tests for device tree `/chosen` patching and initrd loading.
+pub mod fdt;
+
+/// Tests for device tree generation from the configuration.
+pub mod dtb;
@@ -0,0 +1,202 @@
+//! # Symbol Resolution Tests
+//!
+//! Verifies that function symbols are extracted from an ELF's `.symtab` and
+//! that addresses resolve to the nearest preceding function and offset.
+
+use crate::common::harness::TestContext;
+use rvsim_core::sim::loader;
+use rvsim_core::sim::symbols::SymbolTable;
+use rvsim_core::soc::interconnect::Bus;
+
+const TEXT_ADDR: u64 = 0x8000_0000;
+
+const STT_NOTYPE: u8 = 0;
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+const STB_LOCAL: u8 = 0;
+const STB_GLOBAL: u8 = 1;
+
+/// A `.symtab` entry: name, type, binding, value, size.
+type Sym = (&'static str, u8, u8, u64, u64);
+
+/// Appends `name` to a string table and returns its offset.
+fn add_str(table: &mut Vec<u8>, name: &str) -> u32 {
+    let offset = table.len() as u32;
+    table.extend_from_slice(name.as_bytes());
+    table.push(0);
+    offset
+}
+
+/// Builds a minimal RV64 executable with a 32-byte `.text` at `TEXT_ADDR`
+/// and the given symbols (locals first), all defined in `.text`.
+fn build_elf(symbols: &[Sym]) -> Vec<u8> {
+    const EHDR: usize = 64;
+    const PHDR: usize = 56;
+    const TEXT_SIZE: usize = 32;
+
+    let mut strtab = vec![0u8];
+    let mut symtab = vec![0u8; 24];
+    for &(name, kind, bind, value, size) in symbols {
+        symtab.extend_from_slice(&add_str(&mut strtab, name).to_le_bytes());
+        symtab.push((bind << 4) | kind);
+        symtab.push(0);
+        symtab.extend_from_slice(&1u16.to_le_bytes()); // .text
+        symtab.extend_from_slice(&value.to_le_bytes());
+        symtab.extend_from_slice(&size.to_le_bytes());
+    }
+    let first_global = 1 + symbols.iter().filter(|s| s.2 == STB_LOCAL).count() as u32;
+
+    let mut shstrtab = vec![0u8];
+    let names = [".text", ".symtab", ".strtab", ".shstrtab"].map(|n| add_str(&mut shstrtab, n));
+
+    let text_off = EHDR + PHDR;
+    let symtab_off = text_off + TEXT_SIZE;
+    let strtab_off = symtab_off + symtab.len();
+    let shstrtab_off = strtab_off + strtab.len();
+    let shdr_off = (shstrtab_off + shstrtab.len()).next_multiple_of(8);
+
+    let mut elf = Vec::new();
+    elf.extend_from_slice(b"\x7fELF\x02\x01\x01");
+    elf.resize(16, 0);
+    elf.extend_from_slice(&2u16.to_le_bytes()); // ET_EXEC
+    elf.extend_from_slice(&243u16.to_le_bytes()); // EM_RISCV
+    elf.extend_from_slice(&1u32.to_le_bytes());
+    elf.extend_from_slice(&TEXT_ADDR.to_le_bytes()); // e_entry
+    elf.extend_from_slice(&(EHDR as u64).to_le_bytes()); // e_phoff
+    elf.extend_from_slice(&(shdr_off as u64).to_le_bytes()); // e_shoff
+    elf.extend_from_slice(&0u32.to_le_bytes());
+    for half in [EHDR as u16, PHDR as u16, 1, 64, 5, 4] {
+        elf.extend_from_slice(&half.to_le_bytes());
+    }
+
+    // PT_LOAD for .text
+    elf.extend_from_slice(&1u32.to_le_bytes());
+    elf.extend_from_slice(&5u32.to_le_bytes()); // R+X
+    for word in [
+        text_off as u64,
+        TEXT_ADDR,
+        TEXT_ADDR,
+        TEXT_SIZE as u64,
+        TEXT_SIZE as u64,
+        4,
+    ] {
+        elf.extend_from_slice(&word.to_le_bytes());
+    }
+
+    elf.extend(std::iter::repeat_n(0x13, TEXT_SIZE)); // not executed
+    elf.extend_from_slice(&symtab);
+    elf.extend_from_slice(&strtab);
+    elf.extend_from_slice(&shstrtab);
+    elf.resize(shdr_off, 0);
+
+    // (name, type, flags, addr, offset, size, link, info, align, entsize)
+    let sections = [
+        (0, 0, 0, 0, 0, 0, 0, 0, 0, 0),
+        (names[0], 1, 6, TEXT_ADDR, text_off, TEXT_SIZE, 0, 0, 4, 0),
+        (
+            names[1],
+            2,
+            0,
+            0,
+            symtab_off,
+            symtab.len(),
+            3,
+            first_global,
+            8,
+            24,
+        ),
+        (names[2], 3, 0, 0, strtab_off, strtab.len(), 0, 0, 1, 0),
+        (names[3], 3, 0, 0, shstrtab_off, shstrtab.len(), 0, 0, 1, 0),
+    ];
+    for (name, kind, flags, addr, offset, size, link, info, align, entsize) in sections {
+        elf.extend_from_slice(&name.to_le_bytes());
+        elf.extend_from_slice(&(kind as u32).to_le_bytes());
+        elf.extend_from_slice(&(flags as u64).to_le_bytes());
+        elf.extend_from_slice(&addr.to_le_bytes());
+        elf.extend_from_slice(&(offset as u64).to_le_bytes());
+        elf.extend_from_slice(&(size as u64).to_le_bytes());
+        elf.extend_from_slice(&(link as u32).to_le_bytes());
+        elf.extend_from_slice(&info.to_le_bytes());
+        elf.extend_from_slice(&(align as u64).to_le_bytes());
+        elf.extend_from_slice(&(entsize as u64).to_le_bytes());
+    }
+    elf
+}
+
+/// `_start` (untyped label), `main` (sized function), and symbols that must
+/// be skipped: a mapping symbol, a local label, and a data object.
+fn sample_elf() -> Vec<u8> {
+    build_elf(&[
+        ("$x", STT_NOTYPE, STB_LOCAL, TEXT_ADDR, 0),
+        (".L_loop", STT_NOTYPE, STB_LOCAL, TEXT_ADDR + 4, 0),
+        ("_start", STT_NOTYPE, STB_GLOBAL, TEXT_ADDR, 0),
+        ("main", STT_FUNC, STB_GLOBAL, TEXT_ADDR + 0x10, 8),
+        ("table", STT_OBJECT, STB_GLOBAL, TEXT_ADDR + 0x18, 8),
+    ])
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Lookup
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn resolves_nearest_preceding_symbol() {
+    let table = SymbolTable::new([("b".to_string(), 0x200, 0), ("a".to_string(), 0x100, 0)]);
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.resolve(0xFF), None);
+    assert_eq!(table.resolve(0x100), Some(("a", 0)));
+    assert_eq!(table.resolve(0x1FC), Some(("a", 0xFC)));
+    assert_eq!(table.resolve(0x208), Some(("b", 8)));
+}
+
+#[test]
+fn sized_symbol_does_not_cover_past_its_end() {
+    let table = SymbolTable::new([("f".to_string(), 0x100, 0x10)]);
+    assert_eq!(table.resolve(0x10C), Some(("f", 0xC)));
+    assert_eq!(table.resolve(0x110), None);
+}
+
+#[test]
+fn describe_formats_offsets() {
+    let table = SymbolTable::new([("main".to_string(), 0x100, 0)]);
+    assert_eq!(table.describe(0x100), "<main>");
+    assert_eq!(table.describe(0x11C), "<main+0x1c>");
+    assert_eq!(table.describe(0x10), "");
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. ELF extraction
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn extracts_code_symbols_from_elf() {
+    let table = SymbolTable::from_elf(&sample_elf());
+    assert_eq!(table.len(), 2, "only _start and main are code symbols");
+    assert_eq!(table.resolve(TEXT_ADDR + 4), Some(("_start", 4)));
+    assert_eq!(table.resolve(TEXT_ADDR + 0x14), Some(("main", 4)));
+    assert_eq!(table.resolve(TEXT_ADDR + 0x18), None);
+}
+
+#[test]
+fn non_elf_has_no_symbols() {
+    assert!(SymbolTable::from_elf(b"not an elf").is_empty());
+}
+
+#[test]
+fn loader_returns_symbols_and_cpu_resolves_them() {
+    let mut bus = Bus::new(8, 0);
+    let result = loader::try_load_elf(&sample_elf(), &mut bus).expect("valid ELF");
+    assert_eq!(result.entry, TEXT_ADDR);
+
+    let mut tc = TestContext::new();
+    tc.cpu_mut().symbols = result.symbols;
+    assert_eq!(
+        tc.cpu().resolve_symbol(TEXT_ADDR + 0x14),
+        Some(("main".to_string(), 4))
+    );
+    assert_eq!(
+        tc.cpu().describe_pc(TEXT_ADDR + 0x14),
+        "0x80000014 <main+0x4>"
+    );
+    assert_eq!(tc.cpu().describe_pc(0x1000), "0x1000");
+}
@@ -5,3 +5,6 @@
 
 /// Tests for binary loader and kernel setup.
 pub mod loader;
+
+/// Tests for SysCon-triggered reboots.
+pub mod reset;
@@ -0,0 +1,182 @@
+//! SysCon Reboot Tests.
+//!
+//! Verifies that writing the SysCon reset magic reboots the machine instead
+//! of terminating it: architectural state returns to its reset values, the
+//! boot hook runs again, and the simulator reports the reboot once.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, PROGRAM_MEM_SIZE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::core::arch::csr::MSTATUS_MPP;
+use rvsim_core::core::arch::mode::PrivilegeMode;
+use rvsim_core::core::cpu::semihosting::SemihostFile;
+use rvsim_core::sim::simulator::Outcome;
+use rvsim_core::soc::devices::syscon::SysCon;
+use std::cell::Cell;
+use std::rc::Rc;
+
+const SYSCON_BASE: u64 = 0x10_0000;
+
+/// Offset of the boot counter from the program start.
+const COUNTER_OFFSET: i32 = 0x100;
+
+/// Number of boots before the program exits instead of rebooting.
+const BOOTS: i32 = 3;
+
+/// Counts boots in RAM, writes the reset magic until the count reaches
+/// `BOOTS`, then exits.
+fn program() -> Vec<u32> {
+    // Program:
+    //   0: x1 = SYSCON_BASE
+    //   4: x2 = 0x7000
+    //   8: x2 = x2 + 0x777          (reset magic)
+    //  12: AUIPC x3, 0
+    //  16: LW x4, COUNTER(x3)
+    //  20: x4 = x4 + 1
+    //  24: SW x4, COUNTER(x3)
+    //  28: x5 = BOOTS
+    //  32: BEQ x4, x5, +12
+    //  36: SW x2, 0(x1)             (reboot)
+    //  40: JAL x0, 0                (spin until the reset lands)
+    //  44: (zero word: exit)
+    vec![
+        InstructionBuilder::new()
+            .lui(1, (SYSCON_BASE >> 12) as i32)
+            .build(),
+        InstructionBuilder::new().lui(2, 0x7).build(),
+        InstructionBuilder::new().addi(2, 2, 0x777).build(),
+        InstructionBuilder::new().auipc(3, 0).build(),
+        InstructionBuilder::new().lw(4, 3, COUNTER_OFFSET).build(),
+        InstructionBuilder::new().addi(4, 4, 1).build(),
+        InstructionBuilder::new().sw(3, 4, COUNTER_OFFSET).build(),
+        InstructionBuilder::new().addi(5, 0, BOOTS).build(),
+        InstructionBuilder::new().beq(4, 5, 12).build(),
+        InstructionBuilder::new().sw(1, 2, 0).build(),
+        InstructionBuilder::new().jal(0, 0).build(),
+    ]
+}
+
+/// Builds a context with a SysCon sharing the system's exit signal.
+fn context() -> TestContext {
+    let mut tc = TestContext::program(&Config::default(), &program());
+    let exit_request = tc.sim.cpu.bus.exit_request.clone();
+    tc.sim
+        .cpu
+        .bus
+        .bus
+        .add_device(Box::new(SysCon::new(SYSCON_BASE, exit_request)));
+    tc
+}
+
+/// Runs until the next reboot or exit and returns what happened.
+fn run_until_outcome(tc: &mut TestContext, max_cycles: u64) -> Option<Outcome> {
+    for _ in 0..max_cycles {
+        tc.sim.tick().expect("tick");
+        if let Some(outcome) = tc.sim.take_outcome() {
+            return Some(outcome);
+        }
+    }
+    None
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Reboot loop
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn reset_magic_reboots_instead_of_exiting() {
+    let mut tc = context();
+    let boots = Rc::new(Cell::new(0));
+    let counter = boots.clone();
+    tc.sim.set_boot(move |cpu| {
+        counter.set(counter.get() + 1);
+        cpu.pc = PROGRAM_BASE;
+    });
+
+    for _ in 1..BOOTS {
+        assert_eq!(run_until_outcome(&mut tc, 2000), Some(Outcome::Reset));
+        assert!(tc.cpu().exit_code.is_none());
+        assert_eq!(tc.cpu().pc, PROGRAM_BASE);
+    }
+    assert_eq!(boots.get(), BOOTS - 1);
+
+    assert_eq!(run_until_outcome(&mut tc, 2000), Some(Outcome::Exit(0)));
+    // The counter in RAM survived every reboot.
+    assert_eq!(tc.get_reg(4), BOOTS as u64);
+}
+
+#[test]
+fn reboot_is_reported_once() {
+    let mut tc = context();
+    assert_eq!(run_until_outcome(&mut tc, 2000), Some(Outcome::Reset));
+    assert_eq!(tc.sim.take_outcome(), None);
+    assert!(!tc.sim.cpu.bus.take_reset());
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Reset state
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn reboot_restores_reset_state() {
+    let mut tc = context();
+    tc.cpu_mut().reset_pc = PROGRAM_BASE;
+    assert_eq!(run_until_outcome(&mut tc, 2000), Some(Outcome::Reset));
+
+    // Without a boot hook the CPU restarts at its reset vector with
+    // cleared registers.
+    assert_eq!(tc.cpu().pc, PROGRAM_BASE);
+    assert_eq!(tc.cpu().privilege, PrivilegeMode::Machine);
+    for reg in 1..32 {
+        assert_eq!(tc.get_reg(reg), 0, "x{reg} survived the reset");
+    }
+}
+
+#[test]
+fn cpu_reset_clears_architectural_state() {
+    let mut tc = context();
+    tc.set_reg(7, 0x1234);
+    tc.cpu_mut().csrs.mscratch = 0xdead;
+    tc.cpu_mut().privilege = PrivilegeMode::User;
+    tc.cpu_mut().exit_code = Some(3);
+    tc.cpu_mut().atomic_line = Some(PROGRAM_BASE);
+    tc.cpu_mut().alu_timer = 9;
+    tc.cpu_mut().fu_busy_until[0] = 100;
+    tc.cpu_mut().semihost_files.push(Some(SemihostFile::Stdout));
+    let misa = tc.cpu().csrs.misa;
+
+    tc.cpu_mut().reset();
+
+    assert_eq!(tc.get_reg(7), 0);
+    assert_eq!(tc.cpu().csrs.mscratch, 0);
+    assert_eq!(tc.cpu().csrs.misa, misa);
+    assert_eq!(tc.cpu().privilege, PrivilegeMode::Machine);
+    assert_eq!(tc.cpu().exit_code, None);
+    assert_eq!(tc.cpu().pc, tc.cpu().reset_pc);
+    assert_eq!(tc.cpu().atomic_line, None);
+    assert_eq!(tc.cpu().alu_timer, 0);
+    assert!(tc.cpu().fu_busy_until.iter().all(|&t| t == 0));
+    assert!(tc.cpu().semihost_files.is_empty());
+}
+
+#[test]
+fn configured_reset_values_apply_at_power_on_and_reset() {
+    let mut config = Config::default();
+    config.general.reset_mstatus = Some(MSTATUS_MPP);
+    config.general.reset_medeleg = 0xb109;
+    config.general.reset_mideleg = 0x222;
+    config.general.reset_privilege = PrivilegeMode::Supervisor;
+    let mut tc = TestContext::with_config(&config).with_memory(PROGRAM_MEM_SIZE, PROGRAM_BASE);
+
+    for _ in 0..2 {
+        assert_eq!(tc.cpu().csrs.mstatus, MSTATUS_MPP);
+        assert_eq!(tc.cpu().csrs.medeleg, 0xb109);
+        assert_eq!(tc.cpu().csrs.mideleg, 0x222);
+        assert_eq!(tc.cpu().privilege, PrivilegeMode::Supervisor);
+
+        tc.cpu_mut().csrs.mstatus = 0;
+        tc.cpu_mut().csrs.medeleg = 0;
+        tc.cpu_mut().privilege = PrivilegeMode::Machine;
+        tc.cpu_mut().reset();
+    }
+}
@@ -1,10 +1,11 @@
 //! CLINT (Core Local Interruptor) Unit Tests.
 //!
 //! Verifies timer operation, MSIP/MTIME/MTIMECMP register read/write,
-//! divider-based tick counting, and interrupt generation.
+//! divider-based tick counting, interrupt generation, and the per-hart
+//! MSIP/MTIMECMP banks.
 
 use rvsim_core::soc::devices::Device;
-use rvsim_core::soc::devices::clint::Clint;
+use rvsim_core::soc::devices::clint::{Clint, ClintIrqs, MAX_HARTS};
 
 #[test]
 fn clint_name() {
@@ -133,3 +134,94 @@ fn clint_unrecognized_offset_returns_zero() {
     assert_eq!(clint.read_u64(0x1000), 0);
     assert_eq!(clint.read_u32(0x1000), 0);
 }
+
+#[test]
+fn clint_default_has_one_hart() {
+    let clint = Clint::new(0, 1);
+    assert_eq!(clint.num_harts(), 1);
+}
+
+#[test]
+fn clint_hart_count_clamped() {
+    assert_eq!(Clint::with_harts(0, 1, 0).num_harts(), 1);
+    assert_eq!(Clint::with_harts(0, 1, 10_000).num_harts(), MAX_HARTS);
+}
+
+#[test]
+fn clint_msip_per_hart_offsets() {
+    let mut clint = Clint::with_harts(0, 1, 4);
+    clint.write_u32(0x0004 * 2, 1);
+    assert_eq!(clint.read_u32(0x0000), 0);
+    assert_eq!(clint.read_u32(0x0004), 0);
+    assert_eq!(clint.read_u32(0x0008), 1);
+    assert_eq!(clint.read_u32(0x000C), 0);
+}
+
+#[test]
+fn clint_mtimecmp_per_hart_offsets() {
+    let mut clint = Clint::with_harts(0, 1, 3);
+    clint.write_u64(0x4000 + 8, 0x1111);
+    clint.write_u32(0x4000 + 16, 0x2222);
+    clint.write_u32(0x4000 + 16 + 4, 0x3);
+    assert_eq!(clint.read_u64(0x4000), u64::MAX);
+    assert_eq!(clint.read_u64(0x4008), 0x1111);
+    assert_eq!(clint.read_u64(0x4010), 0x3_0000_2222);
+    assert_eq!(clint.read_u32(0x4014), 0x3);
+}
+
+#[test]
+fn clint_out_of_range_hart_ignored() {
+    let mut clint = Clint::with_harts(0, 1, 2);
+    clint.write_u32(0x0008, 1);
+    clint.write_u64(0x4010, 0);
+    assert_eq!(clint.read_u32(0x0008), 0);
+    assert_eq!(clint.read_u64(0x4010), 0);
+    assert_eq!(clint.pending(), ClintIrqs::default());
+}
+
+#[test]
+fn clint_pending_bitmap_per_hart() {
+    let mut clint = Clint::with_harts(0, 1, 4);
+    clint.write_u32(0x0004, 1); // IPI to hart 1
+    clint.write_u64(0x4000 + 3 * 8, 2); // hart 3 timer at mtime 2
+    clint.tick();
+    let irqs = clint.pending();
+    assert_eq!(irqs.msip, 0b0010);
+    assert_eq!(irqs.mtip, 0);
+    clint.tick();
+    let irqs = clint.pending();
+    assert_eq!(irqs.mtip, 0b1000);
+    assert!(irqs.msip(1) && !irqs.msip(0));
+    assert!(irqs.mtip(3) && !irqs.mtip(2));
+}
+
+#[test]
+fn clint_msip_does_not_raise_mtip() {
+    let mut clint = Clint::new(0, 1);
+    clint.write_u32(0x0000, 1);
+    clint.tick();
+    let irqs = clint.pending();
+    assert!(irqs.msip(0));
+    assert!(
+        !irqs.mtip(0),
+        "a software interrupt is not a timer interrupt"
+    );
+}
+
+#[test]
+fn clint_state_roundtrip_per_hart() {
+    let mut clint = Clint::with_harts(0, 1, 2);
+    clint.write_u32(0x0004, 1);
+    clint.write_u64(0x4008, 77);
+    clint.tick();
+    let state = clint.save_state().unwrap();
+
+    let mut restored = Clint::with_harts(0, 1, 2);
+    restored.restore_state(&state).unwrap();
+    assert_eq!(restored.read_u32(0x0004), 1);
+    assert_eq!(restored.read_u64(0x4008), 77);
+    assert_eq!(restored.read_u64(0xBFF8), 1);
+
+    let mut mismatched = Clint::with_harts(0, 1, 3);
+    assert!(mismatched.restore_state(&state).is_err());
+}
@@ -0,0 +1,89 @@
+//! Framebuffer unit tests.
+//!
+//! Verifies the pixel layout, bus-width access, PPM output, checkpointing,
+//! and that the system maps the framebuffer only when enabled.
+
+use rvsim_core::config::Config;
+use rvsim_core::soc::System;
+use rvsim_core::soc::devices::Device;
+use rvsim_core::soc::devices::framebuffer::Framebuffer;
+
+const BASE: u64 = 0x4000_0000;
+
+#[test]
+fn framebuffer_address_range() {
+    let fb = Framebuffer::new(BASE, 4, 3);
+    assert_eq!(fb.name(), "Framebuffer");
+    assert_eq!(fb.address_range(), (BASE, 4 * 3 * 4));
+    assert_eq!((fb.width(), fb.height()), (4, 3));
+}
+
+#[test]
+fn pixels_are_row_major_xrgb() {
+    let mut fb = Framebuffer::new(BASE, 4, 3);
+    fb.write_u32((2 * 4 + 1) * 4, 0xFF12_3456);
+    assert_eq!(fb.pixel(1, 2), 0x0012_3456);
+    assert_eq!(fb.read_u8((2 * 4 + 1) * 4), 0x56);
+    assert_eq!(fb.read_u16((2 * 4 + 1) * 4 + 1), 0x1234);
+    assert_eq!(fb.pixel(0, 0), 0);
+}
+
+#[test]
+fn out_of_range_accesses_are_ignored() {
+    let mut fb = Framebuffer::new(BASE, 2, 2);
+    fb.write_u64(14, u64::MAX);
+    assert_eq!(fb.read_u64(14), 0);
+    assert!(fb.pixels().iter().all(|&b| b == 0));
+}
+
+#[test]
+fn ppm_output_is_rgb() {
+    let mut fb = Framebuffer::new(BASE, 2, 1);
+    fb.write_u32(0, 0x00FF_8000);
+    fb.write_u32(4, 0x0000_00FF);
+    let mut out = Vec::new();
+    fb.write_ppm(&mut out).unwrap();
+    let mut expected = b"P6\n2 1\n255\n".to_vec();
+    expected.extend_from_slice(&[0xFF, 0x80, 0x00, 0x00, 0x00, 0xFF]);
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn dump_ppm_writes_file() {
+    let path = std::env::temp_dir().join(format!("rvsim_fb_{}.ppm", std::process::id()));
+    let mut fb = Framebuffer::new(BASE, 3, 2);
+    fb.write_u32(0, 0x00AB_CDEF);
+    fb.dump_ppm(&path).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(data.starts_with(b"P6\n3 2\n255\n"));
+    assert_eq!(data.len(), 11 + 3 * 2 * 3);
+    assert_eq!(&data[11..14], &[0xAB, 0xCD, 0xEF]);
+}
+
+#[test]
+fn state_round_trips() {
+    let mut fb = Framebuffer::new(BASE, 2, 2);
+    fb.write_u32(8, 0x0011_2233);
+    let state = fb.save_state().unwrap();
+
+    let mut restored = Framebuffer::new(BASE, 2, 2);
+    restored.restore_state(&state).unwrap();
+    assert_eq!(restored.pixel(0, 1), 0x0011_2233);
+    assert!(Framebuffer::new(BASE, 3, 3).restore_state(&state).is_err());
+}
+
+#[test]
+fn system_maps_framebuffer_when_enabled() {
+    let mut system = System::new(&Config::default(), "");
+    assert!(system.bus.framebuffer().is_none());
+
+    let mut config = Config::default();
+    config.system.framebuffer.enabled = true;
+    config.system.framebuffer.width = 8;
+    config.system.framebuffer.height = 4;
+    let mut system = System::new(&config, "");
+    system.bus.write_u32(BASE + 4 * 9, 0x00C0_FFEE).unwrap();
+    let fb = system.bus.framebuffer().unwrap();
+    assert_eq!(fb.pixel(1, 1), 0x00C0_FFEE);
+}
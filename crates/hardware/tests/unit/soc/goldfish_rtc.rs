@@ -1,33 +1,99 @@
 //! Goldfish RTC unit tests.
 //!
-//! Verifies basic device identification for the Goldfish real-time clock.
+//! Verifies device identification, the TIME_LOW/TIME_HIGH latch, setting the
+//! time, and that the clock advances with simulated `mtime` ticks.
 
 use rvsim_core::soc::devices::Device;
 use rvsim_core::soc::devices::goldfish_rtc::GoldfishRtc;
 
+/// Nanoseconds per `mtime` tick at the 10 MHz timebase.
+const NS_PER_TICK: u64 = 100;
+/// Default `timebase-frequency`.
+const TIMEBASE_HZ: u64 = 10_000_000;
+
+/// Reads the 64-bit time through the two 32-bit registers.
+fn read_time(rtc: &mut GoldfishRtc) -> u64 {
+    let low = rtc.read_u32(0x0);
+    let high = rtc.read_u32(0x4);
+    ((high as u64) << 32) | low as u64
+}
+
 #[test]
 fn goldfish_rtc_name() {
-    let rtc = GoldfishRtc::new(0x101000);
+    let rtc = GoldfishRtc::new(0x101000, 10, TIMEBASE_HZ);
     assert_eq!(rtc.name(), "GoldfishRTC");
 }
 
 #[test]
 fn goldfish_rtc_address_range() {
-    let rtc = GoldfishRtc::new(0x101000);
+    let rtc = GoldfishRtc::new(0x101000, 10, TIMEBASE_HZ);
     let (base, size) = rtc.address_range();
     assert_eq!(base, 0x101000);
     assert_eq!(size, 0x1000);
 }
 
 #[test]
-fn goldfish_rtc_read_time_low_nonzero() {
-    // The low word of the time should be > 0 (we're running this test
-    // some time after the Unix epoch).
-    let mut rtc = GoldfishRtc::new(0);
-    let time_low = rtc.read_u32(0x0);
-    // We don't know the exact time, but it should be positive
-    // (nanoseconds since epoch mod 2^32 should be nonzero)
-    let _time_high = rtc.read_u32(0x4);
-    let time_ns = ((_time_high as u64) << 32) | (time_low as u64);
-    assert!(time_ns > 0, "Time since epoch should be > 0");
+fn goldfish_rtc_starts_at_host_time() {
+    // 2020-01-01T00:00:00Z in nanoseconds.
+    const Y2020_NS: u64 = 1_577_836_800_000_000_000;
+    let mut rtc = GoldfishRtc::new(0, 10, TIMEBASE_HZ);
+    assert!(
+        read_time(&mut rtc) > Y2020_NS,
+        "guest should not boot in 1970"
+    );
+}
+
+#[test]
+fn goldfish_rtc_set_time_via_registers() {
+    let mut rtc = GoldfishRtc::new(0, 10, TIMEBASE_HZ);
+    rtc.write_u32(0x4, 0x1234);
+    rtc.write_u32(0x0, 0x5678_9ABC);
+    assert_eq!(read_time(&mut rtc), 0x1234_5678_9ABC);
+    assert_eq!(rtc.read_u64(0x0), 0x1234_5678_9ABC);
+}
+
+#[test]
+fn goldfish_rtc_advances_with_mtime() {
+    let mut rtc = GoldfishRtc::new(0, 10, TIMEBASE_HZ);
+    rtc.set_time_ns(1_000);
+    for _ in 0..35 {
+        assert!(!rtc.tick(), "the RTC never raises an interrupt");
+    }
+    // 35 cycles at divider 10 = 3 mtime ticks.
+    assert_eq!(rtc.time_ns(), 1_000 + 3 * NS_PER_TICK);
+}
+
+#[test]
+fn goldfish_rtc_follows_timebase_frequency() {
+    let mut rtc = GoldfishRtc::new(0, 1, 1_000_000);
+    rtc.set_time_ns(0);
+    for _ in 0..3 {
+        rtc.tick();
+    }
+    assert_eq!(rtc.time_ns(), 3_000, "1 MHz timebase = 1 us per tick");
+}
+
+#[test]
+fn goldfish_rtc_high_word_latched_on_low_read() {
+    let mut rtc = GoldfishRtc::new(0, 1, TIMEBASE_HZ);
+    rtc.set_time_ns(0xFFFF_FFFF - NS_PER_TICK + 1);
+    let low = rtc.read_u32(0x0);
+    rtc.tick(); // carries into the high word
+    let high = rtc.read_u32(0x4);
+    assert_eq!(
+        ((high as u64) << 32) | low as u64,
+        0xFFFF_FFFF - NS_PER_TICK + 1
+    );
+    assert_eq!(read_time(&mut rtc), 0x1_0000_0000);
+}
+
+#[test]
+fn goldfish_rtc_state_roundtrip() {
+    let mut rtc = GoldfishRtc::new(0, 1, TIMEBASE_HZ);
+    rtc.set_time_ns(42);
+    rtc.tick();
+    let state = rtc.save_state().unwrap();
+    let mut restored = GoldfishRtc::new(0, 1, TIMEBASE_HZ);
+    restored.restore_state(&state).unwrap();
+    assert_eq!(restored.time_ns(), 42 + NS_PER_TICK);
 }
@@ -0,0 +1,161 @@
+//! Bus Access Log Tests.
+//!
+//! Verifies that the bus records reads and writes in a bounded, filterable
+//! ring buffer, and that accesses issued by the pipeline carry the PC of
+//! the instruction that made them.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::{PROGRAM_BASE, TestContext};
+use rvsim_core::config::Config;
+use rvsim_core::soc::access_log::{AccessLog, BusAccess};
+use rvsim_core::soc::devices::Uart;
+use rvsim_core::soc::interconnect::Bus;
+
+const UART_BASE: u64 = 0x1000_0000;
+
+fn access(addr: u64) -> BusAccess {
+    BusAccess {
+        pc: 0,
+        addr,
+        size: 1,
+        is_write: false,
+        value: 0,
+    }
+}
+
+fn uart_bus() -> Bus {
+    let mut bus = Bus::new(8, 0);
+    bus.add_device(Box::new(Uart::new(UART_BASE, false, true)));
+    bus
+}
+
+// ══════════════════════════════════════════════════════════
+// 1. Ring buffer
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn full_log_drops_oldest() {
+    let mut log = AccessLog::new(2);
+    for addr in 1..=3 {
+        log.record(access(addr));
+    }
+    let addrs: Vec<u64> = log.drain().iter().map(|a| a.addr).collect();
+    assert_eq!(addrs, vec![2, 3]);
+    assert!(log.is_empty(), "drain empties the log");
+}
+
+#[test]
+fn filter_keeps_only_range() {
+    let mut log = AccessLog::new(8);
+    log.set_filter(Some((0x100, 0x200)));
+    for addr in [0xff, 0x100, 0x1ff, 0x200] {
+        log.record(access(addr));
+    }
+    let addrs: Vec<u64> = log.drain().iter().map(|a| a.addr).collect();
+    assert_eq!(addrs, vec![0x100, 0x1ff]);
+}
+
+#[test]
+fn zero_capacity_records_nothing() {
+    let mut log = AccessLog::new(0);
+    log.record(access(0x100));
+    assert_eq!(log.len(), 0);
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Bus
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn disabled_bus_log_is_empty() {
+    let mut bus = uart_bus();
+    bus.write_u8(UART_BASE, b'A').unwrap();
+    assert!(bus.drain_access_log().is_empty());
+}
+
+#[test]
+fn bus_records_reads_and_writes() {
+    let mut bus = uart_bus();
+    bus.enable_access_log(16);
+    bus.set_access_pc(0x8000_0010);
+    bus.write_u8(UART_BASE, b'A').unwrap();
+    bus.set_access_pc(0x8000_0014);
+    let lsr = bus.read_u8(UART_BASE + 5).unwrap();
+
+    let log = bus.drain_access_log();
+    assert_eq!(
+        log,
+        vec![
+            BusAccess {
+                pc: 0x8000_0010,
+                addr: UART_BASE,
+                size: 1,
+                is_write: true,
+                value: b'A' as u64,
+            },
+            BusAccess {
+                pc: 0x8000_0014,
+                addr: UART_BASE + 5,
+                size: 1,
+                is_write: false,
+                value: lsr as u64,
+            },
+        ]
+    );
+}
+
+#[test]
+fn unclaimed_accesses_are_recorded() {
+    let mut bus = uart_bus();
+    bus.enable_access_log(4);
+    assert!(bus.write_u32(0x2000_0000, 0xdead_beef).is_err());
+    let log = bus.drain_access_log();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].size, 4);
+    assert_eq!(log[0].value, 0xdead_beef);
+}
+
+#[test]
+fn config_enables_log() {
+    let mut config = Config::default();
+    config.general.mem_trace_size = 8;
+    let mut tc = TestContext::with_config(&config);
+    let bus = &mut tc.cpu_mut().bus.bus;
+    bus.add_device(Box::new(Uart::new(UART_BASE, false, true)));
+    bus.write_u8(UART_BASE, b'A').unwrap();
+    assert_eq!(bus.drain_access_log().len(), 1);
+}
+
+// ══════════════════════════════════════════════════════════
+// 3. Pipeline
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn pipeline_accesses_carry_instruction_pc() {
+    let program = [
+        InstructionBuilder::new().lui(5, 0x10000).build(),
+        InstructionBuilder::new().addi(6, 0, 0x41).build(),
+        InstructionBuilder::new().sw(5, 6, 0).build(),
+        InstructionBuilder::new().lw(7, 5, 0).build(),
+    ];
+    let mut tc = TestContext::program(&Config::default(), &program);
+    let bus = &mut tc.cpu_mut().bus.bus;
+    bus.add_device(Box::new(Uart::new(UART_BASE, false, true)));
+    bus.enable_access_log(64);
+    bus.set_access_log_filter(Some((UART_BASE, UART_BASE + 0x100)));
+    tc.run(10_000);
+
+    let log = tc.cpu_mut().bus.bus.drain_access_log();
+    let summary: Vec<(u64, bool, u8, u64)> = log
+        .iter()
+        .map(|a| (a.pc, a.is_write, a.size, a.addr))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (PROGRAM_BASE + 8, true, 4, UART_BASE),
+            (PROGRAM_BASE + 12, false, 4, UART_BASE),
+        ]
+    );
+    assert_eq!(log[0].value, 0x41);
+}
@@ -3,10 +3,13 @@
 //! Verifies device registration, address routing, read/write operations,
 //! transit time calculation, and valid address checks.
 
+use rvsim_core::common::BusError;
+use rvsim_core::soc::devices::{Htif, Uart};
 use rvsim_core::soc::interconnect::Bus;
 use rvsim_core::soc::memory::Memory;
 use rvsim_core::soc::memory::buffer::DramBuffer;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 fn make_bus_with_ram(size: usize, base: u64) -> Bus {
     let mut bus = Bus::new(8, 1);
@@ -55,35 +58,35 @@ fn transit_time_zero_bytes() {
 #[test]
 fn ram_write_u8_read_u8() {
     let mut bus = make_bus_with_ram(4096, 0x8000_0000);
-    bus.write_u8(0x8000_0000, 0xAB);
-    assert_eq!(bus.read_u8(0x8000_0000), 0xAB);
+    bus.write_u8(0x8000_0000, 0xAB).unwrap();
+    assert_eq!(bus.read_u8(0x8000_0000).unwrap(), 0xAB);
 }
 
 #[test]
 fn ram_write_u16_read_u16() {
     let mut bus = make_bus_with_ram(4096, 0x8000_0000);
-    bus.write_u16(0x8000_0000, 0xBEEF);
-    assert_eq!(bus.read_u16(0x8000_0000), 0xBEEF);
+    bus.write_u16(0x8000_0000, 0xBEEF).unwrap();
+    assert_eq!(bus.read_u16(0x8000_0000).unwrap(), 0xBEEF);
 }
 
 #[test]
 fn ram_write_u32_read_u32() {
     let mut bus = make_bus_with_ram(4096, 0x8000_0000);
-    bus.write_u32(0x8000_0000, 0xDEAD_BEEF);
-    assert_eq!(bus.read_u32(0x8000_0000), 0xDEAD_BEEF);
+    bus.write_u32(0x8000_0000, 0xDEAD_BEEF).unwrap();
+    assert_eq!(bus.read_u32(0x8000_0000).unwrap(), 0xDEAD_BEEF);
 }
 
 #[test]
 fn ram_write_u64_read_u64() {
     let mut bus = make_bus_with_ram(4096, 0x8000_0000);
-    bus.write_u64(0x8000_0000, 0xCAFE_BABE_DEAD_BEEF);
-    assert_eq!(bus.read_u64(0x8000_0000), 0xCAFE_BABE_DEAD_BEEF);
+    bus.write_u64(0x8000_0000, 0xCAFE_BABE_DEAD_BEEF).unwrap();
+    assert_eq!(bus.read_u64(0x8000_0000).unwrap(), 0xCAFE_BABE_DEAD_BEEF);
 }
 
 #[test]
 fn ram_initial_value_zero() {
     let mut bus = make_bus_with_ram(4096, 0x8000_0000);
-    assert_eq!(bus.read_u64(0x8000_0000), 0);
+    assert_eq!(bus.read_u64(0x8000_0000).unwrap(), 0);
 }
 
 // ══════════════════════════════════════════════════════════
@@ -109,12 +112,41 @@ fn is_valid_address_outside_ram() {
 // ══════════════════════════════════════════════════════════
 
 #[test]
-fn read_unmapped_address_returns_zero() {
+fn unmapped_read_fails() {
     let mut bus = Bus::new(8, 0);
-    assert_eq!(bus.read_u8(0x1000), 0);
-    assert_eq!(bus.read_u16(0x1000), 0);
-    assert_eq!(bus.read_u32(0x1000), 0);
-    assert_eq!(bus.read_u64(0x1000), 0);
+    assert_eq!(bus.read_u8(0x1000), Err(BusError::Unmapped(0x1000)));
+    assert_eq!(bus.read_u16(0x1000), Err(BusError::Unmapped(0x1000)));
+    assert_eq!(bus.read_u32(0x1000), Err(BusError::Unmapped(0x1000)));
+    assert_eq!(bus.read_u64(0x1000), Err(BusError::Unmapped(0x1000)));
+}
+
+#[test]
+fn access_past_device_end_fails() {
+    let mut bus = make_bus_with_ram(0x1004, 0x8000_0000);
+    assert_eq!(bus.read_u32(0x8000_1000).unwrap(), 0);
+    assert_eq!(
+        bus.read_u64(0x8000_1000),
+        Err(BusError::OutOfBounds(0x8000_1000))
+    );
+    assert_eq!(
+        bus.write_u64(0x8000_1000, u64::MAX),
+        Err(BusError::OutOfBounds(0x8000_1000))
+    );
+    assert_eq!(
+        bus.check_access(0x8000_1002, 4),
+        Err(BusError::OutOfBounds(0x8000_1002))
+    );
+    assert_eq!(bus.check_access(0x8000_1000, 4), Ok(()));
+}
+
+#[test]
+fn unmapped_write_fails() {
+    let mut bus = make_bus_with_ram(4096, 0x8000_0000);
+    assert_eq!(bus.write_u32(0x1000, 1), Err(BusError::Unmapped(0x1000)));
+    assert_eq!(
+        bus.write_u64(0x8000_1000, 1),
+        Err(BusError::Unmapped(0x8000_1000))
+    );
 }
 
 // ══════════════════════════════════════════════════════════
@@ -127,10 +159,10 @@ fn load_binary_at_writes_data() {
     let data = [0xDE, 0xAD, 0xBE, 0xEF];
     bus.load_binary_at(&data, 0x8000_0000);
 
-    assert_eq!(bus.read_u8(0x8000_0000), 0xDE);
-    assert_eq!(bus.read_u8(0x8000_0001), 0xAD);
-    assert_eq!(bus.read_u8(0x8000_0002), 0xBE);
-    assert_eq!(bus.read_u8(0x8000_0003), 0xEF);
+    assert_eq!(bus.read_u8(0x8000_0000).unwrap(), 0xDE);
+    assert_eq!(bus.read_u8(0x8000_0001).unwrap(), 0xAD);
+    assert_eq!(bus.read_u8(0x8000_0002).unwrap(), 0xBE);
+    assert_eq!(bus.read_u8(0x8000_0003).unwrap(), 0xEF);
 }
 
 // ══════════════════════════════════════════════════════════
@@ -149,11 +181,66 @@ fn multiple_devices_routed_correctly() {
     let mem2 = Memory::new(buf2, 0x2000);
     bus.add_device(Box::new(mem2));
 
-    bus.write_u32(0x1000, 0xAAAA);
-    bus.write_u32(0x2000, 0xBBBB);
+    bus.write_u32(0x1000, 0xAAAA).unwrap();
+    bus.write_u32(0x2000, 0xBBBB).unwrap();
+
+    assert_eq!(bus.read_u32(0x1000).unwrap(), 0xAAAA);
+    assert_eq!(bus.read_u32(0x2000).unwrap(), 0xBBBB);
+}
+
+#[test]
+fn many_devices_with_gaps_routed_correctly() {
+    let mut bus = Bus::new(8, 0);
+    // Added out of order; the bus keeps them sorted.
+    for k in (1..=8u64).rev() {
+        let buf = Arc::new(DramBuffer::new(0x100));
+        bus.add_device(Box::new(Memory::new(buf, k * 0x1000)));
+    }
+    for k in 1..=8u64 {
+        bus.write_u32(k * 0x1000 + 0x80, k as u32).unwrap();
+    }
+    for k in 1..=8u64 {
+        assert_eq!(bus.read_u32(k * 0x1000 + 0x80).unwrap(), k as u32);
+        assert!(bus.is_valid_address(k * 0x1000 + 0xff));
+        assert!(!bus.is_valid_address(k * 0x1000 + 0x100), "gap after {k}");
+    }
+    assert!(!bus.is_valid_address(0xfff), "below the first device");
+    assert!(!bus.is_valid_address(0x9000), "above the last device");
+}
+
+#[test]
+fn htif_window_inside_ram_is_not_ram() {
+    const TOHOST: u64 = 0x8000_0100;
+    let mut bus = make_bus_with_ram(4096, 0x8000_0000);
+    let exit = Arc::new(AtomicU64::new(u64::MAX));
+    bus.add_device(Box::new(Htif::new(TOHOST, exit.clone())));
+
+    bus.write_u64(TOHOST - 8, 0x1111).unwrap();
+    bus.write_u64(TOHOST + 16, 0x2222).unwrap();
+    bus.write_u64(TOHOST, 1).unwrap();
+    assert_eq!(exit.load(Ordering::Relaxed), 0, "HTIF saw the tohost write");
+    assert_eq!(
+        bus.read_u64(TOHOST).unwrap(),
+        0,
+        "HTIF, not RAM, answers reads"
+    );
+    assert_eq!(bus.read_u64(TOHOST - 8).unwrap(), 0x1111);
+    assert_eq!(bus.read_u64(TOHOST + 16).unwrap(), 0x2222);
+}
 
-    assert_eq!(bus.read_u32(0x1000), 0xAAAA);
-    assert_eq!(bus.read_u32(0x2000), 0xBBBB);
+#[test]
+fn ram_and_mmio_interleaved() {
+    const UART: u64 = 0x1000_0000;
+    let mut bus = make_bus_with_ram(4096, 0x8000_0000);
+    bus.add_device(Box::new(Uart::new(UART, false, true)));
+    for i in 0..4u64 {
+        bus.write_u32(0x8000_0000 + i * 4, i as u32 + 1).unwrap();
+        // LSR: transmitter empty.
+        assert_eq!(bus.read_u8(UART + 5).unwrap() & 0x60, 0x60);
+    }
+    for i in 0..4u64 {
+        assert_eq!(bus.read_u32(0x8000_0000 + i * 4).unwrap(), i as u32 + 1);
+    }
 }
 
 // ══════════════════════════════════════════════════════════
@@ -175,3 +262,36 @@ fn get_ram_info_none_when_no_ram() {
     let mut bus = Bus::new(8, 0);
     assert!(bus.get_ram_info().is_none());
 }
+
+// ══════════════════════════════════════════════════════════
+// 8. Memory map
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn memory_map_lists_devices_in_address_order() {
+    let mut bus = make_bus_with_ram(4096, 0x8000_0000);
+    bus.add_device(Box::new(Uart::new(0x1000_0000, false, true)));
+
+    let map = bus.memory_map();
+    assert_eq!(
+        map,
+        vec![
+            ("UART0".to_string(), 0x1000_0000, 0x1000_0100),
+            ("DRAM".to_string(), 0x8000_0000, 0x8000_1000),
+        ]
+    );
+}
+
+#[test]
+fn memory_map_matches_system_config() {
+    let config = rvsim_core::config::Config::default();
+    let system = rvsim_core::soc::System::new(&config, "");
+    let map = system.bus.memory_map();
+
+    let clint = map.iter().find(|(name, ..)| name == "CLINT").unwrap();
+    assert_eq!(clint.1, config.system.clint_base);
+    assert!(
+        map.windows(2).all(|w| w[0].2 <= w[1].1),
+        "sorted, no overlap"
+    );
+}
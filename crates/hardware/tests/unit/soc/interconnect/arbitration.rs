@@ -16,16 +16,17 @@ fn bus_tick_propagates_to_clint() {
     bus.add_device(Box::new(clint));
 
     // Write mtimecmp = 3
-    bus.write_u64(0x200_0000 + 0x4000, 3);
+    bus.write_u64(0x200_0000 + 0x4000, 3).unwrap();
 
     // Tick 3 times → mtime reaches 3, should trigger timer
     let (t1, _, _) = bus.tick();
     let (t2, _, _) = bus.tick();
     let (t3, _, _) = bus.tick();
 
-    assert!(!t1);
-    assert!(!t2);
-    assert!(t3, "Timer should fire on 3rd tick");
+    assert!(!t1.mtip(0));
+    assert!(!t2.mtip(0));
+    assert!(t3.mtip(0), "Timer should fire on 3rd tick");
+    assert!(!t3.msip(0));
 }
 
 #[test]
@@ -36,10 +37,10 @@ fn bus_last_device_cache_hit() {
     bus.add_device(Box::new(mem));
 
     // First access primes the cache
-    bus.write_u32(0x8000_0000, 0x1234);
+    bus.write_u32(0x8000_0000, 0x1234).unwrap();
     // Second access should hit the cache
-    assert_eq!(bus.read_u32(0x8000_0000), 0x1234);
+    assert_eq!(bus.read_u32(0x8000_0000).unwrap(), 0x1234);
     // Nearby address should also hit cache
-    bus.write_u32(0x8000_0004, 0x5678);
-    assert_eq!(bus.read_u32(0x8000_0004), 0x5678);
+    bus.write_u32(0x8000_0004, 0x5678).unwrap();
+    assert_eq!(bus.read_u32(0x8000_0004).unwrap(), 0x5678);
 }
@@ -0,0 +1,112 @@
+//! Clock-Domain-Crossing Latency Tests.
+//!
+//! Verifies that `system.cdc_latency` adds a per-device synchronizer cost on
+//! top of the bus transit time, both in the bus model and for a UART access
+//! issued by the pipeline.
+
+use crate::common::builder::instruction::InstructionBuilder;
+use crate::common::harness::TestContext;
+use rvsim_core::config::Config;
+use rvsim_core::soc::System;
+use rvsim_core::soc::devices::Uart;
+use rvsim_core::soc::interconnect::Bus;
+
+const UART_BASE: u64 = 0x1000_0000;
+const UART_CDC: u64 = 10;
+
+// ══════════════════════════════════════════════════════════
+// 1. Bus model
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn device_without_cdc_costs_transit_only() {
+    let mut bus = Bus::new(8, 2);
+    bus.add_device(Box::new(Uart::new(UART_BASE, false, true)));
+    assert_eq!(
+        bus.device_access_time(UART_BASE, 4),
+        bus.calculate_transit_time(4)
+    );
+}
+
+#[test]
+fn cdc_applies_to_whole_device_range() {
+    let mut bus = Bus::new(8, 2);
+    bus.add_device(Box::new(Uart::new(UART_BASE, false, true)));
+    bus.set_cdc_latency(UART_BASE, UART_CDC);
+
+    let transit = bus.calculate_transit_time(1);
+    assert_eq!(bus.device_access_time(UART_BASE, 1), transit + UART_CDC);
+    assert_eq!(bus.device_access_time(UART_BASE + 5, 1), transit + UART_CDC);
+    assert_eq!(
+        bus.device_access_time(0x2000_0000, 1),
+        transit,
+        "unmapped address has no CDC"
+    );
+}
+
+#[test]
+fn setting_cdc_again_replaces_previous_value() {
+    let mut bus = Bus::new(8, 0);
+    bus.add_device(Box::new(Uart::new(UART_BASE, false, true)));
+    bus.set_cdc_latency(UART_BASE, 7);
+    bus.set_cdc_latency(UART_BASE, 3);
+    assert_eq!(bus.device_access_time(UART_BASE, 8), 1 + 3);
+    bus.set_cdc_latency(UART_BASE, 0);
+    assert_eq!(bus.device_access_time(UART_BASE, 8), 1);
+}
+
+#[test]
+fn system_applies_configured_cdc_latency() {
+    let mut config = Config::default();
+    config.system.cdc_latency.uart = UART_CDC;
+    let system = System::new(&config, "");
+
+    let transit = system.bus.calculate_transit_time(1);
+    assert_eq!(
+        system.bus.device_access_time(config.system.uart_base, 1),
+        transit + UART_CDC
+    );
+    assert_eq!(
+        system.bus.device_access_time(config.system.clint_base, 1),
+        transit,
+        "other devices keep their default of zero"
+    );
+}
+
+// ══════════════════════════════════════════════════════════
+// 2. Pipeline UART access
+// ══════════════════════════════════════════════════════════
+
+/// Runs `loads` UART loads with the given CDC latency and returns the context.
+fn run_uart_loads(loads: usize, cdc: u64) -> TestContext {
+    let mut program = vec![InstructionBuilder::new().lui(5, 0x10000).build()];
+    program.extend(std::iter::repeat_n(
+        InstructionBuilder::new().lw(6, 5, 0).build(),
+        loads,
+    ));
+    let mut tc = TestContext::program(&Config::default(), &program);
+    let bus = &mut tc.cpu_mut().bus.bus;
+    bus.add_device(Box::new(Uart::new(UART_BASE, false, true)));
+    bus.set_cdc_latency(UART_BASE, cdc);
+    tc.run_to_exit();
+    tc
+}
+
+#[test]
+fn uart_access_costs_transit_plus_cdc() {
+    let tc = run_uart_loads(1, UART_CDC);
+    let stats = &tc.cpu().stats;
+    let transit = tc.cpu().bus.bus.calculate_transit_time(4);
+    assert_eq!(stats.max_mem_latency_addr, UART_BASE);
+    assert_eq!(stats.max_mem_latency, transit + UART_CDC);
+}
+
+#[test]
+fn cdc_latency_adds_cycles_per_uart_access() {
+    let marginal = |cdc| {
+        let shorter = run_uart_loads(3, cdc).cpu().stats.cycles;
+        let longer = run_uart_loads(4, cdc).cpu().stats.cycles;
+        longer - shorter
+    };
+    assert_eq!(marginal(UART_CDC) - marginal(0), UART_CDC);
+}
@@ -1,2 +1,4 @@
+pub mod access_log;
 pub mod address_map;
 pub mod arbitration;
+pub mod cdc_latency;
@@ -1,8 +1,10 @@
 //! DRAM Buffer Unit Tests.
 //!
 //! Verifies allocation, read/write at byte and slice level,
-//! indexing, and boundary checks.
+//! indexing, boundary checks, and fill patterns.
 
+use rvsim_core::config::Config;
+use rvsim_core::soc::System;
 use rvsim_core::soc::memory::buffer::DramBuffer;
 
 // ══════════════════════════════════════════════════════════
@@ -133,3 +135,28 @@ fn buffer_overwrite_slice() {
     buf.write_slice(0, &[5, 6, 7, 8]);
     assert_eq!(buf.read_slice(0, 4), &[5, 6, 7, 8]);
 }
+
+// ══════════════════════════════════════════════════════════
+// 8. Fill pattern
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn buffer_fill_repeats_pattern() {
+    let buf = DramBuffer::new(10);
+    buf.fill(0xDEAD_BEEF);
+    assert_eq!(
+        buf.read_slice(0, 10),
+        &[0xEF, 0xBE, 0xAD, 0xDE, 0xEF, 0xBE, 0xAD, 0xDE, 0xEF, 0xBE]
+    );
+}
+
+#[test]
+fn system_ram_uses_configured_fill() {
+    let mut config = Config::default();
+    config.memory.ram_size = 0x1000;
+    config.memory.fill_pattern = 0xDEAD_BEEF;
+    let mut system = System::new(&config, "");
+    let base = config.system.ram_base;
+    assert_eq!(system.bus.read_u32(base).unwrap(), 0xDEAD_BEEF);
+    assert_eq!(system.bus.read_u32(base + 0xFFC).unwrap(), 0xDEAD_BEEF);
+}
@@ -1,9 +1,23 @@
 //! Memory Controller Unit Tests.
 //!
 //! Verifies SimpleController (fixed latency) and DramController
-//! (row-buffer-aware latency with CAS/RAS/precharge).
+//! (row-buffer-aware latency with CAS/RAS/precharge, bank/channel
+//! interleaving, bank-level parallelism, FR-FCFS request scheduling, and
+//! separate write timing, open- vs closed-page row buffer policy, and
+//! periodic refresh).
 
-use rvsim_core::soc::memory::controller::{DramController, MemoryController, SimpleController};
+use rvsim_core::config::PagePolicy;
+use rvsim_core::soc::memory::controller::{
+    DramController, MemRequest, MemoryController, SimpleController,
+};
+
+fn read(addr: u64, arrival: u64) -> MemRequest {
+    MemRequest {
+        addr,
+        is_write: false,
+        arrival,
+    }
+}
 
 // ══════════════════════════════════════════════════════════
 // 1. SimpleController
@@ -12,23 +26,23 @@ use rvsim_core::soc::memory::controller::{DramController, MemoryController, Simp
 #[test]
 fn simple_controller_fixed_latency() {
     let mut ctrl = SimpleController::new(10);
-    assert_eq!(ctrl.access_latency(0x1000), 10);
-    assert_eq!(ctrl.access_latency(0x2000), 10);
-    assert_eq!(ctrl.access_latency(0x3000), 10);
+    assert_eq!(ctrl.access_latency(0x1000, false, 0), 10);
+    assert_eq!(ctrl.access_latency(0x2000, false, 100), 10);
+    assert_eq!(ctrl.access_latency(0x3000, false, 200), 10);
 }
 
 #[test]
 fn simple_controller_zero_latency() {
     let mut ctrl = SimpleController::new(0);
-    assert_eq!(ctrl.access_latency(0), 0);
+    assert_eq!(ctrl.access_latency(0, false, 0), 0);
 }
 
 #[test]
 fn simple_controller_address_independent() {
     let mut ctrl = SimpleController::new(5);
     // Same latency regardless of address
-    assert_eq!(ctrl.access_latency(0), 5);
-    assert_eq!(ctrl.access_latency(u64::MAX), 5);
+    assert_eq!(ctrl.access_latency(0, false, 0), 5);
+    assert_eq!(ctrl.access_latency(u64::MAX, false, 100), 5);
 }
 
 // ══════════════════════════════════════════════════════════
@@ -39,7 +53,7 @@ fn simple_controller_address_independent() {
 fn dram_cold_start_latency() {
     let mut ctrl = DramController::new(5, 10, 8);
     // First access: no row open → t_ras + t_cas = 10 + 5 = 15
-    assert_eq!(ctrl.access_latency(0x1000), 15);
+    assert_eq!(ctrl.access_latency(0x1000, false, 0), 15);
 }
 
 // ══════════════════════════════════════════════════════════
@@ -50,20 +64,20 @@ fn dram_cold_start_latency() {
 fn dram_row_buffer_hit() {
     let mut ctrl = DramController::new(5, 10, 8);
     // First access opens row
-    ctrl.access_latency(0x1000);
+    ctrl.access_latency(0x1000, false, 0);
     // Second access to same row → t_cas = 5
-    assert_eq!(ctrl.access_latency(0x1004), 5);
+    assert_eq!(ctrl.access_latency(0x1004, false, 100), 5);
 }
 
 #[test]
 fn dram_row_buffer_hit_multiple() {
     let mut ctrl = DramController::new(5, 10, 8);
-    ctrl.access_latency(0x2000);
+    ctrl.access_latency(0x2000, false, 0);
     // Multiple accesses within the same row (row_mask = !2047, so row = addr & ~0x7FF)
     // 0x2000 and 0x2100 are in the same row (both & !0x7FF = 0x2000)
-    assert_eq!(ctrl.access_latency(0x2100), 5);
-    assert_eq!(ctrl.access_latency(0x2200), 5);
-    assert_eq!(ctrl.access_latency(0x27FF), 5);
+    assert_eq!(ctrl.access_latency(0x2100, false, 100), 5);
+    assert_eq!(ctrl.access_latency(0x2200, false, 200), 5);
+    assert_eq!(ctrl.access_latency(0x27FF, false, 300), 5);
 }
 
 // ══════════════════════════════════════════════════════════
@@ -74,19 +88,19 @@ fn dram_row_buffer_hit_multiple() {
 fn dram_row_buffer_miss() {
     let mut ctrl = DramController::new(5, 10, 8);
     // First access: cold start
-    ctrl.access_latency(0x1000);
+    ctrl.access_latency(0x1000, false, 0);
     // Access different row → t_pre + t_ras + t_cas = 8 + 10 + 5 = 23
-    assert_eq!(ctrl.access_latency(0x2800), 23);
+    assert_eq!(ctrl.access_latency(0x2800, false, 100), 23);
 }
 
 #[test]
 fn dram_row_switch_back() {
     let mut ctrl = DramController::new(5, 10, 8);
-    ctrl.access_latency(0x1000); // cold: 15
-    ctrl.access_latency(0x1004); // hit: 5
-    ctrl.access_latency(0x2800); // miss: 23
-    ctrl.access_latency(0x2804); // hit: 5
-    assert_eq!(ctrl.access_latency(0x1000), 23); // miss again
+    ctrl.access_latency(0x1000, false, 0); // cold: 15
+    ctrl.access_latency(0x1004, false, 100); // hit: 5
+    ctrl.access_latency(0x2800, false, 200); // miss: 23
+    ctrl.access_latency(0x2804, false, 300); // hit: 5
+    assert_eq!(ctrl.access_latency(0x1000, false, 400), 23); // miss again
 }
 
 // ══════════════════════════════════════════════════════════
@@ -99,9 +113,9 @@ fn dram_row_boundary_exact() {
     // row_mask = !2047 = 0xFFFF_FFFF_FFFF_F800
     // Row 0: [0x0000, 0x07FF]
     // Row 1: [0x0800, 0x0FFF]
-    ctrl.access_latency(0x07FF); // row 0
+    ctrl.access_latency(0x07FF, false, 0); // row 0
     assert_eq!(
-        ctrl.access_latency(0x0800),
+        ctrl.access_latency(0x0800, false, 100),
         23,
         "0x0800 should be a different row"
     );
@@ -114,15 +128,329 @@ fn dram_row_boundary_exact() {
 #[test]
 fn dram_low_latency() {
     let mut ctrl = DramController::new(1, 2, 1);
-    assert_eq!(ctrl.access_latency(0), 3); // cold: ras+cas = 3
-    assert_eq!(ctrl.access_latency(0), 1); // hit: cas = 1
-    assert_eq!(ctrl.access_latency(0x1000), 4); // miss: pre+ras+cas = 4
+    assert_eq!(ctrl.access_latency(0, false, 0), 3); // cold: ras+cas = 3
+    assert_eq!(ctrl.access_latency(0, false, 100), 1); // hit: cas = 1
+    assert_eq!(ctrl.access_latency(0x1000, false, 200), 4); // miss: pre+ras+cas = 4
 }
 
 #[test]
 fn dram_high_latency() {
     let mut ctrl = DramController::new(20, 40, 30);
-    assert_eq!(ctrl.access_latency(0), 60); // cold: 40+20
-    assert_eq!(ctrl.access_latency(0), 20); // hit
-    assert_eq!(ctrl.access_latency(0x1000), 90); // miss: 30+40+20
+    assert_eq!(ctrl.access_latency(0, false, 0), 60); // cold: 40+20
+    assert_eq!(ctrl.access_latency(0, false, 100), 20); // hit
+    assert_eq!(ctrl.access_latency(0x1000, false, 200), 90); // miss: 30+40+20
+}
+
+// ══════════════════════════════════════════════════════════
+// 7. DramController: Banks, channels, and contention
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn dram_busy_bank_serializes_accesses() {
+    let mut ctrl = DramController::new(5, 10, 8);
+    assert_eq!(ctrl.access_latency(0x1000, false, 0), 15);
+    // Same bank, issued at cycle 1: waits until cycle 15, then a row hit.
+    assert_eq!(ctrl.access_latency(0x1004, false, 1), 14 + 5);
+}
+
+#[test]
+fn dram_different_banks_overlap() {
+    // 2 banks, 1 channel, 2 KiB interleaving: 0x0000 -> bank 0, 0x0800 -> bank 1
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 2, 1, 2048);
+    assert_eq!(ctrl.access_latency(0x0000, false, 0), 15);
+    assert_eq!(
+        ctrl.access_latency(0x0800, false, 1),
+        15,
+        "an idle bank should not wait for the busy one"
+    );
+}
+
+#[test]
+fn dram_banks_keep_independent_rows() {
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 2, 1, 2048);
+    ctrl.access_latency(0x0000, false, 0); // bank 0, row 0
+    ctrl.access_latency(0x0800, false, 100); // bank 1, row 0
+    // Both rows are still open.
+    assert_eq!(ctrl.access_latency(0x0010, false, 200), 5);
+    assert_eq!(ctrl.access_latency(0x0810, false, 300), 5);
+    // 0x1000 maps to bank 0, row 1: conflicts only with bank 0's row.
+    assert_eq!(ctrl.access_latency(0x1000, false, 400), 23);
+    assert_eq!(ctrl.access_latency(0x0810, false, 500), 5);
+}
+
+#[test]
+fn dram_channels_interleave_before_banks() {
+    // 2 channels x 2 banks, 64-byte blocks: channel = block % 2, bank = (block / 2) % 2
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 2, 2, 64);
+    let latencies: Vec<u64> = [0x00, 0x40, 0x80, 0xC0]
+        .iter()
+        .map(|&addr| ctrl.access_latency(addr, false, 0))
+        .collect();
+    assert_eq!(
+        latencies,
+        [15, 15, 15, 15],
+        "four distinct banks in parallel"
+    );
+    // The fifth block wraps to channel 0, bank 0 (same row), which is busy.
+    assert_eq!(ctrl.access_latency(0x100, false, 0), 15 + 5);
+}
+
+#[test]
+fn dram_row_excludes_interleave_bits() {
+    // With 64-byte interleaving over 4 banks, a bank's 2 KiB row spans 8 KiB
+    // of the address space.
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 4, 1, 64);
+    ctrl.access_latency(0x0000, false, 0);
+    assert_eq!(
+        ctrl.access_latency(0x1F00, false, 100),
+        5,
+        "same bank-local row"
+    );
+    assert_eq!(
+        ctrl.access_latency(0x2000, false, 200),
+        23,
+        "next row of bank 0"
+    );
+}
+
+#[test]
+fn dram_zero_geometry_is_clamped() {
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 0, 0, 0);
+    assert_eq!(ctrl.access_latency(0x1000, false, 0), 15);
+    assert_eq!(ctrl.access_latency(0x1000, false, 100), 5);
+}
+
+// ══════════════════════════════════════════════════════════
+// 8. Request scheduling
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn simple_controller_schedules_fcfs() {
+    let mut ctrl = SimpleController::new(10);
+    let done = ctrl.schedule(&[read(0x1000, 5), read(0x2000, 0)]);
+    assert_eq!(done, [15, 10]);
+}
+
+#[test]
+fn dram_schedule_prioritizes_row_hits() {
+    let mut ctrl = DramController::new(5, 10, 8);
+    // Row 1 opens first; the row-2 request is older than the second row-1
+    // request, but both are waiting when the bank frees up at cycle 15.
+    let done = ctrl.schedule(&[read(0x0800, 0), read(0x1000, 1), read(0x0840, 2)]);
+    assert_eq!(done[0], 15);
+    assert_eq!(done[2], 15 + 5, "row hit served first");
+    assert_eq!(done[1], 20 + 23, "row conflict served last");
+}
+
+#[test]
+fn dram_schedule_prefers_reads_over_writes() {
+    let mut ctrl = DramController::new(5, 10, 8);
+    let write = MemRequest {
+        addr: 0x0010,
+        is_write: true,
+        arrival: 1,
+    };
+    let done = ctrl.schedule(&[read(0x0000, 0), write, read(0x0020, 2)]);
+    assert_eq!(done, [15, 25, 20]);
+}
+
+#[test]
+fn dram_schedule_waits_for_arrival() {
+    let mut ctrl = DramController::new(5, 10, 8);
+    // The row hit arrives after the bank is free, so the conflicting
+    // request (already waiting) goes first.
+    let done = ctrl.schedule(&[read(0x0000, 0), read(0x1000, 10), read(0x0010, 20)]);
+    assert_eq!(done, [15, 15 + 23, 38 + 23]);
+}
+
+#[test]
+fn dram_schedule_overlaps_independent_banks() {
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 4, 1, 64);
+    let burst: Vec<MemRequest> = (0..4).map(|i| read(i * 64, 0)).collect();
+    assert_eq!(
+        ctrl.schedule(&burst),
+        [15, 15, 15, 15],
+        "misses to different banks complete together, not in sequence"
+    );
+}
+
+#[test]
+fn dram_schedule_matches_access_latency() {
+    let mut batched = DramController::new(5, 10, 8);
+    let mut single = DramController::new(5, 10, 8);
+    let done = batched.schedule(&[read(0x0000, 0), read(0x1000, 100), read(0x1010, 200)]);
+    let latencies: Vec<u64> = [(0x0000, 0), (0x1000, 100), (0x1010, 200)]
+        .iter()
+        .map(|&(addr, now)| now + single.access_latency(addr, false, now))
+        .collect();
+    assert_eq!(done, latencies);
+}
+
+// ══════════════════════════════════════════════════════════
+// 9. Write timing
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn simple_controller_ignores_direction() {
+    let mut ctrl = SimpleController::new(10);
+    assert_eq!(ctrl.access_latency(0x1000, true, 0), 10);
+    assert_eq!(ctrl.access_latency(0x1000, false, 100), 10);
+}
+
+#[test]
+fn dram_writes_timed_like_reads_by_default() {
+    let mut ctrl = DramController::new(5, 10, 8);
+    assert_eq!(ctrl.access_latency(0x1000, true, 0), 15);
+    assert_eq!(ctrl.access_latency(0x1010, false, 100), 5);
+    assert_eq!(ctrl.access_latency(0x1020, true, 200), 5);
+}
+
+#[test]
+fn dram_write_uses_cwl() {
+    let mut ctrl = DramController::new(5, 10, 8).with_write_timing(3, 0, 0);
+    assert_eq!(ctrl.access_latency(0x1000, true, 0), 10 + 3);
+    assert_eq!(ctrl.access_latency(0x1010, true, 100), 3, "row hit");
+    assert_eq!(ctrl.access_latency(0x1020, false, 200), 5, "reads keep CAS");
+}
+
+#[test]
+fn dram_write_recovery_keeps_bank_busy() {
+    let mut ctrl = DramController::new(5, 10, 8).with_write_timing(5, 6, 0);
+    assert_eq!(ctrl.access_latency(0x1000, true, 0), 15);
+    // The bank frees up at 15 + 6, then serves a row hit.
+    assert_eq!(ctrl.access_latency(0x1010, false, 15), 6 + 5);
+}
+
+#[test]
+fn dram_write_to_read_turnaround_spans_the_channel() {
+    // 2 banks, 1 channel: 0x0000 -> bank 0, 0x0800 -> bank 1
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 2, 1, 2048).with_write_timing(5, 0, 4);
+    assert_eq!(ctrl.access_latency(0x0000, true, 0), 15);
+    // An idle bank still waits for the channel to turn around.
+    assert_eq!(ctrl.access_latency(0x0800, false, 1), 15 + 4 + 15 - 1);
+}
+
+#[test]
+fn dram_write_to_read_turnaround_skips_writes_and_other_channels() {
+    // 2 channels x 2 banks, 64-byte blocks
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 2, 2, 64).with_write_timing(5, 0, 4);
+    assert_eq!(ctrl.access_latency(0x00, true, 0), 15);
+    assert_eq!(ctrl.access_latency(0x40, false, 0), 15, "other channel");
+    assert_eq!(
+        ctrl.access_latency(0x80, true, 0),
+        15,
+        "write in the same channel"
+    );
+}
+
+#[test]
+fn dram_schedule_applies_turnaround_to_batched_reads() {
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 2, 1, 2048).with_write_timing(5, 0, 4);
+    let write = MemRequest {
+        addr: 0x0000,
+        is_write: true,
+        arrival: 0,
+    };
+    // Different banks, so only the turnaround delays the read.
+    let done = ctrl.schedule(&[write, read(0x0800, 1)]);
+    assert_eq!(done, [15, 19 + 15]);
+}
+
+// ══════════════════════════════════════════════════════════
+// 10. Page policy
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn page_policy_defaults_to_open() {
+    assert_eq!(PagePolicy::default(), PagePolicy::Open);
+    let mut ctrl = DramController::new(5, 10, 8).with_page_policy(PagePolicy::Open);
+    ctrl.access_latency(0x1000, false, 0);
+    assert_eq!(ctrl.access_latency(0x1010, false, 100), 5, "row hit");
+}
+
+#[test]
+fn closed_page_every_access_activates() {
+    let mut ctrl = DramController::new(5, 10, 8).with_page_policy(PagePolicy::Closed);
+    assert_eq!(ctrl.access_latency(0x1000, false, 0), 15);
+    assert_eq!(
+        ctrl.access_latency(0x1010, false, 100),
+        15,
+        "same row, no hit"
+    );
+    assert_eq!(
+        ctrl.access_latency(0x2000, false, 200),
+        15,
+        "other row, no precharge"
+    );
+}
+
+#[test]
+fn closed_page_precharge_keeps_bank_busy() {
+    let mut ctrl = DramController::new(5, 10, 8).with_page_policy(PagePolicy::Closed);
+    assert_eq!(ctrl.access_latency(0x1000, false, 0), 15);
+    // The bank precharges until 15 + 8 before the next activate.
+    assert_eq!(ctrl.access_latency(0x1010, false, 15), 8 + 15);
+}
+
+#[test]
+fn closed_page_random_access_beats_open_page() {
+    let rows = [0x0000, 0x1000, 0x2000, 0x3000];
+    let total = |policy| {
+        let mut ctrl = DramController::new(5, 10, 8).with_page_policy(policy);
+        rows.iter()
+            .enumerate()
+            .map(|(i, &addr)| ctrl.access_latency(addr, false, i as u64 * 100))
+            .sum::<u64>()
+    };
+    assert_eq!(total(PagePolicy::Open), 15 + 3 * 23);
+    assert_eq!(total(PagePolicy::Closed), 4 * 15);
+}
+
+// ══════════════════════════════════════════════════════════
+// 11. Refresh
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn refresh_disabled_by_default() {
+    let mut ctrl = DramController::new(5, 10, 8);
+    ctrl.access_latency(0x1000, false, 0);
+    assert_eq!(ctrl.access_latency(0x1010, false, 1_000_000), 5);
+}
+
+#[test]
+fn access_during_refresh_waits_for_it() {
+    let mut ctrl = DramController::new(5, 10, 8).with_refresh(100, 20);
+    // Refresh runs from cycle 100 to 120.
+    assert_eq!(ctrl.access_latency(0x1000, false, 105), 15 + 15);
+}
+
+#[test]
+fn access_between_refreshes_is_not_delayed() {
+    let mut ctrl = DramController::new(5, 10, 8).with_refresh(100, 20);
+    assert_eq!(ctrl.access_latency(0x1000, false, 0), 15, "no refresh at 0");
+    assert_eq!(ctrl.access_latency(0x1010, false, 50), 5, "row still open");
+    assert_eq!(
+        ctrl.access_latency(0x1020, false, 120),
+        15,
+        "refresh closed it"
+    );
+}
+
+#[test]
+fn refresh_delays_queued_accesses() {
+    let mut ctrl = DramController::new(5, 10, 8).with_refresh(100, 20);
+    ctrl.access_latency(0x1000, false, 90);
+    // The bank frees up at 105, mid-refresh; the row hit starts at 120 but
+    // the refresh has closed the row.
+    assert_eq!(ctrl.access_latency(0x1010, false, 91), 120 + 15 - 91);
+}
+
+#[test]
+fn refresh_stalls_every_bank() {
+    let mut ctrl = DramController::with_geometry(5, 10, 8, 2, 2, 64).with_refresh(100, 20);
+    let latencies: Vec<u64> = [0x00, 0x40, 0x80, 0xC0]
+        .iter()
+        .map(|&addr| ctrl.access_latency(addr, false, 210))
+        .collect();
+    assert_eq!(latencies, [10 + 15; 4]);
 }
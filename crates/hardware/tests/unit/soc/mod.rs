@@ -1,5 +1,6 @@
 pub mod clint_timer;
 pub mod devices;
+pub mod framebuffer;
 pub mod goldfish_rtc;
 pub mod interconnect;
 pub mod memory;
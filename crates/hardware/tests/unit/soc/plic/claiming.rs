@@ -50,3 +50,105 @@ fn plic_tick_no_interrupt() {
     plic.update_irqs(0);
     assert!(!plic.tick());
 }
+
+// ══════════════════════════════════════════════════════════
+// Multiple contexts
+// ══════════════════════════════════════════════════════════
+
+/// Offset of a context's first enable word.
+fn enable(ctx: u64) -> u64 {
+    0x2000 + ctx * 0x80
+}
+
+/// Offset of a context's threshold register (claim/complete is at +4).
+fn context(ctx: u64) -> u64 {
+    0x200000 + ctx * 0x1000
+}
+
+#[test]
+fn plic_default_has_two_contexts() {
+    assert_eq!(Plic::new(0).num_contexts(), 2);
+    assert_eq!(Plic::with_contexts(0, 0).num_contexts(), 1);
+}
+
+#[test]
+fn plic_contexts_signal_separately() {
+    // Two harts: contexts 0/1 for hart 0, 2/3 for hart 1.
+    let mut plic = Plic::with_contexts(0, 4);
+    plic.write_u32(4 * 5, 1);
+    plic.write_u32(enable(3), 1 << 5);
+    plic.update_irqs(1 << 5);
+
+    assert!(plic.tick());
+    assert_eq!(plic.hart_interrupts(0), (false, false));
+    assert_eq!(plic.hart_interrupts(1), (false, true));
+    assert!(plic.context_interrupt(3));
+    assert!(!plic.context_interrupt(4), "unknown context");
+}
+
+#[test]
+fn plic_contexts_pick_their_own_highest_priority() {
+    let mut plic = Plic::with_contexts(0, 4);
+    plic.write_u32(4 * 2, 3);
+    plic.write_u32(4 * 7, 6);
+    plic.write_u32(enable(0), (1 << 2) | (1 << 7));
+    plic.write_u32(enable(2), 1 << 2);
+    plic.update_irqs((1 << 2) | (1 << 7));
+    plic.tick();
+
+    assert_eq!(plic.read_u32(context(2) + 4), 2);
+    assert_eq!(plic.read_u32(context(0) + 4), 7);
+}
+
+#[test]
+fn plic_thresholds_are_per_context() {
+    let mut plic = Plic::with_contexts(0, 4);
+    plic.write_u32(4 * 9, 2);
+    plic.write_u32(enable(0), 1 << 9);
+    plic.write_u32(enable(2), 1 << 9);
+    plic.write_u32(context(2), 5);
+    assert_eq!(plic.read_u32(context(2)), 5);
+    plic.update_irqs(1 << 9);
+    plic.tick();
+
+    assert!(plic.context_interrupt(0));
+    assert!(
+        !plic.context_interrupt(2),
+        "masked by context 2's threshold"
+    );
+}
+
+#[test]
+fn plic_claim_is_independent_per_context() {
+    let mut plic = Plic::with_contexts(0, 4);
+    plic.write_u32(4 * 4, 1);
+    plic.write_u32(4 * 6, 1);
+    plic.write_u32(enable(1), 1 << 4);
+    plic.write_u32(enable(3), 1 << 6);
+    plic.update_irqs((1 << 4) | (1 << 6));
+    plic.tick();
+
+    assert_eq!(plic.read_u32(context(3) + 4), 6);
+    plic.tick();
+    assert!(
+        plic.context_interrupt(1),
+        "claim by context 3 leaves context 1 pending"
+    );
+    assert_eq!(plic.read_u32(context(1) + 4), 4);
+}
+
+#[test]
+fn plic_enables_above_irq_31_are_per_context() {
+    let mut plic = Plic::with_contexts(0, 4);
+    plic.write_u32(4 * 33, 1);
+    // IRQ 33 lives in the second enable word of each context.
+    plic.write_u32(enable(2) + 4, 1 << 1);
+    assert_eq!(plic.read_u32(enable(2) + 4), 1 << 1);
+    assert_eq!(plic.read_u32(enable(0) + 4), 0);
+    plic.update_irqs(1 << 33);
+    plic.tick();
+
+    assert!(plic.context_interrupt(2));
+    assert!(!plic.context_interrupt(0));
+    assert_eq!(plic.read_u32(context(2) + 4), 33);
+}
@@ -5,7 +5,7 @@
 //! registers.
 
 use rvsim_core::soc::devices::Device;
-use rvsim_core::soc::devices::syscon::SysCon;
+use rvsim_core::soc::devices::syscon::{RESET_REQUEST, SysCon};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -98,8 +98,8 @@ fn test_syscon_reset_signal() {
     // Write reset magic value (0x7777) to offset 0
     syscon.write_u32(0, 0x7777);
 
-    // Exit signal should be set to 0 (simulated as exit)
-    assert_eq!(exit_signal.load(Ordering::Relaxed), 0);
+    // Reset is requested rather than an exit
+    assert_eq!(exit_signal.load(Ordering::Relaxed), RESET_REQUEST);
 }
 
 #[test]
@@ -176,7 +176,7 @@ fn test_syscon_all_magic_values() {
     let exit_signal = Arc::new(AtomicU64::new(u64::MAX));
     let mut syscon = SysCon::new(base_addr, exit_signal.clone());
     syscon.write_u32(0, 0x7777);
-    assert_eq!(exit_signal.load(Ordering::Relaxed), 0);
+    assert_eq!(exit_signal.load(Ordering::Relaxed), RESET_REQUEST);
 
     // Test failure
     let exit_signal = Arc::new(AtomicU64::new(u64::MAX));
@@ -214,8 +214,10 @@ fn uart_iir_no_interrupt_pending() {
 #[test]
 fn uart_iir_fifo_enabled() {
     let mut uart = Uart::new(0, true, true);
-    let _iir = uart.read_u8(2);
-    // Bits 6-7 should indicate FIFO status
+    // Bits 6-7 indicate FIFO status
+    assert_eq!(uart.read_u8(2) & 0xC0, 0);
+    uart.write_u8(2, 0x01); // FCR: enable FIFOs
+    assert_eq!(uart.read_u8(2) & 0xC0, 0xC0);
 }
 
 // ══════════════════════════════════════════════════════════
@@ -470,3 +472,76 @@ fn uart_scratch_pattern() {
     uart.write_u8(7, 0xAA);
     assert_eq!(uart.read_u8(7), 0xAA);
 }
+
+// ══════════════════════════════════════════════════════════
+// Receive, Interrupt, and Loopback Tests
+// ══════════════════════════════════════════════════════════
+
+#[test]
+fn uart_receive_sets_data_ready() {
+    let mut uart = Uart::new(0, true, true);
+    uart.push_input(b"hi");
+    assert_eq!(uart.read_u8(5) & 0x01, 0x01);
+    assert_eq!(uart.read_u8(0), b'h');
+    assert_eq!(uart.read_u8(0), b'i');
+    assert_eq!(uart.read_u8(5) & 0x01, 0, "FIFO drained");
+}
+
+#[test]
+fn uart_rx_interrupt_follows_ier() {
+    let mut uart = Uart::new(0, true, true);
+    uart.push_input(b"x");
+    assert!(!uart.tick(), "RX interrupt disabled in IER");
+
+    uart.write_u8(1, 0x01); // IER: RDA
+    assert!(uart.tick());
+    assert_eq!(uart.read_u8(2) & 0x0F, 0x04, "IIR reports received data");
+
+    uart.read_u8(0);
+    assert!(!uart.tick(), "reading RBR clears the interrupt");
+}
+
+#[test]
+fn uart_thre_interrupt_cleared_by_iir_read() {
+    let mut uart = Uart::new(0, true, true);
+    uart.write_u8(1, 0x02); // IER: THRE
+    assert!(uart.tick());
+    assert_eq!(uart.read_u8(2) & 0x0F, 0x02);
+    assert!(!uart.tick());
+
+    uart.write_u8(0, b'a');
+    assert!(uart.tick(), "THR empties again after a write");
+}
+
+#[test]
+fn uart_rx_interrupt_has_priority_over_thre() {
+    let mut uart = Uart::new(0, true, true);
+    uart.write_u8(1, 0x03);
+    uart.push_input(b"x");
+    assert_eq!(uart.read_u8(2) & 0x0F, 0x04);
+}
+
+#[test]
+fn uart_fcr_clears_rx_fifo() {
+    let mut uart = Uart::new(0, true, true);
+    uart.push_input(b"abc");
+    uart.write_u8(2, 0x03); // enable + clear RX
+    assert_eq!(uart.read_u8(5) & 0x01, 0);
+}
+
+#[test]
+fn uart_ier_upper_bits_read_as_zero() {
+    let mut uart = Uart::new(0, true, true);
+    uart.write_u8(1, 0xFF);
+    assert_eq!(uart.read_u8(1), 0x0F);
+}
+
+#[test]
+fn uart_loopback_echoes_transmitted_bytes() {
+    let mut uart = Uart::new(0, true, true);
+    uart.write_u8(4, 0x10 | 0x03); // LOOP, RTS, DTR
+    uart.write_u8(0, b'z');
+    assert_eq!(uart.read_u8(5) & 0x01, 0x01);
+    assert_eq!(uart.read_u8(0), b'z');
+    assert_eq!(uart.read_u8(6), 0x30, "CTS and DSR follow RTS and DTR");
+}
@@ -98,9 +98,9 @@ fn uart_irq_id() {
 }
 
 #[test]
-fn uart_msr_returns_zero() {
+fn uart_msr_reports_carrier_and_clear_to_send() {
     let mut uart = Uart::new(0, true, true);
-    assert_eq!(uart.read_u8(6), 0, "MSR should return 0");
+    assert_eq!(uart.read_u8(6), 0xB0, "MSR should report DCD, DSR and CTS");
 }
 
 #[test]
@@ -13,6 +13,8 @@ fn default_stats_all_zero() {
     assert_eq!(stats.inst_load, 0);
     assert_eq!(stats.inst_store, 0);
     assert_eq!(stats.inst_branch, 0);
+    assert_eq!(stats.inst_jump, 0);
+    assert_eq!(stats.inst_atomic, 0);
     assert_eq!(stats.inst_alu, 0);
     assert_eq!(stats.inst_system, 0);
     assert_eq!(stats.inst_fp_load, 0);
@@ -103,6 +105,56 @@ fn stats_stall_breakdown() {
     assert!((stall_ratio - 0.18).abs() < 1e-10);
 }
 
+#[test]
+fn stats_ideal_cycles_and_overhead() {
+    let mut stats = SimStats::default();
+    assert_eq!(stats.ideal_cycles(), 0);
+    assert_eq!(stats.stall_overhead_pct(), 0.0);
+
+    stats.stalls_mem = 100;
+    stats.stalls_control = 50;
+    stats.stalls_data = 50;
+    stats.cycles = 1000;
+    assert_eq!(stats.total_stalls(), 200);
+    assert_eq!(stats.ideal_cycles(), 800);
+    assert!((stats.stall_overhead_pct() - 25.0).abs() < 1e-10);
+
+    // Overlapping stall categories never drive ideal cycles negative.
+    stats.cycles = 150;
+    assert_eq!(stats.ideal_cycles(), 0);
+    assert_eq!(stats.stall_overhead_pct(), 0.0);
+}
+
+#[test]
+fn pipeline_records_memory_and_data_stalls() {
+    use crate::common::builder::instruction::InstructionBuilder;
+    use crate::common::harness::TestContext;
+    use rvsim_core::config::Config;
+
+    const BASE: u64 = 0x8000_0000;
+
+    // A cold L1-D miss stalls Memory1, and the dependent add waits on it.
+    let mut config = Config::default();
+    config.cache.l1_d.enabled = true;
+    let mut tc = TestContext::with_config(&config)
+        .with_memory(0x1000, BASE)
+        .load_program(
+            BASE,
+            &[
+                InstructionBuilder::new().auipc(10, 0).build(),
+                InstructionBuilder::new().lw(11, 10, 0x400).build(),
+                InstructionBuilder::new().add(12, 11, 11).build(),
+            ],
+        );
+    tc.run(500);
+
+    let stats = &tc.cpu().stats;
+    assert_eq!(tc.cpu().exit_code, Some(0));
+    assert!(stats.stalls_mem > 0);
+    assert!(stats.stalls_data > 0);
+    assert!(stats.ideal_cycles() < stats.cycles);
+}
+
 #[test]
 fn stats_mode_cycle_breakdown() {
     let mut stats = SimStats::default();
@@ -151,5 +203,317 @@ fn stats_sections_constant_available() {
     assert!(STATS_SECTIONS.contains(&"instruction_mix"));
     assert!(STATS_SECTIONS.contains(&"branch"));
     assert!(STATS_SECTIONS.contains(&"memory"));
-    assert_eq!(STATS_SECTIONS.len(), 5);
+    assert!(STATS_SECTIONS.contains(&"cpi_stack"));
+    assert_eq!(STATS_SECTIONS.len(), 6);
+}
+
+#[test]
+fn record_mem_latency_keeps_worst_access() {
+    let mut stats = SimStats::default();
+    stats.record_mem_latency(0x8000_0000, 0x8000_1000, 5);
+    stats.record_mem_latency(0x8000_0004, 0x8000_2000, 90);
+    stats.record_mem_latency(0x8000_0008, 0x8000_3000, 12);
+    stats.record_mem_latency(0x8000_000c, 0x8000_4000, 90);
+
+    assert_eq!(stats.max_mem_latency, 90);
+    assert_eq!(stats.max_mem_latency_pc, 0x8000_0004);
+    assert_eq!(stats.max_mem_latency_addr, 0x8000_2000);
+}
+
+#[test]
+fn max_mem_latency_tracks_writeback_plus_row_conflict() {
+    use crate::common::builder::instruction::InstructionBuilder;
+    use crate::common::harness::TestContext;
+    use rvsim_core::config::Config;
+    use rvsim_core::soc::memory::controller::DramController;
+
+    const BASE: u64 = 0x8000_0000;
+    const ROW_CONFLICT: u64 = 14 + 14 + 14;
+
+    // Direct-mapped 4 KiB L1-D: BASE+0x400 and BASE+0x1400 share a set
+    // but live in different 2 KiB DRAM rows.
+    let mut config = Config::default();
+    config.cache.l1_d.enabled = true;
+    config.cache.l1_d.size_bytes = 4096;
+    config.cache.l1_d.ways = 1;
+
+    let nop = InstructionBuilder::new().nop().build();
+    let mut tc = TestContext::with_config(&config)
+        .with_memory(0x2000, BASE)
+        .load_program(
+            BASE,
+            &[
+                InstructionBuilder::new().auipc(10, 0).build(), // x10 = BASE
+                InstructionBuilder::new().lui(13, 1).build(),   // x13 = 0x1000
+                InstructionBuilder::new().add(12, 10, 13).build(), // x12 = BASE + 0x1000
+                InstructionBuilder::new().sw(10, 0, 0x400).build(), // dirty line, row 0
+                InstructionBuilder::new().lw(11, 12, 0x400).build(), // evict + row conflict
+                nop,
+                nop,
+                nop,
+            ],
+        );
+    tc.cpu_mut().mmio_base = 0;
+    tc.cpu_mut().bus.mem_controller = Box::new(DramController::new(14, 14, 14));
+    tc.run(300);
+
+    let stats = &tc.cpu().stats;
+    assert_eq!(
+        stats.max_mem_latency_pc,
+        BASE + 16,
+        "load should be the worst access"
+    );
+    assert_eq!(stats.max_mem_latency_addr, BASE + 0x1400);
+    // The write-back hits the open row and goes first; the refill then
+    // waits for it and pays the row conflict.
+    assert!(
+        stats.max_mem_latency >= 14 + ROW_CONFLICT,
+        "refill should wait for the write-back, got {}",
+        stats.max_mem_latency
+    );
+}
+
+#[test]
+fn stats_json_export_has_every_entry() {
+    let mut stats = SimStats::default();
+    stats.cycles = 200;
+    stats.instructions_retired = 100;
+    stats.dcache_misses = 7;
+
+    let json: serde_json::Value = serde_json::from_str(&stats.to_json()).unwrap();
+    let object = json.as_object().unwrap();
+    assert_eq!(object.len(), stats.entries().len());
+    assert_eq!(object["cycles"], 200);
+    assert_eq!(object["dcache_misses"], 7);
+    assert!((object["ipc"].as_f64().unwrap() - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn stats_csv_export_is_header_and_row() {
+    let mut stats = SimStats::default();
+    stats.cycles = 10;
+    stats.stalls_mem = 4;
+
+    let csv = stats.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    let header: Vec<&str> = lines[0].split(',').collect();
+    let values: Vec<&str> = lines[1].split(',').collect();
+    assert_eq!(header.len(), values.len());
+    assert_eq!(header[0], "cycles");
+    assert_eq!(values[0], "10");
+    let stalls = header.iter().position(|&h| h == "stalls_mem").unwrap();
+    assert_eq!(values[stalls], "4");
+    let ideal = header.iter().position(|&h| h == "ideal_cycles").unwrap();
+    assert_eq!(values[ideal], "6");
+}
+
+#[test]
+fn retired_instructions_split_by_privilege_mode() {
+    use rvsim_core::core::arch::mode::PrivilegeMode;
+
+    let mut stats = SimStats::default();
+    stats.record_retired(PrivilegeMode::User, 3);
+    stats.record_retired(PrivilegeMode::Supervisor, 2);
+    stats.record_retired(PrivilegeMode::Machine, 1);
+    assert_eq!(stats.insts_user, 3);
+    assert_eq!(stats.insts_kernel, 2);
+    assert_eq!(stats.insts_machine, 1);
+    assert_eq!(stats.instructions_retired, 6);
+}
+
+#[test]
+fn pipeline_attributes_instructions_to_the_retiring_mode() {
+    use crate::common::builder::instruction::InstructionBuilder;
+    use crate::common::harness::TestContext;
+
+    const BASE: u64 = 0x8000_0000;
+
+    let nop = InstructionBuilder::new().nop().build();
+    let mut tc = TestContext::new()
+        .with_memory(0x1000, BASE)
+        .load_program(BASE, &[nop, nop, nop, nop]);
+    tc.run(200);
+
+    let stats = &tc.cpu().stats;
+    assert_eq!(stats.insts_machine, 4);
+    assert_eq!(stats.insts_user + stats.insts_kernel, 0);
+    assert_eq!(stats.insts_machine, stats.instructions_retired);
+}
+
+#[test]
+fn cpi_stack_attributes_stalls_and_sums_to_cpi() {
+    let mut stats = SimStats::default();
+    stats.cycles = 1000;
+    stats.instructions_retired = 500;
+    stats.stalls_control = 100;
+    stats.stalls_icache = 50;
+    stats.stalls_itlb = 20;
+    stats.stalls_dtlb = 30;
+    stats.stalls_dcache = 200;
+    // 250 memory-stage stall cycles: 230 charged to D-TLB/D-cache, 20 other.
+    stats.stalls_mem = 250;
+
+    let stack = stats.cpi_stack();
+    assert!((stack.branch - 0.2).abs() < 1e-9);
+    assert!((stack.icache - 0.1).abs() < 1e-9);
+    assert!((stack.tlb - 0.1).abs() < 1e-9);
+    assert!((stack.dcache - 0.4).abs() < 1e-9);
+    assert!((stack.memory - 0.04).abs() < 1e-9);
+    assert!((stack.base - 1.16).abs() < 1e-9);
+    assert!((stack.total() - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn cpi_stack_scales_overlapping_stalls_to_fit() {
+    // Frontend and backend stalls overlapped: 300 charged in 200 cycles.
+    let mut stats = SimStats::default();
+    stats.cycles = 200;
+    stats.instructions_retired = 100;
+    stats.stalls_icache = 150;
+    stats.stalls_dcache = 150;
+    stats.stalls_mem = 150;
+
+    let stack = stats.cpi_stack();
+    assert_eq!(stack.base, 0.0);
+    assert!((stack.icache - 1.0).abs() < 1e-9);
+    assert!((stack.dcache - 1.0).abs() < 1e-9);
+    assert!((stack.total() - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn cpi_stack_is_zero_before_retirement() {
+    let mut stats = SimStats::default();
+    stats.cycles = 10;
+    stats.stalls_icache = 5;
+    assert_eq!(stats.cpi_stack().total(), 0.0);
+}
+
+#[test]
+fn pipeline_cpi_stack_sums_to_measured_cpi() {
+    use crate::common::builder::instruction::InstructionBuilder;
+    use crate::common::harness::TestContext;
+
+    const BASE: u64 = 0x8000_0000;
+
+    // Loads plus a loop whose exit branch mispredicts.
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 8).build(),
+        InstructionBuilder::new().lw(6, 7, 0).build(),
+        InstructionBuilder::new().addi(5, 5, -1).build(),
+        InstructionBuilder::new().bne(5, 0, -8).build(),
+    ];
+    let mut tc = TestContext::new()
+        .with_memory(0x1000, BASE)
+        .load_program(BASE, &program);
+    tc.set_reg(7, BASE + 0x800);
+    tc.run(2000);
+
+    let stats = &tc.cpu().stats;
+    let stack = stats.cpi_stack();
+    let cpi = stats.cycles as f64 / stats.instructions_retired as f64;
+    assert!(stats.stalls_control > 0);
+    assert!(stack.branch > 0.0);
+    assert!((stack.total() - cpi).abs() < 1e-9);
+    assert!(stats.entries().iter().any(|(name, _)| *name == "cpi_base"));
+}
+
+#[test]
+fn reset_folds_counts_into_warmup() {
+    let mut stats = SimStats::default();
+    stats.cycles = 100;
+    stats.instructions_retired = 40;
+    stats.dcache_misses = 7;
+    stats.reset();
+    assert_eq!(stats.cycles, 0);
+    assert_eq!(stats.instructions_retired, 0);
+    assert_eq!(stats.dcache_misses, 0);
+    assert_eq!(stats.warmup_cycles, 100);
+    assert_eq!(stats.warmup_instructions, 40);
+
+    stats.cycles = 5;
+    stats.reset();
+    assert_eq!(stats.total_cycles(), 105, "warmup accumulates");
+    stats.set_total_cycles(1_000);
+    assert_eq!(stats.total_cycles(), 1_000);
+    assert_eq!(stats.cycles, 895);
+}
+
+#[test]
+fn reset_stats_keeps_caches_warm_and_counters_monotonic() {
+    use crate::common::builder::instruction::InstructionBuilder;
+    use crate::common::harness::TestContext;
+    use rvsim_core::config::Config;
+    use rvsim_core::core::arch::csr;
+
+    const BASE: u64 = 0x8000_0000;
+    let mut config = Config::default();
+    config.cache.l1_i.enabled = true;
+    config.cache.l1_i.size_bytes = 4096;
+    // Loop 64 times over one cache line.
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 64).build(),
+        InstructionBuilder::new().addi(5, 5, -1).build(),
+        InstructionBuilder::new().bne(5, 0, -4).build(),
+    ];
+    let mut tc = TestContext::with_config(&config)
+        .with_memory(0x1000, BASE)
+        .load_program(BASE, &program);
+    tc.run(40);
+    assert!(tc.cpu().stats.icache_misses > 0, "cold start misses");
+    let cycles = tc.cpu().stats.cycles;
+    let insts = tc.cpu().stats.instructions_retired;
+
+    tc.cpu_mut().reset_stats();
+    let stats = &tc.cpu().stats;
+    assert_eq!((stats.cycles, stats.instructions_retired), (0, 0));
+    assert_eq!(stats.icache_misses + stats.icache_hits, 0);
+    assert_eq!(
+        (stats.warmup_cycles, stats.warmup_instructions),
+        (cycles, insts)
+    );
+
+    tc.run(40);
+    let stats = &tc.cpu().stats;
+    assert!(stats.icache_hits > 0);
+    assert_eq!(stats.icache_misses, 0, "the loop stays cached");
+    assert_eq!(tc.cpu().csr_read(csr::MCYCLE), cycles + stats.cycles);
+    assert_eq!(
+        tc.cpu().csr_read(csr::MINSTRET),
+        insts + stats.instructions_retired
+    );
+}
+
+#[test]
+fn perceptron_training_counts_reach_stats() {
+    use crate::common::builder::instruction::InstructionBuilder;
+    use crate::common::harness::TestContext;
+    use rvsim_core::config::{BranchPredictor, Config};
+
+    const BASE: u64 = 0x8000_0000;
+    let mut config = Config::default();
+    config.pipeline.branch_predictor = BranchPredictor::Perceptron;
+    config.pipeline.perceptron.history_length = 8;
+    config.pipeline.perceptron.table_bits = 6;
+    // Loop 64 times.
+    let program = [
+        InstructionBuilder::new().addi(5, 0, 64).build(),
+        InstructionBuilder::new().addi(5, 5, -1).build(),
+        InstructionBuilder::new().bne(5, 0, -4).build(),
+    ];
+    let mut tc = TestContext::with_config(&config)
+        .with_memory(0x1000, BASE)
+        .load_program(BASE, &program);
+    tc.run(1_000);
+    let stats = &tc.cpu().stats;
+    assert_eq!(
+        stats.bp_trained + stats.bp_train_skipped,
+        stats.cond_branch_predictions + stats.cond_branch_mispredictions,
+        "every resolved conditional branch is counted once"
+    );
+    assert!(
+        stats.bp_train_skipped > 0,
+        "the loop branch becomes confident"
+    );
+    assert_eq!(stats.bp_threshold, 29);
 }
@@ -6,14 +6,14 @@ A Python-first interface to the cycle-accurate RISC-V simulator:
 2. **Execution:** ``System``, ``Cpu``, ``Simulator``.
 3. **Experiments:** ``Environment``, ``Result``.
 4. **Statistics:** ``Stats``, ``Table``.
-5. **ISA:** ``reg``, ``csr``, ``Disassemble``.
+5. **ISA:** ``reg``, ``csr``, ``disasm``, ``Disassemble``.
 """
 
 from importlib.metadata import version as _metadata_version
 
 from .config import Config
 from .experiment import Environment, Result
-from .isa import Disassemble, csr, reg
+from .isa import Disassemble, csr, disasm, reg
 from .objects import Cpu, Instruction, Simulator, System
 from .stats import Stats, Table
 from .types import (
@@ -53,5 +53,6 @@ __all__ = [
     "Table",
     "reg",
     "csr",
+    "disasm",
     "Disassemble",
 ]
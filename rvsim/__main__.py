@@ -7,7 +7,7 @@ Invoked by the ``rvsim`` console script installed by pip, or directly via
 Usage::
 
     rvsim <file>                           Auto-detect mode by extension
-    rvsim -f <elf> [--limit N]             Bare-metal ELF
+    rvsim -f <elf> [--max-cycles N]        Bare-metal ELF
     rvsim --kernel <Image> [--disk <img>]  Boot a kernel
     rvsim --script <script.py> [args ...]  Run a Python script
     rvsim list                             List bundled programs
@@ -91,6 +91,12 @@ def _apply_cli_overrides(sim, args):
 
     if args.trace:
         sim._config_obj.trace = True
+    if args.asm_dump is not None:
+        sim._config_obj.asm_dump = args.asm_dump
+    if args.commit_log is not None:
+        sim._config_obj.commit_log = args.commit_log
+    if args.max_same_pc is not None:
+        sim._config_obj.max_same_pc = args.max_same_pc
     if args.width is not None:
         sim._config_obj.width = args.width
     if args.bp is not None:
@@ -100,6 +106,7 @@ def _apply_cli_overrides(sim, args):
             "tage": BranchPredictor.TAGE,
             "perceptron": BranchPredictor.Perceptron,
             "tournament": BranchPredictor.Tournament,
+            "perfect": BranchPredictor.Perfect,
         }
         sim._config_obj.branch_predictor = bp_map[args.bp]()
 
@@ -157,14 +164,36 @@ def main() -> None:
     # Simulation
     parser.add_argument("--disk", metavar="IMG", help="disk image (requires --kernel)")
     parser.add_argument(
-        "--dtb", metavar="DTB", help="device tree blob (requires --kernel)"
+        "--dtb",
+        metavar="DTB",
+        help="device tree blob (requires --kernel; generated from the config if omitted)",
+    )
+    parser.add_argument(
+        "--initrd",
+        metavar="IMG",
+        help="initramfs image, recorded in the DTB's /chosen node (requires --kernel)",
+    )
+    parser.add_argument(
+        "--print-memmap",
+        action="store_true",
+        default=False,
+        help="print each bus device as '<start> <end> <name>' (end exclusive) before running",
+    )
+    parser.add_argument(
+        "--dump-dtb",
+        metavar="FILE",
+        default=None,
+        help="write the DTB to FILE (the kernel's, or one generated from the config)",
     )
     parser.add_argument(
         "--limit",
+        "--max-cycles",
+        dest="limit",
         metavar="N",
         type=_parse_cycles,
         default=None,
-        help="max cycles to simulate (supports K/M/G, e.g. 5M)",
+        help="max cycles to simulate (supports K/M/G, e.g. 5M); stats are "
+        "printed and the exit status is 124 if the limit is reached",
     )
     parser.add_argument(
         "--progress",
@@ -176,6 +205,25 @@ def main() -> None:
     parser.add_argument(
         "--trace", action="store_true", default=False, help="enable instruction tracing"
     )
+    parser.add_argument(
+        "--asm-dump",
+        metavar="FILE",
+        default=None,
+        help="write retired instructions to FILE as assembly, in commit order",
+    )
+    parser.add_argument(
+        "--commit-log",
+        metavar="FILE",
+        default=None,
+        help="write a Spike-compatible (--log-commits) commit log to FILE",
+    )
+    parser.add_argument(
+        "--max-same-pc",
+        type=int,
+        metavar="N",
+        default=None,
+        help="stop with an error after N consecutive instructions retire at the same PC",
+    )
 
     # Stats control
     parser.add_argument(
@@ -184,16 +232,18 @@ def main() -> None:
         const="all",
         default="all",
         metavar="SECTIONS",
-        help="stats sections to print (comma-separated: summary,core,instruction_mix,branch,memory)",
+        help="stats sections to print (comma-separated: summary,core,cpi_stack,instruction_mix,branch,memory)",
     )
     parser.add_argument(
         "--no-stats", action="store_true", default=False, help="suppress stats output"
     )
     parser.add_argument(
+        "--stats-out",
         "--output-stats",
+        dest="output_stats",
         metavar="FILE",
         default=None,
-        help="write stats as JSON to FILE",
+        help="write stats to FILE (CSV if FILE ends in .csv, JSON otherwise)",
     )
 
     # Pipeline overrides
@@ -202,10 +252,10 @@ def main() -> None:
     )
     parser.add_argument(
         "--bp",
-        choices=["static", "gshare", "tage", "perceptron", "tournament"],
+        choices=["static", "gshare", "tage", "perceptron", "tournament", "perfect"],
         default=None,
         metavar="TYPE",
-        help="branch predictor override (static, gshare, tage, perceptron, tournament)",
+        help="branch predictor override (static, gshare, tage, perceptron, tournament, perfect)",
     )
 
     # Positional: file + optional script args
@@ -249,6 +299,8 @@ def main() -> None:
         parser.error("--disk requires --kernel or a kernel image")
     if args.dtb and mode != "kernel":
         parser.error("--dtb requires --kernel or a kernel image")
+    if args.initrd and mode != "kernel":
+        parser.error("--initrd requires --kernel or a kernel image")
 
     # Resolve stats sections
     if args.no_stats:
@@ -271,11 +323,17 @@ def main() -> None:
         if args.config:
             sim = sim.config(args.config)
         _apply_cli_overrides(sim, args)
+        if args.print_memmap:
+            sim = sim.print_memmap()
+        if args.dump_dtb:
+            sim = sim.dump_dtb(args.dump_dtb)
         sim = sim.kernel(target).kernel_mode()
         if args.disk:
             sim = sim.disk(args.disk)
         if args.dtb:
             sim = sim.dtb(args.dtb)
+        if args.initrd:
+            sim = sim.initrd(args.initrd)
         sys.exit(
             sim.run(
                 limit=args.limit,
@@ -292,6 +350,10 @@ def main() -> None:
         if args.config:
             sim = sim.config(args.config)
         _apply_cli_overrides(sim, args)
+        if args.print_memmap:
+            sim = sim.print_memmap()
+        if args.dump_dtb:
+            sim = sim.dump_dtb(args.dump_dtb)
         sim = sim.binary(target)
         sys.exit(
             sim.run(
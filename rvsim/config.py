@@ -8,7 +8,7 @@ dict that the Rust backend expects.
 
 from __future__ import annotations
 
-from typing import Any, Dict, Optional
+from typing import Any, Dict, List, Optional
 
 from .types import (
     _parse_size,
@@ -44,10 +44,25 @@ class Config:
         self,
         # Pipeline
         width: int = 1,
+        fetch_width: int = 0,
+        fetch_queue_size: int = 0,
         branch_predictor=BranchPredictor.TAGE(),
         backend=None,
         btb_size: int = 4096,
         ras_size: int = 32,
+        loop_predictor: bool = False,
+        loop_table_size: int = 64,
+        indirect_predictor: bool = False,
+        indirect_table_size: int = 256,
+        mul_latency: int = 3,
+        div_latency: int = 20,
+        fdiv_latency: int = 20,
+        fsqrt_latency: int = 20,
+        store_forward_latency: int = 1,
+        fence_latency: int = 1,
+        frontend_depth: int = 2,
+        subreg_tracking: bool = False,
+        vlen: int = 128,
         # Caches (None = disabled)
         l1i=Cache("32KB", ways=4, latency=1, prefetcher=Prefetcher.NextLine(degree=1)),
         l1d=Cache(
@@ -58,41 +73,86 @@ class Config:
         ),
         l2=Cache("256KB", ways=8, latency=10),
         l3: Optional[Cache] = None,
+        cache_random_seed: int = 123_456_789,
         # Memory
         ram_size="256MB",
         memory_controller=None,
         tlb_size: int = 32,
+        misaligned_access: bool = True,
+        big_endian: bool = False,
+        fill_pattern: int = 0,
+        svade: bool = False,
         # General
         trace: bool = False,
         start_pc: int = 0x8000_0000,
         direct_mode: bool = True,
         initial_sp: Optional[int] = None,
+        simple_timing: bool = False,
+        simple_mem_latency: int = 10,
+        simple_mispredict_penalty: int = 3,
+        max_same_pc: int = 0,
+        asm_dump: Optional[str] = None,
+        commit_log: Optional[str] = None,
+        mem_trace_size: int = 0,
+        trap_log_size: int = 0,
+        reset_mstatus: Optional[int] = None,
+        reset_medeleg: int = 0,
+        reset_mideleg: int = 0,
+        reset_privilege: str = "Machine",
+        hartid: int = 0,
+        strict_csrs: bool = False,
+        interrupt_priority: Optional[List[int]] = None,
+        sbi: bool = False,
+        semihosting: bool = False,
+        ebreak_halt: bool = False,
         # System (advanced)
         ram_base: int = 0x8000_0000,
         uart_base: int = 0x1000_0000,
         disk_base: int = 0x9000_0000,
         clint_base: int = 0x0200_0000,
         syscon_base: int = 0x0010_0000,
+        rtc_base: int = 0x0010_1000,
         kernel_offset: int = 0x0020_0000,
+        initrd_offset: int = 0x0400_0000,
         bus_width: int = 8,
         bus_latency: int = 4,
         clint_divider: int = 10,
+        timebase_frequency: int = 10_000_000,
+        num_harts: int = 1,
         uart_to_stderr: bool = False,
         uart_quiet: bool = False,
         tohost_addr: int = 0,
+        cdc_latency: Optional[Dict[str, int]] = None,
+        framebuffer: Optional[Dict[str, int]] = None,
     ):
         # Pipeline
         self.width = width
+        self.fetch_width = fetch_width
+        self.fetch_queue_size = fetch_queue_size
         self.branch_predictor = branch_predictor
         self.backend = backend if backend is not None else Backend.InOrder()
         self.btb_size = btb_size
         self.ras_size = ras_size
+        self.loop_predictor = loop_predictor
+        self.loop_table_size = loop_table_size
+        self.indirect_predictor = indirect_predictor
+        self.indirect_table_size = indirect_table_size
+        self.mul_latency = mul_latency
+        self.div_latency = div_latency
+        self.fdiv_latency = fdiv_latency
+        self.fsqrt_latency = fsqrt_latency
+        self.store_forward_latency = store_forward_latency
+        self.fence_latency = fence_latency
+        self.frontend_depth = frontend_depth
+        self.subreg_tracking = subreg_tracking
+        self.vlen = vlen
 
         # Caches
         self.l1i = l1i
         self.l1d = l1d
         self.l2 = l2
         self.l3 = l3
+        self.cache_random_seed = cache_random_seed
 
         # Memory
         self.ram_size = _parse_size(ram_size)
@@ -102,12 +162,34 @@ class Config:
             else MemoryController.Simple()
         )
         self.tlb_size = tlb_size
+        self.misaligned_access = misaligned_access
+        self.big_endian = big_endian
+        self.fill_pattern = fill_pattern
+        self.svade = svade
 
         # General
         self.trace = trace
         self.start_pc = start_pc
         self.direct_mode = direct_mode
         self.initial_sp = initial_sp
+        self.simple_timing = simple_timing
+        self.simple_mem_latency = simple_mem_latency
+        self.simple_mispredict_penalty = simple_mispredict_penalty
+        self.max_same_pc = max_same_pc
+        self.asm_dump = asm_dump
+        self.commit_log = commit_log
+        self.mem_trace_size = mem_trace_size
+        self.trap_log_size = trap_log_size
+        self.reset_mstatus = reset_mstatus
+        self.reset_medeleg = reset_medeleg
+        self.reset_mideleg = reset_mideleg
+        self.reset_privilege = reset_privilege
+        self.hartid = hartid
+        self.strict_csrs = strict_csrs
+        self.interrupt_priority = interrupt_priority
+        self.sbi = sbi
+        self.semihosting = semihosting
+        self.ebreak_halt = ebreak_halt
 
         # System
         self.ram_base = ram_base
@@ -115,13 +197,19 @@ class Config:
         self.disk_base = disk_base
         self.clint_base = clint_base
         self.syscon_base = syscon_base
+        self.rtc_base = rtc_base
         self.kernel_offset = kernel_offset
+        self.initrd_offset = initrd_offset
         self.bus_width = bus_width
         self.bus_latency = bus_latency
         self.clint_divider = clint_divider
+        self.timebase_frequency = timebase_frequency
+        self.num_harts = num_harts
         self.uart_to_stderr = uart_to_stderr
         self.uart_quiet = uart_quiet
         self.tohost_addr = tohost_addr
+        self.cdc_latency = cdc_latency
+        self.framebuffer = framebuffer
 
     def to_dict(self) -> Dict[str, Any]:
         """Produce the nested dict expected by the Rust backend."""
@@ -130,9 +218,31 @@ class Config:
             "trace_instructions": self.trace,
             "start_pc": self.start_pc,
             "direct_mode": self.direct_mode,
+            "simple_timing": self.simple_timing,
+            "simple_mem_latency": self.simple_mem_latency,
+            "simple_mispredict_penalty": self.simple_mispredict_penalty,
+            "max_same_pc": self.max_same_pc,
+            "mem_trace_size": self.mem_trace_size,
+            "trap_log_size": self.trap_log_size,
+            "reset_medeleg": self.reset_medeleg,
+            "reset_mideleg": self.reset_mideleg,
+            "reset_privilege": self.reset_privilege,
+            "hartid": self.hartid,
+            "strict_csrs": self.strict_csrs,
+            "sbi": self.sbi,
+            "semihosting": self.semihosting,
+            "ebreak_halt": self.ebreak_halt,
         }
         if self.initial_sp is not None:
             general["initial_sp"] = self.initial_sp
+        if self.interrupt_priority is not None:
+            general["interrupt_priority"] = list(self.interrupt_priority)
+        if self.asm_dump is not None:
+            general["asm_dump"] = self.asm_dump
+        if self.commit_log is not None:
+            general["commit_log"] = self.commit_log
+        if self.reset_mstatus is not None:
+            general["reset_mstatus"] = self.reset_mstatus
 
         # System
         system = {
@@ -141,14 +251,22 @@ class Config:
             "disk_base": self.disk_base,
             "clint_base": self.clint_base,
             "syscon_base": self.syscon_base,
+            "rtc_base": self.rtc_base,
             "kernel_offset": self.kernel_offset,
+            "initrd_offset": self.initrd_offset,
             "bus_width": self.bus_width,
             "bus_latency": self.bus_latency,
             "clint_divider": self.clint_divider,
+            "timebase_frequency": self.timebase_frequency,
+            "num_harts": self.num_harts,
             "uart_to_stderr": self.uart_to_stderr,
             "uart_quiet": self.uart_quiet,
             "tohost_addr": self.tohost_addr,
         }
+        if self.cdc_latency is not None:
+            system["cdc_latency"] = dict(self.cdc_latency)
+        if self.framebuffer is not None:
+            system["framebuffer"] = {"enabled": True, **self.framebuffer}
 
         # Memory — merge controller-specific params
         mc = self.memory_controller
@@ -156,6 +274,10 @@ class Config:
             "ram_size": self.ram_size,
             "controller": mc._to_dict_value(),
             "tlb_size": self.tlb_size,
+            "misaligned_access": self.misaligned_access,
+            "big_endian": self.big_endian,
+            "fill_pattern": self.fill_pattern,
+            "svade": self.svade,
         }
         # Always emit DRAM timing keys (Rust expects them)
         if isinstance(mc, MemoryController.DRAM):
@@ -188,15 +310,19 @@ class Config:
                 if self.l3 is not None
                 else _DISABLED_CACHE_DICT_ZERO
             ),
+            "random_seed": self.cache_random_seed,
         }
 
-        # Pipeline — always emit all three BP sub-configs with defaults
+        # Pipeline — always emit all BP sub-configs with defaults
         bp = self.branch_predictor
+        gshare_dict = BranchPredictor.GShare()._sub_dict()
         tage_dict = BranchPredictor.TAGE()._sub_dict()
         perceptron_dict = BranchPredictor.Perceptron()._sub_dict()
         tournament_dict = BranchPredictor.Tournament()._sub_dict()
 
-        if isinstance(bp, BranchPredictor.TAGE):
+        if isinstance(bp, BranchPredictor.GShare):
+            gshare_dict = bp._sub_dict()
+        elif isinstance(bp, BranchPredictor.TAGE):
             tage_dict = bp._sub_dict()
         elif isinstance(bp, BranchPredictor.Perceptron):
             perceptron_dict = bp._sub_dict()
@@ -205,12 +331,28 @@ class Config:
 
         pipeline = {
             "width": self.width,
+            "fetch_width": self.fetch_width,
+            "fetch_queue_size": self.fetch_queue_size,
             "branch_predictor": bp._to_dict_value(),
             "btb_size": self.btb_size,
             "ras_size": self.ras_size,
+            "loop_predictor": self.loop_predictor,
+            "loop_table_size": self.loop_table_size,
+            "indirect_predictor": self.indirect_predictor,
+            "indirect_table_size": self.indirect_table_size,
             "backend": self.backend._to_dict_value(),
             "rob_size": self.backend._rob_size(),
             "store_buffer_size": self.backend._store_buffer_size(),
+            "mul_latency": self.mul_latency,
+            "div_latency": self.div_latency,
+            "fdiv_latency": self.fdiv_latency,
+            "fsqrt_latency": self.fsqrt_latency,
+            "store_forward_latency": self.store_forward_latency,
+            "fence_latency": self.fence_latency,
+            "frontend_depth": self.frontend_depth,
+            "subreg_tracking": self.subreg_tracking,
+            "vlen": self.vlen,
+            "gshare": gshare_dict,
             "tage": tage_dict,
             "perceptron": perceptron_dict,
             "tournament": tournament_dict,
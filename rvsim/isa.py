@@ -2,6 +2,7 @@
 RISC-V ABI register and CSR definitions.
 
 Provides callable namespace objects:
+- ``disasm``: One instruction as assembly text (``disasm(0x00a00513)`` → ``"addi a0, zero, 10"``)
 - ``reg``: Register lookup (``reg.RA`` → 1, ``reg("ra")`` → 1, ``reg.name(5)`` → ``"t0"``)
 - ``csr``: CSR lookup (``csr.MSTATUS`` → 0x300, ``csr("mstatus")`` → 0x300, ``csr.name(0x300)`` → ``"mstatus"``)
 """
@@ -13,6 +14,15 @@ from typing import List, Optional, Tuple
 from ._core import disassemble
 
 
+def disasm(inst: int) -> str:
+    """Return the assembly text for one raw instruction encoding.
+
+    Compressed instructions are given in the low 16 bits and rendered as
+    their 32-bit expansion.
+    """
+    return disassemble(inst)
+
+
 class Disassemble:
     """Fluent disassembler for RISC-V binaries and raw bytes.
 
@@ -230,6 +240,7 @@ _CSR_BY_NAME: dict[str, int] = {
     "minstret": 0xB02,
     # Stimecmp (Sstc extension)
     "stimecmp": 0x14D,
+    "menvcfg": 0x30A,
 }
 
 _CSR_BY_ADDR: dict[int, str] = {addr: name for name, addr in _CSR_BY_NAME.items()}
@@ -275,6 +286,7 @@ class _CsrLookup:
     MINSTRET = 0xB02
     # Sstc
     STIMECMP = 0x14D
+    MENVCFG = 0x30A
 
     def __call__(self, name) -> int:
         if isinstance(name, int):
@@ -13,13 +13,17 @@ from __future__ import annotations
 import importlib.util
 import os
 import sys
-from typing import Any, Dict, Optional
+from typing import Any, Callable, Dict, Optional
 
 from ._cli import info, warn, error, tag
-from ._core import PySystem, PyCpu
+from ._core import PySystem, PyCpu, generate_dtb
 from .config import Config, _config_to_dict
 from .stats import Stats
 
+# Exit status when a run hits its cycle limit without the guest exiting
+# (the same status ``timeout(1)`` uses, so CI can tell a hang from a failure).
+CYCLE_LIMIT_EXIT_CODE = 124
+
 
 class _RegisterFile:
     """Indexable register access: ``cpu.regs[10]`` reads x10."""
@@ -43,7 +47,11 @@ class _RegisterFile:
 
 
 class _MemView:
-    """Indexable memory access: ``cpu.mem32[addr]`` reads a u32."""
+    """Indexable memory access: ``cpu.mem32[addr]`` reads a u32 and
+    ``cpu.mem32[addr] = val`` writes one.
+
+    Accessing an address no device is mapped at raises ``ValueError``.
+    """
 
     def __init__(self, rust_cpu: PyCpu, width: int):
         self._cpu = rust_cpu
@@ -56,6 +64,14 @@ class _MemView:
             return self._cpu.read_memory_u64(addr)
         raise ValueError(f"Unsupported width: {self._width}")
 
+    def __setitem__(self, addr: int, value: int) -> None:
+        if self._width == 32:
+            self._cpu.write_memory_u32(addr, value)
+        elif self._width == 64:
+            self._cpu.write_memory_u64(addr, value)
+        else:
+            raise ValueError(f"Unsupported width: {self._width}")
+
 
 class Instruction:
     """Result of a single-step execution."""
@@ -82,15 +98,22 @@ class Cpu:
         stats: Performance statistics (read, returns Stats)
         trace: Instruction tracing (read/write)
         regs: Register file (_RegisterFile, indexable)
-        mem32: Memory view for u32 reads (_MemView)
-        mem64: Memory view for u64 reads (_MemView)
+        mem32: Memory view for u32 reads and writes (_MemView)
+        mem64: Memory view for u64 reads and writes (_MemView)
 
     Methods:
         step(): Execute one instruction, return Instruction
-        run(): Run until exit with optional limit and progress
+        step_cycles(n): Advance up to n cycles, return a StepResult
+        run(): Run until exit with optional limit, progress, and warmup
+        reset_stats(): Clear stats after a warmup phase
         tick(): Advance one cycle
         csr(name): Read a CSR by name or address
+        load_bytes(paddr, data): Copy a bytes-like object into memory
         get_pc_trace(): Get committed PC trace
+        save_checkpoint(path): Save architectural state to a file
+        load_checkpoint(path): Restore architectural state from a file
+        dump_framebuffer(path): Write the framebuffer as a PPM image
+        set_functional(enabled): Toggle functional fast-forward execution
     """
 
     def __init__(self, rust_cpu: PyCpu):
@@ -107,6 +130,11 @@ class Cpu:
     def pc(self, value: int) -> None:
         self._cpu.set_pc(value)
 
+    @property
+    def halted_at(self) -> Optional[int]:
+        """PC of the ``ebreak`` that halted the hart (``ebreak_halt``), or ``None``."""
+        return self._cpu.halted_at()
+
     @property
     def stats(self) -> Stats:
         raw = self._cpu.get_stats()
@@ -135,12 +163,23 @@ class Cpu:
         cycles = self._cpu.get_stats().cycles
         return Instruction(pc, raw, asm, cycles)
 
+    def step_cycles(self, n: int):
+        """Advance up to ``n`` cycles, stopping early on exit or ``ebreak``.
+
+        Returns a ``StepResult`` with ``cycles``, ``instructions``, ``trapped``,
+        ``breakpoint``, ``exited``, ``exit_code``, and the stopping ``pc``.
+        """
+        return self._cpu.step(n)
+
     def run(
         self,
         limit: Optional[int] = None,
         progress: int = 0,
         print_stats: bool = False,
         stats_sections: Optional[list] = None,
+        sample_interval: Optional[int] = None,
+        callback: Optional[Callable[[Stats], Optional[bool]]] = None,
+        warmup: Optional[int] = None,
     ) -> Optional[int]:
         """Run the simulation until exit or cycle limit.
 
@@ -150,11 +189,30 @@ class Cpu:
             print_stats: Print performance stats on completion/error (legacy).
             stats_sections: Sections to print (``[]`` = all, ``None`` = suppress,
                 ``["summary", ...]`` = specific). Overrides *print_stats* when set.
+            sample_interval: Call *callback* every N cycles (at multiples of N).
+            callback: Receives a ``Stats`` snapshot at each sample; returning
+                ``False`` stops the run early.
+            warmup: Run this many instructions first, then clear the stats
+                (caches and predictors stay warm). Not counted in *limit*.
 
         Returns:
-            Exit code, or ``None`` if *limit* was reached without exiting.
+            Exit code, or ``None`` if *limit* was reached without exiting,
+            an ``ebreak`` halted the hart (see ``halted_at``), or *callback*
+            stopped the run.
         """
         raw = self._cpu
+        stopped = False
+        sampling = {}
+        if sample_interval is not None or callback is not None:
+
+            def _sample(s):
+                nonlocal stopped
+                keep_going = callback(Stats(s.to_dict()))
+                if keep_going is False:
+                    stopped = True
+                return keep_going
+
+            sampling = {"sample_interval": sample_interval, "callback": _sample}
 
         def _stats():
             if stats_sections is not None:
@@ -167,6 +225,14 @@ class Cpu:
                 raw.get_stats().print()
 
         try:
+            if warmup is not None:
+                exit_code = raw.run(limit=0, warmup=warmup)
+                if exit_code is not None:
+                    _stats()
+                    return int(exit_code)
+                if raw.halted_at() is not None:
+                    _stats()
+                    return None
             if progress > 0:
                 cycles_run = 0
                 while True:
@@ -178,12 +244,16 @@ class Cpu:
                             _stats()
                             return None
                         chunk = min(chunk, remaining)
-                    exit_code = raw.run(limit=chunk)
+                    exit_code = raw.run(limit=chunk, **sampling)
                     cycles_run += chunk
                     if exit_code is not None:
                         print(file=sys.stderr)
                         _stats()
                         return int(exit_code)
+                    if stopped or raw.halted_at() is not None:
+                        print(file=sys.stderr)
+                        _stats()
+                        return None
                     s = raw.get_stats()
                     print(
                         f"\r{tag('rvsim', stderr=True)} {s.cycles:,} cycles, "
@@ -193,7 +263,7 @@ class Cpu:
                         flush=True,
                     )
             else:
-                exit_code = raw.run(limit=limit)
+                exit_code = raw.run(limit=limit, **sampling)
                 _stats()
                 if exit_code is not None:
                     return int(exit_code)
@@ -208,6 +278,10 @@ class Cpu:
         """Advance one cycle."""
         self._cpu.tick()
 
+    def reset_stats(self) -> None:
+        """Clear the stats, keeping caches and predictors warm."""
+        self._cpu.reset_stats()
+
     def csr(self, name) -> int:
         """Read a CSR by name (str) or address (int)."""
         from .isa import csr as _csr_lookup
@@ -215,14 +289,99 @@ class Cpu:
         addr = _csr_lookup(name) if isinstance(name, str) else name
         return self._cpu.read_csr(addr)
 
+    def load_bytes(self, paddr: int, data) -> None:
+        """Copy ``data`` (``bytes``, ``bytearray``, or any buffer such as a
+        NumPy array) into physical memory at ``paddr``.
+
+        Raises ``ValueError`` if the range is not entirely mapped; nothing is
+        written in that case.
+        """
+        self._cpu.load_bytes(paddr, bytes(memoryview(data).cast("B")))
+
+    def cache_set_stats(self, level: str) -> list:
+        """Per-set ``(accesses, misses)`` of a cache level (``"l1i"``,
+        ``"l1d"``, ``"l2"``, or ``"l3"``), indexed by set.
+
+        Counts demand accesses since the last ``reset_stats``. Empty unless
+        the level was built with ``Cache(..., set_stats=True)``.
+        """
+        return self._cpu.cache_set_stats(level)
+
     def get_pc_trace(self):
         """Get the committed PC trace from the pipeline."""
         return self._cpu.get_pc_trace()
 
-    def load_kernel(self, kernel_path: str, dtb_path: Optional[str] = None) -> None:
-        """Load a kernel image and optionally a DTB."""
+    def save_checkpoint(self, path: str) -> None:
+        """Save registers, CSRs, RAM, and device state to ``path``."""
+        self._cpu.save_checkpoint(path)
+
+    def load_checkpoint(self, path: str) -> None:
+        """Restore state saved by ``save_checkpoint`` (same system configuration)."""
+        self._cpu.load_checkpoint(path)
+
+    def resolve_symbol(self, pc: int) -> Optional[tuple]:
+        """Return ``(function, offset)`` for ``pc`` from the ELF symbol table, or ``None``."""
+        return self._cpu.resolve_symbol(pc)
+
+    def backtrace(self) -> list:
+        """Return the call stack as return addresses, innermost first (best effort)."""
+        return self._cpu.backtrace()
+
+    def enable_mem_trace(
+        self, capacity: int, start: Optional[int] = None, end: Optional[int] = None
+    ) -> None:
+        """Record bus accesses (optionally only in ``[start, end)``) in a ring buffer."""
+        self._cpu.enable_mem_trace(capacity, start, end)
+
+    def inject_interrupt_at(self, inst_count: int, interrupt: str) -> None:
+        """Raise ``interrupt`` (e.g. ``"MachineTimerInterrupt"``) once ``inst_count`` instructions have retired."""
+        self._cpu.inject_interrupt_at(inst_count, interrupt)
+
+    def disable_mem_trace(self) -> None:
+        """Stop recording bus accesses and discard the log."""
+        self._cpu.disable_mem_trace()
+
+    def get_mem_trace(self) -> list:
+        """Drain the bus access log as ``(pc, addr, size, is_write, value)`` tuples."""
+        return self._cpu.get_mem_trace()
+
+    def enable_trap_log(self, capacity: int) -> None:
+        """Record taken traps in a ring buffer of ``capacity`` entries."""
+        self._cpu.enable_trap_log(capacity)
+
+    def disable_trap_log(self) -> None:
+        """Stop recording traps and discard the log."""
+        self._cpu.disable_trap_log()
+
+    def get_trap_log(self) -> list:
+        """Drain the trap log as ``(pc, cause, tval, privilege)`` tuples."""
+        return self._cpu.get_trap_log()
+
+    def dump_framebuffer(self, path: str) -> None:
+        """Write the framebuffer contents to ``path`` as a PPM image."""
+        self._cpu.dump_framebuffer(path)
+
+    def set_functional(self, enabled: bool) -> None:
+        """Fast-forward one instruction per tick without timing (``False`` for detailed)."""
+        self._cpu.set_functional(enabled)
+
+    def load_kernel(
+        self,
+        kernel_path: str,
+        dtb_path: Optional[str] = None,
+        initrd_path: Optional[str] = None,
+    ) -> None:
+        """Load a kernel image and optionally a DTB and an initrd (recorded in the DTB)."""
         config_dict = self._config_dict if hasattr(self, "_config_dict") else {}
-        self._cpu.load_kernel(kernel_path, config_dict, dtb_path)
+        self._cpu.load_kernel(kernel_path, config_dict, dtb_path, initrd_path)
+
+    def dtb(self) -> Optional[bytes]:
+        """The DTB ``load_kernel`` placed in RAM, or ``None`` before a kernel is loaded."""
+        return self._cpu.dtb()
+
+    def memory_map(self) -> list:
+        """Bus devices as ``(name, start, end)`` tuples in address order (``end`` exclusive)."""
+        return self._cpu.memory_map()
 
     @property
     def raw(self) -> PyCpu:
@@ -281,9 +440,12 @@ class Simulator:
         self._kernel_path = None
         self._disk_path = None
         self._dtb_path = None
+        self._initrd_path = None
         self._binary_path = None
         self._config_obj: Optional[Config] = None
         self._is_kernel_mode = False
+        self._print_memmap = False
+        self._dump_dtb_path = None
 
     def with_config(self, config: Config) -> Simulator:
         """Set the machine configuration directly."""
@@ -358,6 +520,10 @@ class Simulator:
         self._dtb_path = path
         return self
 
+    def initrd(self, path: str) -> Simulator:
+        self._initrd_path = path
+        return self
+
     def kernel_mode(self) -> Simulator:
         self._is_kernel_mode = True
         return self
@@ -366,6 +532,16 @@ class Simulator:
         self._binary_path = path
         return self
 
+    def print_memmap(self) -> Simulator:
+        """Print every bus device's name and address range before running."""
+        self._print_memmap = True
+        return self
+
+    def dump_dtb(self, path: str) -> Simulator:
+        """Write the DTB (the kernel's, or one generated from the config) to ``path``."""
+        self._dump_dtb_path = path
+        return self
+
     _UNSET = object()
 
     def run(
@@ -382,10 +558,12 @@ class Simulator:
             progress: Print progress every N cycles. 0 = silent.
             stats_sections: Stats sections to print (``[]`` = all, ``None`` = suppress).
                 Defaults to ``[]`` (print all) for backward compatibility.
-            output_stats: Path to write JSON stats after simulation.
+            output_stats: Path to write stats after simulation (CSV if it ends in
+                ``.csv``, JSON otherwise).
 
         Returns:
-            Exit code (int).
+            Exit code (int), or ``CYCLE_LIMIT_EXIT_CODE`` if *limit* was
+            reached first.
         """
         if stats_sections is self._UNSET:
             stats_sections = []  # backward compat: print all
@@ -450,19 +628,37 @@ class Simulator:
                     info("Simulator", f"Loading DTB: {self._dtb_path}", stderr=True),
                     file=sys.stderr,
                 )
-            cpu.load_kernel(self._kernel_path, self._dtb_path)
+            if self._initrd_path:
+                print(
+                    info("Simulator", f"Loading initrd: {self._initrd_path}", stderr=True),
+                    file=sys.stderr,
+                )
+            cpu.load_kernel(self._kernel_path, self._dtb_path, self._initrd_path)
+
+        if self._print_memmap:
+            for name, start, end in cpu.memory_map():
+                print(f"{start:#018x} {end:#018x} {name}")
+        if self._dump_dtb_path is not None:
+            blob = cpu.dtb() or generate_dtb(_config_to_dict(self._config_obj))
+            with open(self._dump_dtb_path, "wb") as f:
+                f.write(blob)
+            print(
+                info("rvsim", f"DTB written to {self._dump_dtb_path}", stderr=True),
+                file=sys.stderr,
+            )
 
         exit_code = cpu.run(
             limit=limit, progress=progress, stats_sections=stats_sections
         )
 
-        # Write JSON stats if requested
+        # Write stats if requested
         if output_stats is not None:
-            import json
-
-            stats_dict = dict(cpu.stats)
+            raw_stats = cpu.raw.get_stats()
             with open(output_stats, "w") as f:
-                json.dump(stats_dict, f, indent=2)
+                if output_stats.lower().endswith(".csv"):
+                    f.write(raw_stats.to_csv())
+                else:
+                    f.write(raw_stats.to_json())
             print(
                 info("rvsim", f"Stats written to {output_stats}", stderr=True),
                 file=sys.stderr,
@@ -473,7 +669,7 @@ class Simulator:
                 warn(f"Simulation did not exit within {limit:,} cycles."),
                 file=sys.stderr,
             )
-            return 1
+            return CYCLE_LIMIT_EXIT_CODE
 
         print(
             info("rvsim", f"Exited with code {exit_code}", stderr=True),
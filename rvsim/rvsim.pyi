@@ -1,6 +1,6 @@
 """Type stubs for rvsim."""
 
-from typing import Any, Dict, List, Optional, Sequence, Union
+from typing import Any, Callable, Dict, List, Optional, Sequence, Union
 
 # ── types.py ─────────────────────────────────────────────────────────────────
 
@@ -12,6 +12,15 @@ class BranchPredictor:
         def _sub_dict(self) -> dict: ...
 
     class GShare:
+        table_bits: int
+        history_length: int
+        index: str
+        def __init__(
+            self,
+            table_bits: int = 12,
+            history_length: int = 12,
+            index: str = "Xor",
+        ) -> None: ...
         def _to_dict_value(self) -> str: ...
         def _sub_dict(self) -> dict: ...
 
@@ -37,7 +46,13 @@ class BranchPredictor:
     class Perceptron:
         history_length: int
         table_bits: int
-        def __init__(self, history_length: int = 32, table_bits: int = 10) -> None: ...
+        adaptive_threshold: bool
+        def __init__(
+            self,
+            history_length: int = 32,
+            table_bits: int = 10,
+            adaptive_threshold: bool = False,
+        ) -> None: ...
         def _to_dict_value(self) -> str: ...
         def _sub_dict(self) -> dict: ...
 
@@ -54,6 +69,10 @@ class BranchPredictor:
         def _to_dict_value(self) -> str: ...
         def _sub_dict(self) -> dict: ...
 
+    class Perfect:
+        def _to_dict_value(self) -> str: ...
+        def _sub_dict(self) -> dict: ...
+
 class ReplacementPolicy:
     class LRU:
         def _to_dict_value(self) -> str: ...
@@ -70,6 +89,9 @@ class ReplacementPolicy:
     class MRU:
         def _to_dict_value(self) -> str: ...
 
+    class DIP:
+        def _to_dict_value(self) -> str: ...
+
 class Prefetcher:
     class None_:
         def _to_dict_value(self) -> str: ...
@@ -101,6 +123,14 @@ class Prefetcher:
         def _degree(self) -> int: ...
         def _table_size(self) -> int: ...
 
+    class Markov:
+        degree: int
+        table_size: int
+        def __init__(self, degree: int = 1, table_size: int = 64) -> None: ...
+        def _to_dict_value(self) -> str: ...
+        def _degree(self) -> int: ...
+        def _table_size(self) -> int: ...
+
 class MemoryController:
     class Simple:
         def _to_dict_value(self) -> str: ...
@@ -111,12 +141,30 @@ class MemoryController:
         t_ras: int
         t_pre: int
         row_miss_latency: int
+        num_banks: int
+        num_channels: int
+        interleave_granularity: int
+        t_cwl: Optional[int]
+        t_wr: int
+        t_wtr: int
+        page_policy: str
+        t_refi: int
+        t_rfc: int
         def __init__(
             self,
             t_cas: int = 14,
             t_ras: int = 14,
             t_pre: int = 14,
             row_miss_latency: int = 120,
+            num_banks: int = 1,
+            num_channels: int = 1,
+            interleave_granularity: int = 2048,
+            t_cwl: Optional[int] = None,
+            t_wr: int = 0,
+            t_wtr: int = 0,
+            page_policy: str = "Open",
+            t_refi: int = 0,
+            t_rfc: int = 0,
         ) -> None: ...
         def _to_dict_value(self) -> str: ...
         def _sub_dict(self) -> dict: ...
@@ -142,6 +190,8 @@ class Cache:
     policy: Any
     latency: int
     prefetcher: Any
+    banks: int
+    set_stats: bool
     def __init__(
         self,
         size: str | int = "4KB",
@@ -150,6 +200,8 @@ class Cache:
         policy: Any = None,
         latency: int = 1,
         prefetcher: Any = None,
+        banks: int = 0,
+        set_stats: bool = False,
     ) -> None: ...
     def _to_cache_dict(self) -> Dict[str, Any]: ...
 
@@ -157,60 +209,148 @@ class Cache:
 
 class Config:
     width: int
+    fetch_width: int
+    fetch_queue_size: int
     branch_predictor: Any
     backend: Any
     btb_size: int
     ras_size: int
+    loop_predictor: bool
+    loop_table_size: int
+    indirect_predictor: bool
+    indirect_table_size: int
+    mul_latency: int
+    div_latency: int
+    fdiv_latency: int
+    fsqrt_latency: int
+    store_forward_latency: int
+    fence_latency: int
+    frontend_depth: int
+    subreg_tracking: bool
+    vlen: int
     l1i: Optional[Cache]
     l1d: Optional[Cache]
     l2: Optional[Cache]
     l3: Optional[Cache]
+    cache_random_seed: int
     ram_size: int
     memory_controller: Any
     tlb_size: int
+    misaligned_access: bool
+    big_endian: bool
+    fill_pattern: int
+    svade: bool
     trace: bool
     start_pc: int
     direct_mode: bool
     initial_sp: Optional[int]
+    simple_timing: bool
+    simple_mem_latency: int
+    simple_mispredict_penalty: int
+    max_same_pc: int
+    asm_dump: Optional[str]
+    commit_log: Optional[str]
+    mem_trace_size: int
+    trap_log_size: int
+    reset_mstatus: Optional[int]
+    reset_medeleg: int
+    reset_mideleg: int
+    reset_privilege: str
+    hartid: int
+    strict_csrs: bool
+    interrupt_priority: Optional[List[int]]
+    sbi: bool
+    semihosting: bool
+    ebreak_halt: bool
     ram_base: int
     uart_base: int
     disk_base: int
     clint_base: int
     syscon_base: int
+    rtc_base: int
     kernel_offset: int
+    initrd_offset: int
     bus_width: int
     bus_latency: int
     clint_divider: int
+    timebase_frequency: int
+    num_harts: int
     uart_to_stderr: bool
+    cdc_latency: Optional[Dict[str, int]]
+    framebuffer: Optional[Dict[str, int]]
     def __init__(
         self,
         width: int = 1,
+        fetch_width: int = 0,
+        fetch_queue_size: int = 0,
         branch_predictor: Any = None,
         backend: Any = None,
         btb_size: int = 256,
         ras_size: int = 8,
+        loop_predictor: bool = False,
+        loop_table_size: int = 64,
+        indirect_predictor: bool = False,
+        indirect_table_size: int = 256,
+        mul_latency: int = 3,
+        div_latency: int = 20,
+        fdiv_latency: int = 20,
+        fsqrt_latency: int = 20,
+        store_forward_latency: int = 1,
+        fence_latency: int = 1,
+        frontend_depth: int = 2,
+        subreg_tracking: bool = False,
+        vlen: int = 128,
         l1i: Optional[Cache] = None,
         l1d: Optional[Cache] = None,
         l2: Optional[Cache] = None,
         l3: Optional[Cache] = None,
+        cache_random_seed: int = 123_456_789,
         ram_size: str | int = "256MB",
         memory_controller: Any = None,
         tlb_size: int = 32,
+        misaligned_access: bool = True,
+        big_endian: bool = False,
+        fill_pattern: int = 0,
+        svade: bool = False,
         trace: bool = False,
         start_pc: int = 0x8000_0000,
         direct_mode: bool = True,
         initial_sp: Optional[int] = None,
+        simple_timing: bool = False,
+        simple_mem_latency: int = 10,
+        simple_mispredict_penalty: int = 3,
+        max_same_pc: int = 0,
+        asm_dump: Optional[str] = None,
+        commit_log: Optional[str] = None,
+        mem_trace_size: int = 0,
+        trap_log_size: int = 0,
+        reset_mstatus: Optional[int] = None,
+        reset_medeleg: int = 0,
+        reset_mideleg: int = 0,
+        reset_privilege: str = "Machine",
+        hartid: int = 0,
+        strict_csrs: bool = False,
+        interrupt_priority: Optional[List[int]] = None,
+        sbi: bool = False,
+        semihosting: bool = False,
+        ebreak_halt: bool = False,
         ram_base: int = 0x8000_0000,
         uart_base: int = 0x1000_0000,
         disk_base: int = 0x9000_0000,
         clint_base: int = 0x0200_0000,
         syscon_base: int = 0x0010_0000,
+        rtc_base: int = 0x0010_1000,
         kernel_offset: int = 0x0020_0000,
+        initrd_offset: int = 0x0400_0000,
         bus_width: int = 8,
         bus_latency: int = 4,
         clint_divider: int = 10,
+        timebase_frequency: int = 10_000_000,
+        num_harts: int = 1,
         uart_to_stderr: bool = False,
         uart_quiet: bool = False,
+        cdc_latency: Optional[Dict[str, int]] = None,
+        framebuffer: Optional[Dict[str, int]] = None,
     ) -> None: ...
     def to_dict(self) -> Dict[str, Any]: ...
 
@@ -219,6 +359,7 @@ class Config:
 class Stats(dict):
     def __init__(self, data: Dict[str, Any]) -> None: ...
     def query(self, pattern: str) -> Stats: ...
+    def cpi_stack(self) -> Dict[str, float]: ...
     def compare(self, other: Stats) -> None: ...
     @staticmethod
     def tabulate(rows: Dict[str, Stats], *, title: str = "") -> Table: ...
@@ -235,6 +376,7 @@ class _RegisterFile:
 
 class _MemView:
     def __getitem__(self, addr: int) -> int: ...
+    def __setitem__(self, addr: int, value: int) -> None: ...
 
 class Instruction:
     pc: int
@@ -243,6 +385,15 @@ class Instruction:
     cycles: int
     def __init__(self, pc: int, raw: int, asm: str, cycles: int) -> None: ...
 
+class StepResult:
+    cycles: int
+    instructions: int
+    trapped: bool
+    breakpoint: bool
+    exited: bool
+    exit_code: Optional[int]
+    pc: int
+
 class Cpu:
     def __init__(self, rust_cpu: Any) -> None: ...
     @property
@@ -250,6 +401,8 @@ class Cpu:
     @pc.setter
     def pc(self, value: int) -> None: ...
     @property
+    def halted_at(self) -> Optional[int]: ...
+    @property
     def stats(self) -> Stats: ...
     @property
     def regs(self) -> _RegisterFile: ...
@@ -258,16 +411,46 @@ class Cpu:
     @property
     def mem64(self) -> _MemView: ...
     def step(self) -> Optional[Instruction]: ...
+    def step_cycles(self, n: int) -> StepResult: ...
     def run(
         self,
         limit: Optional[int] = None,
         progress: int = 0,
         print_stats: bool = False,
+        stats_sections: Optional[list] = None,
+        sample_interval: Optional[int] = None,
+        callback: Optional[Callable[[Stats], Optional[bool]]] = None,
+        warmup: Optional[int] = None,
     ) -> Optional[int]: ...
     def tick(self) -> None: ...
+    def reset_stats(self) -> None: ...
     def csr(self, name: str | int) -> int: ...
+    def load_bytes(self, paddr: int, data: Any) -> None: ...
+    def cache_set_stats(self, level: str) -> list[tuple[int, int]]: ...
     def get_pc_trace(self) -> list: ...
-    def load_kernel(self, kernel_path: str, dtb_path: Optional[str] = None) -> None: ...
+    def save_checkpoint(self, path: str) -> None: ...
+    def load_checkpoint(self, path: str) -> None: ...
+    def resolve_symbol(self, pc: int) -> Optional[tuple[str, int]]: ...
+    def backtrace(self) -> list[int]: ...
+    def enable_mem_trace(
+        self, capacity: int, start: Optional[int] = None, end: Optional[int] = None
+    ) -> None: ...
+    def inject_interrupt_at(self, inst_count: int, interrupt: str) -> None: ...
+    def disable_mem_trace(self) -> None: ...
+    def get_mem_trace(self) -> list[tuple[int, int, int, bool, int]]: ...
+    def enable_trap_log(self, capacity: int) -> None: ...
+    def disable_trap_log(self) -> None: ...
+    def get_trap_log(self) -> list[tuple[int, int, int, str]]: ...
+    def dump_framebuffer(self, path: str) -> None: ...
+    def set_functional(self, enabled: bool) -> None: ...
+    def load_kernel(
+        self,
+        kernel_path: str,
+        dtb_path: Optional[str] = None,
+        initrd_path: Optional[str] = None,
+    ) -> None: ...
+    def dtb(self) -> Optional[bytes]: ...
+    def memory_map(self) -> list[tuple[str, int, int]]: ...
     @property
     def raw(self) -> Any: ...
 
@@ -292,8 +475,11 @@ class Simulator:
     def kernel(self, path: str) -> Simulator: ...
     def disk(self, path: str) -> Simulator: ...
     def dtb(self, path: str) -> Simulator: ...
+    def initrd(self, path: str) -> Simulator: ...
     def kernel_mode(self) -> Simulator: ...
     def binary(self, path: str) -> Simulator: ...
+    def print_memmap(self) -> Simulator: ...
+    def dump_dtb(self, path: str) -> Simulator: ...
     def run(self, limit: Optional[int] = None, progress: int = 0) -> int: ...
 
 # ── experiment.py ────────────────────────────────────────────────────────────
@@ -402,6 +588,7 @@ class _CsrLookup:
     MCYCLE: int
     MINSTRET: int
     STIMECMP: int
+    MENVCFG: int
     def name(self, addr: int) -> str: ...
     def __call__(self, name: str | int) -> int: ...
 
@@ -409,3 +596,5 @@ reg: _RegLookup
 csr: _CsrLookup
 
 def version() -> str: ...
+def generate_dtb(config_dict: Dict[str, Any]) -> bytes: ...
+def disasm(inst: int) -> str: ...
@@ -50,6 +50,11 @@ class Stats(dict):
 
         return Stats(matches)
 
+    def cpi_stack(self) -> Dict[str, float]:
+        """CPI split into base, branch, icache, dcache, tlb and memory; sums to the CPI."""
+        names = ("base", "branch", "icache", "dcache", "tlb", "memory")
+        return {name: self.get(f"cpi_{name}", 0.0) for name in names}
+
     @staticmethod
     def tabulate(rows: Dict[str, Stats], *, title: str = "") -> Table:
         """Build a comparison table from labeled :class:`Stats` objects.
@@ -165,27 +170,59 @@ def _geometric_mean(values: Sequence[float]) -> float:
     return math.exp(sum(logs) / len(logs))
 
 
-_RATE_METRICS = {"ipc", "branch_accuracy_pct"}
+_RATE_METRICS = {
+    "ipc",
+    "ipc_user",
+    "ipc_kernel",
+    "ipc_machine",
+    "branch_accuracy_pct",
+}
 _COUNT_METRICS = {
     "cycles",
     "instructions_retired",
+    "cycles_wfi",
+    "insts_user",
+    "insts_kernel",
+    "insts_machine",
     "stalls_mem",
     "stalls_control",
     "stalls_data",
+    "stalls_struct",
+    "subreg_dependency_stalls",
+    "stalls_icache",
+    "stalls_dcache",
+    "stalls_itlb",
+    "stalls_dtlb",
     "icache_hits",
     "icache_misses",
+    "icache_invalidations",
     "dcache_hits",
     "dcache_misses",
+    "dcache_bank_conflicts",
+    "warmup_cycles",
+    "warmup_instructions",
     "l2_hits",
     "l2_misses",
     "l3_hits",
     "l3_misses",
     "branch_predictions",
     "branch_mispredictions",
+    "cond_branch_predictions",
+    "cond_branch_mispredictions",
+    "direct_jump_predictions",
+    "direct_jump_mispredictions",
+    "indirect_jump_predictions",
+    "indirect_jump_mispredictions",
+    "return_predictions",
+    "return_mispredictions",
+    "bp_trained",
+    "bp_train_skipped",
     "traps_taken",
     "inst_load",
     "inst_store",
     "inst_branch",
+    "inst_jump",
+    "inst_atomic",
     "inst_alu",
     "inst_system",
     "inst_fp_load",
@@ -2,9 +2,9 @@
 Namespace types for simulator configuration.
 
 Provides structured, Pythonic alternatives to raw string enums:
-- BranchPredictor: Static, GShare, TAGE, Perceptron, Tournament
-- ReplacementPolicy: LRU, PLRU, FIFO, Random, MRU
-- Prefetcher: None_, NextLine, Stride, Stream, Tagged
+- BranchPredictor: Static, GShare, TAGE, Perceptron, Tournament, Perfect
+- ReplacementPolicy: LRU, PLRU, FIFO, Random, MRU, DIP
+- Prefetcher: None_, NextLine, Stride, Stream, Tagged, Markov
 - MemoryController: Simple, DRAM
 - Backend: InOrder, OutOfOrder
 - Cache: cache level configuration with size parsing
@@ -69,14 +69,35 @@ class BranchPredictor:
             return "BranchPredictor.Static()"
 
     class GShare:
+        def __init__(
+            self,
+            table_bits: int = 12,
+            history_length: int = 12,
+            index: str = "Xor",
+        ):
+            if index not in ("Pc", "History", "Xor"):
+                raise ValueError(
+                    f"GShare index must be 'Pc', 'History', or 'Xor', got {index!r}"
+                )
+            self.table_bits = table_bits
+            self.history_length = history_length
+            self.index = index
+
         def _to_dict_value(self) -> str:
             return "GShare"
 
         def _sub_dict(self) -> dict:
-            return {}
+            return {
+                "table_bits": self.table_bits,
+                "history_length": self.history_length,
+                "index": self.index,
+            }
 
         def __repr__(self) -> str:
-            return "BranchPredictor.GShare()"
+            return (
+                f"BranchPredictor.GShare(table_bits={self.table_bits}, "
+                f"history_length={self.history_length}, index={self.index!r})"
+            )
 
     class TAGE:
         def __init__(
@@ -121,9 +142,15 @@ class BranchPredictor:
             )
 
     class Perceptron:
-        def __init__(self, history_length: int = 32, table_bits: int = 10):
+        def __init__(
+            self,
+            history_length: int = 32,
+            table_bits: int = 10,
+            adaptive_threshold: bool = False,
+        ):
             self.history_length = history_length
             self.table_bits = table_bits
+            self.adaptive_threshold = adaptive_threshold
 
         def _to_dict_value(self) -> str:
             return "Perceptron"
@@ -132,12 +159,14 @@ class BranchPredictor:
             return {
                 "history_length": self.history_length,
                 "table_bits": self.table_bits,
+                "adaptive_threshold": self.adaptive_threshold,
             }
 
         def __repr__(self) -> str:
             return (
                 f"BranchPredictor.Perceptron(history_length={self.history_length}, "
-                f"table_bits={self.table_bits})"
+                f"table_bits={self.table_bits}, "
+                f"adaptive_threshold={self.adaptive_threshold})"
             )
 
     class Tournament:
@@ -168,6 +197,18 @@ class BranchPredictor:
                 f"local_pred_bits={self.local_pred_bits})"
             )
 
+    class Perfect:
+        """Oracle predictor that never mispredicts, for IPC upper bounds."""
+
+        def _to_dict_value(self) -> str:
+            return "Perfect"
+
+        def _sub_dict(self) -> dict:
+            return {}
+
+        def __repr__(self) -> str:
+            return "BranchPredictor.Perfect()"
+
 
 # ── Replacement Policy ───────────────────────────────────────────────────────
 
@@ -210,6 +251,13 @@ class ReplacementPolicy:
         def __repr__(self) -> str:
             return "ReplacementPolicy.MRU()"
 
+    class DIP:
+        def _to_dict_value(self) -> str:
+            return "DIP"
+
+        def __repr__(self) -> str:
+            return "ReplacementPolicy.DIP()"
+
 
 # ── Prefetcher ───────────────────────────────────────────────────────────────
 
@@ -291,6 +339,25 @@ class Prefetcher:
         def __repr__(self) -> str:
             return "Prefetcher.Tagged()"
 
+    class Markov:
+        def __init__(self, degree: int = 1, table_size: int = 64):
+            self.degree = degree
+            self.table_size = table_size
+
+        def _to_dict_value(self) -> str:
+            return "Markov"
+
+        def _degree(self) -> int:
+            return self.degree
+
+        def _table_size(self) -> int:
+            return self.table_size
+
+        def __repr__(self) -> str:
+            return (
+                f"Prefetcher.Markov(degree={self.degree}, table_size={self.table_size})"
+            )
+
 
 # ── Memory Controller ────────────────────────────────────────────────────────
 
@@ -315,27 +382,61 @@ class MemoryController:
             t_ras: int = 14,
             t_pre: int = 14,
             row_miss_latency: int = 120,
+            num_banks: int = 1,
+            num_channels: int = 1,
+            interleave_granularity: int = 2048,
+            t_cwl: Optional[int] = None,
+            t_wr: int = 0,
+            t_wtr: int = 0,
+            page_policy: str = "Open",
+            t_refi: int = 0,
+            t_rfc: int = 0,
         ):
             self.t_cas = t_cas
             self.t_ras = t_ras
             self.t_pre = t_pre
             self.row_miss_latency = row_miss_latency
+            self.num_banks = num_banks
+            self.num_channels = num_channels
+            self.interleave_granularity = interleave_granularity
+            self.t_cwl = t_cwl
+            self.t_wr = t_wr
+            self.t_wtr = t_wtr
+            self.page_policy = page_policy
+            self.t_refi = t_refi
+            self.t_rfc = t_rfc
 
         def _to_dict_value(self) -> str:
             return "Dram"
 
         def _sub_dict(self) -> dict:
-            return {
+            d = {
                 "t_cas": self.t_cas,
                 "t_ras": self.t_ras,
                 "t_pre": self.t_pre,
                 "row_miss_latency": self.row_miss_latency,
+                "num_banks": self.num_banks,
+                "num_channels": self.num_channels,
+                "interleave_granularity": self.interleave_granularity,
+                "t_wr": self.t_wr,
+                "t_wtr": self.t_wtr,
+                "page_policy": self.page_policy,
+                "t_refi": self.t_refi,
+                "t_rfc": self.t_rfc,
             }
+            if self.t_cwl is not None:
+                d["t_cwl"] = self.t_cwl
+            return d
 
         def __repr__(self) -> str:
             return (
                 f"MemoryController.DRAM(t_cas={self.t_cas}, t_ras={self.t_ras}, "
-                f"t_pre={self.t_pre}, row_miss_latency={self.row_miss_latency})"
+                f"t_pre={self.t_pre}, row_miss_latency={self.row_miss_latency}, "
+                f"num_banks={self.num_banks}, num_channels={self.num_channels}, "
+                f"interleave_granularity={self.interleave_granularity}, "
+                f"t_cwl={self.t_cwl}, t_wr={self.t_wr}, t_wtr={self.t_wtr}, "
+                f"page_policy={self.page_policy!r}, t_refi={self.t_refi}, "
+                f"t_rfc={self.t_rfc})"
             )
 
 
@@ -393,13 +494,29 @@ class Cache:
         policy=None,
         latency: int = 1,
         prefetcher=None,
+        banks: int = 0,
+        set_stats: bool = False,
     ):
         self.size_bytes = _parse_size(size)
         self.line_bytes = _parse_size(line)
         self.ways = ways
+        set_bytes = self.line_bytes * ways
+        if set_bytes <= 0 or self.size_bytes <= 0 or self.size_bytes % set_bytes:
+            raise ValueError(
+                f"invalid cache geometry: size ({self.size_bytes}) must be a "
+                f"non-zero multiple of line ({self.line_bytes}) * ways ({ways})"
+            )
+        sets = self.size_bytes // set_bytes
+        if self.line_bytes & (self.line_bytes - 1) or sets & (sets - 1):
+            raise ValueError(
+                f"invalid cache geometry: line ({self.line_bytes}) and the number "
+                f"of sets ({sets}) must be powers of two"
+            )
         self.policy = policy if policy is not None else ReplacementPolicy.LRU()
         self.latency = latency
         self.prefetcher = prefetcher if prefetcher is not None else Prefetcher.None_()
+        self.banks = banks
+        self.set_stats = set_stats
 
     def _to_cache_dict(self) -> Dict[str, Any]:
         return {
@@ -412,13 +529,16 @@ class Cache:
             "prefetcher": self.prefetcher._to_dict_value(),
             "prefetch_table_size": self.prefetcher._table_size(),
             "prefetch_degree": self.prefetcher._degree(),
+            "banks": self.banks,
+            "set_stats": self.set_stats,
         }
 
     def __repr__(self) -> str:
         return (
             f"Cache(size={self.size_bytes}, line={self.line_bytes}, "
             f"ways={self.ways}, policy={self.policy!r}, "
-            f"latency={self.latency}, prefetcher={self.prefetcher!r})"
+            f"latency={self.latency}, prefetcher={self.prefetcher!r}, "
+            f"banks={self.banks})"
         )
 
 
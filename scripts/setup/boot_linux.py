@@ -45,7 +45,8 @@ BR2_TARGET_ROOTFS_EXT2_SIZE="60M"
 BR2_PACKAGE_HOST_LINUX_HEADERS_CUSTOM_6_6=y
 """
 
-# Device tree matching sim: RAM 128MB @ 0x80000000, CLINT @ 0x02000000, UART @ 0x10000000, PLIC @ 0x0c000000.
+# Device tree matching sim: RAM 128MB @ 0x80000000, CLINT @ 0x02000000, UART @ 0x10000000, PLIC @ 0x0c000000,
+# Goldfish RTC @ 0x00101000.
 # OpenSBI requires CLINT (timer) and PLIC with interrupts-extended to init irqchip.
 SYSTEM_DTS = """/dts-v1/;
 
@@ -102,6 +103,8 @@ SYSTEM_DTS = """/dts-v1/;
             compatible = "ns16550a";
             reg = <0x0 0x10000000 0x0 0x100>;
             clock-frequency = <10000000>;
+            interrupt-parent = <&plic>;
+            interrupts = <10>;
             status = "okay";
         };
 
@@ -112,6 +115,13 @@ SYSTEM_DTS = """/dts-v1/;
             interrupts = <1>;
         };
 
+        rtc@101000 {
+            compatible = "google,goldfish-rtc";
+            reg = <0x0 0x00101000 0x0 0x1000>;
+            interrupt-parent = <&plic>;
+            interrupts = <11>;
+        };
+
         plic: interrupt-controller@c000000 {
             compatible = "riscv,plic0";
             reg = <0x0 0x0c000000 0x0 0x4000000>;